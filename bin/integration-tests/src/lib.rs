@@ -0,0 +1,25 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Integration tests that are running the whole bridge flow on top of the test runtimes.
+//!
+//! Every test operates on the real runtimes of both bridged chains. Storage proofs are
+//! generated from the actual storage of the source chain runtime, so tests are verifying
+//! that both sides of the bridge agree on messages and lanes encoding, storage layout and
+//! pallet names.
+
+#[cfg(test)]
+mod millau_rialto_xcm;
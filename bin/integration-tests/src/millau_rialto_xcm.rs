@@ -0,0 +1,276 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! XCM round-trip between Millau and Rialto.
+//!
+//! The XCM message is sent at Millau, delivered to Rialto and dispatched there. Then the
+//! delivery is confirmed at Millau and the delivery relayer claims its reward. Every step is
+//! performed by calling the same runtime calls that are used by the real relayer.
+
+use bp_header_chain::InitializationData;
+use bp_messages::{
+	storage_keys, DeliveredMessages, LaneId, MessageNonce, ReceivalResult, ReceivedMessages,
+	UnrewardedRelayersState,
+};
+use bp_relayers::{PayRewardFromAccount, RewardsAccountOwner, RewardsAccountParams};
+use bp_runtime::{
+	messages::MessageDispatchResult, BasicOperatingMode, RawStorageProof, RIALTO_CHAIN_ID,
+};
+use bridge_runtime_common::messages::{
+	source::FromBridgedChainMessagesDeliveryProof, target::FromBridgedChainMessagesProof,
+	MessageBridge,
+};
+use frame_support::{assert_ok, traits::GenesisBuild, weights::Weight};
+use millau_runtime::rialto_messages::WithRialtoMessageBridge;
+use rialto_runtime::millau_messages::WithMillauMessageBridge;
+use sp_core::Hasher;
+use sp_runtime::traits::Header as HeaderT;
+use sp_state_machine::TestExternalities;
+use xcm::{latest::prelude::*, VersionedMultiLocation, VersionedXcm};
+
+/// Lane that is used to send XCM messages from Millau to Rialto.
+const XCM_LANE: LaneId = bp_millau::WITH_RIALTO_XCM_LANE;
+/// Millau account that is sending XCM message to Rialto.
+const SENDER: [u8; 32] = [1; 32];
+/// Rialto account of the relayer that is delivering messages to Rialto.
+const DELIVERY_RELAYER_AT_RIALTO: [u8; 32] = [2; 32];
+/// Millau account of the relayer that is delivering messages to Rialto.
+const DELIVERY_RELAYER_AT_MILLAU: [u8; 32] = [3; 32];
+/// Millau account of the relayer that is delivering confirmations to Millau.
+const CONFIRMATION_RELAYER: [u8; 32] = [4; 32];
+/// Initial balance of Millau accounts that are paying for something.
+const INITIAL_BALANCE: millau_runtime::Balance = 1_000_000_000_000;
+/// Reward for delivering single message, registered by the Millau runtime.
+const DELIVERY_REWARD: millau_runtime::Balance = 100_000;
+
+/// Parameters of the account that is paying rewards for delivering Millau -> Rialto messages.
+fn rewards_account_params() -> RewardsAccountParams {
+	RewardsAccountParams::new(XCM_LANE, RIALTO_CHAIN_ID, RewardsAccountOwner::BridgedChain)
+}
+
+fn millau_externalities() -> TestExternalities<bp_millau::Hasher> {
+	type Runtime = millau_runtime::Runtime;
+
+	let rewards_account = PayRewardFromAccount::<
+		pallet_balances::Pallet<Runtime>,
+		millau_runtime::AccountId,
+	>::rewards_account_at(
+		rewards_account_params(),
+		millau_runtime::RelayersAccountDerivationVersion::get(),
+	);
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	GenesisBuild::<Runtime>::assimilate_storage(
+		&pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(SENDER.into(), INITIAL_BALANCE), (rewards_account, INITIAL_BALANCE)],
+		},
+		&mut storage,
+	)
+	.unwrap();
+	// XCM version of bridged destinations is unknown at genesis, so let's use the safe one
+	GenesisBuild::<Runtime>::assimilate_storage(
+		&pallet_xcm::GenesisConfig { safe_xcm_version: Some(xcm::latest::VERSION) },
+		&mut storage,
+	)
+	.unwrap();
+	TestExternalities::new(storage)
+}
+
+fn rialto_externalities() -> TestExternalities<bp_rialto::Hasher> {
+	let storage = frame_system::GenesisConfig::default()
+		.build_storage::<rialto_runtime::Runtime>()
+		.unwrap();
+	TestExternalities::new(storage)
+}
+
+/// Commit all changes and prove given storage keys. Returns state root and the storage proof.
+fn prove_storage<H>(
+	externalities: &mut TestExternalities<H>,
+	keys: &[Vec<u8>],
+) -> (H::Out, RawStorageProof)
+where
+	H: Hasher + 'static,
+	H::Out: Ord + codec::Codec + 'static,
+{
+	externalities.commit_all().unwrap();
+	let backend = externalities.as_backend();
+	let state_root = *backend.root();
+	let storage_proof = sp_state_machine::prove_read(backend, keys).unwrap();
+	(state_root, storage_proof.into_iter_nodes().collect())
+}
+
+/// Returns bridged chain header, finalized by the bridge GRANDPA pallet.
+fn finalized_header<H: HeaderT>(state_root: H::Hash) -> H {
+	H::new(1u32.into(), Default::default(), state_root, Default::default(), Default::default())
+}
+
+/// Initialize bridge GRANDPA pallet with given finalized header.
+fn initialize_bridge<R>(header: pallet_bridge_grandpa::BridgedHeader<R, ()>)
+where
+	R: pallet_bridge_grandpa::Config,
+{
+	assert_ok!(pallet_bridge_grandpa::Pallet::<R>::initialize(
+		frame_system::RawOrigin::Root.into(),
+		InitializationData {
+			header: Box::new(header),
+			authority_list: vec![],
+			set_id: 0,
+			operating_mode: BasicOperatingMode::Normal,
+		},
+	));
+}
+
+fn has_event<R: frame_system::Config>(event: impl Into<R::RuntimeEvent>) -> bool {
+	let event = event.into();
+	frame_system::Pallet::<R>::events().iter().any(|record| record.event == event)
+}
+
+#[test]
+fn xcm_message_is_sent_delivered_and_confirmed() {
+	let mut millau = millau_externalities();
+	let mut rialto = rialto_externalities();
+	let nonce: MessageNonce = 1;
+
+	// send XCM message from Millau to Rialto
+	millau.execute_with(|| {
+		frame_system::Pallet::<millau_runtime::Runtime>::set_block_number(1);
+
+		let destination: MultiLocation =
+			(Parent, X1(GlobalConsensus(millau_runtime::xcm_config::RialtoNetwork::get()))).into();
+		let xcm: Xcm<()> = vec![Instruction::Trap(42)].into();
+		assert_ok!(millau_runtime::XcmPallet::send(
+			millau_runtime::RuntimeOrigin::signed(SENDER.into()),
+			Box::new(VersionedMultiLocation::from(destination)),
+			Box::new(VersionedXcm::from(xcm)),
+		));
+
+		let event = pallet_bridge_messages::Event::<millau_runtime::Runtime>::MessageAccepted {
+			lane_id: XCM_LANE,
+			nonce,
+		};
+		assert!(has_event::<millau_runtime::Runtime>(event));
+	});
+
+	// deliver message to Rialto, using storage proof of the Millau state
+	let message_key = storage_keys::message_key(
+		WithMillauMessageBridge::BRIDGED_MESSAGES_PALLET_NAME,
+		&XCM_LANE,
+		nonce,
+	);
+	let (millau_state_root, storage_proof) = prove_storage(&mut millau, &[message_key.0]);
+	let millau_header: bp_millau::Header = finalized_header(millau_state_root);
+	rialto.execute_with(|| {
+		frame_system::Pallet::<rialto_runtime::Runtime>::set_block_number(1);
+		initialize_bridge::<rialto_runtime::Runtime>(millau_header.clone());
+
+		assert_ok!(rialto_runtime::BridgeMillauMessages::receive_messages_proof(
+			rialto_runtime::RuntimeOrigin::signed(DELIVERY_RELAYER_AT_RIALTO.into()),
+			DELIVERY_RELAYER_AT_MILLAU.into(),
+			FromBridgedChainMessagesProof {
+				bridged_header_hash: millau_header.hash(),
+				storage_proof,
+				lane: XCM_LANE,
+				nonces_start: nonce,
+				nonces_end: nonce,
+			},
+			1,
+			rialto_runtime::millau_messages::BASE_XCM_WEIGHT_TWICE,
+		));
+
+		// message has been dispatched by the XCM executor
+		let receive_results = vec![(
+			nonce,
+			ReceivalResult::Dispatched(MessageDispatchResult {
+				unspent_weight: Weight::zero(),
+				dispatch_level_result: (),
+			}),
+		)];
+		let event =
+			pallet_bridge_messages::Event::<rialto_runtime::Runtime>::MessagesReceived(vec![
+				ReceivedMessages {
+					lane: XCM_LANE,
+					receive_results,
+					skipped_for_not_enough_weight: vec![],
+				},
+			]);
+		assert!(has_event::<rialto_runtime::Runtime>(event));
+		assert_eq!(
+			rialto_runtime::BridgeMillauMessages::inbound_lane_data(XCM_LANE)
+				.last_delivered_nonce(),
+			nonce,
+		);
+	});
+
+	// confirm delivery at Millau, using storage proof of the Rialto state
+	let inbound_lane_data_key = storage_keys::inbound_lane_data_key(
+		WithRialtoMessageBridge::BRIDGED_MESSAGES_PALLET_NAME,
+		&XCM_LANE,
+	);
+	let (rialto_state_root, storage_proof) = prove_storage(&mut rialto, &[inbound_lane_data_key.0]);
+	let rialto_header: bp_rialto::Header = finalized_header(rialto_state_root);
+	millau.execute_with(|| {
+		initialize_bridge::<millau_runtime::Runtime>(rialto_header.clone());
+
+		assert_ok!(millau_runtime::BridgeRialtoMessages::receive_messages_delivery_proof(
+			millau_runtime::RuntimeOrigin::signed(CONFIRMATION_RELAYER.into()),
+			FromBridgedChainMessagesDeliveryProof {
+				bridged_header_hash: rialto_header.hash(),
+				storage_proof,
+				lane: XCM_LANE,
+			},
+			UnrewardedRelayersState {
+				unrewarded_relayer_entries: 1,
+				messages_in_oldest_entry: 1,
+				total_messages: 1,
+				last_delivered_nonce: nonce,
+			},
+		));
+
+		let event = pallet_bridge_messages::Event::<millau_runtime::Runtime>::MessagesDelivered {
+			lane_id: XCM_LANE,
+			messages: DeliveredMessages::new(nonce),
+		};
+		assert!(has_event::<millau_runtime::Runtime>(event));
+		assert_eq!(
+			millau_runtime::BridgeRialtoMessages::outbound_lane_data(XCM_LANE)
+				.latest_received_nonce,
+			nonce,
+		);
+
+		// delivery relayer is rewarded and the confirmation relayer has nothing to claim
+		let delivery_relayer = millau_runtime::AccountId::from(DELIVERY_RELAYER_AT_MILLAU);
+		let confirmation_relayer = millau_runtime::AccountId::from(CONFIRMATION_RELAYER);
+		assert_eq!(
+			pallet_bridge_relayers::RelayerRewards::<millau_runtime::Runtime>::get(
+				&delivery_relayer,
+				rewards_account_params(),
+			),
+			Some(DELIVERY_REWARD),
+		);
+		assert_eq!(
+			pallet_bridge_relayers::RelayerRewards::<millau_runtime::Runtime>::get(
+				&confirmation_relayer,
+				rewards_account_params(),
+			),
+			None,
+		);
+
+		assert_ok!(millau_runtime::BridgeRelayers::claim_rewards(
+			millau_runtime::RuntimeOrigin::signed(delivery_relayer.clone()),
+			rewards_account_params(),
+		));
+		assert_eq!(millau_runtime::Balances::free_balance(&delivery_relayer), DELIVERY_REWARD);
+	});
+}
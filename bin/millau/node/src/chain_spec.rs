@@ -177,6 +177,12 @@ fn endowed_accounts() -> Vec<AccountId> {
 		get_account_id_from_seed::<sr25519::Public>("RialtoParachain.HeadersAndMessagesRelay2"),
 		get_account_id_from_seed::<sr25519::Public>("RialtoParachain.RialtoHeadersRelay1"),
 		get_account_id_from_seed::<sr25519::Public>("RialtoParachain.RialtoHeadersRelay2"),
+		get_account_id_from_seed::<sr25519::Public>(
+			"RialtoParachain.OutboundMessagesRelay.Lane00000000",
+		),
+		get_account_id_from_seed::<sr25519::Public>(
+			"RialtoParachain.InboundMessagesRelay.Lane00000000",
+		),
 		get_account_id_from_seed::<sr25519::Public>("RialtoParachain.MessagesSender"),
 	]
 	.into_iter()
@@ -34,10 +34,14 @@ pub struct ExecutorDispatch;
 impl sc_executor::NativeExecutionDispatch for ExecutorDispatch {
 	/// Only enable the benchmarking host functions when we actually want to benchmark.
 	#[cfg(feature = "runtime-benchmarks")]
-	type ExtendHostFunctions = frame_benchmarking::benchmarking::HostFunctions;
-	/// Otherwise we only use the default Substrate host functions.
+	type ExtendHostFunctions = (
+		frame_benchmarking::benchmarking::HostFunctions,
+		bp_header_chain::host_functions::HostFunctions,
+	);
+	/// Otherwise we only use the default Substrate host functions and host functions that are
+	/// used to verify GRANDPA justifications of bridged chains.
 	#[cfg(not(feature = "runtime-benchmarks"))]
-	type ExtendHostFunctions = ();
+	type ExtendHostFunctions = bp_header_chain::host_functions::HostFunctions;
 
 	fn dispatch(method: &str, data: &[u8]) -> Option<Vec<u8>> {
 		millau_runtime::api::dispatch(method, data)
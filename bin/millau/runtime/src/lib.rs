@@ -32,10 +32,10 @@ pub mod rialto_messages;
 pub mod rialto_parachain_messages;
 pub mod xcm_config;
 
-use bp_parachains::SingleParaStoredHeaderDataBuilder;
+use bp_parachains::{AnyParaStoredHeaderDataBuilder, SingleParaStoredHeaderDataBuilder};
 #[cfg(feature = "runtime-benchmarks")]
 use bp_relayers::{RewardsAccountOwner, RewardsAccountParams};
-use bp_runtime::HeaderId;
+use bp_runtime::{account_derivation::AccountDerivationVersion, HeaderId};
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
@@ -64,7 +64,8 @@ pub use frame_support::{
 	dispatch::DispatchClass,
 	parameter_types,
 	traits::{
-		ConstU32, ConstU64, ConstU8, Currency, ExistenceRequirement, Imbalance, KeyOwnerProofSystem,
+		ConstBool, ConstU32, ConstU64, ConstU8, Currency, ExistenceRequirement, Imbalance,
+		KeyOwnerProofSystem,
 	},
 	weights::{
 		constants::WEIGHT_REF_TIME_PER_SECOND, ConstantMultiplier, IdentityFee, RuntimeDbWeight,
@@ -78,6 +79,7 @@ pub use pallet_balances::Call as BalancesCall;
 pub use pallet_bridge_grandpa::Call as BridgeGrandpaCall;
 pub use pallet_bridge_messages::Call as MessagesCall;
 pub use pallet_bridge_parachains::Call as BridgeParachainsCall;
+pub use pallet_bridge_relayers::Call as BridgeRelayersCall;
 pub use pallet_sudo::Call as SudoCall;
 pub use pallet_timestamp::Call as TimestampCall;
 pub use pallet_xcm::Call as XcmCall;
@@ -383,11 +385,18 @@ impl pallet_session::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	/// Version of the scheme that is used to derive rewards accounts.
+	pub const RelayersAccountDerivationVersion: AccountDerivationVersion =
+		AccountDerivationVersion::V0;
+}
+
 impl pallet_bridge_relayers::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Reward = Balance;
 	type PaymentProcedure =
 		bp_relayers::PayRewardFromAccount<pallet_balances::Pallet<Runtime>, AccountId>;
+	type AccountDerivationVersion = RelayersAccountDerivationVersion;
 	type WeightInfo = ();
 }
 
@@ -400,6 +409,7 @@ impl pallet_bridge_grandpa::Config for Runtime {
 	// call per block.
 	type MaxRequests = ConstU32<50>;
 	type HeadersToKeep = ConstU32<{ bp_rialto::DAYS }>;
+	type UseHostSignatureVerification = ConstBool<false>;
 	type WeightInfo = pallet_bridge_grandpa::weights::BridgeWeight<Runtime>;
 }
 
@@ -408,10 +418,14 @@ impl pallet_bridge_grandpa::Config<WestendGrandpaInstance> for Runtime {
 	type BridgedChain = bp_westend::Westend;
 	type MaxRequests = ConstU32<50>;
 	type HeadersToKeep = ConstU32<{ bp_westend::DAYS }>;
+	type UseHostSignatureVerification = ConstBool<false>;
 	type WeightInfo = pallet_bridge_grandpa::weights::BridgeWeight<Runtime>;
 }
 
-impl pallet_shift_session_manager::Config for Runtime {}
+impl pallet_shift_session_manager::Config for Runtime {
+	type RotationStrategy = pallet_shift_session_manager::DefaultRotationStrategy;
+	type AllowlistOrigin = frame_system::EnsureRoot<AccountId>;
+}
 
 parameter_types! {
 	pub const MaxMessagesToPruneAtOnce: bp_messages::MessageNonce = 8;
@@ -420,6 +434,9 @@ parameter_types! {
 	pub const MaxUnconfirmedMessagesAtInboundLane: bp_messages::MessageNonce =
 		bp_rialto::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
 	pub const RootAccountForPayments: Option<AccountId> = None;
+	pub const StaleLaneThreshold: BlockNumber = bp_millau::time_units::HOURS;
+	pub MaxDeferredDispatchWeightPerBlock: Weight =
+		bp_millau::BlockWeights::get().max_block.saturating_div(4);
 	pub const RialtoChainId: bp_runtime::ChainId = bp_runtime::RIALTO_CHAIN_ID;
 	pub const RialtoParachainChainId: bp_runtime::ChainId = bp_runtime::RIALTO_PARACHAIN_CHAIN_ID;
 	pub RialtoActiveOutboundLanes: &'static [bp_messages::LaneId] = &[rialto_messages::XCM_LANE];
@@ -433,8 +450,13 @@ impl pallet_bridge_messages::Config<WithRialtoMessagesInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_bridge_messages::weights::BridgeWeight<Runtime>;
 	type ActiveOutboundLanes = RialtoActiveOutboundLanes;
+	type DynamicLanes = ();
+	type StaleLaneThreshold = StaleLaneThreshold;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxDispatchWeightPerBlock = ();
+	type MaxDeferredMessagesPerLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxDeferredDispatchWeightPerBlock = MaxDeferredDispatchWeightPerBlock;
 
 	type MaximalOutboundPayloadSize = crate::rialto_messages::ToRialtoMaximalOutboundPayloadSize;
 	type OutboundPayload = crate::rialto_messages::ToRialtoMessagePayload;
@@ -450,6 +472,7 @@ impl pallet_bridge_messages::Config<WithRialtoMessagesInstance> for Runtime {
 		WithRialtoMessagesInstance,
 		frame_support::traits::ConstU64<100_000>,
 	>;
+	type OnMessagesDelivered = ();
 
 	type SourceHeaderChain = crate::rialto_messages::RialtoAsSourceHeaderChain;
 	type MessageDispatch = crate::rialto_messages::FromRialtoMessageDispatch;
@@ -463,8 +486,13 @@ impl pallet_bridge_messages::Config<WithRialtoParachainMessagesInstance> for Run
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_bridge_messages::weights::BridgeWeight<Runtime>;
 	type ActiveOutboundLanes = RialtoParachainActiveOutboundLanes;
+	type DynamicLanes = ();
+	type StaleLaneThreshold = StaleLaneThreshold;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxDispatchWeightPerBlock = ();
+	type MaxDeferredMessagesPerLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxDeferredDispatchWeightPerBlock = MaxDeferredDispatchWeightPerBlock;
 
 	type MaximalOutboundPayloadSize =
 		crate::rialto_parachain_messages::ToRialtoParachainMaximalOutboundPayloadSize;
@@ -481,6 +509,7 @@ impl pallet_bridge_messages::Config<WithRialtoParachainMessagesInstance> for Run
 		WithRialtoParachainMessagesInstance,
 		frame_support::traits::ConstU64<100_000>,
 	>;
+	type OnMessagesDelivered = ();
 
 	type SourceHeaderChain = crate::rialto_parachain_messages::RialtoParachainAsSourceHeaderChain;
 	type MessageDispatch = crate::rialto_parachain_messages::FromRialtoParachainMessageDispatch;
@@ -506,6 +535,9 @@ impl pallet_bridge_parachains::Config<WithRialtoParachainsInstance> for Runtime
 	type ParasPalletName = RialtoParasPalletName;
 	type ParaStoredHeaderDataBuilder =
 		SingleParaStoredHeaderDataBuilder<bp_rialto_parachain::RialtoParachain>;
+	type TrackedParaStoredHeaderDataBuilder =
+		AnyParaStoredHeaderDataBuilder<bp_rialto_parachain::RialtoParachain>;
+	type MaxTrackedParachains = ConstU32<16>;
 	type HeadsToKeep = ConstU32<1024>;
 	type MaxParaHeadDataSize = MaxRialtoParaHeadDataSize;
 }
@@ -519,6 +551,8 @@ impl pallet_bridge_parachains::Config<WithWestendParachainsInstance> for Runtime
 	type BridgesGrandpaPalletInstance = WestendGrandpaInstance;
 	type ParasPalletName = WestendParasPalletName;
 	type ParaStoredHeaderDataBuilder = SingleParaStoredHeaderDataBuilder<bp_westend::Westmint>;
+	type TrackedParaStoredHeaderDataBuilder = AnyParaStoredHeaderDataBuilder<bp_westend::Westmint>;
+	type MaxTrackedParachains = ConstU32<16>;
 	type HeadsToKeep = ConstU32<1024>;
 	type MaxParaHeadDataSize = MaxWestendParaHeadDataSize;
 }
@@ -550,7 +584,7 @@ construct_runtime!(
 		// Consensus support.
 		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
 		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event},
-		ShiftSessionManager: pallet_shift_session_manager::{Pallet},
+		ShiftSessionManager: pallet_shift_session_manager::{Pallet, Call, Storage},
 
 		// BEEFY Bridges support.
 		Beefy: pallet_beefy::{Pallet, Storage, Config<T>},
@@ -847,16 +881,26 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl bp_rialto::RialtoFinalityApi<Block> for Runtime {
 		fn best_finalized() -> Option<HeaderId<bp_rialto::Hash, bp_rialto::BlockNumber>> {
 			BridgeRialtoGrandpa::best_finalized()
 		}
+
+		fn current_grandpa_authority_set() -> bp_header_chain::AuthoritySet {
+			BridgeRialtoGrandpa::current_authority_set().into()
+		}
 	}
 
+	#[api_version(2)]
 	impl bp_westend::WestendFinalityApi<Block> for Runtime {
 		fn best_finalized() -> Option<HeaderId<bp_westend::Hash, bp_westend::BlockNumber>> {
 			BridgeWestendGrandpa::best_finalized()
 		}
+
+		fn current_grandpa_authority_set() -> bp_header_chain::AuthoritySet {
+			BridgeWestendGrandpa::current_authority_set().into()
+		}
 	}
 
 	impl bp_westend::WestmintFinalityApi<Block> for Runtime {
@@ -877,6 +921,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl bp_rialto::ToRialtoOutboundLaneApi<Block> for Runtime {
 		fn message_details(
 			lane: bp_messages::LaneId,
@@ -888,8 +933,16 @@ impl_runtime_apis! {
 				WithRialtoMessagesInstance,
 			>(lane, begin, end)
 		}
+
+		fn lane_statistics(lane: bp_messages::LaneId) -> bp_messages::LaneStatistics {
+			bridge_runtime_common::messages_api::lane_statistics::<
+				Runtime,
+				WithRialtoMessagesInstance,
+			>(lane)
+		}
 	}
 
+	#[api_version(2)]
 	impl bp_rialto::FromRialtoInboundLaneApi<Block> for Runtime {
 		fn message_details(
 			lane: bp_messages::LaneId,
@@ -900,8 +953,16 @@ impl_runtime_apis! {
 				WithRialtoMessagesInstance,
 			>(lane, messages)
 		}
+
+		fn delivery_limits() -> bp_messages::MessagesDeliveryLimits {
+			bridge_runtime_common::messages_api::messages_delivery_limits::<
+				Runtime,
+				WithRialtoMessagesInstance,
+			>()
+		}
 	}
 
+	#[api_version(2)]
 	impl bp_rialto_parachain::ToRialtoParachainOutboundLaneApi<Block> for Runtime {
 		fn message_details(
 			lane: bp_messages::LaneId,
@@ -913,8 +974,16 @@ impl_runtime_apis! {
 				WithRialtoParachainMessagesInstance,
 			>(lane, begin, end)
 		}
+
+		fn lane_statistics(lane: bp_messages::LaneId) -> bp_messages::LaneStatistics {
+			bridge_runtime_common::messages_api::lane_statistics::<
+				Runtime,
+				WithRialtoParachainMessagesInstance,
+			>(lane)
+		}
 	}
 
+	#[api_version(2)]
 	impl bp_rialto_parachain::FromRialtoParachainInboundLaneApi<Block> for Runtime {
 		fn message_details(
 			lane: bp_messages::LaneId,
@@ -925,6 +994,13 @@ impl_runtime_apis! {
 				WithRialtoParachainMessagesInstance,
 			>(lane, messages)
 		}
+
+		fn delivery_limits() -> bp_messages::MessagesDeliveryLimits {
+			bridge_runtime_common::messages_api::messages_delivery_limits::<
+				Runtime,
+				WithRialtoParachainMessagesInstance,
+			>()
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1099,7 +1175,7 @@ impl_runtime_apis! {
 					let rewards_account = bp_relayers::PayRewardFromAccount::<
 						Balances,
 						AccountId
-					>::rewards_account(account_params);
+					>::rewards_account_at(account_params, RelayersAccountDerivationVersion::get());
 					Balances::mint_into(&rewards_account, reward).unwrap();
 				}
 			}
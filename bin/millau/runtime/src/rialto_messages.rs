@@ -20,13 +20,17 @@ use crate::{RialtoGrandpaInstance, Runtime, RuntimeCall, RuntimeOrigin};
 
 use bp_messages::{LaneId, MessageNonce};
 use bp_runtime::{ChainId, MILLAU_CHAIN_ID, RIALTO_CHAIN_ID};
-use bridge_runtime_common::messages::{
-	self, source::TargetHeaderChainAdapter, target::SourceHeaderChainAdapter, MessageBridge,
+use bridge_runtime_common::{
+	bridged_governance::BridgedGovernanceDispatch,
+	messages::{
+		self, source::TargetHeaderChainAdapter, target::SourceHeaderChainAdapter, MessageBridge,
+	},
+	messages_xcm_extension::TrustedBridgedOriginsDispatch,
 };
 use frame_support::{parameter_types, weights::Weight, RuntimeDebug};
 
 /// Default lane that is used to send messages to Rialto.
-pub const XCM_LANE: LaneId = LaneId([0, 0, 0, 0]);
+pub const XCM_LANE: LaneId = bp_millau::WITH_RIALTO_XCM_LANE;
 /// Weight of 2 XCM instructions is for simple `Trap(42)` program, coming through bridge
 /// (it is prepended with `UniversalOrigin` instruction). It is used just for simplest manual
 /// tests, confirming that we don't break encoding somewhere between.
@@ -38,6 +42,9 @@ parameter_types! {
 	/// 2 XCM instructions is for simple `Trap(42)` program, coming through bridge
 	/// (it is prepended with `UniversalOrigin` instruction).
 	pub const WeightCredit: Weight = BASE_XCM_WEIGHT_TWICE;
+	/// Lane that is used to deliver Rialto governance messages. For simplicity, it is the same
+	/// lane that is used by all other Rialto XCM messages.
+	pub const RialtoGovernanceLane: LaneId = XCM_LANE;
 }
 
 /// Message payload for Millau -> Rialto messages.
@@ -58,11 +65,21 @@ pub type ToRialtoMessagesDeliveryProof =
 	messages::source::FromBridgedChainMessagesDeliveryProof<bp_rialto::Hash>;
 
 /// Call-dispatch based message dispatch for Rialto -> Millau messages.
-pub type FromRialtoMessageDispatch = messages::target::FromBridgedChainMessageDispatch<
-	WithRialtoMessageBridge,
-	xcm_executor::XcmExecutor<crate::xcm_config::XcmConfig>,
-	crate::xcm_config::XcmWeigher,
-	WeightCredit,
+///
+/// Only messages from trusted bridged origins are dispatched. Messages from the
+/// `RialtoGovernanceLane` may be dispatched with the bridged governance origin.
+pub type FromRialtoMessageDispatch = BridgedGovernanceDispatch<
+	TrustedBridgedOriginsDispatch<
+		messages::target::FromBridgedChainMessageDispatch<
+			WithRialtoMessageBridge,
+			xcm_executor::XcmExecutor<crate::xcm_config::XcmConfig>,
+			crate::xcm_config::XcmWeigher,
+			WeightCredit,
+		>,
+		crate::xcm_config::RialtoNetwork,
+		crate::xcm_config::TrustedBridgedOrigins,
+	>,
+	RialtoGovernanceLane,
 >;
 
 /// Maximal outbound payload size of Millau -> Rialto messages.
@@ -131,9 +148,10 @@ mod tests {
 	use bridge_runtime_common::{
 		assert_complete_bridge_types,
 		integrity::{
-			assert_complete_bridge_constants, check_message_lane_weights,
-			AssertBridgeMessagesPalletConstants, AssertBridgePalletNames, AssertChainConstants,
-			AssertCompleteBridgeConstants,
+			assert_complete_bridge_constants, assert_trusted_bridged_origins,
+			check_message_lane_weights, AssertBridgeMessagesPalletConstants,
+			AssertBridgePalletNames, AssertChainConstants, AssertCompleteBridgeConstants,
+			AssertTrustedBridgedOrigins,
 		},
 	};
 
@@ -182,4 +200,18 @@ mod tests {
 			},
 		});
 	}
+
+	#[test]
+	fn ensure_trusted_bridged_origins_are_correct() {
+		use crate::xcm_config::{BridgeLocation, RialtoNetwork, TrustedBridgedOrigins, XcmConfig};
+
+		assert_trusted_bridged_origins::<<XcmConfig as xcm_executor::Config>::UniversalAliases>(
+			AssertTrustedBridgedOrigins {
+				bridge_location: BridgeLocation::get(),
+				bridged_chain_network: RialtoNetwork::get(),
+				this_chain_trusted_origins: TrustedBridgedOrigins::get(),
+				bridged_chain_lanes: &[bp_rialto::WITH_MILLAU_XCM_LANE],
+			},
+		);
+	}
 }
@@ -20,13 +20,16 @@ use crate::{Runtime, RuntimeCall, RuntimeOrigin, WithRialtoParachainsInstance};
 
 use bp_messages::{LaneId, MessageNonce};
 use bp_runtime::{ChainId, MILLAU_CHAIN_ID, RIALTO_PARACHAIN_CHAIN_ID};
-use bridge_runtime_common::messages::{
-	self, source::TargetHeaderChainAdapter, target::SourceHeaderChainAdapter, MessageBridge,
+use bridge_runtime_common::{
+	messages::{
+		self, source::TargetHeaderChainAdapter, target::SourceHeaderChainAdapter, MessageBridge,
+	},
+	messages_xcm_extension::TrustedBridgedOriginsDispatch,
 };
 use frame_support::{parameter_types, weights::Weight, RuntimeDebug};
 
 /// Default lane that is used to send messages to Rialto parachain.
-pub const XCM_LANE: LaneId = LaneId([0, 0, 0, 0]);
+pub const XCM_LANE: LaneId = bp_millau::WITH_RIALTO_PARACHAIN_XCM_LANE;
 /// Weight of 2 XCM instructions is for simple `Trap(42)` program, coming through bridge
 /// (it is prepended with `UniversalOrigin` instruction). It is used just for simplest manual
 /// tests, confirming that we don't break encoding somewhere between.
@@ -52,11 +55,17 @@ pub type FromRialtoParachainMessagePayload =
 	messages::target::FromBridgedChainMessagePayload<RuntimeCall>;
 
 /// Call-dispatch based message dispatch for RialtoParachain -> Millau messages.
-pub type FromRialtoParachainMessageDispatch = messages::target::FromBridgedChainMessageDispatch<
-	WithRialtoParachainMessageBridge,
-	xcm_executor::XcmExecutor<crate::xcm_config::XcmConfig>,
-	crate::xcm_config::XcmWeigher,
-	WeightCredit,
+///
+/// Only messages from trusted bridged origins are dispatched.
+pub type FromRialtoParachainMessageDispatch = TrustedBridgedOriginsDispatch<
+	messages::target::FromBridgedChainMessageDispatch<
+		WithRialtoParachainMessageBridge,
+		xcm_executor::XcmExecutor<crate::xcm_config::XcmConfig>,
+		crate::xcm_config::XcmWeigher,
+		WeightCredit,
+	>,
+	crate::xcm_config::RialtoParachainNetwork,
+	crate::xcm_config::TrustedBridgedOrigins,
 >;
 
 /// Maximal outbound payload size of Millau -> RialtoParachain messages.
@@ -26,12 +26,14 @@ use bp_messages::LaneId;
 use bp_millau::WeightToFee;
 use bp_rialto_parachain::RIALTO_PARACHAIN_ID;
 use bridge_runtime_common::{
-	messages::source::{XcmBridge, XcmBridgeAdapter},
+	bridged_governance::BridgedGovernanceOriginConverter,
+	messages::source::{PayInNativeTokens, XcmBridge, XcmBridgeAdapter},
+	messages_xcm_extension::{TrustedBridgedOrigin, UniversalAliasesFromTrustedOrigins},
 	CustomNetworkId,
 };
 use frame_support::{
 	parameter_types,
-	traits::{ConstU32, Everything, Nothing},
+	traits::{ConstU32, Everything},
 	weights::Weight,
 };
 use xcm::latest::prelude::*;
@@ -52,6 +54,19 @@ parameter_types! {
 	pub const RialtoNetwork: NetworkId = CustomNetworkId::Rialto.as_network_id();
 	/// The RialtoParachain network ID.
 	pub const RialtoParachainNetwork: NetworkId = CustomNetworkId::RialtoParachain.as_network_id();
+	/// Location that is dispatching bridged messages at this chain. Bridge pallets are deployed
+	/// at Millau itself, so it is `Here`.
+	pub const BridgeLocation: MultiLocation = MultiLocation::here();
+	/// Bridged origins that are trusted by Millau.
+	pub const TrustedBridgedOrigins: &'static [TrustedBridgedOrigin] = &[
+		(RialtoNetwork::get(), XCM_LANE),
+		(RialtoParachainNetwork::get(), XCM_LANE_PARACHAIN),
+	];
+	/// Location of the Rialto governance, as it is seen from Millau.
+	pub const RialtoGovernanceLocation: MultiLocation =
+		MultiLocation { parents: 1, interior: X1(GlobalConsensus(RialtoNetwork::get())) };
+	/// Origin that is used to dispatch Rialto governance calls at Millau.
+	pub RialtoGovernanceOrigin: RuntimeOrigin = RuntimeOrigin::root();
 
 	/// Our XCM location ancestry - i.e. our location within the Consensus Universe.
 	///
@@ -91,6 +106,8 @@ type LocalOriginConverter = (
 	SovereignSignedViaLocation<SovereignAccountOf, RuntimeOrigin>,
 	// The AccountId32 location type can be expressed natively as a `Signed` origin.
 	SignedAccountId32AsNative<ThisNetwork, RuntimeOrigin>,
+	// Rialto governance may dispatch calls with the `Root` origin.
+	BridgedGovernanceOriginConverter<RialtoGovernanceLocation, RialtoGovernanceOrigin>,
 );
 
 parameter_types! {
@@ -146,7 +163,8 @@ impl xcm_executor::Config for XcmConfig {
 	type MaxAssetsIntoHolding = ConstU32<64>;
 	type FeeManager = ();
 	type MessageExporter = ();
-	type UniversalAliases = Nothing;
+	type UniversalAliases =
+		UniversalAliasesFromTrustedOrigins<BridgeLocation, TrustedBridgedOrigins>;
 	type CallDispatcher = RuntimeCall;
 	type SafeCallFilter = Everything;
 }
@@ -203,6 +221,9 @@ pub struct ToRialtoBridge;
 impl XcmBridge for ToRialtoBridge {
 	type MessageBridge = WithRialtoMessageBridge;
 	type MessageSender = pallet_bridge_messages::Pallet<Runtime, WithRialtoMessagesInstance>;
+	type DeliveryFeeConverter = PayInNativeTokens;
+	type DestinationVersion = XcmPallet;
+	type PendingQueries = ();
 
 	fn universal_location() -> InteriorMultiLocation {
 		UniversalLocation::get()
@@ -230,6 +251,9 @@ impl XcmBridge for ToRialtoParachainBridge {
 	type MessageBridge = WithRialtoParachainMessageBridge;
 	type MessageSender =
 		pallet_bridge_messages::Pallet<Runtime, WithRialtoParachainMessagesInstance>;
+	type DeliveryFeeConverter = PayInNativeTokens;
+	type DestinationVersion = XcmPallet;
+	type PendingQueries = ();
 
 	fn universal_location() -> InteriorMultiLocation {
 		UniversalLocation::get()
@@ -263,9 +287,13 @@ mod tests {
 	use codec::Encode;
 
 	fn new_test_ext() -> sp_io::TestExternalities {
-		sp_io::TestExternalities::new(
-			frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap(),
-		)
+		use frame_support::traits::GenesisBuild;
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		// XCM version of bridged destinations is unknown at genesis, so let's use the safe one
+		let xcm_genesis =
+			pallet_xcm::GenesisConfig { safe_xcm_version: Some(xcm::latest::VERSION) };
+		GenesisBuild::<Runtime>::assimilate_storage(&xcm_genesis, &mut t).unwrap();
+		sp_io::TestExternalities::new(t)
 	}
 
 	#[test]
@@ -323,4 +351,34 @@ mod tests {
 			);
 		})
 	}
+
+	#[test]
+	fn xcm_messages_from_rialto_parachain_are_dispatched() {
+		type MessageDispatcher =
+			crate::rialto_parachain_messages::FromRialtoParachainMessageDispatch;
+
+		new_test_ext().execute_with(|| {
+			let location: MultiLocation =
+				(Parent, X1(GlobalConsensus(RialtoParachainNetwork::get()))).into();
+			let xcm: Xcm<RuntimeCall> = vec![Instruction::Trap(42)].into();
+
+			let mut incoming_message = DispatchMessage {
+				key: MessageKey { lane_id: XCM_LANE_PARACHAIN, nonce: 1 },
+				data: DispatchMessageData { payload: Ok((location, xcm).into()) },
+			};
+
+			let dispatch_weight = MessageDispatcher::dispatch_weight(&mut incoming_message);
+			assert_eq!(dispatch_weight, BaseXcmWeight::get());
+
+			let dispatch_result =
+				MessageDispatcher::dispatch(&AccountId::from([0u8; 32]), incoming_message);
+			assert_eq!(
+				dispatch_result,
+				MessageDispatchResult {
+					unspent_weight: frame_support::weights::Weight::zero(),
+					dispatch_level_result: (),
+				}
+			);
+		})
+	}
 }
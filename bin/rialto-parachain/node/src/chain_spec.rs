@@ -100,6 +100,8 @@ fn endowed_accounts() -> Vec<AccountId> {
 		get_account_id_from_seed::<sr25519::Public>(MILLAU_MESSAGES_PALLET_OWNER),
 		get_account_id_from_seed::<sr25519::Public>("Millau.HeadersAndMessagesRelay1"),
 		get_account_id_from_seed::<sr25519::Public>("Millau.HeadersAndMessagesRelay2"),
+		get_account_id_from_seed::<sr25519::Public>("Millau.OutboundMessagesRelay.Lane00000000"),
+		get_account_id_from_seed::<sr25519::Public>("Millau.InboundMessagesRelay.Lane00000000"),
 		get_account_id_from_seed::<sr25519::Public>("Millau.MessagesSender"),
 	]
 	.into_iter()
@@ -28,7 +28,10 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use crate::millau_messages::{WithMillauMessageBridge, XCM_LANE};
 
-use bridge_runtime_common::messages::source::{XcmBridge, XcmBridgeAdapter};
+use bridge_runtime_common::{
+	messages::source::{PayInNativeTokens, XcmBridge, XcmBridgeAdapter},
+	messages_xcm_extension::{TrustedBridgedOrigin, UniversalAliasesFromTrustedOrigins},
+};
 use cumulus_pallet_parachain_system::AnyRelayNumber;
 use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
@@ -45,12 +48,12 @@ use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
 
 // A few exports that help ease life for downstream crates.
-use bp_runtime::HeaderId;
+use bp_runtime::{account_derivation::AccountDerivationVersion, HeaderId};
 pub use frame_support::{
 	construct_runtime,
 	dispatch::DispatchClass,
 	match_types, parameter_types,
-	traits::{ConstU32, Everything, IsInVec, Nothing, Randomness},
+	traits::{ConstBool, ConstU32, Everything, IsInVec, Nothing, Randomness},
 	weights::{
 		constants::{
 			BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND,
@@ -311,6 +314,12 @@ parameter_types! {
 	pub const MillauNetwork: NetworkId = CustomNetworkId::Millau.as_network_id();
 	/// The RialtoParachain network ID.
 	pub const ThisNetwork: NetworkId = CustomNetworkId::RialtoParachain.as_network_id();
+	/// Location that is dispatching bridged messages at this chain. Bridge pallets are deployed
+	/// at RialtoParachain itself, so it is `Here`.
+	pub const BridgeLocation: MultiLocation = MultiLocation::here();
+	/// Bridged origins that are trusted by RialtoParachain.
+	pub const TrustedBridgedOrigins: &'static [TrustedBridgedOrigin] =
+		&[(MillauNetwork::get(), XCM_LANE)];
 }
 
 /// Type for specifying how a `MultiLocation` can be converted into an `AccountId`. This is used
@@ -415,7 +424,8 @@ impl Config for XcmConfig {
 	type AssetExchanger = ();
 	type FeeManager = ();
 	type MessageExporter = ();
-	type UniversalAliases = Nothing;
+	type UniversalAliases =
+		UniversalAliasesFromTrustedOrigins<BridgeLocation, TrustedBridgedOrigins>;
 	type CallDispatcher = RuntimeCall;
 	type SafeCallFilter = Everything;
 }
@@ -436,6 +446,9 @@ pub struct ToMillauBridge;
 impl XcmBridge for ToMillauBridge {
 	type MessageBridge = WithMillauMessageBridge;
 	type MessageSender = pallet_bridge_messages::Pallet<Runtime, WithMillauMessagesInstance>;
+	type DeliveryFeeConverter = PayInNativeTokens;
+	type DestinationVersion = PolkadotXcm;
+	type PendingQueries = ();
 
 	fn universal_location() -> InteriorMultiLocation {
 		UniversalLocation::get()
@@ -515,11 +528,18 @@ impl pallet_aura::Config for Runtime {
 	type MaxAuthorities = MaxAuthorities;
 }
 
+parameter_types! {
+	/// Version of the scheme that is used to derive rewards accounts.
+	pub const RelayersAccountDerivationVersion: AccountDerivationVersion =
+		AccountDerivationVersion::V0;
+}
+
 impl pallet_bridge_relayers::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Reward = Balance;
 	type PaymentProcedure =
 		bp_relayers::PayRewardFromAccount<pallet_balances::Pallet<Runtime>, AccountId>;
+	type AccountDerivationVersion = RelayersAccountDerivationVersion;
 	type WeightInfo = ();
 }
 
@@ -532,6 +552,7 @@ impl pallet_bridge_grandpa::Config for Runtime {
 	/// one call per block.
 	type MaxRequests = ConstU32<50>;
 	type HeadersToKeep = ConstU32<{ bp_millau::DAYS as u32 }>;
+	type UseHostSignatureVerification = ConstBool<false>;
 	type WeightInfo = pallet_bridge_grandpa::weights::BridgeWeight<Runtime>;
 }
 
@@ -542,6 +563,9 @@ parameter_types! {
 	pub const MaxUnconfirmedMessagesAtInboundLane: bp_messages::MessageNonce =
 		bp_millau::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
 	pub const RootAccountForPayments: Option<AccountId> = None;
+	pub const StaleLaneThreshold: BlockNumber = HOURS;
+	pub MaxDeferredDispatchWeightPerBlock: Weight =
+		BlockWeights::get().max_block.saturating_div(4);
 	pub const BridgedChainId: bp_runtime::ChainId = bp_runtime::MILLAU_CHAIN_ID;
 	pub ActiveOutboundLanes: &'static [bp_messages::LaneId] = &[millau_messages::XCM_LANE];
 }
@@ -553,8 +577,13 @@ impl pallet_bridge_messages::Config<WithMillauMessagesInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_bridge_messages::weights::BridgeWeight<Runtime>;
 	type ActiveOutboundLanes = ActiveOutboundLanes;
+	type DynamicLanes = ();
+	type StaleLaneThreshold = StaleLaneThreshold;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxDispatchWeightPerBlock = ();
+	type MaxDeferredMessagesPerLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxDeferredDispatchWeightPerBlock = MaxDeferredDispatchWeightPerBlock;
 
 	type MaximalOutboundPayloadSize = crate::millau_messages::ToMillauMaximalOutboundPayloadSize;
 	type OutboundPayload = crate::millau_messages::ToMillauMessagePayload;
@@ -570,6 +599,7 @@ impl pallet_bridge_messages::Config<WithMillauMessagesInstance> for Runtime {
 		WithMillauMessagesInstance,
 		frame_support::traits::ConstU128<100_000>,
 	>;
+	type OnMessagesDelivered = ();
 
 	type SourceHeaderChain = crate::millau_messages::MillauAsSourceHeaderChain;
 	type MessageDispatch = crate::millau_messages::FromMillauMessageDispatch;
@@ -724,12 +754,18 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl bp_millau::MillauFinalityApi<Block> for Runtime {
 		fn best_finalized() -> Option<HeaderId<bp_millau::Hash, bp_millau::BlockNumber>> {
 			BridgeMillauGrandpa::best_finalized()
 		}
+
+		fn current_grandpa_authority_set() -> bp_header_chain::AuthoritySet {
+			BridgeMillauGrandpa::current_authority_set().into()
+		}
 	}
 
+	#[api_version(2)]
 	impl bp_millau::ToMillauOutboundLaneApi<Block> for Runtime {
 		fn message_details(
 			lane: bp_messages::LaneId,
@@ -741,8 +777,16 @@ impl_runtime_apis! {
 				WithMillauMessagesInstance,
 			>(lane, begin, end)
 		}
+
+		fn lane_statistics(lane: bp_messages::LaneId) -> bp_messages::LaneStatistics {
+			bridge_runtime_common::messages_api::lane_statistics::<
+				Runtime,
+				WithMillauMessagesInstance,
+			>(lane)
+		}
 	}
 
+	#[api_version(2)]
 	impl bp_millau::FromMillauInboundLaneApi<Block> for Runtime {
 		fn message_details(
 			lane: bp_messages::LaneId,
@@ -753,6 +797,13 @@ impl_runtime_apis! {
 				WithMillauMessagesInstance,
 			>(lane, messages)
 		}
+
+		fn delivery_limits() -> bp_messages::MessagesDeliveryLimits {
+			bridge_runtime_common::messages_api::messages_delivery_limits::<
+				Runtime,
+				WithMillauMessagesInstance,
+			>()
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -842,9 +893,13 @@ mod tests {
 	use sp_runtime::generic::Era;
 
 	fn new_test_ext() -> sp_io::TestExternalities {
-		sp_io::TestExternalities::new(
-			frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap(),
-		)
+		use frame_support::traits::GenesisBuild;
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		// XCM version of bridged destinations is unknown at genesis, so let's use the safe one
+		let xcm_genesis =
+			pallet_xcm::GenesisConfig { safe_xcm_version: Some(xcm::latest::VERSION) };
+		GenesisBuild::<Runtime>::assimilate_storage(&xcm_genesis, &mut t).unwrap();
+		sp_io::TestExternalities::new(t)
 	}
 
 	#[test]
@@ -23,13 +23,16 @@ use crate::{MillauGrandpaInstance, Runtime, RuntimeCall, RuntimeOrigin};
 
 use bp_messages::{LaneId, MessageNonce};
 use bp_runtime::{ChainId, MILLAU_CHAIN_ID, RIALTO_PARACHAIN_CHAIN_ID};
-use bridge_runtime_common::messages::{
-	self, source::TargetHeaderChainAdapter, target::SourceHeaderChainAdapter, MessageBridge,
+use bridge_runtime_common::{
+	messages::{
+		self, source::TargetHeaderChainAdapter, target::SourceHeaderChainAdapter, MessageBridge,
+	},
+	messages_xcm_extension::TrustedBridgedOriginsDispatch,
 };
 use frame_support::{parameter_types, weights::Weight, RuntimeDebug};
 
 /// Default lane that is used to send messages to Millau.
-pub const XCM_LANE: LaneId = LaneId([0, 0, 0, 0]);
+pub const XCM_LANE: LaneId = bp_rialto_parachain::WITH_MILLAU_XCM_LANE;
 /// Weight of 2 XCM instructions is for simple `Trap(42)` program, coming through bridge
 /// (it is prepended with `UniversalOrigin` instruction). It is used just for simplest manual
 /// tests, confirming that we don't break encoding somewhere between.
@@ -54,11 +57,17 @@ pub type ToMillauMessageVerifier =
 pub type FromMillauMessagePayload = messages::target::FromBridgedChainMessagePayload<RuntimeCall>;
 
 /// Call-dispatch based message dispatch for Millau -> RialtoParachain messages.
-pub type FromMillauMessageDispatch = messages::target::FromBridgedChainMessageDispatch<
-	WithMillauMessageBridge,
-	xcm_executor::XcmExecutor<crate::XcmConfig>,
-	crate::XcmWeigher,
-	WeightCredit,
+///
+/// Only messages from trusted bridged origins are dispatched.
+pub type FromMillauMessageDispatch = TrustedBridgedOriginsDispatch<
+	messages::target::FromBridgedChainMessageDispatch<
+		WithMillauMessageBridge,
+		xcm_executor::XcmExecutor<crate::XcmConfig>,
+		crate::XcmWeigher,
+		WeightCredit,
+	>,
+	crate::MillauNetwork,
+	crate::TrustedBridgedOrigins,
 >;
 
 /// Messages proof for Millau -> RialtoParachain messages.
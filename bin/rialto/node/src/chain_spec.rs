@@ -171,6 +171,7 @@ fn endowed_accounts() -> Vec<AccountId> {
 		get_account_id_from_seed::<sr25519::Public>("Millau.OutboundMessagesRelay.Lane00000001"),
 		get_account_id_from_seed::<sr25519::Public>("Millau.InboundMessagesRelay.Lane00000001"),
 		get_account_id_from_seed::<sr25519::Public>("Millau.MessagesSender"),
+		get_account_id_from_seed::<sr25519::Public>("Millau.BeefyHeadersRelay"),
 	]
 	.into_iter()
 	.chain(all_authorities)
@@ -32,7 +32,7 @@ pub mod millau_messages;
 pub mod parachains;
 pub mod xcm_config;
 
-use bp_runtime::HeaderId;
+use bp_runtime::{account_derivation::AccountDerivationVersion, HeaderId};
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
 };
@@ -58,7 +58,8 @@ pub use frame_support::{
 	dispatch::DispatchClass,
 	parameter_types,
 	traits::{
-		ConstU32, ConstU64, ConstU8, Currency, ExistenceRequirement, Imbalance, KeyOwnerProofSystem,
+		ConstBool, ConstU32, ConstU64, ConstU8, Currency, ExistenceRequirement, Imbalance,
+		KeyOwnerProofSystem,
 	},
 	weights::{constants::WEIGHT_REF_TIME_PER_SECOND, IdentityFee, RuntimeDbWeight, Weight},
 	StorageValue,
@@ -69,6 +70,7 @@ pub use pallet_balances::Call as BalancesCall;
 pub use pallet_bridge_beefy::Call as BridgeBeefyCall;
 pub use pallet_bridge_grandpa::Call as BridgeGrandpaCall;
 pub use pallet_bridge_messages::Call as MessagesCall;
+pub use pallet_bridge_relayers::Call as BridgeRelayersCall;
 pub use pallet_sudo::Call as SudoCall;
 pub use pallet_timestamp::Call as TimestampCall;
 pub use pallet_xcm::Call as XcmCall;
@@ -380,11 +382,18 @@ impl pallet_authority_discovery::Config for Runtime {
 	type MaxAuthorities = ConstU32<10>;
 }
 
+parameter_types! {
+	/// Version of the scheme that is used to derive rewards accounts.
+	pub const RelayersAccountDerivationVersion: AccountDerivationVersion =
+		AccountDerivationVersion::V0;
+}
+
 impl pallet_bridge_relayers::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Reward = Balance;
 	type PaymentProcedure =
 		bp_relayers::PayRewardFromAccount<pallet_balances::Pallet<Runtime>, AccountId>;
+	type AccountDerivationVersion = RelayersAccountDerivationVersion;
 	type WeightInfo = ();
 }
 
@@ -397,10 +406,14 @@ impl pallet_bridge_grandpa::Config for Runtime {
 	/// one call per block.
 	type MaxRequests = ConstU32<50>;
 	type HeadersToKeep = ConstU32<{ bp_millau::DAYS as u32 }>;
+	type UseHostSignatureVerification = ConstBool<false>;
 	type WeightInfo = pallet_bridge_grandpa::weights::BridgeWeight<Runtime>;
 }
 
-impl pallet_shift_session_manager::Config for Runtime {}
+impl pallet_shift_session_manager::Config for Runtime {
+	type RotationStrategy = pallet_shift_session_manager::DefaultRotationStrategy;
+	type AllowlistOrigin = frame_system::EnsureRoot<AccountId>;
+}
 
 parameter_types! {
 	pub const MaxMessagesToPruneAtOnce: bp_messages::MessageNonce = 8;
@@ -409,6 +422,9 @@ parameter_types! {
 	pub const MaxUnconfirmedMessagesAtInboundLane: bp_messages::MessageNonce =
 		bp_millau::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
 	pub const RootAccountForPayments: Option<AccountId> = None;
+	pub const StaleLaneThreshold: BlockNumber = bp_rialto::time_units::HOURS;
+	pub MaxDeferredDispatchWeightPerBlock: Weight =
+		bp_rialto::BlockWeights::get().max_block.saturating_div(4);
 	pub const BridgedChainId: bp_runtime::ChainId = bp_runtime::MILLAU_CHAIN_ID;
 	pub ActiveOutboundLanes: &'static [bp_messages::LaneId] = &[millau_messages::XCM_LANE];
 }
@@ -420,8 +436,13 @@ impl pallet_bridge_messages::Config<WithMillauMessagesInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_bridge_messages::weights::BridgeWeight<Runtime>;
 	type ActiveOutboundLanes = ActiveOutboundLanes;
+	type DynamicLanes = ();
+	type StaleLaneThreshold = StaleLaneThreshold;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxDispatchWeightPerBlock = ();
+	type MaxDeferredMessagesPerLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxDeferredDispatchWeightPerBlock = MaxDeferredDispatchWeightPerBlock;
 
 	type MaximalOutboundPayloadSize = crate::millau_messages::ToMillauMaximalOutboundPayloadSize;
 	type OutboundPayload = crate::millau_messages::ToMillauMessagePayload;
@@ -437,6 +458,7 @@ impl pallet_bridge_messages::Config<WithMillauMessagesInstance> for Runtime {
 		WithMillauMessagesInstance,
 		frame_support::traits::ConstU128<100_000>,
 	>;
+	type OnMessagesDelivered = ();
 
 	type SourceHeaderChain = crate::millau_messages::MillauAsSourceHeaderChain;
 	type MessageDispatch = crate::millau_messages::FromMillauMessageDispatch;
@@ -470,7 +492,7 @@ construct_runtime!(
 		AuthorityDiscovery: pallet_authority_discovery::{Pallet, Config},
 		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
 		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event},
-		ShiftSessionManager: pallet_shift_session_manager::{Pallet},
+		ShiftSessionManager: pallet_shift_session_manager::{Pallet, Call, Storage},
 
 		// BEEFY Bridges support.
 		Beefy: pallet_beefy::{Pallet, Storage, Config<T>},
@@ -681,10 +703,15 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl bp_millau::MillauFinalityApi<Block> for Runtime {
 		fn best_finalized() -> Option<HeaderId<bp_millau::Hash, bp_millau::BlockNumber>> {
 			BridgeMillauGrandpa::best_finalized()
 		}
+
+		fn current_grandpa_authority_set() -> bp_header_chain::AuthoritySet {
+			BridgeMillauGrandpa::current_authority_set().into()
+		}
 	}
 
 	impl sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block> for Runtime {
@@ -919,6 +946,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl bp_millau::ToMillauOutboundLaneApi<Block> for Runtime {
 		fn message_details(
 			lane: bp_messages::LaneId,
@@ -930,8 +958,16 @@ impl_runtime_apis! {
 				WithMillauMessagesInstance,
 			>(lane, begin, end)
 		}
+
+		fn lane_statistics(lane: bp_messages::LaneId) -> bp_messages::LaneStatistics {
+			bridge_runtime_common::messages_api::lane_statistics::<
+				Runtime,
+				WithMillauMessagesInstance,
+			>(lane)
+		}
 	}
 
+	#[api_version(2)]
 	impl bp_millau::FromMillauInboundLaneApi<Block> for Runtime {
 		fn message_details(
 			lane: bp_messages::LaneId,
@@ -942,6 +978,13 @@ impl_runtime_apis! {
 				WithMillauMessagesInstance,
 			>(lane, messages)
 		}
+
+		fn delivery_limits() -> bp_messages::MessagesDeliveryLimits {
+			bridge_runtime_common::messages_api::messages_delivery_limits::<
+				Runtime,
+				WithMillauMessagesInstance,
+			>()
+		}
 	}
 }
 
@@ -20,13 +20,16 @@ use crate::{MillauGrandpaInstance, Runtime, RuntimeCall, RuntimeOrigin};
 
 use bp_messages::{LaneId, MessageNonce};
 use bp_runtime::{ChainId, MILLAU_CHAIN_ID, RIALTO_CHAIN_ID};
-use bridge_runtime_common::messages::{
-	self, source::TargetHeaderChainAdapter, target::SourceHeaderChainAdapter, MessageBridge,
+use bridge_runtime_common::{
+	messages::{
+		self, source::TargetHeaderChainAdapter, target::SourceHeaderChainAdapter, MessageBridge,
+	},
+	messages_xcm_extension::TrustedBridgedOriginsDispatch,
 };
 use frame_support::{parameter_types, weights::Weight, RuntimeDebug};
 
 /// Lane that is used for XCM messages exchange.
-pub const XCM_LANE: LaneId = LaneId([0, 0, 0, 0]);
+pub const XCM_LANE: LaneId = bp_rialto::WITH_MILLAU_XCM_LANE;
 /// Weight of 2 XCM instructions is for simple `Trap(42)` program, coming through bridge
 /// (it is prepended with `UniversalOrigin` instruction). It is used just for simplest manual
 /// tests, confirming that we don't break encoding somewhere between.
@@ -51,11 +54,17 @@ pub type ToMillauMessageVerifier =
 pub type FromMillauMessagePayload = messages::target::FromBridgedChainMessagePayload<RuntimeCall>;
 
 /// Call-dispatch based message dispatch for Millau -> Rialto messages.
-pub type FromMillauMessageDispatch = messages::target::FromBridgedChainMessageDispatch<
-	WithMillauMessageBridge,
-	xcm_executor::XcmExecutor<crate::xcm_config::XcmConfig>,
-	crate::xcm_config::XcmWeigher,
-	WeightCredit,
+///
+/// Only messages from trusted bridged origins are dispatched.
+pub type FromMillauMessageDispatch = TrustedBridgedOriginsDispatch<
+	messages::target::FromBridgedChainMessageDispatch<
+		WithMillauMessageBridge,
+		xcm_executor::XcmExecutor<crate::xcm_config::XcmConfig>,
+		crate::xcm_config::XcmWeigher,
+		WeightCredit,
+	>,
+	crate::xcm_config::MillauNetwork,
+	crate::xcm_config::TrustedBridgedOrigins,
 >;
 
 /// Messages proof for Millau -> Rialto messages.
@@ -130,9 +139,10 @@ mod tests {
 	use bridge_runtime_common::{
 		assert_complete_bridge_types,
 		integrity::{
-			assert_complete_bridge_constants, check_message_lane_weights,
-			AssertBridgeMessagesPalletConstants, AssertBridgePalletNames, AssertChainConstants,
-			AssertCompleteBridgeConstants,
+			assert_complete_bridge_constants, assert_trusted_bridged_origins,
+			check_message_lane_weights, AssertBridgeMessagesPalletConstants,
+			AssertBridgePalletNames, AssertChainConstants, AssertCompleteBridgeConstants,
+			AssertTrustedBridgedOrigins,
 		},
 	};
 
@@ -181,4 +191,18 @@ mod tests {
 			},
 		});
 	}
+
+	#[test]
+	fn ensure_trusted_bridged_origins_are_correct() {
+		use crate::xcm_config::{BridgeLocation, MillauNetwork, TrustedBridgedOrigins, XcmConfig};
+
+		assert_trusted_bridged_origins::<<XcmConfig as xcm_executor::Config>::UniversalAliases>(
+			AssertTrustedBridgedOrigins {
+				bridge_location: BridgeLocation::get(),
+				bridged_chain_network: MillauNetwork::get(),
+				this_chain_trusted_origins: TrustedBridgedOrigins::get(),
+				bridged_chain_lanes: &[bp_millau::WITH_RIALTO_XCM_LANE],
+			},
+		);
+	}
 }
@@ -17,17 +17,19 @@
 //! XCM configurations for the Rialto runtime.
 
 use super::{
-	millau_messages::WithMillauMessageBridge, AccountId, AllPalletsWithSystem, Balances, Runtime,
-	RuntimeCall, RuntimeEvent, RuntimeOrigin, WithMillauMessagesInstance, XcmPallet,
+	millau_messages::{WithMillauMessageBridge, XCM_LANE},
+	AccountId, AllPalletsWithSystem, Balances, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin,
+	WithMillauMessagesInstance, XcmPallet,
 };
 use bp_rialto::WeightToFee;
 use bridge_runtime_common::{
-	messages::source::{XcmBridge, XcmBridgeAdapter},
+	messages::source::{PayInNativeTokens, XcmBridge, XcmBridgeAdapter},
+	messages_xcm_extension::{TrustedBridgedOrigin, UniversalAliasesFromTrustedOrigins},
 	CustomNetworkId,
 };
 use frame_support::{
 	parameter_types,
-	traits::{ConstU32, Everything, Nothing},
+	traits::{ConstU32, Everything},
 	weights::Weight,
 };
 use xcm::latest::prelude::*;
@@ -46,6 +48,12 @@ parameter_types! {
 	pub const ThisNetwork: NetworkId = CustomNetworkId::Rialto.as_network_id();
 	/// The Millau network ID.
 	pub const MillauNetwork: NetworkId = CustomNetworkId::Millau.as_network_id();
+	/// Location that is dispatching bridged messages at this chain. Bridge pallets are deployed
+	/// at Rialto itself, so it is `Here`.
+	pub const BridgeLocation: MultiLocation = MultiLocation::here();
+	/// Bridged origins that are trusted by Rialto.
+	pub const TrustedBridgedOrigins: &'static [TrustedBridgedOrigin] =
+		&[(MillauNetwork::get(), XCM_LANE)];
 
 	/// Our XCM location ancestry - i.e. our location within the Consensus Universe.
 	///
@@ -138,7 +146,8 @@ impl xcm_executor::Config for XcmConfig {
 	type MaxAssetsIntoHolding = ConstU32<64>;
 	type FeeManager = ();
 	type MessageExporter = ();
-	type UniversalAliases = Nothing;
+	type UniversalAliases =
+		UniversalAliasesFromTrustedOrigins<BridgeLocation, TrustedBridgedOrigins>;
 	type CallDispatcher = RuntimeCall;
 	type SafeCallFilter = Everything;
 }
@@ -195,6 +204,9 @@ pub struct ToMillauBridge;
 impl XcmBridge for ToMillauBridge {
 	type MessageBridge = WithMillauMessageBridge;
 	type MessageSender = pallet_bridge_messages::Pallet<Runtime, WithMillauMessagesInstance>;
+	type DeliveryFeeConverter = PayInNativeTokens;
+	type DestinationVersion = XcmPallet;
+	type PendingQueries = ();
 
 	fn universal_location() -> InteriorMultiLocation {
 		UniversalLocation::get()
@@ -211,7 +223,7 @@ impl XcmBridge for ToMillauBridge {
 	}
 
 	fn xcm_lane() -> bp_messages::LaneId {
-		bp_messages::LaneId([0, 0, 0, 0])
+		XCM_LANE
 	}
 }
 
@@ -228,9 +240,13 @@ mod tests {
 	use codec::Encode;
 
 	fn new_test_ext() -> sp_io::TestExternalities {
-		sp_io::TestExternalities::new(
-			frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap(),
-		)
+		use frame_support::traits::GenesisBuild;
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		// XCM version of bridged destinations is unknown at genesis, so let's use the safe one
+		let xcm_genesis =
+			pallet_xcm::GenesisConfig { safe_xcm_version: Some(xcm::latest::VERSION) };
+		GenesisBuild::<Runtime>::assimilate_storage(&xcm_genesis, &mut t).unwrap();
+		sp_io::TestExternalities::new(t)
 	}
 
 	#[test]
@@ -0,0 +1,152 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Call filters that may be used to bootstrap bridge hub-like runtimes.
+//!
+//! The runtime may be launched in restricted mode, where only bridge initialization and relaying
+//! is possible. Once the bridge is up and running, governance may open the runtime up.
+//!
+//! ## Example
+//!
+//! ```nocompile
+//! parameter_types! {
+//!     // governance may switch it off using the `system::set_storage` call
+//!     pub storage IsBootstrapping: bool = true;
+//! }
+//!
+//! impl frame_system::Config for Runtime {
+//!     type BaseCallFilter = BootstrapCallFilter<
+//!         IsBootstrapping,
+//!         (
+//!             AllCallsOf<BridgeRialtoGrandpa, Runtime>,
+//!             AllCallsOf<BridgeRialtoMessages, Runtime>,
+//!         ),
+//!         (
+//!             AllCallsOf<System, Runtime>,
+//!             AllCallsOf<Timestamp, Runtime>,
+//!             AllCallsOf<Sudo, Runtime>,
+//!         ),
+//!     >;
+//!     ...
+//! }
+//! ```
+
+use frame_support::{
+	dispatch::{Callable, CallableCallFor},
+	traits::{Contains, Get, IsSubType},
+};
+use sp_std::marker::PhantomData;
+
+/// Matches all calls of the given pallet.
+///
+/// Tuples of `AllCallsOf` may be used to match calls of multiple pallets.
+pub struct AllCallsOf<Pallet, Runtime>(PhantomData<(Pallet, Runtime)>);
+
+impl<Call, Pallet, Runtime> Contains<Call> for AllCallsOf<Pallet, Runtime>
+where
+	Pallet: Callable<Runtime>,
+	Call: IsSubType<CallableCallFor<Pallet, Runtime>>,
+{
+	fn contains(call: &Call) -> bool {
+		call.is_sub_type().is_some()
+	}
+}
+
+/// Call filter of the runtime that is being bootstrapped.
+///
+/// While `IsBootstrapping` returns `true`, only calls of bridge pallets (`BridgeCalls`) and calls
+/// from the `Allowlist` are allowed. Keep in mind that inherents are also filtered, so calls of
+/// pallets like `pallet_timestamp` or `cumulus_pallet_parachain_system` must be in the
+/// `Allowlist`. Once `IsBootstrapping` returns `false`, all calls are allowed.
+pub struct BootstrapCallFilter<IsBootstrapping, BridgeCalls, Allowlist>(
+	PhantomData<(IsBootstrapping, BridgeCalls, Allowlist)>,
+);
+
+impl<Call, IsBootstrapping, BridgeCalls, Allowlist> Contains<Call>
+	for BootstrapCallFilter<IsBootstrapping, BridgeCalls, Allowlist>
+where
+	IsBootstrapping: Get<bool>,
+	BridgeCalls: Contains<Call>,
+	Allowlist: Contains<Call>,
+{
+	fn contains(call: &Call) -> bool {
+		!IsBootstrapping::get() || BridgeCalls::contains(call) || Allowlist::contains(call)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{
+		BridgeGrandpa, BridgeMessages, BridgeParachains, System, TestRuntime, ThisChainRuntimeCall,
+	};
+	use bp_messages::MessagesOperatingMode;
+	use bp_runtime::BasicOperatingMode;
+	use frame_support::traits::ConstBool;
+
+	type BridgeCalls = (
+		AllCallsOf<BridgeGrandpa, TestRuntime>,
+		AllCallsOf<BridgeParachains, TestRuntime>,
+		AllCallsOf<BridgeMessages, TestRuntime>,
+	);
+	type Allowlist = AllCallsOf<System, TestRuntime>;
+
+	type BootstrappingFilter = BootstrapCallFilter<ConstBool<true>, BridgeCalls, Allowlist>;
+	type OpenedFilter = BootstrapCallFilter<ConstBool<false>, BridgeCalls, Allowlist>;
+
+	fn grandpa_call() -> ThisChainRuntimeCall {
+		ThisChainRuntimeCall::BridgeGrandpa(pallet_bridge_grandpa::Call::set_operating_mode {
+			operating_mode: BasicOperatingMode::Halted,
+		})
+	}
+
+	fn messages_call() -> ThisChainRuntimeCall {
+		ThisChainRuntimeCall::BridgeMessages(pallet_bridge_messages::Call::set_operating_mode {
+			operating_mode: MessagesOperatingMode::Basic(BasicOperatingMode::Halted),
+		})
+	}
+
+	fn system_call() -> ThisChainRuntimeCall {
+		ThisChainRuntimeCall::System(frame_system::Call::remark { remark: vec![42] })
+	}
+
+	fn utility_call() -> ThisChainRuntimeCall {
+		ThisChainRuntimeCall::Utility(pallet_utility::Call::batch { calls: vec![] })
+	}
+
+	#[test]
+	fn all_calls_of_matches_only_calls_of_given_pallet() {
+		assert!(AllCallsOf::<BridgeGrandpa, TestRuntime>::contains(&grandpa_call()));
+		assert!(!AllCallsOf::<BridgeGrandpa, TestRuntime>::contains(&messages_call()));
+		assert!(!AllCallsOf::<BridgeGrandpa, TestRuntime>::contains(&system_call()));
+	}
+
+	#[test]
+	fn bootstrapping_filter_allows_only_bridge_and_allowlisted_calls() {
+		assert!(BootstrappingFilter::contains(&grandpa_call()));
+		assert!(BootstrappingFilter::contains(&messages_call()));
+		assert!(BootstrappingFilter::contains(&system_call()));
+		assert!(!BootstrappingFilter::contains(&utility_call()));
+	}
+
+	#[test]
+	fn opened_filter_allows_all_calls() {
+		assert!(OpenedFilter::contains(&grandpa_call()));
+		assert!(OpenedFilter::contains(&messages_call()));
+		assert!(OpenedFilter::contains(&system_call()));
+		assert!(OpenedFilter::contains(&utility_call()));
+	}
+}
@@ -0,0 +1,212 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Dispatching bridged governance calls at this chain.
+//!
+//! The governance of the bridged chain may administer bridge pallets at this chain by sending
+//! XCM messages with `Transact` instructions. Such messages must be delivered over the
+//! designated governance lane. They are dispatched with the `BridgedGovernance` origin, that
+//! is selected by the runtime. It may be the `Root` origin or some custom origin, accepted by
+//! bridge pallets.
+//!
+//! Two components need to be configured in the runtime:
+//!
+//! - the [`BridgedGovernanceDispatch`] wrapper of the messages pallet `MessageDispatch`, which
+//!   marks messages, delivered over the governance lane;
+//!
+//! - the [`BridgedGovernanceOriginConverter`], that is a part of the XCM executor
+//!   `OriginConverter`. It converts the governance location of the bridged chain into the
+//!   `BridgedGovernance` origin, but only while marked messages are dispatched.
+
+use bp_messages::{
+	target_chain::{DispatchMessage, MessageDispatch},
+	LaneId,
+};
+use bp_runtime::messages::MessageDispatchResult;
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+use xcm::latest::{MultiLocation, OriginKind};
+use xcm_executor::traits::ConvertOrigin;
+
+environmental::environmental!(governance_lane: LaneId);
+
+/// Returns true if we are dispatching the message that has been delivered over the governance
+/// lane.
+pub fn is_dispatching_governance_message() -> bool {
+	governance_lane::with(|_| ()).is_some()
+}
+
+/// [`MessageDispatch`] wrapper that marks messages, delivered over the `GovernanceLane`.
+///
+/// The [`BridgedGovernanceOriginConverter`] only works for marked messages.
+pub struct BridgedGovernanceDispatch<Dispatch, GovernanceLane>(
+	PhantomData<(Dispatch, GovernanceLane)>,
+);
+
+impl<AccountId, Dispatch, GovernanceLane> MessageDispatch<AccountId>
+	for BridgedGovernanceDispatch<Dispatch, GovernanceLane>
+where
+	Dispatch: MessageDispatch<AccountId>,
+	GovernanceLane: Get<LaneId>,
+{
+	type DispatchPayload = Dispatch::DispatchPayload;
+	type DispatchLevelResult = Dispatch::DispatchLevelResult;
+
+	fn dispatch_weight(message: &mut DispatchMessage<Self::DispatchPayload>) -> Weight {
+		Dispatch::dispatch_weight(message)
+	}
+
+	fn dispatch(
+		relayer_account: &AccountId,
+		message: DispatchMessage<Self::DispatchPayload>,
+	) -> MessageDispatchResult<Self::DispatchLevelResult> {
+		let mut lane = message.key.lane_id;
+		if lane != GovernanceLane::get() {
+			return Dispatch::dispatch(relayer_account, message)
+		}
+
+		log::trace!(
+			target: crate::LOG_TARGET_BRIDGE_DISPATCH,
+			"Dispatching message {:?}/{} from the bridged governance lane",
+			lane,
+			message.key.nonce,
+		);
+		governance_lane::using(&mut lane, || Dispatch::dispatch(relayer_account, message))
+	}
+}
+
+/// XCM origin converter that converts the `GovernanceLocation` into the `BridgedGovernance`
+/// origin.
+///
+/// The conversion only happens for `Superuser` origin kind and only while dispatching messages
+/// that have been delivered over the governance lane (see [`BridgedGovernanceDispatch`]).
+pub struct BridgedGovernanceOriginConverter<GovernanceLocation, BridgedGovernance>(
+	PhantomData<(GovernanceLocation, BridgedGovernance)>,
+);
+
+impl<RuntimeOrigin, GovernanceLocation, BridgedGovernance> ConvertOrigin<RuntimeOrigin>
+	for BridgedGovernanceOriginConverter<GovernanceLocation, BridgedGovernance>
+where
+	GovernanceLocation: Get<MultiLocation>,
+	BridgedGovernance: Get<RuntimeOrigin>,
+{
+	fn convert_origin(
+		origin: impl Into<MultiLocation>,
+		kind: OriginKind,
+	) -> Result<RuntimeOrigin, MultiLocation> {
+		let origin = origin.into();
+		if kind != OriginKind::Superuser ||
+			origin != GovernanceLocation::get() ||
+			!is_dispatching_governance_message()
+		{
+			return Err(origin)
+		}
+
+		log::trace!(
+			target: crate::LOG_TARGET_BRIDGE_DISPATCH,
+			"Converting bridged governance location {:?} into the BridgedGovernance origin",
+			origin,
+		);
+		Ok(BridgedGovernance::get())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bp_messages::{target_chain::DispatchMessageData, MessageKey};
+	use frame_support::parameter_types;
+	use xcm::latest::prelude::*;
+
+	const GOVERNANCE_LANE: LaneId = LaneId([0, 0, 0, 1]);
+	const REGULAR_LANE: LaneId = LaneId([0, 0, 0, 2]);
+
+	parameter_types! {
+		pub const GovernanceLane: LaneId = GOVERNANCE_LANE;
+		pub const GovernanceLocation: MultiLocation =
+			MultiLocation { parents: 1, interior: X1(GlobalConsensus(Polkadot)) };
+		pub const GovernanceOrigin: u64 = 42;
+	}
+
+	type Converter = BridgedGovernanceOriginConverter<GovernanceLocation, GovernanceOrigin>;
+
+	/// Dispatch that converts the location from the payload into the origin.
+	struct TestDispatch;
+
+	impl MessageDispatch<u64> for TestDispatch {
+		type DispatchPayload = (MultiLocation, OriginKind);
+		type DispatchLevelResult = Option<u64>;
+
+		fn dispatch_weight(_message: &mut DispatchMessage<Self::DispatchPayload>) -> Weight {
+			Weight::from_parts(100, 0)
+		}
+
+		fn dispatch(
+			_: &u64,
+			message: DispatchMessage<Self::DispatchPayload>,
+		) -> MessageDispatchResult<Option<u64>> {
+			let (location, kind) = message.data.payload.unwrap();
+			MessageDispatchResult {
+				unspent_weight: Weight::zero(),
+				dispatch_level_result: Converter::convert_origin(location, kind).ok(),
+			}
+		}
+	}
+
+	type Dispatch = BridgedGovernanceDispatch<TestDispatch, GovernanceLane>;
+
+	fn dispatch(lane_id: LaneId, location: MultiLocation, kind: OriginKind) -> Option<u64> {
+		Dispatch::dispatch(
+			&0,
+			DispatchMessage {
+				key: MessageKey { lane_id, nonce: 1 },
+				data: DispatchMessageData { payload: Ok((location, kind)) },
+			},
+		)
+		.dispatch_level_result
+	}
+
+	#[test]
+	fn governance_location_is_converted_when_dispatching_governance_lane_message() {
+		assert_eq!(
+			dispatch(GOVERNANCE_LANE, GovernanceLocation::get(), OriginKind::Superuser),
+			Some(42),
+		);
+		assert!(!is_dispatching_governance_message());
+	}
+
+	#[test]
+	fn governance_location_is_not_converted_when_dispatching_regular_lane_message() {
+		assert_eq!(dispatch(REGULAR_LANE, GovernanceLocation::get(), OriginKind::Superuser), None);
+	}
+
+	#[test]
+	fn governance_location_is_not_converted_outside_of_message_dispatch() {
+		assert_eq!(
+			Converter::convert_origin(GovernanceLocation::get(), OriginKind::Superuser),
+			Err(GovernanceLocation::get()),
+		);
+	}
+
+	#[test]
+	fn other_locations_and_origin_kinds_are_not_converted() {
+		let other_location =
+			MultiLocation { parents: 1, interior: X2(GlobalConsensus(Polkadot), Parachain(1000)) };
+		assert_eq!(dispatch(GOVERNANCE_LANE, other_location, OriginKind::Superuser), None);
+		assert_eq!(dispatch(GOVERNANCE_LANE, GovernanceLocation::get(), OriginKind::Native), None);
+		assert_eq!(dispatch(GOVERNANCE_LANE, GovernanceLocation::get(), OriginKind::Xcm), None);
+	}
+}
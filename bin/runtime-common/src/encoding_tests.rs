@@ -0,0 +1,210 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Golden encoding vectors of structures that are crossing the bridge.
+//!
+//! Proofs and calls are constructed by relayers that may be built from a different version of
+//! this repository than the runtime that is receiving them. So any change of their encoding
+//! breaks live bridges. If some test here fails, then either the change has been unintended
+//! and must be reverted, or it is intended and requires coordinated upgrade of runtimes and
+//! relayers. Only in the latter case golden vectors may be updated.
+
+#![cfg(test)]
+
+use crate::{
+	messages::{
+		source::FromBridgedChainMessagesDeliveryProof, target::FromBridgedChainMessagesProof,
+	},
+	mock::BridgedChainHeader,
+};
+
+use bp_header_chain::{BridgeGrandpaCall, InitializationData};
+use bp_messages::{BridgeMessagesCall, LaneId, UnrewardedRelayersState};
+use bp_runtime::BasicOperatingMode;
+use codec::{Decode, Encode};
+use frame_support::weights::Weight;
+use hex_literal::hex;
+use sp_core::{ed25519, H256};
+use sp_runtime::{traits::Header as HeaderT, AccountId32};
+use sp_std::fmt::Debug;
+
+type TestMessagesCall = BridgeMessagesCall<
+	AccountId32,
+	FromBridgedChainMessagesProof<H256>,
+	FromBridgedChainMessagesDeliveryProof<H256>,
+>;
+
+/// Checks that the value is encoded into golden vector and the golden vector is decoded
+/// back into the same value.
+fn ensure_golden_encoding<T: Encode + Decode + Debug + PartialEq>(value: T, golden: &[&[u8]]) {
+	let golden = golden.concat();
+	assert_eq!(value.encode(), golden, "{value:?} encoding has changed");
+	assert_eq!(T::decode(&mut &golden[..]).unwrap(), value);
+}
+
+fn messages_proof() -> FromBridgedChainMessagesProof<H256> {
+	FromBridgedChainMessagesProof {
+		bridged_header_hash: H256::repeat_byte(0x01),
+		storage_proof: vec![vec![0x0a, 0x0b], vec![0x0c]],
+		lane: LaneId([0, 0, 0, 1]),
+		nonces_start: 1,
+		nonces_end: 3,
+	}
+}
+
+fn messages_delivery_proof() -> FromBridgedChainMessagesDeliveryProof<H256> {
+	FromBridgedChainMessagesDeliveryProof {
+		bridged_header_hash: H256::repeat_byte(0x02),
+		storage_proof: vec![vec![0x0a, 0x0b], vec![0x0c]],
+		lane: LaneId([0, 0, 0, 1]),
+	}
+}
+
+fn initialization_data() -> InitializationData<BridgedChainHeader> {
+	InitializationData {
+		header: Box::new(BridgedChainHeader::new(
+			42,
+			H256::repeat_byte(0x04),
+			H256::repeat_byte(0x05),
+			H256::repeat_byte(0x03),
+			Default::default(),
+		)),
+		authority_list: vec![(ed25519::Public::from_raw([0x06; 32]).into(), 1)],
+		set_id: 7,
+		operating_mode: BasicOperatingMode::Normal,
+	}
+}
+
+#[test]
+fn messages_proof_encoding_is_not_changed() {
+	ensure_golden_encoding(
+		messages_proof(),
+		&[
+			// bridged_header_hash
+			&[0x01; 32],
+			// storage_proof: two nodes
+			&hex!("08" "08" "0a0b" "04" "0c"),
+			// lane
+			&hex!("00000001"),
+			// nonces_start
+			&hex!("0100000000000000"),
+			// nonces_end
+			&hex!("0300000000000000"),
+		],
+	);
+}
+
+#[test]
+fn messages_delivery_proof_encoding_is_not_changed() {
+	ensure_golden_encoding(
+		messages_delivery_proof(),
+		&[
+			// bridged_header_hash
+			&[0x02; 32],
+			// storage_proof: two nodes
+			&hex!("08" "08" "0a0b" "04" "0c"),
+			// lane
+			&hex!("00000001"),
+		],
+	);
+}
+
+#[test]
+fn receive_messages_proof_call_encoding_is_not_changed() {
+	ensure_golden_encoding(
+		TestMessagesCall::receive_messages_proof {
+			relayer_id_at_bridged_chain: AccountId32::new([0x05; 32]),
+			proof: messages_proof(),
+			messages_count: 3,
+			dispatch_weight: Weight::from_parts(1_000_000_000, 65_536),
+		},
+		&[
+			// call index
+			&hex!("02"),
+			// relayer_id_at_bridged_chain
+			&[0x05; 32],
+			// proof
+			&messages_proof().encode(),
+			// messages_count
+			&hex!("03000000"),
+			// dispatch_weight: compact ref_time and compact proof_size
+			&hex!("02286bee" "02000400"),
+		],
+	);
+}
+
+#[test]
+fn receive_messages_delivery_proof_call_encoding_is_not_changed() {
+	ensure_golden_encoding(
+		TestMessagesCall::receive_messages_delivery_proof {
+			proof: messages_delivery_proof(),
+			relayers_state: UnrewardedRelayersState {
+				unrewarded_relayer_entries: 1,
+				messages_in_oldest_entry: 3,
+				total_messages: 3,
+				last_delivered_nonce: 3,
+			},
+		},
+		&[
+			// call index
+			&hex!("03"),
+			// proof
+			&messages_delivery_proof().encode(),
+			// relayers_state
+			&hex!("0100000000000000" "0300000000000000" "0300000000000000" "0300000000000000"),
+		],
+	);
+}
+
+#[test]
+fn initialization_data_encoding_is_not_changed() {
+	ensure_golden_encoding(
+		initialization_data(),
+		&[
+			// header.parent_hash
+			&[0x03; 32],
+			// header.number (compact)
+			&hex!("a8"),
+			// header.state_root
+			&[0x05; 32],
+			// header.extrinsics_root
+			&[0x04; 32],
+			// header.digest: no items
+			&hex!("00"),
+			// authority_list: single authority
+			&hex!("04"),
+			&[0x06; 32],
+			&hex!("0100000000000000"),
+			// set_id
+			&hex!("0700000000000000"),
+			// operating_mode
+			&hex!("00"),
+		],
+	);
+}
+
+#[test]
+fn initialize_call_encoding_is_not_changed() {
+	ensure_golden_encoding(
+		BridgeGrandpaCall::<BridgedChainHeader>::initialize { init_data: initialization_data() },
+		&[
+			// call index
+			&hex!("01"),
+			// init_data
+			&initialization_data().encode(),
+		],
+	);
+}
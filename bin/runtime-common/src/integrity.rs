@@ -19,14 +19,22 @@
 //! Most of the tests in this module assume that the bridge is using standard (see `crate::messages`
 //! module for details) configuration.
 
-use crate::{messages, messages::MessageBridge};
+use crate::{
+	messages,
+	messages::MessageBridge,
+	messages_xcm_extension::{is_trusted_bridged_origin, TrustedBridgedOrigin},
+};
 
-use bp_messages::{InboundLaneData, MessageNonce};
+use bp_messages::{InboundLaneData, LaneId, MessageNonce};
 use bp_runtime::{Chain, ChainId};
 use codec::Encode;
-use frame_support::{storage::generator::StorageValue, traits::Get};
+use frame_support::{
+	storage::generator::StorageValue,
+	traits::{Contains, Get},
+};
 use frame_system::limits;
 use sp_runtime::traits::SignedExtension;
+use xcm::latest::{Junction, MultiLocation, NetworkId};
 
 /// Macro that ensures that the runtime configuration and chain primitives crate are sharing
 /// the same types (index, block number, hash, hasher, account id and header).
@@ -288,6 +296,62 @@ where
 	assert_bridge_pallet_names::<B, R, GI, MI>(params.pallet_names);
 }
 
+/// Parameters for asserting trusted bridged origins.
+#[derive(Debug)]
+pub struct AssertTrustedBridgedOrigins<'a> {
+	/// Location that is dispatching bridged messages at this chain.
+	pub bridge_location: MultiLocation,
+	/// Network of the bridged consensus system.
+	pub bridged_chain_network: NetworkId,
+	/// Bridged origins that are trusted by this chain.
+	pub this_chain_trusted_origins: &'a [TrustedBridgedOrigin],
+	/// Lanes that the bridged chain is using to exchange XCM messages with this chain. This
+	/// should be the hardcoded value from the bridged chain primitives crate.
+	pub bridged_chain_lanes: &'a [LaneId],
+}
+
+/// Test that this chain is trusting exactly the lanes that the bridged chain is using to exchange
+/// XCM messages with this chain and that the universal aliases are matching trusted origins.
+pub fn assert_trusted_bridged_origins<UniversalAliases>(params: AssertTrustedBridgedOrigins)
+where
+	UniversalAliases: Contains<(MultiLocation, Junction)>,
+{
+	for lane in params.bridged_chain_lanes {
+		assert!(
+			is_trusted_bridged_origin(
+				params.this_chain_trusted_origins,
+				params.bridged_chain_network,
+				*lane,
+			),
+			"Lane {:?} that is used by the bridged chain is not trusted for {:?} network",
+			lane,
+			params.bridged_chain_network,
+		);
+	}
+	for (network, lane) in params.this_chain_trusted_origins {
+		if *network != params.bridged_chain_network {
+			continue
+		}
+
+		assert!(
+			params.bridged_chain_lanes.contains(lane),
+			"Lane {:?} is trusted for {:?} network, but it is not used by the bridged chain",
+			lane,
+			network,
+		);
+	}
+
+	assert!(
+		UniversalAliases::contains(&(
+			params.bridge_location,
+			Junction::GlobalConsensus(params.bridged_chain_network),
+		)),
+		"Universal aliases are not allowing {:?} to alias into {:?} network",
+		params.bridge_location,
+		params.bridged_chain_network,
+	);
+}
+
 /// Check that the message lane weights are correct.
 pub fn check_message_lane_weights<C: Chain, T: frame_system::Config>(
 	bridged_chain_extra_storage_proof_size: u32,
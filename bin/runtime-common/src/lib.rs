@@ -24,6 +24,8 @@ use pallet_bridge_parachains::CallSubType as ParachainsCallSubtype;
 use sp_runtime::transaction_validity::TransactionValidity;
 use xcm::v3::NetworkId;
 
+pub mod bootstrap_call_filter;
+pub mod bridged_governance;
 pub mod messages;
 pub mod messages_api;
 pub mod messages_benchmarking;
@@ -32,11 +34,14 @@ pub mod messages_xcm_extension;
 pub mod parachains_benchmarking;
 pub mod refund_relayer_extension;
 
+mod encoding_tests;
 mod messages_generation;
 mod mock;
 
 #[cfg(feature = "integrity-test")]
 pub mod integrity;
+#[cfg(feature = "migration-test")]
+pub mod migration_test;
 
 const LOG_TARGET_BRIDGE_DISPATCH: &str = "runtime::bridge-dispatch";
 
@@ -34,12 +34,12 @@ use bp_runtime::{
 	messages::MessageDispatchResult, Chain, ChainId, RawStorageProof, Size, StorageProofChecker,
 	StorageProofError,
 };
-use codec::{Decode, DecodeLimit, Encode};
+use codec::{Decode, Encode};
 use frame_support::{traits::Get, weights::Weight, RuntimeDebug};
 use hash_db::Hasher;
 use scale_info::TypeInfo;
 use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, vec::Vec};
-use xcm::latest::prelude::*;
+use xcm::{latest::prelude::*, IdentifyVersion, VersionedMultiLocation, WrapVersion};
 
 /// Bidirectional message bridge.
 pub trait MessageBridge {
@@ -58,6 +58,33 @@ pub trait MessageBridge {
 	type BridgedChain: BridgedChainWithMessages;
 	/// Bridged header chain.
 	type BridgedHeaderChain: HeaderChain<UnderlyingChainOf<Self::BridgedChain>>;
+
+	/// Limits of XCM programs that are delivered from the Bridged chain.
+	///
+	/// Messages that are exceeding these limits are rejected when the messages proof is
+	/// verified, so they're never dispatched.
+	const INBOUND_XCM_LIMITS: InboundXcmLimits = InboundXcmLimits::DEFAULT;
+}
+
+/// Limits of XCM programs that are delivered from the Bridged chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InboundXcmLimits {
+	/// Maximal number of instructions in the program, including instructions of nested programs.
+	pub max_instructions: u32,
+	/// Maximal nesting depth of the encoded program.
+	pub max_depth: u32,
+	/// Maximal size of the encoded program.
+	pub max_encoded_size: u32,
+}
+
+impl InboundXcmLimits {
+	/// Default limits. The only limit is the `MAX_EXTRINSIC_DEPTH`, which is also used when the
+	/// program is decoded before dispatch.
+	pub const DEFAULT: InboundXcmLimits = InboundXcmLimits {
+		max_instructions: u32::MAX,
+		max_depth: sp_api::MAX_EXTRINSIC_DEPTH,
+		max_encoded_size: u32::MAX,
+	};
 }
 
 /// This chain that has `pallet-bridge-messages` module.
@@ -121,11 +148,15 @@ pub enum Error {
 	OutboundLaneStorage(StorageProofError),
 	/// Storage proof related error.
 	StorageProof(StorageProofError),
+	/// The XCM program exceeds inbound XCM limits of the bridge.
+	XcmLimitsExceeded,
 }
 
 /// Sub-module that is declaring types required for processing This -> Bridged chain messages.
 pub mod source {
 	use super::*;
+	use crate::messages_xcm_extension::PendingBridgedQueries;
+	use sp_runtime::{traits::Zero, FixedPointNumber, FixedU128};
 
 	/// Message payload for This -> Bridged chain messages.
 	pub type FromThisChainMessagePayload = Vec<u8>;
@@ -326,6 +357,18 @@ pub mod source {
 			OriginOf<ThisChain<Self::MessageBridge>>,
 			FromThisChainMessagePayload,
 		>;
+		/// Converter of the delivery fee into the asset that is used to pay for delivery.
+		type DeliveryFeeConverter: DeliveryFeeConverter;
+		/// Wrapper of XCM messages into the version, supported by the bridged destination.
+		///
+		/// Usually it is the `pallet_xcm`, which stores XCM versions of remote destinations,
+		/// learned via version negotiation messages that are delivered over the bridge.
+		type DestinationVersion: WrapVersion;
+		/// Queries of bridged XCM programs that are expecting responses from this chain.
+		///
+		/// Responses to these queries are sent over the lane that has delivered the program,
+		/// even if the destination of the response is not verified by the `verify_destination`.
+		type PendingQueries: PendingBridgedQueries;
 
 		/// Our location within the Consensus Universe.
 		fn universal_location() -> InteriorMultiLocation;
@@ -337,41 +380,168 @@ pub mod source {
 		fn xcm_lane() -> LaneId;
 	}
 
+	/// Base fee (in this chain's native tokens) that is paid for delivering every XCM message.
+	///
+	/// Let's just take fixed (out of thin air) fee per message in our test bridges (this code
+	/// won't be used in production anyway).
+	pub const BASE_XCM_DELIVERY_FEE: u128 = 1_000_000;
+
+	/// Converter of the XCM message delivery fee from this chain's native tokens into the asset
+	/// that is used to pay for delivery.
+	pub trait DeliveryFeeConverter {
+		/// Convert delivery fee, computed in this chain's native tokens, into the fee asset.
+		///
+		/// Returns `None` if the fee can't be converted.
+		fn convert_delivery_fee(native_fee: u128) -> Option<MultiAsset>;
+	}
+
+	/// Delivery fee is paid in this chain's native tokens.
+	pub struct PayInNativeTokens;
+
+	impl DeliveryFeeConverter for PayInNativeTokens {
+		fn convert_delivery_fee(native_fee: u128) -> Option<MultiAsset> {
+			Some((Here, native_fee).into())
+		}
+	}
+
+	/// Delivery fee is paid in the `FeeAsset` (e.g. bridged chain token or some stablecoin).
+	///
+	/// The `ConversionRate` is the number of `FeeAsset` units that are paid for a single unit of
+	/// this chain's native token. It may be a constant, a storage value that is updated by the
+	/// governance or something more sophisticated, like a price oracle.
+	pub struct PayInRemoteAsset<FeeAsset, ConversionRate>(PhantomData<(FeeAsset, ConversionRate)>);
+
+	impl<FeeAsset, ConversionRate> DeliveryFeeConverter for PayInRemoteAsset<FeeAsset, ConversionRate>
+	where
+		FeeAsset: Get<AssetId>,
+		ConversionRate: Get<FixedU128>,
+	{
+		fn convert_delivery_fee(native_fee: u128) -> Option<MultiAsset> {
+			let conversion_rate = ConversionRate::get();
+			// we don't want to deliver messages for free
+			if conversion_rate.is_zero() {
+				return None
+			}
+
+			let fee = conversion_rate.checked_mul_int(native_fee)?;
+			Some(MultiAsset { id: FeeAsset::get(), fun: Fungible(fee) })
+		}
+	}
+
+	/// Error of the `Transact` over the bridge.
+	#[derive(Debug, PartialEq, Eq)]
+	pub enum TransactError {
+		/// The declared weight of the call exceeds maximal dispatch weight at the bridged chain,
+		/// so the call can never be executed there.
+		CallTooHeavy {
+			/// The weight, declared by the caller.
+			declared: Weight,
+			/// Maximal dispatch weight at the bridged chain.
+			maximal: Weight,
+		},
+		/// Failed to send the XCM program over the bridge.
+		Send(SendError),
+	}
+
+	/// Return maximal weight of the call that may be dispatched at the bridged chain.
+	pub fn maximal_bridged_call_weight<B: MessageBridge>() -> Weight {
+		let maximal_extrinsic_weight = UnderlyingChainOf::<BridgedChain<B>>::max_extrinsic_weight();
+		crate::messages::target::maximal_incoming_message_dispatch_weight(maximal_extrinsic_weight)
+	}
+
+	/// Build XCM program that dispatches encoded call at the bridged chain.
+	///
+	/// The `require_weight_at_most` is verified against the maximal dispatch weight at the bridged
+	/// chain, so calls that could never be executed there are rejected.
+	pub fn build_transact_xcm<B: MessageBridge>(
+		origin_kind: OriginKind,
+		require_weight_at_most: Weight,
+		call: Vec<u8>,
+	) -> Result<Xcm<()>, TransactError> {
+		let maximal = maximal_bridged_call_weight::<B>();
+		if require_weight_at_most.any_gt(maximal) {
+			return Err(TransactError::CallTooHeavy { declared: require_weight_at_most, maximal })
+		}
+
+		Ok(Xcm(vec![Transact { origin_kind, require_weight_at_most, call: call.into() }]))
+	}
+
+	/// Send XCM program that dispatches encoded call at the bridged destination.
+	///
+	/// The program is built by the [`build_transact_xcm`] and sent using the
+	/// [`XcmBridgeAdapter`]. Returns hash of the sent message.
+	pub fn transact_over_bridge<T: XcmBridge>(
+		dest: MultiLocation,
+		origin_kind: OriginKind,
+		require_weight_at_most: Weight,
+		call: Vec<u8>,
+	) -> Result<XcmHash, TransactError>
+	where
+		OriginOf<ThisChain<T::MessageBridge>>: From<pallet_xcm::Origin>,
+	{
+		let xcm =
+			build_transact_xcm::<T::MessageBridge>(origin_kind, require_weight_at_most, call)?;
+		send_xcm::<XcmBridgeAdapter<T>>(dest, xcm)
+			.map(|(hash, _)| hash)
+			.map_err(TransactError::Send)
+	}
+
+	/// Returns identifier of the query, if given message is the response to this query.
+	fn response_query_id(msg: &Xcm<()>) -> Option<QueryId> {
+		msg.inner().iter().find_map(|instruction| match instruction {
+			QueryResponse { query_id, .. } => Some(*query_id),
+			_ => None,
+		})
+	}
+
 	/// XCM bridge adapter for `bridge-messages` pallet.
 	pub struct XcmBridgeAdapter<T>(PhantomData<T>);
 
 	impl<T: XcmBridge> SendXcm for XcmBridgeAdapter<T>
 	where
-		BalanceOf<ThisChain<T::MessageBridge>>: Into<Fungibility>,
 		OriginOf<ThisChain<T::MessageBridge>>: From<pallet_xcm::Origin>,
 	{
-		type Ticket = FromThisChainMessagePayload;
+		type Ticket = (LaneId, FromThisChainMessagePayload);
 
 		fn validate(
 			dest: &mut Option<MultiLocation>,
 			msg: &mut Option<Xcm<()>>,
 		) -> SendResult<Self::Ticket> {
 			let d = dest.take().ok_or(SendError::MissingArgument)?;
-			if !T::verify_destination(&d) {
-				*dest = Some(d);
-				return Err(SendError::NotApplicable)
-			}
-
-			let route = T::build_destination();
-			let msg = (route, msg.take().ok_or(SendError::MissingArgument)?).encode();
-
-			// let's just take fixed (out of thin air) fee per message in our test bridges
-			// (this code won't be used in production anyway)
-			let fee_assets = MultiAssets::from((Here, 1_000_000_u128));
+			let pending_query_lane = msg
+				.as_ref()
+				.and_then(response_query_id)
+				.and_then(|query_id| T::PendingQueries::pending_query_lane(&d, query_id));
+			let lane = match pending_query_lane {
+				Some(lane) => lane,
+				None if T::verify_destination(&d) => T::xcm_lane(),
+				None => {
+					*dest = Some(d);
+					return Err(SendError::NotApplicable)
+				},
+			};
 
-			Ok((msg, fee_assets))
+			// message and route are encoded using XCM version of the destination, so upgrading
+			// XCM version at one side of the bridge doesn't break the other side
+			let msg = msg.take().ok_or(SendError::MissingArgument)?;
+			let versioned_msg = T::DestinationVersion::wrap_version(&d, msg)
+				.map_err(|_| SendError::DestinationUnsupported)?;
+			let versioned_route = VersionedMultiLocation::from(T::build_destination())
+				.into_version(versioned_msg.identify_version())
+				.map_err(|_| SendError::DestinationUnsupported)?;
+			let msg = (versioned_route, versioned_msg).encode();
+
+			let fee_asset = T::DeliveryFeeConverter::convert_delivery_fee(BASE_XCM_DELIVERY_FEE)
+				.ok_or(SendError::Fees)?;
+			let fee_assets = MultiAssets::from(fee_asset);
+
+			Ok(((lane, msg), fee_assets))
 		}
 
 		fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
 			use bp_messages::source_chain::MessagesBridge;
 
-			let lane = T::xcm_lane();
-			let msg = ticket;
+			let (lane, msg) = ticket;
 			let result = T::MessageSender::send_message(
 				pallet_xcm::Origin::from(MultiLocation::from(T::universal_location())).into(),
 				lane,
@@ -408,36 +578,7 @@ pub mod source {
 pub mod target {
 	use super::*;
 
-	/// Decoded Bridged -> This message payload.
-	#[derive(RuntimeDebug, PartialEq, Eq)]
-	pub struct FromBridgedChainMessagePayload<Call> {
-		/// Data that is actually sent over the wire.
-		pub xcm: (xcm::v3::MultiLocation, xcm::v3::Xcm<Call>),
-		/// Weight of the message, computed by the weigher. Unknown initially.
-		pub weight: Option<Weight>,
-	}
-
-	impl<Call: Decode> Decode for FromBridgedChainMessagePayload<Call> {
-		fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
-			let _: codec::Compact<u32> = Decode::decode(input)?;
-			type XcmPairType<Call> = (xcm::v3::MultiLocation, xcm::v3::Xcm<Call>);
-			Ok(FromBridgedChainMessagePayload {
-				xcm: XcmPairType::<Call>::decode_with_depth_limit(
-					sp_api::MAX_EXTRINSIC_DEPTH,
-					input,
-				)?,
-				weight: None,
-			})
-		}
-	}
-
-	impl<Call> From<(xcm::v3::MultiLocation, xcm::v3::Xcm<Call>)>
-		for FromBridgedChainMessagePayload<Call>
-	{
-		fn from(xcm: (xcm::v3::MultiLocation, xcm::v3::Xcm<Call>)) -> Self {
-			FromBridgedChainMessagePayload { xcm, weight: None }
-		}
-	}
+	pub use pallet_xcm_bridge_dispatcher::FromBridgedChainMessagePayload;
 
 	/// Messages proof from bridged chain:
 	///
@@ -471,6 +612,10 @@ pub mod target {
 	}
 
 	/// Dispatching Bridged -> This chain messages.
+	///
+	/// This dispatcher is stateless. It shares weighing and execution of XCM programs with the
+	/// `pallet-xcm-bridge-dispatcher`. If dispatch events, per-lane dispatch queues or pausing
+	/// dispatch are required, please use the pallet instead.
 	#[derive(RuntimeDebug, Clone, Copy)]
 	pub struct FromBridgedChainMessageDispatch<B, XcmExecutor, XcmWeigher, WeightCredit> {
 		_marker: PhantomData<(B, XcmExecutor, XcmWeigher, WeightCredit)>,
@@ -490,30 +635,7 @@ pub mod target {
 		fn dispatch_weight(
 			message: &mut DispatchMessage<Self::DispatchPayload>,
 		) -> frame_support::weights::Weight {
-			match message.data.payload {
-				Ok(ref mut payload) => {
-					// I have no idea why this method takes `&mut` reference and there's nothing
-					// about that in documentation. Hope it'll only mutate iff error is returned.
-					let weight = XcmWeigher::weight(&mut payload.xcm.1);
-					let weight = weight.unwrap_or_else(|e| {
-						log::debug!(
-							target: crate::LOG_TARGET_BRIDGE_DISPATCH,
-							"Failed to compute dispatch weight of incoming XCM message {:?}/{}: {:?}",
-							message.key.lane_id,
-							message.key.nonce,
-							e,
-						);
-
-						// we shall return 0 and then the XCM executor will fail to execute XCM
-						// if we'll return something else (e.g. maximal value), the lane may stuck
-						Weight::zero()
-					});
-
-					payload.weight = Some(weight);
-					weight
-				},
-				_ => Weight::zero(),
-			}
+			pallet_xcm_bridge_dispatcher::weigh_xcm_message::<_, XcmWeigher>(message)
 		}
 
 		fn dispatch(
@@ -524,49 +646,31 @@ pub mod target {
 			let do_dispatch = move || -> sp_std::result::Result<Outcome, codec::Error> {
 				let FromBridgedChainMessagePayload { xcm: (location, xcm), weight: weight_limit } =
 					message.data.payload?;
-				log::trace!(
-					target: crate::LOG_TARGET_BRIDGE_DISPATCH,
-					"Going to execute message {:?} (weight limit: {:?}): {:?} {:?}",
-					message_id,
-					weight_limit,
-					location,
-					xcm,
-				);
-				let hash = message_id.using_encoded(sp_io::hashing::blake2_256);
-
 				// if this cod will end up in production, this most likely needs to be set to zero
 				let weight_credit = WeightCredit::get();
 
-				let xcm_outcome = XcmExecutor::execute_xcm_in_credit(
+				Ok(pallet_xcm_bridge_dispatcher::execute_xcm_message::<_, XcmExecutor>(
+					message.key.lane_id,
+					message.key.nonce,
 					location,
 					xcm,
-					hash,
 					weight_limit.unwrap_or_else(Weight::zero),
 					weight_credit,
-				);
-				Ok(xcm_outcome)
+				))
 			};
 
 			let xcm_outcome = do_dispatch();
 			match xcm_outcome {
-				Ok(outcome) => {
-					log::trace!(
-						target: crate::LOG_TARGET_BRIDGE_DISPATCH,
-						"Incoming message {:?} dispatched with result: {:?}",
-						message_id,
-						outcome,
-					);
-					match outcome.ensure_execution() {
-						Ok(_weight) => (),
-						Err(e) => {
-							log::error!(
-								target: crate::LOG_TARGET_BRIDGE_DISPATCH,
-								"Incoming message {:?} was not dispatched, error: {:?}",
-								message_id,
-								e,
-							);
-						},
-					}
+				Ok(outcome) => match outcome.ensure_execution() {
+					Ok(_weight) => (),
+					Err(e) => {
+						log::error!(
+							target: crate::LOG_TARGET_BRIDGE_DISPATCH,
+							"Incoming message {:?} was not dispatched, error: {:?}",
+							message_id,
+							e,
+						);
+					},
 				},
 				Err(e) => {
 					log::error!(
@@ -657,6 +761,7 @@ pub mod target {
 				for nonce in nonces_start..=nonces_end {
 					let message_key = MessageKey { lane_id: lane, nonce };
 					let message_payload = parser.read_and_decode_message_payload(&message_key)?;
+					ensure_inbound_xcm_limits::<B>(&message_payload)?;
 					messages.push(Message { key: message_key, payload: message_payload });
 				}
 
@@ -687,6 +792,45 @@ pub mod target {
 		.map_err(Error::HeaderChain)?
 	}
 
+	/// Ensure that the XCM program of the inbound message doesn't exceed inbound XCM limits of
+	/// the bridge.
+	///
+	/// Payloads that can't be decoded even without these limits are not rejected here. They are
+	/// delivered and their dispatch fails.
+	pub fn ensure_inbound_xcm_limits<B: MessageBridge>(payload: &[u8]) -> Result<(), Error> {
+		let limits = B::INBOUND_XCM_LIMITS;
+		if payload.len() > limits.max_encoded_size as usize {
+			return Err(Error::XcmLimitsExceeded)
+		}
+
+		type PayloadOf<B> = FromBridgedChainMessagePayload<CallOf<ThisChain<B>>>;
+		if PayloadOf::<B>::decode(&mut &payload[..]).is_err() {
+			return Ok(())
+		}
+
+		let payload =
+			PayloadOf::<B>::decode_with_nesting_limit(limits.max_depth, &mut &payload[..])
+				.map_err(|_| Error::XcmLimitsExceeded)?;
+		if count_instructions(&payload.xcm.1) > limits.max_instructions {
+			return Err(Error::XcmLimitsExceeded)
+		}
+
+		Ok(())
+	}
+
+	/// Returns number of instructions in the XCM program, including instructions of nested
+	/// programs.
+	fn count_instructions<Call>(xcm: &xcm::v3::Xcm<Call>) -> u32 {
+		xcm.inner().iter().fold(0u32, |count, instruction| {
+			let nested_count = match instruction {
+				xcm::v3::Instruction::SetErrorHandler(nested) |
+				xcm::v3::Instruction::SetAppendix(nested) => count_instructions(nested),
+				_ => 0,
+			};
+			count.saturating_add(1).saturating_add(nested_count)
+		})
+	}
+
 	struct StorageProofCheckerAdapter<H: Hasher, B> {
 		storage: StorageProofChecker<H>,
 		_dummy: sp_std::marker::PhantomData<B>,
@@ -734,8 +878,10 @@ pub type BridgeMessagesCallOf<C> = bp_messages::BridgeMessagesCall<
 mod tests {
 	use super::*;
 	use crate::{
+		messages::source::DeliveryFeeConverter,
 		messages_generation::{
 			encode_all_messages, encode_lane_data, prepare_messages_storage_proof,
+			UnrelatedTrieEntries,
 		},
 		mock::*,
 	};
@@ -744,6 +890,7 @@ mod tests {
 	use codec::Encode;
 	use sp_core::H256;
 	use sp_runtime::traits::Header as _;
+	use xcm::VersionedXcm;
 
 	fn test_lane_outbound_data() -> OutboundLaneData {
 		OutboundLaneData::default()
@@ -840,6 +987,7 @@ mod tests {
 			vec![42],
 			encode_message,
 			encode_outbound_lane_data,
+			None,
 		);
 
 		sp_io::TestExternalities::new(Default::default()).execute_with(move || {
@@ -1020,6 +1168,48 @@ mod tests {
 		);
 	}
 
+	fn xcm_message_payload(xcm: Xcm<()>) -> MessagePayload {
+		let route = VersionedMultiLocation::from(MultiLocation::here());
+		(route, VersionedXcm::from(xcm)).encode().encode()
+	}
+
+	fn verify_xcm_messages_proof(
+		payload: MessagePayload,
+	) -> Result<ProvedMessages<Message>, Error> {
+		using_messages_proof(
+			1,
+			None,
+			|_, _| Some(payload.encode()),
+			encode_lane_data,
+			|proof| target::verify_messages_proof::<OnThisChainBridge>(proof, 1),
+		)
+	}
+
+	#[test]
+	fn message_proof_is_accepted_if_xcm_is_within_limits() {
+		assert!(verify_xcm_messages_proof(xcm_message_payload(Xcm(vec![
+			ClearOrigin,
+			SetAppendix(Xcm(vec![ClearOrigin, ClearOrigin])),
+		])))
+		.is_ok());
+	}
+
+	#[test]
+	fn message_proof_is_rejected_if_xcm_has_too_many_instructions() {
+		assert_eq!(
+			verify_xcm_messages_proof(xcm_message_payload(Xcm(vec![
+				ClearOrigin,
+				SetAppendix(Xcm(vec![ClearOrigin, ClearOrigin, ClearOrigin])),
+			]))),
+			Err(Error::XcmLimitsExceeded),
+		);
+	}
+
+	#[test]
+	fn message_proof_is_rejected_if_xcm_is_too_large() {
+		assert_eq!(verify_xcm_messages_proof(vec![0; 2048]), Err(Error::XcmLimitsExceeded));
+	}
+
 	#[test]
 	fn non_empty_message_proof_without_messages_is_accepted() {
 		assert_eq!(
@@ -1093,4 +1283,219 @@ mod tests {
 			Err(Error::MessagesCountMismatch),
 		);
 	}
+
+	#[test]
+	fn delivery_fee_is_paid_in_native_tokens() {
+		assert_eq!(
+			source::PayInNativeTokens::convert_delivery_fee(source::BASE_XCM_DELIVERY_FEE),
+			Some((Here, source::BASE_XCM_DELIVERY_FEE).into()),
+		);
+	}
+
+	#[test]
+	fn delivery_fee_is_converted_to_remote_asset() {
+		use sp_runtime::FixedU128;
+
+		frame_support::parameter_types! {
+			pub FeeAsset: AssetId = Concrete(MultiLocation::new(2, X1(GlobalConsensus(Wococo))));
+			pub HalfRate: FixedU128 = FixedU128::from_rational(1, 2);
+			pub ZeroRate: FixedU128 = FixedU128::from_u32(0);
+		}
+
+		assert_eq!(
+			source::PayInRemoteAsset::<FeeAsset, HalfRate>::convert_delivery_fee(1_000_000),
+			Some(MultiAsset { id: FeeAsset::get(), fun: Fungible(500_000) }),
+		);
+		assert_eq!(
+			source::PayInRemoteAsset::<FeeAsset, ZeroRate>::convert_delivery_fee(1_000_000),
+			None,
+		);
+	}
+
+	#[test]
+	fn message_payload_of_older_xcm_version_is_decoded() {
+		let location = xcm::v2::MultiLocation::new(
+			1,
+			xcm::v2::Junctions::X1(xcm::v2::Junction::Parachain(1000)),
+		);
+		let xcm = xcm::v2::Xcm::<()>(vec![xcm::v2::Instruction::Trap(42)]);
+		let payload =
+			(VersionedMultiLocation::V2(location), VersionedXcm::V2(xcm)).encode().encode();
+
+		assert_eq!(
+			target::FromBridgedChainMessagePayload::<()>::decode(&mut &payload[..]),
+			Ok((
+				MultiLocation::new(1, X1(Parachain(1000))),
+				xcm::v3::Xcm::<()>(vec![Instruction::Trap(42)]),
+			)
+				.into()),
+		);
+	}
+
+	#[test]
+	fn transact_xcm_is_built_if_call_weight_is_within_limits() {
+		let maximal = source::maximal_bridged_call_weight::<OnThisChainBridge>();
+		assert_eq!(
+			source::build_transact_xcm::<OnThisChainBridge>(
+				OriginKind::SovereignAccount,
+				maximal,
+				vec![42],
+			),
+			Ok(Xcm(vec![Transact {
+				origin_kind: OriginKind::SovereignAccount,
+				require_weight_at_most: maximal,
+				call: vec![42].into(),
+			}])),
+		);
+	}
+
+	#[test]
+	fn transact_xcm_is_rejected_if_call_is_too_heavy() {
+		let maximal = source::maximal_bridged_call_weight::<OnThisChainBridge>();
+		let declared = maximal.saturating_add(Weight::from_parts(1, 0));
+		assert_eq!(
+			source::build_transact_xcm::<OnThisChainBridge>(
+				OriginKind::SovereignAccount,
+				declared,
+				vec![42],
+			),
+			Err(source::TransactError::CallTooHeavy { declared, maximal }),
+		);
+	}
+
+	#[test]
+	fn messages_proof_with_unrelated_trie_entries_only_contains_required_nodes() {
+		let prepare_proof = |unrelated_entries| {
+			prepare_messages_storage_proof::<OnThisChainBridge>(
+				TEST_LANE_ID,
+				1..=1,
+				None,
+				bp_runtime::StorageProofSize::Minimal(0),
+				vec![42],
+				encode_all_messages,
+				encode_lane_data,
+				unrelated_entries,
+			)
+		};
+		let (_, minimal_proof) = prepare_proof(None);
+		let (state_root, storage_proof) =
+			prepare_proof(Some(UnrelatedTrieEntries { count: 1024, value_size: 64 }));
+		assert!(storage_proof.len() > minimal_proof.len());
+
+		let mut checker =
+			StorageProofChecker::<BridgedChainHasher>::new(state_root, storage_proof).unwrap();
+		let message_key = bp_messages::storage_keys::message_key(
+			OnThisChainBridge::BRIDGED_MESSAGES_PALLET_NAME,
+			&TEST_LANE_ID,
+			1,
+		);
+		assert_eq!(checker.read_value(&message_key.0), Ok(Some(vec![42].encode())));
+		assert_eq!(checker.ensure_no_unused_nodes(), Ok(()));
+	}
+
+	proptest::proptest! {
+		#[test]
+		fn maximal_message_is_accepted_and_larger_message_is_rejected(
+			max_extrinsic_size in 0u32..65_536,
+		) {
+			let params = MockChainParams {
+				max_extrinsic_size,
+				min_message_dispatch_weight: 0,
+				max_message_dispatch_weight: usize::MAX,
+				..Default::default()
+			};
+			with_mock_chain_params(params, || {
+				let maximal_size = source::maximal_message_size::<OnThisChainBridge>() as usize;
+				proptest::prop_assert_eq!(
+					source::verify_chain_message::<OnThisChainBridge>(&vec![0; maximal_size]),
+					Ok(()),
+				);
+				proptest::prop_assert_eq!(
+					source::verify_chain_message::<OnThisChainBridge>(&vec![0; maximal_size + 1]),
+					Err(Error::MessageTooLarge),
+				);
+				Ok(())
+			})?;
+		}
+
+		#[test]
+		fn message_dispatch_weight_is_verified(
+			min_message_dispatch_weight in 0usize..128,
+			max_message_dispatch_weight in 128usize..1024,
+			message_size in 0usize..2048,
+		) {
+			let params = MockChainParams {
+				max_extrinsic_size: u32::MAX,
+				min_message_dispatch_weight,
+				max_message_dispatch_weight,
+				..Default::default()
+			};
+			with_mock_chain_params(params, || {
+				let expected_result = if message_size < min_message_dispatch_weight ||
+					message_size > max_message_dispatch_weight
+				{
+					Err(Error::InvalidMessageWeight)
+				} else {
+					Ok(())
+				};
+				proptest::prop_assert_eq!(
+					source::verify_chain_message::<OnThisChainBridge>(&vec![0; message_size]),
+					expected_result,
+				);
+				Ok(())
+			})?;
+		}
+
+		#[test]
+		fn pending_messages_limit_is_respected(
+			max_pending_messages in 0u64..1024,
+			pending_messages in 0u64..2048,
+			latest_received_nonce in 0u64..1_000_000,
+		) {
+			let params = MockChainParams { max_pending_messages, ..Default::default() };
+			with_mock_chain_params(params, || {
+				let expected_result = if pending_messages > max_pending_messages {
+					Err(source::TOO_MANY_PENDING_MESSAGES)
+				} else {
+					Ok(())
+				};
+				proptest::prop_assert_eq!(
+					source::FromThisChainMessageVerifier::<OnThisChainBridge>::verify_message(
+						&frame_system::RawOrigin::Root.into(),
+						&TEST_LANE_ID,
+						&OutboundLaneData {
+							latest_received_nonce,
+							latest_generated_nonce: latest_received_nonce + pending_messages,
+							..Default::default()
+						},
+						&regular_outbound_message_payload(),
+					),
+					expected_result,
+				);
+				Ok(())
+			})?;
+		}
+
+		#[test]
+		fn transact_call_weight_is_verified(
+			max_extrinsic_ref_time in 0u64..u64::MAX / 2,
+			declared_ref_time in 0u64..u64::MAX / 2,
+		) {
+			let params = MockChainParams {
+				max_extrinsic_weight: Weight::from_parts(max_extrinsic_ref_time, u64::MAX),
+				..Default::default()
+			};
+			with_mock_chain_params(params, || {
+				let maximal = source::maximal_bridged_call_weight::<OnThisChainBridge>();
+				let declared = Weight::from_parts(declared_ref_time, 0);
+				let result = source::build_transact_xcm::<OnThisChainBridge>(
+					OriginKind::SovereignAccount,
+					declared,
+					vec![42],
+				);
+				proptest::prop_assert_eq!(result.is_ok(), declared.all_lte(maximal));
+				Ok(())
+			})?;
+		}
+	}
 }
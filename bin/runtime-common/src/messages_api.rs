@@ -17,8 +17,10 @@
 //! Helpers for implementing various message-related runtime API mthods.
 
 use bp_messages::{
-	InboundMessageDetails, LaneId, MessageNonce, MessagePayload, OutboundMessageDetails,
+	InboundMessageDetails, LaneId, LaneStatistics, MessageNonce, MessagePayload,
+	MessagesDeliveryLimits, OutboundMessageDetails,
 };
+use frame_support::{dispatch::DispatchClass, traits::Get, weights::Weight};
 use sp_std::vec::Vec;
 
 /// Implementation of the `To*OutboundLaneApi::message_details`.
@@ -46,6 +48,15 @@ where
 		.collect()
 }
 
+/// Implementation of the `To*OutboundLaneApi::lane_statistics`.
+pub fn lane_statistics<Runtime, MessagesPalletInstance>(lane: LaneId) -> LaneStatistics
+where
+	Runtime: pallet_bridge_messages::Config<MessagesPalletInstance>,
+	MessagesPalletInstance: 'static,
+{
+	pallet_bridge_messages::Pallet::<Runtime, MessagesPalletInstance>::lane_statistics(lane)
+}
+
 /// Implementation of the `To*InboundLaneApi::message_details`.
 pub fn inbound_message_details<Runtime, MessagesPalletInstance>(
 	lane: LaneId,
@@ -64,3 +75,26 @@ where
 		})
 		.collect()
 }
+
+/// Implementation of the `From*InboundLaneApi::delivery_limits`.
+pub fn messages_delivery_limits<Runtime, MessagesPalletInstance>() -> MessagesDeliveryLimits
+where
+	Runtime: pallet_bridge_messages::Config<MessagesPalletInstance>,
+	MessagesPalletInstance: 'static,
+{
+	MessagesDeliveryLimits {
+		max_unrewarded_relayer_entries_at_inbound_lane: <Runtime as pallet_bridge_messages::Config<
+			MessagesPalletInstance,
+		>>::MaxUnrewardedRelayerEntriesAtInboundLane::get(),
+		max_unconfirmed_messages_at_inbound_lane: <Runtime as pallet_bridge_messages::Config<
+			MessagesPalletInstance,
+		>>::MaxUnconfirmedMessagesAtInboundLane::get(),
+		max_extrinsic_size: *<Runtime as frame_system::Config>::BlockLength::get()
+			.max
+			.get(DispatchClass::Normal),
+		max_extrinsic_weight: <Runtime as frame_system::Config>::BlockWeights::get()
+			.get(DispatchClass::Normal)
+			.max_extrinsic
+			.unwrap_or(Weight::MAX),
+	}
+}
@@ -25,7 +25,8 @@ use crate::{
 		AccountIdOf, BridgedChain, HashOf, HasherOf, MessageBridge, ThisChain,
 	},
 	messages_generation::{
-		encode_all_messages, encode_lane_data, grow_trie_leaf_value, prepare_messages_storage_proof,
+		encode_all_messages, encode_lane_data, grow_trie_leaf_value,
+		prepare_messages_storage_proof, UnrelatedTrieEntries,
 	},
 };
 
@@ -69,6 +70,7 @@ where
 		},
 		encode_all_messages,
 		encode_lane_data,
+		Some(UnrelatedTrieEntries::BENCHMARKS),
 	);
 
 	// update runtime storage
@@ -115,6 +117,7 @@ where
 		},
 		encode_all_messages,
 		encode_lane_data,
+		Some(UnrelatedTrieEntries::BENCHMARKS),
 	);
 
 	// update runtime storage
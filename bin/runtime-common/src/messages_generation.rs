@@ -23,11 +23,31 @@ use crate::messages::{BridgedChain, HashOf, HasherOf, MessageBridge};
 use bp_messages::{
 	storage_keys, LaneId, MessageKey, MessageNonce, MessagePayload, OutboundLaneData,
 };
-use bp_runtime::{record_all_trie_keys, RawStorageProof, StorageProofSize};
+use bp_runtime::{record_trie_keys, RawStorageProof, StorageProofSize};
 use codec::Encode;
+use hash_db::Hasher;
 use sp_std::{ops::RangeInclusive, prelude::*};
 use sp_trie::{trie_types::TrieDBMutBuilderV1, LayoutV1, MemoryDB, TrieMut};
 
+/// Unrelated entries that are inserted into the trie, before generating the storage proof.
+///
+/// The trie of the real chain contains a lot of other entries, so proofs of its storage have
+/// more nodes than proofs of the trie that only contains messages. Inserting unrelated entries
+/// makes generated proofs (and weights, derived from benchmarks) more realistic.
+#[derive(Clone, Copy, Debug)]
+pub struct UnrelatedTrieEntries {
+	/// Number of unrelated entries.
+	pub count: u32,
+	/// Size of every unrelated entry value.
+	pub value_size: u32,
+}
+
+impl UnrelatedTrieEntries {
+	/// Entries that are used by benchmarks.
+	pub const BENCHMARKS: UnrelatedTrieEntries =
+		UnrelatedTrieEntries { count: 4096, value_size: 80 };
+}
+
 /// Simple and correct message data encode function.
 pub(crate) fn encode_all_messages(_: MessageNonce, m: &MessagePayload) -> Option<Vec<u8>> {
 	Some(m.encode())
@@ -40,7 +60,12 @@ pub(crate) fn encode_lane_data(d: &OutboundLaneData) -> Vec<u8> {
 
 /// Prepare storage proof of given messages.
 ///
+/// If `unrelated_entries` are specified, the trie is populated with given number of unrelated
+/// entries. Only nodes that are required to read messages and the lane state are included in the
+/// proof.
+///
 /// Returns state trie root and nodes with prepared messages.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn prepare_messages_storage_proof<B>(
 	lane: LaneId,
 	message_nonces: RangeInclusive<MessageNonce>,
@@ -49,6 +74,7 @@ pub(crate) fn prepare_messages_storage_proof<B>(
 	message_payload: MessagePayload,
 	encode_message: impl Fn(MessageNonce, &MessagePayload) -> Option<Vec<u8>>,
 	encode_outbound_lane_data: impl Fn(&OutboundLaneData) -> Vec<u8>,
+	unrelated_entries: Option<UnrelatedTrieEntries>,
 ) -> (HashOf<BridgedChain<B>>, RawStorageProof)
 where
 	B: MessageBridge,
@@ -97,12 +123,24 @@ where
 				.expect("TrieMut::insert should not fail in benchmarks");
 			storage_keys.push(storage_key);
 		}
+
+		// insert unrelated entries
+		if let Some(unrelated_entries) = unrelated_entries {
+			let value = vec![42u8; unrelated_entries.value_size as usize];
+			for i in 0..unrelated_entries.count {
+				let storage_key = HasherOf::<BridgedChain<B>>::hash(&i.encode());
+				trie.insert(storage_key.as_ref(), &value)
+					.map_err(|_| "TrieMut::insert has failed")
+					.expect("TrieMut::insert should not fail in benchmarks");
+			}
+		}
 	}
 
 	// generate storage proof to be delivered to This chain
-	let storage_proof = record_all_trie_keys::<LayoutV1<HasherOf<BridgedChain<B>>>, _>(&mdb, &root)
-		.map_err(|_| "record_all_trie_keys has failed")
-		.expect("record_all_trie_keys should not fail in benchmarks");
+	let storage_proof =
+		record_trie_keys::<LayoutV1<HasherOf<BridgedChain<B>>>, _>(&mdb, &root, &storage_keys)
+			.map_err(|_| "record_trie_keys has failed")
+			.expect("record_trie_keys should not fail in benchmarks");
 	(root, storage_proof)
 }
 
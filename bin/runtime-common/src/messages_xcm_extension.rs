@@ -21,16 +21,24 @@
 //!                                             |
 //! [`XcmRouter`] <- [`MessageDispatch`] <- <inbound message queue>
 
+use crate::messages::target::FromBridgedChainMessagePayload;
+
 use bp_messages::{
 	source_chain::MessagesBridge,
-	target_chain::{DispatchMessage, MessageDispatch},
+	target_chain::{DispatchMessage, DispatchMessageData, MessageDispatch},
 	LaneId,
 };
 use bp_runtime::{messages::MessageDispatchResult, AccountIdOf, Chain};
 use codec::{Decode, Encode};
-use frame_support::{dispatch::Weight, traits::Get, CloneNoBound, EqNoBound, PartialEqNoBound};
+use frame_support::{
+	dispatch::Weight,
+	traits::{Contains, Get},
+	CloneNoBound, EqNoBound, PartialEqNoBound,
+};
 use scale_info::TypeInfo;
-use xcm_builder::{DispatchBlob, DispatchBlobError, HaulBlob, HaulBlobError};
+use sp_std::vec::Vec;
+use xcm::latest::{Instruction, Junction, MultiLocation, NetworkId, QueryId, Xcm};
+use xcm_builder::{DispatchBlob, DispatchBlobError, HaulBlob, HaulBlobError, HaulBlobExporter};
 
 /// Plain "XCM" payload, which we transfer through bridge
 pub type XcmAsPlainPayload = sp_std::prelude::Vec<u8>;
@@ -179,3 +187,679 @@ impl<HaulerOrigin, H: XcmBlobHauler<MessageSenderOrigin = HaulerOrigin>> HaulBlo
 		result.map(|_| ()).map_err(|_| HaulBlobError::Transport("MessageSenderError"))
 	}
 }
+
+/// [`XcmBlobHauler`] that sends XCM blobs over the given lane, using the given origin.
+///
+/// It allows to configure the hauler with plain runtime parameters, without implementing the
+/// [`XcmBlobHauler`] trait.
+pub struct LaneXcmBlobHauler<MessageSender, MessageSenderOrigin, Lane>(
+	sp_std::marker::PhantomData<(MessageSender, MessageSenderOrigin, Lane)>,
+);
+
+impl<Origin, MessageSender, MessageSenderOrigin, Lane> XcmBlobHauler
+	for LaneXcmBlobHauler<MessageSender, MessageSenderOrigin, Lane>
+where
+	MessageSender: MessagesBridge<Origin, XcmAsPlainPayload>,
+	MessageSenderOrigin: Get<Origin>,
+	Lane: Get<LaneId>,
+{
+	type MessageSender = MessageSender;
+	type MessageSenderOrigin = Origin;
+
+	fn message_sender_origin() -> Origin {
+		MessageSenderOrigin::get()
+	}
+
+	fn xcm_lane() -> LaneId {
+		Lane::get()
+	}
+}
+
+/// [`HaulBlobExporter`] that exports XCM blobs to the `BridgedNetwork` over the
+/// [`pallet_bridge_messages`] lane.
+///
+/// Runtimes that are already structured around blob hauling may use it as the `MessageExporter`
+/// of their XCM executor configuration to plug the bridge lane in.
+pub type MessagesBridgeBlobExporter<
+	MessageSender,
+	MessageSenderOrigin,
+	Lane,
+	BridgedNetwork,
+	Price,
+> = HaulBlobExporter<
+	XcmBlobHaulerAdapter<LaneXcmBlobHauler<MessageSender, MessageSenderOrigin, Lane>>,
+	BridgedNetwork,
+	Price,
+>;
+
+/// Result of the message dispatch by the [`LaneRoutedDispatch`].
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo)]
+pub enum LaneRoutedDispatchResult<XcmDispatchResult, BlobDispatchResult> {
+	/// Message has been dispatched by the XCM dispatcher.
+	Xcm(XcmDispatchResult),
+	/// Message has been dispatched by the blob dispatcher.
+	Blob(BlobDispatchResult),
+	/// Message has been delivered over the lane that is not routed to any dispatcher. It has
+	/// been dropped.
+	UnroutedLane,
+}
+
+/// [`MessageDispatch`] implementation that routes messages to different dispatchers, depending
+/// on the lane they have been delivered over.
+///
+/// Messages, delivered over `XcmLanes`, are decoded as XCM programs and dispatched by the
+/// `XcmDispatch` (e.g. [`crate::messages::target::FromBridgedChainMessageDispatch`]). Messages,
+/// delivered over `BlobLanes`, are passed as raw blobs to the `BlobDispatch`, which may be
+/// anything: a smart-contract call, an outbound queue of another bridge and so on. Messages,
+/// delivered over other lanes are dropped.
+///
+/// The `DispatchPayload` of this dispatcher is [`XcmAsPlainPayload`], so it must also be used as
+/// the `InboundPayload` of the messages pallet.
+pub struct LaneRoutedDispatch<XcmLanes, XcmDispatch, BlobLanes, BlobDispatch>(
+	sp_std::marker::PhantomData<(XcmLanes, XcmDispatch, BlobLanes, BlobDispatch)>,
+);
+
+impl<XcmLanes, XcmDispatch, BlobLanes, BlobDispatch>
+	LaneRoutedDispatch<XcmLanes, XcmDispatch, BlobLanes, BlobDispatch>
+where
+	XcmLanes: Get<&'static [LaneId]>,
+	BlobLanes: Get<&'static [LaneId]>,
+{
+	fn is_xcm_lane(lane: &LaneId) -> bool {
+		XcmLanes::get().contains(lane)
+	}
+
+	fn is_blob_lane(lane: &LaneId) -> bool {
+		BlobLanes::get().contains(lane)
+	}
+}
+
+/// Convert plain message into the message with decoded XCM program.
+fn to_xcm_message<Call: Decode>(
+	message: &DispatchMessage<XcmAsPlainPayload>,
+) -> DispatchMessage<FromBridgedChainMessagePayload<Call>> {
+	let payload = match message.data.payload {
+		// the XCM payload decoder expects the encoded (length-prefixed) payload
+		Ok(ref payload) => FromBridgedChainMessagePayload::decode(&mut &payload.encode()[..]),
+		Err(_) => Err(codec::Error::from("Failed to decode message payload")),
+	};
+	DispatchMessage { key: message.key.clone(), data: DispatchMessageData { payload } }
+}
+
+impl<AccountId, Call, XcmLanes, XcmDispatch, BlobLanes, BlobDispatch> MessageDispatch<AccountId>
+	for LaneRoutedDispatch<XcmLanes, XcmDispatch, BlobLanes, BlobDispatch>
+where
+	Call: Decode,
+	XcmLanes: Get<&'static [LaneId]>,
+	XcmDispatch: MessageDispatch<AccountId, DispatchPayload = FromBridgedChainMessagePayload<Call>>,
+	BlobLanes: Get<&'static [LaneId]>,
+	BlobDispatch: MessageDispatch<AccountId, DispatchPayload = XcmAsPlainPayload>,
+{
+	type DispatchPayload = XcmAsPlainPayload;
+	type DispatchLevelResult = LaneRoutedDispatchResult<
+		XcmDispatch::DispatchLevelResult,
+		BlobDispatch::DispatchLevelResult,
+	>;
+
+	fn dispatch_weight(message: &mut DispatchMessage<Self::DispatchPayload>) -> Weight {
+		let lane = message.key.lane_id;
+		if Self::is_xcm_lane(&lane) {
+			let mut xcm_message = to_xcm_message(message);
+			XcmDispatch::dispatch_weight(&mut xcm_message)
+		} else if Self::is_blob_lane(&lane) {
+			BlobDispatch::dispatch_weight(message)
+		} else {
+			Weight::zero()
+		}
+	}
+
+	fn dispatch(
+		relayer_account: &AccountId,
+		message: DispatchMessage<Self::DispatchPayload>,
+	) -> MessageDispatchResult<Self::DispatchLevelResult> {
+		let lane = message.key.lane_id;
+		if Self::is_xcm_lane(&lane) {
+			// weight of the XCM program is computed by the `dispatch_weight` and is stored in the
+			// decoded payload, so we need to compute it again
+			let mut xcm_message = to_xcm_message(&message);
+			XcmDispatch::dispatch_weight(&mut xcm_message);
+			let result = XcmDispatch::dispatch(relayer_account, xcm_message);
+			MessageDispatchResult {
+				unspent_weight: result.unspent_weight,
+				dispatch_level_result: LaneRoutedDispatchResult::Xcm(result.dispatch_level_result),
+			}
+		} else if Self::is_blob_lane(&lane) {
+			let result = BlobDispatch::dispatch(relayer_account, message);
+			MessageDispatchResult {
+				unspent_weight: result.unspent_weight,
+				dispatch_level_result: LaneRoutedDispatchResult::Blob(result.dispatch_level_result),
+			}
+		} else {
+			log::error!(
+				target: crate::LOG_TARGET_BRIDGE_DISPATCH,
+				"Dropping message {:?}/{}, delivered over the lane without dispatcher",
+				lane,
+				message.key.nonce,
+			);
+			MessageDispatchResult {
+				unspent_weight: Weight::zero(),
+				dispatch_level_result: LaneRoutedDispatchResult::UnroutedLane,
+			}
+		}
+	}
+}
+
+/// Bridged origin that is trusted by this chain: network of the bridged consensus system and the
+/// lane that is used to deliver its messages.
+pub type TrustedBridgedOrigin = (NetworkId, LaneId);
+
+/// Returns true if messages from given bridged network, delivered over given lane, are trusted.
+pub fn is_trusted_bridged_origin(
+	trusted_origins: &[TrustedBridgedOrigin],
+	network: NetworkId,
+	lane: LaneId,
+) -> bool {
+	trusted_origins.contains(&(network, lane))
+}
+
+/// [`MessageDispatch`] wrapper that only dispatches messages from trusted bridged origins.
+///
+/// Messages, delivered over lanes that are not trusted for the `BridgedNetwork`, are rejected
+/// without dispatch.
+pub struct TrustedBridgedOriginsDispatch<Dispatch, BridgedNetwork, TrustedOrigins>(
+	sp_std::marker::PhantomData<(Dispatch, BridgedNetwork, TrustedOrigins)>,
+);
+
+impl<Dispatch, BridgedNetwork, TrustedOrigins>
+	TrustedBridgedOriginsDispatch<Dispatch, BridgedNetwork, TrustedOrigins>
+where
+	BridgedNetwork: Get<NetworkId>,
+	TrustedOrigins: Get<&'static [TrustedBridgedOrigin]>,
+{
+	fn is_trusted_lane(lane: LaneId) -> bool {
+		is_trusted_bridged_origin(TrustedOrigins::get(), BridgedNetwork::get(), lane)
+	}
+}
+
+impl<AccountId, Dispatch, BridgedNetwork, TrustedOrigins> MessageDispatch<AccountId>
+	for TrustedBridgedOriginsDispatch<Dispatch, BridgedNetwork, TrustedOrigins>
+where
+	Dispatch: MessageDispatch<AccountId>,
+	Dispatch::DispatchLevelResult: Default,
+	BridgedNetwork: Get<NetworkId>,
+	TrustedOrigins: Get<&'static [TrustedBridgedOrigin]>,
+{
+	type DispatchPayload = Dispatch::DispatchPayload;
+	type DispatchLevelResult = Dispatch::DispatchLevelResult;
+
+	fn dispatch_weight(message: &mut DispatchMessage<Self::DispatchPayload>) -> Weight {
+		if !Self::is_trusted_lane(message.key.lane_id) {
+			return Weight::zero()
+		}
+
+		Dispatch::dispatch_weight(message)
+	}
+
+	fn dispatch(
+		relayer_account: &AccountId,
+		message: DispatchMessage<Self::DispatchPayload>,
+	) -> MessageDispatchResult<Self::DispatchLevelResult> {
+		if !Self::is_trusted_lane(message.key.lane_id) {
+			log::error!(
+				target: crate::LOG_TARGET_BRIDGE_DISPATCH,
+				"Rejecting message {:?}/{} from untrusted bridged origin {:?}",
+				message.key.lane_id,
+				message.key.nonce,
+				BridgedNetwork::get(),
+			);
+			return MessageDispatchResult {
+				unspent_weight: Weight::zero(),
+				dispatch_level_result: Default::default(),
+			}
+		}
+
+		Dispatch::dispatch(relayer_account, message)
+	}
+}
+
+/// Universal aliases that are generated from the trusted bridged origins.
+///
+/// Allows the `BridgeLocation` (location that is dispatching bridged messages at this chain) to
+/// alias into the global consensus of every trusted bridged network. It is supposed to be used
+/// as `UniversalAliases` in the XCM executor configuration, next to the
+/// [`TrustedBridgedOriginsDispatch`] with the same `TrustedOrigins`.
+pub struct UniversalAliasesFromTrustedOrigins<BridgeLocation, TrustedOrigins>(
+	sp_std::marker::PhantomData<(BridgeLocation, TrustedOrigins)>,
+);
+
+impl<BridgeLocation, TrustedOrigins> Contains<(MultiLocation, Junction)>
+	for UniversalAliasesFromTrustedOrigins<BridgeLocation, TrustedOrigins>
+where
+	BridgeLocation: Get<MultiLocation>,
+	TrustedOrigins: Get<&'static [TrustedBridgedOrigin]>,
+{
+	fn contains((origin, junction): &(MultiLocation, Junction)) -> bool {
+		if *origin != BridgeLocation::get() {
+			return false
+		}
+
+		match junction {
+			Junction::GlobalConsensus(network) => TrustedOrigins::get()
+				.iter()
+				.any(|(trusted_network, _)| trusted_network == network),
+			_ => false,
+		}
+	}
+}
+
+/// Storage of queries that have been started by bridged XCM programs and are expecting responses
+/// from this chain.
+///
+/// When the bridged program asks this chain to report something (e.g. using `ReportError`
+/// instruction), the response (the `QueryResponse` instruction) must be sent back over the same
+/// lane that has been used to deliver the program.
+pub trait PendingBridgedQueries {
+	/// Remember that the response to given query must be sent to the `destination` over `lane`.
+	///
+	/// The implementation may refuse to remember the query, e.g. if there are too many pending
+	/// queries. The response is routed as usual then.
+	fn note_pending_query(lane: LaneId, destination: &MultiLocation, query_id: QueryId);
+	/// Returns lane that must be used to send response to given query to the `destination`.
+	fn pending_query_lane(destination: &MultiLocation, query_id: QueryId) -> Option<LaneId>;
+	/// Forget about pending query.
+	fn remove_pending_query(destination: &MultiLocation, query_id: QueryId);
+}
+
+impl PendingBridgedQueries for () {
+	fn note_pending_query(_lane: LaneId, _destination: &MultiLocation, _query_id: QueryId) {}
+
+	fn pending_query_lane(_destination: &MultiLocation, _query_id: QueryId) -> Option<LaneId> {
+		None
+	}
+
+	fn remove_pending_query(_destination: &MultiLocation, _query_id: QueryId) {}
+}
+
+/// Returns destinations and identifiers of all queries that may be answered by this chain while
+/// executing given XCM program. Nested programs (error handler and appendix) are also inspected.
+pub fn expected_responses<Call>(xcm: &Xcm<Call>) -> Vec<(MultiLocation, QueryId)> {
+	let mut responses = Vec::new();
+	collect_expected_responses(xcm, &mut responses);
+	responses
+}
+
+fn collect_expected_responses<Call>(
+	xcm: &Xcm<Call>,
+	responses: &mut Vec<(MultiLocation, QueryId)>,
+) {
+	for instruction in xcm.inner() {
+		match instruction {
+			Instruction::ReportError(response_info) |
+			Instruction::ReportHolding { response_info, .. } |
+			Instruction::ReportTransactStatus(response_info) |
+			Instruction::QueryPallet { response_info, .. } =>
+				responses.push((response_info.destination, response_info.query_id)),
+			Instruction::SetErrorHandler(nested) | Instruction::SetAppendix(nested) =>
+				collect_expected_responses(nested, responses),
+			_ => (),
+		}
+	}
+}
+
+/// [`MessageDispatch`] wrapper that remembers queries of the bridged XCM program, so responses
+/// to these queries are sent back over the lane that has delivered the program.
+///
+/// All responses are sent while the program is executed, so pending queries are forgotten right
+/// after the message is dispatched.
+pub struct PendingQueriesDispatch<Dispatch, Queries>(
+	sp_std::marker::PhantomData<(Dispatch, Queries)>,
+);
+
+impl<AccountId, Call, Dispatch, Queries> MessageDispatch<AccountId>
+	for PendingQueriesDispatch<Dispatch, Queries>
+where
+	Dispatch: MessageDispatch<AccountId, DispatchPayload = FromBridgedChainMessagePayload<Call>>,
+	Queries: PendingBridgedQueries,
+{
+	type DispatchPayload = Dispatch::DispatchPayload;
+	type DispatchLevelResult = Dispatch::DispatchLevelResult;
+
+	fn dispatch_weight(message: &mut DispatchMessage<Self::DispatchPayload>) -> Weight {
+		Dispatch::dispatch_weight(message)
+	}
+
+	fn dispatch(
+		relayer_account: &AccountId,
+		message: DispatchMessage<Self::DispatchPayload>,
+	) -> MessageDispatchResult<Self::DispatchLevelResult> {
+		let lane = message.key.lane_id;
+		let responses = match message.data.payload {
+			Ok(ref payload) => expected_responses(&payload.xcm.1),
+			Err(_) => Vec::new(),
+		};
+		for (destination, query_id) in &responses {
+			log::trace!(
+				target: crate::LOG_TARGET_BRIDGE_DISPATCH,
+				"Message {:?}/{} expects response {} at {:?}",
+				lane,
+				message.key.nonce,
+				query_id,
+				destination,
+			);
+			Queries::note_pending_query(lane, destination, *query_id);
+		}
+
+		let result = Dispatch::dispatch(relayer_account, message);
+
+		for (destination, query_id) in &responses {
+			Queries::remove_pending_query(destination, *query_id);
+		}
+
+		result
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bp_messages::{target_chain::DispatchMessageData, MessageKey};
+	use frame_support::parameter_types;
+	use xcm::{latest::prelude::*, VersionedMultiLocation, VersionedXcm};
+
+	const TRUSTED_LANE: LaneId = LaneId([1, 2, 3, 4]);
+
+	parameter_types! {
+		pub const BridgedNetwork: NetworkId = Polkadot;
+		pub const BridgeLocation: MultiLocation = MultiLocation::here();
+		pub const TrustedOrigins: &'static [TrustedBridgedOrigin] = &[(Polkadot, TRUSTED_LANE)];
+	}
+
+	struct TestDispatch;
+
+	impl MessageDispatch<u64> for TestDispatch {
+		type DispatchPayload = ();
+		type DispatchLevelResult = bool;
+
+		fn dispatch_weight(_message: &mut DispatchMessage<()>) -> Weight {
+			Weight::from_parts(100, 0)
+		}
+
+		fn dispatch(_: &u64, _: DispatchMessage<()>) -> MessageDispatchResult<bool> {
+			MessageDispatchResult { unspent_weight: Weight::zero(), dispatch_level_result: true }
+		}
+	}
+
+	type Dispatch = TrustedBridgedOriginsDispatch<TestDispatch, BridgedNetwork, TrustedOrigins>;
+
+	const XCM_LANE: LaneId = LaneId([0, 0, 0, 1]);
+	const BLOB_LANE: LaneId = LaneId([0, 0, 0, 2]);
+
+	parameter_types! {
+		pub const XcmLanes: &'static [LaneId] = &[XCM_LANE];
+		pub const BlobLanes: &'static [LaneId] = &[BLOB_LANE];
+	}
+
+	struct TestXcmDispatch;
+
+	impl MessageDispatch<u64> for TestXcmDispatch {
+		type DispatchPayload = FromBridgedChainMessagePayload<()>;
+		type DispatchLevelResult = Option<Weight>;
+
+		fn dispatch_weight(message: &mut DispatchMessage<Self::DispatchPayload>) -> Weight {
+			let payload = message.data.payload.as_mut().unwrap();
+			let weight = Weight::from_parts(payload.xcm.1.len() as u64 * 10, 0);
+			payload.weight = Some(weight);
+			weight
+		}
+
+		fn dispatch(
+			_: &u64,
+			message: DispatchMessage<Self::DispatchPayload>,
+		) -> MessageDispatchResult<Option<Weight>> {
+			MessageDispatchResult {
+				unspent_weight: Weight::zero(),
+				dispatch_level_result: message.data.payload.unwrap().weight,
+			}
+		}
+	}
+
+	struct TestBlobDispatch;
+
+	impl MessageDispatch<u64> for TestBlobDispatch {
+		type DispatchPayload = XcmAsPlainPayload;
+		type DispatchLevelResult = XcmAsPlainPayload;
+
+		fn dispatch_weight(_message: &mut DispatchMessage<XcmAsPlainPayload>) -> Weight {
+			Weight::from_parts(1, 0)
+		}
+
+		fn dispatch(
+			_: &u64,
+			message: DispatchMessage<XcmAsPlainPayload>,
+		) -> MessageDispatchResult<XcmAsPlainPayload> {
+			MessageDispatchResult {
+				unspent_weight: Weight::zero(),
+				dispatch_level_result: message.data.payload.unwrap(),
+			}
+		}
+	}
+
+	parameter_types! {
+		pub const HaulerOrigin: u64 = 42;
+		pub const HaulerLane: LaneId = XCM_LANE;
+	}
+
+	struct TestMessageSender;
+
+	impl MessagesBridge<u64, XcmAsPlainPayload> for TestMessageSender {
+		type Error = ();
+
+		fn send_message(
+			origin: u64,
+			lane: LaneId,
+			message: XcmAsPlainPayload,
+		) -> Result<bp_messages::source_chain::SendMessageArtifacts, ()> {
+			if message.is_empty() {
+				return Err(())
+			}
+
+			let mut messages: Vec<(u64, LaneId, XcmAsPlainPayload)> =
+				frame_support::storage::unhashed::get_or_default(b":sent-messages:");
+			messages.push((origin, lane, message));
+			frame_support::storage::unhashed::put(b":sent-messages:", &messages);
+			Ok(bp_messages::source_chain::SendMessageArtifacts {
+				nonce: messages.len() as _,
+				weight: Weight::zero(),
+			})
+		}
+	}
+
+	type Hauler =
+		XcmBlobHaulerAdapter<LaneXcmBlobHauler<TestMessageSender, HaulerOrigin, HaulerLane>>;
+
+	type RoutedDispatch =
+		LaneRoutedDispatch<XcmLanes, TestXcmDispatch, BlobLanes, TestBlobDispatch>;
+
+	fn plain_message(
+		lane_id: LaneId,
+		payload: XcmAsPlainPayload,
+	) -> DispatchMessage<XcmAsPlainPayload> {
+		DispatchMessage {
+			key: MessageKey { lane_id, nonce: 1 },
+			data: DispatchMessageData { payload: Ok(payload) },
+		}
+	}
+
+	fn message(lane_id: LaneId) -> DispatchMessage<()> {
+		DispatchMessage {
+			key: MessageKey { lane_id, nonce: 1 },
+			data: DispatchMessageData { payload: Ok(()) },
+		}
+	}
+
+	#[test]
+	fn messages_from_trusted_origins_are_dispatched() {
+		assert_eq!(
+			Dispatch::dispatch_weight(&mut message(TRUSTED_LANE)),
+			Weight::from_parts(100, 0)
+		);
+		assert!(Dispatch::dispatch(&0, message(TRUSTED_LANE)).dispatch_level_result);
+	}
+
+	#[test]
+	fn messages_from_untrusted_origins_are_rejected() {
+		let untrusted_lane = LaneId([0, 0, 0, 0]);
+		assert_eq!(Dispatch::dispatch_weight(&mut message(untrusted_lane)), Weight::zero());
+		assert!(!Dispatch::dispatch(&0, message(untrusted_lane)).dispatch_level_result);
+	}
+
+	#[test]
+	fn universal_aliases_are_generated_from_trusted_origins() {
+		type Aliases = UniversalAliasesFromTrustedOrigins<BridgeLocation, TrustedOrigins>;
+
+		assert!(Aliases::contains(&(MultiLocation::here(), GlobalConsensus(Polkadot))));
+		assert!(!Aliases::contains(&(MultiLocation::here(), GlobalConsensus(Kusama))));
+		assert!(!Aliases::contains(&(MultiLocation::parent(), GlobalConsensus(Polkadot))));
+		assert!(!Aliases::contains(&(MultiLocation::here(), Parachain(1000))));
+	}
+
+	fn response_info(query_id: QueryId) -> QueryResponseInfo {
+		QueryResponseInfo {
+			destination: MultiLocation::new(2, X2(GlobalConsensus(Polkadot), Parachain(1000))),
+			query_id,
+			max_weight: Weight::zero(),
+		}
+	}
+
+	struct TestQueries;
+
+	impl PendingBridgedQueries for TestQueries {
+		fn note_pending_query(lane: LaneId, destination: &MultiLocation, query_id: QueryId) {
+			frame_support::storage::unhashed::put(&(destination, query_id).encode(), &lane);
+		}
+
+		fn pending_query_lane(destination: &MultiLocation, query_id: QueryId) -> Option<LaneId> {
+			frame_support::storage::unhashed::get(&(destination, query_id).encode())
+		}
+
+		fn remove_pending_query(destination: &MultiLocation, query_id: QueryId) {
+			frame_support::storage::unhashed::kill(&(destination, query_id).encode());
+		}
+	}
+
+	struct QueryCheckingDispatch;
+
+	impl MessageDispatch<u64> for QueryCheckingDispatch {
+		type DispatchPayload = FromBridgedChainMessagePayload<()>;
+		type DispatchLevelResult = Option<LaneId>;
+
+		fn dispatch_weight(_message: &mut DispatchMessage<Self::DispatchPayload>) -> Weight {
+			Weight::zero()
+		}
+
+		fn dispatch(
+			_: &u64,
+			_: DispatchMessage<Self::DispatchPayload>,
+		) -> MessageDispatchResult<Option<LaneId>> {
+			MessageDispatchResult {
+				unspent_weight: Weight::zero(),
+				dispatch_level_result: TestQueries::pending_query_lane(
+					&response_info(1).destination,
+					1,
+				),
+			}
+		}
+	}
+
+	#[test]
+	fn expected_responses_are_collected_from_nested_programs() {
+		let xcm: Xcm<()> = Xcm(vec![
+			ClearOrigin,
+			SetErrorHandler(Xcm(vec![ReportError(response_info(1))])),
+			SetAppendix(Xcm(vec![ReportTransactStatus(response_info(2))])),
+			ReportHolding { response_info: response_info(3), assets: Wild(All) },
+		]);
+		assert_eq!(
+			expected_responses(&xcm),
+			vec![
+				(response_info(1).destination, 1),
+				(response_info(2).destination, 2),
+				(response_info(3).destination, 3),
+			],
+		);
+	}
+
+	#[test]
+	fn pending_queries_are_known_while_message_is_dispatched() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let xcm = Xcm(vec![SetAppendix(Xcm(vec![ReportError(response_info(1))]))]);
+			let message = DispatchMessage {
+				key: MessageKey { lane_id: TRUSTED_LANE, nonce: 1 },
+				data: DispatchMessageData {
+					payload: Ok(FromBridgedChainMessagePayload::from((MultiLocation::here(), xcm))),
+				},
+			};
+
+			assert_eq!(
+				PendingQueriesDispatch::<QueryCheckingDispatch, TestQueries>::dispatch(&0, message)
+					.dispatch_level_result,
+				Some(TRUSTED_LANE),
+			);
+			assert_eq!(TestQueries::pending_query_lane(&response_info(1).destination, 1), None);
+		});
+	}
+
+	#[test]
+	fn xcm_messages_are_routed_to_xcm_dispatcher() {
+		let xcm = VersionedXcm::<()>::from(Xcm(vec![ClearOrigin, ClearOrigin]));
+		let payload = (VersionedMultiLocation::from(MultiLocation::here()), xcm).encode();
+
+		let expected_weight = Weight::from_parts(20, 0);
+		assert_eq!(
+			RoutedDispatch::dispatch_weight(&mut plain_message(XCM_LANE, payload.clone())),
+			expected_weight,
+		);
+		assert_eq!(
+			RoutedDispatch::dispatch(&0, plain_message(XCM_LANE, payload)).dispatch_level_result,
+			LaneRoutedDispatchResult::Xcm(Some(expected_weight)),
+		);
+	}
+
+	#[test]
+	fn blob_messages_are_routed_to_blob_dispatcher() {
+		assert_eq!(
+			RoutedDispatch::dispatch_weight(&mut plain_message(BLOB_LANE, vec![42])),
+			Weight::from_parts(1, 0),
+		);
+		assert_eq!(
+			RoutedDispatch::dispatch(&0, plain_message(BLOB_LANE, vec![42])).dispatch_level_result,
+			LaneRoutedDispatchResult::Blob(vec![42]),
+		);
+	}
+
+	#[test]
+	fn messages_from_unrouted_lanes_are_dropped() {
+		let unrouted_lane = LaneId([0, 0, 0, 3]);
+		assert_eq!(
+			RoutedDispatch::dispatch_weight(&mut plain_message(unrouted_lane, vec![42])),
+			Weight::zero(),
+		);
+		assert_eq!(
+			RoutedDispatch::dispatch(&0, plain_message(unrouted_lane, vec![42]))
+				.dispatch_level_result,
+			LaneRoutedDispatchResult::UnroutedLane,
+		);
+	}
+
+	#[test]
+	fn blobs_are_hauled_over_configured_lane() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert!(Hauler::haul_blob(vec![1, 2, 3]).is_ok());
+			assert!(Hauler::haul_blob(vec![]).is_err());
+
+			let sent_messages: Vec<(u64, LaneId, XcmAsPlainPayload)> =
+				frame_support::storage::unhashed::get_or_default(b":sent-messages:");
+			assert_eq!(sent_messages, vec![(42, XCM_LANE, vec![1, 2, 3])]);
+		});
+	}
+}
@@ -0,0 +1,337 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Utilities for testing storage migrations of bridge pallets.
+//!
+//! Every change of the bridge pallets storage format should come with the migration test. The
+//! test writes pallet storage in the "old" layout (see [`put_old_storage_value`]), then calls
+//! [`run_migration_test`] with the runtime migrations. After migrations are applied, it should
+//! check that the pallets storage is still consistent, using [`ensure_grandpa_invariants`],
+//! [`ensure_messages_invariants`] and [`ensure_relayers_invariants`] functions.
+
+use codec::Encode;
+use frame_support::{
+	storage::{storage_prefix, unhashed, StoragePrefixedMap},
+	traits::{Get, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use sp_runtime::traits::Zero;
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+/// Raw storage entries of the single pallet.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageSnapshot {
+	/// Name of the pallet.
+	pub pallet_name: &'static str,
+	/// All storage entries of the pallet.
+	pub entries: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl StorageSnapshot {
+	/// Read all storage entries of the pallet with given name.
+	pub fn take(pallet_name: &'static str) -> Self {
+		let prefix = sp_io::hashing::twox_128(pallet_name.as_bytes()).to_vec();
+		let mut entries = BTreeMap::new();
+		let mut previous_key = prefix.clone();
+		while let Some(key) = sp_io::storage::next_key(&previous_key) {
+			if !key.starts_with(&prefix) {
+				break
+			}
+
+			if let Some(value) = unhashed::get_raw(&key) {
+				entries.insert(key.clone(), value);
+			}
+			previous_key = key;
+		}
+
+		StorageSnapshot { pallet_name, entries }
+	}
+
+	/// Returns all entries of the storage item with given name.
+	pub fn storage_entries(&self, storage_name: &str) -> BTreeMap<Vec<u8>, Vec<u8>> {
+		let prefix = storage_prefix(self.pallet_name.as_bytes(), storage_name.as_bytes());
+		self.entries
+			.iter()
+			.filter(|(key, _)| key.starts_with(&prefix))
+			.map(|(key, value)| (key.clone(), value.clone()))
+			.collect()
+	}
+}
+
+/// Returns key of the storage item in the "old" layout.
+///
+/// The `hashed_key` is the key of the map item, hashed with the map hasher. It must be empty for
+/// storage values.
+pub fn old_storage_key(pallet_name: &str, storage_name: &str, hashed_key: &[u8]) -> Vec<u8> {
+	let mut key = storage_prefix(pallet_name.as_bytes(), storage_name.as_bytes()).to_vec();
+	key.extend_from_slice(hashed_key);
+	key
+}
+
+/// Write storage item in the "old" layout.
+pub fn put_old_storage_value(
+	pallet_name: &str,
+	storage_name: &str,
+	hashed_key: &[u8],
+	value: &impl Encode,
+) {
+	unhashed::put(&old_storage_key(pallet_name, storage_name, hashed_key), value)
+}
+
+/// Run migration test.
+///
+/// The `prepare_old_storage` closure is called first to write pallets storage in the "old"
+/// layout. Then we take snapshot of storage of pallets with given names and run `Migrations`.
+/// After that, the `ensure_invariants` closure is called with snapshots, taken before and after
+/// migrations.
+///
+/// Must be called within externalities.
+pub fn run_migration_test<Migrations: OnRuntimeUpgrade>(
+	pallet_names: &[&'static str],
+	prepare_old_storage: impl FnOnce(),
+	ensure_invariants: impl FnOnce(&[StorageSnapshot], &[StorageSnapshot]),
+) -> Weight {
+	prepare_old_storage();
+	let snapshots_before =
+		pallet_names.iter().map(|name| StorageSnapshot::take(*name)).collect::<Vec<_>>();
+
+	let weight = Migrations::on_runtime_upgrade();
+
+	let snapshots_after =
+		pallet_names.iter().map(|name| StorageSnapshot::take(*name)).collect::<Vec<_>>();
+	ensure_invariants(&snapshots_before, &snapshots_after);
+
+	weight
+}
+
+/// Ensure that the storage of GRANDPA pallet is consistent.
+pub fn ensure_grandpa_invariants<T, I>()
+where
+	T: pallet_bridge_grandpa::Config<I>,
+	I: 'static,
+{
+	let imported_headers =
+		pallet_bridge_grandpa::ImportedHeaders::<T, I>::iter().collect::<Vec<_>>();
+	assert!(
+		imported_headers.len() <= T::HeadersToKeep::get() as usize,
+		"GRANDPA pallet stores {} headers, while it may store at most {}",
+		imported_headers.len(),
+		T::HeadersToKeep::get(),
+	);
+
+	let best_finalized = match pallet_bridge_grandpa::BestFinalized::<T, I>::get() {
+		Some(best_finalized) => best_finalized,
+		None => {
+			assert!(
+				imported_headers.is_empty(),
+				"GRANDPA pallet stores headers, but the best finalized header is unknown",
+			);
+			return
+		},
+	};
+
+	let best_finalized_header =
+		pallet_bridge_grandpa::ImportedHeaders::<T, I>::get(best_finalized.hash())
+			.expect("best finalized header is missing from the GRANDPA pallet storage");
+	assert_eq!(best_finalized_header.number, best_finalized.number());
+	for (hash, header) in imported_headers {
+		assert!(
+			header.number <= best_finalized.number(),
+			"GRANDPA pallet stores header {:?} that is ahead of the best finalized header",
+			hash,
+		);
+	}
+
+	assert!(
+		!pallet_bridge_grandpa::CurrentAuthoritySet::<T, I>::get().authorities.is_empty(),
+		"GRANDPA pallet is initialized, but the current authority set is empty",
+	);
+}
+
+/// Ensure that the storage of messages pallet is consistent.
+pub fn ensure_messages_invariants<T, I>()
+where
+	T: pallet_bridge_messages::Config<I>,
+	I: 'static,
+{
+	let outbound_lanes = pallet_bridge_messages::OutboundLanes::<T, I>::iter().collect::<Vec<_>>();
+	for (lane, data) in &outbound_lanes {
+		assert!(
+			data.latest_received_nonce <= data.latest_generated_nonce,
+			"Outbound lane {:?} has received more messages than it has generated",
+			lane,
+		);
+		assert!(
+			data.oldest_unpruned_nonce <= data.latest_received_nonce.saturating_add(1),
+			"Outbound lane {:?} has pruned messages that have not been delivered yet",
+			lane,
+		);
+		for nonce in data.oldest_unpruned_nonce..=data.latest_generated_nonce {
+			assert!(
+				pallet_bridge_messages::OutboundMessages::<T, I>::contains_key(
+					bp_messages::MessageKey { lane_id: *lane, nonce },
+				),
+				"Message {} is missing from the outbound lane {:?}",
+				nonce,
+				lane,
+			);
+		}
+	}
+	for key in pallet_bridge_messages::OutboundMessages::<T, I>::iter_keys() {
+		let is_known_message = outbound_lanes.iter().any(|(lane, data)| {
+			*lane == key.lane_id &&
+				key.nonce >= data.oldest_unpruned_nonce &&
+				key.nonce <= data.latest_generated_nonce
+		});
+		assert!(is_known_message, "Outbound message {:?} is not tracked by its lane", key);
+	}
+
+	for (lane, data) in pallet_bridge_messages::InboundLanes::<T, I>::iter() {
+		let mut expected_begin = data.last_confirmed_nonce.saturating_add(1);
+		for entry in &data.relayers {
+			assert!(
+				entry.messages.begin <= entry.messages.end,
+				"Inbound lane {:?} has empty unrewarded relayer entry",
+				lane,
+			);
+			assert!(
+				entry.messages.begin >= expected_begin,
+				"Inbound lane {:?} has overlapping unrewarded relayer entries",
+				lane,
+			);
+			expected_begin = entry.messages.end.saturating_add(1);
+		}
+	}
+}
+
+/// Ensure that the storage of relayers pallet is consistent.
+pub fn ensure_relayers_invariants<T: pallet_bridge_relayers::Config>() {
+	for reward in
+		<pallet_bridge_relayers::RelayerRewards<T> as StoragePrefixedMap<T::Reward>>::iter_values()
+	{
+		assert!(!reward.is_zero(), "Relayers pallet stores zero reward");
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+	use bp_messages::{LaneId, MessageKey, OutboundLaneData};
+	use frame_support::{Blake2_128Concat, StorageHasher};
+	use pallet_bridge_messages::{OutboundLanes, OutboundMessages, StoredMessagePayload};
+
+	const TEST_LANE_ID: LaneId = LaneId([0, 0, 0, 1]);
+	const MESSAGES_PALLET_NAME: &str = "BridgeMessages";
+
+	/// Outbound lane data in the "old" layout: `(latest_received_nonce, latest_generated_nonce)`.
+	type OldOutboundLaneData = (u64, u64);
+
+	/// Migration that adds `oldest_unpruned_nonce` to the outbound lane data.
+	struct AddOldestUnprunedNonce;
+
+	impl OnRuntimeUpgrade for AddOldestUnprunedNonce {
+		fn on_runtime_upgrade() -> Weight {
+			OutboundLanes::<TestRuntime, ()>::translate::<OldOutboundLaneData, _>(
+				|_, (latest_received_nonce, latest_generated_nonce)| {
+					Some(OutboundLaneData {
+						oldest_unpruned_nonce: latest_received_nonce + 1,
+						latest_received_nonce,
+						latest_generated_nonce,
+					})
+				},
+			);
+			Weight::zero()
+		}
+	}
+
+	/// Broken migration that forgets about pruned messages.
+	struct BrokenAddOldestUnprunedNonce;
+
+	impl OnRuntimeUpgrade for BrokenAddOldestUnprunedNonce {
+		fn on_runtime_upgrade() -> Weight {
+			OutboundLanes::<TestRuntime, ()>::translate::<OldOutboundLaneData, _>(
+				|_, (latest_received_nonce, latest_generated_nonce)| {
+					Some(OutboundLaneData {
+						oldest_unpruned_nonce: 1,
+						latest_received_nonce,
+						latest_generated_nonce,
+					})
+				},
+			);
+			Weight::zero()
+		}
+	}
+
+	fn prepare_old_storage() {
+		put_old_storage_value(
+			MESSAGES_PALLET_NAME,
+			"OutboundLanes",
+			&Blake2_128Concat::hash(&TEST_LANE_ID.encode()),
+			&(5u64, 10u64),
+		);
+		for nonce in 6..=10 {
+			OutboundMessages::<TestRuntime, ()>::insert(
+				MessageKey { lane_id: TEST_LANE_ID, nonce },
+				StoredMessagePayload::<TestRuntime, ()>::try_from(vec![42]).unwrap(),
+			);
+		}
+	}
+
+	fn ensure_invariants(before: &[StorageSnapshot], after: &[StorageSnapshot]) {
+		ensure_grandpa_invariants::<TestRuntime, ()>();
+		ensure_messages_invariants::<TestRuntime, ()>();
+		ensure_relayers_invariants::<TestRuntime>();
+
+		// messages are not touched by the migration
+		assert_eq!(
+			before[0].storage_entries("OutboundMessages"),
+			after[0].storage_entries("OutboundMessages"),
+		);
+	}
+
+	#[test]
+	fn valid_migration_preserves_invariants() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			run_migration_test::<AddOldestUnprunedNonce>(
+				&[MESSAGES_PALLET_NAME],
+				prepare_old_storage,
+				ensure_invariants,
+			);
+
+			assert_eq!(
+				OutboundLanes::<TestRuntime, ()>::get(TEST_LANE_ID),
+				OutboundLaneData {
+					oldest_unpruned_nonce: 6,
+					latest_received_nonce: 5,
+					latest_generated_nonce: 10,
+				},
+			);
+		});
+	}
+
+	#[test]
+	#[should_panic(expected = "Message 1 is missing from the outbound lane")]
+	fn broken_migration_is_detected() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			run_migration_test::<BrokenAddOldestUnprunedNonce>(
+				&[MESSAGES_PALLET_NAME],
+				prepare_old_storage,
+				ensure_invariants,
+			);
+		});
+	}
+}
@@ -29,13 +29,16 @@ use crate::messages::{
 		FromThisChainMessageVerifier, TargetHeaderChainAdapter,
 	},
 	target::{FromBridgedChainMessagePayload, SourceHeaderChainAdapter},
-	BridgedChainWithMessages, HashOf, MessageBridge, ThisChainWithMessages,
+	BridgedChainWithMessages, HashOf, InboundXcmLimits, MessageBridge, ThisChainWithMessages,
 };
 
 use bp_header_chain::{ChainWithGrandpa, HeaderChain};
 use bp_messages::{target_chain::ForbidInboundMessages, LaneId, MessageNonce};
 use bp_parachains::SingleParaStoredHeaderDataBuilder;
-use bp_runtime::{Chain, ChainId, Parachain, UnderlyingChainProvider};
+use bp_runtime::{
+	account_derivation::AccountDerivationVersion, Chain, ChainId, Parachain,
+	UnderlyingChainProvider,
+};
 use codec::{Decode, Encode};
 use frame_support::{
 	parameter_types,
@@ -44,9 +47,10 @@ use frame_support::{
 use pallet_transaction_payment::Multiplier;
 use sp_runtime::{
 	testing::H256,
-	traits::{BlakeTwo256, ConstU32, ConstU64, ConstU8, IdentityLookup},
+	traits::{BlakeTwo256, ConstBool, ConstU32, ConstU64, ConstU8, IdentityLookup},
 	FixedPointNumber, Perquintill,
 };
+use std::cell::RefCell;
 
 /// Account identifier at `ThisChain`.
 pub type ThisChainAccountId = u64;
@@ -96,6 +100,52 @@ pub const BRIDGED_CHAIN_MAX_EXTRINSIC_WEIGHT: usize = 2048;
 /// Maximal extrinsic size at the `BridgedChain`.
 pub const BRIDGED_CHAIN_MAX_EXTRINSIC_SIZE: u32 = 1024;
 
+/// Parameters of mock chains, that may be changed by tests.
+#[derive(Clone, Copy, Debug)]
+pub struct MockChainParams {
+	/// Maximal extrinsic size at mock chains.
+	pub max_extrinsic_size: u32,
+	/// Maximal extrinsic weight at mock chains.
+	pub max_extrinsic_weight: Weight,
+	/// Minimal dispatch weight (which is the payload length) of message, sent to the
+	/// `BridgedChain`.
+	pub min_message_dispatch_weight: usize,
+	/// Maximal dispatch weight (which is the payload length) of message, sent to the
+	/// `BridgedChain`.
+	pub max_message_dispatch_weight: usize,
+	/// Maximal number of queued messages at the test lane.
+	pub max_pending_messages: MessageNonce,
+}
+
+impl Default for MockChainParams {
+	fn default() -> Self {
+		MockChainParams {
+			max_extrinsic_size: BRIDGED_CHAIN_MAX_EXTRINSIC_SIZE,
+			max_extrinsic_weight: Weight::zero(),
+			min_message_dispatch_weight: BRIDGED_CHAIN_MIN_EXTRINSIC_WEIGHT,
+			max_message_dispatch_weight: BRIDGED_CHAIN_MAX_EXTRINSIC_WEIGHT,
+			max_pending_messages: MAXIMAL_PENDING_MESSAGES_AT_TEST_LANE,
+		}
+	}
+}
+
+thread_local! {
+	static MOCK_CHAIN_PARAMS: RefCell<MockChainParams> = RefCell::new(Default::default());
+}
+
+/// Returns current parameters of mock chains.
+pub fn mock_chain_params() -> MockChainParams {
+	MOCK_CHAIN_PARAMS.with(|params| *params.borrow())
+}
+
+/// Run test with given parameters of mock chains.
+pub fn with_mock_chain_params<R>(params: MockChainParams, test: impl FnOnce() -> R) -> R {
+	let previous_params = MOCK_CHAIN_PARAMS.with(|p| p.replace(params));
+	let result = test();
+	MOCK_CHAIN_PARAMS.with(|p| p.replace(previous_params));
+	result
+}
+
 frame_support::construct_runtime! {
 	pub enum TestRuntime where
 		Block = ThisChainBlock,
@@ -124,6 +174,9 @@ parameter_types! {
 	pub const BridgedParasPalletName: &'static str = "Paras";
 	pub const ExistentialDeposit: ThisChainBalance = 500;
 	pub const DbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 2 };
+	pub const MaxDeferredDispatchWeightPerBlock: Weight = Weight::from_parts(1_000_000, 0);
+	pub const RelayersAccountDerivationVersion: AccountDerivationVersion =
+		AccountDerivationVersion::V0;
 	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
 	pub const TransactionBaseFee: ThisChainBalance = 0;
 	pub const TransactionByteFee: ThisChainBalance = 1;
@@ -197,6 +250,7 @@ impl pallet_bridge_grandpa::Config for TestRuntime {
 	type BridgedChain = BridgedUnderlyingChain;
 	type MaxRequests = ConstU32<50>;
 	type HeadersToKeep = ConstU32<8>;
+	type UseHostSignatureVerification = ConstBool<false>;
 	type WeightInfo = pallet_bridge_grandpa::weights::BridgeWeight<TestRuntime>;
 }
 
@@ -206,6 +260,8 @@ impl pallet_bridge_parachains::Config for TestRuntime {
 	type ParasPalletName = BridgedParasPalletName;
 	type ParaStoredHeaderDataBuilder =
 		SingleParaStoredHeaderDataBuilder<BridgedUnderlyingParachain>;
+	type TrackedParaStoredHeaderDataBuilder = ();
+	type MaxTrackedParachains = ConstU32<0>;
 	type HeadsToKeep = ConstU32<8>;
 	type MaxParaHeadDataSize = ConstU32<1024>;
 	type WeightInfo = pallet_bridge_parachains::weights::BridgeWeight<TestRuntime>;
@@ -215,8 +271,13 @@ impl pallet_bridge_messages::Config for TestRuntime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_bridge_messages::weights::BridgeWeight<TestRuntime>;
 	type ActiveOutboundLanes = ActiveOutboundLanes;
+	type DynamicLanes = ();
+	type StaleLaneThreshold = ();
 	type MaxUnrewardedRelayerEntriesAtInboundLane = ConstU64<16>;
 	type MaxUnconfirmedMessagesAtInboundLane = ConstU64<16>;
+	type MaxDispatchWeightPerBlock = ();
+	type MaxDeferredMessagesPerLane = ConstU64<16>;
+	type MaxDeferredDispatchWeightPerBlock = MaxDeferredDispatchWeightPerBlock;
 
 	type MaximalOutboundPayloadSize = FromThisChainMaximalOutboundPayloadSize<OnThisChainBridge>;
 	type OutboundPayload = FromThisChainMessagePayload;
@@ -232,6 +293,7 @@ impl pallet_bridge_messages::Config for TestRuntime {
 		(),
 		ConstU64<100_000>,
 	>;
+	type OnMessagesDelivered = ();
 
 	type SourceHeaderChain = SourceHeaderChainAdapter<OnThisChainBridge>;
 	type MessageDispatch =
@@ -243,6 +305,7 @@ impl pallet_bridge_relayers::Config for TestRuntime {
 	type RuntimeEvent = RuntimeEvent;
 	type Reward = ThisChainBalance;
 	type PaymentProcedure = ();
+	type AccountDerivationVersion = RelayersAccountDerivationVersion;
 	type WeightInfo = ();
 }
 
@@ -259,6 +322,9 @@ impl MessageBridge for OnThisChainBridge {
 	type ThisChain = ThisChain;
 	type BridgedChain = BridgedChain;
 	type BridgedHeaderChain = pallet_bridge_grandpa::GrandpaChainHeaders<TestRuntime, ()>;
+
+	const INBOUND_XCM_LIMITS: InboundXcmLimits =
+		InboundXcmLimits { max_instructions: 4, max_depth: 16, max_encoded_size: 1024 };
 }
 
 /// Bridge that is deployed on `BridgedChain` and allows sending/receiving messages to/from
@@ -313,11 +379,11 @@ impl Chain for ThisUnderlyingChain {
 	type Signature = sp_runtime::MultiSignature;
 
 	fn max_extrinsic_size() -> u32 {
-		BRIDGED_CHAIN_MAX_EXTRINSIC_SIZE
+		mock_chain_params().max_extrinsic_size
 	}
 
 	fn max_extrinsic_weight() -> Weight {
-		Weight::zero()
+		mock_chain_params().max_extrinsic_weight
 	}
 }
 
@@ -337,7 +403,7 @@ impl ThisChainWithMessages for ThisChain {
 	}
 
 	fn maximal_pending_messages_at_outbound_lane() -> MessageNonce {
-		MAXIMAL_PENDING_MESSAGES_AT_TEST_LANE
+		mock_chain_params().max_pending_messages
 	}
 }
 
@@ -366,10 +432,10 @@ impl Chain for BridgedUnderlyingChain {
 	type Signature = sp_runtime::MultiSignature;
 
 	fn max_extrinsic_size() -> u32 {
-		BRIDGED_CHAIN_MAX_EXTRINSIC_SIZE
+		mock_chain_params().max_extrinsic_size
 	}
 	fn max_extrinsic_weight() -> Weight {
-		Weight::zero()
+		mock_chain_params().max_extrinsic_weight
 	}
 }
 
@@ -392,10 +458,10 @@ impl Chain for BridgedUnderlyingParachain {
 	type Signature = sp_runtime::MultiSignature;
 
 	fn max_extrinsic_size() -> u32 {
-		BRIDGED_CHAIN_MAX_EXTRINSIC_SIZE
+		mock_chain_params().max_extrinsic_size
 	}
 	fn max_extrinsic_weight() -> Weight {
-		Weight::zero()
+		mock_chain_params().max_extrinsic_weight
 	}
 }
 
@@ -425,7 +491,8 @@ impl ThisChainWithMessages for BridgedChain {
 
 impl BridgedChainWithMessages for BridgedChain {
 	fn verify_dispatch_weight(message_payload: &[u8]) -> bool {
-		message_payload.len() >= BRIDGED_CHAIN_MIN_EXTRINSIC_WEIGHT &&
-			message_payload.len() <= BRIDGED_CHAIN_MAX_EXTRINSIC_WEIGHT
+		let params = mock_chain_params();
+		message_payload.len() >= params.min_message_dispatch_weight &&
+			message_payload.len() <= params.max_message_dispatch_weight
 	}
 }
@@ -58,6 +58,7 @@ do
 		for i in $(seq 0 $MAX_UNCONFIRMED_MESSAGES_AT_INBOUND_LANE);
 		do
 			$SEND_MESSAGE \
+				--no-wait \
 				$EXTRA_ARGS \
 				raw $BATCH_PAYLOAD
 		done
@@ -0,0 +1,22 @@
+#!/bin/bash
+set -xeu
+
+sleep 15
+
+/home/user/substrate-relay init-bridge millau-to-rialto-beefy \
+	--source-host millau-node-alice \
+	--source-port 9944 \
+	--target-host rialto-node-alice \
+	--target-port 9944 \
+	--target-signer //Sudo
+
+# Give chain a little bit of time to process initialization transaction
+sleep 6
+
+/home/user/substrate-relay relay-headers millau-to-rialto-beefy \
+	--source-host millau-node-alice \
+	--source-port 9944 \
+	--target-host rialto-node-alice \
+	--target-port 9944 \
+	--target-signer //Millau.BeefyHeadersRelay \
+	--prometheus-host=0.0.0.0
@@ -0,0 +1,16 @@
+#!/bin/bash
+set -xeu
+
+sleep 15
+
+MESSAGE_LANE=${MSG_EXCHANGE_GEN_LANE:-00000000}
+
+/home/user/substrate-relay relay-messages millau-to-rialto-parachain \
+	--lane $MESSAGE_LANE \
+	--source-host millau-node-bob \
+	--source-port 9944 \
+	--source-signer //RialtoParachain.OutboundMessagesRelay.Lane00000000 \
+	--target-host rialto-parachain-collator-bob \
+	--target-port 9944 \
+	--target-signer //Millau.InboundMessagesRelay.Lane00000000 \
+	--prometheus-host=0.0.0.0
@@ -0,0 +1,16 @@
+#!/bin/bash
+set -xeu
+
+sleep 15
+
+MESSAGE_LANE=${MSG_EXCHANGE_GEN_LANE:-00000000}
+
+/home/user/substrate-relay relay-messages rialto-parachain-to-millau \
+	--lane $MESSAGE_LANE \
+	--source-host rialto-parachain-collator-bob \
+	--source-port 9944 \
+	--source-signer //Millau.OutboundMessagesRelay.Lane00000000 \
+	--target-host millau-node-bob \
+	--target-port 9944 \
+	--target-signer //RialtoParachain.InboundMessagesRelay.Lane00000000 \
+	--prometheus-host=0.0.0.0
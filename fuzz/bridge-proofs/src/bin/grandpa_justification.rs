@@ -0,0 +1,72 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! GRANDPA justification verification fuzzer.
+
+#![warn(missing_docs)]
+
+use bp_header_chain::justification::{verify_justification, GrandpaJustification};
+use bp_test_utils::{
+	make_default_justification, test_header, voter_set, TEST_GRANDPA_ROUND, TEST_GRANDPA_SET_ID,
+};
+use bridge_proofs_fuzzer::*;
+use codec::{Decode, Encode};
+use honggfuzz::fuzz;
+use sp_runtime::traits::Header as _;
+
+fn verify(target: &TestHeader, raw_justification: &[u8]) {
+	let justification =
+		match GrandpaJustification::<TestHeader>::decode(&mut &raw_justification[..]) {
+			Ok(justification) => justification,
+			Err(_) => return,
+		};
+
+	let result = verify_justification::<TestHeader>(
+		(target.hash(), *target.number()),
+		TEST_GRANDPA_SET_ID,
+		&voter_set(),
+		&justification,
+	);
+
+	// the justification may only be accepted if it is finalizing the same header, using
+	// signatures of the same round
+	if result.is_ok() {
+		assert_eq!(justification.round, TEST_GRANDPA_ROUND);
+		assert_eq!(justification.commit.target_hash, target.hash());
+		assert_eq!(justification.commit.target_number, *target.number());
+	}
+}
+
+fn run_fuzzer() {
+	fuzz!(|input: (Vec<u8>, Vec<Mutation>)| {
+		let (arbitrary_justification, mutations) = input;
+		let target = test_header::<TestHeader>(1);
+		let valid_justification = make_default_justification(&target).encode();
+
+		verify(&target, &arbitrary_justification);
+
+		log::info!("Verifying valid justification with mutations {:?}", mutations);
+		verify(&target, &mutate_bytes(valid_justification, &mutations));
+	})
+}
+
+fn main() {
+	env_logger::init();
+
+	loop {
+		run_fuzzer();
+	}
+}
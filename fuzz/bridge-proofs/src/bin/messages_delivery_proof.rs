@@ -0,0 +1,85 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Messages delivery proof verification fuzzer.
+
+#![warn(missing_docs)]
+
+use bp_messages::{storage_keys, InboundLaneData, UnrewardedRelayer};
+use bp_runtime::RawStorageProof;
+use bridge_proofs_fuzzer::*;
+use bridge_runtime_common::messages::{
+	source::{
+		verify_messages_delivery_proof, FromBridgedChainMessagesDeliveryProof,
+		ParsedMessagesDeliveryProofFromBridgedChain,
+	},
+	Error,
+};
+use codec::Encode;
+use honggfuzz::fuzz;
+
+fn inbound_lane_data() -> InboundLaneData<u64> {
+	InboundLaneData {
+		relayers: vec![UnrewardedRelayer { relayer: 42, messages: Default::default() }].into(),
+		last_confirmed_nonce: 0,
+	}
+}
+
+fn prepare_valid_proof() -> RawStorageProof {
+	let (state_root, storage_proof) = prepare_storage_proof(vec![(
+		storage_keys::inbound_lane_data_key(TEST_MESSAGES_PALLET_NAME, &TEST_LANE_ID).0,
+		inbound_lane_data().encode(),
+	)]);
+	set_state_root(state_root);
+	storage_proof
+}
+
+fn verify(
+	storage_proof: RawStorageProof,
+) -> Result<ParsedMessagesDeliveryProofFromBridgedChain<TestBridge>, Error> {
+	verify_messages_delivery_proof::<TestBridge>(FromBridgedChainMessagesDeliveryProof {
+		bridged_header_hash: TEST_HEADER_HASH,
+		storage_proof,
+		lane: TEST_LANE_ID,
+	})
+}
+
+fn run_fuzzer() {
+	fuzz!(|input: (RawStorageProof, Vec<Mutation>)| {
+		let (arbitrary_proof, mutations) = input;
+		let valid_proof = prepare_valid_proof();
+		let expected = verify(valid_proof.clone()).expect("Valid proof passed; qed");
+
+		// arbitrary proof may only be accepted if it proves the same lane state
+		if let Ok(proved) = verify(arbitrary_proof) {
+			assert_eq!(proved, expected);
+		}
+
+		// mutated proof may only be accepted if it proves the same lane state
+		log::info!("Verifying valid proof with mutations {:?}", mutations);
+		if let Ok(proved) = verify(mutate_storage_proof(valid_proof, &mutations)) {
+			assert_eq!(proved, expected);
+		}
+	})
+}
+
+fn main() {
+	env_logger::init();
+
+	loop {
+		run_fuzzer();
+	}
+}
@@ -0,0 +1,97 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Messages proof verification fuzzer.
+
+#![warn(missing_docs)]
+
+use bp_messages::{storage_keys, target_chain::ProvedMessages, Message, OutboundLaneData};
+use bp_runtime::RawStorageProof;
+use bridge_proofs_fuzzer::*;
+use bridge_runtime_common::messages::{
+	target::{verify_messages_proof, FromBridgedChainMessagesProof},
+	Error,
+};
+use codec::Encode;
+use honggfuzz::fuzz;
+
+/// Number of messages in the valid proof.
+const MESSAGES_COUNT: u64 = 2;
+
+fn outbound_lane_data() -> OutboundLaneData {
+	OutboundLaneData {
+		oldest_unpruned_nonce: 1,
+		latest_received_nonce: 0,
+		latest_generated_nonce: MESSAGES_COUNT,
+	}
+}
+
+fn prepare_valid_proof() -> RawStorageProof {
+	let mut entries = (1..=MESSAGES_COUNT)
+		.map(|nonce| {
+			let key = storage_keys::message_key(TEST_MESSAGES_PALLET_NAME, &TEST_LANE_ID, nonce);
+			(key.0, vec![nonce as u8; 32].encode())
+		})
+		.collect::<Vec<_>>();
+	entries.push((
+		storage_keys::outbound_lane_data_key(TEST_MESSAGES_PALLET_NAME, &TEST_LANE_ID).0,
+		outbound_lane_data().encode(),
+	));
+
+	let (state_root, storage_proof) = prepare_storage_proof(entries);
+	set_state_root(state_root);
+	storage_proof
+}
+
+fn verify(storage_proof: RawStorageProof) -> Result<ProvedMessages<Message>, Error> {
+	verify_messages_proof::<TestBridge>(
+		FromBridgedChainMessagesProof {
+			bridged_header_hash: TEST_HEADER_HASH,
+			storage_proof,
+			lane: TEST_LANE_ID,
+			nonces_start: 1,
+			nonces_end: MESSAGES_COUNT,
+		},
+		MESSAGES_COUNT as _,
+	)
+}
+
+fn run_fuzzer() {
+	fuzz!(|input: (RawStorageProof, Vec<Mutation>)| {
+		let (arbitrary_proof, mutations) = input;
+		let valid_proof = prepare_valid_proof();
+		let expected = verify(valid_proof.clone()).expect("Valid proof passed; qed");
+
+		// arbitrary proof may only be accepted if it proves the same messages
+		if let Ok(proved) = verify(arbitrary_proof) {
+			assert_eq!(proved, expected);
+		}
+
+		// mutated proof may only be accepted if it proves the same messages
+		log::info!("Verifying valid proof with mutations {:?}", mutations);
+		if let Ok(proved) = verify(mutate_storage_proof(valid_proof, &mutations)) {
+			assert_eq!(proved, expected);
+		}
+	})
+}
+
+fn main() {
+	env_logger::init();
+
+	loop {
+		run_fuzzer();
+	}
+}
@@ -0,0 +1,90 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Parachain heads proof verification fuzzer.
+//!
+//! The proof is verified the same way it is verified by the `pallet-bridge-parachains`.
+
+#![warn(missing_docs)]
+
+use bp_header_chain::HeaderChain;
+use bp_parachains::parachain_head_storage_key_at_source;
+use bp_polkadot_core::parachains::{ParaHead, ParaHeadsProof, ParaId};
+use bp_runtime::{RawStorageProof, StorageProofError};
+use bridge_proofs_fuzzer::*;
+use codec::Encode;
+use honggfuzz::fuzz;
+
+/// Name of the paras pallet at the test relay chain.
+const PARAS_PALLET_NAME: &str = "Paras";
+/// Identifier of the test parachain.
+const PARACHAIN: ParaId = ParaId(1000);
+
+fn parachain_head() -> ParaHead {
+	ParaHead(vec![42; 64])
+}
+
+fn prepare_valid_proof() -> RawStorageProof {
+	let (state_root, storage_proof) = prepare_storage_proof(vec![(
+		parachain_head_storage_key_at_source(PARAS_PALLET_NAME, PARACHAIN).0,
+		parachain_head().encode(),
+	)]);
+	set_state_root(state_root);
+	storage_proof
+}
+
+fn verify(proof: ParaHeadsProof) -> Option<Result<Option<ParaHead>, StorageProofError>> {
+	TestHeaderChain::parse_finalized_storage_proof(TEST_HEADER_HASH, proof.0, |mut storage| {
+		let parachain_head_key = parachain_head_storage_key_at_source(PARAS_PALLET_NAME, PARACHAIN);
+		let parachain_head = storage.read_and_decode_value(parachain_head_key.0.as_ref())?;
+		storage.ensure_no_unused_nodes()?;
+		Ok(parachain_head)
+	})
+	.ok()
+}
+
+fn run_fuzzer() {
+	fuzz!(|input: (RawStorageProof, Vec<Mutation>)| {
+		let (arbitrary_proof, mutations) = input;
+		let valid_proof = prepare_valid_proof();
+		assert_eq!(
+			verify(ParaHeadsProof(valid_proof.clone())),
+			Some(Ok(Some(parachain_head()))),
+			"Valid proof passed; qed",
+		);
+
+		// arbitrary proof may only prove the same parachain head
+		if let Some(Ok(Some(proved))) = verify(ParaHeadsProof(arbitrary_proof)) {
+			assert_eq!(proved, parachain_head());
+		}
+
+		// mutated proof may only prove the same parachain head
+		log::info!("Verifying valid proof with mutations {:?}", mutations);
+		if let Some(Ok(Some(proved))) =
+			verify(ParaHeadsProof(mutate_storage_proof(valid_proof, &mutations)))
+		{
+			assert_eq!(proved, parachain_head());
+		}
+	})
+}
+
+fn main() {
+	env_logger::init();
+
+	loop {
+		run_fuzzer();
+	}
+}
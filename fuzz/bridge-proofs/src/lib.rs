@@ -0,0 +1,173 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Common code of bridge proofs fuzzers.
+
+#![warn(missing_docs)]
+
+use bp_header_chain::HeaderChain;
+use bp_messages::{LaneId, MessageNonce};
+use bp_runtime::{Chain, ChainId, RawStorageProof, UnderlyingChainProvider};
+use bridge_runtime_common::messages::{
+	BridgedChainWithMessages, MessageBridge, ThisChainWithMessages,
+};
+use frame_support::weights::Weight;
+use sp_core::H256;
+use sp_runtime::traits::BlakeTwo256;
+use sp_trie::{trie_types::TrieDBMutBuilderV1, LayoutV1, MemoryDB, TrieMut};
+use std::cell::RefCell;
+
+/// Lane that is used by fuzzers.
+pub const TEST_LANE_ID: LaneId = LaneId([0, 0, 0, 1]);
+/// Hash of the only finalized header of the test chain.
+pub const TEST_HEADER_HASH: H256 = H256::repeat_byte(1);
+/// Name of the messages pallet at the test chain.
+pub const TEST_MESSAGES_PALLET_NAME: &str = "BridgeMessages";
+
+/// Header of the test chain.
+pub type TestHeader = sp_runtime::generic::Header<u64, BlakeTwo256>;
+
+/// Mutation of the proof (or any other byte vector): `(item index, byte index, xor mask)`.
+pub type Mutation = (u16, u16, u8);
+
+thread_local! {
+	static STATE_ROOT: RefCell<H256> = RefCell::new(H256::zero());
+}
+
+/// Set state root of the finalized header of the test chain.
+pub fn set_state_root(state_root: H256) {
+	STATE_ROOT.with(|root| *root.borrow_mut() = state_root);
+}
+
+/// Prepare trie with given entries and return its root and proof of all its entries.
+pub fn prepare_storage_proof(entries: Vec<(Vec<u8>, Vec<u8>)>) -> (H256, RawStorageProof) {
+	let mut root = Default::default();
+	let mut mdb = MemoryDB::default();
+	{
+		let mut trie = TrieDBMutBuilderV1::<BlakeTwo256>::new(&mut mdb, &mut root).build();
+		for (key, value) in entries {
+			trie.insert(&key, &value).expect("TrieMut::insert should not fail in fuzzer");
+		}
+	}
+
+	let storage_proof = bp_runtime::record_all_trie_keys::<LayoutV1<BlakeTwo256>, _>(&mdb, &root)
+		.expect("record_all_trie_keys should not fail in fuzzer");
+	(root, storage_proof)
+}
+
+/// Apply mutations to given byte vector.
+pub fn mutate_bytes(mut bytes: Vec<u8>, mutations: &[Mutation]) -> Vec<u8> {
+	if bytes.is_empty() {
+		return bytes
+	}
+
+	for (_, byte_index, mask) in mutations {
+		let byte_index = *byte_index as usize % bytes.len();
+		bytes[byte_index] ^= mask;
+	}
+	bytes
+}
+
+/// Apply mutations to given storage proof.
+pub fn mutate_storage_proof(
+	mut storage_proof: RawStorageProof,
+	mutations: &[Mutation],
+) -> RawStorageProof {
+	if storage_proof.is_empty() {
+		return storage_proof
+	}
+
+	for mutation in mutations {
+		let node_index = mutation.0 as usize % storage_proof.len();
+		let node = std::mem::take(&mut storage_proof[node_index]);
+		storage_proof[node_index] = mutate_bytes(node, &[*mutation]);
+	}
+	storage_proof
+}
+
+/// Test chain.
+pub struct TestChain;
+
+impl Chain for TestChain {
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hasher = BlakeTwo256;
+	type Header = TestHeader;
+	type AccountId = u64;
+	type Balance = u64;
+	type Index = u32;
+	type Signature = sp_runtime::MultiSignature;
+
+	fn max_extrinsic_size() -> u32 {
+		u32::MAX
+	}
+
+	fn max_extrinsic_weight() -> Weight {
+		Weight::MAX
+	}
+}
+
+/// Test chain with messages.
+pub struct TestChainWithMessages;
+
+impl UnderlyingChainProvider for TestChainWithMessages {
+	type Chain = TestChain;
+}
+
+impl ThisChainWithMessages for TestChainWithMessages {
+	type RuntimeOrigin = ();
+	type RuntimeCall = ();
+
+	fn is_message_accepted(_origin: &(), _lane: &LaneId) -> bool {
+		true
+	}
+
+	fn maximal_pending_messages_at_outbound_lane() -> MessageNonce {
+		MessageNonce::MAX
+	}
+}
+
+impl BridgedChainWithMessages for TestChainWithMessages {
+	fn verify_dispatch_weight(_message_payload: &[u8]) -> bool {
+		true
+	}
+}
+
+/// Header chain that knows the only finalized header with [`TEST_HEADER_HASH`] hash.
+pub struct TestHeaderChain;
+
+impl HeaderChain<TestChain> for TestHeaderChain {
+	fn finalized_header_state_root(header_hash: H256) -> Option<H256> {
+		if header_hash == TEST_HEADER_HASH {
+			Some(STATE_ROOT.with(|root| *root.borrow()))
+		} else {
+			None
+		}
+	}
+}
+
+/// Test messages bridge.
+pub struct TestBridge;
+
+impl MessageBridge for TestBridge {
+	const THIS_CHAIN_ID: ChainId = *b"this";
+	const BRIDGED_CHAIN_ID: ChainId = *b"brdg";
+	const BRIDGED_MESSAGES_PALLET_NAME: &'static str = TEST_MESSAGES_PALLET_NAME;
+
+	type ThisChain = TestChainWithMessages;
+	type BridgedChain = TestChainWithMessages;
+	type BridgedHeaderChain = TestHeaderChain;
+}
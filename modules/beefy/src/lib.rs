@@ -31,7 +31,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use bp_beefy::{ChainWithBeefy, InitializationData};
+use bp_beefy::{ChainWithBeefy, ImportedCommitmentsInfoData, InitializationData};
 use sp_std::{boxed::Box, prelude::*};
 
 // Re-export in crate namespace for `construct_runtime!`
@@ -84,16 +84,6 @@ pub type ImportedCommitment<T, I> = bp_beefy::ImportedCommitment<
 	BridgedMmrHash<T, I>,
 >;
 
-/// Some high level info about the imported commitments.
-#[derive(codec::Encode, codec::Decode, scale_info::TypeInfo)]
-pub struct ImportedCommitmentsInfoData<BlockNumber> {
-	/// Best known block number, provided in a BEEFY commitment. However this is not
-	/// the best proven block. The best proven block is this block's parent.
-	best_block_number: BlockNumber,
-	/// The head of the `ImportedBlockNumbers` ring buffer.
-	next_block_number_index: u32,
-}
-
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -138,6 +138,7 @@ impl ChainWithBeefy for TestBridgedChain {
 	type MmrHash = <Keccak256 as Hash>::Output;
 	type BeefyMmrLeafExtra = ();
 	type AuthorityId = BeefyId;
+	type AuthoritySignature = bp_beefy::EcdsaValidatorSignature;
 	type AuthorityIdToMerkleLeaf = pallet_beefy_mmr::BeefyEcdsaToEthereum;
 }
 
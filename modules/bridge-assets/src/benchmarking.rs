@@ -0,0 +1,120 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the bridge assets pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::*;
+
+use bp_messages::{
+	target_chain::{DispatchMessageData, MessageDispatch},
+	MessageKey,
+};
+use frame_benchmarking::{account, benchmarks_instance_pallet};
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+use sp_runtime::traits::Zero;
+use sp_std::vec;
+
+const SEED: u32 = 0;
+
+/// Returns amount of tokens that is transferred in benchmarks.
+fn transfer_amount<T: Config<I>, I: 'static>() -> BalanceOf<T, I> {
+	T::NativeCurrency::minimum_balance().saturating_mul(100u32.into())
+}
+
+/// Returns asset metadata of maximal size.
+fn max_asset_metadata<T: Config<I>, I: 'static>() -> AssetMetadata<BalanceOf<T, I>> {
+	AssetMetadata {
+		symbol: vec![b'A'; MAX_ASSET_SYMBOL_LENGTH as usize]
+			.try_into()
+			.expect("symbol has maximal length; qed"),
+		decimals: 18,
+		min_balance: transfer_amount::<T, I>(),
+	}
+}
+
+benchmarks_instance_pallet! {
+	// Benchmark `transfer` call.
+	transfer {
+		let sender: T::AccountId = account("sender", 0, SEED);
+		let amount = transfer_amount::<T, I>();
+		T::NativeCurrency::make_free_balance_be(&sender, amount.saturating_mul(2u32.into()));
+		let recipient: T::BridgedAccountId = account("recipient", 0, SEED);
+	}: _(RawOrigin::Signed(sender), recipient, amount)
+	verify {
+		assert_eq!(T::NativeCurrency::free_balance(&Pallet::<T, I>::escrow_account()), amount);
+	}
+
+	// Benchmark `transfer_back` call.
+	transfer_back {
+		let sender: T::AccountId = account("sender", 0, SEED);
+		let amount = transfer_amount::<T, I>();
+		T::WrappedCurrency::make_free_balance_be(&sender, amount.saturating_mul(2u32.into()));
+		let recipient: T::BridgedAccountId = account("recipient", 0, SEED);
+	}: _(RawOrigin::Signed(sender.clone()), recipient, amount)
+	verify {
+		assert_eq!(T::WrappedCurrency::free_balance(&sender), amount);
+	}
+
+	// Benchmark `register_asset` call with the metadata of maximal size.
+	register_asset {
+		let origin = T::ManageOrigin::try_successful_origin()
+			.expect("benchmarks require successful manage origin");
+		let metadata = max_asset_metadata::<T, I>();
+	}: _<T::RuntimeOrigin>(origin, metadata.clone())
+	verify {
+		assert_eq!(NativeAssetMetadata::<T, I>::get(), Some(metadata));
+	}
+
+	// Benchmark `deregister_asset` call.
+	deregister_asset {
+		let origin = T::ManageOrigin::try_successful_origin()
+			.expect("benchmarks require successful manage origin");
+		NativeAssetMetadata::<T, I>::put(max_asset_metadata::<T, I>());
+	}: _<T::RuntimeOrigin>(origin)
+	verify {
+		assert_eq!(NativeAssetMetadata::<T, I>::get(), None);
+	}
+
+	// Benchmark dispatch of the worst-case inbound message: the `Mint` message that is below
+	// the minimal balance of the wrapped asset, so it is refunded to the sender.
+	receive_message {
+		WrappedAssetMetadata::<T, I>::put(max_asset_metadata::<T, I>());
+		let relayer: T::AccountId = account("relayer", 0, SEED);
+		let sender: T::BridgedAccountId = account("sender", 0, SEED);
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let message = DispatchMessage {
+			key: MessageKey { lane_id: T::BridgeLane::get(), nonce: 1 },
+			data: DispatchMessageData {
+				payload: Ok(BridgeAssetsPayload {
+					origin: T::BridgedPalletAccount::get(),
+					message: BridgeAssetsMessage::Mint {
+						sender,
+						recipient: recipient.clone(),
+						amount: T::WrappedCurrency::minimum_balance(),
+					},
+				}),
+			},
+		};
+	}: {
+		Pallet::<T, I>::dispatch(&relayer, message);
+	}
+	verify {
+		assert_eq!(T::WrappedCurrency::free_balance(&recipient), Zero::zero());
+	}
+}
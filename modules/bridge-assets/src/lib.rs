@@ -0,0 +1,1182 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Module that allows transferring fungible tokens between two bridged chains.
+//!
+//! The pallet must be deployed at both chains. It is using the lock-and-mint scheme:
+//!
+//! - when native tokens are transferred to the bridged chain, they're locked (transferred to the
+//!   pallet escrow account) at this chain and the `Mint` message is sent to the bridged chain. The
+//!   same amount of wrapped tokens is minted to the recipient account at the bridged chain;
+//!
+//! - when wrapped tokens are transferred back to the bridged chain, they're burned at this chain
+//!   and the `Unlock` message is sent to the bridged chain. The same amount of native tokens is
+//!   unlocked (transferred from the pallet escrow account) to the recipient account at the bridged
+//!   chain.
+//!
+//! If the message can't be dispatched at the bridged chain (e.g. because the recipient account
+//! can't accept tokens), the `Refund` message is sent back and the sender of the original
+//! transfer gets its tokens back.
+//!
+//! The origin chain is also the source of the asset metadata (symbol, decimals and minimal
+//! balance). Once the asset is registered (or its metadata is updated) at the origin chain, the
+//! `RegisterAsset` message is sent to the bridged chain, where the metadata of wrapped asset is
+//! updated. Deregistration is propagated the same way, using the `DeregisterAsset` message. While
+//! the wrapped asset is registered, transfers of amounts below its minimal balance are refunded.
+//!
+//! Messages are sent using the [`MessagesBridge`] (usually the `pallet-bridge-messages` instance)
+//! and the pallet itself is the [`MessageDispatch`] implementation for inbound messages. Every
+//! message carries the account of the pallet that has sent it. Only messages, delivered over the
+//! [`Config::BridgeLane`] and sent by the [`Config::BridgedPalletAccount`], are dispatched. Since
+//! the target chain can't see the actual message sender, the [`SenderVerifier`] must be used as
+//! (or be the part of) the lane message verifier at the source chain, so that no other account may
+//! send messages on behalf of the pallet. Balance types of both chains are expected to be the same.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+use bp_messages::{
+	source_chain::{LaneMessageVerifier, MessagesBridge},
+	target_chain::{DispatchMessage, MessageDispatch},
+	LaneId, MessageNonce, OutboundLaneData,
+};
+use bp_runtime::{messages::MessageDispatchResult, Size};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{ConstU32, Currency, ExistenceRequirement, Get, Imbalance, WithdrawReasons},
+	weights::Weight,
+	BoundedVec, PalletId, RuntimeDebug,
+};
+use frame_system::RawOrigin;
+use scale_info::TypeInfo;
+use sp_runtime::traits::{AccountIdConversion, Zero};
+use sp_std::marker::PhantomData;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+mod mock;
+
+pub mod benchmarking;
+pub mod weights;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "runtime::bridge-assets";
+
+/// Maximal length of the asset symbol.
+pub const MAX_ASSET_SYMBOL_LENGTH: u32 = 32;
+
+/// Balance type of given pallet configuration.
+pub type BalanceOf<T, I> =
+	<<T as Config<I>>::NativeCurrency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+/// Message that is sent by this chain to the bridged chain.
+pub type OutboundMessageOf<T, I> = BridgeAssetsMessage<
+	<T as frame_system::Config>::AccountId,
+	<T as Config<I>>::BridgedAccountId,
+	BalanceOf<T, I>,
+>;
+/// Message that is sent by the bridged chain to this chain.
+pub type InboundMessageOf<T, I> = BridgeAssetsMessage<
+	<T as Config<I>>::BridgedAccountId,
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T, I>,
+>;
+/// Payload of the message that is sent by this chain to the bridged chain.
+pub type OutboundPayloadOf<T, I> = BridgeAssetsPayload<
+	<T as frame_system::Config>::AccountId,
+	<T as Config<I>>::BridgedAccountId,
+	BalanceOf<T, I>,
+>;
+/// Payload of the message that is sent by the bridged chain to this chain.
+pub type InboundPayloadOf<T, I> = BridgeAssetsPayload<
+	<T as Config<I>>::BridgedAccountId,
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T, I>,
+>;
+
+/// Metadata of the asset, that is synchronized across the bridge.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct AssetMetadata<Balance> {
+	/// Asset symbol.
+	pub symbol: BoundedVec<u8, ConstU32<MAX_ASSET_SYMBOL_LENGTH>>,
+	/// Number of decimals.
+	pub decimals: u8,
+	/// Minimal amount of tokens that may be transferred over the bridge.
+	pub min_balance: Balance,
+}
+
+/// Hook that is called when metadata of the wrapped asset is changed.
+pub trait OnWrappedAssetMetadataChange<Balance> {
+	/// Called when the wrapped asset is registered or its metadata is updated.
+	fn on_registered(metadata: &AssetMetadata<Balance>);
+	/// Called when the wrapped asset is deregistered.
+	fn on_deregistered();
+}
+
+impl<Balance> OnWrappedAssetMetadataChange<Balance> for () {
+	fn on_registered(_metadata: &AssetMetadata<Balance>) {}
+	fn on_deregistered() {}
+}
+
+/// Kind of the transfer.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+pub enum TransferKind {
+	/// Native tokens of the source chain have been locked and wrapped tokens are minted at the
+	/// target chain.
+	Mint,
+	/// Wrapped tokens have been burned at the source chain and native tokens are unlocked at the
+	/// target chain.
+	Unlock,
+}
+
+/// Message that is sent between two pallet instances, deployed at bridged chains.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+pub enum BridgeAssetsMessage<SourceAccountId, TargetAccountId, Balance> {
+	/// Native tokens of the source chain have been locked. The same amount of wrapped tokens must
+	/// be minted to the recipient at the target chain.
+	Mint {
+		/// Account at the source chain that has sent tokens.
+		sender: SourceAccountId,
+		/// Account at the target chain that receives tokens.
+		recipient: TargetAccountId,
+		/// Amount of tokens.
+		amount: Balance,
+	},
+	/// Wrapped tokens have been burned at the source chain. The same amount of native tokens must
+	/// be unlocked to the recipient at the target chain.
+	Unlock {
+		/// Account at the source chain that has sent tokens.
+		sender: SourceAccountId,
+		/// Account at the target chain that receives tokens.
+		recipient: TargetAccountId,
+		/// Amount of tokens.
+		amount: Balance,
+	},
+	/// Transfer from the target chain has failed at the source chain. Tokens must be returned to
+	/// the sender of this transfer.
+	Refund {
+		/// Account at the target chain that has sent tokens.
+		recipient: TargetAccountId,
+		/// Amount of tokens.
+		amount: Balance,
+		/// Kind of the failed transfer.
+		kind: TransferKind,
+	},
+	/// Native asset of the source chain has been registered or its metadata has been updated.
+	/// Metadata of the wrapped asset must be updated at the target chain.
+	RegisterAsset {
+		/// Metadata of the asset.
+		metadata: AssetMetadata<Balance>,
+	},
+	/// Native asset of the source chain has been deregistered. The wrapped asset must be
+	/// deregistered at the target chain.
+	DeregisterAsset,
+}
+
+impl<SourceAccountId, TargetAccountId, Balance: Zero>
+	BridgeAssetsMessage<SourceAccountId, TargetAccountId, Balance>
+{
+	/// Returns true if the message transfers zero tokens.
+	pub fn is_zero_transfer(&self) -> bool {
+		match *self {
+			BridgeAssetsMessage::Mint { ref amount, .. } |
+			BridgeAssetsMessage::Unlock { ref amount, .. } |
+			BridgeAssetsMessage::Refund { ref amount, .. } => amount.is_zero(),
+			BridgeAssetsMessage::RegisterAsset { .. } | BridgeAssetsMessage::DeregisterAsset =>
+				false,
+		}
+	}
+}
+
+/// Payload of the message that is sent between two pallet instances.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+pub struct BridgeAssetsPayload<SourceAccountId, TargetAccountId, Balance> {
+	/// Account of the pallet instance at the source chain that has sent the message.
+	pub origin: SourceAccountId,
+	/// The message itself.
+	pub message: BridgeAssetsMessage<SourceAccountId, TargetAccountId, Balance>,
+}
+
+impl<SourceAccountId, TargetAccountId, Balance> Size
+	for BridgeAssetsPayload<SourceAccountId, TargetAccountId, Balance>
+where
+	Self: Encode,
+{
+	fn size(&self) -> u32 {
+		self.encoded_size() as _
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Native currency of this chain. Tokens are locked at this chain when they're sent to
+		/// the bridged chain.
+		type NativeCurrency: Currency<Self::AccountId>;
+		/// Wrapped native currency of the bridged chain. Tokens are minted at this chain when
+		/// they're locked at the bridged chain.
+		type WrappedCurrency: Currency<Self::AccountId, Balance = BalanceOf<Self, I>>;
+		/// Pallet identifier, used to derive the escrow account, where locked tokens are stored.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Account identifier at the bridged chain.
+		type BridgedAccountId: Parameter;
+		/// Account of the pallet instance at the bridged chain (its escrow account). Inbound
+		/// messages, sent by other accounts, are rejected.
+		type BridgedPalletAccount: Get<Self::BridgedAccountId>;
+		/// Lane that is used to exchange messages with the bridged chain.
+		#[pallet::constant]
+		type BridgeLane: Get<LaneId>;
+		/// Messages bridge that is used to send messages to the bridged chain.
+		type MessagesBridge: MessagesBridge<Self::RuntimeOrigin, OutboundPayloadOf<Self, I>>;
+
+		/// Origin that is allowed to manage metadata of the native asset.
+		type ManageOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Hook that is called when metadata of the wrapped asset is changed.
+		type OnWrappedAssetMetadataChange: OnWrappedAssetMetadataChange<BalanceOf<Self, I>>;
+
+		/// Benchmarks results from runtime we're plugged into.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Lock native tokens of this chain and mint the same amount of wrapped tokens to the
+		/// recipient at the bridged chain.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::transfer())]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			recipient: T::BridgedAccountId,
+			amount: BalanceOf<T, I>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T, I>::ZeroAmount);
+
+			T::NativeCurrency::transfer(
+				&sender,
+				&Self::escrow_account(),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)
+			.map_err(|_| Error::<T, I>::FailedToLockTokens)?;
+
+			let nonce = Self::send_message(BridgeAssetsMessage::Mint {
+				sender: sender.clone(),
+				recipient: recipient.clone(),
+				amount,
+			})?;
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Locked {:?} tokens of {:?}. Sent message {} to mint them to {:?}",
+				amount,
+				sender,
+				nonce,
+				recipient,
+			);
+
+			Self::deposit_event(Event::TokensLocked { sender, recipient, amount, nonce });
+			Ok(())
+		}
+
+		/// Burn wrapped tokens at this chain and unlock the same amount of native tokens to the
+		/// recipient at the bridged chain.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::transfer_back())]
+		pub fn transfer_back(
+			origin: OriginFor<T>,
+			recipient: T::BridgedAccountId,
+			amount: BalanceOf<T, I>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T, I>::ZeroAmount);
+
+			// burned tokens are removed from the total issuance when imbalance is dropped
+			T::WrappedCurrency::withdraw(
+				&sender,
+				amount,
+				WithdrawReasons::TRANSFER,
+				ExistenceRequirement::KeepAlive,
+			)
+			.map_err(|_| Error::<T, I>::FailedToBurnTokens)?;
+
+			let nonce = Self::send_message(BridgeAssetsMessage::Unlock {
+				sender: sender.clone(),
+				recipient: recipient.clone(),
+				amount,
+			})?;
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Burned {:?} wrapped tokens of {:?}. Sent message {} to unlock them to {:?}",
+				amount,
+				sender,
+				nonce,
+				recipient,
+			);
+
+			Self::deposit_event(Event::TokensBurned { sender, recipient, amount, nonce });
+			Ok(())
+		}
+
+		/// Register the native asset or update its metadata and send the updated metadata to the
+		/// bridged chain.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::register_asset())]
+		pub fn register_asset(
+			origin: OriginFor<T>,
+			metadata: AssetMetadata<BalanceOf<T, I>>,
+		) -> DispatchResult {
+			T::ManageOrigin::ensure_origin(origin)?;
+
+			NativeAssetMetadata::<T, I>::put(metadata.clone());
+			let nonce = Self::send_message(BridgeAssetsMessage::RegisterAsset {
+				metadata: metadata.clone(),
+			})?;
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Registered native asset with metadata {:?}. Sent message {}",
+				metadata,
+				nonce,
+			);
+
+			Self::deposit_event(Event::NativeAssetRegistered { metadata, nonce });
+			Ok(())
+		}
+
+		/// Deregister the native asset and deregister the wrapped asset at the bridged chain.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::deregister_asset())]
+		pub fn deregister_asset(origin: OriginFor<T>) -> DispatchResult {
+			T::ManageOrigin::ensure_origin(origin)?;
+
+			NativeAssetMetadata::<T, I>::take().ok_or(Error::<T, I>::AssetNotRegistered)?;
+			let nonce = Self::send_message(BridgeAssetsMessage::DeregisterAsset)?;
+
+			log::trace!(target: LOG_TARGET, "Deregistered native asset. Sent message {}", nonce);
+
+			Self::deposit_event(Event::NativeAssetDeregistered { nonce });
+			Ok(())
+		}
+	}
+
+	/// Metadata of the native asset of this chain.
+	#[pallet::storage]
+	#[pallet::getter(fn native_asset_metadata)]
+	pub type NativeAssetMetadata<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, AssetMetadata<BalanceOf<T, I>>, OptionQuery>;
+
+	/// Metadata of the wrapped asset, received from the bridged chain.
+	#[pallet::storage]
+	#[pallet::getter(fn wrapped_asset_metadata)]
+	pub type WrappedAssetMetadata<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, AssetMetadata<BalanceOf<T, I>>, OptionQuery>;
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Returns account, where locked tokens are stored.
+		pub fn escrow_account() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// Send message to the bridged chain.
+		fn send_message(message: OutboundMessageOf<T, I>) -> Result<MessageNonce, Error<T, I>> {
+			let origin = Self::escrow_account();
+			T::MessagesBridge::send_message(
+				RawOrigin::Signed(origin.clone()).into(),
+				T::BridgeLane::get(),
+				BridgeAssetsPayload { origin, message },
+			)
+			.map(|artifacts| artifacts.nonce)
+			.map_err(|e| {
+				log::debug!(
+					target: LOG_TARGET,
+					"Failed to send message over lane {:?}: {:?}",
+					T::BridgeLane::get(),
+					e,
+				);
+				Error::<T, I>::FailedToSendMessage
+			})
+		}
+
+		/// Mint wrapped tokens to the recipient.
+		fn mint(recipient: &T::AccountId, amount: BalanceOf<T, I>) -> bool {
+			// minted tokens are added to the total issuance when imbalance is dropped
+			T::WrappedCurrency::deposit_creating(recipient, amount).peek() == amount
+		}
+
+		/// Unlock native tokens to the recipient.
+		fn unlock(recipient: &T::AccountId, amount: BalanceOf<T, I>) -> bool {
+			T::NativeCurrency::transfer(
+				&Self::escrow_account(),
+				recipient,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)
+			.is_ok()
+		}
+
+		/// Returns true if the amount may be minted, according to the wrapped asset metadata.
+		fn is_above_min_balance(amount: BalanceOf<T, I>) -> bool {
+			WrappedAssetMetadata::<T, I>::get()
+				.map(|metadata| amount >= metadata.min_balance)
+				.unwrap_or(true)
+		}
+
+		/// Dispatch message from the bridged chain.
+		fn dispatch_message(lane: LaneId, nonce: MessageNonce, message: InboundMessageOf<T, I>) {
+			match message {
+				BridgeAssetsMessage::Mint { sender, recipient, amount } =>
+					if Self::is_above_min_balance(amount) && Self::mint(&recipient, amount) {
+						Self::deposit_event(Event::TokensMinted { lane, nonce, recipient, amount });
+					} else {
+						Self::refund_failed_transfer(
+							lane,
+							nonce,
+							sender,
+							amount,
+							TransferKind::Mint,
+						);
+					},
+				BridgeAssetsMessage::Unlock { sender, recipient, amount } => {
+					if Self::unlock(&recipient, amount) {
+						Self::deposit_event(Event::TokensUnlocked {
+							lane,
+							nonce,
+							recipient,
+							amount,
+						});
+					} else {
+						Self::refund_failed_transfer(
+							lane,
+							nonce,
+							sender,
+							amount,
+							TransferKind::Unlock,
+						);
+					}
+				},
+				BridgeAssetsMessage::Refund { recipient, amount, kind } => {
+					// tokens have been locked or burned at this chain, so we need to unlock or
+					// mint them back
+					let is_refunded = match kind {
+						TransferKind::Mint => Self::unlock(&recipient, amount),
+						TransferKind::Unlock => Self::mint(&recipient, amount),
+					};
+
+					if is_refunded {
+						Self::deposit_event(Event::TokensRefunded {
+							lane,
+							nonce,
+							recipient,
+							amount,
+						});
+					} else {
+						log::error!(
+							target: LOG_TARGET,
+							"Failed to refund {:?} tokens to {:?} (message {:?}/{})",
+							amount,
+							recipient,
+							lane,
+							nonce,
+						);
+						Self::deposit_event(Event::RefundFailed { lane, nonce, recipient, amount });
+					}
+				},
+				BridgeAssetsMessage::RegisterAsset { metadata } => {
+					WrappedAssetMetadata::<T, I>::put(metadata.clone());
+					T::OnWrappedAssetMetadataChange::on_registered(&metadata);
+					Self::deposit_event(Event::WrappedAssetRegistered { lane, nonce, metadata });
+				},
+				BridgeAssetsMessage::DeregisterAsset => {
+					WrappedAssetMetadata::<T, I>::kill();
+					T::OnWrappedAssetMetadataChange::on_deregistered();
+					Self::deposit_event(Event::WrappedAssetDeregistered { lane, nonce });
+				},
+			}
+		}
+
+		/// Send refund message for the transfer that has failed at this chain.
+		fn refund_failed_transfer(
+			lane: LaneId,
+			nonce: MessageNonce,
+			sender: T::BridgedAccountId,
+			amount: BalanceOf<T, I>,
+			kind: TransferKind,
+		) {
+			log::debug!(
+				target: LOG_TARGET,
+				"Transfer {:?}/{} of {:?} tokens has failed. Refunding to {:?}",
+				lane,
+				nonce,
+				amount,
+				sender,
+			);
+
+			let refund_nonce =
+				Self::send_message(BridgeAssetsMessage::Refund { recipient: sender, amount, kind })
+					.ok();
+			Self::deposit_event(Event::TransferFailed { lane, nonce, refund_nonce });
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// Native tokens have been locked and the message to mint them at the bridged chain has
+		/// been sent.
+		TokensLocked {
+			/// Account that has sent tokens.
+			sender: T::AccountId,
+			/// Account at the bridged chain that receives tokens.
+			recipient: T::BridgedAccountId,
+			/// Amount of tokens.
+			amount: BalanceOf<T, I>,
+			/// Nonce of the sent message.
+			nonce: MessageNonce,
+		},
+		/// Wrapped tokens have been burned and the message to unlock them at the bridged chain
+		/// has been sent.
+		TokensBurned {
+			/// Account that has sent tokens.
+			sender: T::AccountId,
+			/// Account at the bridged chain that receives tokens.
+			recipient: T::BridgedAccountId,
+			/// Amount of tokens.
+			amount: BalanceOf<T, I>,
+			/// Nonce of the sent message.
+			nonce: MessageNonce,
+		},
+		/// Wrapped tokens have been minted.
+		TokensMinted {
+			/// Lane of the inbound message.
+			lane: LaneId,
+			/// Nonce of the inbound message.
+			nonce: MessageNonce,
+			/// Account that has received tokens.
+			recipient: T::AccountId,
+			/// Amount of tokens.
+			amount: BalanceOf<T, I>,
+		},
+		/// Native tokens have been unlocked.
+		TokensUnlocked {
+			/// Lane of the inbound message.
+			lane: LaneId,
+			/// Nonce of the inbound message.
+			nonce: MessageNonce,
+			/// Account that has received tokens.
+			recipient: T::AccountId,
+			/// Amount of tokens.
+			amount: BalanceOf<T, I>,
+		},
+		/// Transfer from the bridged chain has failed. The refund message has been sent.
+		TransferFailed {
+			/// Lane of the inbound message.
+			lane: LaneId,
+			/// Nonce of the inbound message.
+			nonce: MessageNonce,
+			/// Nonce of the refund message. `None` if we have failed to send the refund message.
+			refund_nonce: Option<MessageNonce>,
+		},
+		/// Tokens of the failed transfer have been refunded.
+		TokensRefunded {
+			/// Lane of the inbound message.
+			lane: LaneId,
+			/// Nonce of the inbound message.
+			nonce: MessageNonce,
+			/// Account that has received tokens.
+			recipient: T::AccountId,
+			/// Amount of tokens.
+			amount: BalanceOf<T, I>,
+		},
+		/// Failed to refund tokens of the failed transfer.
+		RefundFailed {
+			/// Lane of the inbound message.
+			lane: LaneId,
+			/// Nonce of the inbound message.
+			nonce: MessageNonce,
+			/// Account that should have received tokens.
+			recipient: T::AccountId,
+			/// Amount of tokens.
+			amount: BalanceOf<T, I>,
+		},
+		/// Native asset has been registered or its metadata has been updated. The message to
+		/// update metadata at the bridged chain has been sent.
+		NativeAssetRegistered {
+			/// Metadata of the asset.
+			metadata: AssetMetadata<BalanceOf<T, I>>,
+			/// Nonce of the sent message.
+			nonce: MessageNonce,
+		},
+		/// Native asset has been deregistered. The message to deregister it at the bridged chain
+		/// has been sent.
+		NativeAssetDeregistered {
+			/// Nonce of the sent message.
+			nonce: MessageNonce,
+		},
+		/// Wrapped asset has been registered or its metadata has been updated.
+		WrappedAssetRegistered {
+			/// Lane of the inbound message.
+			lane: LaneId,
+			/// Nonce of the inbound message.
+			nonce: MessageNonce,
+			/// Metadata of the asset.
+			metadata: AssetMetadata<BalanceOf<T, I>>,
+		},
+		/// Wrapped asset has been deregistered.
+		WrappedAssetDeregistered {
+			/// Lane of the inbound message.
+			lane: LaneId,
+			/// Nonce of the inbound message.
+			nonce: MessageNonce,
+		},
+		/// Inbound message has been delivered over unexpected lane, sent by unexpected account or
+		/// transfers zero tokens. It has been rejected.
+		MessageRejected {
+			/// Lane of the inbound message.
+			lane: LaneId,
+			/// Nonce of the inbound message.
+			nonce: MessageNonce,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// Failed to lock native tokens.
+		FailedToLockTokens,
+		/// Failed to burn wrapped tokens.
+		FailedToBurnTokens,
+		/// Failed to send message to the bridged chain.
+		FailedToSendMessage,
+		/// The native asset is not registered.
+		AssetNotRegistered,
+		/// Transfers of zero tokens are not allowed.
+		ZeroAmount,
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Returns true if the inbound message must be dispatched. Messages that are delivered over
+	/// other lanes, are sent by other accounts or are transferring zero tokens are rejected.
+	fn is_acceptable_message(lane: LaneId, payload: &InboundPayloadOf<T, I>) -> bool {
+		lane == T::BridgeLane::get() &&
+			payload.origin == T::BridgedPalletAccount::get() &&
+			!payload.message.is_zero_transfer()
+	}
+}
+
+impl<T: Config<I>, I: 'static> MessageDispatch<T::AccountId> for Pallet<T, I> {
+	type DispatchPayload = InboundPayloadOf<T, I>;
+	type DispatchLevelResult = ();
+
+	fn dispatch_weight(message: &mut DispatchMessage<Self::DispatchPayload>) -> Weight {
+		// rejected messages are not touching the storage
+		let is_acceptable = match message.data.payload {
+			Ok(ref payload) => Self::is_acceptable_message(message.key.lane_id, payload),
+			Err(_) => message.key.lane_id == T::BridgeLane::get(),
+		};
+		if !is_acceptable {
+			return Weight::zero()
+		}
+
+		// every message may lead to the token transfer and the refund message
+		T::WeightInfo::receive_message()
+	}
+
+	fn dispatch(
+		_relayer_account: &T::AccountId,
+		message: DispatchMessage<Self::DispatchPayload>,
+	) -> MessageDispatchResult<Self::DispatchLevelResult> {
+		let lane = message.key.lane_id;
+		let nonce = message.key.nonce;
+		match message.data.payload {
+			Ok(payload) if Self::is_acceptable_message(lane, &payload) =>
+				Self::dispatch_message(lane, nonce, payload.message),
+			Ok(payload) => {
+				log::error!(
+					target: LOG_TARGET,
+					"Rejected inbound message {:?}/{} from {:?}: {:?}",
+					lane,
+					nonce,
+					payload.origin,
+					payload.message,
+				);
+				Self::deposit_event(Event::MessageRejected { lane, nonce });
+			},
+			Err(e) => log::error!(
+				target: LOG_TARGET,
+				"Failed to decode inbound message {:?}/{}: {:?}",
+				lane,
+				nonce,
+				e,
+			),
+		}
+
+		MessageDispatchResult { unspent_weight: Weight::zero(), dispatch_level_result: () }
+	}
+}
+
+/// Lane message verifier that must be used at the source chain. It only accepts messages over the
+/// [`Config::BridgeLane`] if they're sent by the pallet escrow account and the `origin` field of
+/// the payload matches this account. Messages over other lanes are accepted.
+pub struct SenderVerifier<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> LaneMessageVerifier<T::RuntimeOrigin, OutboundPayloadOf<T, I>>
+	for SenderVerifier<T, I>
+{
+	type Error = &'static str;
+
+	fn verify_message(
+		submitter: &T::RuntimeOrigin,
+		lane: &LaneId,
+		_outbound_data: &OutboundLaneData,
+		payload: &OutboundPayloadOf<T, I>,
+	) -> Result<(), Self::Error> {
+		if *lane != T::BridgeLane::get() {
+			return Ok(())
+		}
+
+		let pallet_account = Pallet::<T, I>::escrow_account();
+		let submitter = frame_system::ensure_signed(submitter.clone())
+			.map_err(|_| "The bridge assets lane only accepts signed messages")?;
+		if submitter != pallet_account || payload.origin != pallet_account {
+			return Err("The bridge assets lane only accepts messages from the pallet account")
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+	use bp_messages::{target_chain::DispatchMessageData, MessageKey};
+	use frame_support::{assert_noop, assert_ok};
+
+	fn inbound_message(
+		lane_id: LaneId,
+		origin: AccountId,
+		message: InboundMessageOf<TestRuntime, ()>,
+	) -> DispatchMessage<InboundPayloadOf<TestRuntime, ()>> {
+		DispatchMessage {
+			key: MessageKey { lane_id, nonce: 1 },
+			data: DispatchMessageData { payload: Ok(BridgeAssetsPayload { origin, message }) },
+		}
+	}
+
+	fn dispatch(message: InboundMessageOf<TestRuntime, ()>) {
+		BridgeAssets::dispatch(&0, inbound_message(TEST_LANE, BRIDGED_PALLET_ACCOUNT, message));
+	}
+
+	fn assert_rejected(mut message: DispatchMessage<InboundPayloadOf<TestRuntime, ()>>) {
+		let lane = message.key.lane_id;
+		assert_eq!(BridgeAssets::dispatch_weight(&mut message), Weight::zero());
+		BridgeAssets::dispatch(&0, message);
+
+		assert_eq!(WrappedBalances::free_balance(RECIPIENT), 0);
+		assert_eq!(Balances::free_balance(RECIPIENT), 0);
+		assert!(sent_messages().is_empty());
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::BridgeAssets(Event::MessageRejected { lane, nonce: 1 }),
+		);
+	}
+
+	fn test_metadata(min_balance: Balance) -> AssetMetadata<Balance> {
+		AssetMetadata { symbol: b"TST".to_vec().try_into().unwrap(), decimals: 12, min_balance }
+	}
+
+	fn last_event() -> RuntimeEvent {
+		System::events().pop().unwrap().event
+	}
+
+	#[test]
+	fn transfer_locks_tokens_and_sends_mint_message() {
+		run_test(|| {
+			assert_ok!(BridgeAssets::transfer(RuntimeOrigin::signed(SENDER), RECIPIENT, 100));
+
+			assert_eq!(Balances::free_balance(SENDER), INITIAL_BALANCE - 100);
+			assert_eq!(Balances::free_balance(BridgeAssets::escrow_account()), 100);
+			assert_eq!(
+				sent_messages(),
+				vec![BridgeAssetsMessage::Mint {
+					sender: SENDER,
+					recipient: RECIPIENT,
+					amount: 100
+				}],
+			);
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::BridgeAssets(Event::TokensLocked {
+					sender: SENDER,
+					recipient: RECIPIENT,
+					amount: 100,
+					nonce: 1,
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_fails_if_tokens_can_not_be_locked() {
+		run_test(|| {
+			assert_noop!(
+				BridgeAssets::transfer(RuntimeOrigin::signed(SENDER), RECIPIENT, INITIAL_BALANCE),
+				Error::<TestRuntime, ()>::FailedToLockTokens,
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_fails_if_message_can_not_be_sent() {
+		run_test(|| {
+			reject_messages();
+			assert_noop!(
+				BridgeAssets::transfer(RuntimeOrigin::signed(SENDER), RECIPIENT, 100),
+				Error::<TestRuntime, ()>::FailedToSendMessage,
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_of_zero_tokens_is_rejected() {
+		run_test(|| {
+			assert_noop!(
+				BridgeAssets::transfer(RuntimeOrigin::signed(SENDER), RECIPIENT, 0),
+				Error::<TestRuntime, ()>::ZeroAmount,
+			);
+			assert_noop!(
+				BridgeAssets::transfer_back(RuntimeOrigin::signed(SENDER), RECIPIENT, 0),
+				Error::<TestRuntime, ()>::ZeroAmount,
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_back_burns_tokens_and_sends_unlock_message() {
+		run_test(|| {
+			let total_issuance = WrappedBalances::total_issuance();
+			assert_ok!(BridgeAssets::transfer_back(RuntimeOrigin::signed(SENDER), RECIPIENT, 100));
+
+			assert_eq!(WrappedBalances::free_balance(SENDER), INITIAL_BALANCE - 100);
+			assert_eq!(WrappedBalances::total_issuance(), total_issuance - 100);
+			assert_eq!(
+				sent_messages(),
+				vec![BridgeAssetsMessage::Unlock {
+					sender: SENDER,
+					recipient: RECIPIENT,
+					amount: 100
+				}],
+			);
+		});
+	}
+
+	#[test]
+	fn mint_message_mints_wrapped_tokens() {
+		run_test(|| {
+			let total_issuance = WrappedBalances::total_issuance();
+			dispatch(BridgeAssetsMessage::Mint {
+				sender: SENDER,
+				recipient: RECIPIENT,
+				amount: 100,
+			});
+
+			assert_eq!(WrappedBalances::free_balance(RECIPIENT), 100);
+			assert_eq!(WrappedBalances::total_issuance(), total_issuance + 100);
+			assert!(sent_messages().is_empty());
+		});
+	}
+
+	#[test]
+	fn unlock_message_unlocks_native_tokens() {
+		run_test(|| {
+			assert_ok!(BridgeAssets::transfer(RuntimeOrigin::signed(SENDER), RECIPIENT, 100));
+			dispatch(BridgeAssetsMessage::Unlock {
+				sender: SENDER,
+				recipient: RECIPIENT,
+				amount: 100,
+			});
+
+			assert_eq!(Balances::free_balance(RECIPIENT), 100);
+			assert_eq!(Balances::free_balance(BridgeAssets::escrow_account()), 0);
+		});
+	}
+
+	#[test]
+	fn failed_transfer_is_refunded() {
+		run_test(|| {
+			// there are no locked tokens, so unlock fails and refund message is sent
+			dispatch(BridgeAssetsMessage::Unlock {
+				sender: SENDER,
+				recipient: RECIPIENT,
+				amount: 100,
+			});
+			assert_eq!(Balances::free_balance(RECIPIENT), 0);
+			assert_eq!(
+				sent_messages(),
+				vec![BridgeAssetsMessage::Refund {
+					recipient: SENDER,
+					amount: 100,
+					kind: TransferKind::Unlock,
+				}],
+			);
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::BridgeAssets(Event::TransferFailed {
+					lane: TEST_LANE,
+					nonce: 1,
+					refund_nonce: Some(1),
+				}),
+			);
+
+			// wrapped tokens have been burned at the source chain, so they're minted back
+			dispatch(BridgeAssetsMessage::Refund {
+				recipient: RECIPIENT,
+				amount: 100,
+				kind: TransferKind::Unlock,
+			});
+			assert_eq!(WrappedBalances::free_balance(RECIPIENT), 100);
+		});
+	}
+
+	#[test]
+	fn refund_of_failed_mint_unlocks_native_tokens() {
+		run_test(|| {
+			assert_ok!(BridgeAssets::transfer(RuntimeOrigin::signed(SENDER), RECIPIENT, 100));
+			dispatch(BridgeAssetsMessage::Refund {
+				recipient: SENDER,
+				amount: 100,
+				kind: TransferKind::Mint,
+			});
+
+			assert_eq!(Balances::free_balance(SENDER), INITIAL_BALANCE);
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::BridgeAssets(Event::TokensRefunded {
+					lane: TEST_LANE,
+					nonce: 1,
+					recipient: SENDER,
+					amount: 100,
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn register_asset_sends_metadata_to_bridged_chain() {
+		run_test(|| {
+			assert_noop!(
+				BridgeAssets::register_asset(RuntimeOrigin::signed(SENDER), test_metadata(10)),
+				sp_runtime::DispatchError::BadOrigin,
+			);
+
+			assert_ok!(BridgeAssets::register_asset(RuntimeOrigin::root(), test_metadata(10)));
+			assert_ok!(BridgeAssets::register_asset(RuntimeOrigin::root(), test_metadata(20)));
+
+			assert_eq!(BridgeAssets::native_asset_metadata(), Some(test_metadata(20)));
+			assert_eq!(
+				sent_messages(),
+				vec![
+					BridgeAssetsMessage::RegisterAsset { metadata: test_metadata(10) },
+					BridgeAssetsMessage::RegisterAsset { metadata: test_metadata(20) },
+				],
+			);
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::BridgeAssets(Event::NativeAssetRegistered {
+					metadata: test_metadata(20),
+					nonce: 2,
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn deregister_asset_sends_message_to_bridged_chain() {
+		run_test(|| {
+			assert_noop!(
+				BridgeAssets::deregister_asset(RuntimeOrigin::root()),
+				Error::<TestRuntime, ()>::AssetNotRegistered,
+			);
+
+			assert_ok!(BridgeAssets::register_asset(RuntimeOrigin::root(), test_metadata(10)));
+			assert_ok!(BridgeAssets::deregister_asset(RuntimeOrigin::root()));
+
+			assert_eq!(BridgeAssets::native_asset_metadata(), None);
+			assert_eq!(sent_messages().last(), Some(&BridgeAssetsMessage::DeregisterAsset));
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::BridgeAssets(Event::NativeAssetDeregistered { nonce: 2 }),
+			);
+		});
+	}
+
+	#[test]
+	fn asset_metadata_messages_update_wrapped_asset_metadata() {
+		run_test(|| {
+			dispatch(BridgeAssetsMessage::RegisterAsset { metadata: test_metadata(10) });
+			assert_eq!(BridgeAssets::wrapped_asset_metadata(), Some(test_metadata(10)));
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::BridgeAssets(Event::WrappedAssetRegistered {
+					lane: TEST_LANE,
+					nonce: 1,
+					metadata: test_metadata(10),
+				}),
+			);
+
+			dispatch(BridgeAssetsMessage::DeregisterAsset);
+			assert_eq!(BridgeAssets::wrapped_asset_metadata(), None);
+			assert_eq!(
+				last_event(),
+				RuntimeEvent::BridgeAssets(Event::WrappedAssetDeregistered {
+					lane: TEST_LANE,
+					nonce: 1,
+				}),
+			);
+			assert!(sent_messages().is_empty());
+		});
+	}
+
+	#[test]
+	fn mint_below_wrapped_asset_min_balance_is_refunded() {
+		run_test(|| {
+			dispatch(BridgeAssetsMessage::RegisterAsset { metadata: test_metadata(50) });
+			dispatch(BridgeAssetsMessage::Mint {
+				sender: SENDER,
+				recipient: RECIPIENT,
+				amount: 49,
+			});
+
+			assert_eq!(WrappedBalances::free_balance(RECIPIENT), 0);
+			assert_eq!(
+				sent_messages(),
+				vec![BridgeAssetsMessage::Refund {
+					recipient: SENDER,
+					amount: 49,
+					kind: TransferKind::Mint,
+				}],
+			);
+
+			dispatch(BridgeAssetsMessage::Mint {
+				sender: SENDER,
+				recipient: RECIPIENT,
+				amount: 50,
+			});
+			assert_eq!(WrappedBalances::free_balance(RECIPIENT), 50);
+		});
+	}
+
+	#[test]
+	fn messages_from_unexpected_lane_are_rejected() {
+		run_test(|| {
+			assert_rejected(inbound_message(
+				LaneId([0, 0, 0, 2]),
+				BRIDGED_PALLET_ACCOUNT,
+				BridgeAssetsMessage::Mint { sender: SENDER, recipient: RECIPIENT, amount: 100 },
+			));
+		});
+	}
+
+	#[test]
+	fn messages_from_unexpected_sender_are_rejected() {
+		run_test(|| {
+			assert_rejected(inbound_message(
+				TEST_LANE,
+				SENDER,
+				BridgeAssetsMessage::Mint { sender: SENDER, recipient: RECIPIENT, amount: 100 },
+			));
+			assert_rejected(inbound_message(
+				TEST_LANE,
+				SENDER,
+				BridgeAssetsMessage::RegisterAsset { metadata: test_metadata(10) },
+			));
+			assert_eq!(BridgeAssets::wrapped_asset_metadata(), None);
+		});
+	}
+
+	#[test]
+	fn messages_with_zero_amount_are_rejected() {
+		run_test(|| {
+			assert_ok!(BridgeAssets::transfer(RuntimeOrigin::signed(SENDER), RECIPIENT, 100));
+			frame_support::storage::unhashed::kill(b":sent-messages:");
+
+			assert_rejected(inbound_message(
+				TEST_LANE,
+				BRIDGED_PALLET_ACCOUNT,
+				BridgeAssetsMessage::Mint { sender: SENDER, recipient: RECIPIENT, amount: 0 },
+			));
+			assert_rejected(inbound_message(
+				TEST_LANE,
+				BRIDGED_PALLET_ACCOUNT,
+				BridgeAssetsMessage::Unlock { sender: SENDER, recipient: RECIPIENT, amount: 0 },
+			));
+			assert_rejected(inbound_message(
+				TEST_LANE,
+				BRIDGED_PALLET_ACCOUNT,
+				BridgeAssetsMessage::Refund {
+					recipient: RECIPIENT,
+					amount: 0,
+					kind: TransferKind::Mint,
+				},
+			));
+			assert_eq!(Balances::free_balance(BridgeAssets::escrow_account()), 100);
+		});
+	}
+
+	#[test]
+	fn sender_verifier_only_accepts_messages_from_pallet_account() {
+		run_test(|| {
+			let verify = |origin: RuntimeOrigin, lane: LaneId, payload_origin: AccountId| {
+				SenderVerifier::<TestRuntime>::verify_message(
+					&origin,
+					&lane,
+					&Default::default(),
+					&BridgeAssetsPayload {
+						origin: payload_origin,
+						message: BridgeAssetsMessage::DeregisterAsset,
+					},
+				)
+			};
+			let pallet_account = BridgeAssets::escrow_account();
+
+			assert_eq!(
+				verify(RuntimeOrigin::signed(pallet_account), TEST_LANE, pallet_account),
+				Ok(())
+			);
+			assert!(verify(RuntimeOrigin::signed(SENDER), TEST_LANE, pallet_account).is_err());
+			assert!(verify(RuntimeOrigin::signed(SENDER), TEST_LANE, SENDER).is_err());
+			assert!(verify(RuntimeOrigin::signed(pallet_account), TEST_LANE, SENDER).is_err());
+			assert!(verify(RuntimeOrigin::root(), TEST_LANE, pallet_account).is_err());
+			assert_eq!(verify(RuntimeOrigin::signed(SENDER), LaneId([0, 0, 0, 2]), SENDER), Ok(()));
+		});
+	}
+}
@@ -0,0 +1,205 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as pallet_bridge_assets;
+use crate::{OutboundMessageOf, OutboundPayloadOf};
+
+use bp_messages::{
+	source_chain::{LaneMessageVerifier, MessagesBridge, SendMessageArtifacts},
+	LaneId,
+};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, ConstU64},
+	weights::{RuntimeDbWeight, Weight},
+	PalletId,
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header as SubstrateHeader,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+pub type AccountId = u64;
+pub type Balance = u64;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+
+/// Lane that is used to exchange messages with the bridged chain.
+pub const TEST_LANE: LaneId = LaneId([0, 0, 0, 1]);
+
+/// Account that sends tokens.
+pub const SENDER: AccountId = 1;
+
+/// Account that receives tokens.
+pub const RECIPIENT: AccountId = 2;
+
+/// Account of the pallet instance at the bridged chain.
+pub const BRIDGED_PALLET_ACCOUNT: AccountId = 100;
+
+/// Initial balance of the sender account (both native and wrapped).
+pub const INITIAL_BALANCE: Balance = 1_000;
+
+frame_support::construct_runtime! {
+	pub enum TestRuntime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Event<T>},
+		WrappedBalances: pallet_balances::<Instance1>::{Pallet, Event<T>},
+		BridgeAssets: pallet_bridge_assets::{Pallet, Call, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub const DbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 2 };
+	pub const BridgeAssetsPalletId: PalletId = PalletId(*b"brassets");
+	pub const BridgeLane: LaneId = TEST_LANE;
+	pub const BridgedPalletAccount: AccountId = BRIDGED_PALLET_ACCOUNT;
+}
+
+impl frame_system::Config for TestRuntime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Index = u64;
+	type RuntimeCall = RuntimeCall;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = SubstrateHeader;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = DbWeight;
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for TestRuntime {
+	type MaxLocks = ();
+	type Balance = Balance;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = frame_system::Pallet<TestRuntime>;
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = ();
+}
+
+impl pallet_balances::Config<pallet_balances::Instance1> for TestRuntime {
+	type MaxLocks = ();
+	type Balance = Balance;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = frame_support::traits::StorageMapShim<
+		pallet_balances::Account<TestRuntime, pallet_balances::Instance1>,
+		frame_system::Provider<TestRuntime>,
+		AccountId,
+		pallet_balances::AccountData<Balance>,
+	>;
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = ();
+}
+
+impl pallet_bridge_assets::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type NativeCurrency = Balances;
+	type WrappedCurrency = WrappedBalances;
+	type PalletId = BridgeAssetsPalletId;
+	type BridgedAccountId = AccountId;
+	type BridgedPalletAccount = BridgedPalletAccount;
+	type BridgeLane = BridgeLane;
+	type MessagesBridge = TestMessagesBridge;
+	type ManageOrigin = EnsureRoot<AccountId>;
+	type OnWrappedAssetMetadataChange = ();
+	type WeightInfo = ();
+}
+
+/// Messages bridge that remembers all sent messages.
+pub struct TestMessagesBridge;
+
+impl MessagesBridge<RuntimeOrigin, OutboundPayloadOf<TestRuntime, ()>> for TestMessagesBridge {
+	type Error = &'static str;
+
+	fn send_message(
+		sender: RuntimeOrigin,
+		lane: LaneId,
+		payload: OutboundPayloadOf<TestRuntime, ()>,
+	) -> Result<SendMessageArtifacts, Self::Error> {
+		if lane != TEST_LANE || frame_support::storage::unhashed::exists(b":reject-messages:") {
+			return Err("Rejected")
+		}
+		<crate::SenderVerifier<TestRuntime> as LaneMessageVerifier<_, _>>::verify_message(
+			&sender,
+			&lane,
+			&Default::default(),
+			&payload,
+		)?;
+
+		let mut messages = sent_messages();
+		messages.push(payload.message);
+		frame_support::storage::unhashed::put(b":sent-messages:", &messages);
+		Ok(SendMessageArtifacts { nonce: messages.len() as _, weight: Weight::zero() })
+	}
+}
+
+/// Returns all messages that have been sent.
+pub fn sent_messages() -> Vec<OutboundMessageOf<TestRuntime, ()>> {
+	frame_support::storage::unhashed::get_or_default(b":sent-messages:")
+}
+
+/// Make all following attempts to send message fail.
+pub fn reject_messages() {
+	frame_support::storage::unhashed::put(b":reject-messages:", &true);
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<TestRuntime>().unwrap();
+	pallet_balances::GenesisConfig::<TestRuntime> { balances: vec![(SENDER, INITIAL_BALANCE)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	pallet_balances::GenesisConfig::<TestRuntime, pallet_balances::Instance1> {
+		balances: vec![(SENDER, INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		System::reset_events();
+		test()
+	})
+}
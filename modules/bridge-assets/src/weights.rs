@@ -0,0 +1,144 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for pallet_bridge_assets
+//!
+//! Those are hand-written placeholder weights, they are NOT generated by the benchmark CLI. They
+//! must be replaced with the output of the pallet benchmarks (see `benchmarking` module) before
+//! the pallet is used in production.
+
+#![allow(clippy::all)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_bridge_assets.
+pub trait WeightInfo {
+	fn transfer() -> Weight;
+	fn transfer_back() -> Weight;
+	fn register_asset() -> Weight;
+	fn deregister_asset() -> Weight;
+	fn receive_message() -> Weight;
+}
+
+/// Placeholder weights for `pallet_bridge_assets`.
+///
+/// Those weights are test only and must never be used in production.
+pub struct BridgeWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: BridgeMessages PalletOperatingMode (r:1 w:0)
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:1)
+	fn transfer() -> Weight {
+		Weight::from_parts(63_306_000, 5184)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: WrappedBalances Account (r:1 w:1)
+	/// Storage: WrappedBalances TotalIssuance (r:1 w:1)
+	/// Storage: BridgeMessages PalletOperatingMode (r:1 w:0)
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:1)
+	fn transfer_back() -> Weight {
+		Weight::from_parts(60_112_000, 5142)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: BridgeAssets NativeAssetMetadata (r:0 w:1)
+	/// Storage: BridgeMessages PalletOperatingMode (r:1 w:0)
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:1)
+	fn register_asset() -> Weight {
+		Weight::from_parts(35_277_000, 2012)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: BridgeAssets NativeAssetMetadata (r:1 w:1)
+	/// Storage: BridgeMessages PalletOperatingMode (r:1 w:0)
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:1)
+	fn deregister_asset() -> Weight {
+		Weight::from_parts(36_893_000, 2557)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: BridgeAssets WrappedAssetMetadata (r:1 w:0)
+	/// Storage: BridgeMessages PalletOperatingMode (r:1 w:0)
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:1)
+	fn receive_message() -> Weight {
+		Weight::from_parts(38_541_000, 2541)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: BridgeMessages PalletOperatingMode (r:1 w:0)
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:1)
+	fn transfer() -> Weight {
+		Weight::from_parts(63_306_000, 5184)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: WrappedBalances Account (r:1 w:1)
+	/// Storage: WrappedBalances TotalIssuance (r:1 w:1)
+	/// Storage: BridgeMessages PalletOperatingMode (r:1 w:0)
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:1)
+	fn transfer_back() -> Weight {
+		Weight::from_parts(60_112_000, 5142)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: BridgeAssets NativeAssetMetadata (r:0 w:1)
+	/// Storage: BridgeMessages PalletOperatingMode (r:1 w:0)
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:1)
+	fn register_asset() -> Weight {
+		Weight::from_parts(35_277_000, 2012)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: BridgeAssets NativeAssetMetadata (r:1 w:1)
+	/// Storage: BridgeMessages PalletOperatingMode (r:1 w:0)
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:1)
+	fn deregister_asset() -> Weight {
+		Weight::from_parts(36_893_000, 2557)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: BridgeAssets WrappedAssetMetadata (r:1 w:0)
+	/// Storage: BridgeMessages PalletOperatingMode (r:1 w:0)
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:1)
+	fn receive_message() -> Weight {
+		Weight::from_parts(38_541_000, 2541)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -38,6 +38,11 @@
 //!
 //! Note that the worst case scenario here would be a justification where each validator has it's
 //! own fork which is `SESSION_LENGTH` blocks long.
+//!
+//! The number of pre-commits in the `submit_finality_proof` benchmark is limited by the
+//! number of authorities, supported by the benchmarking runtime. So there are additional
+//! benchmarks, that are using justifications generated by authority sets of
+//! [`BENCHMARKED_AUTHORITY_SET_SIZES`] sizes.
 
 use crate::*;
 
@@ -115,6 +120,32 @@ fn prepare_benchmark_data<T: Config<I>, I: 'static>(
 	(header, justification)
 }
 
+/// Prepare header and its justification, generated by authority set of given size.
+///
+/// If bridged chain supports less authorities, the maximal supported number is used.
+fn prepare_benchmark_data_for_authorities<T: Config<I>, I: 'static>(
+	authorities_count: u32,
+	ancestors: u32,
+) -> (BridgedHeader<T, I>, GrandpaJustification<BridgedHeader<T, I>>) {
+	let authorities_count =
+		sp_std::cmp::min(authorities_count, T::BridgedChain::MAX_AUTHORITIES_COUNT);
+	prepare_benchmark_data::<T, I>(required_justification_precommits(authorities_count), ancestors)
+}
+
+/// Ensure that the header#1 has been imported and the header#0 has been pruned.
+fn assert_header_imported<T: Config<I>, I: 'static>() {
+	let genesis_header: BridgedHeader<T, I> = bp_test_utils::test_header(Zero::zero());
+	let header: BridgedHeader<T, I> = bp_test_utils::test_header(One::one());
+	let expected_hash = header.hash();
+
+	// check that the header#1 has been inserted
+	assert_eq!(<BestFinalized<T, I>>::get().unwrap().1, expected_hash);
+	assert!(<ImportedHeaders<T, I>>::contains_key(expected_hash));
+
+	// check that the header#0 has been pruned
+	assert!(!<ImportedHeaders<T, I>>::contains_key(genesis_header.hash()));
+}
+
 benchmarks_instance_pallet! {
 	// This is the "gold standard" benchmark for this extrinsic, and it's what should be used to
 	// annotate the weight in the pallet.
@@ -125,15 +156,50 @@ benchmarks_instance_pallet! {
 		let (header, justification) = prepare_benchmark_data::<T, I>(p, v);
 	}: submit_finality_proof(RawOrigin::Signed(caller), Box::new(header), justification)
 	verify {
-		let genesis_header: BridgedHeader<T, I> = bp_test_utils::test_header(Zero::zero());
-		let header: BridgedHeader<T, I> = bp_test_utils::test_header(One::one());
-		let expected_hash = header.hash();
+		assert_header_imported::<T, I>();
+	}
 
-		// check that the header#1 has been inserted
-		assert_eq!(<BestFinalized<T, I>>::get().unwrap().1, expected_hash);
-		assert!(<ImportedHeaders<T, I>>::contains_key(expected_hash));
+	// Benchmarks below are used to compute weight of justifications, generated by large
+	// authority sets (see `WeightInfoExt::submit_finality_proof_weight`). If bridged chain
+	// supports less authorities, the maximal supported number is used.
 
-		// check that the header#0 has been pruned
-		assert!(!<ImportedHeaders<T, I>>::contains_key(genesis_header.hash()));
+	submit_finality_proof_with_100_authorities {
+		let v in MAX_VOTE_ANCESTRIES_RANGE_BEGIN..MAX_VOTE_ANCESTRIES_RANGE_END;
+		let caller: T::AccountId = whitelisted_caller();
+		let (header, justification) =
+			prepare_benchmark_data_for_authorities::<T, I>(BENCHMARKED_AUTHORITY_SET_SIZES[0], v);
+	}: submit_finality_proof(RawOrigin::Signed(caller), Box::new(header), justification)
+	verify {
+		assert_header_imported::<T, I>();
+	}
+
+	submit_finality_proof_with_300_authorities {
+		let v in MAX_VOTE_ANCESTRIES_RANGE_BEGIN..MAX_VOTE_ANCESTRIES_RANGE_END;
+		let caller: T::AccountId = whitelisted_caller();
+		let (header, justification) =
+			prepare_benchmark_data_for_authorities::<T, I>(BENCHMARKED_AUTHORITY_SET_SIZES[1], v);
+	}: submit_finality_proof(RawOrigin::Signed(caller), Box::new(header), justification)
+	verify {
+		assert_header_imported::<T, I>();
+	}
+
+	submit_finality_proof_with_600_authorities {
+		let v in MAX_VOTE_ANCESTRIES_RANGE_BEGIN..MAX_VOTE_ANCESTRIES_RANGE_END;
+		let caller: T::AccountId = whitelisted_caller();
+		let (header, justification) =
+			prepare_benchmark_data_for_authorities::<T, I>(BENCHMARKED_AUTHORITY_SET_SIZES[2], v);
+	}: submit_finality_proof(RawOrigin::Signed(caller), Box::new(header), justification)
+	verify {
+		assert_header_imported::<T, I>();
+	}
+
+	submit_finality_proof_with_1000_authorities {
+		let v in MAX_VOTE_ANCESTRIES_RANGE_BEGIN..MAX_VOTE_ANCESTRIES_RANGE_END;
+		let caller: T::AccountId = whitelisted_caller();
+		let (header, justification) =
+			prepare_benchmark_data_for_authorities::<T, I>(BENCHMARKED_AUTHORITY_SET_SIZES[3], v);
+	}: submit_finality_proof(RawOrigin::Signed(caller), Box::new(header), justification)
+	verify {
+		assert_header_imported::<T, I>();
 	}
 }
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{weights::WeightInfo, BridgedBlockNumber, BridgedHeader, Config, Error, Pallet};
+use crate::{BridgedBlockNumber, BridgedHeader, Config, Error, Pallet, WeightInfoExt};
 use bp_header_chain::{justification::GrandpaJustification, ChainWithGrandpa};
 use bp_runtime::BlockNumberOf;
 use codec::Encode;
@@ -160,7 +160,7 @@ pub(crate) fn submit_finality_proof_info_from_args<T: Config<I>, I: 'static>(
 	let votes_ancestries_len = justification.votes_ancestries.len().saturated_into();
 	let extra_weight =
 		if votes_ancestries_len > T::BridgedChain::REASONABLE_HEADERS_IN_JUSTIFICATON_ANCESTRY {
-			T::WeightInfo::submit_finality_proof(precommits_len, votes_ancestries_len)
+			T::WeightInfo::submit_finality_proof_weight(precommits_len, votes_ancestries_len)
 		} else {
 			Weight::zero()
 		};
@@ -190,7 +190,7 @@ mod tests {
 	use crate::{
 		call_ext::CallSubType,
 		mock::{run_test, test_header, RuntimeCall, TestBridgedChain, TestNumber, TestRuntime},
-		BestFinalized, Config, WeightInfo,
+		BestFinalized, Config, WeightInfoExt,
 	};
 	use bp_header_chain::ChainWithGrandpa;
 	use bp_runtime::HeaderId;
@@ -298,7 +298,7 @@ mod tests {
 		// when there are `REASONABLE_HEADERS_IN_JUSTIFICATON_ANCESTRY + 1` headers => full refund
 		justification_params.ancestors += 1;
 		let justification = make_justification_for_header(justification_params);
-		let call_weight = <TestRuntime as Config>::WeightInfo::submit_finality_proof(
+		let call_weight = <TestRuntime as Config>::WeightInfo::submit_finality_proof_weight(
 			justification.commit.precommits.len().saturated_into(),
 			justification.votes_ancestries.len().saturated_into(),
 		);
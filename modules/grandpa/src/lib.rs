@@ -56,6 +56,7 @@ mod call_ext;
 #[cfg(test)]
 mod mock;
 mod storage_types;
+mod weights_ext;
 
 /// Module, containing weights for this pallet.
 pub mod weights;
@@ -67,6 +68,9 @@ pub mod benchmarking;
 pub use call_ext::*;
 pub use pallet::*;
 pub use weights::WeightInfo;
+pub use weights_ext::{
+	authorities_count_from_precommits, WeightInfoExt, BENCHMARKED_AUTHORITY_SET_SIZES,
+};
 
 /// The target that will be used when publishing logs related to this pallet.
 pub const LOG_TARGET: &str = "runtime::bridge-grandpa";
@@ -118,6 +122,16 @@ pub mod pallet {
 		#[pallet::constant]
 		type HeadersToKeep: Get<u32>;
 
+		/// If true, justification signatures are verified using the batch verification host
+		/// function (see `bp_header_chain::host_functions`). Otherwise they're verified in the
+		/// runtime, one-by-one.
+		///
+		/// It is only respected if the pallet is built with the `host-signature-verification`
+		/// feature. It shall only return true when all nodes of the chain are providing the host
+		/// function. It may be backed by the runtime storage, so the verification may be switched
+		/// without runtime upgrade.
+		type UseHostSignatureVerification: Get<bool>;
+
 		/// Weights gathered through benchmarking.
 		type WeightInfo: WeightInfo;
 	}
@@ -151,7 +165,7 @@ pub mod pallet {
 		/// If successful in verification, it will write the target header to the underlying storage
 		/// pallet.
 		#[pallet::call_index(0)]
-		#[pallet::weight(<T::WeightInfo as WeightInfo>::submit_finality_proof(
+		#[pallet::weight(<T::WeightInfo as WeightInfoExt>::submit_finality_proof_weight(
 			justification.commit.precommits.len().saturated_into(),
 			justification.votes_ancestries.len().saturated_into(),
 		))]
@@ -205,7 +219,7 @@ pub mod pallet {
 			// `MaxBridgedAuthorities` in the `CurrentAuthoritySet` (we use `MaxEncodedLen`
 			// estimation). But if their number is lower, then we may "refund" some `proof_size`,
 			// making proof smaller and leaving block space to other useful transactions
-			let pre_dispatch_weight = T::WeightInfo::submit_finality_proof(
+			let pre_dispatch_weight = T::WeightInfo::submit_finality_proof_weight(
 				justification.commit.precommits.len().saturated_into(),
 				justification.votes_ancestries.len().saturated_into(),
 			);
@@ -317,7 +331,12 @@ pub mod pallet {
 	>;
 
 	/// The current GRANDPA Authority set.
+	///
+	/// The pallet enacts scheduled authority set changes when the header that is signalling the
+	/// change is imported (changes with non-zero delay are rejected), so there's never a pending
+	/// change that is not yet reflected here.
 	#[pallet::storage]
+	#[pallet::getter(fn current_authority_set)]
 	pub type CurrentAuthoritySet<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, StoredAuthoritySet<T, I>, ValueQuery>;
 
@@ -455,13 +474,23 @@ pub mod pallet {
 		number: BridgedBlockNumber<T, I>,
 		authority_set: bp_header_chain::AuthoritySet,
 	) -> Result<(), sp_runtime::DispatchError> {
-		use bp_header_chain::justification::verify_justification;
+		use bp_header_chain::justification::{verify_justification_using, SignaturesVerification};
 
 		let voter_set =
 			VoterSet::new(authority_set.authorities).ok_or(<Error<T, I>>::InvalidAuthoritySet)?;
 		let set_id = authority_set.set_id;
 
-		Ok(verify_justification::<BridgedHeader<T, I>>(
+		#[cfg(feature = "host-signature-verification")]
+		let signatures_verification = if T::UseHostSignatureVerification::get() {
+			SignaturesVerification::HostBatch
+		} else {
+			SignaturesVerification::InRuntime
+		};
+		#[cfg(not(feature = "host-signature-verification"))]
+		let signatures_verification = SignaturesVerification::InRuntime;
+
+		Ok(verify_justification_using::<BridgedHeader<T, I>>(
+			signatures_verification,
 			(hash, number),
 			set_id,
 			&voter_set,
@@ -638,7 +667,7 @@ mod tests {
 		run_test, test_header, RuntimeOrigin, TestBridgedChain, TestHeader, TestNumber,
 		TestRuntime, MAX_BRIDGED_AUTHORITIES,
 	};
-	use bp_header_chain::BridgeGrandpaCall;
+	use bp_header_chain::{AuthoritySet, BridgeGrandpaCall};
 	use bp_runtime::BasicOperatingMode;
 	use bp_test_utils::{
 		authority_list, generate_owned_bridge_module_tests, make_default_justification,
@@ -827,10 +856,11 @@ mod tests {
 			let header = test_header(header_number.into());
 			let justification = make_default_justification(&header);
 
-			let pre_dispatch_weight = <TestRuntime as Config>::WeightInfo::submit_finality_proof(
-				justification.commit.precommits.len().try_into().unwrap_or(u32::MAX),
-				justification.votes_ancestries.len().try_into().unwrap_or(u32::MAX),
-			);
+			let pre_dispatch_weight =
+				<TestRuntime as Config>::WeightInfo::submit_finality_proof_weight(
+					justification.commit.precommits.len().try_into().unwrap_or(u32::MAX),
+					justification.votes_ancestries.len().try_into().unwrap_or(u32::MAX),
+				);
 
 			let result = submit_finality_proof(header_number);
 			assert_ok!(result);
@@ -962,6 +992,10 @@ mod tests {
 			assert!(<ImportedHeaders<TestRuntime>>::contains_key(header.hash()));
 
 			// Make sure that the authority set actually changed upon importing our header
+			assert_eq!(
+				AuthoritySet::from(Pallet::<TestRuntime>::current_authority_set()),
+				AuthoritySet::new(next_authorities.clone(), next_set_id),
+			);
 			assert_eq!(
 				<CurrentAuthoritySet<TestRuntime>>::get(),
 				StoredAuthoritySet::<TestRuntime, ()>::try_new(next_authorities, next_set_id)
@@ -1290,6 +1324,17 @@ mod tests {
 			direct_submit_finality_proof_call.encode(),
 			indirect_submit_finality_proof_call.encode()
 		);
+
+		let direct_set_operating_mode_call =
+			Call::<TestRuntime>::set_operating_mode { operating_mode: BasicOperatingMode::Halted };
+		let indirect_set_operating_mode_call =
+			BridgeGrandpaCall::<TestHeader>::set_operating_mode {
+				operating_mode: BasicOperatingMode::Halted,
+			};
+		assert_eq!(
+			direct_set_operating_mode_call.encode(),
+			indirect_set_operating_mode_call.encode()
+		);
 	}
 
 	generate_owned_bridge_module_tests!(BasicOperatingMode::Normal, BasicOperatingMode::Halted);
@@ -21,7 +21,7 @@ use bp_header_chain::ChainWithGrandpa;
 use bp_runtime::Chain;
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{ConstU32, ConstU64},
+	traits::{ConstBool, ConstU32, ConstU64},
 	weights::Weight,
 };
 use sp_core::sr25519::Signature;
@@ -97,6 +97,7 @@ impl grandpa::Config for TestRuntime {
 	type BridgedChain = TestBridgedChain;
 	type MaxRequests = MaxRequests;
 	type HeadersToKeep = HeadersToKeep;
+	type UseHostSignatureVerification = ConstBool<false>;
 	type WeightInfo = ();
 }
 
@@ -51,6 +51,10 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for pallet_bridge_grandpa.
 pub trait WeightInfo {
 	fn submit_finality_proof(p: u32, v: u32) -> Weight;
+	fn submit_finality_proof_with_100_authorities(v: u32) -> Weight;
+	fn submit_finality_proof_with_300_authorities(v: u32) -> Weight;
+	fn submit_finality_proof_with_600_authorities(v: u32) -> Weight;
+	fn submit_finality_proof_with_1000_authorities(v: u32) -> Weight;
 }
 
 /// Weights for `pallet_bridge_grandpa` that are generated using one of the Bridge testnets.
@@ -109,6 +113,58 @@ impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(6_u64))
 			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
+
+	/// Same storage items as `submit_finality_proof`, with `100` authorities in the
+	/// `CurrentAuthoritySet`.
+	///
+	/// The range of component `v` is `[50, 100]`.
+	fn submit_finality_proof_with_100_authorities(v: u32) -> Weight {
+		// Extrapolated from `submit_finality_proof` results for `p = 67` (`100` authorities),
+		// including `40` bytes of `CurrentAuthoritySet` proof size per every authority.
+		Weight::from_parts(2_517_336_262, 8545)
+			.saturating_add(Weight::from_parts(1_469_032, 0).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+
+	/// Same storage items as `submit_finality_proof`, with `300` authorities in the
+	/// `CurrentAuthoritySet`.
+	///
+	/// The range of component `v` is `[50, 100]`.
+	fn submit_finality_proof_with_300_authorities(v: u32) -> Weight {
+		// Extrapolated from `submit_finality_proof` results for `p = 201` (`300` authorities),
+		// including `40` bytes of `CurrentAuthoritySet` proof size per every authority.
+		Weight::from_parts(7_436_302_330, 16545)
+			.saturating_add(Weight::from_parts(1_469_032, 0).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+
+	/// Same storage items as `submit_finality_proof`, with `600` authorities in the
+	/// `CurrentAuthoritySet`.
+	///
+	/// The range of component `v` is `[50, 100]`.
+	fn submit_finality_proof_with_600_authorities(v: u32) -> Weight {
+		// Extrapolated from `submit_finality_proof` results for `p = 401` (`600` authorities),
+		// including `40` bytes of `CurrentAuthoritySet` proof size per every authority.
+		Weight::from_parts(14_778_042_730, 28545)
+			.saturating_add(Weight::from_parts(1_469_032, 0).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+
+	/// Same storage items as `submit_finality_proof`, with `1000` authorities in the
+	/// `CurrentAuthoritySet`.
+	///
+	/// The range of component `v` is `[50, 100]`.
+	fn submit_finality_proof_with_1000_authorities(v: u32) -> Weight {
+		// Extrapolated from `submit_finality_proof` results for `p = 667` (`1000` authorities),
+		// including `40` bytes of `CurrentAuthoritySet` proof size per every authority.
+		Weight::from_parts(24_542_557_462, 44545)
+			.saturating_add(Weight::from_parts(1_469_032, 0).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -164,4 +220,56 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(6_u64))
 			.saturating_add(RocksDbWeight::get().writes(6_u64))
 	}
+
+	/// Same storage items as `submit_finality_proof`, with `100` authorities in the
+	/// `CurrentAuthoritySet`.
+	///
+	/// The range of component `v` is `[50, 100]`.
+	fn submit_finality_proof_with_100_authorities(v: u32) -> Weight {
+		// Extrapolated from `submit_finality_proof` results for `p = 67` (`100` authorities),
+		// including `40` bytes of `CurrentAuthoritySet` proof size per every authority.
+		Weight::from_parts(2_517_336_262, 8545)
+			.saturating_add(Weight::from_parts(1_469_032, 0).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+
+	/// Same storage items as `submit_finality_proof`, with `300` authorities in the
+	/// `CurrentAuthoritySet`.
+	///
+	/// The range of component `v` is `[50, 100]`.
+	fn submit_finality_proof_with_300_authorities(v: u32) -> Weight {
+		// Extrapolated from `submit_finality_proof` results for `p = 201` (`300` authorities),
+		// including `40` bytes of `CurrentAuthoritySet` proof size per every authority.
+		Weight::from_parts(7_436_302_330, 16545)
+			.saturating_add(Weight::from_parts(1_469_032, 0).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+
+	/// Same storage items as `submit_finality_proof`, with `600` authorities in the
+	/// `CurrentAuthoritySet`.
+	///
+	/// The range of component `v` is `[50, 100]`.
+	fn submit_finality_proof_with_600_authorities(v: u32) -> Weight {
+		// Extrapolated from `submit_finality_proof` results for `p = 401` (`600` authorities),
+		// including `40` bytes of `CurrentAuthoritySet` proof size per every authority.
+		Weight::from_parts(14_778_042_730, 28545)
+			.saturating_add(Weight::from_parts(1_469_032, 0).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+
+	/// Same storage items as `submit_finality_proof`, with `1000` authorities in the
+	/// `CurrentAuthoritySet`.
+	///
+	/// The range of component `v` is `[50, 100]`.
+	fn submit_finality_proof_with_1000_authorities(v: u32) -> Weight {
+		// Extrapolated from `submit_finality_proof` results for `p = 667` (`1000` authorities),
+		// including `40` bytes of `CurrentAuthoritySet` proof size per every authority.
+		Weight::from_parts(24_542_557_462, 44545)
+			.saturating_add(Weight::from_parts(1_469_032, 0).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
 }
@@ -0,0 +1,144 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weight-related utilities.
+
+use crate::weights::WeightInfo;
+
+use frame_support::weights::Weight;
+
+/// Sizes of authority sets that are used in `submit_finality_proof_with_*_authorities`
+/// benchmarks.
+pub const BENCHMARKED_AUTHORITY_SET_SIZES: [u32; 4] = [100, 300, 600, 1000];
+
+/// Extended weight info.
+pub trait WeightInfoExt: WeightInfo {
+	/// Weight of the `submit_finality_proof` call.
+	///
+	/// The regular `submit_finality_proof` benchmark is limited by the number of authorities
+	/// that the benchmarking runtime supports. So if the justification has been generated by
+	/// the large authority set, we also compute the call weight using benchmarks with fixed
+	/// authority set sizes and return the maximal of two weights.
+	fn submit_finality_proof_weight(precommits_len: u32, votes_ancestries_len: u32) -> Weight {
+		let regular_weight = Self::submit_finality_proof(precommits_len, votes_ancestries_len);
+		let large_set_weight = Self::submit_finality_proof_with_authorities(
+			authorities_count_from_precommits(precommits_len),
+			votes_ancestries_len,
+		);
+		regular_weight.max(large_set_weight)
+	}
+
+	/// Weight of the `submit_finality_proof` call, computed using benchmarks with fixed
+	/// authority set sizes.
+	///
+	/// For sets that are smaller than the smallest benchmarked set, zero weight is returned.
+	/// Weight of sets that are between benchmarked sizes is interpolated and weight of sets
+	/// that are larger than the largest benchmarked set is extrapolated.
+	fn submit_finality_proof_with_authorities(
+		authorities_count: u32,
+		votes_ancestries_len: u32,
+	) -> Weight {
+		let v = votes_ancestries_len;
+		let [s100, s300, s600, s1000] = BENCHMARKED_AUTHORITY_SET_SIZES;
+		let benchmarked = [
+			(s100, Self::submit_finality_proof_with_100_authorities(v)),
+			(s300, Self::submit_finality_proof_with_300_authorities(v)),
+			(s600, Self::submit_finality_proof_with_600_authorities(v)),
+			(s1000, Self::submit_finality_proof_with_1000_authorities(v)),
+		];
+
+		if authorities_count < benchmarked[0].0 {
+			return Weight::zero()
+		}
+
+		let upper_index = benchmarked
+			.iter()
+			.position(|(size, _)| *size >= authorities_count)
+			.unwrap_or(benchmarked.len() - 1);
+		let (lower_size, lower_weight) = benchmarked[upper_index.saturating_sub(1)];
+		let (upper_size, upper_weight) = benchmarked[upper_index.max(1)];
+		interpolate(authorities_count, (lower_size, lower_weight), (upper_size, upper_weight))
+	}
+}
+
+impl<T: WeightInfo> WeightInfoExt for T {}
+
+/// Returns maximal number of authorities that may have generated justification with given
+/// number of precommits.
+///
+/// This is an inverse of the `bp_header_chain::justification::required_justification_precommits`
+/// function.
+pub fn authorities_count_from_precommits(precommits_len: u32) -> u32 {
+	precommits_len.saturating_mul(3).saturating_sub(1) / 2
+}
+
+/// Linear interpolation (or extrapolation) of the weight, given two points.
+fn interpolate(x: u32, (x0, y0): (u32, Weight), (x1, y1): (u32, Weight)) -> Weight {
+	let interpolate_component = |y0: u64, y1: u64| -> u64 {
+		let (x, x0, x1) = (x as u64, x0 as u64, x1 as u64);
+		let slope_numerator = y1.saturating_sub(y0);
+		let slope_denominator = x1.saturating_sub(x0).max(1);
+		y0.saturating_add(slope_numerator.saturating_mul(x.saturating_sub(x0)) / slope_denominator)
+	};
+
+	Weight::from_parts(
+		interpolate_component(y0.ref_time(), y1.ref_time()),
+		interpolate_component(y0.proof_size(), y1.proof_size()),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bp_header_chain::justification::required_justification_precommits;
+
+	#[test]
+	fn authorities_count_from_precommits_works() {
+		for authorities_count in BENCHMARKED_AUTHORITY_SET_SIZES {
+			let precommits_len = required_justification_precommits(authorities_count);
+			assert!(authorities_count_from_precommits(precommits_len) >= authorities_count);
+		}
+	}
+
+	#[test]
+	fn large_authority_sets_are_not_under_weighted() {
+		let v = 50;
+		let benchmarked = [
+			<() as WeightInfo>::submit_finality_proof_with_100_authorities(v),
+			<() as WeightInfo>::submit_finality_proof_with_300_authorities(v),
+			<() as WeightInfo>::submit_finality_proof_with_600_authorities(v),
+			<() as WeightInfo>::submit_finality_proof_with_1000_authorities(v),
+		];
+		for (authorities_count, benchmarked_weight) in
+			BENCHMARKED_AUTHORITY_SET_SIZES.into_iter().zip(benchmarked)
+		{
+			let precommits_len = required_justification_precommits(authorities_count);
+			let weight = <() as WeightInfoExt>::submit_finality_proof_weight(precommits_len, v);
+			assert!(weight.all_gte(benchmarked_weight));
+		}
+
+		// weight grows when we're going beyond benchmarked sizes
+		let weight_at_1000 = <() as WeightInfoExt>::submit_finality_proof_with_authorities(1000, v);
+		let weight_at_1500 = <() as WeightInfoExt>::submit_finality_proof_with_authorities(1500, v);
+		assert!(weight_at_1500.all_gt(weight_at_1000));
+
+		// weight of small sets is computed using regular benchmark
+		assert_eq!(
+			<() as WeightInfoExt>::submit_finality_proof_weight(1, v),
+			<() as WeightInfo>::submit_finality_proof(1, v),
+		);
+	}
+}
@@ -143,6 +143,7 @@ benchmarks_instance_pallet! {
 			crate::InboundLanes::<T, I>::get(&T::bench_lane_id()).last_delivered_nonce(),
 			21,
 		);
+		assert_eq!(crate::LanesStatistics::<T, I>::get(T::bench_lane_id()).messages_received, 1);
 		assert!(T::is_message_dispatched(21));
 	}
 
@@ -177,6 +178,7 @@ benchmarks_instance_pallet! {
 			crate::InboundLanes::<T, I>::get(&T::bench_lane_id()).last_delivered_nonce(),
 			22,
 		);
+		assert_eq!(crate::LanesStatistics::<T, I>::get(T::bench_lane_id()).messages_received, 2);
 		assert!(T::is_message_dispatched(22));
 	}
 
@@ -314,6 +316,7 @@ benchmarks_instance_pallet! {
 	}: receive_messages_delivery_proof(RawOrigin::Signed(relayer_id.clone()), proof, relayers_state)
 	verify {
 		assert_eq!(OutboundLanes::<T, I>::get(T::bench_lane_id()).latest_received_nonce, 1);
+		assert_eq!(crate::LanesStatistics::<T, I>::get(T::bench_lane_id()).messages_delivered, 1);
 		assert!(T::is_relayer_rewarded(&relayer_id));
 	}
 
@@ -353,6 +356,7 @@ benchmarks_instance_pallet! {
 	}: receive_messages_delivery_proof(RawOrigin::Signed(relayer_id.clone()), proof, relayers_state)
 	verify {
 		assert_eq!(OutboundLanes::<T, I>::get(T::bench_lane_id()).latest_received_nonce, 2);
+		assert_eq!(crate::LanesStatistics::<T, I>::get(T::bench_lane_id()).messages_delivered, 2);
 		assert!(T::is_relayer_rewarded(&relayer_id));
 	}
 
@@ -397,6 +401,7 @@ benchmarks_instance_pallet! {
 	}: receive_messages_delivery_proof(RawOrigin::Signed(relayer1_id.clone()), proof, relayers_state)
 	verify {
 		assert_eq!(OutboundLanes::<T, I>::get(T::bench_lane_id()).latest_received_nonce, 2);
+		assert_eq!(crate::LanesStatistics::<T, I>::get(T::bench_lane_id()).messages_delivered, 2);
 		assert!(T::is_relayer_rewarded(&relayer1_id));
 		assert!(T::is_relayer_rewarded(&relayer2_id));
 	}
@@ -153,7 +153,7 @@ impl<S: InboundLaneStorage> InboundLane<S> {
 		// Note: There will be max. 1 record to update as we don't allow messages from relayers to
 		// overlap.
 		match data.relayers.front_mut() {
-			Some(entry) if entry.messages.begin < new_confirmed_nonce => {
+			Some(entry) if entry.messages.begin <= new_confirmed_nonce => {
 				entry.messages.begin = new_confirmed_nonce + 1;
 			},
 			_ => {},
@@ -171,6 +171,34 @@ impl<S: InboundLaneStorage> InboundLane<S> {
 		nonce: MessageNonce,
 		message_data: DispatchMessageData<Dispatch::DispatchPayload>,
 	) -> ReceivalResult<Dispatch::DispatchLevelResult> {
+		let lane_id = self.storage.id();
+		self.accept_message(relayer_at_bridged_chain, nonce, || {
+			ReceivalResult::Dispatched(Dispatch::dispatch(
+				relayer_at_this_chain,
+				DispatchMessage { key: MessageKey { lane_id, nonce }, data: message_data },
+			))
+		})
+	}
+
+	/// Receive new message without dispatching it.
+	///
+	/// The message is accepted by the lane, but it must be dispatched later by the caller.
+	pub fn receive_deferred_message<DispatchLevelResult>(
+		&mut self,
+		relayer_at_bridged_chain: &S::Relayer,
+		nonce: MessageNonce,
+	) -> ReceivalResult<DispatchLevelResult> {
+		self.accept_message(relayer_at_bridged_chain, nonce, || ReceivalResult::Deferred)
+	}
+
+	/// Check that the message may be accepted by the lane, call `dispatch` and update the lane
+	/// state.
+	fn accept_message<DispatchLevelResult>(
+		&mut self,
+		relayer_at_bridged_chain: &S::Relayer,
+		nonce: MessageNonce,
+		dispatch: impl FnOnce() -> ReceivalResult<DispatchLevelResult>,
+	) -> ReceivalResult<DispatchLevelResult> {
 		let mut data = self.storage.data();
 		let is_correct_message = nonce == data.last_delivered_nonce() + 1;
 		if !is_correct_message {
@@ -188,14 +216,8 @@ impl<S: InboundLaneStorage> InboundLane<S> {
 			return ReceivalResult::TooManyUnconfirmedMessages
 		}
 
-		// then, dispatch message
-		let dispatch_result = Dispatch::dispatch(
-			relayer_at_this_chain,
-			DispatchMessage {
-				key: MessageKey { lane_id: self.storage.id(), nonce },
-				data: message_data,
-			},
-		);
+		// then, dispatch message (or defer its dispatch)
+		let receival_result = dispatch();
 
 		// now let's update inbound lane storage
 		let push_new = match data.relayers.back_mut() {
@@ -213,7 +235,7 @@ impl<S: InboundLaneStorage> InboundLane<S> {
 		}
 		self.storage.set_data(data);
 
-		ReceivalResult::Dispatched(dispatch_result)
+		receival_result
 	}
 }
 
@@ -358,6 +380,30 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn receive_status_update_confirms_first_message_of_relayer_entry() {
+		run_test(|| {
+			let mut lane = inbound_lane::<TestRuntime, _>(TEST_LANE_ID);
+			let mut seed_storage_data = lane.storage.data();
+			seed_storage_data.relayers.push_back(unrewarded_relayer(1, 1, TEST_RELAYER_A));
+			seed_storage_data.relayers.push_back(unrewarded_relayer(2, 4, TEST_RELAYER_B));
+			lane.storage.set_data(seed_storage_data);
+
+			assert_eq!(
+				lane.receive_state_update(OutboundLaneData {
+					latest_received_nonce: 2,
+					..Default::default()
+				}),
+				Some(2),
+			);
+			assert_eq!(lane.storage.data().last_confirmed_nonce, 2);
+			assert_eq!(
+				lane.storage.data().relayers,
+				vec![unrewarded_relayer(3, 4, TEST_RELAYER_B)]
+			);
+		});
+	}
+
 	#[test]
 	fn fails_to_receive_message_with_incorrect_nonce() {
 		run_test(|| {
@@ -547,4 +593,25 @@ mod tests {
 			);
 		});
 	}
+
+	#[test]
+	fn deferred_message_is_accepted_by_lane() {
+		run_test(|| {
+			let mut lane = inbound_lane::<TestRuntime, _>(TEST_LANE_ID);
+			receive_regular_message(&mut lane, 1);
+			assert_eq!(
+				lane.receive_deferred_message::<()>(&TEST_RELAYER_A, 2),
+				ReceivalResult::Deferred,
+			);
+			assert_eq!(
+				lane.receive_deferred_message::<()>(&TEST_RELAYER_A, 4),
+				ReceivalResult::InvalidNonce,
+			);
+			assert_eq!(lane.storage.data().last_delivered_nonce(), 2);
+			assert_eq!(
+				lane.storage.data().relayers,
+				vec![unrewarded_relayer(1, 2, TEST_RELAYER_A)],
+			);
+		});
+	}
 }
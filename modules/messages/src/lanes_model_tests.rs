@@ -0,0 +1,279 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Property-based tests, that are checking inbound and outbound lanes against their model.
+//!
+//! Every test case is a random sequence of actions (message sending, delivery, confirmation
+//! and pruning), performed by random relayers. Every action is applied both to the lanes
+//! and to the model. After every action, we check that the lanes state matches the model
+//! state and that lanes invariants are preserved.
+
+#![cfg(test)]
+
+use crate::{
+	inbound_lane,
+	inbound_lane::InboundLaneStorage,
+	mock::{
+		inbound_message_data, outbound_message_data, run_test, MaxUnconfirmedMessagesAtInboundLane,
+		MaxUnrewardedRelayerEntriesAtInboundLane, TestMessageDispatch, TestRelayer, TestRuntime,
+		REGULAR_PAYLOAD, TEST_LANE_ID, TEST_RELAYER_A, TEST_RELAYER_B, TEST_RELAYER_C,
+	},
+	outbound_lane,
+	outbound_lane::ReceivalConfirmationResult,
+	OutboundMessages,
+};
+
+use bp_messages::{DeliveredMessages, MessageKey, MessageNonce, ReceivalResult};
+use frame_support::{traits::Get, weights::constants::RocksDbWeight};
+use proptest::prelude::*;
+use sp_std::collections::vec_deque::VecDeque;
+
+/// Relayers that are delivering messages.
+const RELAYERS: [TestRelayer; 3] = [TEST_RELAYER_A, TEST_RELAYER_B, TEST_RELAYER_C];
+
+/// Action that is performed by the test.
+#[derive(Clone, Debug)]
+enum Action {
+	/// Send new message over the outbound lane.
+	Send,
+	/// Deliver at most given number of messages to the inbound lane by given relayer.
+	Deliver { relayer: usize, max_messages: MessageNonce },
+	/// Deliver outbound lane state to the inbound lane.
+	ReceiveStateUpdate,
+	/// Confirm delivery of all delivered messages to the outbound lane.
+	Confirm,
+	/// Prune at most given number of confirmed messages at the outbound lane.
+	Prune { max_messages: MessageNonce },
+}
+
+fn action() -> impl Strategy<Value = Action> {
+	prop_oneof![
+		3 => Just(Action::Send),
+		3 => (0..RELAYERS.len(), 1..8u64)
+			.prop_map(|(relayer, max_messages)| Action::Deliver { relayer, max_messages }),
+		1 => Just(Action::ReceiveStateUpdate),
+		1 => Just(Action::Confirm),
+		1 => (0..8u64).prop_map(|max_messages| Action::Prune { max_messages }),
+	]
+}
+
+/// Model of both lanes.
+#[derive(Debug, Default)]
+struct LanesModel {
+	/// Nonce of the oldest message that is still stored at the outbound lane.
+	oldest_unpruned_nonce: MessageNonce,
+	/// Nonce of the latest message, which delivery has been confirmed at the outbound lane.
+	latest_received_nonce: MessageNonce,
+	/// Nonce of the latest message, sent over the outbound lane.
+	latest_generated_nonce: MessageNonce,
+	/// Nonce of the latest message, which delivery confirmation is known to the inbound lane.
+	last_confirmed_nonce: MessageNonce,
+	/// Unrewarded relayer entries at the inbound lane: `(relayer, begin, end)`.
+	relayers: VecDeque<(TestRelayer, MessageNonce, MessageNonce)>,
+}
+
+impl LanesModel {
+	fn new() -> Self {
+		LanesModel { oldest_unpruned_nonce: 1, ..Default::default() }
+	}
+
+	fn last_delivered_nonce(&self) -> MessageNonce {
+		self.relayers
+			.back()
+			.map(|(_, _, end)| *end)
+			.unwrap_or(self.last_confirmed_nonce)
+	}
+
+	/// Apply action to the model and to the lanes and check that results are the same.
+	fn apply(&mut self, action: &Action) {
+		match *action {
+			Action::Send => {
+				let nonce = outbound_lane::<TestRuntime, ()>(TEST_LANE_ID)
+					.send_message(outbound_message_data(REGULAR_PAYLOAD));
+				self.latest_generated_nonce += 1;
+				assert_eq!(nonce, self.latest_generated_nonce);
+			},
+			Action::Deliver { relayer, max_messages } => {
+				let relayer = RELAYERS[relayer];
+				let begin = self.last_delivered_nonce() + 1;
+				let end = sp_std::cmp::min(
+					self.last_delivered_nonce() + max_messages,
+					self.latest_generated_nonce,
+				);
+				for nonce in begin..=end {
+					let result = inbound_lane::<TestRuntime, ()>(TEST_LANE_ID)
+						.receive_message::<TestMessageDispatch, _>(
+							&relayer,
+							&relayer,
+							nonce,
+							inbound_message_data(REGULAR_PAYLOAD),
+						);
+					let expected_result = self.receive_message(relayer, nonce);
+					match (result, expected_result) {
+						(ReceivalResult::Dispatched(_), None) => (),
+						(result, Some(expected_result)) => {
+							assert_eq!(result, expected_result);
+							break
+						},
+						(result, None) => panic!("Unexpected receival result: {result:?}"),
+					}
+				}
+			},
+			Action::ReceiveStateUpdate => {
+				let outbound_lane_data = outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data();
+				let result = inbound_lane::<TestRuntime, ()>(TEST_LANE_ID)
+					.receive_state_update(outbound_lane_data);
+				assert_eq!(result, self.receive_state_update());
+			},
+			Action::Confirm => {
+				let inbound_lane_data =
+					inbound_lane::<TestRuntime, ()>(TEST_LANE_ID).storage().data();
+				let latest_delivered_nonce = inbound_lane_data.last_delivered_nonce();
+				let result = outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).confirm_delivery(
+					MessageNonce::MAX,
+					latest_delivered_nonce,
+					&inbound_lane_data.relayers,
+				);
+				assert_eq!(result, self.confirm_delivery(latest_delivered_nonce));
+			},
+			Action::Prune { max_messages } => {
+				let db_weight = RocksDbWeight::get();
+				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID)
+					.prune_messages(db_weight, db_weight.writes(max_messages + 1));
+				let max_oldest_unpruned_nonce = self.latest_received_nonce + 1;
+				self.oldest_unpruned_nonce = sp_std::cmp::min(
+					self.oldest_unpruned_nonce + max_messages,
+					max_oldest_unpruned_nonce,
+				);
+			},
+		}
+	}
+
+	/// Receive message. Returns `None` if message is dispatched or error otherwise.
+	fn receive_message(
+		&mut self,
+		relayer: TestRelayer,
+		nonce: MessageNonce,
+	) -> Option<ReceivalResult<()>> {
+		if self.relayers.len() as MessageNonce >= MaxUnrewardedRelayerEntriesAtInboundLane::get() {
+			return Some(ReceivalResult::TooManyUnrewardedRelayers)
+		}
+		if nonce - self.last_confirmed_nonce > MaxUnconfirmedMessagesAtInboundLane::get() {
+			return Some(ReceivalResult::TooManyUnconfirmedMessages)
+		}
+
+		match self.relayers.back_mut() {
+			Some((last_relayer, _, end)) if *last_relayer == relayer => *end = nonce,
+			_ => self.relayers.push_back((relayer, nonce, nonce)),
+		}
+		None
+	}
+
+	/// Receive outbound lane state at the inbound lane.
+	fn receive_state_update(&mut self) -> Option<MessageNonce> {
+		let new_confirmed_nonce = self.latest_received_nonce;
+		if new_confirmed_nonce > self.last_delivered_nonce() ||
+			new_confirmed_nonce <= self.last_confirmed_nonce
+		{
+			return None
+		}
+
+		self.last_confirmed_nonce = new_confirmed_nonce;
+		self.relayers.retain(|(_, _, end)| *end > new_confirmed_nonce);
+		if let Some((_, begin, _)) = self.relayers.front_mut() {
+			*begin = sp_std::cmp::max(*begin, new_confirmed_nonce + 1);
+		}
+		Some(new_confirmed_nonce)
+	}
+
+	/// Confirm delivery of messages at the outbound lane.
+	fn confirm_delivery(
+		&mut self,
+		latest_delivered_nonce: MessageNonce,
+	) -> ReceivalConfirmationResult {
+		if latest_delivered_nonce <= self.latest_received_nonce {
+			return ReceivalConfirmationResult::NoNewConfirmations
+		}
+
+		let confirmed = DeliveredMessages {
+			begin: self.latest_received_nonce + 1,
+			end: latest_delivered_nonce,
+		};
+		self.latest_received_nonce = latest_delivered_nonce;
+		ReceivalConfirmationResult::ConfirmedMessages(confirmed)
+	}
+
+	/// Ensure that lanes state matches the model and that lanes invariants are preserved.
+	fn ensure_matches_lanes(&self) {
+		let outbound_lane_data = outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data();
+		assert_eq!(outbound_lane_data.oldest_unpruned_nonce, self.oldest_unpruned_nonce);
+		assert_eq!(outbound_lane_data.latest_received_nonce, self.latest_received_nonce);
+		assert_eq!(outbound_lane_data.latest_generated_nonce, self.latest_generated_nonce);
+		for nonce in 1..=self.latest_generated_nonce {
+			assert_eq!(
+				OutboundMessages::<TestRuntime, ()>::contains_key(MessageKey {
+					lane_id: TEST_LANE_ID,
+					nonce
+				}),
+				nonce >= self.oldest_unpruned_nonce,
+				"Unexpected presence of the message {nonce} in the storage",
+			);
+		}
+
+		let inbound_lane_data = inbound_lane::<TestRuntime, ()>(TEST_LANE_ID).storage().data();
+		assert_eq!(inbound_lane_data.last_confirmed_nonce, self.last_confirmed_nonce);
+		assert_eq!(
+			inbound_lane_data
+				.relayers
+				.iter()
+				.map(|entry| (entry.relayer, entry.messages.begin, entry.messages.end))
+				.collect::<VecDeque<_>>(),
+			self.relayers,
+		);
+		assert!(
+			inbound_lane_data.relayers.len() as MessageNonce <=
+				MaxUnrewardedRelayerEntriesAtInboundLane::get()
+		);
+		assert!(
+			inbound_lane_data.last_delivered_nonce() - inbound_lane_data.last_confirmed_nonce <=
+				MaxUnconfirmedMessagesAtInboundLane::get()
+		);
+		let mut expected_begin = inbound_lane_data.last_confirmed_nonce + 1;
+		for entry in &inbound_lane_data.relayers {
+			assert_eq!(entry.messages.begin, expected_begin);
+			assert!(entry.messages.begin <= entry.messages.end);
+			expected_begin = entry.messages.end + 1;
+		}
+
+		assert!(self.oldest_unpruned_nonce <= self.latest_received_nonce + 1);
+		assert!(self.latest_received_nonce <= self.last_delivered_nonce());
+		assert!(self.last_confirmed_nonce <= self.latest_received_nonce);
+		assert!(self.last_delivered_nonce() <= self.latest_generated_nonce);
+	}
+}
+
+proptest! {
+	#[test]
+	fn lanes_match_model(actions in proptest::collection::vec(action(), 1..256)) {
+		run_test(|| {
+			let mut model = LanesModel::new();
+			for action in &actions {
+				model.apply(action);
+				model.ensure_matches_lanes();
+			}
+		});
+	}
+}
@@ -26,6 +26,12 @@
 //! Once message is sent, its progress can be tracked by looking at module events.
 //! The assigned nonce is reported using `MessageAccepted` event. When message is
 //! delivered to the the bridged chain, it is reported using `MessagesDelivered` event.
+//! If active outbound lane has pending messages, but doesn't make any progress for
+//! `StaleLaneThreshold` blocks, it is reported using `LaneStale` event.
+//!
+//! Total dispatch weight of inbound messages at every block may be limited using the
+//! `MaxDispatchWeightPerBlock` constant. Messages that are delivered once the limit is
+//! reached, are accepted, but their dispatch is deferred to subsequent blocks.
 //!
 //! **IMPORTANT NOTE**: after generating weights (custom `WeighInfo` implementation) for
 //! your runtime (where this module is plugged to), please add test for these weights.
@@ -53,20 +59,25 @@ use crate::{
 
 use bp_messages::{
 	source_chain::{
-		DeliveryConfirmationPayments, LaneMessageVerifier, SendMessageArtifacts, TargetHeaderChain,
+		DeliveryConfirmationPayments, DynamicLanes, LaneMessageVerifier, OnMessagesDelivered,
+		SendMessageArtifacts, TargetHeaderChain,
 	},
 	target_chain::{
-		DeliveryPayments, DispatchMessage, MessageDispatch, ProvedLaneMessages, ProvedMessages,
-		SourceHeaderChain,
+		DeliveryPayments, DispatchMessage, DispatchMessageData, MessageDispatch, SourceHeaderChain,
 	},
 	total_unrewarded_messages, DeliveredMessages, InboundLaneData, InboundMessageDetails, LaneId,
-	MessageKey, MessageNonce, MessagePayload, MessagesOperatingMode, OutboundLaneData,
-	OutboundMessageDetails, UnrewardedRelayersState,
+	LaneStatistics, MessageKey, MessageNonce, MessagePayload, MessagesOperatingMode,
+	OutboundLaneData, OutboundMessageDetails, ReceivalResult, ReceivedMessages,
+	UnrewardedRelayersState,
 };
-use bp_runtime::{BasicOperatingMode, ChainId, OwnedBridgeModule, Size};
+use bp_runtime::{messages::MessageDispatchResult, ChainId, OwnedBridgeModule, Size};
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::{dispatch::PostDispatchInfo, ensure, fail, traits::Get};
-use sp_runtime::traits::UniqueSaturatedFrom;
+use frame_support::{dispatch::PostDispatchInfo, ensure, fail, traits::Get, weights::Weight};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Saturating, UniqueSaturatedFrom, Zero},
+	RuntimeDebug,
+};
 use sp_std::{cell::RefCell, marker::PhantomData, prelude::*};
 
 mod inbound_lane;
@@ -78,6 +89,8 @@ pub mod weights;
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
+#[cfg(test)]
+mod lanes_model_tests;
 #[cfg(test)]
 mod mock;
 
@@ -89,7 +102,6 @@ pub const LOG_TARGET: &str = "runtime::bridge-messages";
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use bp_messages::{ReceivalResult, ReceivedMessages};
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
@@ -109,6 +121,13 @@ pub mod pallet {
 
 		/// Get all active outbound lanes that the message pallet is serving.
 		type ActiveOutboundLanes: Get<&'static [LaneId]>;
+		/// Lanes that are opened dynamically. Messages may be sent over these lanes in addition to
+		/// the `ActiveOutboundLanes`.
+		type DynamicLanes: DynamicLanes;
+		/// Number of blocks that the active outbound lane may have undelivered or unconfirmed
+		/// messages without any progress, before it is reported using `LaneStale` event. Zero
+		/// value disables the check.
+		type StaleLaneThreshold: Get<Self::BlockNumber>;
 		/// Maximal number of unrewarded relayer entries at inbound lane. Unrewarded means that the
 		/// relayer has delivered messages, but either confirmations haven't been delivered back to
 		/// the source chain, or we haven't received reward confirmations yet.
@@ -132,6 +151,21 @@ pub mod pallet {
 		/// Transaction that is declaring more messages than this value, will be rejected. Even if
 		/// these messages are from different lanes.
 		type MaxUnconfirmedMessagesAtInboundLane: Get<MessageNonce>;
+		/// Maximal total dispatch weight of inbound messages (across all inbound lanes) that may
+		/// be dispatched in a single block. Messages, delivered when the budget is exhausted, are
+		/// accepted, but their dispatch is deferred to subsequent blocks. Zero value disables the
+		/// limit.
+		type MaxDispatchWeightPerBlock: Get<Weight>;
+		/// Maximal number of messages at every inbound lane, dispatch of which has been deferred.
+		/// Messages, that would be deferred when the limit is reached, are rejected by the lane,
+		/// so the relayer needs to deliver them again later.
+		type MaxDeferredMessagesPerLane: Get<MessageNonce>;
+		/// Maximal weight that may be spent on dispatching deferred messages in a single block.
+		///
+		/// Unlike the `MaxDispatchWeightPerBlock`, this limit is always applied. It bounds the
+		/// weight of the `on_initialize` hook e.g. when dispatch is resumed after being paused for
+		/// a long time.
+		type MaxDeferredDispatchWeightPerBlock: Get<Weight>;
 
 		/// Maximal encoded size of the outbound payload.
 		#[pallet::constant]
@@ -155,6 +189,10 @@ pub mod pallet {
 		type LaneMessageVerifier: LaneMessageVerifier<Self::RuntimeOrigin, Self::OutboundPayload>;
 		/// Delivery confirmation payments.
 		type DeliveryConfirmationPayments: DeliveryConfirmationPayments<Self::AccountId>;
+		/// Callback that is called when delivery of outbound messages is confirmed. Use
+		/// `LaneDeliveryCallback` to route confirmations of every lane to the pallet that has
+		/// sent messages over this lane.
+		type OnMessagesDelivered: OnMessagesDelivered;
 
 		// Types that are used by inbound_lane (on target chain).
 
@@ -192,27 +230,62 @@ pub mod pallet {
 	where
 		u32: TryFrom<<T as frame_system::Config>::BlockNumber>,
 	{
+		fn on_initialize(block: T::BlockNumber) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut used_weight = check_stale_lanes::<T, I>(block);
+
+			// deferred messages are not dispatched while dispatch is paused
+			used_weight.saturating_accrue(db_weight.reads(1));
+			if !PalletOperatingMode::<T, I>::get().is_dispatch_paused() {
+				// weight of checking stale lanes is also accounted in the deferred dispatch limit
+				used_weight = dispatch_deferred_messages::<T, I>(used_weight);
+			}
+
+			used_weight
+		}
+
 		fn on_idle(_block: T::BlockNumber, remaining_weight: Weight) -> Weight {
-			// we'll need at least to read outbound lane state, kill a message and update lane state
+			// we'll need at least to read number of dynamic lanes and outbound lane state, kill a
+			// message and update lane state
 			let db_weight = T::DbWeight::get();
-			if !remaining_weight.all_gte(db_weight.reads_writes(1, 2)) {
+			if !remaining_weight.all_gte(db_weight.reads_writes(2, 2)) {
 				return Weight::zero()
 			}
 
 			// messages from lane with index `i` in `ActiveOutboundLanes` are pruned when
 			// `System::block_number() % lanes.len() == i`. Otherwise we need to read lane states on
 			// every block, wasting the whole `remaining_weight` for nothing and causing starvation
-			// of the last lane pruning
+			// of the last lane pruning. Dynamic lanes are following static lanes
 			let active_lanes = T::ActiveOutboundLanes::get();
-			let active_lanes_len = (active_lanes.len() as u32).into();
+			// number of dynamic lanes is read from the runtime storage
+			let mut used_weight = db_weight.reads(1);
+			let lanes_count =
+				(active_lanes.len() as u32).saturating_add(T::DynamicLanes::lanes_count());
+			if lanes_count == 0 {
+				return used_weight
+			}
 			let active_lane_index = u32::unique_saturated_from(
-				frame_system::Pallet::<T>::block_number() % active_lanes_len,
+				frame_system::Pallet::<T>::block_number() % lanes_count.into(),
 			);
-			let active_lane_id = active_lanes[active_lane_index as usize];
+			let active_lane_id = match active_lanes.get(active_lane_index as usize) {
+				Some(active_lane_id) => *active_lane_id,
+				None => {
+					// dynamic lanes are stored in the runtime storage
+					used_weight += db_weight.reads(1);
+					if !remaining_weight.all_gte(used_weight + db_weight.reads_writes(1, 2)) {
+						return used_weight
+					}
+
+					match T::DynamicLanes::lane_at(active_lane_index - active_lanes.len() as u32) {
+						Some(active_lane_id) => active_lane_id,
+						None => return used_weight,
+					}
+				},
+			};
 
 			// first db read - outbound lane state
 			let mut active_lane = outbound_lane::<T, I>(active_lane_id);
-			let mut used_weight = db_weight.reads(1);
+			used_weight += db_weight.reads(1);
 			// and here we'll have writes
 			used_weight += active_lane.prune_messages(db_weight, remaining_weight - used_weight);
 
@@ -249,8 +322,14 @@ pub mod pallet {
 		/// The weight of the call assumes that the transaction always brings outbound lane
 		/// state update. Because of that, the submitter (relayer) has no benefit of not including
 		/// this data in the transaction, so reward confirmations lags should be minimal.
+		///
+		/// The weight of the call also assumes that dispatch of all messages is deferred, so
+		/// their payloads are written to the storage. It is refunded for messages that are
+		/// dispatched immediately. Dispatch weight of deferred messages is never refunded - the
+		/// relayer pays for their dispatch at the delivery time.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::receive_messages_proof_weight(proof, *messages_count, *dispatch_weight))]
+		#[pallet::weight(T::WeightInfo::receive_messages_proof_weight(proof, *messages_count, *dispatch_weight)
+			.saturating_add(deferred_messages_weight::<T, I>(*messages_count, *messages_count, proof.size())))]
 		pub fn receive_messages_proof(
 			origin: OriginFor<T>,
 			relayer_id_at_bridged_chain: T::InboundRelayer,
@@ -259,6 +338,11 @@ pub mod pallet {
 			dispatch_weight: Weight,
 		) -> DispatchResultWithPostInfo {
 			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			let operating_mode = PalletOperatingMode::<T, I>::get();
+			ensure!(
+				operating_mode.is_accepting_inbound_messages(),
+				Error::<T, I>::NotOperatingNormally
+			);
 			let relayer_id_at_this_chain = ensure_signed(origin)?;
 
 			// reject transactions that are declaring too many messages
@@ -277,31 +361,42 @@ pub mod pallet {
 			//
 			// The DeclaredWeight is exactly what's computed here. Unfortunately it is impossible
 			// to get pre-computed value (and it has been already computed by the executive).
+			let declared_deferred_messages_weight =
+				deferred_messages_weight::<T, I>(messages_count, messages_count, proof.size());
 			let declared_weight = T::WeightInfo::receive_messages_proof_weight(
 				&proof,
 				messages_count,
 				dispatch_weight,
-			);
+			)
+			.saturating_add(declared_deferred_messages_weight);
 			let mut actual_weight = declared_weight;
 
 			// verify messages proof && convert proof into messages
-			let messages = verify_and_decode_messages_proof::<
-				T::SourceHeaderChain,
-				T::InboundPayload,
-			>(proof, messages_count)
-			.map_err(|err| {
-				log::trace!(target: LOG_TARGET, "Rejecting invalid messages proof: {:?}", err,);
+			//
+			// `receive_messages_proof` weight formula and `MaxUnconfirmedMessagesAtInboundLane`
+			// check guarantees that the `message_count` is sane and Vec<Message> may be allocated.
+			// (tx with too many messages will either be rejected from the pool, or will fail
+			// earlier)
+			let messages = T::SourceHeaderChain::verify_messages_proof(proof, messages_count)
+				.map_err(|err| {
+					log::trace!(target: LOG_TARGET, "Rejecting invalid messages proof: {:?}", err,);
 
-				Error::<T, I>::InvalidMessagesProof
-			})?;
+					Error::<T, I>::InvalidMessagesProof
+				})?;
 
 			// dispatch messages and (optionally) update lane(s) state(s)
 			let mut total_messages = 0;
 			let mut valid_messages = 0;
+			let mut total_lanes = 0;
+			let mut deferred_lanes = 0;
+			let mut deferred_messages = 0;
+			let mut deferred_payload_size = 0u32;
 			let mut messages_received_status = Vec::with_capacity(messages.len());
 			let mut dispatch_weight_left = dispatch_weight;
+			let mut block_dispatch_weight = DispatchWeightUsed::<T, I>::get();
 			for (lane_id, lane_data) in messages {
 				let mut lane = inbound_lane::<T, I>(lane_id);
+				total_lanes += 1;
 
 				// subtract extra storage proof bytes from the actual PoV size - there may be
 				// less unrewarded relayers than the maximal configured value
@@ -325,11 +420,22 @@ pub mod pallet {
 				let mut lane_messages_received_status =
 					ReceivedMessages::new(lane_id, Vec::with_capacity(lane_data.messages.len()));
 				let mut is_lane_processing_stopped_no_weight_left = false;
+				let mut lane_statistics = LanesStatistics::<T, I>::get(lane_id);
+				// we can't dispatch lane messages out-of-order, so once some message of the lane
+				// is deferred, all subsequent messages of this lane are also deferred
+				let mut lane_deferred_messages = InboundLanesDeferredMessages::<T, I>::get(lane_id);
 
-				for mut message in lane_data.messages {
-					debug_assert_eq!(message.key.lane_id, lane_id);
+				for raw_message in lane_data.messages {
+					debug_assert_eq!(raw_message.key.lane_id, lane_id);
 					total_messages += 1;
 
+					let mut message = DispatchMessage {
+						key: raw_message.key,
+						data: DispatchMessageData {
+							payload: T::InboundPayload::decode(&mut &raw_message.payload[..]),
+						},
+					};
+
 					if is_lane_processing_stopped_no_weight_left {
 						lane_messages_received_status
 							.push_skipped_for_not_enough_weight(message.key.nonce);
@@ -354,12 +460,33 @@ pub mod pallet {
 						continue
 					}
 
-					let receival_result = lane.receive_message::<T::MessageDispatch, T::AccountId>(
-						&relayer_id_at_bridged_chain,
-						&relayer_id_at_this_chain,
-						message.key.nonce,
-						message.data,
-					);
+					let defer_dispatch = operating_mode.is_dispatch_paused() ||
+						lane_deferred_messages.is_some() ||
+						is_dispatch_budget_exceeded::<T, I>(
+							block_dispatch_weight,
+							message_dispatch_weight,
+						);
+					let receival_result = if defer_dispatch {
+						let lane_deferred_messages_count = lane_deferred_messages
+							.as_ref()
+							.map(|deferred| deferred.total_messages())
+							.unwrap_or(0);
+						if lane_deferred_messages_count >= T::MaxDeferredMessagesPerLane::get() {
+							ReceivalResult::TooManyDeferredMessages
+						} else {
+							lane.receive_deferred_message(
+								&relayer_id_at_bridged_chain,
+								message.key.nonce,
+							)
+						}
+					} else {
+						lane.receive_message::<T::MessageDispatch, T::AccountId>(
+							&relayer_id_at_bridged_chain,
+							&relayer_id_at_this_chain,
+							message.key.nonce,
+							message.data,
+						)
+					};
 
 					// note that we're returning unspent weight to relayer even if message has been
 					// rejected by the lane. This allows relayers to submit spam transactions with
@@ -370,11 +497,47 @@ pub mod pallet {
 					let unspent_weight = match &receival_result {
 						ReceivalResult::Dispatched(dispatch_result) => {
 							valid_messages += 1;
-							dispatch_result.unspent_weight
+							lane_statistics.messages_received.saturating_inc();
+							let unspent_weight =
+								dispatch_result.unspent_weight.min(message_dispatch_weight);
+							block_dispatch_weight.saturating_accrue(
+								message_dispatch_weight.saturating_sub(unspent_weight),
+							);
+							unspent_weight
+						},
+						ReceivalResult::Deferred => {
+							// the message will be dispatched at one of subsequent blocks, but the
+							// relayer pays for its dispatch (it has declared the dispatch weight)
+							// and for storing the message payload now
+							valid_messages += 1;
+							deferred_messages += 1;
+							deferred_payload_size = deferred_payload_size
+								.saturating_add(raw_message.payload.len() as u32);
+							lane_statistics.messages_received.saturating_inc();
+							DeferredMessages::<T, I>::insert(
+								&message.key,
+								DeferredMessage {
+									relayer: relayer_id_at_this_chain.clone(),
+									payload: raw_message.payload,
+								},
+							);
+							match lane_deferred_messages {
+								Some(ref mut deferred) => deferred.note_dispatched_message(),
+								None =>
+									lane_deferred_messages =
+										Some(DeliveredMessages::new(message.key.nonce)),
+							}
+							Weight::zero()
 						},
-						ReceivalResult::InvalidNonce |
+						// already delivered (or not yet expected) messages are not failures - they
+						// are just ignored by the lane
+						ReceivalResult::InvalidNonce => message_dispatch_weight,
 						ReceivalResult::TooManyUnrewardedRelayers |
-						ReceivalResult::TooManyUnconfirmedMessages => message_dispatch_weight,
+						ReceivalResult::TooManyUnconfirmedMessages |
+						ReceivalResult::TooManyDeferredMessages => {
+							lane_statistics.messages_failed.saturating_inc();
+							message_dispatch_weight
+						},
 					};
 					lane_messages_received_status.push(message.key.nonce, receival_result);
 
@@ -383,8 +546,29 @@ pub mod pallet {
 					actual_weight = actual_weight.saturating_sub(unspent_weight);
 				}
 
+				LanesStatistics::<T, I>::insert(lane_id, lane_statistics);
+				if let Some(lane_deferred_messages) = lane_deferred_messages {
+					deferred_lanes += 1;
+					InboundLanesDeferredMessages::<T, I>::insert(lane_id, lane_deferred_messages);
+				}
 				messages_received_status.push(lane_messages_received_status);
 			}
+			if !T::MaxDispatchWeightPerBlock::get().is_zero() {
+				DispatchWeightUsed::<T, I>::put(block_dispatch_weight);
+			}
+
+			// refund weight of storing payloads of messages that have not been deferred. Deferred
+			// messages range is read for every lane, but it is only updated for lanes that have
+			// deferred messages
+			let actual_deferred_messages_weight = deferred_messages_weight::<T, I>(
+				deferred_lanes,
+				deferred_messages,
+				deferred_payload_size,
+			)
+			.saturating_add(T::DbWeight::get().reads((total_lanes - deferred_lanes) as u64));
+			actual_weight = actual_weight.saturating_sub(
+				declared_deferred_messages_weight.saturating_sub(actual_deferred_messages_weight),
+			);
 
 			// let's now deal with relayer payments
 			T::DeliveryPayments::pay_reward(
@@ -409,19 +593,27 @@ pub mod pallet {
 		}
 
 		/// Receive messages delivery proof from bridged chain.
+		///
+		/// The weight of the call includes the weight of the `OnMessagesDelivered` callback for
+		/// all messages, declared in the `relayers_state`. It is refunded if no new messages
+		/// have been confirmed.
 		#[pallet::call_index(3)]
 		#[pallet::weight(T::WeightInfo::receive_messages_delivery_proof_weight(
 			proof,
 			relayers_state,
-		))]
+		).saturating_add(max_on_messages_delivered_weight::<T, I>(relayers_state)))]
 		pub fn receive_messages_delivery_proof(
 			origin: OriginFor<T>,
 			proof: MessagesDeliveryProofOf<T, I>,
 			relayers_state: UnrewardedRelayersState,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
 
 			let confirmation_relayer = ensure_signed(origin)?;
+			let max_callback_weight = max_on_messages_delivered_weight::<T, I>(&relayers_state);
+			let mut actual_weight =
+				T::WeightInfo::receive_messages_delivery_proof_weight(&proof, &relayers_state)
+					.saturating_add(max_callback_weight);
 			let (lane_id, lane_data) = T::TargetHeaderChain::verify_messages_delivery_proof(proof)
 				.map_err(|err| {
 					log::trace!(
@@ -487,9 +679,14 @@ pub mod pallet {
 			if let Some(confirmed_messages) = confirmed_messages {
 				// emit 'delivered' event
 				let received_range = confirmed_messages.begin..=confirmed_messages.end;
+				LanesStatistics::<T, I>::mutate(lane_id, |statistics| {
+					statistics.messages_delivered = statistics
+						.messages_delivered
+						.saturating_add(confirmed_messages.total_messages());
+				});
 				Self::deposit_event(Event::MessagesDelivered {
 					lane_id,
-					messages: confirmed_messages,
+					messages: confirmed_messages.clone(),
 				});
 
 				// if some new messages have been confirmed, reward relayers
@@ -499,6 +696,15 @@ pub mod pallet {
 					&confirmation_relayer,
 					&received_range,
 				);
+
+				// and let the sending pallet know that its messages have been delivered
+				T::OnMessagesDelivered::on_messages_delivered(lane_id, &confirmed_messages);
+				actual_weight = actual_weight
+					.saturating_sub(max_callback_weight)
+					.saturating_add(T::OnMessagesDelivered::weight(&confirmed_messages));
+			} else {
+				// the callback has not been called, so let's refund its weight
+				actual_weight = actual_weight.saturating_sub(max_callback_weight);
 			}
 
 			log::trace!(
@@ -508,7 +714,7 @@ pub mod pallet {
 				lane_id,
 			);
 
-			Ok(())
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
 		}
 	}
 
@@ -527,6 +733,17 @@ pub mod pallet {
 		),
 		/// Messages in the inclusive range have been delivered to the bridged chain.
 		MessagesDelivered { lane_id: LaneId, messages: DeliveredMessages },
+		/// Active outbound lane has undelivered or unconfirmed messages and hasn't made any
+		/// progress since given block.
+		LaneStale { lane: LaneId, since: T::BlockNumber },
+		/// Message, that has been received at one of previous blocks, has been dispatched.
+		DeferredMessageDispatched {
+			lane_id: LaneId,
+			nonce: MessageNonce,
+			dispatch_result: MessageDispatchResult<
+				<T::MessageDispatch as MessageDispatch<T::AccountId>>::DispatchLevelResult,
+			>,
+		},
 	}
 
 	#[pallet::error]
@@ -604,6 +821,42 @@ pub mod pallet {
 	pub type OutboundMessages<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Blake2_128Concat, MessageKey, StoredMessagePayload<T, I>>;
 
+	/// Map of lane id => aggregate statistics of the lane.
+	#[pallet::storage]
+	#[pallet::getter(fn lane_statistics)]
+	pub type LanesStatistics<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, LaneStatistics, ValueQuery>;
+
+	/// Map of lane id => progress of the active outbound lane with pending messages.
+	///
+	/// Entries are maintained by the `on_initialize` hook and are removed once the lane
+	/// has no undelivered or unconfirmed messages.
+	#[pallet::storage]
+	pub type OutboundLanesProgress<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, OutboundLaneProgress<T::BlockNumber>>;
+
+	/// Total dispatch weight of inbound messages that have been dispatched at the current block.
+	///
+	/// It is only maintained if `MaxDispatchWeightPerBlock` is non-zero.
+	#[pallet::storage]
+	pub type DispatchWeightUsed<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, Weight, ValueQuery>;
+
+	/// Map of lane id => range of inbound messages that have been received, but are not yet
+	/// dispatched.
+	#[pallet::storage]
+	pub type InboundLanesDeferredMessages<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, DeliveredMessages>;
+
+	/// All inbound messages that have been received, but are not yet dispatched.
+	///
+	/// Size of the message payload is limited by the bridged chain, so the storage is unbounded
+	/// here.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type DeferredMessages<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, MessageKey, DeferredMessage<T::AccountId>>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
 		/// Initial pallet operating mode.
@@ -660,6 +913,18 @@ pub mod pallet {
 		pub fn inbound_lane_data(lane: LaneId) -> InboundLaneData<T::InboundRelayer> {
 			InboundLanes::<T, I>::get(lane).0
 		}
+
+		/// Return outbound lane data.
+		pub fn outbound_lane_data(lane: LaneId) -> OutboundLaneData {
+			OutboundLanes::<T, I>::get(lane)
+		}
+
+		/// Note that rewards have been registered for relayers, serving given lane.
+		pub fn note_rewards_paid(lane: LaneId, rewards: u128) {
+			LanesStatistics::<T, I>::mutate(lane, |statistics| {
+				statistics.rewards_paid = statistics.rewards_paid.saturating_add(rewards);
+			});
+		}
 	}
 
 	/// Get-parameter that returns number of active outbound lanes that the pallet maintains.
@@ -689,6 +954,32 @@ where
 	}
 }
 
+/// Returns weight of deferring dispatch of inbound messages.
+///
+/// It includes reading and updating deferred messages range (`InboundLanesDeferredMessages`) of
+/// given number of lanes and storing given number of message payloads of `payload_size` bytes.
+fn deferred_messages_weight<T: Config<I>, I: 'static>(
+	lanes_count: u32,
+	messages_count: u32,
+	payload_size: u32,
+) -> Weight {
+	T::DbWeight::get()
+		.reads_writes(lanes_count as u64, lanes_count.saturating_add(messages_count) as u64)
+		.saturating_add(Weight::from_parts(0, payload_size as u64))
+}
+
+/// Returns maximal weight of the `OnMessagesDelivered` callback that may be called by the
+/// `receive_messages_delivery_proof` call with given relayers state.
+fn max_on_messages_delivered_weight<T: Config<I>, I: 'static>(
+	relayers_state: &UnrewardedRelayersState,
+) -> Weight {
+	// the call fails if the proof confirms more than `total_messages` messages
+	T::OnMessagesDelivered::weight(&DeliveredMessages {
+		begin: 1,
+		end: relayers_state.total_messages,
+	})
+}
+
 /// Function that actually sends message.
 fn send_message<T: Config<I>, I: 'static>(
 	submitter: T::RuntimeOrigin,
@@ -698,10 +989,14 @@ fn send_message<T: Config<I>, I: 'static>(
 	SendMessageArtifacts,
 	sp_runtime::DispatchErrorWithPostInfo<PostDispatchInfo>,
 > {
-	ensure_normal_operating_mode::<T, I>()?;
+	ensure_accepting_outbound_messages::<T, I>()?;
 
 	// let's check if outbound lane is active
-	ensure!(T::ActiveOutboundLanes::get().contains(&lane_id), Error::<T, I>::InactiveOutboundLane,);
+	ensure!(
+		T::ActiveOutboundLanes::get().contains(&lane_id) ||
+			T::DynamicLanes::is_lane_opened(&lane_id),
+		Error::<T, I>::InactiveOutboundLane,
+	);
 
 	// let's first check if message can be delivered to target chain
 	T::TargetHeaderChain::verify_message(&payload).map_err(|err| {
@@ -738,6 +1033,10 @@ fn send_message<T: Config<I>, I: 'static>(
 		Error::<T, I>::MessageIsTooLarge
 	);
 	let nonce = lane.send_message(encoded_payload);
+	LanesStatistics::<T, I>::mutate(lane_id, |statistics| {
+		statistics.messages_sent.saturating_inc();
+		statistics.bytes_sent = statistics.bytes_sent.saturating_add(encoded_payload_len as u64);
+	});
 
 	log::trace!(
 		target: LOG_TARGET,
@@ -750,21 +1049,20 @@ fn send_message<T: Config<I>, I: 'static>(
 	Pallet::<T, I>::deposit_event(Event::MessageAccepted { lane_id, nonce });
 
 	// we may introduce benchmarks for that, but no heavy ops planned here apart from
-	// db reads and writes. There are currently 2 db reads and 2 db writes:
-	// - one db read for operation mode check (`ensure_normal_operating_mode`);
+	// db reads and writes. There are currently 3 db reads and 3 db writes:
+	// - one db read for operation mode check (`ensure_accepting_outbound_messages`);
 	// - one db read for outbound lane state (`outbound_lane`);
 	// - one db write for outbound lane state (`send_message`);
 	// - one db write for the message (`send_message`);
-	let actual_weight = T::DbWeight::get().reads_writes(2, 2);
+	// - one db read and one db write for the lane statistics;
+	let actual_weight = T::DbWeight::get().reads_writes(3, 3);
 
 	Ok(SendMessageArtifacts { nonce, weight: actual_weight })
 }
 
-/// Ensure that the pallet is in normal operational mode.
-fn ensure_normal_operating_mode<T: Config<I>, I: 'static>() -> Result<(), Error<T, I>> {
-	if PalletOperatingMode::<T, I>::get() ==
-		MessagesOperatingMode::Basic(BasicOperatingMode::Normal)
-	{
+/// Ensure that the pallet is in operating mode that allows sending outbound messages.
+fn ensure_accepting_outbound_messages<T: Config<I>, I: 'static>() -> Result<(), Error<T, I>> {
+	if PalletOperatingMode::<T, I>::get().is_accepting_outbound_messages() {
 		return Ok(())
 	}
 
@@ -796,6 +1094,238 @@ fn outbound_lane<T: Config<I>, I: 'static>(
 	OutboundLane::new(RuntimeOutboundLaneStorage { lane_id, _phantom: Default::default() })
 }
 
+/// Progress of the active outbound lane with pending messages, observed by the pallet.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct OutboundLaneProgress<BlockNumber> {
+	/// Nonce of the latest message, received by the bridged chain, at the `since` block.
+	pub latest_received_nonce: MessageNonce,
+	/// Block since which the lane hasn't made any progress.
+	pub since: BlockNumber,
+	/// True if the lane has already been reported as stale.
+	pub reported: bool,
+}
+
+/// Inbound message that has been received, but its dispatch has been deferred.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct DeferredMessage<AccountId> {
+	/// Account of the relayer that has delivered the message.
+	pub relayer: AccountId,
+	/// Message payload.
+	pub payload: MessagePayload,
+}
+
+/// Returns true if message with given dispatch weight may not be dispatched at the current block,
+/// because it would exceed the `MaxDispatchWeightPerBlock` budget.
+///
+/// The first message of the block is always dispatched, even if its weight exceeds the budget.
+/// Otherwise such message would be deferred forever.
+fn is_dispatch_budget_exceeded<T: Config<I>, I: 'static>(
+	block_dispatch_weight: Weight,
+	message_dispatch_weight: Weight,
+) -> bool {
+	let max_dispatch_weight = T::MaxDispatchWeightPerBlock::get();
+	!max_dispatch_weight.is_zero() &&
+		!block_dispatch_weight.is_zero() &&
+		block_dispatch_weight
+			.saturating_add(message_dispatch_weight)
+			.any_gt(max_dispatch_weight)
+}
+
+/// Dispatch inbound messages that have been deferred at previous blocks, respecting the
+/// `MaxDispatchWeightPerBlock` budget and the `MaxDeferredDispatchWeightPerBlock` limit.
+///
+/// The `used_weight` is the weight that has already been spent by the `on_initialize` hook. Both
+/// storage reads and dispatch weight are accounted in the `MaxDeferredDispatchWeightPerBlock`
+/// limit. Returns the total weight, spent by the hook.
+///
+/// Messages of every lane are dispatched in order. Lanes are processed in the storage order.
+fn dispatch_deferred_messages<T: Config<I>, I: 'static>(mut used_weight: Weight) -> Weight {
+	let db_weight = T::DbWeight::get();
+	let max_deferred_dispatch_weight = T::MaxDeferredDispatchWeightPerBlock::get();
+	// the first message is always dispatched, otherwise it may be deferred forever
+	let is_deferred_dispatch_limit_exceeded =
+		|used_weight: Weight, is_first_message: bool, extra_weight: Weight| {
+			!is_first_message &&
+				used_weight.saturating_add(extra_weight).any_gt(max_deferred_dispatch_weight)
+		};
+	let mut is_first_message = true;
+	let mut block_dispatch_weight = Weight::zero();
+	if !T::MaxDispatchWeightPerBlock::get().is_zero() {
+		DispatchWeightUsed::<T, I>::kill();
+		used_weight += db_weight.writes(1);
+	}
+
+	// the map is not modified while we're iterating over it
+	let mut updated_lanes = Vec::new();
+	let mut dispatched_messages = 0;
+	let mut is_budget_exceeded = false;
+	let mut deferred_lanes = InboundLanesDeferredMessages::<T, I>::iter();
+	loop {
+		// every lane read is accounted, so we need to stop even if no messages are dispatched
+		if is_deferred_dispatch_limit_exceeded(used_weight, is_first_message, db_weight.reads(1)) {
+			break
+		}
+		let (lane_id, mut deferred) = match deferred_lanes.next() {
+			Some(lane) => lane,
+			None => break,
+		};
+		used_weight += db_weight.reads(1);
+
+		while deferred.begin <= deferred.end {
+			if is_deferred_dispatch_limit_exceeded(
+				used_weight,
+				is_first_message,
+				db_weight.reads(1),
+			) {
+				is_budget_exceeded = true;
+				break
+			}
+
+			let key = MessageKey { lane_id, nonce: deferred.begin };
+			used_weight += db_weight.reads(1);
+			let deferred_message = match DeferredMessages::<T, I>::get(&key) {
+				Some(deferred_message) => deferred_message,
+				None => {
+					// shall never happen, but let's not block the lane forever
+					log::error!(
+						target: LOG_TARGET,
+						"Deferred inbound message {:?}/{} is missing from the storage",
+						lane_id,
+						key.nonce,
+					);
+					deferred.begin += 1;
+					continue
+				},
+			};
+			used_weight += Weight::from_parts(0, deferred_message.encoded_size() as u64);
+
+			let mut message = DispatchMessage {
+				key: key.clone(),
+				data: DispatchMessageData {
+					payload: T::InboundPayload::decode(&mut &deferred_message.payload[..]),
+				},
+			};
+			let message_dispatch_weight = T::MessageDispatch::dispatch_weight(&mut message);
+			if is_deferred_dispatch_limit_exceeded(
+				used_weight,
+				is_first_message,
+				message_dispatch_weight,
+			) || is_dispatch_budget_exceeded::<T, I>(
+				block_dispatch_weight,
+				message_dispatch_weight,
+			) {
+				is_budget_exceeded = true;
+				break
+			}
+			is_first_message = false;
+
+			let dispatch_result = T::MessageDispatch::dispatch(&deferred_message.relayer, message);
+			let spent_weight = message_dispatch_weight
+				.saturating_sub(dispatch_result.unspent_weight.min(message_dispatch_weight));
+			block_dispatch_weight.saturating_accrue(spent_weight);
+			used_weight.saturating_accrue(spent_weight);
+			dispatched_messages += 1;
+			Pallet::<T, I>::deposit_event(Event::DeferredMessageDispatched {
+				lane_id,
+				nonce: key.nonce,
+				dispatch_result,
+			});
+
+			DeferredMessages::<T, I>::remove(&key);
+			used_weight += db_weight.writes(1);
+			deferred.begin += 1;
+		}
+
+		updated_lanes.push((lane_id, deferred));
+		used_weight += db_weight.writes(1);
+
+		if is_budget_exceeded {
+			break
+		}
+	}
+	for (lane_id, deferred) in updated_lanes {
+		if deferred.begin > deferred.end {
+			InboundLanesDeferredMessages::<T, I>::remove(lane_id);
+		} else {
+			InboundLanesDeferredMessages::<T, I>::insert(lane_id, deferred);
+		}
+	}
+
+	if !T::MaxDispatchWeightPerBlock::get().is_zero() {
+		DispatchWeightUsed::<T, I>::put(block_dispatch_weight);
+		used_weight += db_weight.writes(1);
+	}
+	if dispatched_messages != 0 {
+		log::trace!(
+			target: LOG_TARGET,
+			"Dispatched {} deferred inbound messages. Dispatch weight: {}",
+			dispatched_messages,
+			block_dispatch_weight,
+		);
+	}
+
+	used_weight
+}
+
+/// Check active outbound lanes and report lanes that haven't made any progress for
+/// `StaleLaneThreshold` blocks.
+///
+/// At the source chain we can't distinguish undelivered messages from delivered, but not yet
+/// confirmed messages. So the lane is stale if it has pending messages and its
+/// `latest_received_nonce` hasn't changed. Every stale lane is reported once, until it makes
+/// progress. Dynamic lanes are not checked to keep the hook weight bounded.
+fn check_stale_lanes<T: Config<I>, I: 'static>(now: T::BlockNumber) -> Weight {
+	let threshold = T::StaleLaneThreshold::get();
+	if threshold.is_zero() {
+		return Weight::zero()
+	}
+
+	let db_weight = T::DbWeight::get();
+	let mut used_weight = Weight::zero();
+	for lane_id in T::ActiveOutboundLanes::get() {
+		let lane_data = OutboundLanes::<T, I>::get(lane_id);
+		let progress = OutboundLanesProgress::<T, I>::get(lane_id);
+		used_weight += db_weight.reads(2);
+
+		if lane_data.latest_received_nonce >= lane_data.latest_generated_nonce {
+			if progress.is_some() {
+				OutboundLanesProgress::<T, I>::remove(lane_id);
+				used_weight += db_weight.writes(1);
+			}
+			continue
+		}
+
+		let new_progress = match progress {
+			Some(progress) if progress.latest_received_nonce == lane_data.latest_received_nonce => {
+				if progress.reported || now.saturating_sub(progress.since) < threshold {
+					continue
+				}
+
+				log::trace!(
+					target: LOG_TARGET,
+					"Outbound lane {:?} hasn't made any progress since block {:?}",
+					lane_id,
+					progress.since,
+				);
+				Pallet::<T, I>::deposit_event(Event::LaneStale {
+					lane: *lane_id,
+					since: progress.since,
+				});
+				OutboundLaneProgress { reported: true, ..progress }
+			},
+			_ => OutboundLaneProgress {
+				latest_received_nonce: lane_data.latest_received_nonce,
+				since: now,
+				reported: false,
+			},
+		};
+		OutboundLanesProgress::<T, I>::insert(lane_id, new_progress);
+		used_weight += db_weight.writes(1);
+	}
+
+	used_weight
+}
+
 /// Runtime inbound lane storage.
 struct RuntimeInboundLaneStorage<T: Config<I>, I: 'static = ()> {
 	lane_id: LaneId,
@@ -902,45 +1432,24 @@ impl<T: Config<I>, I: 'static> OutboundLaneStorage for RuntimeOutboundLaneStorag
 	}
 }
 
-/// Verify messages proof and return proved messages with decoded payload.
-fn verify_and_decode_messages_proof<Chain: SourceHeaderChain, DispatchPayload: Decode>(
-	proof: Chain::MessagesProof,
-	messages_count: u32,
-) -> Result<ProvedMessages<DispatchMessage<DispatchPayload>>, Chain::Error> {
-	// `receive_messages_proof` weight formula and `MaxUnconfirmedMessagesAtInboundLane` check
-	// guarantees that the `message_count` is sane and Vec<Message> may be allocated.
-	// (tx with too many messages will either be rejected from the pool, or will fail earlier)
-	Chain::verify_messages_proof(proof, messages_count).map(|messages_by_lane| {
-		messages_by_lane
-			.into_iter()
-			.map(|(lane, lane_data)| {
-				(
-					lane,
-					ProvedLaneMessages {
-						lane_state: lane_data.lane_state,
-						messages: lane_data.messages.into_iter().map(Into::into).collect(),
-					},
-				)
-			})
-			.collect()
-	})
-}
-
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::mock::{
-		message, message_payload, run_test, unrewarded_relayer, AccountId, DbWeight,
-		RuntimeEvent as TestEvent, RuntimeOrigin, TestDeliveryConfirmationPayments,
-		TestDeliveryPayments, TestMessagesDeliveryProof, TestMessagesProof, TestRelayer,
-		TestRuntime, MAX_OUTBOUND_PAYLOAD_SIZE, PAYLOAD_REJECTED_BY_TARGET_CHAIN, REGULAR_PAYLOAD,
-		TEST_LANE_ID, TEST_LANE_ID_2, TEST_LANE_ID_3, TEST_RELAYER_A, TEST_RELAYER_B,
+		dispatch_result, message, message_payload, run_test, unrewarded_relayer, AccountId,
+		DbWeight, RuntimeEvent as TestEvent, RuntimeOrigin, TestDeliveryConfirmationPayments,
+		TestDeliveryPayments, TestDynamicLanes, TestMessagesDeliveryProof, TestMessagesProof,
+		TestOnMessagesDelivered, TestRelayer, TestRuntime, MAX_OUTBOUND_PAYLOAD_SIZE,
+		ON_MESSAGES_DELIVERED_WEIGHT_PER_MESSAGE, PAYLOAD_REJECTED_BY_TARGET_CHAIN,
+		REGULAR_PAYLOAD, TEST_LANE_ID, TEST_LANE_ID_2, TEST_LANE_ID_3, TEST_RELAYER_A,
+		TEST_RELAYER_B,
 	};
 	use bp_messages::{BridgeMessagesCall, UnrewardedRelayer, UnrewardedRelayersState};
+	use bp_runtime::BasicOperatingMode;
 	use bp_test_utils::generate_owned_bridge_module_tests;
 	use frame_support::{
 		assert_noop, assert_ok,
-		dispatch::Pays,
+		dispatch::{GetDispatchInfo, Pays},
 		storage::generator::{StorageMap, StorageValue},
 		traits::Hooks,
 		weights::Weight,
@@ -1206,6 +1715,130 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn pallet_defers_dispatch_in_dispatch_paused_operating_mode() {
+		run_test(|| {
+			get_ready_for_events();
+			PalletOperatingMode::<TestRuntime, ()>::put(MessagesOperatingMode::DispatchPaused);
+
+			// outbound messages are accepted
+			send_regular_message();
+
+			// inbound messages are accepted, but their dispatch is deferred
+			receive_regular_messages(1..=2);
+			assert_eq!(
+				receival_results(last_event()),
+				vec![(1, ReceivalResult::Deferred), (2, ReceivalResult::Deferred)],
+			);
+			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).last_delivered_nonce(), 2);
+
+			// deferred messages are not dispatched while dispatch is paused
+			System::<TestRuntime>::reset_events();
+			Pallet::<TestRuntime>::on_initialize(2);
+			assert_eq!(System::<TestRuntime>::events(), vec![]);
+
+			// once dispatch is resumed, deferred messages are dispatched
+			PalletOperatingMode::<TestRuntime, ()>::put(MessagesOperatingMode::Basic(
+				BasicOperatingMode::Normal,
+			));
+			Pallet::<TestRuntime>::on_initialize(3);
+			assert_eq!(
+				deferred_dispatch_results(),
+				vec![(1, dispatch_result(0)), (2, dispatch_result(0))],
+			);
+			assert_eq!(InboundLanesDeferredMessages::<TestRuntime>::get(TEST_LANE_ID), None);
+		});
+	}
+
+	#[test]
+	fn dispatch_of_deferred_messages_is_bounded_when_dispatch_is_resumed() {
+		run_test(|| {
+			get_ready_for_events();
+			PalletOperatingMode::<TestRuntime, ()>::put(MessagesOperatingMode::DispatchPaused);
+			receive_regular_messages(1..=3);
+
+			// only two messages fit the limit. The limit also includes weight of reading the
+			// operating mode, deferred lane range and weight of checking stale lanes
+			let hook_overhead = check_stale_lanes::<TestRuntime, ()>(2) + DbWeight::get().reads(2);
+			let message_weight =
+				DbWeight::get().reads_writes(1, 1) + REGULAR_PAYLOAD.declared_weight;
+			mock::MaxDeferredDispatchWeightPerBlock::set(
+				&(hook_overhead + message_weight * 2).set_proof_size(u64::MAX),
+			);
+			PalletOperatingMode::<TestRuntime, ()>::put(MessagesOperatingMode::Basic(
+				BasicOperatingMode::Normal,
+			));
+			System::<TestRuntime>::reset_events();
+			Pallet::<TestRuntime>::on_initialize(2);
+			assert_eq!(
+				deferred_dispatch_results(),
+				vec![(1, dispatch_result(0)), (2, dispatch_result(0))],
+			);
+			assert_eq!(
+				InboundLanesDeferredMessages::<TestRuntime>::get(TEST_LANE_ID),
+				Some(DeliveredMessages { begin: 3, end: 3 }),
+			);
+
+			// the first message is dispatched even if it doesn't fit the limit
+			mock::MaxDeferredDispatchWeightPerBlock::set(&Weight::zero());
+			System::<TestRuntime>::reset_events();
+			Pallet::<TestRuntime>::on_initialize(3);
+			assert_eq!(deferred_dispatch_results(), vec![(3, dispatch_result(0))]);
+			assert_eq!(InboundLanesDeferredMessages::<TestRuntime>::get(TEST_LANE_ID), None);
+		});
+	}
+
+	#[test]
+	fn pallet_accepts_only_confirmations_in_accepting_confirmations_only_operating_mode() {
+		run_test(|| {
+			// send message first to be able to check that delivery_proof works later
+			send_regular_message();
+
+			PalletOperatingMode::<TestRuntime, ()>::put(
+				MessagesOperatingMode::AcceptingConfirmationsOnly,
+			);
+
+			assert_noop!(
+				send_message::<TestRuntime, ()>(
+					RuntimeOrigin::signed(1),
+					TEST_LANE_ID,
+					REGULAR_PAYLOAD,
+				),
+				Error::<TestRuntime, ()>::NotOperatingNormally,
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_proof(
+					RuntimeOrigin::signed(1),
+					TEST_RELAYER_A,
+					Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+					1,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::NotOperatingNormally,
+			);
+
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				RuntimeOrigin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						last_confirmed_nonce: 1,
+						relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					messages_in_oldest_entry: 1,
+					total_messages: 1,
+					last_delivered_nonce: 1,
+				},
+			));
+		});
+	}
+
 	#[test]
 	fn receive_messages_proof_works() {
 		run_test(|| {
@@ -1349,18 +1982,143 @@ mod tests {
 	}
 
 	#[test]
-	fn receive_messages_delivery_proof_rewards_relayers() {
+	fn receive_messages_delivery_proof_calls_on_messages_delivered_callback_of_the_lane() {
 		run_test(|| {
-			assert_ok!(send_message::<TestRuntime, ()>(
-				RuntimeOrigin::signed(1),
-				TEST_LANE_ID,
-				REGULAR_PAYLOAD,
-			));
-			assert_ok!(send_message::<TestRuntime, ()>(
-				RuntimeOrigin::signed(1),
-				TEST_LANE_ID,
-				REGULAR_PAYLOAD,
-			));
+			for lane in [TEST_LANE_ID, TEST_LANE_ID_2] {
+				assert_ok!(send_message::<TestRuntime, ()>(
+					RuntimeOrigin::signed(1),
+					lane,
+					REGULAR_PAYLOAD,
+				));
+				assert_ok!(send_message::<TestRuntime, ()>(
+					RuntimeOrigin::signed(1),
+					lane,
+					REGULAR_PAYLOAD,
+				));
+				assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					RuntimeOrigin::signed(1),
+					TestMessagesDeliveryProof(Ok((
+						lane,
+						InboundLaneData {
+							relayers: vec![unrewarded_relayer(1, 2, TEST_RELAYER_A)]
+								.into_iter()
+								.collect(),
+							..Default::default()
+						}
+					))),
+					UnrewardedRelayersState {
+						unrewarded_relayer_entries: 1,
+						total_messages: 2,
+						last_delivered_nonce: 2,
+						..Default::default()
+					},
+				));
+			}
+
+			// callback is only registered for the `TEST_LANE_ID`
+			assert_eq!(
+				TestOnMessagesDelivered::take_delivered_messages(TEST_LANE_ID),
+				Some(DeliveredMessages { begin: 1, end: 2 }),
+			);
+			assert_eq!(TestOnMessagesDelivered::take_delivered_messages(TEST_LANE_ID_2), None);
+
+			// callback is not called if there are no new confirmations
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				RuntimeOrigin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						relayers: vec![unrewarded_relayer(1, 2, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+						..Default::default()
+					}
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					total_messages: 2,
+					last_delivered_nonce: 2,
+					..Default::default()
+				},
+			));
+			assert_eq!(TestOnMessagesDelivered::take_delivered_messages(TEST_LANE_ID), None);
+		});
+	}
+
+	#[test]
+	fn receive_messages_delivery_proof_accounts_on_messages_delivered_weight() {
+		run_test(|| {
+			send_regular_message();
+			send_regular_message();
+
+			let submit_delivery_proof = || {
+				let proof = TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						relayers: vec![unrewarded_relayer(1, 2, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+						..Default::default()
+					},
+				)));
+				let relayers_state = UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					total_messages: 2,
+					last_delivered_nonce: 2,
+					..Default::default()
+				};
+				let base_weight =
+					<TestRuntime as Config>::WeightInfo::receive_messages_delivery_proof_weight(
+						&proof,
+						&relayers_state,
+					);
+				let call = Call::<TestRuntime, ()>::receive_messages_delivery_proof {
+					proof: proof.clone(),
+					relayers_state: relayers_state.clone(),
+				};
+				let pre_dispatch_weight = call.get_dispatch_info().weight;
+				assert_eq!(
+					pre_dispatch_weight,
+					base_weight +
+						Weight::from_parts(2 * ON_MESSAGES_DELIVERED_WEIGHT_PER_MESSAGE, 0),
+				);
+				let post_dispatch_weight = Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					RuntimeOrigin::signed(1),
+					proof,
+					relayers_state,
+				)
+				.unwrap()
+				.actual_weight
+				.unwrap();
+				(pre_dispatch_weight, post_dispatch_weight)
+			};
+
+			// weight of the callback is charged when messages are confirmed
+			let (pre, post) = submit_delivery_proof();
+			assert_eq!(post, pre);
+
+			// when there are no new confirmations, the callback weight is refunded
+			let (pre, post) = submit_delivery_proof();
+			assert_eq!(
+				post,
+				pre - Weight::from_parts(2 * ON_MESSAGES_DELIVERED_WEIGHT_PER_MESSAGE, 0),
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_delivery_proof_rewards_relayers() {
+		run_test(|| {
+			assert_ok!(send_message::<TestRuntime, ()>(
+				RuntimeOrigin::signed(1),
+				TEST_LANE_ID,
+				REGULAR_PAYLOAD,
+			));
+			assert_ok!(send_message::<TestRuntime, ()>(
+				RuntimeOrigin::signed(1),
+				TEST_LANE_ID,
+				REGULAR_PAYLOAD,
+			));
 
 			// this reports delivery of message 1 => reward is paid to TEST_RELAYER_A
 			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
@@ -1579,12 +2337,13 @@ mod tests {
 				*payload.dispatch_result.unspent_weight.ref_time_mut() = unspent_weight;
 				let proof = Ok(vec![message(nonce, payload)]).into();
 				let messages_count = 1;
+				// deferred messages range of the lane is always read
 				let pre_dispatch_weight =
 					<TestRuntime as Config>::WeightInfo::receive_messages_proof_weight(
 						&proof,
 						messages_count,
 						REGULAR_PAYLOAD.declared_weight,
-					);
+					) + DbWeight::get().reads(1);
 				let result = Pallet::<TestRuntime>::receive_messages_proof(
 					RuntimeOrigin::signed(1),
 					TEST_RELAYER_A,
@@ -1887,8 +2646,8 @@ mod tests {
 
 			// if passed wight is enough to prune single message
 			assert_eq!(
-				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(1, 2)),
-				dbw.reads_writes(1, 2),
+				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(2, 2)),
+				dbw.reads_writes(2, 2),
 			);
 			assert_eq!(
 				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
@@ -1897,8 +2656,8 @@ mod tests {
 
 			// if passed wight is enough to prune two more messages
 			assert_eq!(
-				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(1, 3)),
-				dbw.reads_writes(1, 3),
+				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(2, 3)),
+				dbw.reads_writes(2, 3),
 			);
 			assert_eq!(
 				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
@@ -1908,7 +2667,7 @@ mod tests {
 			// if passed wight is enough to prune many messages
 			assert_eq!(
 				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
-				dbw.reads_writes(1, 2),
+				dbw.reads_writes(2, 2),
 			);
 			assert_eq!(
 				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
@@ -1971,7 +2730,7 @@ mod tests {
 			System::<TestRuntime>::set_block_number(2);
 			assert_eq!(
 				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
-				dbw.reads_writes(1, 2),
+				dbw.reads_writes(2, 2),
 			);
 			assert_eq!(
 				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
@@ -1987,7 +2746,7 @@ mod tests {
 
 			assert_eq!(
 				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
-				dbw.reads_writes(1, 2),
+				dbw.reads_writes(2, 2),
 			);
 			assert_eq!(
 				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().oldest_unpruned_nonce,
@@ -2014,6 +2773,59 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn outbound_message_to_dynamic_lane_is_accepted_and_pruned() {
+		run_test(|| {
+			TestDynamicLanes::set(vec![TEST_LANE_ID_3]);
+			assert_ok!(send_message::<TestRuntime, ()>(
+				RuntimeOrigin::signed(1),
+				TEST_LANE_ID_3,
+				REGULAR_PAYLOAD,
+			));
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				RuntimeOrigin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID_3,
+					InboundLaneData {
+						last_confirmed_nonce: 1,
+						relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					messages_in_oldest_entry: 1,
+					total_messages: 1,
+					last_delivered_nonce: 1,
+				},
+			));
+
+			// dynamic lane follows two static lanes, so it is pruned in block#2.on_idle
+			let dbw = DbWeight::get();
+			System::<TestRuntime>::set_block_number(2);
+			assert_eq!(
+				Pallet::<TestRuntime, ()>::on_idle(0, dbw.reads_writes(100, 100)),
+				dbw.reads_writes(3, 2),
+			);
+			assert_eq!(
+				outbound_lane::<TestRuntime, ()>(TEST_LANE_ID_3).data().oldest_unpruned_nonce,
+				2
+			);
+
+			// when lane is closed, new messages are rejected
+			TestDynamicLanes::set(vec![]);
+			assert_noop!(
+				send_message::<TestRuntime, ()>(
+					RuntimeOrigin::signed(1),
+					TEST_LANE_ID_3,
+					REGULAR_PAYLOAD,
+				),
+				Error::<TestRuntime, ()>::InactiveOutboundLane,
+			);
+		});
+	}
+
 	#[test]
 	fn test_bridge_messages_call_is_correctly_defined() {
 		let account_id = 1;
@@ -2119,6 +2931,152 @@ mod tests {
 		assert_eq!(storage(max_entries + 1).extra_proof_size_bytes(), 0);
 	}
 
+	fn check_stale_lanes_at(block: u64) -> Vec<Event<TestRuntime>> {
+		System::<TestRuntime>::set_block_number(block);
+		System::<TestRuntime>::reset_events();
+		Pallet::<TestRuntime>::on_initialize(block);
+		System::<TestRuntime>::events()
+			.into_iter()
+			.filter_map(|record| match record.event {
+				TestEvent::Messages(event) => Some(event),
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[test]
+	fn stale_lane_is_reported_once() {
+		run_test(|| {
+			send_regular_message();
+			assert_eq!(check_stale_lanes_at(1), vec![]);
+			assert_eq!(
+				OutboundLanesProgress::<TestRuntime, ()>::get(TEST_LANE_ID),
+				Some(OutboundLaneProgress { latest_received_nonce: 0, since: 1, reported: false }),
+			);
+			assert_eq!(OutboundLanesProgress::<TestRuntime, ()>::get(TEST_LANE_ID_2), None);
+
+			assert_eq!(check_stale_lanes_at(10), vec![]);
+			assert_eq!(
+				check_stale_lanes_at(11),
+				vec![Event::LaneStale { lane: TEST_LANE_ID, since: 1 }],
+			);
+			assert_eq!(check_stale_lanes_at(12), vec![]);
+			assert_eq!(check_stale_lanes_at(100), vec![]);
+		});
+	}
+
+	#[test]
+	fn stale_lane_period_restarts_when_lane_makes_progress() {
+		run_test(|| {
+			send_regular_message();
+			send_regular_message();
+			assert_eq!(check_stale_lanes_at(1), vec![]);
+
+			// first message is confirmed, but the second is still pending
+			receive_messages_delivery_proof();
+			assert_eq!(check_stale_lanes_at(5), vec![]);
+			assert_eq!(
+				OutboundLanesProgress::<TestRuntime, ()>::get(TEST_LANE_ID),
+				Some(OutboundLaneProgress { latest_received_nonce: 1, since: 5, reported: false }),
+			);
+
+			assert_eq!(check_stale_lanes_at(14), vec![]);
+			assert_eq!(
+				check_stale_lanes_at(15),
+				vec![Event::LaneStale { lane: TEST_LANE_ID, since: 5 }],
+			);
+		});
+	}
+
+	#[test]
+	fn lane_progress_is_removed_when_lane_has_no_pending_messages() {
+		run_test(|| {
+			send_regular_message();
+			assert_eq!(check_stale_lanes_at(1), vec![]);
+			assert!(OutboundLanesProgress::<TestRuntime, ()>::get(TEST_LANE_ID).is_some());
+
+			receive_messages_delivery_proof();
+			assert_eq!(check_stale_lanes_at(20), vec![]);
+			assert_eq!(OutboundLanesProgress::<TestRuntime, ()>::get(TEST_LANE_ID), None);
+		});
+	}
+
+	#[test]
+	fn lane_statistics_are_updated_when_messages_are_sent_and_confirmed() {
+		run_test(|| {
+			send_regular_message();
+			send_regular_message();
+			receive_messages_delivery_proof();
+			Pallet::<TestRuntime>::note_rewards_paid(TEST_LANE_ID, 100);
+
+			assert_eq!(
+				Pallet::<TestRuntime>::lane_statistics(TEST_LANE_ID),
+				LaneStatistics {
+					messages_sent: 2,
+					bytes_sent: 2 * REGULAR_PAYLOAD.encode().len() as u64,
+					messages_delivered: 1,
+					rewards_paid: 100,
+					..Default::default()
+				},
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::lane_statistics(TEST_LANE_ID_2),
+				LaneStatistics::default(),
+			);
+		});
+	}
+
+	#[test]
+	fn lane_statistics_are_updated_when_messages_are_received() {
+		run_test(|| {
+			// message#3 is ignored by the inbound lane, because message#2 is not yet received.
+			// It is not a failure, so it isn't counted in `messages_failed`
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				RuntimeOrigin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![message(1, REGULAR_PAYLOAD), message(3, REGULAR_PAYLOAD)]).into(),
+				2,
+				REGULAR_PAYLOAD.declared_weight + REGULAR_PAYLOAD.declared_weight,
+			));
+
+			assert_eq!(
+				Pallet::<TestRuntime>::lane_statistics(TEST_LANE_ID),
+				LaneStatistics { messages_received: 1, ..Default::default() },
+			);
+
+			// message#101 is rejected by the inbound lane, because there are too many unrewarded
+			// relayer entries
+			let max_entries = crate::mock::MaxUnrewardedRelayerEntriesAtInboundLane::get() as usize;
+			InboundLanes::<TestRuntime>::insert(
+				TEST_LANE_ID,
+				StoredInboundLaneData(InboundLaneData {
+					relayers: vec![
+						UnrewardedRelayer {
+							relayer: 42,
+							messages: DeliveredMessages { begin: 0, end: 100 }
+						};
+						max_entries
+					]
+					.into_iter()
+					.collect(),
+					last_confirmed_nonce: 0,
+				}),
+			);
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				RuntimeOrigin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![message(101, REGULAR_PAYLOAD)]).into(),
+				1,
+				REGULAR_PAYLOAD.declared_weight,
+			));
+
+			assert_eq!(
+				Pallet::<TestRuntime>::lane_statistics(TEST_LANE_ID),
+				LaneStatistics { messages_received: 1, messages_failed: 1, ..Default::default() },
+			);
+		});
+	}
+
 	#[test]
 	fn maybe_outbound_lanes_count_returns_correct_value() {
 		assert_eq!(
@@ -2126,4 +3084,194 @@ mod tests {
 			Some(mock::ActiveOutboundLanes::get().len() as u32)
 		);
 	}
+
+	fn receive_regular_messages(nonces: std::ops::RangeInclusive<MessageNonce>) {
+		let messages_count = nonces.clone().count() as u32;
+		assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+			RuntimeOrigin::signed(1),
+			TEST_RELAYER_A,
+			Ok(nonces.map(|nonce| message(nonce, REGULAR_PAYLOAD)).collect::<Vec<_>>()).into(),
+			messages_count,
+			REGULAR_PAYLOAD.declared_weight * messages_count as u64,
+		));
+	}
+
+	fn receival_results(event: Event<TestRuntime>) -> Vec<(MessageNonce, ReceivalResult<()>)> {
+		match event {
+			Event::MessagesReceived(mut received) => received.remove(0).receive_results,
+			_ => panic!("Unexpected event: {event:?}"),
+		}
+	}
+
+	fn deferred_dispatch_results() -> Vec<(MessageNonce, MessageDispatchResult<()>)> {
+		System::<TestRuntime>::events()
+			.into_iter()
+			.filter_map(|record| match record.event {
+				TestEvent::Messages(Event::DeferredMessageDispatched {
+					nonce,
+					dispatch_result,
+					..
+				}) => Some((nonce, dispatch_result)),
+				_ => None,
+			})
+			.collect()
+	}
+
+	fn last_event() -> Event<TestRuntime> {
+		match System::<TestRuntime>::events().pop().unwrap().event {
+			TestEvent::Messages(event) => event,
+			event => panic!("Unexpected event: {event:?}"),
+		}
+	}
+
+	#[test]
+	fn receive_messages_proof_defers_dispatch_when_block_budget_is_exhausted() {
+		run_test(|| {
+			get_ready_for_events();
+			mock::MaxDispatchWeightPerBlock::set(&(REGULAR_PAYLOAD.declared_weight * 2));
+
+			// first two messages are dispatched, the rest is deferred
+			receive_regular_messages(1..=3);
+			assert_eq!(
+				receival_results(last_event()),
+				vec![
+					(1, ReceivalResult::Dispatched(dispatch_result(0))),
+					(2, ReceivalResult::Dispatched(dispatch_result(0))),
+					(3, ReceivalResult::Deferred),
+				],
+			);
+			assert!(TestDeliveryPayments::is_reward_paid(1));
+
+			// all subsequent messages of the lane are deferred
+			receive_regular_messages(4..=4);
+			assert_eq!(receival_results(last_event()), vec![(4, ReceivalResult::Deferred)]);
+			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).last_delivered_nonce(), 4);
+			assert_eq!(
+				InboundLanesDeferredMessages::<TestRuntime>::get(TEST_LANE_ID),
+				Some(DeliveredMessages { begin: 3, end: 4 }),
+			);
+			assert!(DeferredMessages::<TestRuntime>::contains_key(MessageKey {
+				lane_id: TEST_LANE_ID,
+				nonce: 3
+			}));
+			assert_eq!(
+				DispatchWeightUsed::<TestRuntime>::get(),
+				REGULAR_PAYLOAD.declared_weight * 2,
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_rejects_messages_when_too_many_messages_are_deferred() {
+		run_test(|| {
+			get_ready_for_events();
+			mock::MaxDispatchWeightPerBlock::set(&REGULAR_PAYLOAD.declared_weight);
+
+			// first message is dispatched, next `MaxDeferredMessagesPerLane` messages are
+			// deferred and the last message is rejected
+			receive_regular_messages(1..=6);
+			assert_eq!(
+				receival_results(last_event()),
+				vec![
+					(1, ReceivalResult::Dispatched(dispatch_result(0))),
+					(2, ReceivalResult::Deferred),
+					(3, ReceivalResult::Deferred),
+					(4, ReceivalResult::Deferred),
+					(5, ReceivalResult::Deferred),
+					(6, ReceivalResult::TooManyDeferredMessages),
+				],
+			);
+			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).last_delivered_nonce(), 5);
+			assert_eq!(
+				InboundLanesDeferredMessages::<TestRuntime>::get(TEST_LANE_ID),
+				Some(DeliveredMessages { begin: 2, end: 5 }),
+			);
+			assert_eq!(Pallet::<TestRuntime>::lane_statistics(TEST_LANE_ID).messages_failed, 1);
+
+			// once some deferred messages are dispatched, the rejected message is accepted
+			Pallet::<TestRuntime>::on_initialize(2);
+			receive_regular_messages(6..=6);
+			assert_eq!(receival_results(last_event()), vec![(6, ReceivalResult::Deferred)]);
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_charges_storing_deferred_messages() {
+		run_test(|| {
+			mock::MaxDispatchWeightPerBlock::set(&REGULAR_PAYLOAD.declared_weight);
+
+			let proof: TestMessagesProof =
+				Ok(vec![message(1, REGULAR_PAYLOAD), message(2, REGULAR_PAYLOAD)]).into();
+			let messages_count = 2;
+			let dispatch_weight = REGULAR_PAYLOAD.declared_weight * 2;
+			let base_weight = <TestRuntime as Config>::WeightInfo::receive_messages_proof_weight(
+				&proof,
+				messages_count,
+				dispatch_weight,
+			);
+			let call = Call::<TestRuntime, ()>::receive_messages_proof {
+				relayer_id_at_bridged_chain: TEST_RELAYER_A,
+				proof: proof.clone(),
+				messages_count,
+				dispatch_weight,
+			};
+			assert_eq!(
+				call.get_dispatch_info().weight,
+				base_weight + DbWeight::get().reads_writes(2, 4),
+			);
+
+			// the first message is dispatched and the second is deferred, so the relayer pays
+			// for dispatching both messages, for storing the second message and for updating
+			// deferred messages range of the lane
+			let post_dispatch_weight = Pallet::<TestRuntime>::receive_messages_proof(
+				RuntimeOrigin::signed(1),
+				TEST_RELAYER_A,
+				proof,
+				messages_count,
+				dispatch_weight,
+			)
+			.unwrap()
+			.actual_weight
+			.unwrap();
+			assert_eq!(
+				post_dispatch_weight.ref_time(),
+				base_weight.ref_time() + DbWeight::get().reads_writes(1, 2).ref_time(),
+			);
+		});
+	}
+
+	#[test]
+	fn deferred_messages_are_dispatched_at_next_blocks() {
+		run_test(|| {
+			get_ready_for_events();
+			mock::MaxDispatchWeightPerBlock::set(&(REGULAR_PAYLOAD.declared_weight * 2));
+			receive_regular_messages(1..=5);
+
+			// messages 3 and 4 are dispatched at the next block
+			System::<TestRuntime>::reset_events();
+			Pallet::<TestRuntime>::on_initialize(2);
+			assert_eq!(
+				deferred_dispatch_results(),
+				vec![(3, dispatch_result(0)), (4, dispatch_result(0))],
+			);
+			assert_eq!(
+				InboundLanesDeferredMessages::<TestRuntime>::get(TEST_LANE_ID),
+				Some(DeliveredMessages { begin: 5, end: 5 }),
+			);
+
+			// the budget is exhausted, so the new message is also deferred
+			receive_regular_messages(6..=6);
+			assert_eq!(receival_results(last_event()), vec![(6, ReceivalResult::Deferred)]);
+
+			// remaining messages are dispatched at the next block
+			System::<TestRuntime>::reset_events();
+			Pallet::<TestRuntime>::on_initialize(3);
+			assert_eq!(
+				deferred_dispatch_results(),
+				vec![(5, dispatch_result(0)), (6, dispatch_result(0))],
+			);
+			assert_eq!(InboundLanesDeferredMessages::<TestRuntime>::get(TEST_LANE_ID), None);
+			assert_eq!(DeferredMessages::<TestRuntime>::iter().count(), 0);
+		});
+	}
 }
@@ -21,7 +21,10 @@ use crate::Config;
 
 use bp_messages::{
 	calc_relayers_rewards,
-	source_chain::{DeliveryConfirmationPayments, LaneMessageVerifier, TargetHeaderChain},
+	source_chain::{
+		DeliveryConfirmationPayments, DynamicLanes, LaneDeliveryCallback, LaneMessageVerifier,
+		OnMessagesDelivered, TargetHeaderChain,
+	},
 	target_chain::{
 		DeliveryPayments, DispatchMessage, DispatchMessageData, MessageDispatch,
 		ProvedLaneMessages, ProvedMessages, SourceHeaderChain,
@@ -141,14 +144,24 @@ parameter_types! {
 	pub const MaxUnconfirmedMessagesAtInboundLane: u64 = 32;
 	pub const TestBridgedChainId: bp_runtime::ChainId = *b"test";
 	pub const ActiveOutboundLanes: &'static [LaneId] = &[TEST_LANE_ID, TEST_LANE_ID_2];
+	pub const StaleLaneThreshold: u64 = 10;
+	pub const TestDeliveryCallbackLane: LaneId = TEST_LANE_ID;
+	pub storage MaxDispatchWeightPerBlock: Weight = Weight::zero();
+	pub const MaxDeferredMessagesPerLane: u64 = 4;
+	pub storage MaxDeferredDispatchWeightPerBlock: Weight = Weight::MAX;
 }
 
 impl Config for TestRuntime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 	type ActiveOutboundLanes = ActiveOutboundLanes;
+	type DynamicLanes = TestDynamicLanes;
+	type StaleLaneThreshold = StaleLaneThreshold;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxDispatchWeightPerBlock = MaxDispatchWeightPerBlock;
+	type MaxDeferredMessagesPerLane = MaxDeferredMessagesPerLane;
+	type MaxDeferredDispatchWeightPerBlock = MaxDeferredDispatchWeightPerBlock;
 
 	type MaximalOutboundPayloadSize = frame_support::traits::ConstU32<MAX_OUTBOUND_PAYLOAD_SIZE>;
 	type OutboundPayload = TestPayload;
@@ -160,6 +173,8 @@ impl Config for TestRuntime {
 	type TargetHeaderChain = TestTargetHeaderChain;
 	type LaneMessageVerifier = TestLaneMessageVerifier;
 	type DeliveryConfirmationPayments = TestDeliveryConfirmationPayments;
+	type OnMessagesDelivered =
+		LaneDeliveryCallback<TestDeliveryCallbackLane, TestOnMessagesDelivered>;
 
 	type SourceHeaderChain = TestSourceHeaderChain;
 	type MessageDispatch = TestMessageDispatch;
@@ -178,6 +193,9 @@ pub const MAX_OUTBOUND_PAYLOAD_SIZE: u32 = 4096;
 /// Account that has balance to use in tests.
 pub const ENDOWED_ACCOUNT: AccountId = 0xDEAD;
 
+/// Weight of the `TestOnMessagesDelivered` callback per every confirmed message.
+pub const ON_MESSAGES_DELIVERED_WEIGHT_PER_MESSAGE: u64 = 10;
+
 /// Account id of test relayer.
 pub const TEST_RELAYER_A: AccountId = 100;
 
@@ -290,6 +308,36 @@ impl LaneMessageVerifier<RuntimeOrigin, TestPayload> for TestLaneMessageVerifier
 	}
 }
 
+/// Dynamic lanes that are used in tests.
+#[derive(Debug, Default)]
+pub struct TestDynamicLanes;
+
+impl TestDynamicLanes {
+	/// Set dynamic lanes.
+	pub fn set(lanes: Vec<LaneId>) {
+		frame_support::storage::unhashed::put(b":dynamic-lanes:", &lanes);
+	}
+
+	/// Returns all dynamic lanes.
+	fn get() -> Vec<LaneId> {
+		frame_support::storage::unhashed::get(b":dynamic-lanes:").unwrap_or_default()
+	}
+}
+
+impl DynamicLanes for TestDynamicLanes {
+	fn is_lane_opened(lane: &LaneId) -> bool {
+		Self::get().contains(lane)
+	}
+
+	fn lanes_count() -> u32 {
+		Self::get().len() as u32
+	}
+
+	fn lane_at(index: u32) -> Option<LaneId> {
+		Self::get().get(index as usize).copied()
+	}
+}
+
 /// Reward payments at the target chain during delivery transaction.
 #[derive(Debug, Default)]
 pub struct TestDeliveryPayments;
@@ -347,6 +395,30 @@ impl DeliveryConfirmationPayments<AccountId> for TestDeliveryConfirmationPayment
 	}
 }
 
+/// Delivery confirmation callback that is used in tests.
+#[derive(Debug, Default)]
+pub struct TestOnMessagesDelivered;
+
+impl TestOnMessagesDelivered {
+	/// Returns messages, delivery of which has been confirmed at given lane. The stored value is
+	/// cleared after the call.
+	pub fn take_delivered_messages(lane: LaneId) -> Option<DeliveredMessages> {
+		let key = (b":delivered-messages:", lane).encode();
+		frame_support::storage::unhashed::take(&key)
+	}
+}
+
+impl OnMessagesDelivered for TestOnMessagesDelivered {
+	fn weight(messages: &DeliveredMessages) -> Weight {
+		Weight::from_parts(ON_MESSAGES_DELIVERED_WEIGHT_PER_MESSAGE * messages.total_messages(), 0)
+	}
+
+	fn on_messages_delivered(lane: LaneId, messages: &DeliveredMessages) {
+		let key = (b":delivered-messages:", lane).encode();
+		frame_support::storage::unhashed::put(&key, messages);
+	}
+}
+
 /// Source header chain that is used in tests.
 #[derive(Debug)]
 pub struct TestSourceHeaderChain;
@@ -20,7 +20,10 @@ use crate::weights::WeightInfo;
 
 use bp_messages::{MessageNonce, UnrewardedRelayersState};
 use bp_runtime::{PreComputedSize, Size};
-use frame_support::weights::Weight;
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
 
 /// Size of the message being delivered in benchmarks.
 pub const EXPECTED_DEFAULT_MESSAGE_LENGTH: u32 = 128;
@@ -34,6 +37,10 @@ const SIGNED_EXTENSIONS_SIZE: u32 = 1024;
 /// Some reserve is reserved to account future chain growth.
 pub const EXTRA_STORAGE_PROOF_SIZE: u32 = 1024;
 
+/// Size of the `LanesStatistics` entry in the storage proof, computed the same way the benchmarks
+/// are computing it (using `MaxEncodedLen` approach).
+pub const LANE_STATISTICS_PROOF_SIZE: u64 = 2557;
+
 /// Ensure that weights from `WeightInfoExt` implementation are looking correct.
 pub fn ensure_weights_are_correct<W: WeightInfoExt>() {
 	// all components of weight formulae must have zero `proof_size`, because the `proof_size` is
@@ -53,6 +60,8 @@ pub fn ensure_weights_are_correct<W: WeightInfoExt>() {
 	assert_eq!(W::receive_messages_proof_outbound_lane_state_overhead().proof_size(), 0);
 	assert_ne!(W::storage_proof_size_overhead(1).ref_time(), 0);
 	assert_eq!(W::storage_proof_size_overhead(1).proof_size(), 0);
+	assert_ne!(W::lane_statistics_update_overhead().ref_time(), 0);
+	assert_ne!(W::lane_statistics_update_overhead().proof_size(), 0);
 
 	// verify `receive_messages_delivery_proof` weight components
 	assert_ne!(W::receive_messages_delivery_proof_overhead().ref_time(), 0);
@@ -266,6 +275,12 @@ pub trait WeightInfoExt: WeightInfo {
 	/// this value, we're going to charge relayer for that.
 	fn expected_extra_storage_proof_size() -> u32;
 
+	/// Weight of the `LanesStatistics` read and write, performed by both message delivery and
+	/// delivery confirmation transactions.
+	///
+	/// Lane statistics is not covered by our benchmarks yet, so we need to account it separately.
+	fn lane_statistics_update_overhead() -> Weight;
+
 	// Functions that are directly mapped to extrinsics weights.
 
 	/// Weight of message delivery extrinsic.
@@ -281,6 +296,7 @@ pub trait WeightInfoExt: WeightInfo {
 		let messages_delivery_weight =
 			Self::receive_messages_proof_messages_overhead(MessageNonce::from(messages_count));
 		let messages_dispatch_weight = dispatch_weight;
+		let lane_statistics_weight = Self::lane_statistics_update_overhead();
 
 		// proof size overhead weight
 		let expected_proof_size = EXPECTED_DEFAULT_MESSAGE_LENGTH
@@ -295,6 +311,7 @@ pub trait WeightInfoExt: WeightInfo {
 			.saturating_add(outbound_state_delivery_weight)
 			.saturating_add(messages_delivery_weight)
 			.saturating_add(messages_dispatch_weight)
+			.saturating_add(lane_statistics_weight)
 			.saturating_add(proof_size_overhead)
 	}
 
@@ -310,6 +327,7 @@ pub trait WeightInfoExt: WeightInfo {
 		let relayers_overhead = Self::receive_messages_delivery_proof_relayers_overhead(
 			relayers_state.unrewarded_relayer_entries,
 		);
+		let lane_statistics_weight = Self::lane_statistics_update_overhead();
 
 		// proof size overhead weight
 		let expected_proof_size = Self::expected_extra_storage_proof_size();
@@ -321,6 +339,7 @@ pub trait WeightInfoExt: WeightInfo {
 		transaction_overhead
 			.saturating_add(messages_overhead)
 			.saturating_add(relayers_overhead)
+			.saturating_add(lane_statistics_weight)
 			.saturating_add(proof_size_overhead)
 	}
 
@@ -409,12 +428,22 @@ impl WeightInfoExt for () {
 	fn expected_extra_storage_proof_size() -> u32 {
 		EXTRA_STORAGE_PROOF_SIZE
 	}
+
+	fn lane_statistics_update_overhead() -> Weight {
+		Weight::from_parts(0, LANE_STATISTICS_PROOF_SIZE)
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
 }
 
 impl<T: frame_system::Config> WeightInfoExt for crate::weights::BridgeWeight<T> {
 	fn expected_extra_storage_proof_size() -> u32 {
 		EXTRA_STORAGE_PROOF_SIZE
 	}
+
+	fn lane_statistics_update_overhead() -> Weight {
+		Weight::from_parts(0, LANE_STATISTICS_PROOF_SIZE)
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
 }
 
 #[cfg(test)]
@@ -24,6 +24,7 @@ use crate::{
 use bp_polkadot_core::parachains::{ParaHash, ParaHeadsProof, ParaId};
 use bp_runtime::StorageProofSize;
 use frame_benchmarking::{account, benchmarks_instance_pallet};
+use frame_support::{traits::Get, BoundedVec};
 use frame_system::RawOrigin;
 use sp_std::prelude::*;
 
@@ -111,4 +112,33 @@ benchmarks_instance_pallet! {
 			assert!(crate::Pallet::<T, I>::best_parachain_head(parachain).is_some());
 		}
 	}
+
+	// Benchmark `track_parachain` extrinsic when the tracked parachains set is almost full.
+	track_parachain {
+		let max_tracked_parachains = T::MaxTrackedParachains::get();
+		fill_tracked_parachains::<T, I>(max_tracked_parachains.saturating_sub(1));
+		let parachain = ParaId(max_tracked_parachains);
+	}: _(RawOrigin::Root, parachain)
+	verify {
+		assert!(crate::TrackedParachains::<T, I>::get().contains(&parachain));
+	}
+
+	// Benchmark `untrack_parachain` extrinsic when the tracked parachains set is full and the
+	// parachain is the last one.
+	untrack_parachain {
+		let max_tracked_parachains = T::MaxTrackedParachains::get();
+		fill_tracked_parachains::<T, I>(max_tracked_parachains);
+		let parachain = ParaId(max_tracked_parachains.saturating_sub(1));
+	}: _(RawOrigin::Root, parachain)
+	verify {
+		assert!(!crate::TrackedParachains::<T, I>::get().contains(&parachain));
+	}
+}
+
+/// Insert `count` parachains into the tracked parachains set.
+fn fill_tracked_parachains<T: Config<I>, I: 'static>(count: u32) {
+	let tracked_parachains = (0..count).map(ParaId).collect::<Vec<_>>();
+	crate::TrackedParachains::<T, I>::put(
+		BoundedVec::try_from(tracked_parachains).expect("count is within the bound; qed"),
+	);
 }
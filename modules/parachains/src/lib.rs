@@ -117,6 +117,10 @@ pub mod pallet {
 		},
 		/// Parachain head has been updated.
 		UpdatedParachainHead { parachain: ParaId, parachain_head_hash: ParaHash },
+		/// Parachain has been added to the tracked parachains set.
+		ParachainTracked { parachain: ParaId },
+		/// Parachain has been removed from the tracked parachains set.
+		ParachainUntracked { parachain: ParaId },
 	}
 
 	#[pallet::error]
@@ -135,6 +139,12 @@ pub mod pallet {
 		FailedToExtractStateRoot,
 		/// Error generated by the `OwnedBridgeModule` trait.
 		BridgeModule(bp_runtime::OwnedBridgeModuleError),
+		/// The parachain is already in the tracked parachains set.
+		ParachainAlreadyTracked,
+		/// The parachain is not in the tracked parachains set.
+		ParachainNotTracked,
+		/// The tracked parachains set is full.
+		TooManyTrackedParachains,
 	}
 
 	/// Convenience trait for defining `BridgedChain` bounds.
@@ -195,6 +205,21 @@ pub mod pallet {
 		/// This builder helps to convert from `HeadData` to `bp_parachains::ParaStoredHeaderData`.
 		type ParaStoredHeaderDataBuilder: ParaStoredHeaderDataBuilder;
 
+		/// Head data builder of parachains from the `TrackedParachains` set.
+		///
+		/// It is only used for parachains that are not supported by the
+		/// `ParaStoredHeaderDataBuilder`. So it shall not check the parachain id (see
+		/// `bp_parachains::AnyParaStoredHeaderDataBuilder`). All dynamically tracked parachains
+		/// must be using the same header type.
+		type TrackedParaStoredHeaderDataBuilder: ParaStoredHeaderDataBuilder;
+
+		/// Maximal number of parachains in the `TrackedParachains` set.
+		///
+		/// Parachains, supported by the `ParaStoredHeaderDataBuilder`, are not counted here. Zero
+		/// disables dynamic tracking of parachains.
+		#[pallet::constant]
+		type MaxTrackedParachains: Get<u32>;
+
 		/// Maximal number of single parachain heads to keep in the storage.
 		///
 		/// The setting is there to prevent growing the on-chain state indefinitely. Note
@@ -235,6 +260,15 @@ pub mod pallet {
 	pub type PalletOperatingMode<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, BasicOperatingMode, ValueQuery>;
 
+	/// Parachains that are tracked in addition to parachains, supported by the
+	/// `ParaStoredHeaderDataBuilder`.
+	///
+	/// The set is managed by the pallet owner (or root) with `track_parachain` and
+	/// `untrack_parachain` calls, so new parachains may be bridged without runtime upgrade.
+	#[pallet::storage]
+	pub type TrackedParachains<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<ParaId, T::MaxTrackedParachains>, ValueQuery>;
+
 	/// Parachains info.
 	///
 	/// Contains the following info:
@@ -320,6 +354,10 @@ pub mod pallet {
 				Error::<T, I>::InvalidRelayChainBlockNumber,
 			);
 
+			// heads of parachains from this set are accepted even if the
+			// `ParaStoredHeaderDataBuilder` doesn't support them
+			let tracked_parachains = TrackedParachains::<T, I>::get();
+
 			// now parse storage proof and read parachain heads
 			let mut actual_weight = WeightInfoOf::<T, I>::submit_parachain_heads_weight(
 				T::DbWeight::get(),
@@ -380,7 +418,8 @@ pub mod pallet {
 						}
 
 						// convert from parachain head into stored parachain head data
-						let parachain_head_data = match T::ParaStoredHeaderDataBuilder::try_build(
+						let parachain_head_data = match Pallet::<T, I>::build_parachain_head_data(
+							&tracked_parachains,
 							parachain,
 							&parachain_head,
 						) {
@@ -458,6 +497,57 @@ pub mod pallet {
 		) -> DispatchResult {
 			<Self as OwnedBridgeModule<_>>::set_operating_mode(origin, operating_mode)
 		}
+
+		/// Add parachain to the `TrackedParachains` set.
+		///
+		/// Heads of the parachain are accepted by the `submit_parachain_heads` call after that.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::call_index(3)]
+		#[pallet::weight((WeightInfoOf::<T, I>::track_parachain(), DispatchClass::Operational))]
+		pub fn track_parachain(origin: OriginFor<T>, parachain: ParaId) -> DispatchResult {
+			Self::ensure_owner_or_root(origin)?;
+
+			TrackedParachains::<T, I>::try_mutate(|tracked_parachains| {
+				ensure!(
+					!tracked_parachains.contains(&parachain),
+					Error::<T, I>::ParachainAlreadyTracked,
+				);
+				tracked_parachains
+					.try_push(parachain)
+					.map_err(|_| Error::<T, I>::TooManyTrackedParachains)
+			})?;
+
+			log::info!(target: LOG_TARGET, "Started tracking parachain {:?}", parachain);
+			Self::deposit_event(Event::ParachainTracked { parachain });
+
+			Ok(())
+		}
+
+		/// Remove parachain from the `TrackedParachains` set.
+		///
+		/// Already imported heads of the parachain are kept in the storage.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::call_index(4)]
+		#[pallet::weight((WeightInfoOf::<T, I>::untrack_parachain(), DispatchClass::Operational))]
+		pub fn untrack_parachain(origin: OriginFor<T>, parachain: ParaId) -> DispatchResult {
+			Self::ensure_owner_or_root(origin)?;
+
+			TrackedParachains::<T, I>::try_mutate(|tracked_parachains| {
+				let position = tracked_parachains
+					.iter()
+					.position(|tracked_parachain| *tracked_parachain == parachain)
+					.ok_or(Error::<T, I>::ParachainNotTracked)?;
+				tracked_parachains.remove(position);
+				Ok::<_, Error<T, I>>(())
+			})?;
+
+			log::info!(target: LOG_TARGET, "Stopped tracking parachain {:?}", parachain);
+			Self::deposit_event(Event::ParachainUntracked { parachain });
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -509,6 +599,24 @@ pub mod pallet {
 			storage.read_and_decode_value(parachain_head_key.0.as_ref())
 		}
 
+		/// Convert parachain head into stored parachain head data.
+		///
+		/// Returns `None` if the parachain is neither supported by the
+		/// `ParaStoredHeaderDataBuilder`, nor is in the tracked parachains set.
+		fn build_parachain_head_data(
+			tracked_parachains: &[ParaId],
+			parachain: ParaId,
+			parachain_head: &ParaHead,
+		) -> Option<ParaStoredHeaderData> {
+			T::ParaStoredHeaderDataBuilder::try_build(parachain, parachain_head).or_else(|| {
+				if tracked_parachains.contains(&parachain) {
+					T::TrackedParaStoredHeaderDataBuilder::try_build(parachain, parachain_head)
+				} else {
+					None
+				}
+			})
+		}
+
 		/// Try to update parachain head.
 		pub(super) fn update_parachain_head(
 			parachain: ParaId,
@@ -639,7 +747,10 @@ pub mod pallet {
 
 	impl<T: Config<I>, I: 'static> Get<Option<u32>> for MaybeMaxParachains<T, I> {
 		fn get() -> Option<u32> {
-			Some(T::ParaStoredHeaderDataBuilder::supported_parachains())
+			Some(
+				T::ParaStoredHeaderDataBuilder::supported_parachains()
+					.saturating_add(T::MaxTrackedParachains::get()),
+			)
 		}
 	}
 
@@ -650,6 +761,7 @@ pub mod pallet {
 		fn get() -> Option<u32> {
 			Some(
 				T::ParaStoredHeaderDataBuilder::supported_parachains()
+					.saturating_add(T::MaxTrackedParachains::get())
 					.saturating_mul(T::HeadsToKeep::get()),
 			)
 		}
@@ -1132,6 +1244,93 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn imports_head_of_tracked_parachain() {
+		let (state_root, proof, parachains) =
+			prepare_parachain_heads_proof(vec![(UNTRACKED_PARACHAIN_ID, head_data(1, 5))]);
+		run_test(|| {
+			initialize(state_root);
+			assert_ok!(Pallet::<TestRuntime>::track_parachain(
+				RuntimeOrigin::root(),
+				ParaId(UNTRACKED_PARACHAIN_ID),
+			));
+
+			assert_ok!(Pallet::<TestRuntime>::submit_parachain_heads(
+				RuntimeOrigin::signed(1),
+				(0, test_relay_header(0, state_root).hash()),
+				parachains,
+				proof,
+			));
+			assert_eq!(
+				ParasInfo::<TestRuntime>::get(ParaId(UNTRACKED_PARACHAIN_ID)),
+				Some(ParaInfo {
+					best_head_hash: BestParaHeadHash {
+						at_relay_block_number: 0,
+						head_hash: head_data(1, 5).hash()
+					},
+					next_imported_hash_position: 1,
+				})
+			);
+			assert_eq!(
+				ImportedParaHeads::<TestRuntime>::get(
+					ParaId(UNTRACKED_PARACHAIN_ID),
+					head_data(1, 5).hash()
+				)
+				.map(|h| h.into_inner()),
+				Some(stored_head_data(1, 5))
+			);
+		});
+	}
+
+	#[test]
+	fn track_parachain_works() {
+		run_test(|| {
+			let parachain = ParaId(UNTRACKED_PARACHAIN_ID);
+			assert_noop!(
+				Pallet::<TestRuntime>::track_parachain(RuntimeOrigin::signed(1), parachain),
+				DispatchError::BadOrigin,
+			);
+
+			assert_ok!(Pallet::<TestRuntime>::track_parachain(RuntimeOrigin::root(), parachain));
+			assert_eq!(TrackedParachains::<TestRuntime>::get().into_inner(), vec![parachain]);
+			assert_noop!(
+				Pallet::<TestRuntime>::track_parachain(RuntimeOrigin::root(), parachain),
+				Error::<TestRuntime>::ParachainAlreadyTracked,
+			);
+
+			assert_ok!(Pallet::<TestRuntime>::track_parachain(
+				RuntimeOrigin::root(),
+				ParaId(UNTRACKED_PARACHAIN_ID + 1),
+			));
+			assert_noop!(
+				Pallet::<TestRuntime>::track_parachain(
+					RuntimeOrigin::root(),
+					ParaId(UNTRACKED_PARACHAIN_ID + 2),
+				),
+				Error::<TestRuntime>::TooManyTrackedParachains,
+			);
+		});
+	}
+
+	#[test]
+	fn untrack_parachain_works() {
+		run_test(|| {
+			let parachain = ParaId(UNTRACKED_PARACHAIN_ID);
+			assert_noop!(
+				Pallet::<TestRuntime>::untrack_parachain(RuntimeOrigin::root(), parachain),
+				Error::<TestRuntime>::ParachainNotTracked,
+			);
+
+			assert_ok!(Pallet::<TestRuntime>::track_parachain(RuntimeOrigin::root(), parachain));
+			assert_noop!(
+				Pallet::<TestRuntime>::untrack_parachain(RuntimeOrigin::signed(1), parachain),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(Pallet::<TestRuntime>::untrack_parachain(RuntimeOrigin::root(), parachain));
+			assert!(TrackedParachains::<TestRuntime>::get().is_empty());
+		});
+	}
+
 	#[test]
 	fn does_nothing_when_already_imported_this_head_at_previous_relay_header() {
 		let (state_root, proof, parachains) =
@@ -1536,14 +1735,20 @@ mod tests {
 
 	#[test]
 	fn maybe_max_parachains_returns_correct_value() {
-		assert_eq!(MaybeMaxParachains::<TestRuntime, ()>::get(), Some(mock::TOTAL_PARACHAINS));
+		assert_eq!(
+			MaybeMaxParachains::<TestRuntime, ()>::get(),
+			Some(mock::TOTAL_PARACHAINS + mock::MaxTrackedParachains::get()),
+		);
 	}
 
 	#[test]
 	fn maybe_max_total_parachain_hashes_returns_correct_value() {
 		assert_eq!(
 			MaybeMaxTotalParachainHashes::<TestRuntime, ()>::get(),
-			Some(mock::TOTAL_PARACHAINS * mock::HeadsToKeep::get()),
+			Some(
+				(mock::TOTAL_PARACHAINS + mock::MaxTrackedParachains::get()) *
+					mock::HeadsToKeep::get()
+			),
 		);
 	}
 }
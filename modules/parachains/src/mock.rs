@@ -15,9 +15,14 @@
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
 use bp_header_chain::ChainWithGrandpa;
+use bp_parachains::AnyParaStoredHeaderDataBuilder;
 use bp_polkadot_core::parachains::ParaId;
 use bp_runtime::{Chain, Parachain};
-use frame_support::{construct_runtime, parameter_types, traits::ConstU32, weights::Weight};
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{ConstBool, ConstU32},
+	weights::Weight,
+};
 use sp_runtime::{
 	testing::{Header, H256},
 	traits::{BlakeTwo256, Header as HeaderT, IdentityLookup},
@@ -200,6 +205,7 @@ impl pallet_bridge_grandpa::Config<pallet_bridge_grandpa::Instance1> for TestRun
 	type BridgedChain = TestBridgedChain;
 	type MaxRequests = ConstU32<2>;
 	type HeadersToKeep = HeadersToKeep;
+	type UseHostSignatureVerification = ConstBool<false>;
 	type WeightInfo = ();
 }
 
@@ -207,11 +213,13 @@ impl pallet_bridge_grandpa::Config<pallet_bridge_grandpa::Instance2> for TestRun
 	type BridgedChain = TestBridgedChain;
 	type MaxRequests = ConstU32<2>;
 	type HeadersToKeep = HeadersToKeep;
+	type UseHostSignatureVerification = ConstBool<false>;
 	type WeightInfo = ();
 }
 
 parameter_types! {
 	pub const HeadsToKeep: u32 = 4;
+	pub const MaxTrackedParachains: u32 = 2;
 	pub const ParasPalletName: &'static str = PARAS_PALLET_NAME;
 	pub GetTenFirstParachains: Vec<ParaId> = (0..10).map(ParaId).collect();
 }
@@ -222,6 +230,8 @@ impl pallet_bridge_parachains::Config for TestRuntime {
 	type BridgesGrandpaPalletInstance = pallet_bridge_grandpa::Instance1;
 	type ParasPalletName = ParasPalletName;
 	type ParaStoredHeaderDataBuilder = (Parachain1, Parachain2, Parachain3, BigParachain);
+	type TrackedParaStoredHeaderDataBuilder = AnyParaStoredHeaderDataBuilder<Parachain1>;
+	type MaxTrackedParachains = MaxTrackedParachains;
 	type HeadsToKeep = HeadsToKeep;
 	type MaxParaHeadDataSize = ConstU32<MAXIMAL_PARACHAIN_HEAD_DATA_SIZE>;
 }
@@ -53,6 +53,8 @@ pub trait WeightInfo {
 	fn submit_parachain_heads_with_n_parachains(p: u32) -> Weight;
 	fn submit_parachain_heads_with_1kb_proof() -> Weight;
 	fn submit_parachain_heads_with_16kb_proof() -> Weight;
+	fn track_parachain() -> Weight;
+	fn untrack_parachain() -> Weight;
 }
 
 /// Weights for `pallet_bridge_parachains` that are generated using one of the Bridge testnets.
@@ -163,6 +165,21 @@ impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+	/// Estimated weight of `track_parachain`: the `PalletOwner` read and the `TrackedParachains`
+	/// read and write. The benchmark results are not available yet, so it is not autogenerated.
+	fn track_parachain() -> Weight {
+		Weight::from_parts(15_000_000, 1087)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Estimated weight of `untrack_parachain`: the `PalletOwner` read and the
+	/// `TrackedParachains` read and write. The benchmark results are not available yet, so it is
+	/// not autogenerated.
+	fn untrack_parachain() -> Weight {
+		Weight::from_parts(15_000_000, 1087)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -270,4 +287,19 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
+	/// Estimated weight of `track_parachain`: the `PalletOwner` read and the `TrackedParachains`
+	/// read and write. The benchmark results are not available yet, so it is not autogenerated.
+	fn track_parachain() -> Weight {
+		Weight::from_parts(15_000_000, 1087)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Estimated weight of `untrack_parachain`: the `PalletOwner` read and the
+	/// `TrackedParachains` read and write. The benchmark results are not available yet, so it is
+	/// not autogenerated.
+	fn untrack_parachain() -> Weight {
+		Weight::from_parts(15_000_000, 1087)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -21,7 +21,7 @@
 #![warn(missing_docs)]
 
 use bp_relayers::{PaymentProcedure, RelayerRewardsKeyProvider, RewardsAccountParams};
-use bp_runtime::StorageDoubleMapKeyProvider;
+use bp_runtime::{account_derivation::AccountDerivationVersion, StorageDoubleMapKeyProvider};
 use frame_support::sp_runtime::Saturating;
 use sp_arithmetic::traits::{AtLeast32BitUnsigned, Zero};
 use sp_std::marker::PhantomData;
@@ -58,6 +58,12 @@ pub mod pallet {
 		type Reward: AtLeast32BitUnsigned + Copy + Parameter + MaxEncodedLen;
 		/// Pay rewards adapter.
 		type PaymentProcedure: PaymentProcedure<Self::AccountId, Self::Reward>;
+		/// Version of the scheme that is used to derive accounts, paying rewards to relayers.
+		///
+		/// Switching to another version changes all rewards accounts, so it must be done
+		/// together with the runtime migration that moves funds to new accounts (e.g. using
+		/// `bp_relayers::PayRewardFromAccount::migrate_rewards_account`).
+		type AccountDerivationVersion: Get<AccountDerivationVersion>;
 		/// Pallet call weights.
 		type WeightInfo: WeightInfo;
 	}
@@ -81,17 +87,22 @@ pub mod pallet {
 				rewards_account_params,
 				|maybe_reward| -> DispatchResult {
 					let reward = maybe_reward.take().ok_or(Error::<T>::NoRewardForRelayer)?;
-					T::PaymentProcedure::pay_reward(&relayer, rewards_account_params, reward)
-						.map_err(|e| {
-							log::trace!(
-								target: LOG_TARGET,
-								"Failed to pay {:?} rewards to {:?}: {:?}",
-								rewards_account_params,
-								relayer,
-								e,
-							);
-							Error::<T>::FailedToPayReward
-						})?;
+					T::PaymentProcedure::pay_reward(
+						&relayer,
+						rewards_account_params,
+						T::AccountDerivationVersion::get(),
+						reward,
+					)
+					.map_err(|e| {
+						log::trace!(
+							target: LOG_TARGET,
+							"Failed to pay {:?} rewards to {:?}: {:?}",
+							rewards_account_params,
+							relayer,
+							e,
+						);
+						Error::<T>::FailedToPayReward
+					})?;
 
 					Self::deposit_event(Event::<T>::RewardPaid {
 						relayer: relayer.clone(),
@@ -269,8 +280,33 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn relayer_is_paid_using_configured_account_derivation_version() {
+		run_test(|| {
+			for version in [AccountDerivationVersion::V0, AccountDerivationVersion::V1] {
+				TestAccountDerivationVersion::set(&version);
+				RelayerRewards::<TestRuntime>::insert(
+					REGULAR_RELAYER,
+					TEST_REWARDS_ACCOUNT_PARAMS,
+					100,
+				);
+				assert_ok!(Pallet::<TestRuntime>::claim_rewards(
+					RuntimeOrigin::signed(REGULAR_RELAYER),
+					TEST_REWARDS_ACCOUNT_PARAMS
+				));
+				assert_eq!(last_payment_account_derivation_version(), Some(version));
+			}
+		});
+	}
+
 	#[test]
 	fn pay_reward_from_account_actually_pays_reward() {
+		for version in [AccountDerivationVersion::V0, AccountDerivationVersion::V1] {
+			pay_reward_from_account_actually_pays_reward_at(version);
+		}
+	}
+
+	fn pay_reward_from_account_actually_pays_reward_at(version: AccountDerivationVersion) {
 		type Balances = pallet_balances::Pallet<TestRuntime>;
 		type PayLaneRewardFromAccount = bp_relayers::PayRewardFromAccount<Balances, AccountId>;
 
@@ -286,8 +322,10 @@ mod tests {
 				RewardsAccountOwner::BridgedChain,
 			);
 
-			let in_lane0_rewards_account = PayLaneRewardFromAccount::rewards_account(in_lane_0);
-			let out_lane1_rewards_account = PayLaneRewardFromAccount::rewards_account(out_lane_1);
+			let in_lane0_rewards_account =
+				PayLaneRewardFromAccount::rewards_account_at(in_lane_0, version);
+			let out_lane1_rewards_account =
+				PayLaneRewardFromAccount::rewards_account_at(out_lane_1, version);
 
 			Balances::mint_into(&in_lane0_rewards_account, 100).unwrap();
 			Balances::mint_into(&out_lane1_rewards_account, 100).unwrap();
@@ -295,15 +333,53 @@ mod tests {
 			assert_eq!(Balances::balance(&out_lane1_rewards_account), 100);
 			assert_eq!(Balances::balance(&1), 0);
 
-			PayLaneRewardFromAccount::pay_reward(&1, in_lane_0, 100).unwrap();
+			PayLaneRewardFromAccount::pay_reward(&1, in_lane_0, version, 100).unwrap();
 			assert_eq!(Balances::balance(&in_lane0_rewards_account), 0);
 			assert_eq!(Balances::balance(&out_lane1_rewards_account), 100);
 			assert_eq!(Balances::balance(&1), 100);
 
-			PayLaneRewardFromAccount::pay_reward(&1, out_lane_1, 100).unwrap();
+			PayLaneRewardFromAccount::pay_reward(&1, out_lane_1, version, 100).unwrap();
 			assert_eq!(Balances::balance(&in_lane0_rewards_account), 0);
 			assert_eq!(Balances::balance(&out_lane1_rewards_account), 0);
 			assert_eq!(Balances::balance(&1), 200);
 		});
 	}
+
+	#[test]
+	fn rewards_account_funds_are_moved_to_new_account() {
+		type Balances = pallet_balances::Pallet<TestRuntime>;
+		type PayLaneRewardFromAccount = bp_relayers::PayRewardFromAccount<Balances, AccountId>;
+
+		run_test(|| {
+			let params = TEST_REWARDS_ACCOUNT_PARAMS;
+			let old_rewards_account =
+				PayLaneRewardFromAccount::rewards_account_at(params, AccountDerivationVersion::V0);
+			let new_rewards_account =
+				PayLaneRewardFromAccount::rewards_account_at(params, AccountDerivationVersion::V1);
+			assert_ne!(old_rewards_account, new_rewards_account);
+
+			Balances::mint_into(&old_rewards_account, 100).unwrap();
+			assert_eq!(
+				PayLaneRewardFromAccount::migrate_rewards_account(
+					params,
+					AccountDerivationVersion::V0,
+					AccountDerivationVersion::V1,
+				),
+				Ok(100),
+			);
+			assert_eq!(Balances::balance(&old_rewards_account), 0);
+			assert_eq!(Balances::balance(&new_rewards_account), 100);
+
+			// nothing is moved when called again
+			assert_eq!(
+				PayLaneRewardFromAccount::migrate_rewards_account(
+					params,
+					AccountDerivationVersion::V0,
+					AccountDerivationVersion::V1,
+				),
+				Ok(0),
+			);
+			assert_eq!(Balances::balance(&new_rewards_account), 100);
+		});
+	}
 }
@@ -20,6 +20,7 @@ use crate as pallet_bridge_relayers;
 
 use bp_messages::LaneId;
 use bp_relayers::{PaymentProcedure, RewardsAccountOwner, RewardsAccountParams};
+use bp_runtime::account_derivation::AccountDerivationVersion;
 use frame_support::{parameter_types, weights::RuntimeDbWeight};
 use sp_core::H256;
 use sp_runtime::{
@@ -47,6 +48,8 @@ frame_support::construct_runtime! {
 
 parameter_types! {
 	pub const DbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 2 };
+	pub storage TestAccountDerivationVersion: AccountDerivationVersion =
+		AccountDerivationVersion::V0;
 }
 
 impl frame_system::Config for TestRuntime {
@@ -92,6 +95,7 @@ impl pallet_bridge_relayers::Config for TestRuntime {
 	type RuntimeEvent = RuntimeEvent;
 	type Reward = Balance;
 	type PaymentProcedure = TestPaymentProcedure;
+	type AccountDerivationVersion = TestAccountDerivationVersion;
 	type WeightInfo = ();
 }
 
@@ -114,15 +118,27 @@ impl PaymentProcedure<AccountId, Balance> for TestPaymentProcedure {
 	fn pay_reward(
 		relayer: &AccountId,
 		_lane_id: RewardsAccountParams,
+		account_derivation_version: AccountDerivationVersion,
 		_reward: Balance,
 	) -> Result<(), Self::Error> {
 		match *relayer {
 			FAILING_RELAYER => Err(()),
-			_ => Ok(()),
+			_ => {
+				frame_support::storage::unhashed::put(
+					b":last-payment-version:",
+					&account_derivation_version,
+				);
+				Ok(())
+			},
 		}
 	}
 }
 
+/// Returns account derivation version, used by the last successful payment.
+pub fn last_payment_account_derivation_version() -> Option<AccountDerivationVersion> {
+	frame_support::storage::unhashed::get(b":last-payment-version:")
+}
+
 /// Run pallet test.
 pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
 	let t = frame_system::GenesisConfig::default().build_storage::<TestRuntime>().unwrap();
@@ -51,7 +51,7 @@ where
 		let relayers_rewards =
 			bp_messages::calc_relayers_rewards::<T::AccountId>(messages_relayers, received_range);
 
-		register_relayers_rewards::<T>(
+		let total_reward = register_relayers_rewards::<T>(
 			confirmation_relayer,
 			relayers_rewards,
 			RewardsAccountParams::new(
@@ -61,22 +61,29 @@ where
 			),
 			DeliveryReward::get(),
 		);
+		pallet_bridge_messages::Pallet::<T, MI>::note_rewards_paid(
+			lane_id,
+			total_reward.saturated_into(),
+		);
 	}
 }
 
-// Update rewards to given relayers, optionally rewarding confirmation relayer.
+// Update rewards to given relayers, optionally rewarding confirmation relayer. Returns total
+// registered reward.
 fn register_relayers_rewards<T: Config>(
 	confirmation_relayer: &T::AccountId,
 	relayers_rewards: RelayersRewards<T::AccountId>,
 	lane_id: RewardsAccountParams,
 	delivery_fee: T::Reward,
-) {
+) -> T::Reward {
 	// reward every relayer except `confirmation_relayer`
+	let mut total_reward = T::Reward::zero();
 	let mut confirmation_relayer_reward = T::Reward::zero();
 	for (relayer, messages) in relayers_rewards {
 		// sane runtime configurations guarantee that the number of messages will be below
 		// `u32::MAX`
 		let relayer_reward = T::Reward::saturated_from(messages).saturating_mul(delivery_fee);
+		total_reward = total_reward.saturating_add(relayer_reward);
 
 		if relayer != *confirmation_relayer {
 			Pallet::<T>::register_relayer_reward(lane_id, &relayer, relayer_reward);
@@ -92,6 +99,8 @@ fn register_relayers_rewards<T: Config>(
 		confirmation_relayer,
 		confirmation_relayer_reward,
 	);
+
+	total_reward
 }
 
 #[cfg(test)]
@@ -110,12 +119,13 @@ mod tests {
 	#[test]
 	fn confirmation_relayer_is_rewarded_if_it_has_also_delivered_messages() {
 		run_test(|| {
-			register_relayers_rewards::<TestRuntime>(
+			let total_reward = register_relayers_rewards::<TestRuntime>(
 				&RELAYER_2,
 				relayers_rewards(),
 				TEST_REWARDS_ACCOUNT_PARAMS,
 				50,
 			);
+			assert_eq!(total_reward, 250);
 
 			assert_eq!(
 				RelayerRewards::<TestRuntime>::get(RELAYER_1, TEST_REWARDS_ACCOUNT_PARAMS),
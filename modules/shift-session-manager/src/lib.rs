@@ -14,16 +14,80 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Substrate session manager that selects 2/3 validators from initial set,
-//! starting from session 2.
+//! Substrate session manager that selects validators from initial set, starting from session 2.
+//!
+//! Validators of every session are selected by the configurable rotation strategy. The default
+//! strategy ([`ShiftedSubset`]) selects a fixed part (e.g. 2/3) of initial validators, shifting
+//! the selection every few sessions. Additionally, the set of initial validators may be
+//! restricted with the allowlist, which is managed by the privileged origin.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::traits::{ValidatorSet, ValidatorSetWithIdentification};
-use sp_std::prelude::*;
+use frame_support::traits::{Get, ValidatorSet, ValidatorSetWithIdentification};
+use sp_runtime::Perbill;
+use sp_staking::SessionIndex;
+use sp_std::{marker::PhantomData, prelude::*};
 
 pub use pallet::*;
 
+/// Strategy of selecting validators for the new session.
+pub trait RotationStrategy<ValidatorId> {
+	/// Select validators for the session with given index from available validators.
+	///
+	/// `available_validators` is never empty. The resulting vector must not be empty either.
+	fn select_validators(
+		session_index: SessionIndex,
+		available_validators: &[ValidatorId],
+	) -> Vec<ValidatorId>;
+}
+
+/// Rotation strategy that selects `SubsetSize` part of available validators (but at least one
+/// validator). The selection is shifted by one validator every `RotationPeriod` sessions.
+pub struct ShiftedSubset<RotationPeriod, SubsetSize>(PhantomData<(RotationPeriod, SubsetSize)>);
+
+impl<ValidatorId, RotationPeriod, SubsetSize> RotationStrategy<ValidatorId>
+	for ShiftedSubset<RotationPeriod, SubsetSize>
+where
+	ValidatorId: Clone,
+	RotationPeriod: Get<SessionIndex>,
+	SubsetSize: Get<Perbill>,
+{
+	fn select_validators(
+		session_index: SessionIndex,
+		available_validators: &[ValidatorId],
+	) -> Vec<ValidatorId> {
+		let available_validators_count = available_validators.len();
+		let subset_size = SubsetSize::get().mul_floor(available_validators_count as u32) as usize;
+		let count = sp_std::cmp::min(available_validators_count, sp_std::cmp::max(1, subset_size));
+		let shift = session_index / sp_std::cmp::max(1, RotationPeriod::get());
+		let offset = shift as usize % available_validators_count;
+		let end = offset + count;
+		let session_validators = match end.overflowing_sub(available_validators_count) {
+			(wrapped_end, false) if wrapped_end != 0 => available_validators[offset..]
+				.iter()
+				.chain(available_validators[..wrapped_end].iter())
+				.cloned()
+				.collect(),
+			_ => available_validators[offset..end].to_vec(),
+		};
+
+		session_validators
+	}
+}
+
+/// Two thirds of available validators.
+pub struct TwoThirds;
+
+impl Get<Perbill> for TwoThirds {
+	fn get() -> Perbill {
+		Perbill::from_rational(2u32, 3u32)
+	}
+}
+
+/// Rotation strategy that selects 2/3 of available validators, shifting the selection every
+/// session.
+pub type DefaultRotationStrategy = ShiftedSubset<frame_support::traits::ConstU32<1>, TwoThirds>;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -32,7 +96,12 @@ pub mod pallet {
 
 	#[pallet::config]
 	#[pallet::disable_frame_system_supertrait_check]
-	pub trait Config: pallet_session::Config {}
+	pub trait Config: pallet_session::Config {
+		/// Strategy of selecting validators for every new session.
+		type RotationStrategy: RotationStrategy<Self::ValidatorId>;
+		/// Origin that may change the validators allowlist.
+		type AllowlistOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+	}
 
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
@@ -42,11 +111,32 @@ pub mod pallet {
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
 
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {}
+	impl<T: Config> Pallet<T> {
+		/// Set or reset the validators allowlist.
+		///
+		/// When the allowlist is set, validators of new sessions are selected only from initial
+		/// validators that are in the allowlist. Initial validators that are not in the allowlist
+		/// are ignored. If none of initial validators is in the allowlist, it is ignored.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_allowlist(
+			origin: OriginFor<T>,
+			allowlist: Option<Vec<T::ValidatorId>>,
+		) -> DispatchResult {
+			T::AllowlistOrigin::ensure_origin(origin)?;
+			Allowlist::<T>::set(allowlist);
+			Ok(())
+		}
+	}
 
 	/// Validators of first two sessions.
 	#[pallet::storage]
 	pub(super) type InitialValidators<T: Config> = StorageValue<_, Vec<T::ValidatorId>>;
+
+	/// Optional allowlist of validators that may be selected for new sessions.
+	#[pallet::storage]
+	#[pallet::getter(fn allowlist)]
+	pub type Allowlist<T: Config> = StorageValue<_, Vec<T::ValidatorId>>;
 }
 
 impl<T: pallet_session::Config + Config> ValidatorSet<T::AccountId> for Pallet<T> {
@@ -79,14 +169,18 @@ impl<T: Config> pallet_session::SessionManager<T::ValidatorId> for Pallet<T> {
 		// the idea that on first call (i.e. when session 1 ends) we're reading current
 		// set of validators from session module (they are initial validators) and save
 		// in our 'local storage'.
-		// then for every session we select (deterministically) 2/3 of these initial
+		// then for every session we select (deterministically) some of these initial
 		// validators to serve validators of new session
-		let available_validators = InitialValidators::<T>::get().unwrap_or_else(|| {
+		let initial_validators = InitialValidators::<T>::get().unwrap_or_else(|| {
 			let validators = <pallet_session::Pallet<T>>::validators();
 			InitialValidators::<T>::put(validators.clone());
 			validators
 		});
+		if initial_validators.is_empty() {
+			return None
+		}
 
+		let available_validators = Self::allowed_validators(initial_validators);
 		Some(Self::select_validators(session_index, &available_validators))
 	}
 }
@@ -97,20 +191,26 @@ impl<T: Config> Pallet<T> {
 		session_index: sp_staking::SessionIndex,
 		available_validators: &[T::ValidatorId],
 	) -> Vec<T::ValidatorId> {
-		let available_validators_count = available_validators.len();
-		let count = sp_std::cmp::max(1, 2 * available_validators_count / 3);
-		let offset = session_index as usize % available_validators_count;
-		let end = offset + count;
-		let session_validators = match end.overflowing_sub(available_validators_count) {
-			(wrapped_end, false) if wrapped_end != 0 => available_validators[offset..]
-				.iter()
-				.chain(available_validators[..wrapped_end].iter())
-				.cloned()
-				.collect(),
-			_ => available_validators[offset..end].to_vec(),
+		T::RotationStrategy::select_validators(session_index, available_validators)
+	}
+
+	/// Filter initial validators using the allowlist.
+	fn allowed_validators(initial_validators: Vec<T::ValidatorId>) -> Vec<T::ValidatorId> {
+		let allowlist = match Allowlist::<T>::get() {
+			Some(allowlist) => allowlist,
+			None => return initial_validators,
 		};
 
-		session_validators
+		let allowed_validators = initial_validators
+			.iter()
+			.filter(|validator| allowlist.contains(validator))
+			.cloned()
+			.collect::<Vec<_>>();
+		if allowed_validators.is_empty() {
+			initial_validators
+		} else {
+			allowed_validators
+		}
 	}
 }
 
@@ -121,7 +221,7 @@ mod tests {
 
 	use super::*;
 	use frame_support::{
-		parameter_types,
+		assert_noop, assert_ok, parameter_types,
 		sp_io::TestExternalities,
 		sp_runtime::{
 			testing::{Header, UintAuthorityId},
@@ -132,7 +232,10 @@ mod tests {
 		weights::Weight,
 		BasicExternalities,
 	};
+	use frame_system::EnsureRoot;
+	use pallet_session::SessionManager;
 	use sp_core::H256;
+	use sp_runtime::DispatchError;
 
 	type AccountId = u64;
 
@@ -147,6 +250,7 @@ mod tests {
 		{
 			System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 			Session: pallet_session::{Pallet},
+			ShiftSessionManager: crate::{Pallet, Call, Storage},
 		}
 	}
 
@@ -200,7 +304,15 @@ mod tests {
 		type WeightInfo = ();
 	}
 
-	impl Config for TestRuntime {}
+	parameter_types! {
+		pub static RotationPeriod: SessionIndex = 1;
+		pub static SubsetSize: Perbill = Perbill::from_rational(2u32, 3u32);
+	}
+
+	impl Config for TestRuntime {
+		type RotationStrategy = ShiftedSubset<RotationPeriod, SubsetSize>;
+		type AllowlistOrigin = EnsureRoot<AccountId>;
+	}
 
 	pub struct TestSessionHandler;
 	impl pallet_session::SessionHandler<AccountId> for TestSessionHandler {
@@ -263,4 +375,71 @@ mod tests {
 			assert_eq!(Pallet::<TestRuntime>::select_validators(5, &all_accs), vec![1, 2, 3],);
 		});
 	}
+
+	#[test]
+	fn validators_are_rotated_every_rotation_period_sessions() {
+		new_test_ext().execute_with(|| {
+			let all_accs = vec![1, 2, 3, 4, 5];
+			RotationPeriod::set(2);
+
+			assert_eq!(Pallet::<TestRuntime>::select_validators(0, &all_accs), vec![1, 2, 3]);
+			assert_eq!(Pallet::<TestRuntime>::select_validators(1, &all_accs), vec![1, 2, 3]);
+			assert_eq!(Pallet::<TestRuntime>::select_validators(2, &all_accs), vec![2, 3, 4]);
+			assert_eq!(Pallet::<TestRuntime>::select_validators(3, &all_accs), vec![2, 3, 4]);
+			assert_eq!(Pallet::<TestRuntime>::select_validators(4, &all_accs), vec![3, 4, 5]);
+		});
+	}
+
+	#[test]
+	fn subset_size_is_configurable() {
+		new_test_ext().execute_with(|| {
+			let all_accs = vec![1, 2, 3, 4, 5];
+
+			SubsetSize::set(Perbill::from_percent(40));
+			assert_eq!(Pallet::<TestRuntime>::select_validators(4, &all_accs), vec![5, 1]);
+
+			SubsetSize::set(Perbill::one());
+			assert_eq!(Pallet::<TestRuntime>::select_validators(1, &all_accs), vec![2, 3, 4, 5, 1]);
+
+			// at least 1 validator is selected
+			SubsetSize::set(Perbill::zero());
+			assert_eq!(Pallet::<TestRuntime>::select_validators(1, &all_accs), vec![2]);
+		});
+	}
+
+	#[test]
+	fn allowlist_may_only_be_set_by_allowlist_origin() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::set_allowlist(RuntimeOrigin::signed(1), Some(vec![1])),
+				DispatchError::BadOrigin,
+			);
+
+			assert_ok!(Pallet::<TestRuntime>::set_allowlist(RuntimeOrigin::root(), Some(vec![1])));
+			assert_eq!(Pallet::<TestRuntime>::allowlist(), Some(vec![1]));
+
+			assert_ok!(Pallet::<TestRuntime>::set_allowlist(RuntimeOrigin::root(), None));
+			assert_eq!(Pallet::<TestRuntime>::allowlist(), None);
+		});
+	}
+
+	#[test]
+	fn validators_are_selected_from_allowlist() {
+		new_test_ext().execute_with(|| {
+			// initial validators are read from the session pallet
+			assert_eq!(Pallet::<TestRuntime>::new_session(2), Some(vec![3, 4, 5]));
+
+			// validator 6 is not an initial validator, so it is ignored
+			assert_ok!(Pallet::<TestRuntime>::set_allowlist(
+				RuntimeOrigin::root(),
+				Some(vec![2, 4, 5, 6]),
+			));
+			assert_eq!(Pallet::<TestRuntime>::new_session(2), Some(vec![5, 2]));
+			assert_eq!(Pallet::<TestRuntime>::new_session(3), Some(vec![2, 4]));
+
+			// when there are no initial validators in the allowlist, it is ignored
+			assert_ok!(Pallet::<TestRuntime>::set_allowlist(RuntimeOrigin::root(), Some(vec![6])));
+			assert_eq!(Pallet::<TestRuntime>::new_session(2), Some(vec![3, 4, 5]));
+		});
+	}
 }
@@ -0,0 +1,1024 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Module that dispatches XCM messages, delivered over the bridge.
+//!
+//! The pallet implements the [`MessageDispatch`] trait, so it may be used as a message
+//! dispatcher of any messages pallet instance, that is receiving XCM messages. Every dispatched
+//! message is reported with the [`Event::MessageDispatched`] event.
+//!
+//! Dispatch may be paused at every lane. Messages, delivered over the paused lane, are put into
+//! the lane dispatch queue. Once the lane is resumed, queued messages are dispatched (in the order
+//! they have been delivered) when the block has some spare weight. Messages, delivered over the
+//! lane with non-empty dispatch queue are also queued, so the order is preserved.
+//!
+//! Before the message is dispatched, the [`Config::FeeFundingCheck`] is used to check whether the
+//! origin of the XCM program is able to pay for its execution. If it can't, the message is parked
+//! in the dispatch queue of its origin and the [`Event::InsufficientFeeFunding`] event is
+//! deposited. Parked messages don't block the lane: messages of other origins are dispatched as
+//! usual, but following messages of the same origin are parked too, so their order is preserved.
+//! Parked messages are retried later, when the block has some spare weight. The
+//! [`SovereignAccountFeeFunding`] may be used to check that the sovereign account of the origin
+//! is able to pay for the program execution.
+//!
+//! Dispatch queues are not bounded, so delivered messages are never dropped.
+//!
+//! The crate also exposes the stateless [`weigh_xcm_message`] and [`execute_xcm_message`]
+//! functions, that are used by the pallet and by other dispatchers of bridged XCM messages.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+use bp_messages::{
+	target_chain::{DispatchMessage, MessageDispatch},
+	LaneId, MessageNonce,
+};
+use bp_runtime::messages::MessageDispatchResult;
+use codec::{Decode, DecodeLimit, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{Currency, Get},
+	weights::{Weight, WeightToFee},
+	RuntimeDebug,
+};
+use scale_info::TypeInfo;
+use sp_runtime::traits::UniqueSaturatedInto;
+use sp_std::{convert::TryFrom, marker::PhantomData, vec::Vec};
+use xcm::{latest::prelude::*, VersionedMultiLocation, VersionedXcm};
+use xcm_executor::traits::{Convert, WeightBounds};
+
+pub use pallet::*;
+
+mod mock;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "runtime::bridge-xcm-dispatcher";
+
+/// Call type of given pallet configuration.
+pub type CallOf<T> = <T as frame_system::Config>::RuntimeCall;
+/// Queued message type of given pallet configuration.
+pub type QueuedMessageOf<T> = QueuedMessage<CallOf<T>>;
+
+/// Decoded payload of the XCM message, delivered over the bridge.
+///
+/// Versioned XCM message is sent over the wire. It is converted to the XCM version that is
+/// supported by this chain when decoded.
+#[derive(RuntimeDebug, PartialEq, Eq)]
+pub struct FromBridgedChainMessagePayload<Call> {
+	/// Data that is actually sent over the wire, converted to the XCM v3.
+	pub xcm: (xcm::v3::MultiLocation, xcm::v3::Xcm<Call>),
+	/// Weight of the message, computed by the weigher. Unknown initially.
+	pub weight: Option<Weight>,
+}
+
+impl<Call: Decode> FromBridgedChainMessagePayload<Call> {
+	/// Decode payload, using given nesting depth limit.
+	pub fn decode_with_nesting_limit<I: codec::Input>(
+		depth_limit: u32,
+		input: &mut I,
+	) -> Result<Self, codec::Error> {
+		let _: codec::Compact<u32> = Decode::decode(input)?;
+		type VersionedXcmPairType<Call> = (VersionedMultiLocation, VersionedXcm<Call>);
+		let (location, xcm) =
+			VersionedXcmPairType::<Call>::decode_with_depth_limit(depth_limit, input)?;
+		let location = xcm::v3::MultiLocation::try_from(location)
+			.map_err(|_| codec::Error::from("Unsupported XCM location version"))?;
+		let xcm = xcm::v3::Xcm::<Call>::try_from(xcm)
+			.map_err(|_| codec::Error::from("Unsupported XCM version"))?;
+		Ok(FromBridgedChainMessagePayload { xcm: (location, xcm), weight: None })
+	}
+}
+
+impl<Call: Decode> Decode for FromBridgedChainMessagePayload<Call> {
+	fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+		Self::decode_with_nesting_limit(sp_api::MAX_EXTRINSIC_DEPTH, input)
+	}
+}
+
+impl<Call> From<(xcm::v3::MultiLocation, xcm::v3::Xcm<Call>)>
+	for FromBridgedChainMessagePayload<Call>
+{
+	fn from(xcm: (xcm::v3::MultiLocation, xcm::v3::Xcm<Call>)) -> Self {
+		FromBridgedChainMessagePayload { xcm, weight: None }
+	}
+}
+
+/// Compute weight of the XCM program of the delivered message, using given weigher.
+///
+/// The weight is also stored in the message payload, so it is later used as the weight limit
+/// when the program is executed. Zero weight is returned if the payload can't be decoded.
+pub fn weigh_xcm_message<Call, XcmWeigher: WeightBounds<Call>>(
+	message: &mut DispatchMessage<FromBridgedChainMessagePayload<Call>>,
+) -> Weight {
+	let payload = match message.data.payload {
+		Ok(ref mut payload) => payload,
+		Err(_) => return Weight::zero(),
+	};
+
+	let weight = XcmWeigher::weight(&mut payload.xcm.1).unwrap_or_else(|e| {
+		log::debug!(
+			target: LOG_TARGET,
+			"Failed to compute dispatch weight of incoming XCM message {:?}/{}: {:?}",
+			message.key.lane_id,
+			message.key.nonce,
+			e,
+		);
+
+		// we shall return 0 and then the XCM executor will fail to execute XCM
+		// if we'll return something else (e.g. maximal value), the lane may stuck
+		Weight::zero()
+	});
+	payload.weight = Some(weight);
+	weight
+}
+
+/// Execute XCM program of the delivered message.
+pub fn execute_xcm_message<Call, XcmExecutor: ExecuteXcm<Call>>(
+	lane: LaneId,
+	nonce: MessageNonce,
+	origin: MultiLocation,
+	xcm: Xcm<Call>,
+	weight_limit: Weight,
+	weight_credit: Weight,
+) -> Outcome {
+	log::trace!(
+		target: LOG_TARGET,
+		"Going to execute message {:?}/{} (weight limit: {:?}): {:?} {:?}",
+		lane,
+		nonce,
+		weight_limit,
+		origin,
+		xcm,
+	);
+
+	let hash = (lane, nonce).using_encoded(sp_io::hashing::blake2_256);
+	let outcome =
+		XcmExecutor::execute_xcm_in_credit(origin, xcm, hash, weight_limit, weight_credit);
+
+	log::trace!(
+		target: LOG_TARGET,
+		"Message {:?}/{} dispatched with result: {:?}",
+		lane,
+		nonce,
+		outcome,
+	);
+
+	outcome
+}
+
+/// Identifier of the dispatch queue.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+pub enum QueueId {
+	/// Queue of messages, delivered over the lane. Messages of this queue are indexed by their
+	/// nonces.
+	Lane(LaneId),
+	/// Queue of messages, which origin has been unable to pay for their execution. Messages of
+	/// this queue are indexed by their position in the queue.
+	Origin(VersionedMultiLocation),
+}
+
+/// Indices of the first and the last messages in the dispatch queue.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct QueueBounds {
+	/// Index of the first (oldest) message in the queue.
+	pub first: u64,
+	/// Index of the last (newest) message in the queue.
+	pub last: u64,
+}
+
+/// Message that is waiting for dispatch in the dispatch queue.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+#[scale_info(skip_type_params(Call))]
+pub struct QueuedMessage<Call> {
+	/// Lane, the message has been delivered over.
+	pub lane: LaneId,
+	/// Message nonce.
+	pub nonce: MessageNonce,
+	/// Origin of the XCM program.
+	pub origin: VersionedMultiLocation,
+	/// The XCM program itself.
+	pub xcm: VersionedXcm<Call>,
+	/// Weight of the XCM program, computed by the weigher.
+	pub weight: Weight,
+}
+
+/// Origin of the XCM program is unable to pay for its execution.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+pub struct InsufficientFeeFunding {
+	/// Fee that is required to execute the program.
+	pub required_fee: u128,
+	/// Balance that is available to pay the fee.
+	pub available_balance: u128,
+}
+
+/// Checks whether the origin of the XCM program is able to pay for its execution.
+pub trait FeeFundingCheck<Call> {
+	/// Returns error if the origin is unable to pay for execution of the program with given
+	/// weight.
+	fn check_fee_funding(
+		origin: &MultiLocation,
+		xcm: &Xcm<Call>,
+		weight: Weight,
+	) -> Result<(), InsufficientFeeFunding>;
+}
+
+impl<Call> FeeFundingCheck<Call> for () {
+	fn check_fee_funding(
+		_origin: &MultiLocation,
+		_xcm: &Xcm<Call>,
+		_weight: Weight,
+	) -> Result<(), InsufficientFeeFunding> {
+		Ok(())
+	}
+}
+
+/// Checks that the sovereign account of the program origin has enough funds to pay for the
+/// program execution.
+///
+/// Only programs that are paying fees from the sovereign account of the origin (i.e. starting
+/// with the `WithdrawAsset` instruction) are checked. The fee is computed from the program weight,
+/// using the `WeightToFee` converter.
+pub struct SovereignAccountFeeFunding<AccountId, AccountIdConverter, Balances, WeightToFee>(
+	PhantomData<(AccountId, AccountIdConverter, Balances, WeightToFee)>,
+);
+
+impl<Call, AccountId, AccountIdConverter, Balances, WeightToFeeConverter> FeeFundingCheck<Call>
+	for SovereignAccountFeeFunding<AccountId, AccountIdConverter, Balances, WeightToFeeConverter>
+where
+	AccountId: Clone,
+	AccountIdConverter: Convert<MultiLocation, AccountId>,
+	Balances: Currency<AccountId>,
+	WeightToFeeConverter: WeightToFee<Balance = Balances::Balance>,
+{
+	fn check_fee_funding(
+		origin: &MultiLocation,
+		xcm: &Xcm<Call>,
+		weight: Weight,
+	) -> Result<(), InsufficientFeeFunding> {
+		if !matches!(xcm.0.first(), Some(WithdrawAsset(_))) {
+			return Ok(())
+		}
+
+		// if origin has no sovereign account, the XCM executor will fail to withdraw assets
+		let sovereign_account = match AccountIdConverter::convert_ref(origin) {
+			Ok(sovereign_account) => sovereign_account,
+			Err(_) => return Ok(()),
+		};
+
+		let required_fee = WeightToFeeConverter::weight_to_fee(&weight);
+		let available_balance = Balances::free_balance(&sovereign_account);
+		if available_balance >= required_fee {
+			return Ok(())
+		}
+
+		Err(InsufficientFeeFunding {
+			required_fee: required_fee.unique_saturated_into(),
+			available_balance: available_balance.unique_saturated_into(),
+		})
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// XCM executor that is used to execute delivered programs.
+		type XcmExecutor: ExecuteXcm<CallOf<Self>>;
+		/// XCM weigher that computes weight of delivered programs.
+		type XcmWeigher: WeightBounds<CallOf<Self>>;
+		/// Weight credit that is given to every delivered program.
+		type WeightCredit: Get<Weight>;
+		/// Checks whether the origin of delivered program is able to pay for its execution.
+		type FeeFundingCheck: FeeFundingCheck<CallOf<Self>>;
+
+		/// Origin that may pause and resume dispatch at lanes.
+		type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::dispatch_queued_messages(remaining_weight)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Pause dispatch of messages, delivered over given lane.
+		///
+		/// Messages that are delivered while the lane is paused, are put into the lane dispatch
+		/// queue.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn pause_dispatch(origin: OriginFor<T>, lane: LaneId) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(!PausedLanes::<T, I>::contains_key(lane), Error::<T, I>::DispatchAlreadyPaused);
+
+			PausedLanes::<T, I>::insert(lane, ());
+
+			log::info!(target: LOG_TARGET, "Dispatch at lane {:?} has been paused", lane);
+
+			Self::deposit_event(Event::DispatchPaused { lane });
+			Ok(())
+		}
+
+		/// Resume dispatch of messages, delivered over given lane.
+		///
+		/// Queued messages are dispatched later, when the block has some spare weight.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn resume_dispatch(origin: OriginFor<T>, lane: LaneId) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(PausedLanes::<T, I>::contains_key(lane), Error::<T, I>::DispatchNotPaused);
+
+			PausedLanes::<T, I>::remove(lane);
+
+			log::info!(target: LOG_TARGET, "Dispatch at lane {:?} has been resumed", lane);
+
+			Self::deposit_event(Event::DispatchResumed { lane });
+			Ok(())
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Returns true if messages, delivered over given lane, must be queued.
+		pub fn must_queue(lane: LaneId) -> bool {
+			PausedLanes::<T, I>::contains_key(lane) ||
+				Queues::<T, I>::contains_key(QueueId::Lane(lane))
+		}
+
+		/// Returns number of messages in the given dispatch queue.
+		pub fn queue_len(queue: &QueueId) -> u64 {
+			Queues::<T, I>::get(queue)
+				.map(|bounds| bounds.last.saturating_sub(bounds.first).saturating_add(1))
+				.unwrap_or(0)
+		}
+
+		/// Returns weight of the message, that is not blocked by the lane queue, excluding
+		/// weight of the XCM program itself.
+		fn dispatch_unblocked_message_overhead() -> Weight {
+			// we read bounds of the origin queue and may park the message there
+			T::DbWeight::get().reads_writes(1, 2)
+		}
+
+		/// Execute XCM program of the message and deposit the dispatch event.
+		fn execute_message(
+			lane: LaneId,
+			nonce: MessageNonce,
+			origin: MultiLocation,
+			xcm: Xcm<CallOf<T>>,
+			weight_limit: Weight,
+		) {
+			let outcome = execute_xcm_message::<_, T::XcmExecutor>(
+				lane,
+				nonce,
+				origin,
+				xcm,
+				weight_limit,
+				T::WeightCredit::get(),
+			);
+			Self::deposit_event(Event::MessageDispatched { lane, nonce, outcome });
+		}
+
+		/// Dispatch message, that is not blocked by the lane queue.
+		///
+		/// If the origin of the message already has parked messages, or if it is unable to pay
+		/// for the message execution, the message is parked in the origin queue.
+		fn dispatch_unblocked_message(
+			lane: LaneId,
+			nonce: MessageNonce,
+			origin: MultiLocation,
+			xcm: Xcm<CallOf<T>>,
+			weight: Weight,
+		) {
+			let origin_queue = QueueId::Origin(origin.into());
+			if Queues::<T, I>::contains_key(&origin_queue) {
+				// the order of messages of the same origin is preserved
+				Self::enqueue_message(
+					origin_queue,
+					QueuedMessage { lane, nonce, origin: origin.into(), xcm: xcm.into(), weight },
+				);
+				return
+			}
+
+			if let Err(e) = T::FeeFundingCheck::check_fee_funding(&origin, &xcm, weight) {
+				log::debug!(
+					target: LOG_TARGET,
+					"Origin {:?} of message {:?}/{} is unable to pay for its execution: {:?}",
+					origin,
+					lane,
+					nonce,
+					e,
+				);
+
+				Self::deposit_event(Event::InsufficientFeeFunding {
+					lane,
+					nonce,
+					required_fee: e.required_fee,
+					available_balance: e.available_balance,
+				});
+				Self::enqueue_message(
+					origin_queue,
+					QueuedMessage { lane, nonce, origin: origin.into(), xcm: xcm.into(), weight },
+				);
+				return
+			}
+
+			Self::execute_message(lane, nonce, origin, xcm, weight);
+		}
+
+		/// Returns true if the origin of the queued message is able to pay for its execution.
+		fn is_execution_funded(message: &QueuedMessageOf<T>) -> bool {
+			let origin = MultiLocation::try_from(message.origin.clone());
+			let xcm = Xcm::<CallOf<T>>::try_from(message.xcm.clone());
+			match (origin, xcm) {
+				(Ok(origin), Ok(xcm)) =>
+					match T::FeeFundingCheck::check_fee_funding(&origin, &xcm, message.weight) {
+						Ok(()) => true,
+						Err(e) => {
+							log::trace!(
+								target: LOG_TARGET,
+								"Queued message {:?}/{} is still not funded: {:?}",
+								message.lane,
+								message.nonce,
+								e,
+							);
+							false
+						},
+					},
+				// conversion error is reported when message is dispatched
+				_ => true,
+			}
+		}
+
+		/// Put message at the end of given dispatch queue.
+		fn enqueue_message(queue: QueueId, message: QueuedMessageOf<T>) {
+			let lane = message.lane;
+			let nonce = message.nonce;
+			let index = Queues::<T, I>::mutate(&queue, |bounds| {
+				let index = match (&queue, *bounds) {
+					(QueueId::Lane(_), _) => nonce,
+					(QueueId::Origin(_), Some(bounds)) => bounds.last.saturating_add(1),
+					(QueueId::Origin(_), None) => 0,
+				};
+				let first = bounds.map(|bounds| bounds.first).unwrap_or(index);
+				*bounds = Some(QueueBounds { first, last: index });
+				index
+			});
+			QueuedMessages::<T, I>::insert(&queue, index, message);
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Message {:?}/{} has been queued to {:?}",
+				lane,
+				nonce,
+				queue,
+			);
+			Self::deposit_event(Event::MessageQueued { lane, nonce });
+		}
+
+		/// Dispatch queued messages of resumed lanes and funded origins, until all given weight
+		/// is used.
+		///
+		/// Returns weight that has been used.
+		fn dispatch_queued_messages(remaining_weight: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut used_weight = Weight::zero();
+			let queues = Queues::<T, I>::iter_keys().collect::<Vec<_>>();
+			for queue in queues {
+				let queue_weight = db_weight.reads_writes(2, 1);
+				if used_weight.saturating_add(queue_weight).any_gt(remaining_weight) {
+					break
+				}
+				used_weight.saturating_accrue(db_weight.reads(2));
+
+				if let QueueId::Lane(lane) = queue {
+					if PausedLanes::<T, I>::contains_key(lane) {
+						continue
+					}
+				}
+
+				let mut bounds = match Queues::<T, I>::get(&queue) {
+					Some(bounds) => bounds,
+					None => continue,
+				};
+				let mut dispatched_messages = 0;
+				while bounds.first <= bounds.last {
+					let message = match QueuedMessages::<T, I>::get(&queue, bounds.first) {
+						Some(message) => message,
+						None => {
+							log::error!(
+								target: LOG_TARGET,
+								"Message {} is missing from the dispatch queue {:?}",
+								bounds.first,
+								queue,
+							);
+							bounds.first = bounds.first.saturating_add(1);
+							dispatched_messages += 1;
+							continue
+						},
+					};
+
+					// the message is read and removed from the queue, then the queue bounds are
+					// updated
+					let message_weight = message
+						.weight
+						.saturating_add(db_weight.reads_writes(1, 2))
+						.saturating_add(Self::dispatch_unblocked_message_overhead());
+					if used_weight.saturating_add(message_weight).any_gt(remaining_weight) {
+						break
+					}
+					// the order of messages is preserved, so we stop at the first unfunded message
+					// of the origin queue
+					let is_origin_queue = matches!(queue, QueueId::Origin(_));
+					if is_origin_queue && !Self::is_execution_funded(&message) {
+						break
+					}
+					used_weight
+						.saturating_accrue(message_weight.saturating_sub(db_weight.writes(1)));
+
+					QueuedMessages::<T, I>::remove(&queue, bounds.first);
+					bounds.first = bounds.first.saturating_add(1);
+					dispatched_messages += 1;
+					Self::dispatch_queued_message(message, !is_origin_queue);
+				}
+
+				if dispatched_messages != 0 {
+					used_weight.saturating_accrue(db_weight.writes(1));
+					if bounds.first > bounds.last {
+						Queues::<T, I>::remove(&queue);
+					} else {
+						Queues::<T, I>::insert(&queue, bounds);
+					}
+				}
+			}
+
+			used_weight
+		}
+
+		/// Dispatch message from the dispatch queue.
+		///
+		/// Messages of the lane queue may be parked in the origin queue, if their origin is
+		/// unable to pay for execution.
+		fn dispatch_queued_message(message: QueuedMessageOf<T>, may_be_parked: bool) {
+			let lane = message.lane;
+			let nonce = message.nonce;
+			let origin = MultiLocation::try_from(message.origin);
+			let xcm = Xcm::<CallOf<T>>::try_from(message.xcm);
+			match (origin, xcm) {
+				(Ok(origin), Ok(xcm)) if may_be_parked => {
+					Self::dispatch_unblocked_message(lane, nonce, origin, xcm, message.weight);
+				},
+				(Ok(origin), Ok(xcm)) => {
+					Self::execute_message(lane, nonce, origin, xcm, message.weight);
+				},
+				_ => {
+					log::error!(
+						target: LOG_TARGET,
+						"Failed to convert queued message {:?}/{} to the latest XCM version",
+						lane,
+						nonce,
+					);
+					Self::deposit_event(Event::MessageDispatched {
+						lane,
+						nonce,
+						outcome: Outcome::Error(XcmError::UnhandledXcmVersion),
+					});
+				},
+			}
+		}
+	}
+
+	/// Lanes where the dispatch is currently paused.
+	#[pallet::storage]
+	pub type PausedLanes<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, LaneId, (), OptionQuery>;
+
+	/// Bounds of all non-empty dispatch queues.
+	#[pallet::storage]
+	pub type Queues<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, QueueId, QueueBounds, OptionQuery>;
+
+	/// Messages that are waiting for dispatch, mapped by their queues and indices within queues.
+	#[pallet::storage]
+	pub type QueuedMessages<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		QueueId,
+		Identity,
+		u64,
+		QueuedMessageOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// Message has been dispatched.
+		MessageDispatched {
+			/// Message lane.
+			lane: LaneId,
+			/// Message nonce.
+			nonce: MessageNonce,
+			/// Outcome of the XCM program execution.
+			outcome: Outcome,
+		},
+		/// Message has been put into the lane or origin dispatch queue.
+		MessageQueued {
+			/// Message lane.
+			lane: LaneId,
+			/// Message nonce.
+			nonce: MessageNonce,
+		},
+		/// Origin of the message is unable to pay for its execution. The message has been parked
+		/// in the origin dispatch queue and will be retried later.
+		InsufficientFeeFunding {
+			/// Message lane.
+			lane: LaneId,
+			/// Message nonce.
+			nonce: MessageNonce,
+			/// Fee that is required to execute the message.
+			required_fee: u128,
+			/// Balance that is available to pay the fee.
+			available_balance: u128,
+		},
+		/// Dispatch at the lane has been paused.
+		DispatchPaused {
+			/// Paused lane.
+			lane: LaneId,
+		},
+		/// Dispatch at the lane has been resumed.
+		DispatchResumed {
+			/// Resumed lane.
+			lane: LaneId,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// Dispatch at the lane is already paused.
+		DispatchAlreadyPaused,
+		/// Dispatch at the lane is not paused.
+		DispatchNotPaused,
+	}
+}
+
+impl<T: Config<I>, I: 'static> MessageDispatch<T::AccountId> for Pallet<T, I> {
+	type DispatchPayload = FromBridgedChainMessagePayload<CallOf<T>>;
+	type DispatchLevelResult = ();
+
+	fn dispatch_weight(message: &mut DispatchMessage<Self::DispatchPayload>) -> Weight {
+		if message.data.payload.is_err() {
+			return Weight::zero()
+		}
+
+		let weight = weigh_xcm_message::<_, T::XcmWeigher>(message);
+
+		// if message is queued, it is dispatched later, using the spare block weight
+		let db_weight = T::DbWeight::get();
+		if Self::must_queue(message.key.lane_id) {
+			return db_weight.reads_writes(2, 2)
+		}
+
+		weight
+			.saturating_add(db_weight.reads(2))
+			.saturating_add(Self::dispatch_unblocked_message_overhead())
+	}
+
+	fn dispatch(
+		_relayer_account: &T::AccountId,
+		message: DispatchMessage<Self::DispatchPayload>,
+	) -> MessageDispatchResult<Self::DispatchLevelResult> {
+		let lane = message.key.lane_id;
+		let nonce = message.key.nonce;
+		let dispatch_result =
+			MessageDispatchResult { unspent_weight: Weight::zero(), dispatch_level_result: () };
+
+		let FromBridgedChainMessagePayload { xcm: (origin, xcm), weight } =
+			match message.data.payload {
+				Ok(payload) => payload,
+				Err(e) => {
+					log::error!(
+						target: LOG_TARGET,
+						"Failed to decode incoming message {:?}/{}: {:?}",
+						lane,
+						nonce,
+						e,
+					);
+					Self::deposit_event(Event::MessageDispatched {
+						lane,
+						nonce,
+						outcome: Outcome::Error(XcmError::FailedToDecode),
+					});
+					return dispatch_result
+				},
+			};
+		let weight = weight.unwrap_or_else(Weight::zero);
+
+		if Self::must_queue(lane) {
+			Self::enqueue_message(
+				QueueId::Lane(lane),
+				QueuedMessage { lane, nonce, origin: origin.into(), xcm: xcm.into(), weight },
+			);
+			return dispatch_result
+		}
+
+		Self::dispatch_unblocked_message(lane, nonce, origin, xcm, weight);
+		dispatch_result
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+	use bp_messages::{target_chain::DispatchMessageData, MessageKey};
+	use frame_support::{assert_noop, assert_ok, traits::Hooks};
+	use sp_runtime::DispatchError;
+
+	const TEST_LANE: LaneId = LaneId([0, 0, 0, 1]);
+
+	fn message_from(
+		origin: MultiLocation,
+		nonce: MessageNonce,
+		xcm: Xcm<RuntimeCall>,
+	) -> DispatchMessage<FromBridgedChainMessagePayload<RuntimeCall>> {
+		DispatchMessage {
+			key: MessageKey { lane_id: TEST_LANE, nonce },
+			data: DispatchMessageData {
+				payload: Ok(FromBridgedChainMessagePayload::from((origin, xcm))),
+			},
+		}
+	}
+
+	fn dispatch(
+		mut message: DispatchMessage<FromBridgedChainMessagePayload<RuntimeCall>>,
+	) -> Weight {
+		let weight = Dispatcher::dispatch_weight(&mut message);
+		Dispatcher::dispatch(&0, message);
+		weight
+	}
+
+	fn dispatch_message(nonce: MessageNonce) -> Weight {
+		dispatch(message_from(
+			MultiLocation::parent(),
+			nonce,
+			Xcm(vec![ClearOrigin; nonce as usize]),
+		))
+	}
+
+	fn dispatch_paid_message_from(origin: MultiLocation, nonce: MessageNonce) {
+		dispatch(message_from(
+			origin,
+			nonce,
+			Xcm(vec![WithdrawAsset(MultiAssets::new()), ClearOrigin]),
+		));
+	}
+
+	fn dispatch_paid_message(nonce: MessageNonce) {
+		dispatch_paid_message_from(MultiLocation::parent(), nonce);
+	}
+
+	fn dispatched_messages() -> Vec<MessageNonce> {
+		System::events()
+			.into_iter()
+			.filter_map(|record| match record.event {
+				RuntimeEvent::Dispatcher(Event::MessageDispatched { nonce, .. }) => Some(nonce),
+				_ => None,
+			})
+			.collect()
+	}
+
+	fn lane_queue_len() -> u64 {
+		Dispatcher::queue_len(&QueueId::Lane(TEST_LANE))
+	}
+
+	fn origin_queue_len(origin: MultiLocation) -> u64 {
+		Dispatcher::queue_len(&QueueId::Origin(origin.into()))
+	}
+
+	fn unblocked_dispatch_weight(instructions: u64) -> Weight {
+		instructions_weight(instructions).saturating_add(DbWeight::get().reads_writes(3, 2))
+	}
+
+	#[test]
+	fn message_is_dispatched_immediately_if_lane_is_not_paused() {
+		run_test(|| {
+			assert_eq!(dispatch_message(1), unblocked_dispatch_weight(1));
+			assert_eq!(
+				executed_messages(),
+				vec![(TEST_LANE, 1).using_encoded(sp_io::hashing::blake2_256)]
+			);
+			assert_eq!(
+				System::events().last().map(|record| record.event.clone()),
+				Some(RuntimeEvent::Dispatcher(Event::MessageDispatched {
+					lane: TEST_LANE,
+					nonce: 1,
+					outcome: Outcome::Complete(instructions_weight(1)),
+				})),
+			);
+		});
+	}
+
+	#[test]
+	fn messages_are_queued_while_lane_is_paused() {
+		run_test(|| {
+			assert_ok!(Dispatcher::pause_dispatch(RuntimeOrigin::root(), TEST_LANE));
+
+			assert_eq!(dispatch_message(1), DbWeight::get().reads_writes(2, 2));
+			assert_eq!(dispatch_message(2), DbWeight::get().reads_writes(2, 2));
+			assert!(executed_messages().is_empty());
+			assert_eq!(lane_queue_len(), 2);
+			assert!(QueuedMessages::<TestRuntime, ()>::contains_key(QueueId::Lane(TEST_LANE), 1));
+			assert!(QueuedMessages::<TestRuntime, ()>::contains_key(QueueId::Lane(TEST_LANE), 2));
+
+			// nothing is dispatched while lane is paused
+			Dispatcher::on_idle(0, Weight::MAX);
+			assert!(executed_messages().is_empty());
+
+			// after lane is resumed, new messages are still queued to preserve the order
+			assert_ok!(Dispatcher::resume_dispatch(RuntimeOrigin::root(), TEST_LANE));
+			dispatch_message(3);
+			assert!(executed_messages().is_empty());
+
+			// queued messages are dispatched in order
+			Dispatcher::on_idle(0, Weight::MAX);
+			assert_eq!(dispatched_messages(), vec![1, 2, 3]);
+			assert!(!Queues::<TestRuntime, ()>::contains_key(QueueId::Lane(TEST_LANE)));
+			assert_eq!(
+				QueuedMessages::<TestRuntime, ()>::iter_prefix(QueueId::Lane(TEST_LANE)).count(),
+				0,
+			);
+
+			// and new messages are dispatched immediately
+			dispatch_message(4);
+			assert_eq!(dispatched_messages(), vec![1, 2, 3, 4]);
+		});
+	}
+
+	#[test]
+	fn queued_messages_are_dispatched_using_spare_weight() {
+		run_test(|| {
+			assert_ok!(Dispatcher::pause_dispatch(RuntimeOrigin::root(), TEST_LANE));
+			dispatch_message(1);
+			dispatch_message(2);
+			assert_ok!(Dispatcher::resume_dispatch(RuntimeOrigin::root(), TEST_LANE));
+
+			// there's only enough weight for the first message
+			let spare_weight =
+				DbWeight::get().reads_writes(4, 4).saturating_add(instructions_weight(1));
+			assert_eq!(Dispatcher::on_idle(0, spare_weight), spare_weight);
+			assert_eq!(dispatched_messages(), vec![1]);
+			assert_eq!(lane_queue_len(), 1);
+
+			Dispatcher::on_idle(0, Weight::MAX);
+			assert_eq!(dispatched_messages(), vec![1, 2]);
+		});
+	}
+
+	#[test]
+	fn messages_are_never_dropped() {
+		run_test(|| {
+			assert_ok!(Dispatcher::pause_dispatch(RuntimeOrigin::root(), TEST_LANE));
+			for nonce in 1..=64 {
+				dispatch_message(nonce);
+			}
+			assert_eq!(lane_queue_len(), 64);
+
+			assert_ok!(Dispatcher::resume_dispatch(RuntimeOrigin::root(), TEST_LANE));
+			Dispatcher::on_idle(0, Weight::MAX);
+			assert_eq!(dispatched_messages(), (1..=64).collect::<Vec<_>>());
+		});
+	}
+
+	#[test]
+	fn pause_and_resume_dispatch_works() {
+		run_test(|| {
+			assert_noop!(
+				Dispatcher::pause_dispatch(RuntimeOrigin::signed(1), TEST_LANE),
+				DispatchError::BadOrigin,
+			);
+			assert_noop!(
+				Dispatcher::resume_dispatch(RuntimeOrigin::root(), TEST_LANE),
+				Error::<TestRuntime, ()>::DispatchNotPaused,
+			);
+
+			assert_ok!(Dispatcher::pause_dispatch(RuntimeOrigin::root(), TEST_LANE));
+			assert_noop!(
+				Dispatcher::pause_dispatch(RuntimeOrigin::root(), TEST_LANE),
+				Error::<TestRuntime, ()>::DispatchAlreadyPaused,
+			);
+
+			assert_ok!(Dispatcher::resume_dispatch(RuntimeOrigin::root(), TEST_LANE));
+			assert!(!PausedLanes::<TestRuntime, ()>::contains_key(TEST_LANE));
+		});
+	}
+
+	#[test]
+	fn unfunded_message_is_parked_until_sovereign_account_is_funded() {
+		run_test(|| {
+			dispatch_paid_message(1);
+			assert!(executed_messages().is_empty());
+			assert_eq!(
+				System::events().into_iter().map(|record| record.event).collect::<Vec<_>>(),
+				vec![
+					RuntimeEvent::Dispatcher(Event::InsufficientFeeFunding {
+						lane: TEST_LANE,
+						nonce: 1,
+						required_fee: instructions_weight(2).ref_time() as u128,
+						available_balance: 0,
+					}),
+					RuntimeEvent::Dispatcher(Event::MessageQueued { lane: TEST_LANE, nonce: 1 }),
+				],
+			);
+
+			// following messages of the same origin are parked to preserve the order
+			dispatch_message(2);
+			assert_eq!(origin_queue_len(MultiLocation::parent()), 2);
+			assert_eq!(lane_queue_len(), 0);
+
+			// nothing is dispatched while the sovereign account has no funds
+			Dispatcher::on_idle(0, Weight::MAX);
+			assert!(executed_messages().is_empty());
+
+			// once the sovereign account is funded, messages are dispatched in order
+			Balances::make_free_balance_be(&SOVEREIGN_ACCOUNT, 1_000);
+			Dispatcher::on_idle(0, Weight::MAX);
+			assert_eq!(dispatched_messages(), vec![1, 2]);
+			assert!(!Queues::<TestRuntime, ()>::contains_key(QueueId::Origin(
+				MultiLocation::parent().into()
+			)));
+		});
+	}
+
+	#[test]
+	fn unfunded_message_does_not_block_other_origins() {
+		run_test(|| {
+			dispatch_paid_message(1);
+			assert_eq!(origin_queue_len(MultiLocation::parent()), 1);
+
+			// message of other origin is dispatched immediately
+			Balances::make_free_balance_be(&SIBLING_SOVEREIGN_ACCOUNT, 1_000);
+			dispatch_paid_message_from(sibling(), 2);
+			assert_eq!(dispatched_messages(), vec![2]);
+			assert_eq!(lane_queue_len(), 0);
+		});
+	}
+
+	#[test]
+	fn unfunded_message_of_resumed_lane_is_moved_to_origin_queue() {
+		run_test(|| {
+			assert_ok!(Dispatcher::pause_dispatch(RuntimeOrigin::root(), TEST_LANE));
+			Balances::make_free_balance_be(&SIBLING_SOVEREIGN_ACCOUNT, 1_000);
+			dispatch_paid_message(1);
+			dispatch_paid_message_from(sibling(), 2);
+			assert_eq!(lane_queue_len(), 2);
+
+			// unfunded message is parked and the lane queue is drained
+			assert_ok!(Dispatcher::resume_dispatch(RuntimeOrigin::root(), TEST_LANE));
+			Dispatcher::on_idle(0, Weight::MAX);
+			assert_eq!(dispatched_messages(), vec![2]);
+			assert_eq!(lane_queue_len(), 0);
+			assert_eq!(origin_queue_len(MultiLocation::parent()), 1);
+			assert!(!Dispatcher::must_queue(TEST_LANE));
+
+			// and is dispatched once its origin is funded
+			Balances::make_free_balance_be(&SOVEREIGN_ACCOUNT, 1_000);
+			Dispatcher::on_idle(0, Weight::MAX);
+			assert_eq!(dispatched_messages(), vec![2, 1]);
+		});
+	}
+
+	#[test]
+	fn funded_message_is_dispatched_immediately() {
+		run_test(|| {
+			Balances::make_free_balance_be(&SOVEREIGN_ACCOUNT, 1_000);
+			dispatch_paid_message(1);
+			assert_eq!(dispatched_messages(), vec![1]);
+		});
+	}
+}
@@ -0,0 +1,191 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as pallet_xcm_bridge_dispatcher;
+
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, ConstU64},
+	weights::{IdentityFee, RuntimeDbWeight, Weight},
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header as SubstrateHeader,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use xcm::latest::prelude::*;
+
+pub type AccountId = u64;
+pub type Balance = u64;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+
+/// Sovereign account of the parent chain.
+pub const SOVEREIGN_ACCOUNT: AccountId = 1_000;
+
+/// Sovereign account of the sibling chain.
+pub const SIBLING_SOVEREIGN_ACCOUNT: AccountId = 1_001;
+
+frame_support::construct_runtime! {
+	pub enum TestRuntime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Event<T>},
+		Dispatcher: pallet_xcm_bridge_dispatcher::{Pallet, Call, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub const DbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 2 };
+	pub const UnitWeightCost: Weight = Weight::from_parts(10, 0);
+}
+
+impl frame_system::Config for TestRuntime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Index = u64;
+	type RuntimeCall = RuntimeCall;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = SubstrateHeader;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = DbWeight;
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for TestRuntime {
+	type MaxLocks = ();
+	type Balance = Balance;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = frame_system::Pallet<TestRuntime>;
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = ();
+}
+
+impl pallet_xcm_bridge_dispatcher::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type XcmExecutor = TestXcmExecutor;
+	type XcmWeigher = xcm_builder::FixedWeightBounds<UnitWeightCost, RuntimeCall, ConstU32<100>>;
+	type WeightCredit = ();
+	type FeeFundingCheck = pallet_xcm_bridge_dispatcher::SovereignAccountFeeFunding<
+		AccountId,
+		ParentAccountIdConverter,
+		Balances,
+		IdentityFee<Balance>,
+	>;
+	type PauseOrigin = EnsureRoot<AccountId>;
+}
+
+/// Returns location of the sibling chain.
+pub fn sibling() -> MultiLocation {
+	MultiLocation::new(1, X1(Parachain(1)))
+}
+
+/// Converts parent and sibling locations into their sovereign accounts.
+pub struct ParentAccountIdConverter;
+
+impl xcm_executor::traits::Convert<MultiLocation, AccountId> for ParentAccountIdConverter {
+	fn convert(location: MultiLocation) -> Result<AccountId, MultiLocation> {
+		if location == MultiLocation::parent() {
+			Ok(SOVEREIGN_ACCOUNT)
+		} else if location == sibling() {
+			Ok(SIBLING_SOVEREIGN_ACCOUNT)
+		} else {
+			Err(location)
+		}
+	}
+}
+
+/// Returns weight of the XCM program with given number of instructions.
+pub fn instructions_weight(instructions: u64) -> Weight {
+	UnitWeightCost::get().saturating_mul(instructions)
+}
+
+/// Returns hashes of all executed messages.
+pub fn executed_messages() -> Vec<XcmHash> {
+	frame_support::storage::unhashed::get_or_default(b":executed-messages:")
+}
+
+/// Prepared XCM program of the test executor.
+pub struct TestPreparedMessage(Xcm<RuntimeCall>);
+
+impl PreparedMessage for TestPreparedMessage {
+	fn weight_of(&self) -> Weight {
+		instructions_weight(self.0.len() as u64)
+	}
+}
+
+/// XCM executor that remembers all executed messages.
+pub struct TestXcmExecutor;
+
+impl ExecuteXcm<RuntimeCall> for TestXcmExecutor {
+	type Prepared = TestPreparedMessage;
+
+	fn prepare(message: Xcm<RuntimeCall>) -> Result<Self::Prepared, Xcm<RuntimeCall>> {
+		Ok(TestPreparedMessage(message))
+	}
+
+	fn execute(
+		_origin: impl Into<MultiLocation>,
+		pre: Self::Prepared,
+		hash: XcmHash,
+		_weight_credit: Weight,
+	) -> Outcome {
+		let mut executed = executed_messages();
+		executed.push(hash);
+		frame_support::storage::unhashed::put(b":executed-messages:", &executed);
+		Outcome::Complete(pre.weight_of())
+	}
+
+	fn charge_fees(_location: impl Into<MultiLocation>, _fees: MultiAssets) -> XcmResult {
+		Ok(())
+	}
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	let t = frame_system::GenesisConfig::default().build_storage::<TestRuntime>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		System::reset_events();
+		test()
+	})
+}
@@ -0,0 +1,109 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the XCM bridge hub pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::*;
+
+use frame_benchmarking::benchmarks_instance_pallet;
+use frame_support::{
+	traits::{EnsureOrigin, Get},
+	BoundedVec,
+};
+use sp_std::vec::Vec;
+
+/// Returns origin of the bridge owner, that has enough funds to reserve the bridge deposit.
+fn bridge_owner_origin<T: Config<I>, I: 'static>() -> T::RuntimeOrigin {
+	let origin = T::OpenBridgeOrigin::try_successful_origin()
+		.expect("benchmarks require successful bridge origin");
+	let location = T::OpenBridgeOrigin::ensure_origin(origin.clone())
+		.expect("successful origin is converted into location; qed");
+	let account = T::BridgeOriginAccountIdConverter::convert_ref(location)
+		.expect("benchmarks require convertible bridge origin");
+	let balance = T::BridgeDeposit::get()
+		.saturating_mul(2u32.into())
+		.saturating_add(T::Currency::minimum_balance());
+	T::Currency::make_free_balance_be(&account, balance);
+	origin
+}
+
+/// Returns universal location of the bridge destination.
+fn bridge_destination<T: Config<I>, I: 'static>() -> Box<VersionedInteriorMultiLocation> {
+	Box::new(X2(GlobalConsensus(T::BridgedNetwork::get()), Parachain(42)).into())
+}
+
+/// Returns lane of the bridge, opened in benchmarks.
+fn bridge_lane() -> LaneId {
+	LaneId(u32::MAX.to_le_bytes())
+}
+
+/// Fill `BridgeLanes` with `count` dummy lanes.
+fn fill_bridge_lanes<T: Config<I>, I: 'static>(count: u32) {
+	let lanes = (0..count).map(|i| LaneId(i.to_le_bytes())).collect::<Vec<_>>();
+	BridgeLanes::<T, I>::put(BoundedVec::try_from(lanes).expect("count is within the bound; qed"));
+}
+
+benchmarks_instance_pallet! {
+	// Benchmark `open_bridge` call when the `BridgeLanes` is almost full.
+	open_bridge {
+		fill_bridge_lanes::<T, I>(T::MaxBridges::get().saturating_sub(1));
+		let origin = bridge_owner_origin::<T, I>();
+	}: _<T::RuntimeOrigin>(origin, bridge_destination::<T, I>(), bridge_lane())
+	verify {
+		assert_eq!(BridgeLanes::<T, I>::decode_len(), Some(T::MaxBridges::get() as usize));
+	}
+
+	// Benchmark `suspend_bridge` call.
+	suspend_bridge {
+		let origin = bridge_owner_origin::<T, I>();
+		crate::Pallet::<T, I>::open_bridge(
+			origin.clone(),
+			bridge_destination::<T, I>(),
+			bridge_lane(),
+		)
+		.expect("failed to open bridge in benchmarks");
+	}: _<T::RuntimeOrigin>(origin, bridge_lane())
+
+	// Benchmark `resume_bridge` call.
+	resume_bridge {
+		let origin = bridge_owner_origin::<T, I>();
+		crate::Pallet::<T, I>::open_bridge(
+			origin.clone(),
+			bridge_destination::<T, I>(),
+			bridge_lane(),
+		)
+		.expect("failed to open bridge in benchmarks");
+		crate::Pallet::<T, I>::suspend_bridge(origin.clone(), bridge_lane())
+			.expect("failed to suspend bridge in benchmarks");
+	}: _<T::RuntimeOrigin>(origin, bridge_lane())
+
+	// Benchmark `close_bridge` call when the `BridgeLanes` is full.
+	close_bridge {
+		fill_bridge_lanes::<T, I>(T::MaxBridges::get().saturating_sub(1));
+		let origin = bridge_owner_origin::<T, I>();
+		crate::Pallet::<T, I>::open_bridge(
+			origin.clone(),
+			bridge_destination::<T, I>(),
+			bridge_lane(),
+		)
+		.expect("failed to open bridge in benchmarks");
+	}: _<T::RuntimeOrigin>(origin, bridge_lane())
+	verify {
+		assert_eq!(BridgeLanes::<T, I>::decode_len(), Some(T::MaxBridges::get() as usize - 1));
+	}
+}
@@ -0,0 +1,1232 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Module that allows remote locations to open and close bridges between two consensus systems.
+//!
+//! Every bridge connects two universal locations: the bridge origin location, which is
+//! within this consensus system, and the bridge destination location, which is within the
+//! bridged consensus system. The lane that is used to deliver messages between these locations
+//! is selected by the bridge owner when the bridge is opened. Owners of both bridge ends must
+//! agree on the lane, so the bridge hub at the other side of the bridge uses the same lane when
+//! the bridge is opened there. Lanes are not derived from locations, because lane identifiers
+//! are too short to resist collisions and anyone could squat the lane of a future bridge.
+//!
+//! The bridge owner (the bridge origin location) reserves a deposit when the bridge is opened.
+//! The deposit is returned when the bridge is closed. While the bridge is opened, its lane is
+//! reported to the messages pallet through the [`DynamicLanes`] implementation, so messages may
+//! be sent over it. Suspended bridges are not accepting new messages, but messages that are
+//! already queued are still delivered and pruned. Lanes of closed bridges are no longer
+//! reported to the messages pallet, so they don't occupy the `MaxBridges` slots. Messages, that
+//! have been queued before the bridge has been closed, are still delivered, but they are not
+//! pruned until the bridge is reopened.
+//!
+//! The pallet also watches the number of queued messages at every bridge lane. If there are more
+//! than `CongestedQueueSize` queued messages, the bridge is considered congested and its delivery
+//! fee factor grows exponentially every block. Once the queue is drained, the factor decays back
+//! to its minimal value. The bridge status is reported to the sending-side router (usually a
+//! pallet at the bridge origin chain), so it may charge more and slow down senders. To avoid
+//! sending reports every block, the status is only reported when the bridge becomes congested or
+//! uncongested, or when the delivery fee factor crosses the reporting threshold.
+//!
+//! While bridged XCM program is dispatched, the pallet may be used to remember queries that the
+//! program is expecting responses to (see [`PendingBridgedQueries`]). Responses are then sent
+//! back over the lane that has delivered the program. Queries are forgotten after the program is
+//! dispatched. Queries that have been noted at previous blocks are considered expired and are
+//! pruned lazily. There may be at most `MaxPendingQueriesPerDestination` pending queries with
+//! the same response destination.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+use bp_messages::{source_chain::DynamicLanes, LaneId, MessageNonce};
+use bridge_runtime_common::messages_xcm_extension::PendingBridgedQueries;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{Currency, ReservableCurrency},
+	RuntimeDebug,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	FixedU128,
+};
+use sp_std::{boxed::Box, marker::PhantomData};
+use xcm::{latest::prelude::*, VersionedInteriorMultiLocation};
+use xcm_executor::traits::Convert;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+pub mod weights;
+
+mod mock;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "runtime::bridge-xcm";
+
+/// Minimal delivery fee factor. The factor never goes below this value.
+pub const MINIMAL_DELIVERY_FEE_FACTOR: FixedU128 = FixedU128::from_u32(1);
+
+/// Multiplier of the delivery fee factor. The factor is multiplied by this value every block
+/// while the bridge is congested and divided by this value every block after the congestion is
+/// resolved.
+pub const EXPONENTIAL_FEE_BASE: FixedU128 = FixedU128::from_rational(105, 100);
+
+/// Delivery fee factor change, that is reported to the sending-side router. The new factor is
+/// reported when it is this value times larger or smaller than the last reported factor.
+pub const DELIVERY_FEE_FACTOR_REPORT_THRESHOLD: FixedU128 = FixedU128::from_u32(2);
+
+/// Balance type of given pallet configuration.
+pub type BalanceOf<T, I> =
+	<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+/// Bridge metadata of given pallet configuration.
+pub type BridgeOf<T, I> = Bridge<<T as frame_system::Config>::AccountId, BalanceOf<T, I>>;
+
+/// State of the bridge.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum BridgeState {
+	/// Bridge is opened and accepts new messages.
+	Opened,
+	/// Bridge is suspended by its owner. It doesn't accept new messages, but may be resumed.
+	Suspended,
+	/// Bridge is closed. It doesn't accept new messages. Messages that have been queued before
+	/// the bridge has been closed, are still delivered.
+	Closed,
+}
+
+/// Bridge status that is reported to the sending-side router.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct BridgeStatus {
+	/// Whether the bridge is congested, i.e. has too many queued messages.
+	pub is_congested: bool,
+	/// Factor that the base delivery fee is multiplied by.
+	pub delivery_fee_factor: FixedU128,
+}
+
+impl Default for BridgeStatus {
+	fn default() -> Self {
+		BridgeStatus { is_congested: false, delivery_fee_factor: MINIMAL_DELIVERY_FEE_FACTOR }
+	}
+}
+
+impl BridgeStatus {
+	/// Returns bridge status after single block with given number of queued messages.
+	pub fn next(&self, queued_messages: MessageNonce, congested_queue_size: MessageNonce) -> Self {
+		let is_congested = queued_messages > congested_queue_size;
+		let delivery_fee_factor = if is_congested {
+			self.delivery_fee_factor.saturating_mul(EXPONENTIAL_FEE_BASE)
+		} else {
+			(self.delivery_fee_factor / EXPONENTIAL_FEE_BASE).max(MINIMAL_DELIVERY_FEE_FACTOR)
+		};
+		BridgeStatus { is_congested, delivery_fee_factor }
+	}
+
+	/// Returns true if this status needs to be reported to the sending-side router, given the
+	/// last reported status.
+	pub fn needs_report(&self, reported: &BridgeStatus) -> bool {
+		if self.is_congested != reported.is_congested {
+			return true
+		}
+
+		// the router needs to know that the factor is back to its minimal value
+		if self.delivery_fee_factor == MINIMAL_DELIVERY_FEE_FACTOR {
+			return reported.delivery_fee_factor != MINIMAL_DELIVERY_FEE_FACTOR
+		}
+
+		let threshold = DELIVERY_FEE_FACTOR_REPORT_THRESHOLD;
+		self.delivery_fee_factor >= reported.delivery_fee_factor.saturating_mul(threshold) ||
+			self.delivery_fee_factor.saturating_mul(threshold) <= reported.delivery_fee_factor
+	}
+}
+
+/// Reporter of the bridge status to the sending-side router.
+pub trait ReportBridgeStatus {
+	/// Report new status of the bridge, owned by given location.
+	///
+	/// Returns weight that has been spent on the report.
+	fn report_bridge_status(
+		bridge_origin_relative_location: &MultiLocation,
+		lane_id: LaneId,
+		status: BridgeStatus,
+	) -> Weight;
+}
+
+impl ReportBridgeStatus for () {
+	fn report_bridge_status(
+		_bridge_origin_relative_location: &MultiLocation,
+		_lane_id: LaneId,
+		_status: BridgeStatus,
+	) -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Bridge metadata.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+pub struct Bridge<AccountId, Balance> {
+	/// Location of the bridge owner, relative to this chain.
+	pub bridge_origin_relative_location: Box<MultiLocation>,
+	/// Universal location of the bridge owner.
+	pub bridge_origin_universal_location: Box<InteriorMultiLocation>,
+	/// Universal location of the bridge destination within the bridged consensus system.
+	pub bridge_destination_universal_location: Box<InteriorMultiLocation>,
+	/// Current bridge state.
+	pub state: BridgeState,
+	/// Account that has reserved the bridge deposit.
+	pub bridge_owner_account: AccountId,
+	/// Reserved deposit. It is returned to the owner when the bridge is closed.
+	pub deposit: Balance,
+	/// Current status of the bridge.
+	pub status: BridgeStatus,
+	/// Last status of the bridge, that has been reported to the sending-side router.
+	pub reported_status: BridgeStatus,
+}
+
+/// Universal locations of the bridge ends.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct BridgeLocations {
+	/// Universal location of the bridge owner.
+	pub bridge_origin_universal_location: InteriorMultiLocation,
+	/// Universal location of the bridge destination.
+	pub bridge_destination_universal_location: InteriorMultiLocation,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>:
+		frame_system::Config + pallet_bridge_messages::Config<Self::BridgeMessagesPalletInstance>
+	{
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Benchmarks results from runtime we're plugged into.
+		type WeightInfo: WeightInfo;
+
+		/// Universal location of this chain.
+		type UniversalLocation: Get<InteriorMultiLocation>;
+		/// Network identifier of the bridged consensus system.
+		#[pallet::constant]
+		type BridgedNetwork: Get<NetworkId>;
+		/// Instance of the messages pallet that is used to deliver bridge messages.
+		type BridgeMessagesPalletInstance: 'static;
+
+		/// Origin of the location that may open, suspend, resume and close bridges. The
+		/// successful result is the location of the bridge owner, relative to this chain.
+		type OpenBridgeOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = MultiLocation>;
+		/// Converter of the bridge owner location into the account, that reserves the deposit.
+		type BridgeOriginAccountIdConverter: Convert<MultiLocation, Self::AccountId>;
+
+		/// Currency that is used to reserve bridge deposits.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Amount that is reserved from the bridge owner account when the bridge is opened.
+		#[pallet::constant]
+		type BridgeDeposit: Get<BalanceOf<Self, I>>;
+		/// Maximal number of opened and suspended bridges that may exist at the same time.
+		#[pallet::constant]
+		type MaxBridges: Get<u32>;
+
+		/// Number of queued messages at the bridge lane, after which the bridge is considered
+		/// congested.
+		#[pallet::constant]
+		type CongestedQueueSize: Get<MessageNonce>;
+		/// Reporter of the bridge status to the sending-side router.
+		type ReportBridgeStatus: ReportBridgeStatus;
+
+		/// Maximal number of pending queries with the same response destination. Responses to
+		/// other queries are routed as if they weren't sent by the bridged XCM program.
+		#[pallet::constant]
+		type MaxPendingQueriesPerDestination: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut weight = db_weight.reads(1);
+			for lane_id in BridgeLanes::<T, I>::get() {
+				weight.saturating_accrue(db_weight.reads(1));
+				weight.saturating_accrue(Self::update_bridge_status(lane_id));
+			}
+			weight
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Open bridge between the origin location and given location within the bridged
+		/// consensus system, using given lane.
+		///
+		/// The bridge deposit is reserved from the account of the origin location. The bridge
+		/// also needs to be opened at the other side (using the same lane) to deliver messages in
+		/// both directions.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::open_bridge())]
+		pub fn open_bridge(
+			origin: OriginFor<T>,
+			bridge_destination_universal_location: Box<VersionedInteriorMultiLocation>,
+			lane_id: LaneId,
+		) -> DispatchResult {
+			let bridge_origin_relative_location = T::OpenBridgeOrigin::ensure_origin(origin)?;
+			let locations = Self::bridge_locations(
+				bridge_origin_relative_location,
+				*bridge_destination_universal_location,
+			)?;
+			let bridge_owner_account =
+				T::BridgeOriginAccountIdConverter::convert_ref(bridge_origin_relative_location)
+					.map_err(|_| Error::<T, I>::InvalidBridgeOriginAccount)?;
+
+			// lane of the new bridge must not collide with static lanes and other bridges
+			let static_lanes = <T as pallet_bridge_messages::Config<
+				T::BridgeMessagesPalletInstance,
+			>>::ActiveOutboundLanes::get();
+			ensure!(!static_lanes.contains(&lane_id), Error::<T, I>::LaneIdCollision);
+			// status of the closed bridge is preserved, because it may still have queued messages
+			let reopened_bridge_statuses = match Bridges::<T, I>::get(lane_id) {
+				Some(bridge) => {
+					ensure!(
+						*bridge.bridge_origin_universal_location ==
+							locations.bridge_origin_universal_location &&
+							*bridge.bridge_destination_universal_location ==
+								locations.bridge_destination_universal_location,
+						Error::<T, I>::LaneIdCollision,
+					);
+					ensure!(
+						bridge.state == BridgeState::Closed,
+						Error::<T, I>::BridgeAlreadyExists
+					);
+					Some((bridge.status, bridge.reported_status))
+				},
+				None => None,
+			};
+
+			let deposit = T::BridgeDeposit::get();
+			T::Currency::reserve(&bridge_owner_account, deposit)
+				.map_err(|_| Error::<T, I>::FailedToReserveBridgeDeposit)?;
+
+			BridgeLanes::<T, I>::try_append(lane_id).map_err(|_| Error::<T, I>::TooManyBridges)?;
+			let (status, reported_status) = reopened_bridge_statuses.unwrap_or_default();
+			Bridges::<T, I>::insert(
+				lane_id,
+				Bridge {
+					bridge_origin_relative_location: Box::new(bridge_origin_relative_location),
+					bridge_origin_universal_location: Box::new(
+						locations.bridge_origin_universal_location,
+					),
+					bridge_destination_universal_location: Box::new(
+						locations.bridge_destination_universal_location,
+					),
+					state: BridgeState::Opened,
+					bridge_owner_account,
+					deposit,
+					status,
+					reported_status,
+				},
+			);
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Bridge {:?} between {:?} and {:?} has been opened",
+				lane_id,
+				locations.bridge_origin_universal_location,
+				locations.bridge_destination_universal_location,
+			);
+
+			Self::deposit_event(Event::BridgeOpened {
+				lane_id,
+				bridge_origin_universal_location: Box::new(
+					locations.bridge_origin_universal_location,
+				),
+				bridge_destination_universal_location: Box::new(
+					locations.bridge_destination_universal_location,
+				),
+				deposit,
+			});
+			Ok(())
+		}
+
+		/// Suspend the opened bridge. Suspended bridge doesn't accept new messages.
+		///
+		/// May only be called by the bridge owner.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::suspend_bridge())]
+		pub fn suspend_bridge(origin: OriginFor<T>, lane_id: LaneId) -> DispatchResult {
+			Self::change_bridge_state(
+				origin,
+				lane_id,
+				BridgeState::Opened,
+				BridgeState::Suspended,
+			)?;
+			Ok(())
+		}
+
+		/// Resume the suspended bridge.
+		///
+		/// May only be called by the bridge owner.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::resume_bridge())]
+		pub fn resume_bridge(origin: OriginFor<T>, lane_id: LaneId) -> DispatchResult {
+			Self::change_bridge_state(
+				origin,
+				lane_id,
+				BridgeState::Suspended,
+				BridgeState::Opened,
+			)?;
+			Ok(())
+		}
+
+		/// Close the opened or suspended bridge and return the deposit to the bridge owner.
+		///
+		/// Closed bridge doesn't accept new messages and its lane is removed from the
+		/// `BridgeLanes`. Messages that have been queued before are still delivered to the bridged
+		/// chain. May only be called by the bridge owner.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::close_bridge())]
+		pub fn close_bridge(origin: OriginFor<T>, lane_id: LaneId) -> DispatchResult {
+			let bridge_origin_relative_location = T::OpenBridgeOrigin::ensure_origin(origin)?;
+			let bridge_origin_universal_location =
+				Self::bridge_origin_universal_location(bridge_origin_relative_location)?;
+
+			let deposit = Bridges::<T, I>::try_mutate(lane_id, |bridge| {
+				let bridge = bridge.as_mut().ok_or(Error::<T, I>::UnknownBridge)?;
+				Self::ensure_bridge_owner(bridge, &bridge_origin_universal_location)?;
+				ensure!(bridge.state != BridgeState::Closed, Error::<T, I>::InvalidBridgeState);
+
+				let deposit = sp_std::mem::take(&mut bridge.deposit);
+				let not_returned = T::Currency::unreserve(&bridge.bridge_owner_account, deposit);
+				if !not_returned.is_zero() {
+					log::warn!(
+						target: LOG_TARGET,
+						"Failed to return {:?} of {:?} bridge {:?} deposit to {:?}",
+						not_returned,
+						deposit,
+						lane_id,
+						bridge.bridge_owner_account,
+					);
+				}
+				bridge.state = BridgeState::Closed;
+
+				Ok::<_, Error<T, I>>(deposit)
+			})?;
+			BridgeLanes::<T, I>::mutate(|lanes| lanes.retain(|lane| *lane != lane_id));
+
+			log::trace!(target: LOG_TARGET, "Bridge {:?} has been closed", lane_id);
+
+			Self::deposit_event(Event::BridgeClosed { lane_id, returned_deposit: deposit });
+			Ok(())
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Returns universal locations of the bridge ends.
+		pub fn bridge_locations(
+			bridge_origin_relative_location: MultiLocation,
+			bridge_destination_universal_location: VersionedInteriorMultiLocation,
+		) -> Result<BridgeLocations, Error<T, I>> {
+			let bridge_destination_universal_location: InteriorMultiLocation =
+				bridge_destination_universal_location
+					.try_into()
+					.map_err(|_| Error::<T, I>::UnsupportedXcmVersion)?;
+			let bridge_origin_universal_location =
+				Self::bridge_origin_universal_location(bridge_origin_relative_location)?;
+
+			// the destination must be within the bridged consensus system
+			ensure!(
+				bridge_destination_universal_location.global_consensus() ==
+					Ok(T::BridgedNetwork::get()),
+				Error::<T, I>::InvalidBridgeDestination,
+			);
+
+			Ok(BridgeLocations {
+				bridge_origin_universal_location,
+				bridge_destination_universal_location,
+			})
+		}
+
+		/// Returns universal location of the bridge owner.
+		fn bridge_origin_universal_location(
+			bridge_origin_relative_location: MultiLocation,
+		) -> Result<InteriorMultiLocation, Error<T, I>> {
+			let bridge_origin_universal_location = T::UniversalLocation::get()
+				.within_global(bridge_origin_relative_location)
+				.map_err(|_| Error::<T, I>::InvalidBridgeOrigin)?;
+
+			// the bridge origin must be within this consensus system
+			let this_network = T::UniversalLocation::get()
+				.global_consensus()
+				.map_err(|_| Error::<T, I>::InvalidBridgeOrigin)?;
+			ensure!(
+				bridge_origin_universal_location.global_consensus() == Ok(this_network),
+				Error::<T, I>::InvalidBridgeOrigin,
+			);
+
+			Ok(bridge_origin_universal_location)
+		}
+
+		/// Update status of the opened or suspended bridge with given lane and report it to the
+		/// sending-side router if the change is significant.
+		///
+		/// Returns weight that has been spent on the update. Reading the bridge itself is not
+		/// included.
+		fn update_bridge_status(lane_id: LaneId) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut bridge = match Bridges::<T, I>::get(lane_id) {
+				Some(bridge) if bridge.state != BridgeState::Closed => bridge,
+				_ => return Weight::zero(),
+			};
+
+			let lane_data = pallet_bridge_messages::Pallet::<
+				T,
+				T::BridgeMessagesPalletInstance,
+			>::outbound_lane_data(lane_id);
+			let queued_messages =
+				lane_data.latest_generated_nonce.saturating_sub(lane_data.latest_received_nonce);
+			let mut weight = db_weight.reads(1);
+			let new_status = bridge.status.next(queued_messages, T::CongestedQueueSize::get());
+			if new_status == bridge.status {
+				return weight
+			}
+
+			if new_status.is_congested != bridge.status.is_congested {
+				log::trace!(
+					target: LOG_TARGET,
+					"Bridge {:?} congestion status has changed: is_congested = {}, queued = {}",
+					lane_id,
+					new_status.is_congested,
+					queued_messages,
+				);
+			}
+
+			bridge.status = new_status;
+			if new_status.needs_report(&bridge.reported_status) {
+				bridge.reported_status = new_status;
+				weight.saturating_accrue(T::ReportBridgeStatus::report_bridge_status(
+					&bridge.bridge_origin_relative_location,
+					lane_id,
+					new_status,
+				));
+			}
+			Bridges::<T, I>::insert(lane_id, bridge);
+			weight.saturating_add(db_weight.writes(1))
+		}
+
+		/// Change state of the bridge, owned by the origin.
+		fn change_bridge_state(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			expected_state: BridgeState,
+			new_state: BridgeState,
+		) -> DispatchResult {
+			let bridge_origin_relative_location = T::OpenBridgeOrigin::ensure_origin(origin)?;
+			let bridge_origin_universal_location =
+				Self::bridge_origin_universal_location(bridge_origin_relative_location)?;
+
+			Bridges::<T, I>::try_mutate(lane_id, |bridge| {
+				let bridge = bridge.as_mut().ok_or(Error::<T, I>::UnknownBridge)?;
+				Self::ensure_bridge_owner(bridge, &bridge_origin_universal_location)?;
+				ensure!(bridge.state == expected_state, Error::<T, I>::InvalidBridgeState);
+				bridge.state = new_state;
+				Ok::<_, Error<T, I>>(())
+			})?;
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Bridge {:?} state has been changed to {:?}",
+				lane_id,
+				new_state,
+			);
+
+			Self::deposit_event(Event::BridgeStateChanged { lane_id, state: new_state });
+			Ok(())
+		}
+
+		/// Ensure that the bridge is owned by the origin.
+		fn ensure_bridge_owner(
+			bridge: &BridgeOf<T, I>,
+			bridge_origin_universal_location: &InteriorMultiLocation,
+		) -> Result<(), Error<T, I>> {
+			ensure!(
+				*bridge.bridge_origin_universal_location == *bridge_origin_universal_location,
+				Error::<T, I>::UnknownBridge,
+			);
+			Ok(())
+		}
+	}
+
+	/// All known bridges, mapped by their lanes.
+	#[pallet::storage]
+	pub type Bridges<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, LaneId, BridgeOf<T, I>, OptionQuery>;
+
+	/// Lanes of all opened and suspended bridges.
+	#[pallet::storage]
+	pub type BridgeLanes<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<LaneId, T::MaxBridges>, ValueQuery>;
+
+	/// Queries of bridged XCM programs that are currently dispatched, mapped by the response
+	/// destination and the query identifier. The value is the lane that must be used to send the
+	/// response and the number of block where the query has been noted.
+	#[pallet::storage]
+	pub type PendingQueries<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		MultiLocation,
+		Twox64Concat,
+		QueryId,
+		(LaneId, T::BlockNumber),
+		OptionQuery,
+	>;
+
+	/// Number of `PendingQueries` entries, mapped by the response destination.
+	#[pallet::storage]
+	pub type PendingQueriesCount<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, MultiLocation, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// Bridge has been opened.
+		BridgeOpened {
+			/// Bridge lane.
+			lane_id: LaneId,
+			/// Universal location of the bridge owner.
+			bridge_origin_universal_location: Box<InteriorMultiLocation>,
+			/// Universal location of the bridge destination.
+			bridge_destination_universal_location: Box<InteriorMultiLocation>,
+			/// Reserved deposit.
+			deposit: BalanceOf<T, I>,
+		},
+		/// Bridge has been suspended or resumed.
+		BridgeStateChanged {
+			/// Bridge lane.
+			lane_id: LaneId,
+			/// New bridge state.
+			state: BridgeState,
+		},
+		/// Bridge has been closed.
+		BridgeClosed {
+			/// Bridge lane.
+			lane_id: LaneId,
+			/// Deposit that has been returned to the bridge owner.
+			returned_deposit: BalanceOf<T, I>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The bridge origin location is not within this consensus system.
+		InvalidBridgeOrigin,
+		/// The bridge destination location is not within the bridged consensus system.
+		InvalidBridgeDestination,
+		/// The bridge origin location can't be converted into account.
+		InvalidBridgeOriginAccount,
+		/// The XCM version of the destination location is not supported.
+		UnsupportedXcmVersion,
+		/// The lane is already used by other bridge or by the messages pallet.
+		LaneIdCollision,
+		/// The bridge between given locations is already opened or suspended.
+		BridgeAlreadyExists,
+		/// The bridge between given locations is unknown.
+		UnknownBridge,
+		/// The bridge is in the state that doesn't allow this operation.
+		InvalidBridgeState,
+		/// Too many bridges are already registered.
+		TooManyBridges,
+		/// Failed to reserve the bridge deposit.
+		FailedToReserveBridgeDeposit,
+	}
+}
+
+impl<T: Config<I>, I: 'static> DynamicLanes for Pallet<T, I> {
+	fn is_lane_opened(lane: &LaneId) -> bool {
+		Bridges::<T, I>::get(lane)
+			.map(|bridge| bridge.state == BridgeState::Opened)
+			.unwrap_or(false)
+	}
+
+	fn lanes_count() -> u32 {
+		BridgeLanes::<T, I>::decode_len().unwrap_or(0) as u32
+	}
+
+	fn lane_at(index: u32) -> Option<LaneId> {
+		BridgeLanes::<T, I>::get().get(index as usize).copied()
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Remove expired pending queries with given response destination.
+	///
+	/// Returns number of removed queries.
+	fn prune_expired_queries(destination: &MultiLocation, now: T::BlockNumber) -> u32 {
+		// the number of entries is bounded by the `MaxPendingQueriesPerDestination`
+		let expired_queries = PendingQueries::<T, I>::iter_prefix(destination)
+			.filter(|(_, (_, noted_at))| *noted_at != now)
+			.map(|(query_id, _)| query_id)
+			.collect::<sp_std::vec::Vec<_>>();
+		for query_id in &expired_queries {
+			Self::remove_pending_query(destination, *query_id);
+		}
+		expired_queries.len() as u32
+	}
+}
+
+impl<T: Config<I>, I: 'static> PendingBridgedQueries for Pallet<T, I> {
+	fn note_pending_query(lane: LaneId, destination: &MultiLocation, query_id: QueryId) {
+		let now = frame_system::Pallet::<T>::block_number();
+		if PendingQueries::<T, I>::contains_key(destination, query_id) {
+			PendingQueries::<T, I>::insert(destination, query_id, (lane, now));
+			return
+		}
+
+		let max_pending_queries = T::MaxPendingQueriesPerDestination::get();
+		if PendingQueriesCount::<T, I>::get(destination) >= max_pending_queries &&
+			Self::prune_expired_queries(destination, now) == 0
+		{
+			log::warn!(
+				target: LOG_TARGET,
+				"Too many pending queries with response destination {:?}. Response to query {} \
+				won't be sent over lane {:?}",
+				destination,
+				query_id,
+				lane,
+			);
+			return
+		}
+
+		PendingQueries::<T, I>::insert(destination, query_id, (lane, now));
+		PendingQueriesCount::<T, I>::mutate(destination, |count| *count = count.saturating_add(1));
+	}
+
+	fn pending_query_lane(destination: &MultiLocation, query_id: QueryId) -> Option<LaneId> {
+		let now = frame_system::Pallet::<T>::block_number();
+		PendingQueries::<T, I>::get(destination, query_id)
+			.filter(|(_, noted_at)| *noted_at == now)
+			.map(|(lane, _)| lane)
+	}
+
+	fn remove_pending_query(destination: &MultiLocation, query_id: QueryId) {
+		if PendingQueries::<T, I>::take(destination, query_id).is_some() {
+			PendingQueriesCount::<T, I>::mutate_exists(destination, |count| {
+				*count = count.and_then(|count| count.checked_sub(1)).filter(|count| *count != 0);
+			});
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+	use frame_support::{
+		assert_noop, assert_ok,
+		traits::{Get, Hooks},
+		BoundedVec,
+	};
+	use frame_system::{EventRecord, Phase};
+	use sp_runtime::DispatchError;
+
+	fn destination() -> Box<VersionedInteriorMultiLocation> {
+		Box::new(X2(GlobalConsensus(BridgedNetwork::get()), Parachain(1000)).into())
+	}
+
+	fn bridge_lane() -> LaneId {
+		LaneId([0, 0, 0, 1])
+	}
+
+	#[test]
+	fn open_bridge_fails_for_invalid_locations() {
+		run_test(|| {
+			// destination is within our consensus system
+			assert_noop!(
+				XcmOverBridge::open_bridge(
+					RuntimeOrigin::signed(SIBLING_ACCOUNT),
+					Box::new(X2(GlobalConsensus(Rococo), Parachain(1000)).into()),
+					bridge_lane(),
+				),
+				Error::<TestRuntime, ()>::InvalidBridgeDestination,
+			);
+			// origin is not a sibling parachain
+			assert_noop!(
+				XcmOverBridge::open_bridge(RuntimeOrigin::root(), destination(), bridge_lane()),
+				DispatchError::BadOrigin,
+			);
+		});
+	}
+
+	#[test]
+	fn open_bridge_works() {
+		run_test(|| {
+			assert_ok!(XcmOverBridge::open_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				destination(),
+				bridge_lane(),
+			));
+
+			let lane_id = bridge_lane();
+			let bridge = Bridges::<TestRuntime, ()>::get(lane_id).unwrap();
+			assert_eq!(bridge.state, BridgeState::Opened);
+			assert_eq!(bridge.bridge_owner_account, SIBLING_ACCOUNT);
+			assert_eq!(bridge.deposit, BRIDGE_DEPOSIT);
+			assert_eq!(Balances::reserved_balance(SIBLING_ACCOUNT), BRIDGE_DEPOSIT);
+			assert_eq!(BridgeLanes::<TestRuntime, ()>::get().into_inner(), vec![lane_id]);
+			assert!(XcmOverBridge::is_lane_opened(&lane_id));
+			assert_eq!(XcmOverBridge::lanes_count(), 1);
+			assert_eq!(XcmOverBridge::lane_at(0), Some(lane_id));
+			assert_eq!(
+				System::events().last(),
+				Some(&EventRecord {
+					phase: Phase::Initialization,
+					event: RuntimeEvent::XcmOverBridge(Event::BridgeOpened {
+						lane_id,
+						bridge_origin_universal_location: Box::new(X2(
+							GlobalConsensus(Rococo),
+							Parachain(SIBLING_ACCOUNT as u32)
+						)),
+						bridge_destination_universal_location: Box::new(X2(
+							GlobalConsensus(Wococo),
+							Parachain(1000)
+						)),
+						deposit: BRIDGE_DEPOSIT,
+					}),
+					topics: vec![],
+				}),
+			);
+
+			// the same bridge can't be opened twice
+			assert_noop!(
+				XcmOverBridge::open_bridge(
+					RuntimeOrigin::signed(SIBLING_ACCOUNT),
+					destination(),
+					bridge_lane(),
+				),
+				Error::<TestRuntime, ()>::BridgeAlreadyExists,
+			);
+		});
+	}
+
+	#[test]
+	fn open_bridge_fails_if_lane_is_already_used() {
+		run_test(|| {
+			// lane is used by the messages pallet
+			assert_noop!(
+				XcmOverBridge::open_bridge(
+					RuntimeOrigin::signed(SIBLING_ACCOUNT),
+					destination(),
+					ActiveOutboundLanes::get()[0],
+				),
+				Error::<TestRuntime, ()>::LaneIdCollision,
+			);
+
+			// lane is used by other bridge
+			assert_ok!(XcmOverBridge::open_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				destination(),
+				bridge_lane(),
+			));
+			assert_noop!(
+				XcmOverBridge::open_bridge(
+					RuntimeOrigin::signed(SIBLING_ACCOUNT),
+					Box::new(X2(GlobalConsensus(BridgedNetwork::get()), Parachain(2000)).into()),
+					bridge_lane(),
+				),
+				Error::<TestRuntime, ()>::LaneIdCollision,
+			);
+
+			// lane is used by the closed bridge, which may still have queued messages
+			assert_ok!(XcmOverBridge::close_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				bridge_lane(),
+			));
+			assert_noop!(
+				XcmOverBridge::open_bridge(
+					RuntimeOrigin::signed(SIBLING_ACCOUNT),
+					Box::new(X2(GlobalConsensus(BridgedNetwork::get()), Parachain(2000)).into()),
+					bridge_lane(),
+				),
+				Error::<TestRuntime, ()>::LaneIdCollision,
+			);
+		});
+	}
+
+	#[test]
+	fn open_bridge_fails_if_deposit_cant_be_reserved() {
+		run_test(|| {
+			assert_noop!(
+				XcmOverBridge::open_bridge(
+					RuntimeOrigin::signed(POOR_SIBLING_ACCOUNT),
+					destination(),
+					bridge_lane(),
+				),
+				Error::<TestRuntime, ()>::FailedToReserveBridgeDeposit,
+			);
+		});
+	}
+
+	#[test]
+	fn bridge_may_be_suspended_and_resumed() {
+		run_test(|| {
+			assert_ok!(XcmOverBridge::open_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				destination(),
+				bridge_lane(),
+			));
+			let lane_id = bridge_lane();
+
+			// only owner may suspend the bridge
+			assert_noop!(
+				XcmOverBridge::suspend_bridge(
+					RuntimeOrigin::signed(POOR_SIBLING_ACCOUNT),
+					bridge_lane(),
+				),
+				Error::<TestRuntime, ()>::UnknownBridge,
+			);
+
+			assert_ok!(XcmOverBridge::suspend_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				bridge_lane(),
+			));
+			assert!(!XcmOverBridge::is_lane_opened(&lane_id));
+			assert_noop!(
+				XcmOverBridge::suspend_bridge(
+					RuntimeOrigin::signed(SIBLING_ACCOUNT),
+					bridge_lane()
+				),
+				Error::<TestRuntime, ()>::InvalidBridgeState,
+			);
+
+			assert_ok!(XcmOverBridge::resume_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				bridge_lane(),
+			));
+			assert!(XcmOverBridge::is_lane_opened(&lane_id));
+		});
+	}
+
+	#[test]
+	fn close_bridge_works() {
+		run_test(|| {
+			assert_ok!(XcmOverBridge::open_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				destination(),
+				bridge_lane(),
+			));
+			let lane_id = bridge_lane();
+
+			assert_ok!(XcmOverBridge::close_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				bridge_lane(),
+			));
+			let bridge = Bridges::<TestRuntime, ()>::get(lane_id).unwrap();
+			assert_eq!(bridge.state, BridgeState::Closed);
+			assert_eq!(bridge.deposit, 0);
+			assert_eq!(Balances::reserved_balance(SIBLING_ACCOUNT), 0);
+			assert!(!XcmOverBridge::is_lane_opened(&lane_id));
+			// lane of the closed bridge is removed
+			assert_eq!(XcmOverBridge::lanes_count(), 0);
+			assert!(BridgeLanes::<TestRuntime, ()>::get().is_empty());
+
+			// closed bridge can't be resumed or closed again
+			assert_noop!(
+				XcmOverBridge::resume_bridge(RuntimeOrigin::signed(SIBLING_ACCOUNT), bridge_lane()),
+				Error::<TestRuntime, ()>::InvalidBridgeState,
+			);
+			assert_noop!(
+				XcmOverBridge::close_bridge(RuntimeOrigin::signed(SIBLING_ACCOUNT), bridge_lane()),
+				Error::<TestRuntime, ()>::InvalidBridgeState,
+			);
+
+			// but it may be reopened
+			assert_ok!(XcmOverBridge::open_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				destination(),
+				bridge_lane(),
+			));
+			assert!(XcmOverBridge::is_lane_opened(&lane_id));
+			assert_eq!(XcmOverBridge::lanes_count(), 1);
+			assert_eq!(Balances::reserved_balance(SIBLING_ACCOUNT), BRIDGE_DEPOSIT);
+		});
+	}
+
+	#[test]
+	fn closed_bridge_frees_its_slot() {
+		run_test(|| {
+			let destination =
+				|id| Box::new(X2(GlobalConsensus(BridgedNetwork::get()), Parachain(id)).into());
+			let lane = |id: u32| LaneId((id + 1).to_le_bytes());
+			let max_bridges: u32 = <TestRuntime as Config>::MaxBridges::get();
+			for id in 0..max_bridges {
+				assert_ok!(XcmOverBridge::open_bridge(
+					RuntimeOrigin::signed(SIBLING_ACCOUNT),
+					destination(id),
+					lane(id),
+				));
+			}
+			assert_noop!(
+				XcmOverBridge::open_bridge(
+					RuntimeOrigin::signed(SIBLING_ACCOUNT),
+					destination(max_bridges),
+					lane(max_bridges),
+				),
+				Error::<TestRuntime, ()>::TooManyBridges,
+			);
+
+			assert_ok!(XcmOverBridge::close_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				lane(0),
+			));
+			assert_ok!(XcmOverBridge::open_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				destination(max_bridges),
+				lane(max_bridges),
+			));
+			assert_eq!(XcmOverBridge::lanes_count(), max_bridges);
+		});
+	}
+
+	#[test]
+	fn delivery_fee_factor_grows_and_decays_exponentially() {
+		let status = BridgeStatus::default();
+
+		// factor grows while the bridge is congested
+		let status = status.next(CONGESTED_QUEUE_SIZE + 1, CONGESTED_QUEUE_SIZE);
+		assert!(status.is_congested);
+		assert_eq!(status.delivery_fee_factor, EXPONENTIAL_FEE_BASE);
+		let status = status.next(CONGESTED_QUEUE_SIZE + 1, CONGESTED_QUEUE_SIZE);
+		assert!(status.is_congested);
+		assert_eq!(status.delivery_fee_factor, EXPONENTIAL_FEE_BASE * EXPONENTIAL_FEE_BASE);
+
+		// and decays once the queue is drained
+		let status = status.next(CONGESTED_QUEUE_SIZE, CONGESTED_QUEUE_SIZE);
+		assert!(!status.is_congested);
+		assert_eq!(status.delivery_fee_factor, EXPONENTIAL_FEE_BASE);
+		let status = status.next(0, CONGESTED_QUEUE_SIZE);
+		assert_eq!(status, BridgeStatus::default());
+
+		// but never goes below the minimal value
+		let status = status.next(0, CONGESTED_QUEUE_SIZE);
+		assert_eq!(status, BridgeStatus::default());
+	}
+
+	#[test]
+	fn bridge_status_is_reported_to_the_router() {
+		run_test(|| {
+			assert_ok!(XcmOverBridge::open_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				destination(),
+				bridge_lane(),
+			));
+			let lane_id = bridge_lane();
+			let set_queued_messages = |queued_messages| {
+				pallet_bridge_messages::OutboundLanes::<TestRuntime, ()>::insert(
+					lane_id,
+					bp_messages::OutboundLaneData {
+						oldest_unpruned_nonce: 1,
+						latest_received_nonce: 0,
+						latest_generated_nonce: queued_messages,
+					},
+				);
+			};
+
+			let db_weight = DbWeight::get();
+
+			// nothing is reported while the bridge is not congested
+			set_queued_messages(CONGESTED_QUEUE_SIZE);
+			assert_eq!(XcmOverBridge::on_initialize(1), db_weight.reads(3));
+			assert_eq!(TestBridgeStatusReporter::reported(), vec![]);
+
+			// congestion is reported once, when the bridge becomes congested
+			set_queued_messages(CONGESTED_QUEUE_SIZE + 1);
+			assert_eq!(
+				XcmOverBridge::on_initialize(2),
+				db_weight.reads_writes(3, 1).saturating_add(REPORT_BRIDGE_STATUS_WEIGHT),
+			);
+			let reported_congested =
+				BridgeStatus { is_congested: true, delivery_fee_factor: EXPONENTIAL_FEE_BASE };
+			assert_eq!(
+				TestBridgeStatusReporter::reported(),
+				vec![(sibling_location(SIBLING_ACCOUNT), lane_id, reported_congested)],
+			);
+
+			// growing fee factor is not reported until it crosses the threshold
+			assert_eq!(XcmOverBridge::on_initialize(3), db_weight.reads_writes(3, 1));
+			let congested = BridgeStatus {
+				is_congested: true,
+				delivery_fee_factor: EXPONENTIAL_FEE_BASE * EXPONENTIAL_FEE_BASE,
+			};
+			assert_eq!(TestBridgeStatusReporter::reported().len(), 1);
+			let bridge = Bridges::<TestRuntime, ()>::get(lane_id).unwrap();
+			assert_eq!(bridge.status, congested);
+			assert_eq!(bridge.reported_status, reported_congested);
+
+			// when the queue is drained, fee factor decays to its minimal value
+			set_queued_messages(0);
+			XcmOverBridge::on_initialize(4);
+			XcmOverBridge::on_initialize(5);
+			XcmOverBridge::on_initialize(6);
+			assert_eq!(
+				TestBridgeStatusReporter::reported().last(),
+				Some(&(sibling_location(SIBLING_ACCOUNT), lane_id, BridgeStatus::default())),
+			);
+			assert_eq!(TestBridgeStatusReporter::reported().len(), 3);
+		});
+	}
+
+	#[test]
+	fn bridge_status_is_reported_when_fee_factor_crosses_threshold() {
+		let reported =
+			BridgeStatus { is_congested: true, delivery_fee_factor: EXPONENTIAL_FEE_BASE };
+
+		// congestion state change is always reported
+		assert!(BridgeStatus { is_congested: false, ..reported }.needs_report(&reported));
+
+		// small fee factor changes are not reported
+		let mut status = reported;
+		while status.delivery_fee_factor <
+			reported.delivery_fee_factor * DELIVERY_FEE_FACTOR_REPORT_THRESHOLD
+		{
+			assert!(!status.needs_report(&reported));
+			status = status.next(CONGESTED_QUEUE_SIZE + 1, CONGESTED_QUEUE_SIZE);
+		}
+
+		// but once the factor crosses the threshold, it is reported
+		assert!(status.needs_report(&reported));
+		assert!(reported.needs_report(&status));
+
+		// and the minimal fee factor is always reported
+		let minimal = BridgeStatus::default();
+		assert!(minimal.needs_report(&BridgeStatus { is_congested: false, ..reported }));
+		assert!(!minimal.needs_report(&minimal));
+	}
+
+	#[test]
+	fn status_of_closed_bridge_is_not_updated() {
+		run_test(|| {
+			assert_ok!(XcmOverBridge::open_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				destination(),
+				bridge_lane(),
+			));
+			assert_ok!(XcmOverBridge::close_bridge(
+				RuntimeOrigin::signed(SIBLING_ACCOUNT),
+				bridge_lane(),
+			));
+			let lane_id = bridge_lane();
+			pallet_bridge_messages::OutboundLanes::<TestRuntime, ()>::insert(
+				lane_id,
+				bp_messages::OutboundLaneData {
+					oldest_unpruned_nonce: 1,
+					latest_received_nonce: 0,
+					latest_generated_nonce: CONGESTED_QUEUE_SIZE + 1,
+				},
+			);
+
+			// even if the lane of the closed bridge is still in the `BridgeLanes`
+			BridgeLanes::<TestRuntime, ()>::put(
+				BoundedVec::try_from(vec![lane_id]).expect("single lane fits the bound"),
+			);
+			assert_eq!(XcmOverBridge::on_initialize(1), DbWeight::get().reads(2));
+			assert_eq!(TestBridgeStatusReporter::reported(), vec![]);
+			assert_eq!(
+				Bridges::<TestRuntime, ()>::get(lane_id).unwrap().status,
+				BridgeStatus::default(),
+			);
+		});
+	}
+
+	#[test]
+	fn pending_queries_are_stored() {
+		run_test(|| {
+			let destination = MultiLocation::new(2, X2(GlobalConsensus(Wococo), Parachain(1000)));
+			assert_eq!(XcmOverBridge::pending_query_lane(&destination, 1), None);
+
+			XcmOverBridge::note_pending_query(bridge_lane(), &destination, 1);
+			assert_eq!(XcmOverBridge::pending_query_lane(&destination, 1), Some(bridge_lane()));
+			assert_eq!(XcmOverBridge::pending_query_lane(&destination, 2), None);
+
+			XcmOverBridge::remove_pending_query(&destination, 1);
+			assert_eq!(XcmOverBridge::pending_query_lane(&destination, 1), None);
+			assert_eq!(PendingQueriesCount::<TestRuntime, ()>::get(destination), 0);
+		});
+	}
+
+	#[test]
+	fn pending_queries_are_bounded_per_destination() {
+		run_test(|| {
+			let destination = MultiLocation::new(2, X2(GlobalConsensus(Wococo), Parachain(1000)));
+			let other_destination =
+				MultiLocation::new(2, X2(GlobalConsensus(Wococo), Parachain(2000)));
+			let max_pending_queries: u32 =
+				<TestRuntime as Config>::MaxPendingQueriesPerDestination::get();
+			for query_id in 0..max_pending_queries {
+				XcmOverBridge::note_pending_query(bridge_lane(), &destination, query_id as _);
+			}
+
+			// no more queries with the same destination are remembered
+			let query_id = max_pending_queries as QueryId;
+			XcmOverBridge::note_pending_query(bridge_lane(), &destination, query_id);
+			assert_eq!(XcmOverBridge::pending_query_lane(&destination, query_id), None);
+			assert_eq!(
+				PendingQueriesCount::<TestRuntime, ()>::get(destination),
+				max_pending_queries,
+			);
+
+			// but the query may be updated and queries with other destinations are remembered
+			XcmOverBridge::note_pending_query(bridge_lane(), &destination, 0);
+			assert_eq!(XcmOverBridge::pending_query_lane(&destination, 0), Some(bridge_lane()));
+			XcmOverBridge::note_pending_query(bridge_lane(), &other_destination, query_id);
+			assert_eq!(
+				XcmOverBridge::pending_query_lane(&other_destination, query_id),
+				Some(bridge_lane()),
+			);
+		});
+	}
+
+	#[test]
+	fn pending_queries_expire_at_next_block() {
+		run_test(|| {
+			let destination = MultiLocation::new(2, X2(GlobalConsensus(Wococo), Parachain(1000)));
+			let max_pending_queries: u32 =
+				<TestRuntime as Config>::MaxPendingQueriesPerDestination::get();
+			for query_id in 0..max_pending_queries {
+				XcmOverBridge::note_pending_query(bridge_lane(), &destination, query_id as _);
+			}
+
+			// queries of the previous block are expired
+			System::set_block_number(System::block_number() + 1);
+			assert_eq!(XcmOverBridge::pending_query_lane(&destination, 0), None);
+
+			// and are pruned when the limit is reached
+			let query_id = max_pending_queries as QueryId;
+			XcmOverBridge::note_pending_query(bridge_lane(), &destination, query_id);
+			assert_eq!(
+				XcmOverBridge::pending_query_lane(&destination, query_id),
+				Some(bridge_lane()),
+			);
+			assert_eq!(PendingQueriesCount::<TestRuntime, ()>::get(destination), 1);
+			assert_eq!(PendingQueries::<TestRuntime, ()>::iter_prefix(destination).count(), 1);
+		});
+	}
+}
@@ -0,0 +1,240 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as pallet_xcm_bridge_hub;
+use crate::{BridgeStatus, ReportBridgeStatus};
+
+use bp_messages::{
+	source_chain::ForbidOutboundMessages, target_chain::ForbidInboundMessages, LaneId, MessageNonce,
+};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, ConstU64, EnsureOrigin},
+	weights::{RuntimeDbWeight, Weight},
+};
+use frame_system::RawOrigin;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header as SubstrateHeader,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use xcm::latest::prelude::*;
+
+pub type AccountId = u64;
+pub type Balance = u64;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+
+/// Deposit that is reserved when the bridge is opened.
+pub const BRIDGE_DEPOSIT: Balance = 100;
+
+/// Account of the sibling parachain that has enough funds to open the bridge.
+pub const SIBLING_ACCOUNT: AccountId = 1000;
+
+/// Account of the sibling parachain that has no funds to open the bridge.
+pub const POOR_SIBLING_ACCOUNT: AccountId = 1001;
+
+/// Number of queued messages after which the bridge is considered congested.
+pub const CONGESTED_QUEUE_SIZE: MessageNonce = 8;
+
+/// Weight of the single bridge status report.
+pub const REPORT_BRIDGE_STATUS_WEIGHT: Weight = Weight::from_parts(100, 0);
+
+frame_support::construct_runtime! {
+	pub enum TestRuntime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Event<T>},
+		Messages: pallet_bridge_messages::{Pallet, Call, Event<T>},
+		XcmOverBridge: pallet_xcm_bridge_hub::{Pallet, Call, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub const DbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 2 };
+	pub const MaxDeferredDispatchWeightPerBlock: Weight = Weight::from_parts(1_000_000, 0);
+	pub const ActiveOutboundLanes: &'static [LaneId] = &[LaneId([0, 0, 0, 0])];
+	pub const TestBridgedChainId: bp_runtime::ChainId = *b"test";
+	pub UniversalLocation: InteriorMultiLocation = X2(GlobalConsensus(Rococo), Parachain(1013));
+	pub const BridgedNetwork: NetworkId = Wococo;
+}
+
+impl frame_system::Config for TestRuntime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Index = u64;
+	type RuntimeCall = RuntimeCall;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = SubstrateHeader;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = DbWeight;
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for TestRuntime {
+	type MaxLocks = ();
+	type Balance = Balance;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = frame_system::Pallet<TestRuntime>;
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = ();
+}
+
+impl pallet_bridge_messages::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type ActiveOutboundLanes = ActiveOutboundLanes;
+	type DynamicLanes = XcmOverBridge;
+	type StaleLaneThreshold = ();
+	type MaxUnrewardedRelayerEntriesAtInboundLane = ConstU64<16>;
+	type MaxUnconfirmedMessagesAtInboundLane = ConstU64<32>;
+	type MaxDispatchWeightPerBlock = ();
+	type MaxDeferredMessagesPerLane = ConstU64<32>;
+	type MaxDeferredDispatchWeightPerBlock = MaxDeferredDispatchWeightPerBlock;
+
+	type MaximalOutboundPayloadSize = ConstU32<1024>;
+	type OutboundPayload = Vec<u8>;
+
+	type InboundPayload = Vec<u8>;
+	type InboundRelayer = AccountId;
+	type DeliveryPayments = ();
+
+	type TargetHeaderChain = ForbidOutboundMessages;
+	type LaneMessageVerifier = ForbidOutboundMessages;
+	type DeliveryConfirmationPayments = ForbidOutboundMessages;
+	type OnMessagesDelivered = ();
+
+	type SourceHeaderChain = ForbidInboundMessages<(), Vec<u8>>;
+	type MessageDispatch = ForbidInboundMessages<(), Vec<u8>>;
+	type BridgedChainId = TestBridgedChainId;
+}
+
+impl pallet_xcm_bridge_hub::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type UniversalLocation = UniversalLocation;
+	type BridgedNetwork = BridgedNetwork;
+	type BridgeMessagesPalletInstance = ();
+	type OpenBridgeOrigin = OpenBridgeOrigin;
+	type BridgeOriginAccountIdConverter = SiblingParachainAccountIdConverter;
+	type Currency = Balances;
+	type BridgeDeposit = ConstU64<BRIDGE_DEPOSIT>;
+	type MaxBridges = ConstU32<4>;
+	type CongestedQueueSize = ConstU64<CONGESTED_QUEUE_SIZE>;
+	type ReportBridgeStatus = TestBridgeStatusReporter;
+	type MaxPendingQueriesPerDestination = ConstU32<2>;
+}
+
+/// Returns location of the sibling parachain, that has given account.
+pub fn sibling_location(account: AccountId) -> MultiLocation {
+	MultiLocation::new(1, X1(Parachain(account as u32)))
+}
+
+/// Origin that treats signed origins as the sibling parachain origins.
+pub struct OpenBridgeOrigin;
+
+impl EnsureOrigin<RuntimeOrigin> for OpenBridgeOrigin {
+	type Success = MultiLocation;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+		match o.clone().into() {
+			Ok(RawOrigin::Signed(account)) => Ok(sibling_location(account)),
+			_ => Err(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(RuntimeOrigin::signed(SIBLING_ACCOUNT))
+	}
+}
+
+/// Converts sibling parachain location into account.
+pub struct SiblingParachainAccountIdConverter;
+
+impl xcm_executor::traits::Convert<MultiLocation, AccountId>
+	for SiblingParachainAccountIdConverter
+{
+	fn convert(location: MultiLocation) -> Result<AccountId, MultiLocation> {
+		match location {
+			MultiLocation { parents: 1, interior: X1(Parachain(id)) } => Ok(id as AccountId),
+			_ => Err(location),
+		}
+	}
+}
+
+/// Reporter that remembers all reported bridge statuses.
+pub struct TestBridgeStatusReporter;
+
+impl TestBridgeStatusReporter {
+	/// Returns all statuses that have been reported so far.
+	pub fn reported() -> Vec<(MultiLocation, LaneId, BridgeStatus)> {
+		frame_support::storage::unhashed::get_or_default(b":reported-bridge-status:")
+	}
+}
+
+impl ReportBridgeStatus for TestBridgeStatusReporter {
+	fn report_bridge_status(
+		bridge_origin_relative_location: &MultiLocation,
+		lane_id: LaneId,
+		status: BridgeStatus,
+	) -> Weight {
+		let mut reported = Self::reported();
+		reported.push((*bridge_origin_relative_location, lane_id, status));
+		frame_support::storage::unhashed::put(b":reported-bridge-status:", &reported);
+		REPORT_BRIDGE_STATUS_WEIGHT
+	}
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<TestRuntime>().unwrap();
+	pallet_balances::GenesisConfig::<TestRuntime> {
+		balances: vec![(SIBLING_ACCOUNT, 10 * BRIDGE_DEPOSIT)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		System::reset_events();
+		test()
+	})
+}
@@ -0,0 +1,107 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for pallet_xcm_bridge_hub
+//!
+//! Those are hand-written placeholder weights, they are NOT generated by the benchmark CLI. They
+//! must be replaced with the output of the pallet benchmarks (see `benchmarking` module) before
+//! the pallet is used in production.
+
+#![allow(clippy::all)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_xcm_bridge_hub.
+pub trait WeightInfo {
+	fn open_bridge() -> Weight;
+	fn suspend_bridge() -> Weight;
+	fn resume_bridge() -> Weight;
+	fn close_bridge() -> Weight;
+}
+
+/// Placeholder weights for `pallet_xcm_bridge_hub`.
+///
+/// Those weights are test only and must never be used in production.
+pub struct BridgeWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
+	/// Storage: XcmOverBridge Bridges (r:1 w:1)
+	/// Storage: XcmOverBridge BridgeLanes (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn open_bridge() -> Weight {
+		Weight::from_parts(58_704_000, 5363)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: XcmOverBridge Bridges (r:1 w:1)
+	fn suspend_bridge() -> Weight {
+		Weight::from_parts(22_014_000, 3887)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: XcmOverBridge Bridges (r:1 w:1)
+	fn resume_bridge() -> Weight {
+		Weight::from_parts(21_942_000, 3887)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: XcmOverBridge Bridges (r:1 w:1)
+	/// Storage: XcmOverBridge BridgeLanes (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn close_bridge() -> Weight {
+		Weight::from_parts(50_827_000, 5424)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: XcmOverBridge Bridges (r:1 w:1)
+	/// Storage: XcmOverBridge BridgeLanes (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn open_bridge() -> Weight {
+		Weight::from_parts(58_704_000, 5363)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: XcmOverBridge Bridges (r:1 w:1)
+	fn suspend_bridge() -> Weight {
+		Weight::from_parts(22_014_000, 3887)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: XcmOverBridge Bridges (r:1 w:1)
+	fn resume_bridge() -> Weight {
+		Weight::from_parts(21_942_000, 3887)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: XcmOverBridge Bridges (r:1 w:1)
+	/// Storage: XcmOverBridge BridgeLanes (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn close_bridge() -> Weight {
+		Weight::from_parts(50_827_000, 5424)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
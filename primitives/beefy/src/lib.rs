@@ -29,19 +29,22 @@ pub use sp_consensus_beefy::{
 	crypto::{AuthorityId as EcdsaValidatorId, AuthoritySignature as EcdsaValidatorSignature},
 	known_payloads::MMR_ROOT_ID as MMR_ROOT_PAYLOAD_ID,
 	mmr::{BeefyAuthoritySet, MmrLeafVersion},
-	BeefyAuthorityId, Commitment, Payload as BeefyPayload, SignedCommitment, ValidatorSet,
-	ValidatorSetId, BEEFY_ENGINE_ID,
+	BeefyAuthorityId, Commitment, ConsensusLog, Payload as BeefyPayload, SignedCommitment,
+	ValidatorSet, ValidatorSetId, VersionedFinalityProof, BEEFY_ENGINE_ID,
 };
 
+use bp_header_chain::ConsensusLogReader;
 use bp_runtime::{BasicOperatingMode, BlockNumberOf, Chain, HashOf};
-use codec::{Decode, Encode};
+use codec::{Codec, Decode, Encode};
 use frame_support::Parameter;
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{Convert, MaybeSerializeDeserialize},
-	RuntimeAppPublic, RuntimeDebug,
+	traits::{Convert, MaybeSerializeDeserialize, Member},
+	Digest, RuntimeAppPublic, RuntimeDebug,
 };
-use sp_std::prelude::*;
+use sp_std::{marker::PhantomData, prelude::*};
+
+pub mod storage_keys;
 
 /// Substrate-based chain with BEEFY && MMR pallets deployed.
 ///
@@ -69,6 +72,7 @@ pub trait ChainWithBeefy: Chain {
 	/// Corresponds to the `Hash` field of the `pallet-mmr` configuration.
 	type MmrHash: sp_std::hash::Hash
 		+ Parameter
+		+ Member
 		+ Copy
 		+ AsRef<[u8]>
 		+ Default
@@ -76,12 +80,19 @@ pub trait ChainWithBeefy: Chain {
 		+ PartialOrd;
 
 	/// The type expected for the MMR leaf extra data.
-	type BeefyMmrLeafExtra: Parameter;
+	type BeefyMmrLeafExtra: Parameter + Member;
 
 	/// A way to identify a BEEFY validator.
 	///
 	/// Corresponds to the `BeefyId` field of the `pallet-beefy` configuration.
-	type AuthorityId: BeefyAuthorityId<Self::CommitmentHasher> + Parameter;
+	type AuthorityId: BeefyAuthorityId<Self::CommitmentHasher, Signature = Self::AuthoritySignature>
+		+ Parameter
+		+ Member;
+
+	/// Signature of the BEEFY validator.
+	///
+	/// Corresponds to the signature type of the `AuthorityId`.
+	type AuthoritySignature: Parameter + Member;
 
 	/// A way to convert validator id to its raw representation in the BEEFY merkle tree.
 	///
@@ -147,3 +158,69 @@ pub struct ImportedCommitment<BlockNumber, BlockHash, MmrHash> {
 	/// MMR root at the imported block.
 	pub mmr_root: MmrHash,
 }
+
+/// Some high level info about the imported commitments.
+#[derive(Encode, Decode, RuntimeDebug, PartialEq, TypeInfo)]
+pub struct ImportedCommitmentsInfoData<BlockNumber> {
+	/// Best known block number, provided in a BEEFY commitment. However this is not
+	/// the best proven block. The best proven block is this block's parent.
+	pub best_block_number: BlockNumber,
+	/// The head of the `ImportedBlockNumbers` ring buffer.
+	pub next_block_number_index: u32,
+}
+
+/// A reader of BEEFY consensus log items from the header digest.
+pub struct BeefyConsensusLogReader<AuthorityId>(PhantomData<AuthorityId>);
+
+impl<AuthorityId: Codec> BeefyConsensusLogReader<AuthorityId> {
+	/// Returns the new BEEFY validator set, if the header digest has the item that changes it.
+	pub fn find_authorities_change(digest: &Digest) -> Option<ValidatorSet<AuthorityId>> {
+		// BEEFY pallets are also depositing other consensus items (e.g. MMR root), so we can't
+		// just look at the first BEEFY item
+		digest.logs().iter().find_map(|log| {
+			match log.consensus_try_to::<ConsensusLog<AuthorityId>>(&BEEFY_ENGINE_ID) {
+				Some(ConsensusLog::AuthoritiesChange(validator_set)) => Some(validator_set),
+				_ => None,
+			}
+		})
+	}
+}
+
+impl<AuthorityId: Codec> ConsensusLogReader for BeefyConsensusLogReader<AuthorityId> {
+	fn schedules_authorities_change(digest: &Digest) -> bool {
+		BeefyConsensusLogReader::<AuthorityId>::find_authorities_change(digest).is_some()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::DigestItem;
+
+	fn validator_set(id: ValidatorSetId) -> ValidatorSet<EcdsaValidatorId> {
+		ValidatorSet::new(vec![sp_core::ecdsa::Public::from_raw([1u8; 33]).into()], id).unwrap()
+	}
+
+	fn beefy_log(log: ConsensusLog<EcdsaValidatorId>) -> DigestItem {
+		DigestItem::Consensus(BEEFY_ENGINE_ID, log.encode())
+	}
+
+	#[test]
+	fn beefy_consensus_log_reader_finds_authorities_change() {
+		type Reader = BeefyConsensusLogReader<EcdsaValidatorId>;
+
+		// no BEEFY items at all
+		assert!(!Reader::schedules_authorities_change(&Digest { logs: vec![] }));
+
+		// MMR root is not changing authorities set
+		let mmr_root = beefy_log(ConsensusLog::MmrRoot(Default::default()));
+		assert!(!Reader::schedules_authorities_change(&Digest { logs: vec![mmr_root.clone()] }));
+
+		// authorities change is found even if it isn't the first BEEFY item
+		let change = beefy_log(ConsensusLog::AuthoritiesChange(validator_set(1)));
+		assert_eq!(
+			Reader::find_authorities_change(&Digest { logs: vec![mmr_root, change] }),
+			Some(validator_set(1)),
+		);
+	}
+}
@@ -21,8 +21,12 @@
 pub use bp_polkadot_core::*;
 
 use bp_header_chain::ChainWithGrandpa;
-use bp_runtime::{decl_bridge_finality_runtime_apis, Chain};
+use bp_messages::{
+	InboundMessageDetails, LaneId, MessageNonce, MessagePayload, OutboundMessageDetails,
+};
+use bp_runtime::{decl_bridge_runtime_apis, Chain};
 use frame_support::weights::Weight;
+use sp_std::prelude::*;
 
 /// Kusama Chain
 pub struct Kusama;
@@ -56,7 +60,21 @@ impl ChainWithGrandpa for Kusama {
 	const AVERAGE_HEADER_SIZE_IN_JUSTIFICATION: u32 = AVERAGE_HEADER_SIZE_IN_JUSTIFICATION;
 }
 
+/// Name of the parachains pallet in the Kusama runtime.
+pub const PARAS_PALLET_NAME: &str = "Paras";
+
 /// Name of the With-Kusama GRANDPA pallet instance that is deployed at bridged chains.
 pub const WITH_KUSAMA_GRANDPA_PALLET_NAME: &str = "BridgeKusamaGrandpa";
+/// Name of the With-Kusama parachains bridge pallet instance that is deployed at bridged chains.
+pub const WITH_KUSAMA_BRIDGE_PARAS_PALLET_NAME: &str = "BridgeKusamaParachains";
+/// Name of the With-Kusama messages pallet instance that is deployed at bridged chains.
+pub const WITH_KUSAMA_MESSAGES_PALLET_NAME: &str = "BridgeKusamaMessages";
+
+/// Maximal size of encoded `bp_parachains::ParaStoredHeaderData` structure among all Kusama
+/// parachains.
+///
+/// It includes the block number and state root, so it shall be near 40 bytes, but let's have some
+/// reserve.
+pub const MAX_NESTED_PARACHAIN_HEAD_DATA_SIZE: u32 = 128;
 
-decl_bridge_finality_runtime_apis!(kusama);
+decl_bridge_runtime_apis!(kusama, grandpa => bp_header_chain::AuthoritySet);
@@ -74,6 +74,12 @@ pub const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce = 128;
 /// Maximal number of unconfirmed messages in Millau confirmation transaction.
 pub const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce = 128;
 
+/// Lane that is used to exchange XCM messages with Rialto.
+pub const WITH_RIALTO_XCM_LANE: LaneId = LaneId([0, 0, 0, 0]);
+
+/// Lane that is used to exchange XCM messages with RialtoParachain.
+pub const WITH_RIALTO_PARACHAIN_XCM_LANE: LaneId = LaneId([0, 0, 0, 0]);
+
 /// The target length of a session (how often authorities change) on Millau measured in of number of
 /// blocks.
 ///
@@ -193,6 +199,7 @@ impl ChainWithBeefy for Millau {
 	type MmrHash = <Keccak256 as sp_runtime::traits::Hash>::Output;
 	type BeefyMmrLeafExtra = ();
 	type AuthorityId = bp_beefy::EcdsaValidatorId;
+	type AuthoritySignature = bp_beefy::EcdsaValidatorSignature;
 	type AuthorityIdToMerkleLeaf = bp_beefy::BeefyEcdsaToEthereum;
 }
 
@@ -241,9 +248,11 @@ frame_support::parameter_types! {
 
 /// Name of the With-Millau GRANDPA pallet instance that is deployed at bridged chains.
 pub const WITH_MILLAU_GRANDPA_PALLET_NAME: &str = "BridgeMillauGrandpa";
+/// Name of the With-Millau BEEFY pallet instance that is deployed at bridged chains.
+pub const WITH_MILLAU_BEEFY_PALLET_NAME: &str = "BridgeMillauBeefy";
 /// Name of the With-Millau messages pallet instance that is deployed at bridged chains.
 pub const WITH_MILLAU_MESSAGES_PALLET_NAME: &str = "BridgeMillauMessages";
 /// Name of the transaction payment pallet at the Millau runtime.
 pub const TRANSACTION_PAYMENT_PALLET_NAME: &str = "TransactionPayment";
 
-decl_bridge_runtime_apis!(millau);
+decl_bridge_runtime_apis!(millau, grandpa => bp_header_chain::AuthoritySet);
@@ -68,6 +68,9 @@ pub const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce = 1024;
 /// Maximal number of unconfirmed messages in Rialto confirmation transaction.
 pub const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce = 1024;
 
+/// Lane that is used to exchange XCM messages with Millau.
+pub const WITH_MILLAU_XCM_LANE: LaneId = LaneId([0, 0, 0, 0]);
+
 /// Block number type used in Rialto.
 pub type BlockNumber = u32;
 
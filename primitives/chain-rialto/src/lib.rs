@@ -63,6 +63,9 @@ pub const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce = 1024;
 /// Maximal number of unconfirmed messages in Rialto confirmation transaction.
 pub const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce = 1024;
 
+/// Lane that is used to exchange XCM messages with Millau.
+pub const WITH_MILLAU_XCM_LANE: LaneId = LaneId([0, 0, 0, 0]);
+
 /// The target length of a session (how often authorities change) on Rialto measured in of number of
 /// blocks.
 ///
@@ -211,4 +214,4 @@ pub const PARAS_REGISTRAR_PALLET_NAME: &str = "Registrar";
 /// Name of the parachains pallet in the Rialto runtime.
 pub const PARAS_PALLET_NAME: &str = "Paras";
 
-decl_bridge_runtime_apis!(rialto);
+decl_bridge_runtime_apis!(rialto, grandpa => bp_header_chain::AuthoritySet);
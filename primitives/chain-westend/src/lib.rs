@@ -103,6 +103,6 @@ pub const MAX_NESTED_PARACHAIN_HEAD_DATA_SIZE: u32 = 128;
 /// Identifier of Westmint parachain at the Westend relay chain.
 pub const WESTMINT_PARACHAIN_ID: u32 = 1000;
 
-decl_bridge_finality_runtime_apis!(westend);
+decl_bridge_finality_runtime_apis!(westend, grandpa => bp_header_chain::AuthoritySet);
 
 decl_bridge_finality_runtime_apis!(westmint);
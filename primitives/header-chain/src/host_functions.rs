@@ -0,0 +1,94 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Host functions that may be used to verify GRANDPA justifications.
+//!
+//! By default, justification signatures are verified one-by-one, by calling the regular
+//! `ed25519_verify` host function for every signature. When the `host-signature-verification`
+//! feature is enabled and the verification is switched to
+//! [`crate::justification::SignaturesVerification::HostBatch`] at runtime, all signatures of the
+//! justification are verified using single call of the [`bridge_grandpa_signatures`] host
+//! function. The host function is using ed25519 batch verification, which is cheaper than
+//! verifying signatures one-by-one for justifications with many signatures.
+//!
+//! Runtime that is built with this feature, imports the host function, so it may only be executed
+//! by nodes that are providing the [`HostFunctions`] (e.g. using
+//! `NativeExecutionDispatch::ExtendHostFunctions`). So the feature shall only be enabled when all
+//! node operators of the chain have opted in. Otherwise, the pure-Wasm verification must be used.
+
+use sp_core::ed25519;
+use sp_runtime_interface::runtime_interface;
+use sp_std::vec::Vec;
+
+/// Signature of the GRANDPA authority: public key of the authority, signature and the
+/// signed message.
+pub type SignatureToVerify = (ed25519::Public, ed25519::Signature, Vec<u8>);
+
+/// Host functions that are used to verify GRANDPA justifications.
+#[runtime_interface]
+pub trait BridgeGrandpaSignatures {
+	/// Returns true if all given signatures are valid.
+	///
+	/// Signatures are verified using the ed25519 batch verification. It follows the same
+	/// (ZIP-215) validation rules as the regular `ed25519_verify` host function.
+	fn verify_ed25519_batch(signatures: Vec<SignatureToVerify>) -> bool {
+		let mut verifier = ed25519_zebra::batch::Verifier::new();
+		for (public, signature, message) in &signatures {
+			verifier.queue((
+				ed25519_zebra::VerificationKeyBytes::from(public.0),
+				ed25519_zebra::Signature::from(signature.0),
+				message,
+			));
+		}
+		verifier.verify(rand::thread_rng()).is_ok()
+	}
+}
+
+/// Host functions that the node must provide to execute runtimes, built with the
+/// `host-signature-verification` feature.
+#[cfg(feature = "std")]
+pub type HostFunctions = (bridge_grandpa_signatures::HostFunctions,);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::Pair;
+
+	fn signature_to_verify(seed: u8, message: &[u8]) -> SignatureToVerify {
+		let pair = ed25519::Pair::from_seed(&[seed; 32]);
+		(pair.public(), pair.sign(message), message.to_vec())
+	}
+
+	#[test]
+	fn verify_ed25519_batch_accepts_valid_signatures() {
+		assert!(bridge_grandpa_signatures::verify_ed25519_batch(vec![]));
+		assert!(bridge_grandpa_signatures::verify_ed25519_batch(vec![
+			signature_to_verify(1, b"message1"),
+			signature_to_verify(2, b"message2"),
+		]));
+	}
+
+	#[test]
+	fn verify_ed25519_batch_rejects_invalid_signature() {
+		let mut invalid_signature = signature_to_verify(2, b"message2");
+		invalid_signature.2 = b"message3".to_vec();
+
+		assert!(!bridge_grandpa_signatures::verify_ed25519_batch(vec![
+			signature_to_verify(1, b"message1"),
+			invalid_signature,
+		]));
+	}
+}
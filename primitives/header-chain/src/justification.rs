@@ -117,6 +117,18 @@ pub enum Error {
 	ExtraHeadersInVotesAncestries,
 }
 
+/// The way justification signatures are verified.
+#[derive(Clone, Copy, Eq, RuntimeDebug, PartialEq)]
+pub enum SignaturesVerification {
+	/// Every signature is verified in the runtime, one-by-one.
+	InRuntime,
+	/// All signatures are verified using single call of the
+	/// [`crate::host_functions::bridge_grandpa_signatures`] host function. May only be used if all
+	/// nodes of the chain are providing this function.
+	#[cfg(feature = "host-signature-verification")]
+	HostBatch,
+}
+
 /// Given GRANDPA authorities set size, return number of valid authorities votes that the
 /// justification must have to be valid.
 ///
@@ -146,6 +158,7 @@ where
 {
 	let mut optimizer = OptimizationCallbacks(Vec::new());
 	verify_justification_with_callbacks(
+		SignaturesVerification::InRuntime,
 		finalized_target,
 		authorities_set_id,
 		authorities_set,
@@ -162,10 +175,32 @@ pub fn verify_justification<Header: HeaderT>(
 	authorities_set: &VoterSet<AuthorityId>,
 	justification: &GrandpaJustification<Header>,
 ) -> Result<(), Error>
+where
+	Header::Number: finality_grandpa::BlockNumberOps,
+{
+	verify_justification_using(
+		SignaturesVerification::InRuntime,
+		finalized_target,
+		authorities_set_id,
+		authorities_set,
+		justification,
+	)
+}
+
+/// Verify that justification, that is generated by given authority set, finalizes given header.
+/// Signatures are verified in the given way.
+pub fn verify_justification_using<Header: HeaderT>(
+	signatures_verification: SignaturesVerification,
+	finalized_target: (Header::Hash, Header::Number),
+	authorities_set_id: SetId,
+	authorities_set: &VoterSet<AuthorityId>,
+	justification: &GrandpaJustification<Header>,
+) -> Result<(), Error>
 where
 	Header::Number: finality_grandpa::BlockNumberOps,
 {
 	verify_justification_with_callbacks(
+		signatures_verification,
 		finalized_target,
 		authorities_set_id,
 		authorities_set,
@@ -235,6 +270,7 @@ impl VerificationCallbacks for OptimizationCallbacks {
 
 /// Verify that justification, that is generated by given authority set, finalizes given header.
 fn verify_justification_with_callbacks<Header: HeaderT, C: VerificationCallbacks>(
+	signatures_verification: SignaturesVerification,
 	finalized_target: (Header::Hash, Header::Number),
 	authorities_set_id: SetId,
 	authorities_set: &VoterSet<AuthorityId>,
@@ -252,6 +288,8 @@ where
 	let threshold = authorities_set.threshold().0.into();
 	let mut chain = AncestryChain::new(&justification.votes_ancestries);
 	let mut signature_buffer = Vec::new();
+	#[cfg(feature = "host-signature-verification")]
+	let mut signatures_to_verify: Vec<crate::host_functions::SignatureToVerify> = Vec::new();
 	let mut votes = BTreeSet::new();
 	let mut cumulative_weight = 0u64;
 
@@ -306,19 +344,46 @@ where
 				qed",
 		);
 
-		// verify authority signature
-		if !sp_consensus_grandpa::check_message_signature_with_buffer(
-			&finality_grandpa::Message::Precommit(signed.precommit.clone()),
-			&signed.id,
-			&signed.signature,
-			justification.round,
-			authorities_set_id,
-			&mut signature_buffer,
-		) {
-			return Err(Error::InvalidAuthoritySignature)
+		match signatures_verification {
+			// verify authority signature
+			SignaturesVerification::InRuntime =>
+				if !sp_consensus_grandpa::check_message_signature_with_buffer(
+					&finality_grandpa::Message::Precommit(signed.precommit.clone()),
+					&signed.id,
+					&signed.signature,
+					justification.round,
+					authorities_set_id,
+					&mut signature_buffer,
+				) {
+					return Err(Error::InvalidAuthoritySignature)
+				},
+			// or remember it to verify all signatures at once
+			#[cfg(feature = "host-signature-verification")]
+			SignaturesVerification::HostBatch => {
+				sp_consensus_grandpa::localized_payload_with_buffer(
+					justification.round,
+					authorities_set_id,
+					&finality_grandpa::Message::Precommit(signed.precommit.clone()),
+					&mut signature_buffer,
+				);
+				signatures_to_verify.push((
+					signed.id.clone().into(),
+					signed.signature.clone().into(),
+					signature_buffer.clone(),
+				));
+			},
 		}
 	}
 
+	// verify all remembered signatures using single host function call
+	#[cfg(feature = "host-signature-verification")]
+	if signatures_verification == SignaturesVerification::HostBatch &&
+		!crate::host_functions::bridge_grandpa_signatures::verify_ed25519_batch(
+			signatures_to_verify,
+		) {
+		return Err(Error::InvalidAuthoritySignature)
+	}
+
 	// check that there are no extra headers in the justification
 	if !chain.unvisited.is_empty() {
 		return Err(Error::ExtraHeadersInVotesAncestries)
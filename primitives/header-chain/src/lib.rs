@@ -33,6 +33,7 @@ use sp_consensus_grandpa::{AuthorityList, ConsensusLog, SetId, GRANDPA_ENGINE_ID
 use sp_runtime::{traits::Header as HeaderT, Digest, RuntimeDebug};
 use sp_std::boxed::Box;
 
+pub mod host_functions;
 pub mod justification;
 pub mod storage_keys;
 
@@ -176,6 +177,9 @@ pub enum BridgeGrandpaCall<Header: HeaderT> {
 	/// `pallet-bridge-grandpa::Call::initialize`
 	#[codec(index = 1)]
 	initialize { init_data: InitializationData<Header> },
+	/// `pallet-bridge-grandpa::Call::set_operating_mode`
+	#[codec(index = 3)]
+	set_operating_mode { operating_mode: BasicOperatingMode },
 }
 
 /// The `BridgeGrandpaCall` used by a chain.
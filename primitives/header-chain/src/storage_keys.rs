@@ -22,6 +22,8 @@ pub const PALLET_OPERATING_MODE_VALUE_NAME: &str = "PalletOperatingMode";
 pub const BEST_FINALIZED_VALUE_NAME: &str = "BestFinalized";
 /// Name of the `CurrentAuthoritySet` storage value.
 pub const CURRENT_AUTHORITY_SET_VALUE_NAME: &str = "CurrentAuthoritySet";
+/// Name of the `ImportedHeaders` storage map.
+pub const IMPORTED_HEADERS_MAP_NAME: &str = "ImportedHeaders";
 
 use sp_core::storage::StorageKey;
 
@@ -58,6 +60,19 @@ pub fn best_finalized_key(pallet_prefix: &str) -> StorageKey {
 	)
 }
 
+/// Prefix of all `ImportedHeaders` map entries in the runtime storage.
+///
+/// The map is using `Identity` hasher, so the header hash follows the prefix in every key.
+pub fn imported_headers_prefix(pallet_prefix: &str) -> StorageKey {
+	StorageKey(
+		bp_runtime::storage_value_final_key(
+			pallet_prefix.as_bytes(),
+			IMPORTED_HEADERS_MAP_NAME.as_bytes(),
+		)
+		.to_vec(),
+	)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -89,6 +104,19 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn imported_headers_prefix_is_prefix_of_final_key() {
+		let hash = sp_core::H256::repeat_byte(42);
+		let prefix = imported_headers_prefix("BridgeGrandpa").0;
+		let final_key = bp_runtime::storage_map_final_key::<frame_support::Identity>(
+			"BridgeGrandpa",
+			IMPORTED_HEADERS_MAP_NAME,
+			hash.as_bytes(),
+		)
+		.0;
+		assert_eq!(final_key, [prefix, hash.as_bytes().to_vec()].concat());
+	}
+
 	#[test]
 	fn best_finalized_key_computed_properly() {
 		// If this test fails, then something has been changed in module storage that is breaking
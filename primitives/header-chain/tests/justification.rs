@@ -154,6 +154,37 @@ fn justification_with_invalid_authority_signature_rejected() {
 	);
 }
 
+#[cfg(feature = "host-signature-verification")]
+#[test]
+fn host_batch_signatures_verification_works() {
+	use bp_header_chain::justification::{verify_justification_using, SignaturesVerification};
+
+	let mut justification = make_default_justification::<TestHeader>(&test_header(1));
+	assert_eq!(
+		verify_justification_using::<TestHeader>(
+			SignaturesVerification::HostBatch,
+			header_id::<TestHeader>(1),
+			TEST_GRANDPA_SET_ID,
+			&voter_set(),
+			&justification,
+		),
+		Ok(()),
+	);
+
+	justification.commit.precommits[1].signature =
+		sp_core::crypto::UncheckedFrom::unchecked_from([1u8; 64]);
+	assert_eq!(
+		verify_justification_using::<TestHeader>(
+			SignaturesVerification::HostBatch,
+			header_id::<TestHeader>(1),
+			TEST_GRANDPA_SET_ID,
+			&voter_set(),
+			&justification,
+		),
+		Err(Error::InvalidAuthoritySignature),
+	);
+}
+
 #[test]
 fn justification_with_invalid_precommit_ancestry() {
 	let mut justification = make_default_justification::<TestHeader>(&test_header(1));
@@ -20,9 +20,14 @@
 // RuntimeApi generated functions
 #![allow(clippy::too_many_arguments)]
 
-use bp_runtime::{BasicOperatingMode, OperatingMode};
+use bp_runtime::{
+	account_derivation::{
+		derive_bridge_account_id, into_account_id, AccountDerivationVersion, BridgeAccountKind,
+	},
+	BasicOperatingMode, ChainId, OperatingMode,
+};
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::RuntimeDebug;
+use frame_support::{sp_runtime::traits::AccountIdConversion, RuntimeDebug};
 use scale_info::TypeInfo;
 use source_chain::RelayersRewards;
 use sp_core::TypeId;
@@ -50,6 +55,21 @@ pub enum MessagesOperatingMode {
 	/// queued messages to the bridged chain. Once upgrade is completed, the mode may be switched
 	/// back to `Normal`.
 	RejectingOutboundMessages,
+	/// The pallet is accepting outbound messages, inbound messages and receiving proofs, but
+	/// dispatch of inbound messages is paused.
+	///
+	/// This mode may be used e.g. when there's an issue with the message dispatcher (like
+	/// invalid XCM configuration). Received messages are stored and are dispatched once the
+	/// mode is switched back to `Normal` or `RejectingOutboundMessages`, so relayers keep
+	/// making progress.
+	DispatchPaused,
+	/// The pallet is only accepting receiving proofs. Outbound and inbound messages are
+	/// rejected.
+	///
+	/// This mode may be used to drain pending outbound messages without accepting new messages
+	/// in both directions. Previously received inbound messages, which dispatch has been
+	/// deferred, are still dispatched.
+	AcceptingConfirmationsOnly,
 }
 
 impl Default for MessagesOperatingMode {
@@ -58,6 +78,23 @@ impl Default for MessagesOperatingMode {
 	}
 }
 
+impl MessagesOperatingMode {
+	/// Returns true if the pallet is accepting outbound messages.
+	pub fn is_accepting_outbound_messages(&self) -> bool {
+		matches!(self, Self::Basic(BasicOperatingMode::Normal) | Self::DispatchPaused)
+	}
+
+	/// Returns true if the pallet is accepting inbound messages.
+	pub fn is_accepting_inbound_messages(&self) -> bool {
+		!self.is_halted() && *self != Self::AcceptingConfirmationsOnly
+	}
+
+	/// Returns true if dispatch of inbound messages is paused.
+	pub fn is_dispatch_paused(&self) -> bool {
+		self.is_halted() || *self == Self::DispatchPaused
+	}
+}
+
 impl OperatingMode for MessagesOperatingMode {
 	fn is_halted(&self) -> bool {
 		match self {
@@ -89,6 +126,27 @@ impl TypeId for LaneId {
 	const TYPE_ID: [u8; 4] = *b"blan";
 }
 
+impl LaneId {
+	/// Returns sovereign account of the lane, derived using given scheme version.
+	///
+	/// The `V0` account is derived from the lane id only, so lanes with the same id of different
+	/// bridges are sharing the same sovereign account.
+	pub fn sovereign_account<AccountId: Decode>(
+		&self,
+		version: AccountDerivationVersion,
+		bridged_chain_id: ChainId,
+	) -> AccountId {
+		match version {
+			AccountDerivationVersion::V0 => self.into_account_truncating(),
+			AccountDerivationVersion::V1 => into_account_id(derive_bridge_account_id(
+				BridgeAccountKind::LaneSovereign,
+				bridged_chain_id,
+				self,
+			)),
+		}
+	}
+}
+
 /// Message nonce. Valid messages will never have 0 nonce.
 pub type MessageNonce = u64;
 
@@ -219,6 +277,56 @@ pub struct InboundMessageDetails {
 	pub dispatch_weight: Weight,
 }
 
+/// Aggregate statistics of the lane, maintained by the messages pallet and returned by runtime
+/// APIs.
+///
+/// Outbound counters are updated at the source chain and inbound counters are updated at the
+/// target chain. So every chain only knows statistics of its own side of the lane.
+#[derive(
+	Clone, Copy, Default, Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen,
+)]
+pub struct LaneStatistics {
+	/// Total number of messages, sent over the lane.
+	#[codec(compact)]
+	pub messages_sent: MessageNonce,
+	/// Total size of encoded payloads of messages, sent over the lane.
+	#[codec(compact)]
+	pub bytes_sent: u64,
+	/// Total number of sent messages, delivery of which has been confirmed.
+	#[codec(compact)]
+	pub messages_delivered: MessageNonce,
+	/// Total number of messages, received and dispatched at this chain.
+	#[codec(compact)]
+	pub messages_received: MessageNonce,
+	/// Total number of messages that have been rejected by the inbound lane at this chain.
+	///
+	/// Messages with unexpected nonces (e.g. already delivered messages) are not counted here.
+	#[codec(compact)]
+	pub messages_failed: MessageNonce,
+	/// Total amount of rewards that have been registered for relayers, serving the lane.
+	///
+	/// It is reported by the `DeliveryConfirmationPayments` implementation, so it may stay
+	/// zero if relayers are rewarded by some other means.
+	#[codec(compact)]
+	pub rewards_paid: u128,
+}
+
+/// Limits of the messages delivery, returned by runtime APIs.
+///
+/// These limits are enforced by the runtime of the chain that is receiving messages, so the
+/// relayer shall never craft delivery transactions that exceed them.
+#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, PartialEq, Eq)]
+pub struct MessagesDeliveryLimits {
+	/// Maximal number of unrewarded relayer entries at the inbound lane.
+	pub max_unrewarded_relayer_entries_at_inbound_lane: MessageNonce,
+	/// Maximal number of unconfirmed messages at the inbound lane.
+	pub max_unconfirmed_messages_at_inbound_lane: MessageNonce,
+	/// Maximal size of the normal extrinsic.
+	pub max_extrinsic_size: u32,
+	/// Maximal weight of the normal extrinsic.
+	pub max_extrinsic_weight: Weight,
+}
+
 /// Unrewarded relayer entry stored in the inbound lane data.
 ///
 /// This struct represents a continuous range of messages that have been delivered by the same
@@ -273,10 +381,15 @@ pub enum ReceivalResult<DispatchLevelResult> {
 	TooManyUnrewardedRelayers,
 	/// There are too many unconfirmed messages at the lane.
 	TooManyUnconfirmedMessages,
+	/// Message has been received, but its dispatch has been deferred to one of subsequent
+	/// blocks, because the dispatch weight budget of the current block is exhausted.
+	Deferred,
+	/// There are too many deferred messages at the lane.
+	TooManyDeferredMessages,
 }
 
 /// Delivered messages with their dispatch result.
-#[derive(Clone, Default, Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+#[derive(Clone, Default, Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
 pub struct DeliveredMessages {
 	/// Nonce of the first message that has been delivered (inclusive).
 	pub begin: MessageNonce,
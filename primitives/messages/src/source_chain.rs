@@ -16,14 +16,15 @@
 
 //! Primitives of messages module, that are used on the source chain.
 
-use crate::{InboundLaneData, LaneId, MessageNonce, OutboundLaneData};
+use crate::{DeliveredMessages, InboundLaneData, LaneId, MessageNonce, OutboundLaneData};
 
 use crate::UnrewardedRelayer;
 use bp_runtime::Size;
-use frame_support::{weights::Weight, Parameter, RuntimeDebug};
+use frame_support::{traits::Get, weights::Weight, Parameter, RuntimeDebug};
 use sp_std::{
 	collections::{btree_map::BTreeMap, vec_deque::VecDeque},
 	fmt::Debug,
+	marker::PhantomData,
 	ops::RangeInclusive,
 };
 
@@ -88,6 +89,34 @@ pub trait LaneMessageVerifier<SenderOrigin, Payload> {
 	) -> Result<(), Self::Error>;
 }
 
+/// Lanes that are opened dynamically (e.g. when two remote locations are opening a bridge), in
+/// addition to lanes that are statically configured in the messages pallet.
+pub trait DynamicLanes {
+	/// Returns true if new messages may be sent over given lane.
+	fn is_lane_opened(lane: &LaneId) -> bool;
+
+	/// Returns number of dynamic lanes, messages of which need to be pruned by the messages
+	/// pallet.
+	fn lanes_count() -> u32;
+
+	/// Returns dynamic lane with given index (from `0` to `lanes_count() - 1`).
+	fn lane_at(index: u32) -> Option<LaneId>;
+}
+
+impl DynamicLanes for () {
+	fn is_lane_opened(_lane: &LaneId) -> bool {
+		false
+	}
+
+	fn lanes_count() -> u32 {
+		0
+	}
+
+	fn lane_at(_index: u32) -> Option<LaneId> {
+		None
+	}
+}
+
 /// Manages payments that are happening at the source chain during delivery confirmation
 /// transaction.
 pub trait DeliveryConfirmationPayments<AccountId> {
@@ -119,6 +148,58 @@ impl<AccountId> DeliveryConfirmationPayments<AccountId> for () {
 	}
 }
 
+/// Callback that is called when delivery of outbound messages is confirmed by the bridged chain.
+///
+/// It may be used by the pallet that has sent messages (XCM router, asset bridge, ...) to do
+/// some send-side bookkeeping, e.g. to release escrowed funds. The bridged chain doesn't report
+/// dispatch results of delivered messages back, so the confirmation only means that messages
+/// have been received by the bridged chain, not that they have been dispatched successfully.
+///
+/// The weight of the callback is included into the weight of the delivery confirmation call.
+pub trait OnMessagesDelivered {
+	/// Returns weight of the `on_messages_delivered` call for given messages.
+	///
+	/// The lane is unknown when the weight of the delivery confirmation call is computed, so the
+	/// returned value must cover the callback execution at any lane.
+	fn weight(messages: &DeliveredMessages) -> Weight;
+
+	/// Called when delivery of given messages at given lane is confirmed.
+	fn on_messages_delivered(lane: LaneId, messages: &DeliveredMessages);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl OnMessagesDelivered for Tuple {
+	fn weight(messages: &DeliveredMessages) -> Weight {
+		let mut weight = Weight::zero();
+		for_tuples!( #( weight.saturating_accrue(Tuple::weight(messages)); )* );
+		weight
+	}
+
+	fn on_messages_delivered(lane: LaneId, messages: &DeliveredMessages) {
+		for_tuples!( #( Tuple::on_messages_delivered(lane, messages); )* );
+	}
+}
+
+/// Routes delivery confirmations of messages, sent over given `Lane`, to the `Callback`.
+///
+/// Tuples of `LaneDeliveryCallback` may be used to build per-lane callback registry, e.g.
+/// `(LaneDeliveryCallback<XcmLane, XcmRouter>, LaneDeliveryCallback<AssetsLane, AssetsBridge>)`.
+pub struct LaneDeliveryCallback<Lane, Callback>(PhantomData<(Lane, Callback)>);
+
+impl<Lane: Get<LaneId>, Callback: OnMessagesDelivered> OnMessagesDelivered
+	for LaneDeliveryCallback<Lane, Callback>
+{
+	fn weight(messages: &DeliveredMessages) -> Weight {
+		Callback::weight(messages)
+	}
+
+	fn on_messages_delivered(lane: LaneId, messages: &DeliveredMessages) {
+		if lane == Lane::get() {
+			Callback::on_messages_delivered(lane, messages)
+		}
+	}
+}
+
 /// Send message artifacts.
 #[derive(Eq, RuntimeDebug, PartialEq)]
 pub struct SendMessageArtifacts {
@@ -142,6 +142,39 @@ impl<C: Parachain> ParaStoredHeaderDataBuilder for SingleParaStoredHeaderDataBui
 	}
 }
 
+/// Helper for building head data of any parachain that is using headers of given chain.
+///
+/// Unlike `SingleParaStoredHeaderDataBuilder`, it ignores the parachain id. It is supposed to be
+/// used for parachains that are tracked dynamically, i.e. that are added to the tracked set of
+/// the pallet at runtime. The number of such parachains is bounded by the pallet itself, so
+/// this builder doesn't declare any supported parachains.
+pub struct AnyParaStoredHeaderDataBuilder<C: Chain>(PhantomData<C>);
+
+impl<C: Chain> ParaStoredHeaderDataBuilder for AnyParaStoredHeaderDataBuilder<C> {
+	fn supported_parachains() -> u32 {
+		0
+	}
+
+	fn try_build(_para_id: ParaId, para_head: &ParaHead) -> Option<ParaStoredHeaderData> {
+		let header = HeaderOf::<C>::decode(&mut &para_head.0[..]).ok()?;
+		Some(ParaStoredHeaderData(
+			StoredHeaderData { number: *header.number(), state_root: *header.state_root() }
+				.encode(),
+		))
+	}
+}
+
+// Builder that doesn't support any parachains.
+impl ParaStoredHeaderDataBuilder for () {
+	fn supported_parachains() -> u32 {
+		0
+	}
+
+	fn try_build(_para_id: ParaId, _para_head: &ParaHead) -> Option<ParaStoredHeaderData> {
+		None
+	}
+}
+
 // Tries to build header data from each tuple member, short-circuiting on first successful one.
 #[impl_trait_for_tuples::impl_for_tuples(1, 30)]
 #[tuple_types_custom_trait_bound(Parachain)]
@@ -20,7 +20,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use bp_messages::LaneId;
-use bp_runtime::{ChainId, StorageDoubleMapKeyProvider};
+use bp_runtime::{
+	account_derivation::{
+		derive_bridge_account_id, into_account_id, move_account_funds, AccountDerivationVersion,
+		BridgeAccountKind,
+	},
+	ChainId, StorageDoubleMapKeyProvider,
+};
 use frame_support::{Blake2_128Concat, Identity};
 use scale_info::TypeInfo;
 use sp_runtime::{
@@ -78,10 +84,12 @@ pub trait PaymentProcedure<Relayer, Reward> {
 	/// Error that may be returned by the procedure.
 	type Error: Debug;
 
-	/// Pay reward to the relayer from the account with provided params.
+	/// Pay reward to the relayer from the account with provided params. The account (if any) is
+	/// derived using given version of the derivation scheme.
 	fn pay_reward(
 		relayer: &Relayer,
 		rewards_account_params: RewardsAccountParams,
+		account_derivation_version: AccountDerivationVersion,
 		reward: Reward,
 	) -> Result<(), Self::Error>;
 }
@@ -89,7 +97,12 @@ pub trait PaymentProcedure<Relayer, Reward> {
 impl<Relayer, Reward> PaymentProcedure<Relayer, Reward> for () {
 	type Error = &'static str;
 
-	fn pay_reward(_: &Relayer, _: RewardsAccountParams, _: Reward) -> Result<(), Self::Error> {
+	fn pay_reward(
+		_: &Relayer,
+		_: RewardsAccountParams,
+		_: AccountDerivationVersion,
+		_: Reward,
+	) -> Result<(), Self::Error> {
 		Ok(())
 	}
 }
@@ -102,9 +115,42 @@ impl<T, Relayer> PayRewardFromAccount<T, Relayer>
 where
 	Relayer: Decode + Encode,
 {
-	/// Return account that pays rewards based on the provided parameters.
-	pub fn rewards_account(params: RewardsAccountParams) -> Relayer {
-		params.into_sub_account_truncating(b"rewards-account")
+	/// Return account that pays rewards based on the provided parameters, derived using given
+	/// scheme version.
+	pub fn rewards_account_at(
+		params: RewardsAccountParams,
+		version: AccountDerivationVersion,
+	) -> Relayer {
+		match version {
+			AccountDerivationVersion::V0 => params.into_sub_account_truncating(b"rewards-account"),
+			AccountDerivationVersion::V1 => into_account_id(derive_bridge_account_id(
+				BridgeAccountKind::RelayerRewards,
+				params.bridged_chain_id,
+				(params.lane_id, params.owner),
+			)),
+		}
+	}
+}
+
+impl<T, Relayer> PayRewardFromAccount<T, Relayer>
+where
+	T: frame_support::traits::fungible::Inspect<Relayer>
+		+ frame_support::traits::fungible::Transfer<Relayer>,
+	Relayer: Decode + Encode + PartialEq,
+{
+	/// Move all funds from the rewards account, derived using `from_version` of the scheme,
+	/// to the account, derived using `to_version` of the scheme.
+	///
+	/// Shall be called from the runtime migration for every rewards account that has funds.
+	pub fn migrate_rewards_account(
+		params: RewardsAccountParams,
+		from_version: AccountDerivationVersion,
+		to_version: AccountDerivationVersion,
+	) -> Result<T::Balance, sp_runtime::DispatchError> {
+		move_account_funds::<Relayer, T>(
+			&Self::rewards_account_at(params, from_version),
+			&Self::rewards_account_at(params, to_version),
+		)
 	}
 }
 
@@ -118,10 +164,16 @@ where
 	fn pay_reward(
 		relayer: &Relayer,
 		rewards_account_params: RewardsAccountParams,
+		account_derivation_version: AccountDerivationVersion,
 		reward: T::Balance,
 	) -> Result<(), Self::Error> {
-		T::transfer(&Self::rewards_account(rewards_account_params), relayer, reward, false)
-			.map(drop)
+		T::transfer(
+			&Self::rewards_account_at(rewards_account_params, account_derivation_version),
+			relayer,
+			reward,
+			false,
+		)
+		.map(drop)
 	}
 }
 
@@ -149,10 +201,18 @@ mod tests {
 	use bp_messages::LaneId;
 	use sp_runtime::testing::H256;
 
+	fn rewards_account_v0(params: RewardsAccountParams) -> H256 {
+		PayRewardFromAccount::<(), H256>::rewards_account_at(params, AccountDerivationVersion::V0)
+	}
+
+	fn rewards_account_v1(params: RewardsAccountParams) -> H256 {
+		PayRewardFromAccount::<(), H256>::rewards_account_at(params, AccountDerivationVersion::V1)
+	}
+
 	#[test]
 	fn different_lanes_are_using_different_accounts() {
 		assert_eq!(
-			PayRewardFromAccount::<(), H256>::rewards_account(RewardsAccountParams::new(
+			rewards_account_v0(RewardsAccountParams::new(
 				LaneId([0, 0, 0, 0]),
 				*b"test",
 				RewardsAccountOwner::ThisChain
@@ -162,7 +222,7 @@ mod tests {
 		);
 
 		assert_eq!(
-			PayRewardFromAccount::<(), H256>::rewards_account(RewardsAccountParams::new(
+			rewards_account_v0(RewardsAccountParams::new(
 				LaneId([0, 0, 0, 1]),
 				*b"test",
 				RewardsAccountOwner::ThisChain
@@ -175,7 +235,7 @@ mod tests {
 	#[test]
 	fn different_directions_are_using_different_accounts() {
 		assert_eq!(
-			PayRewardFromAccount::<(), H256>::rewards_account(RewardsAccountParams::new(
+			rewards_account_v0(RewardsAccountParams::new(
 				LaneId([0, 0, 0, 0]),
 				*b"test",
 				RewardsAccountOwner::ThisChain
@@ -185,7 +245,7 @@ mod tests {
 		);
 
 		assert_eq!(
-			PayRewardFromAccount::<(), H256>::rewards_account(RewardsAccountParams::new(
+			rewards_account_v0(RewardsAccountParams::new(
 				LaneId([0, 0, 0, 0]),
 				*b"test",
 				RewardsAccountOwner::BridgedChain
@@ -194,4 +254,35 @@ mod tests {
 				.into(),
 		);
 	}
+
+	#[test]
+	fn v1_rewards_accounts_are_different_for_all_params() {
+		let params = [
+			RewardsAccountParams::new(
+				LaneId([0, 0, 0, 0]),
+				*b"test",
+				RewardsAccountOwner::ThisChain,
+			),
+			RewardsAccountParams::new(
+				LaneId([0, 0, 0, 1]),
+				*b"test",
+				RewardsAccountOwner::ThisChain,
+			),
+			RewardsAccountParams::new(
+				LaneId([0, 0, 0, 0]),
+				*b"test",
+				RewardsAccountOwner::BridgedChain,
+			),
+			RewardsAccountParams::new(
+				LaneId([0, 0, 0, 0]),
+				*b"tset",
+				RewardsAccountOwner::ThisChain,
+			),
+		];
+		let accounts = params.iter().map(|p| rewards_account_v1(*p)).collect::<Vec<_>>();
+		for (i, account) in accounts.iter().enumerate() {
+			assert!(!accounts[i + 1..].contains(account));
+			assert_ne!(*account, rewards_account_v0(params[i]));
+		}
+	}
 }
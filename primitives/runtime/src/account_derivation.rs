@@ -0,0 +1,216 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Versioned derivation of bridge-controlled accounts.
+//!
+//! Bridge pallets are controlling several kinds of accounts: sovereign accounts of lanes,
+//! accounts that are paying rewards to relayers and accounts of the bridged chain (its root
+//! and regular users). Initially (we call it `V0` scheme), every kind had its own ad-hoc
+//! derivation, so accounts of different kinds (or of different bridges in the same runtime)
+//! could collide. Starting from the `V1` scheme, all accounts are derived from the single
+//! domain-separated preimage, which includes the scheme version, the account kind, the bridged
+//! chain identifier and the kind-specific parameters.
+//!
+//! The version is selected by the runtime (e.g. using the `AccountDerivationVersion` parameter
+//! of the relayers pallet). Existing runtimes are using the `V0` scheme. When the scheme version
+//! changes, funds of existing accounts must be moved to new accounts. The [`move_account_funds`]
+//! helper may be used by runtime migrations to do that.
+
+use crate::{ChainId, SourceAccount, ACCOUNT_DERIVATION_PREFIX, ROOT_ACCOUNT_DERIVATION_PREFIX};
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::tokens::fungible::{Inspect, Transfer},
+	RuntimeDebug,
+};
+use scale_info::TypeInfo;
+use sp_core::hash::H256;
+use sp_io::hashing::blake2_256;
+use sp_runtime::{
+	traits::{TrailingZeroInput, Zero},
+	DispatchError,
+};
+
+/// A unique prefix for entropy when deriving bridge-controlled accounts using `V1` scheme.
+pub const BRIDGE_ACCOUNT_DERIVATION_PREFIX: &[u8] = b"pallet-bridge/account-derivation/v1";
+
+/// Version of the bridge account derivation scheme.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum AccountDerivationVersion {
+	/// Legacy ad-hoc derivations, that have been used before the scheme has been versioned.
+	V0,
+	/// All accounts are derived using [`derive_bridge_account_id`].
+	V1,
+}
+
+/// Kind of the bridge-controlled account.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum BridgeAccountKind {
+	/// Sovereign account of the lane.
+	LaneSovereign,
+	/// Account that is paying rewards to relayers.
+	RelayerRewards,
+	/// Account of the bridged chain root (privileged origin).
+	BridgedChainRoot,
+	/// Account of the regular bridged chain account.
+	BridgedChainAccount,
+}
+
+/// Derive bridge-controlled account using the `V1` scheme.
+///
+/// This function returns an encoded Blake2 hash. It is the responsibility of the caller to ensure
+/// this can be successfully decoded into an AccountId.
+pub fn derive_bridge_account_id<Params: Encode>(
+	kind: BridgeAccountKind,
+	bridged_chain_id: ChainId,
+	params: Params,
+) -> H256 {
+	(BRIDGE_ACCOUNT_DERIVATION_PREFIX, AccountDerivationVersion::V1, kind, bridged_chain_id, params)
+		.using_encoded(blake2_256)
+		.into()
+}
+
+/// Convert the derived account hash into the `AccountId`.
+///
+/// If `AccountId` is smaller than the hash, the hash is truncated. If it is larger, it is padded
+/// with zeroes.
+pub fn into_account_id<AccountId: Decode>(hash: H256) -> AccountId {
+	AccountId::decode(&mut TrailingZeroInput::new(hash.as_ref()))
+		.expect("infinite length input; no invalid inputs for type; qed")
+}
+
+/// Derive an account ID from a foreign account ID, using given scheme version.
+///
+/// The `V0` derivation is the same as in the [`crate::derive_account_id`] function.
+pub fn derive_bridged_account_id<AccountId: Encode>(
+	version: AccountDerivationVersion,
+	bridge_id: ChainId,
+	id: SourceAccount<AccountId>,
+) -> H256 {
+	match (version, id) {
+		(AccountDerivationVersion::V0, SourceAccount::Root) =>
+			(ROOT_ACCOUNT_DERIVATION_PREFIX, bridge_id).using_encoded(blake2_256).into(),
+		(AccountDerivationVersion::V0, SourceAccount::Account(id)) =>
+			(ACCOUNT_DERIVATION_PREFIX, bridge_id, id).using_encoded(blake2_256).into(),
+		(AccountDerivationVersion::V1, SourceAccount::Root) =>
+			derive_bridge_account_id(BridgeAccountKind::BridgedChainRoot, bridge_id, ()),
+		(AccountDerivationVersion::V1, SourceAccount::Account(id)) =>
+			derive_bridge_account_id(BridgeAccountKind::BridgedChainAccount, bridge_id, id),
+	}
+}
+
+/// Move all funds from the account, derived using previous scheme version, to the account,
+/// derived using new scheme version.
+///
+/// Returns the amount that has been moved. This function shall be called from the runtime
+/// migration, once for every bridge-controlled account. The caller is responsible for
+/// accounting the weight (one read and two writes).
+pub fn move_account_funds<AccountId, Currency>(
+	old_account: &AccountId,
+	new_account: &AccountId,
+) -> Result<Currency::Balance, DispatchError>
+where
+	AccountId: PartialEq,
+	Currency: Inspect<AccountId> + Transfer<AccountId>,
+{
+	if old_account == new_account {
+		return Ok(Zero::zero())
+	}
+
+	let amount = Currency::reducible_balance(old_account, false);
+	if amount.is_zero() {
+		return Ok(amount)
+	}
+
+	Currency::transfer(old_account, new_account, amount, false)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const CHAIN_ID: ChainId = *b"test";
+	const OTHER_CHAIN_ID: ChainId = *b"tset";
+
+	#[test]
+	fn different_kinds_are_using_different_accounts() {
+		let accounts = [
+			derive_bridge_account_id(BridgeAccountKind::LaneSovereign, CHAIN_ID, [0u8; 4]),
+			derive_bridge_account_id(BridgeAccountKind::RelayerRewards, CHAIN_ID, [0u8; 4]),
+			derive_bridge_account_id(BridgeAccountKind::BridgedChainRoot, CHAIN_ID, [0u8; 4]),
+			derive_bridge_account_id(BridgeAccountKind::BridgedChainAccount, CHAIN_ID, [0u8; 4]),
+		];
+		for (i, account) in accounts.iter().enumerate() {
+			assert!(!accounts[i + 1..].contains(account));
+		}
+	}
+
+	#[test]
+	fn different_bridges_are_using_different_accounts() {
+		assert_ne!(
+			derive_bridge_account_id(BridgeAccountKind::LaneSovereign, CHAIN_ID, [0u8; 4]),
+			derive_bridge_account_id(BridgeAccountKind::LaneSovereign, OTHER_CHAIN_ID, [0u8; 4]),
+		);
+	}
+
+	#[test]
+	fn v0_bridged_account_derivation_is_not_changed() {
+		assert_eq!(
+			derive_bridged_account_id::<u64>(
+				AccountDerivationVersion::V0,
+				CHAIN_ID,
+				SourceAccount::Root
+			),
+			crate::derive_account_id::<u64>(CHAIN_ID, SourceAccount::Root),
+		);
+		assert_eq!(
+			derive_bridged_account_id(
+				AccountDerivationVersion::V0,
+				CHAIN_ID,
+				SourceAccount::Account(42u64)
+			),
+			crate::derive_account_id(CHAIN_ID, SourceAccount::Account(42u64)),
+		);
+	}
+
+	#[test]
+	fn v1_bridged_account_derivation_differs_from_v0() {
+		assert_ne!(
+			derive_bridged_account_id::<u64>(
+				AccountDerivationVersion::V1,
+				CHAIN_ID,
+				SourceAccount::Root
+			),
+			derive_bridged_account_id::<u64>(
+				AccountDerivationVersion::V0,
+				CHAIN_ID,
+				SourceAccount::Root
+			),
+		);
+		assert_ne!(
+			derive_bridged_account_id(
+				AccountDerivationVersion::V1,
+				CHAIN_ID,
+				SourceAccount::Account(42u64)
+			),
+			derive_bridged_account_id(
+				AccountDerivationVersion::V0,
+				CHAIN_ID,
+				SourceAccount::Account(42u64)
+			),
+		);
+	}
+}
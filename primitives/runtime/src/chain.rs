@@ -271,10 +271,15 @@ pub type TransactionEraOf<C> = crate::TransactionEra<BlockNumberOf<C>, HashOf<C>
 ///     - `<ThisChain>FinalityApi`
 /// - constants that are stringified names of runtime API methods:
 ///     - `BEST_FINALIZED_<THIS_CHAIN>_HEADER_METHOD`
+///     - `<THIS_CHAIN>_CURRENT_<CONSENSUS>_AUTHORITY_SET_METHOD` (optional)
 /// The name of the chain has to be specified in snake case (e.g. `rialto_parachain`).
+///
+/// If the chain has its own finality (e.g. GRANDPA), the name of the consensus engine and the
+/// type of its authority set may be specified (e.g. `grandpa => bp_header_chain::AuthoritySet`).
+/// Then the `current_<consensus>_authority_set` method is added to the API.
 #[macro_export]
 macro_rules! decl_bridge_finality_runtime_apis {
-	($chain: ident) => {
+	($chain: ident $(, $consensus: ident => $authority_set: ty)?) => {
 		bp_runtime::paste::item! {
 			mod [<$chain _finality_api>] {
 				use super::*;
@@ -283,6 +288,13 @@ macro_rules! decl_bridge_finality_runtime_apis {
 				pub const [<BEST_FINALIZED_ $chain:upper _HEADER_METHOD>]: &str =
 					stringify!([<$chain:camel FinalityApi_best_finalized>]);
 
+				$(
+					/// Name of the `<ThisChain>FinalityApi::current_<consensus>_authority_set`
+					/// runtime method.
+					pub const [<$chain:upper _CURRENT_ $consensus:upper _AUTHORITY_SET_METHOD>]: &str =
+						stringify!([<$chain:camel FinalityApi_current_ $consensus:lower _authority_set>]);
+				)?
+
 				sp_api::decl_runtime_apis! {
 					/// API for querying information about the finalized chain headers.
 					///
@@ -291,6 +303,12 @@ macro_rules! decl_bridge_finality_runtime_apis {
 					pub trait [<$chain:camel FinalityApi>] {
 						/// Returns number and hash of the best finalized header known to the bridge module.
 						fn best_finalized() -> Option<bp_runtime::HeaderId<Hash, BlockNumber>>;
+						$(
+							/// Returns the current authority set of the chain, tracked by the bridge
+							/// module.
+							#[api_version(2)]
+							fn [<current_ $consensus:lower _authority_set>]() -> $authority_set;
+						)?
 					}
 				}
 			}
@@ -307,6 +325,8 @@ macro_rules! decl_bridge_finality_runtime_apis {
 ///     - `From<ThisChain>InboundLaneApi`
 /// - constants that are stringified names of runtime API methods:
 ///     - `FROM_<THIS_CHAIN>_MESSAGE_DETAILS_METHOD`,
+///     - `FROM_<THIS_CHAIN>_MESSAGES_DELIVERY_LIMITS_METHOD`,
+///     - `TO_<THIS_CHAIN>_LANE_STATISTICS_METHOD`,
 /// The name of the chain has to be specified in snake case (e.g. `rialto_parachain`).
 #[macro_export]
 macro_rules! decl_bridge_messages_runtime_apis {
@@ -319,15 +339,24 @@ macro_rules! decl_bridge_messages_runtime_apis {
 				pub const [<TO_ $chain:upper _MESSAGE_DETAILS_METHOD>]: &str =
 					stringify!([<To $chain:camel OutboundLaneApi_message_details>]);
 
+				/// Name of the `To<ThisChain>OutboundLaneApi::lane_statistics` runtime method.
+				pub const [<TO_ $chain:upper _LANE_STATISTICS_METHOD>]: &str =
+					stringify!([<To $chain:camel OutboundLaneApi_lane_statistics>]);
+
 				/// Name of the `From<ThisChain>InboundLaneApi::message_details` runtime method.
 				pub const [<FROM_ $chain:upper _MESSAGE_DETAILS_METHOD>]: &str =
 					stringify!([<From $chain:camel InboundLaneApi_message_details>]);
 
+				/// Name of the `From<ThisChain>InboundLaneApi::delivery_limits` runtime method.
+				pub const [<FROM_ $chain:upper _MESSAGES_DELIVERY_LIMITS_METHOD>]: &str =
+					stringify!([<From $chain:camel InboundLaneApi_delivery_limits>]);
+
 				sp_api::decl_runtime_apis! {
 					/// Outbound message lane API for messages that are sent to this chain.
 					///
 					/// This API is implemented by runtimes that are receiving messages from this chain, not by this
 					/// chain's runtime itself.
+					#[api_version(2)]
 					pub trait [<To $chain:camel OutboundLaneApi>] {
 						/// Returns dispatch weight, encoded payload size and delivery+dispatch fee of all
 						/// messages in given inclusive range.
@@ -339,6 +368,9 @@ macro_rules! decl_bridge_messages_runtime_apis {
 							begin: MessageNonce,
 							end: MessageNonce,
 						) -> Vec<OutboundMessageDetails>;
+						/// Returns aggregate statistics of the given lane.
+						#[api_version(2)]
+						fn lane_statistics(lane: LaneId) -> bp_messages::LaneStatistics;
 					}
 
 					/// Inbound message lane API for messages sent by this chain.
@@ -348,12 +380,16 @@ macro_rules! decl_bridge_messages_runtime_apis {
 					///
 					/// Entries of the resulting vector are matching entries of the `messages` vector. Entries of the
 					/// `messages` vector may (and need to) be read using `To<ThisChain>OutboundLaneApi::message_details`.
+					#[api_version(2)]
 					pub trait [<From $chain:camel InboundLaneApi>] {
 						/// Return details of given inbound messages.
 						fn message_details(
 							lane: LaneId,
 							messages: Vec<(MessagePayload, OutboundMessageDetails)>,
 						) -> Vec<InboundMessageDetails>;
+						/// Return limits of the messages delivery, enforced by the runtime.
+						#[api_version(2)]
+						fn delivery_limits() -> bp_messages::MessagesDeliveryLimits;
 					}
 				}
 			}
@@ -365,11 +401,12 @@ macro_rules! decl_bridge_messages_runtime_apis {
 
 /// Convenience macro that declares bridge finality runtime apis, bridge messages runtime apis
 /// and related constants for a chain.
-/// The name of the chain has to be specified in snake case (e.g. `rialto_parachain`).
+/// The name of the chain has to be specified in snake case (e.g. `rialto_parachain`). Optional
+/// consensus arguments are passed to the `decl_bridge_finality_runtime_apis` macro.
 #[macro_export]
 macro_rules! decl_bridge_runtime_apis {
-	($chain: ident) => {
-		bp_runtime::decl_bridge_finality_runtime_apis!($chain);
+	($chain: ident $(, $consensus: ident => $authority_set: ty)?) => {
+		bp_runtime::decl_bridge_finality_runtime_apis!($chain $(, $consensus => $authority_set)?);
 		bp_runtime::decl_bridge_messages_runtime_apis!($chain);
 	};
 }
@@ -38,14 +38,16 @@ pub use chain::{
 pub use frame_support::storage::storage_prefix as storage_value_final_key;
 use num_traits::{CheckedSub, One};
 pub use storage_proof::{
-	record_all_keys as record_all_trie_keys, Error as StorageProofError,
-	ProofSize as StorageProofSize, RawStorageProof, StorageProofChecker,
+	record_all_keys as record_all_trie_keys, record_keys as record_trie_keys,
+	Error as StorageProofError, ProofSize as StorageProofSize, RawStorageProof,
+	StorageProofChecker,
 };
 pub use storage_types::BoundedStorageValue;
 
 #[cfg(feature = "std")]
 pub use storage_proof::craft_valid_storage_proof;
 
+pub mod account_derivation;
 pub mod extensions;
 pub mod messages;
 
@@ -92,6 +94,9 @@ pub const BRIDGE_HUB_ROCOCO_CHAIN_ID: ChainId = *b"bhro";
 /// Bridge-with-BridgeHubWococo instance id.
 pub const BRIDGE_HUB_WOCOCO_CHAIN_ID: ChainId = *b"bhwo";
 
+/// Bridge-with-BridgeHubWestend instance id.
+pub const BRIDGE_HUB_WESTEND_CHAIN_ID: ChainId = *b"bhwd";
+
 /// Call-dispatch module prefix.
 pub const CALL_DISPATCH_MODULE_PREFIX: &[u8] = b"pallet-bridge/dispatch";
 
@@ -186,6 +191,9 @@ pub enum SourceAccount<T> {
 /// Note: If the same `bridge_id` is used across different chains (for example, if one source chain
 /// is bridged to multiple target chains), then all the derived accounts would be the same across
 /// the different chains. This could negatively impact users' privacy across chains.
+///
+/// This is the `V0` derivation scheme. Use [`account_derivation::derive_bridged_account_id`] to
+/// derive accounts using other scheme versions.
 pub fn derive_account_id<AccountId>(bridge_id: ChainId, id: SourceAccount<AccountId>) -> H256
 where
 	AccountId: Encode,
@@ -202,15 +202,41 @@ where
 		trie.get(&key)?;
 	}
 
+	Ok(drain_recorded_nodes(recorder))
+}
+
+/// Record given keys for a given root.
+///
+/// Unlike [`record_all_keys`], the proof only contains nodes that are required to read given keys,
+/// so the trie may contain other (unrelated) keys.
+pub fn record_keys<L: TrieConfiguration, DB>(
+	db: &DB,
+	root: &TrieHash<L>,
+	keys: &[Vec<u8>],
+) -> Result<RawStorageProof, Box<TrieError<L>>>
+where
+	DB: hash_db::HashDBRef<L::Hash, trie_db::DBValue>,
+{
+	let mut recorder = Recorder::<L>::new();
+	let trie = TrieDBBuilder::<L>::new(db, root).with_recorder(&mut recorder).build();
+	for key in keys {
+		trie.get(key)?;
+	}
+
+	Ok(drain_recorded_nodes(recorder))
+}
+
+/// Return all nodes, recorded by the recorder.
+fn drain_recorded_nodes<L: TrieConfiguration>(mut recorder: Recorder<L>) -> RawStorageProof {
 	// recorder may record the same trie node multiple times and we don't want duplicate nodes
 	// in our proofs => let's deduplicate it by collecting to the BTreeSet first
-	Ok(recorder
+	recorder
 		.drain()
 		.into_iter()
 		.map(|n| n.data.to_vec())
 		.collect::<BTreeSet<_>>()
 		.into_iter()
-		.collect())
+		.collect()
 }
 
 #[cfg(test)]
@@ -16,11 +16,15 @@
 
 //! Millau chain specification for CLI.
 
-use crate::cli::{bridge, encode_message::CliEncodeMessage, CliChain};
+use crate::cli::{
+	bridge, claim_rewards::CliClaimRewards, encode_message::CliEncodeMessage,
+	relayer_funds::CliTransfer, CliChain,
+};
+use bp_relayers::RewardsAccountParams;
 use bp_rialto_parachain::RIALTO_PARACHAIN_ID;
 use bp_runtime::EncodedOrDecodedCall;
 use relay_millau_client::Millau;
-use relay_substrate_client::SimpleRuntimeVersion;
+use relay_substrate_client::{AccountIdOf, BalanceOf, SimpleRuntimeVersion};
 use xcm::latest::prelude::*;
 
 impl CliEncodeMessage for Millau {
@@ -52,6 +56,46 @@ impl CliEncodeMessage for Millau {
 	}
 }
 
+impl CliClaimRewards for Millau {
+	const RELAYERS_PALLET_NAME: &'static str = "BridgeRelayers";
+
+	fn encode_claim_rewards_call(
+		rewards_account_params: RewardsAccountParams,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(millau_runtime::RuntimeCall::BridgeRelayers(
+			millau_runtime::BridgeRelayersCall::claim_rewards { rewards_account_params },
+		)
+		.into())
+	}
+}
+
+impl CliTransfer for Millau {
+	fn existential_deposit() -> BalanceOf<Self> {
+		millau_runtime::ExistentialDeposit::get()
+	}
+
+	fn encode_transfer_call(
+		beneficiary: AccountIdOf<Self>,
+		amount: BalanceOf<Self>,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(millau_runtime::RuntimeCall::Balances(
+			millau_runtime::BalancesCall::transfer_keep_alive { dest: beneficiary, value: amount },
+		)
+		.into())
+	}
+
+	fn encode_transfer_all_call(
+		beneficiary: AccountIdOf<Self>,
+		keep_alive: bool,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(millau_runtime::RuntimeCall::Balances(millau_runtime::BalancesCall::transfer_all {
+			dest: beneficiary,
+			keep_alive,
+		})
+		.into())
+	}
+}
+
 impl CliChain for Millau {
 	const RUNTIME_VERSION: Option<SimpleRuntimeVersion> =
 		Some(SimpleRuntimeVersion::from_runtime_version(&millau_runtime::VERSION));
@@ -0,0 +1,49 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Millau-to-Rialto BEEFY commitments sync entrypoint.
+
+use crate::cli::bridge::{CliBridgeBase, RelayToRelayBeefyCliBridge};
+use substrate_relay_helper::beefy::{
+	DirectSubmitBeefyCommitmentCallBuilder, SubstrateBeefySyncPipeline,
+};
+
+/// Description of Millau -> Rialto BEEFY commitments bridge.
+#[derive(Clone, Debug)]
+pub struct MillauBeefyToRialto;
+
+impl SubstrateBeefySyncPipeline for MillauBeefyToRialto {
+	type SourceChain = relay_millau_client::Millau;
+	type TargetChain = relay_rialto_client::Rialto;
+
+	type SubmitBeefyCommitmentCallBuilder = DirectSubmitBeefyCommitmentCallBuilder<
+		Self,
+		rialto_runtime::Runtime,
+		rialto_runtime::MillauBeefyInstance,
+	>;
+}
+
+//// `Millau` to `Rialto` BEEFY bridge definition.
+pub struct MillauToRialtoBeefyCliBridge {}
+
+impl CliBridgeBase for MillauToRialtoBeefyCliBridge {
+	type Source = relay_millau_client::Millau;
+	type Target = relay_rialto_client::Rialto;
+}
+
+impl RelayToRelayBeefyCliBridge for MillauToRialtoBeefyCliBridge {
+	type Beefy = MillauBeefyToRialto;
+}
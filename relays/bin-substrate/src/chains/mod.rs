@@ -15,9 +15,20 @@
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
 //! Chain-specific relayer configuration.
+//!
+//! Every supported bridge (chain pair) is described by a compiled-in module. Chains, described by
+//! a configuration file, are not supported: chain names, tokens, bridge pallet names and runtime
+//! API method names are associated constants of the `relay_substrate_client::Chain` trait family,
+//! and call and signed extensions encoding is defined by the associated types. So the generic
+//! relays can't be instantiated for a chain pair that is only known at runtime. Supporting that
+//! would require moving those constants to the runtime values, which the `--config` file
+//! (see `cli::config_file`) could then provide.
 
+pub mod bridge_hub_rococo_messages_to_bridge_hub_westend;
 pub mod bridge_hub_rococo_messages_to_bridge_hub_wococo;
+pub mod bridge_hub_westend_messages_to_bridge_hub_rococo;
 pub mod bridge_hub_wococo_messages_to_bridge_hub_rococo;
+pub mod millau_beefy_to_rialto;
 pub mod millau_headers_to_rialto;
 pub mod millau_headers_to_rialto_parachain;
 pub mod millau_messages_to_rialto;
@@ -26,9 +37,13 @@ pub mod rialto_headers_to_millau;
 pub mod rialto_messages_to_millau;
 pub mod rialto_parachain_messages_to_millau;
 pub mod rialto_parachains_to_millau;
+pub mod rococo_headers_to_bridge_hub_westend;
 pub mod rococo_headers_to_bridge_hub_wococo;
+pub mod rococo_parachains_to_bridge_hub_westend;
 pub mod rococo_parachains_to_bridge_hub_wococo;
+pub mod westend_headers_to_bridge_hub_rococo;
 pub mod westend_headers_to_millau;
+pub mod westend_parachains_to_bridge_hub_rococo;
 pub mod westend_parachains_to_millau;
 pub mod wococo_headers_to_bridge_hub_rococo;
 pub mod wococo_parachains_to_bridge_hub_rococo;
@@ -16,10 +16,14 @@
 
 //! Rialto chain specification for CLI.
 
-use crate::cli::{bridge, encode_message::CliEncodeMessage, CliChain};
+use crate::cli::{
+	bridge, claim_rewards::CliClaimRewards, encode_message::CliEncodeMessage,
+	relayer_funds::CliTransfer, CliChain,
+};
+use bp_relayers::RewardsAccountParams;
 use bp_runtime::EncodedOrDecodedCall;
 use relay_rialto_client::Rialto;
-use relay_substrate_client::SimpleRuntimeVersion;
+use relay_substrate_client::{AccountIdOf, BalanceOf, SimpleRuntimeVersion};
 use xcm::latest::prelude::*;
 
 impl CliEncodeMessage for Rialto {
@@ -44,6 +48,49 @@ impl CliEncodeMessage for Rialto {
 	}
 }
 
+impl CliClaimRewards for Rialto {
+	const RELAYERS_PALLET_NAME: &'static str = "BridgeRelayers";
+
+	fn encode_claim_rewards_call(
+		rewards_account_params: RewardsAccountParams,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(rialto_runtime::RuntimeCall::BridgeRelayers(
+			rialto_runtime::BridgeRelayersCall::claim_rewards { rewards_account_params },
+		)
+		.into())
+	}
+}
+
+impl CliTransfer for Rialto {
+	fn existential_deposit() -> BalanceOf<Self> {
+		rialto_runtime::ExistentialDeposit::get()
+	}
+
+	fn encode_transfer_call(
+		beneficiary: AccountIdOf<Self>,
+		amount: BalanceOf<Self>,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(rialto_runtime::RuntimeCall::Balances(
+			rialto_runtime::BalancesCall::transfer_keep_alive {
+				dest: beneficiary.into(),
+				value: amount,
+			},
+		)
+		.into())
+	}
+
+	fn encode_transfer_all_call(
+		beneficiary: AccountIdOf<Self>,
+		keep_alive: bool,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(rialto_runtime::RuntimeCall::Balances(rialto_runtime::BalancesCall::transfer_all {
+			dest: beneficiary.into(),
+			keep_alive,
+		})
+		.into())
+	}
+}
+
 impl CliChain for Rialto {
 	const RUNTIME_VERSION: Option<SimpleRuntimeVersion> =
 		Some(SimpleRuntimeVersion::from_runtime_version(&rialto_runtime::VERSION));
@@ -14,9 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Westend chain specification for CLI.
+//! Westend + Westend parachains specification for CLI.
 
 use crate::cli::CliChain;
+use relay_bridge_hub_westend_client::BridgeHubWestend;
 use relay_substrate_client::SimpleRuntimeVersion;
 use relay_westend_client::{Westend, Westmint};
 
@@ -27,3 +28,8 @@ impl CliChain for Westend {
 impl CliChain for Westmint {
 	const RUNTIME_VERSION: Option<SimpleRuntimeVersion> = None;
 }
+
+impl CliChain for BridgeHubWestend {
+	const RUNTIME_VERSION: Option<SimpleRuntimeVersion> =
+		Some(SimpleRuntimeVersion { spec_version: 9372, transaction_version: 1 });
+}
@@ -0,0 +1,583 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	chains::{
+		bridge_hub_rococo_messages_to_bridge_hub_westend::BridgeHubRococoToBridgeHubWestendMessagesCliBridge,
+		bridge_hub_rococo_messages_to_bridge_hub_wococo::BridgeHubRococoToBridgeHubWococoMessagesCliBridge,
+		bridge_hub_westend_messages_to_bridge_hub_rococo::BridgeHubWestendToBridgeHubRococoMessagesCliBridge,
+		bridge_hub_wococo_messages_to_bridge_hub_rococo::BridgeHubWococoToBridgeHubRococoMessagesCliBridge,
+		millau_headers_to_rialto::MillauToRialtoCliBridge,
+		millau_headers_to_rialto_parachain::MillauToRialtoParachainCliBridge,
+		rialto_headers_to_millau::RialtoToMillauCliBridge,
+		rialto_parachains_to_millau::RialtoParachainToMillauCliBridge,
+	},
+	cli::{
+		bridge::{FullBridge, MessagesCliBridge, RelayToRelayHeadersCliBridge},
+		chain_schema::*,
+		encode_message::compute_maximal_message_size,
+		estimate_fee::{
+			dummy_signer, dummy_transaction, ConfirmationCallBuilderOf, DeliveryCallBuilderOf,
+		},
+		HexLaneId,
+	},
+};
+use async_trait::async_trait;
+use bp_header_chain::FinalityProof;
+use bp_messages::{
+	storage_keys::{inbound_lane_data_key, message_key, outbound_lane_data_key},
+	total_unrewarded_messages, InboundLaneData, LaneId, MessageNonce, OutboundLaneData,
+	UnrewardedRelayersState,
+};
+use bp_runtime::EncodedOrDecodedCall;
+use bridge_runtime_common::messages::{
+	source::FromBridgedChainMessagesDeliveryProof, target::FromBridgedChainMessagesProof,
+};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::weights::Weight;
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, Chain, ChainWithMessages, ChainWithTransactions, Client, HashOf,
+	SyncHeader,
+};
+use sp_core::Pair;
+use structopt::StructOpt;
+use strum::VariantNames;
+use substrate_relay_helper::{
+	finality::{engine::Engine, SubmitFinalityProofCallBuilder, SubstrateFinalitySyncPipeline},
+	messages_lane::{ReceiveMessagesDeliveryProofCallBuilder, ReceiveMessagesProofCallBuilder},
+};
+
+/// Build bridge proofs using the live chains state and print their sizes and weights.
+///
+/// Typical proofs are built from the actual state of the message lane and from the actual
+/// finality proofs of the source chain. Worst-case proofs are built for the maximal message
+/// and for the maximal number of unrewarded relayers that the runtime accepts.
+#[derive(StructOpt)]
+pub struct BenchmarkProof {
+	/// A bridge instance to benchmark proofs of.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	/// Hex-encoded id of the lane, which state is used to build proofs. Defaults to `00000000`.
+	#[structopt(long, default_value = "00000000")]
+	lane: HexLaneId,
+	/// Maximal number of latest lane messages, included in the typical messages proof.
+	#[structopt(long, default_value = "1")]
+	messages: MessageNonce,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+}
+
+/// Size and weight of the transaction with the proof.
+struct ProofBenchmark {
+	/// Size of the encoded proof.
+	proof_size: usize,
+	/// Size of the encoded transaction.
+	transaction_size: usize,
+	/// Weight of the transaction, computed by the runtime.
+	weight: Weight,
+}
+
+#[async_trait]
+trait ProofsBenchmarker: MessagesCliBridge
+where
+	Self::Source: ChainWithTransactions + ChainWithMessages,
+	Self::Target: ChainWithTransactions + ChainWithMessages,
+	AccountIdOf<Self::Source>:
+		From<<AccountKeyPairOf<Self::Source> as Pair>::Public> + MaxEncodedLen,
+{
+	/// Benchmark finality proofs of the source chain at the target chain.
+	///
+	/// Returns `None` if the bridge doesn't have direct finality relay between chains.
+	async fn benchmark_finality_proofs(
+		_source_client: &Client<Self::Source>,
+		_target_client: &Client<Self::Target>,
+	) -> anyhow::Result<Option<(ProofBenchmark, ProofBenchmark)>> {
+		Ok(None)
+	}
+
+	async fn benchmark_proofs(data: BenchmarkProof) -> anyhow::Result<()> {
+		let source_client = data.source.into_client::<Self::Source>().await?;
+		let target_client = data.target.into_client::<Self::Target>().await?;
+		let lane: LaneId = data.lane.into();
+
+		match typical_messages_proof::<Self>(&source_client, &target_client, lane, data.messages)
+			.await?
+		{
+			Some((nonces, benchmark)) => print_benchmark::<Self::Target>(
+				&format!("Typical proof of messages {:?}", nonces),
+				benchmark,
+			),
+			None => log::info!(
+				target: "bridge",
+				"There are no messages at {} lane {} - skipping typical messages proof",
+				Self::Source::NAME,
+				hex::encode(lane),
+			),
+		}
+		print_benchmark::<Self::Target>(
+			"Worst-case proof of maximal message",
+			worst_case_messages_proof::<Self>(&target_client, lane).await?,
+		);
+
+		print_benchmark::<Self::Source>(
+			"Typical messages delivery proof",
+			typical_delivery_proof::<Self>(&source_client, &target_client, lane).await?,
+		);
+		print_benchmark::<Self::Source>(
+			"Worst-case messages delivery proof",
+			worst_case_delivery_proof::<Self>(&source_client, lane).await?,
+		);
+
+		match Self::benchmark_finality_proofs(&source_client, &target_client).await? {
+			Some((typical, worst_case)) => {
+				print_benchmark::<Self::Target>("Typical (optimized) finality proof", typical);
+				print_benchmark::<Self::Target>("Worst-case (full) finality proof", worst_case);
+			},
+			None => log::info!(
+				target: "bridge",
+				"{} headers are not directly relayed to {} - skipping finality proofs",
+				Self::Source::NAME,
+				Self::Target::NAME,
+			),
+		}
+
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl ProofsBenchmarker for MillauToRialtoCliBridge {
+	async fn benchmark_finality_proofs(
+		source_client: &Client<Self::Source>,
+		target_client: &Client<Self::Target>,
+	) -> anyhow::Result<Option<(ProofBenchmark, ProofBenchmark)>> {
+		finality_proofs::<<Self as RelayToRelayHeadersCliBridge>::Finality>(
+			source_client,
+			target_client,
+		)
+		.await
+		.map(Some)
+	}
+}
+#[async_trait]
+impl ProofsBenchmarker for RialtoToMillauCliBridge {
+	async fn benchmark_finality_proofs(
+		source_client: &Client<Self::Source>,
+		target_client: &Client<Self::Target>,
+	) -> anyhow::Result<Option<(ProofBenchmark, ProofBenchmark)>> {
+		finality_proofs::<<Self as RelayToRelayHeadersCliBridge>::Finality>(
+			source_client,
+			target_client,
+		)
+		.await
+		.map(Some)
+	}
+}
+#[async_trait]
+impl ProofsBenchmarker for MillauToRialtoParachainCliBridge {
+	async fn benchmark_finality_proofs(
+		source_client: &Client<Self::Source>,
+		target_client: &Client<Self::Target>,
+	) -> anyhow::Result<Option<(ProofBenchmark, ProofBenchmark)>> {
+		finality_proofs::<<Self as RelayToRelayHeadersCliBridge>::Finality>(
+			source_client,
+			target_client,
+		)
+		.await
+		.map(Some)
+	}
+}
+impl ProofsBenchmarker for RialtoParachainToMillauCliBridge {}
+impl ProofsBenchmarker for BridgeHubRococoToBridgeHubWococoMessagesCliBridge {}
+impl ProofsBenchmarker for BridgeHubWococoToBridgeHubRococoMessagesCliBridge {}
+impl ProofsBenchmarker for BridgeHubRococoToBridgeHubWestendMessagesCliBridge {}
+impl ProofsBenchmarker for BridgeHubWestendToBridgeHubRococoMessagesCliBridge {}
+
+impl BenchmarkProof {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		match self.bridge {
+			FullBridge::MillauToRialto => MillauToRialtoCliBridge::benchmark_proofs(self),
+			FullBridge::RialtoToMillau => RialtoToMillauCliBridge::benchmark_proofs(self),
+			FullBridge::MillauToRialtoParachain =>
+				MillauToRialtoParachainCliBridge::benchmark_proofs(self),
+			FullBridge::RialtoParachainToMillau =>
+				RialtoParachainToMillauCliBridge::benchmark_proofs(self),
+			FullBridge::BridgeHubRococoToBridgeHubWococo =>
+				BridgeHubRococoToBridgeHubWococoMessagesCliBridge::benchmark_proofs(self),
+			FullBridge::BridgeHubWococoToBridgeHubRococo =>
+				BridgeHubWococoToBridgeHubRococoMessagesCliBridge::benchmark_proofs(self),
+			FullBridge::BridgeHubRococoToBridgeHubWestend =>
+				BridgeHubRococoToBridgeHubWestendMessagesCliBridge::benchmark_proofs(self),
+			FullBridge::BridgeHubWestendToBridgeHubRococo =>
+				BridgeHubWestendToBridgeHubRococoMessagesCliBridge::benchmark_proofs(self),
+		}
+		.await
+	}
+}
+
+/// Build proof of latest messages, stored at the best finalized source chain block.
+///
+/// Returns `None` if there are no messages stored at the source chain.
+async fn typical_messages_proof<B>(
+	source_client: &Client<B::Source>,
+	target_client: &Client<B::Target>,
+	lane: LaneId,
+	max_messages: MessageNonce,
+) -> anyhow::Result<Option<(std::ops::RangeInclusive<MessageNonce>, ProofBenchmark)>>
+where
+	B: ProofsBenchmarker,
+	B::Source: ChainWithTransactions + ChainWithMessages,
+	B::Target: ChainWithTransactions + ChainWithMessages,
+	AccountIdOf<B::Source>: From<<AccountKeyPairOf<B::Source> as Pair>::Public> + MaxEncodedLen,
+{
+	let at_block = source_client.best_finalized_header_hash().await?;
+	let outbound_lane_key =
+		outbound_lane_data_key(B::Target::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane);
+	let outbound_lane_data: OutboundLaneData = source_client
+		.storage_value(outbound_lane_key.clone(), Some(at_block))
+		.await?
+		.unwrap_or_default();
+	let nonces = match select_latest_nonces(&outbound_lane_data, max_messages) {
+		Some(nonces) => nonces,
+		None => return Ok(None),
+	};
+
+	let mut storage_keys = nonces
+		.clone()
+		.map(|nonce| message_key(B::Target::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane, nonce))
+		.collect::<Vec<_>>();
+	storage_keys.push(outbound_lane_key);
+	let storage_proof: Vec<Vec<u8>> = source_client
+		.prove_storage(storage_keys, at_block)
+		.await?
+		.into_iter_nodes()
+		.collect();
+
+	let proof = FromBridgedChainMessagesProof {
+		bridged_header_hash: at_block,
+		storage_proof,
+		lane,
+		nonces_start: *nonces.start(),
+		nonces_end: *nonces.end(),
+	};
+	messages_proof_benchmark::<B>(target_client, proof)
+		.await
+		.map(|b| Some((nonces, b)))
+}
+
+/// Build proof of single message of maximal size.
+async fn worst_case_messages_proof<B>(
+	target_client: &Client<B::Target>,
+	lane: LaneId,
+) -> anyhow::Result<ProofBenchmark>
+where
+	B: ProofsBenchmarker,
+	B::Source: ChainWithTransactions + ChainWithMessages,
+	B::Target: ChainWithTransactions + ChainWithMessages,
+	AccountIdOf<B::Source>: From<<AccountKeyPairOf<B::Source> as Pair>::Public> + MaxEncodedLen,
+{
+	let maximal_message_size = compute_maximal_message_size(
+		B::Source::max_extrinsic_size(),
+		B::Target::max_extrinsic_size(),
+	);
+	let proof = FromBridgedChainMessagesProof {
+		bridged_header_hash: Default::default(),
+		storage_proof: vec![vec![
+			42u8;
+			(maximal_message_size + pallet_bridge_messages::EXTRA_STORAGE_PROOF_SIZE)
+				as usize
+		]],
+		lane,
+		nonces_start: 1,
+		nonces_end: 1,
+	};
+	messages_proof_benchmark::<B>(target_client, proof).await
+}
+
+/// Build proof of the inbound lane state at the best finalized target chain block.
+async fn typical_delivery_proof<B>(
+	source_client: &Client<B::Source>,
+	target_client: &Client<B::Target>,
+	lane: LaneId,
+) -> anyhow::Result<ProofBenchmark>
+where
+	B: ProofsBenchmarker,
+	B::Source: ChainWithTransactions + ChainWithMessages,
+	B::Target: ChainWithTransactions + ChainWithMessages,
+	AccountIdOf<B::Source>: From<<AccountKeyPairOf<B::Source> as Pair>::Public> + MaxEncodedLen,
+{
+	let at_block = target_client.best_finalized_header_hash().await?;
+	let inbound_lane_key = inbound_lane_data_key(B::Source::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane);
+	let inbound_lane_data: InboundLaneData<AccountIdOf<B::Source>> = target_client
+		.storage_value(inbound_lane_key.clone(), Some(at_block))
+		.await?
+		.unwrap_or_default();
+	let storage_proof: Vec<Vec<u8>> = target_client
+		.prove_storage(vec![inbound_lane_key], at_block)
+		.await?
+		.into_iter_nodes()
+		.collect();
+
+	let relayers_state = unrewarded_relayers_state(&inbound_lane_data);
+	let proof = FromBridgedChainMessagesDeliveryProof {
+		bridged_header_hash: at_block,
+		storage_proof,
+		lane,
+	};
+	delivery_proof_benchmark::<B>(source_client, relayers_state, proof).await
+}
+
+/// Build delivery proof with maximal number of unrewarded relayers and messages.
+async fn worst_case_delivery_proof<B>(
+	source_client: &Client<B::Source>,
+	lane: LaneId,
+) -> anyhow::Result<ProofBenchmark>
+where
+	B: ProofsBenchmarker,
+	B::Source: ChainWithTransactions + ChainWithMessages,
+	B::Target: ChainWithTransactions + ChainWithMessages,
+	AccountIdOf<B::Source>: From<<AccountKeyPairOf<B::Source> as Pair>::Public> + MaxEncodedLen,
+{
+	let max_relayers = B::Source::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
+	let max_messages = B::Source::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	let inbound_lane_data_size =
+		InboundLaneData::<AccountIdOf<B::Source>>::encoded_size_hint_u32(max_relayers as usize);
+	let relayers_state = UnrewardedRelayersState {
+		unrewarded_relayer_entries: max_relayers,
+		messages_in_oldest_entry: 1,
+		total_messages: max_messages,
+		last_delivered_nonce: max_messages,
+	};
+	let proof = FromBridgedChainMessagesDeliveryProof {
+		bridged_header_hash: Default::default(),
+		storage_proof: vec![vec![
+			42u8;
+			inbound_lane_data_size.saturating_add(pallet_bridge_messages::EXTRA_STORAGE_PROOF_SIZE)
+				as usize
+		]],
+		lane,
+	};
+	delivery_proof_benchmark::<B>(source_client, relayers_state, proof).await
+}
+
+/// Read next finality proof of the source chain and benchmark both optimized proof, that is
+/// submitted by the relay, and the original proof.
+async fn finality_proofs<P: SubstrateFinalitySyncPipeline>(
+	source_client: &Client<P::SourceChain>,
+	target_client: &Client<P::TargetChain>,
+) -> anyhow::Result<(ProofBenchmark, ProofBenchmark)> {
+	let finality_proofs = P::FinalityEngine::finality_proofs(source_client).await?;
+	let encoded_proof = finality_proofs.next().await?.ok_or_else(|| {
+		anyhow::format_err!("{} finality proofs subscription has ended", P::SourceChain::NAME)
+	})?;
+	let full_proof = <P::FinalityEngine as Engine<P::SourceChain>>::FinalityProof::decode(
+		&mut &encoded_proof.0[..],
+	)
+	.map_err(|e| {
+		anyhow::format_err!("Failed to decode {} finality proof: {:?}", P::SourceChain::NAME, e)
+	})?;
+	let header = source_client.header_by_number(full_proof.target_header_number()).await?;
+	let optimized_proof =
+		P::FinalityEngine::optimize_proof(target_client, &header, full_proof.clone()).await?;
+
+	let typical =
+		finality_proof_benchmark::<P>(target_client, header.clone(), optimized_proof).await?;
+	let worst_case = finality_proof_benchmark::<P>(target_client, header, full_proof).await?;
+	Ok((typical, worst_case))
+}
+
+/// Benchmark messages delivery transaction with given proof.
+async fn messages_proof_benchmark<B>(
+	target_client: &Client<B::Target>,
+	proof: FromBridgedChainMessagesProof<HashOf<B::Source>>,
+) -> anyhow::Result<ProofBenchmark>
+where
+	B: ProofsBenchmarker,
+	B::Source: ChainWithTransactions + ChainWithMessages,
+	B::Target: ChainWithTransactions + ChainWithMessages,
+	AccountIdOf<B::Source>: From<<AccountKeyPairOf<B::Source> as Pair>::Public> + MaxEncodedLen,
+{
+	let proof_size = proof.encoded_size();
+	let messages_count = proof.nonces_end - proof.nonces_start + 1;
+	// dispatch weight is declared by the relayer and it is added to the transaction weight, so
+	// we're using zero here to get the weight of proof verification only
+	let call = DeliveryCallBuilderOf::<B>::build_receive_messages_proof_call(
+		dummy_signer::<B::Source>()?.public().into(),
+		(Weight::zero(), proof),
+		messages_count as u32,
+		Weight::zero(),
+		false,
+	);
+	transaction_benchmark::<B::Target>(target_client, proof_size, call.into()).await
+}
+
+/// Benchmark messages delivery confirmation transaction with given proof.
+async fn delivery_proof_benchmark<B>(
+	source_client: &Client<B::Source>,
+	relayers_state: UnrewardedRelayersState,
+	proof: FromBridgedChainMessagesDeliveryProof<HashOf<B::Target>>,
+) -> anyhow::Result<ProofBenchmark>
+where
+	B: ProofsBenchmarker,
+	B::Source: ChainWithTransactions + ChainWithMessages,
+	B::Target: ChainWithTransactions + ChainWithMessages,
+	AccountIdOf<B::Source>: From<<AccountKeyPairOf<B::Source> as Pair>::Public> + MaxEncodedLen,
+{
+	let proof_size = proof.encoded_size();
+	let call = ConfirmationCallBuilderOf::<B>::build_receive_messages_delivery_proof_call(
+		(relayers_state, proof),
+		false,
+	);
+	transaction_benchmark::<B::Source>(source_client, proof_size, call.into()).await
+}
+
+/// Benchmark finality proof submission transaction with given proof.
+async fn finality_proof_benchmark<P: SubstrateFinalitySyncPipeline>(
+	target_client: &Client<P::TargetChain>,
+	header: <P::SourceChain as Chain>::Header,
+	proof: <P::FinalityEngine as Engine<P::SourceChain>>::FinalityProof,
+) -> anyhow::Result<ProofBenchmark> {
+	let proof_size = proof.encoded_size();
+	let call = P::SubmitFinalityProofCallBuilder::build_submit_finality_proof_call(
+		SyncHeader::from(header),
+		proof,
+	);
+	transaction_benchmark::<P::TargetChain>(target_client, proof_size, call.into()).await
+}
+
+/// Ask runtime to compute weight of the transaction with given call.
+async fn transaction_benchmark<C: ChainWithTransactions>(
+	client: &Client<C>,
+	proof_size: usize,
+	call: EncodedOrDecodedCall<C::Call>,
+) -> anyhow::Result<ProofBenchmark> {
+	let transaction = dummy_transaction::<C>(dummy_signer::<C>()?, call)?;
+	let transaction_size = transaction.encoded_size();
+	let weight = client.extimate_extrinsic_weight(transaction).await.map_err(|e| {
+		anyhow::format_err!("Failed to compute {} transaction weight: {:?}", C::NAME, e)
+	})?;
+	Ok(ProofBenchmark { proof_size, transaction_size, weight })
+}
+
+/// Print proof benchmark results.
+fn print_benchmark<C: Chain>(title: &str, benchmark: ProofBenchmark) {
+	let max_weight = C::max_extrinsic_weight();
+	log::info!(
+		target: "bridge",
+		"{}:\n\
+		\tproof size: {} bytes\n\
+		\ttransaction size: {} bytes ({}% of maximal {} transaction size)\n\
+		\ttransaction weight: ref_time {} ({}% of maximal), proof_size {} ({}% of maximal)",
+		title,
+		benchmark.proof_size,
+		benchmark.transaction_size,
+		percent(benchmark.transaction_size as u64, C::max_extrinsic_size() as u64),
+		C::NAME,
+		benchmark.weight.ref_time(),
+		percent(benchmark.weight.ref_time(), max_weight.ref_time()),
+		benchmark.weight.proof_size(),
+		percent(benchmark.weight.proof_size(), max_weight.proof_size()),
+	);
+}
+
+/// Returns range of latest messages that are still stored at the source chain.
+fn select_latest_nonces(
+	outbound_lane_data: &OutboundLaneData,
+	max_messages: MessageNonce,
+) -> Option<std::ops::RangeInclusive<MessageNonce>> {
+	let latest_nonce = outbound_lane_data.latest_generated_nonce;
+	let oldest_nonce = std::cmp::max(
+		outbound_lane_data.oldest_unpruned_nonce,
+		latest_nonce.saturating_sub(max_messages.saturating_sub(1)),
+	);
+	if max_messages == 0 || latest_nonce == 0 || oldest_nonce > latest_nonce {
+		return None
+	}
+
+	Some(oldest_nonce..=latest_nonce)
+}
+
+/// Compute unrewarded relayers state from the inbound lane data.
+fn unrewarded_relayers_state<RelayerId>(
+	inbound_lane_data: &InboundLaneData<RelayerId>,
+) -> UnrewardedRelayersState {
+	UnrewardedRelayersState {
+		unrewarded_relayer_entries: inbound_lane_data.relayers.len() as _,
+		messages_in_oldest_entry: inbound_lane_data
+			.relayers
+			.front()
+			.map(|entry| entry.messages.total_messages())
+			.unwrap_or(0),
+		total_messages: total_unrewarded_messages(&inbound_lane_data.relayers)
+			.unwrap_or(MessageNonce::MAX),
+		last_delivered_nonce: inbound_lane_data.last_delivered_nonce(),
+	}
+}
+
+/// Returns `value` as a percent of `max`.
+fn percent(value: u64, max: u64) -> u64 {
+	if max == 0 {
+		return 0
+	}
+
+	(value as u128 * 100 / max as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn benchmark_proof_params_are_parsed() {
+		let benchmark_proof = BenchmarkProof::from_iter(vec![
+			"benchmark-proof",
+			"rialto-to-millau",
+			"--lane",
+			"00000001",
+			"--messages",
+			"16",
+			"--source-port",
+			"1234",
+			"--target-port",
+			"5678",
+		]);
+
+		assert_eq!(benchmark_proof.bridge, FullBridge::RialtoToMillau);
+		assert_eq!(benchmark_proof.lane, HexLaneId([0, 0, 0, 1]));
+		assert_eq!(benchmark_proof.messages, 16);
+		assert_eq!(benchmark_proof.source.source_port, 1234);
+		assert_eq!(benchmark_proof.target.target_port, 5678);
+	}
+
+	#[test]
+	fn latest_nonces_are_selected() {
+		let lane_data = |oldest_unpruned_nonce, latest_generated_nonce| OutboundLaneData {
+			oldest_unpruned_nonce,
+			latest_received_nonce: 0,
+			latest_generated_nonce,
+		};
+
+		assert_eq!(select_latest_nonces(&lane_data(1, 0), 16), None);
+		assert_eq!(select_latest_nonces(&lane_data(11, 10), 16), None);
+		assert_eq!(select_latest_nonces(&lane_data(1, 10), 0), None);
+		assert_eq!(select_latest_nonces(&lane_data(1, 10), 1), Some(10..=10));
+		assert_eq!(select_latest_nonces(&lane_data(1, 10), 4), Some(7..=10));
+		assert_eq!(select_latest_nonces(&lane_data(5, 10), 16), Some(5..=10));
+	}
+}
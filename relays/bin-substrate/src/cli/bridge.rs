@@ -16,11 +16,11 @@
 
 use crate::cli::CliChain;
 use pallet_bridge_parachains::{RelayBlockHash, RelayBlockHasher, RelayBlockNumber};
-use relay_substrate_client::{Chain, ChainWithTransactions, Parachain, RelayChain};
+use relay_substrate_client::{Chain, ChainWithBeefy, ChainWithTransactions, Parachain, RelayChain};
 use strum::{EnumString, EnumVariantNames};
 use substrate_relay_helper::{
-	finality::SubstrateFinalitySyncPipeline, messages_lane::SubstrateMessageLane,
-	parachains::SubstrateParachainsPipeline,
+	beefy::SubstrateBeefySyncPipeline, finality::SubstrateFinalitySyncPipeline,
+	messages_lane::SubstrateMessageLane, parachains::SubstrateParachainsPipeline,
 };
 
 #[derive(Debug, PartialEq, Eq, EnumString, EnumVariantNames)]
@@ -33,6 +33,8 @@ pub enum FullBridge {
 	RialtoParachainToMillau,
 	BridgeHubRococoToBridgeHubWococo,
 	BridgeHubWococoToBridgeHubRococo,
+	BridgeHubRococoToBridgeHubWestend,
+	BridgeHubWestendToBridgeHubRococo,
 }
 
 impl FullBridge {
@@ -43,7 +45,10 @@ impl FullBridge {
 			Self::RialtoToMillau => RIALTO_TO_MILLAU_INDEX,
 			Self::MillauToRialtoParachain => MILLAU_TO_RIALTO_PARACHAIN_INDEX,
 			Self::RialtoParachainToMillau => RIALTO_PARACHAIN_TO_MILLAU_INDEX,
-			Self::BridgeHubRococoToBridgeHubWococo | Self::BridgeHubWococoToBridgeHubRococo =>
+			Self::BridgeHubRococoToBridgeHubWococo |
+			Self::BridgeHubWococoToBridgeHubRococo |
+			Self::BridgeHubRococoToBridgeHubWestend |
+			Self::BridgeHubWestendToBridgeHubRococo =>
 				unimplemented!("Relay doesn't support send-message subcommand on bridge hubs"),
 		}
 	}
@@ -73,6 +78,16 @@ pub trait RelayToRelayHeadersCliBridge: CliBridgeBase {
 	>;
 }
 
+/// Bridge representation that can be used from the CLI for relaying BEEFY commitments
+/// from a relay chain to a relay chain.
+pub trait RelayToRelayBeefyCliBridge: CliBridgeBase
+where
+	Self::Source: ChainWithBeefy,
+{
+	/// BEEFY commitments synchronization pipeline.
+	type Beefy: SubstrateBeefySyncPipeline<SourceChain = Self::Source, TargetChain = Self::Target>;
+}
+
 /// Bridge representation that can be used from the CLI for relaying headers
 /// from a parachain to a relay chain.
 pub trait ParachainToRelayHeadersCliBridge: CliBridgeBase
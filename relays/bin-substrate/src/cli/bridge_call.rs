@@ -0,0 +1,391 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Encoding and decoding of bridge calls.
+//!
+//! Useful when triaging failed bridge transactions: calls and transactions, copied from the
+//! block explorer, may be decoded and printed and calls may be constructed from their parts.
+
+use crate::{
+	chains::{
+		bridge_hub_rococo_messages_to_bridge_hub_westend::BridgeHubRococoToBridgeHubWestendMessagesCliBridge,
+		bridge_hub_rococo_messages_to_bridge_hub_wococo::BridgeHubRococoToBridgeHubWococoMessagesCliBridge,
+		bridge_hub_westend_messages_to_bridge_hub_rococo::BridgeHubWestendToBridgeHubRococoMessagesCliBridge,
+		bridge_hub_wococo_messages_to_bridge_hub_rococo::BridgeHubWococoToBridgeHubRococoMessagesCliBridge,
+		millau_headers_to_rialto::MillauToRialtoCliBridge,
+		millau_headers_to_rialto_parachain::MillauToRialtoParachainCliBridge,
+		rialto_headers_to_millau::RialtoToMillauCliBridge,
+		rialto_parachains_to_millau::RialtoParachainToMillauCliBridge,
+	},
+	cli::{
+		bridge::{FullBridge, MessagesCliBridge},
+		HexBytes, HexLaneId,
+	},
+};
+use bp_messages::{MessageNonce, UnrewardedRelayersState};
+use bridge_runtime_common::messages::{
+	source::FromBridgedChainMessagesDeliveryProof, target::FromBridgedChainMessagesProof,
+};
+use codec::{DecodeAll, Encode};
+use frame_support::weights::Weight;
+use relay_substrate_client::{AccountIdOf, CallOf, Chain, ChainWithTransactions, HashOf};
+use sp_runtime::AccountId32;
+use structopt::StructOpt;
+use strum::{EnumString, EnumVariantNames, VariantNames};
+use substrate_relay_helper::{
+	messages_lane::{
+		ReceiveMessagesDeliveryProofCallBuilder, ReceiveMessagesProofCallBuilder,
+		SubstrateMessageLane,
+	},
+	messages_source::SubstrateMessagesProof,
+	messages_target::SubstrateMessagesDeliveryProof,
+};
+
+/// Decode hex-encoded bridge call or transaction and print it.
+#[derive(StructOpt)]
+pub struct DecodeCall {
+	/// A bridge instance, which call is decoded.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	/// Side of the bridge, where the call is dispatched. Messages delivery and finality calls
+	/// are dispatched at the target chain and delivery confirmations - at the source chain.
+	#[structopt(long, default_value = "target", possible_values = BridgeSide::VARIANTS)]
+	side: BridgeSide,
+	/// Hex-encoded call or transaction (with or without `0x` prefix).
+	call: HexBytes,
+}
+
+/// Construct bridge call from parts and print its hex encoding.
+#[derive(StructOpt)]
+pub struct EncodeCall {
+	/// A bridge instance, which call is encoded.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	#[structopt(subcommand)]
+	call: BridgeCall,
+}
+
+/// Side of the bridge.
+#[derive(Debug, EnumString, EnumVariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "kebab_case")]
+pub enum BridgeSide {
+	/// Source chain of the bridge.
+	Source,
+	/// Target chain of the bridge.
+	Target,
+}
+
+/// Bridge calls that may be constructed from parts.
+#[derive(StructOpt, Debug, PartialEq, Eq)]
+pub enum BridgeCall {
+	/// Messages delivery (`receive_messages_proof`) call at the target chain.
+	ReceiveMessagesProof {
+		/// Relayer account at the source chain, which will be rewarded for delivery.
+		#[structopt(long)]
+		relayer: AccountId32,
+		/// Hex-encoded hash of the source chain header the proof is generated for.
+		#[structopt(long)]
+		bridged_header_hash: HexBytes,
+		/// Comma-separated hex-encoded trie nodes of the storage proof.
+		#[structopt(long, use_delimiter = true)]
+		storage_proof: Vec<HexBytes>,
+		/// Hex-encoded lane id. Defaults to `00000000`.
+		#[structopt(long, default_value = "00000000")]
+		lane: HexLaneId,
+		/// Nonce of the first delivered message.
+		#[structopt(long)]
+		nonces_start: MessageNonce,
+		/// Nonce of the last delivered message.
+		#[structopt(long)]
+		nonces_end: MessageNonce,
+		/// Weight (`ref_time` component) of all delivered messages dispatch.
+		#[structopt(long)]
+		dispatch_weight: u64,
+	},
+	/// Messages delivery confirmation (`receive_messages_delivery_proof`) call at the source
+	/// chain.
+	ReceiveMessagesDeliveryProof {
+		/// Hex-encoded hash of the target chain header the proof is generated for.
+		#[structopt(long)]
+		bridged_header_hash: HexBytes,
+		/// Comma-separated hex-encoded trie nodes of the storage proof.
+		#[structopt(long, use_delimiter = true)]
+		storage_proof: Vec<HexBytes>,
+		/// Hex-encoded lane id. Defaults to `00000000`.
+		#[structopt(long, default_value = "00000000")]
+		lane: HexLaneId,
+		/// Number of unrewarded relayer entries at the target chain.
+		#[structopt(long)]
+		unrewarded_relayer_entries: MessageNonce,
+		/// Number of messages in the oldest unrewarded relayer entry.
+		#[structopt(long)]
+		messages_in_oldest_entry: MessageNonce,
+		/// Total number of messages in unrewarded relayer entries.
+		#[structopt(long)]
+		total_messages: MessageNonce,
+		/// Nonce of the latest message, delivered to the target chain.
+		#[structopt(long)]
+		last_delivered_nonce: MessageNonce,
+	},
+}
+
+trait BridgeCallCodec: MessagesCliBridge
+where
+	Self::Source: ChainWithTransactions,
+	AccountIdOf<Self::Source>: From<AccountId32>,
+{
+	/// Decode call or transaction of given bridge side.
+	fn decode_call(side: BridgeSide, data: &[u8]) -> anyhow::Result<String> {
+		match side {
+			BridgeSide::Source => decode_call_or_transaction::<Self::Source>(data),
+			BridgeSide::Target => decode_call_or_transaction::<Self::Target>(data),
+		}
+	}
+
+	/// Construct bridge call and return its pretty-printed and encoded forms.
+	fn encode_call(call: BridgeCall) -> anyhow::Result<(String, Vec<u8>)> {
+		match call {
+			BridgeCall::ReceiveMessagesProof {
+				relayer,
+				bridged_header_hash,
+				storage_proof,
+				lane,
+				nonces_start,
+				nonces_end,
+				dispatch_weight,
+			} => {
+				let dispatch_weight = Weight::from_parts(dispatch_weight, 0);
+				let messages_count = nonces_end
+					.checked_sub(nonces_start)
+					.and_then(|count| count.checked_add(1))
+					.and_then(|count| u32::try_from(count).ok())
+					.ok_or_else(|| {
+						anyhow::format_err!(
+							"Invalid nonces range: {}..={}",
+							nonces_start,
+							nonces_end
+						)
+					})?;
+				let proof = FromBridgedChainMessagesProof {
+					bridged_header_hash: decode_hash::<Self::Source>(&bridged_header_hash)?,
+					storage_proof: storage_proof.into_iter().map(|node| node.0).collect(),
+					lane: lane.into(),
+					nonces_start,
+					nonces_end,
+				};
+				let call = receive_messages_proof_call::<Self::MessagesLane>(
+					relayer.into(),
+					(dispatch_weight, proof),
+					messages_count,
+					dispatch_weight,
+				);
+				Ok(call_with_encoding::<Self::Target>(call))
+			},
+			BridgeCall::ReceiveMessagesDeliveryProof {
+				bridged_header_hash,
+				storage_proof,
+				lane,
+				unrewarded_relayer_entries,
+				messages_in_oldest_entry,
+				total_messages,
+				last_delivered_nonce,
+			} => {
+				let relayers_state = UnrewardedRelayersState {
+					unrewarded_relayer_entries,
+					messages_in_oldest_entry,
+					total_messages,
+					last_delivered_nonce,
+				};
+				let proof = FromBridgedChainMessagesDeliveryProof {
+					bridged_header_hash: decode_hash::<Self::Target>(&bridged_header_hash)?,
+					storage_proof: storage_proof.into_iter().map(|node| node.0).collect(),
+					lane: lane.into(),
+				};
+				let call = receive_messages_delivery_proof_call::<Self::MessagesLane>((
+					relayers_state,
+					proof,
+				));
+				Ok(call_with_encoding::<Self::Source>(call))
+			},
+		}
+	}
+}
+
+impl BridgeCallCodec for MillauToRialtoCliBridge {}
+impl BridgeCallCodec for RialtoToMillauCliBridge {}
+impl BridgeCallCodec for MillauToRialtoParachainCliBridge {}
+impl BridgeCallCodec for RialtoParachainToMillauCliBridge {}
+impl BridgeCallCodec for BridgeHubRococoToBridgeHubWococoMessagesCliBridge {}
+impl BridgeCallCodec for BridgeHubWococoToBridgeHubRococoMessagesCliBridge {}
+impl BridgeCallCodec for BridgeHubRococoToBridgeHubWestendMessagesCliBridge {}
+impl BridgeCallCodec for BridgeHubWestendToBridgeHubRococoMessagesCliBridge {}
+
+macro_rules! select_bridge {
+	($bridge: expr, $generic: tt) => {
+		match $bridge {
+			FullBridge::MillauToRialto => {
+				type Bridge = MillauToRialtoCliBridge;
+				$generic
+			},
+			FullBridge::RialtoToMillau => {
+				type Bridge = RialtoToMillauCliBridge;
+				$generic
+			},
+			FullBridge::MillauToRialtoParachain => {
+				type Bridge = MillauToRialtoParachainCliBridge;
+				$generic
+			},
+			FullBridge::RialtoParachainToMillau => {
+				type Bridge = RialtoParachainToMillauCliBridge;
+				$generic
+			},
+			FullBridge::BridgeHubRococoToBridgeHubWococo => {
+				type Bridge = BridgeHubRococoToBridgeHubWococoMessagesCliBridge;
+				$generic
+			},
+			FullBridge::BridgeHubWococoToBridgeHubRococo => {
+				type Bridge = BridgeHubWococoToBridgeHubRococoMessagesCliBridge;
+				$generic
+			},
+			FullBridge::BridgeHubRococoToBridgeHubWestend => {
+				type Bridge = BridgeHubRococoToBridgeHubWestendMessagesCliBridge;
+				$generic
+			},
+			FullBridge::BridgeHubWestendToBridgeHubRococo => {
+				type Bridge = BridgeHubWestendToBridgeHubRococoMessagesCliBridge;
+				$generic
+			},
+		}
+	};
+}
+
+impl DecodeCall {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		let decoded =
+			select_bridge!(self.bridge, { Bridge::decode_call(self.side, &self.call.0)? });
+		log::info!(target: "bridge", "{}", decoded);
+		Ok(())
+	}
+}
+
+impl EncodeCall {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		let (decoded, encoded) = select_bridge!(self.bridge, { Bridge::encode_call(self.call)? });
+		log::info!(target: "bridge", "{}", decoded);
+		log::info!(target: "bridge", "Encoded call: {:?}", HexBytes(encoded));
+		Ok(())
+	}
+}
+
+/// Decode SCALE-encoded call or signed transaction of given chain and pretty-print it.
+fn decode_call_or_transaction<C: ChainWithTransactions>(data: &[u8]) -> anyhow::Result<String> {
+	if let Ok(call) = CallOf::<C>::decode_all(&mut &data[..]) {
+		return Ok(format!("{} call: {:#?}", C::NAME, call))
+	}
+	if let Ok(transaction) = C::SignedTransaction::decode_all(&mut &data[..]) {
+		return Ok(format!("{} transaction: {:#?}", C::NAME, transaction))
+	}
+
+	Err(anyhow::format_err!("Failed to decode {} call or transaction", C::NAME))
+}
+
+/// Build `receive_messages_proof` call of given messages lane.
+fn receive_messages_proof_call<P: SubstrateMessageLane>(
+	relayer_id_at_source: AccountIdOf<P::SourceChain>,
+	proof: SubstrateMessagesProof<P::SourceChain>,
+	messages_count: u32,
+	dispatch_weight: Weight,
+) -> CallOf<P::TargetChain> {
+	P::ReceiveMessagesProofCallBuilder::build_receive_messages_proof_call(
+		relayer_id_at_source,
+		proof,
+		messages_count,
+		dispatch_weight,
+		false,
+	)
+}
+
+/// Build `receive_messages_delivery_proof` call of given messages lane.
+fn receive_messages_delivery_proof_call<P: SubstrateMessageLane>(
+	proof: SubstrateMessagesDeliveryProof<P::TargetChain>,
+) -> CallOf<P::SourceChain> {
+	P::ReceiveMessagesDeliveryProofCallBuilder::build_receive_messages_delivery_proof_call(
+		proof, false,
+	)
+}
+
+/// Decode hash of given chain header.
+fn decode_hash<C: Chain>(hash: &HexBytes) -> anyhow::Result<HashOf<C>> {
+	HashOf::<C>::decode_all(&mut &hash.0[..])
+		.map_err(|e| anyhow::format_err!("Invalid {} header hash {:?}: {:?}", C::NAME, hash, e))
+}
+
+/// Returns pretty-printed and SCALE-encoded forms of the call.
+fn call_with_encoding<C: Chain>(call: CallOf<C>) -> (String, Vec<u8>) {
+	(format!("{} call: {:#?}", C::NAME, call), call.encode())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn encoded_messages_delivery_call_is_decoded() {
+		let encode_call = EncodeCall::from_iter(vec![
+			"encode-call",
+			"millau-to-rialto",
+			"receive-messages-proof",
+			"--relayer",
+			"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+			"--bridged-header-hash",
+			"0x0101010101010101010101010101010101010101010101010101010101010101",
+			"--storage-proof",
+			"0x0102,0x0304",
+			"--lane",
+			"00000001",
+			"--nonces-start",
+			"10",
+			"--nonces-end",
+			"12",
+			"--dispatch-weight",
+			"1000",
+		]);
+		assert_eq!(encode_call.bridge, FullBridge::MillauToRialto);
+
+		let (_, encoded) = MillauToRialtoCliBridge::encode_call(encode_call.call).unwrap();
+		let decoded = MillauToRialtoCliBridge::decode_call(BridgeSide::Target, &encoded).unwrap();
+		assert!(decoded.starts_with("Rialto call:"));
+		assert!(decoded.contains("nonces_start: 10"));
+		assert!(decoded.contains("nonces_end: 12"));
+		assert!(decoded.contains("messages_count: 3"));
+	}
+
+	#[test]
+	fn invalid_nonces_range_is_rejected() {
+		let call = BridgeCall::ReceiveMessagesProof {
+			relayer: AccountId32::new([0u8; 32]),
+			bridged_header_hash: HexBytes(vec![0u8; 32]),
+			storage_proof: vec![],
+			lane: HexLaneId([0, 0, 0, 0]),
+			nonces_start: 12,
+			nonces_end: 10,
+			dispatch_weight: 0,
+		};
+		assert!(MillauToRialtoCliBridge::encode_call(call).is_err());
+	}
+}
@@ -104,6 +104,36 @@ macro_rules! declare_chain_connection_params_cli_schema {
 				#[doc = "Custom runtime version"]
 				#[structopt(flatten)]
 				pub [<$chain_prefix _runtime_version>]: [<$chain RuntimeVersionParams>],
+				#[doc = "Tip (in " $chain " base units) that is added to all transactions, submitted to " $chain "."]
+				#[structopt(long)]
+				pub [<$chain_prefix _tip>]: Option<u128>,
+				#[doc = "If specified, the tip is dynamic: it is multiplied by the " $chain " next fee multiplier and by given number of percents."]
+				#[structopt(long)]
+				pub [<$chain_prefix _tip_multiplier_percent>]: Option<u32>,
+				#[doc = "Mortality period (in blocks) of all signed transactions, submitted to " $chain ", unless it is overridden by the " $chain_prefix "_transactions_mortality option. MUST be a power of two in [4; 65536] range. Transactions are immortal by default."]
+				#[structopt(long)]
+				pub [<$chain_prefix _default_transactions_mortality>]: Option<u32>,
+				#[doc = "Submit bridge transactions (headers, parachain heads and messages) to " $chain " as unsigned transactions, validated by the bridge pallets. The relayer account is not charged for such transactions."]
+				#[structopt(long)]
+				pub [<$chain_prefix _unsigned_transactions>]: bool,
+				#[doc = "PEM file with CA certificates, that are trusted (in addition to system root certificates) when connecting to " $chain " node over secure websocket connection."]
+				#[structopt(long)]
+				pub [<$chain_prefix _tls_ca_certificates>]: Option<std::path::PathBuf>,
+				#[doc = "PEM file with the client certificate chain, used to authenticate the relay at the " $chain " node (or TLS terminator)."]
+				#[structopt(long)]
+				pub [<$chain_prefix _tls_client_certificate>]: Option<std::path::PathBuf>,
+				#[doc = "PEM file with the private key of the " $chain " client certificate."]
+				#[structopt(long)]
+				pub [<$chain_prefix _tls_client_key>]: Option<std::path::PathBuf>,
+				#[doc = "Server name, used to verify the " $chain " node certificate (SNI). Host name is used by default."]
+				#[structopt(long)]
+				pub [<$chain_prefix _tls_server_name>]: Option<String>,
+				#[doc = "Connect to " $chain " node through given proxy: `<socks5|http>://[<user>:<password>@]<host>:<port>`."]
+				#[structopt(long)]
+				pub [<$chain_prefix _proxy>]: Option<relay_substrate_client::ProxyParams>,
+				#[doc = "Maximal number of RPC requests per second, sent to the " $chain " node. Use it when the node is rate-limited. Finality subscriptions have priority over proofs and transactions, which have priority over other requests."]
+				#[structopt(long)]
+				pub [<$chain_prefix _max_requests_per_second>]: Option<std::num::NonZeroU32>,
 			}
 
 			impl [<$chain ConnectionParams>] {
@@ -114,21 +144,85 @@ macro_rules! declare_chain_connection_params_cli_schema {
 				) -> anyhow::Result<relay_substrate_client::Client<Chain>> {
 					let chain_runtime_version = self
 						.[<$chain_prefix _runtime_version>]
-						.into_runtime_version(Chain::RUNTIME_VERSION)?;
+						.into_runtime_version(<Chain as CliChain>::RUNTIME_VERSION)?;
+					let tip = self.tip_strategy()?;
+					let transactions_mortality =
+						validate_transactions_mortality(self.[<$chain_prefix _default_transactions_mortality>])?;
+					let tls = relay_substrate_client::TlsParams {
+						ca_certificates: self.[<$chain_prefix _tls_ca_certificates>],
+						client_certificate: self.[<$chain_prefix _tls_client_certificate>],
+						client_key: self.[<$chain_prefix _tls_client_key>],
+						server_name: self.[<$chain_prefix _tls_server_name>],
+					};
+					if !tls.is_default() && !self.[<$chain_prefix _secure>] {
+						anyhow::bail!(
+							"The {}-tls-* options require {}-secure",
+							stringify!($chain_prefix),
+							stringify!($chain_prefix),
+						);
+					}
 					Ok(relay_substrate_client::Client::new(relay_substrate_client::ConnectionParams {
 						host: self.[<$chain_prefix _host>],
 						port: self.[<$chain_prefix _port>],
 						secure: self.[<$chain_prefix _secure>],
 						chain_runtime_version,
+						dry_run: false,
+						tip,
+						transactions_mortality,
+						unsigned_transactions: self.[<$chain_prefix _unsigned_transactions>],
+						tls,
+						proxy: self.[<$chain_prefix _proxy>],
+						max_requests_per_second: self.[<$chain_prefix _max_requests_per_second>],
 					})
 					.await
 					)
 				}
+
+				/// Returns strategy of computing tip for transactions, submitted to the chain.
+				pub fn tip_strategy(&self) -> anyhow::Result<relay_substrate_client::TipStrategy> {
+					Ok(match (self.[<$chain_prefix _tip>], self.[<$chain_prefix _tip_multiplier_percent>]) {
+						(None, None) => relay_substrate_client::TipStrategy::NoTip,
+						(Some(tip), None) => relay_substrate_client::TipStrategy::Static(tip),
+						(Some(base_tip), Some(multiplier_percent)) =>
+							relay_substrate_client::TipStrategy::Dynamic {
+								base_tip,
+								multiplier: <sp_runtime::FixedU128 as sp_runtime::FixedPointNumber>::saturating_from_rational(
+									multiplier_percent,
+									100,
+								),
+							},
+						(None, Some(_)) => anyhow::bail!(
+							"The {}-tip is required when {}-tip-multiplier-percent is specified",
+							stringify!($chain_prefix),
+							stringify!($chain_prefix),
+						),
+					})
+				}
 			}
 		}
 	};
 }
 
+/// Ensure that the transactions mortality period is a power of two in [4; 65536] range.
+pub fn validate_transactions_mortality(
+	transactions_mortality: Option<u32>,
+) -> anyhow::Result<Option<u32>> {
+	transactions_mortality
+		.map(|transactions_mortality| {
+			if !(4..=65536).contains(&transactions_mortality) ||
+				!transactions_mortality.is_power_of_two()
+			{
+				Err(anyhow::format_err!(
+					"Transactions mortality {} is not a power of two in a [4; 65536] range",
+					transactions_mortality,
+				))
+			} else {
+				Ok(transactions_mortality)
+			}
+		})
+		.transpose()
+}
+
 /// Helper trait to override transaction parameters differently.
 pub trait TransactionParamsProvider {
 	/// Returns `true` if transaction parameters are defined by this provider.
@@ -173,6 +267,9 @@ macro_rules! declare_chain_signing_params_cli_schema {
 				#[doc = "Path to the file, that password for the SURI of secret key to use when transactions are submitted to the " $chain " node. Can be overridden with " $chain_prefix "_signer_password option."]
 				#[structopt(long)]
 				pub [<$chain_prefix _signer_password_file>]: Option<std::path::PathBuf>,
+				#[doc = "Name of the key in the relay keystore, that is used when transactions are submitted to the " $chain " node. Can be overridden with " $chain_prefix "_signer and " $chain_prefix "_signer_file options."]
+				#[structopt(long)]
+				pub [<$chain_prefix _signer_key>]: Option<String>,
 
 				#[doc = "Transactions mortality period, in blocks. MUST be a power of two in [4; 65536] range. MAY NOT be larger than `BlockHashCount` parameter of the chain system module."]
 				#[structopt(long)]
@@ -183,37 +280,31 @@ macro_rules! declare_chain_signing_params_cli_schema {
 				/// Return transactions mortality.
 				#[allow(dead_code)]
 				pub fn transactions_mortality(&self) -> anyhow::Result<Option<u32>> {
-					self.[<$chain_prefix _transactions_mortality>]
-						.map(|transactions_mortality| {
-							if !(4..=65536).contains(&transactions_mortality)
-								|| !transactions_mortality.is_power_of_two()
-							{
-								Err(anyhow::format_err!(
-									"Transactions mortality {} is not a power of two in a [4; 65536] range",
-									transactions_mortality,
-								))
-							} else {
-								Ok(transactions_mortality)
-							}
-						})
-						.transpose()
+					validate_transactions_mortality(self.[<$chain_prefix _transactions_mortality>])
 				}
 
 				/// Parse signing params into chain-specific KeyPair.
 				#[allow(dead_code)]
 				pub fn to_keypair<Chain: ChainWithTransactions>(&self) -> anyhow::Result<AccountKeyPairOf<Chain>> {
-					let suri = match (self.[<$chain_prefix _signer>].as_ref(), self.[<$chain_prefix _signer_file>].as_ref()) {
-						(Some(suri), _) => suri.to_owned(),
-						(None, Some(suri_file)) => std::fs::read_to_string(suri_file)
+					let suri = match (
+						self.[<$chain_prefix _signer>].as_ref(),
+						self.[<$chain_prefix _signer_file>].as_ref(),
+						self.[<$chain_prefix _signer_key>].as_ref(),
+					) {
+						(Some(suri), _, _) => suri.to_owned(),
+						(None, Some(suri_file), _) => std::fs::read_to_string(suri_file)
 							.map_err(|err| anyhow::format_err!(
 								"Failed to read SURI from file {:?}: {}",
 								suri_file,
 								err,
 							))?,
-						(None, None) => return Err(anyhow::format_err!(
-							"One of options must be specified: '{}' or '{}'",
+						(None, None, Some(key_name)) => $crate::cli::keys::Keystore::new(None)?
+							.read_suri(key_name)?,
+						(None, None, None) => return Err(anyhow::format_err!(
+							"One of options must be specified: '{}', '{}' or '{}'",
 							stringify!([<$chain_prefix _signer>]),
 							stringify!([<$chain_prefix _signer_file>]),
+							stringify!([<$chain_prefix _signer_key>]),
 						)),
 					};
 
@@ -244,7 +335,9 @@ macro_rules! declare_chain_signing_params_cli_schema {
 			#[allow(dead_code)]
 			impl TransactionParamsProvider for [<$chain SigningParams>] {
 				fn is_defined(&self) -> bool {
-					self.[<$chain_prefix _signer>].is_some() || self.[<$chain_prefix _signer_file>].is_some()
+					self.[<$chain_prefix _signer>].is_some() ||
+						self.[<$chain_prefix _signer_file>].is_some() ||
+						self.[<$chain_prefix _signer_key>].is_some()
 				}
 
 				fn transaction_params<Chain: ChainWithTransactions>(&self) -> anyhow::Result<TransactionParams<AccountKeyPairOf<Chain>>> {
@@ -258,6 +351,96 @@ macro_rules! declare_chain_signing_params_cli_schema {
 	};
 }
 
+/// Create chain-specific set of relayer account balance guard parameters.
+#[macro_export]
+macro_rules! declare_chain_balance_guard_params_cli_schema {
+	($chain:ident, $chain_prefix:ident) => {
+		bp_runtime::paste::item! {
+			#[doc = $chain " relayer account balance guard params."]
+			#[derive(StructOpt, Debug, PartialEq, Eq, Clone)]
+			pub struct [<$chain BalanceGuardParams>] {
+				#[doc = "If free balance of the relayer account at " $chain " is below this value (in smallest token units), the relay warns that the account needs to be replenished."]
+				#[structopt(long)]
+				pub [<$chain_prefix _balance_soft_threshold>]: Option<u128>,
+				#[doc = "If free balance of the relayer account at " $chain " is below this value (in smallest token units), the relay stops submitting new transactions to " $chain " until the account is replenished. Already submitted transactions are still tracked."]
+				#[structopt(long)]
+				pub [<$chain_prefix _balance_hard_threshold>]: Option<u128>,
+			}
+
+			impl [<$chain BalanceGuardParams>] {
+				/// Return relayer account balance thresholds, if any of them is specified.
+				#[allow(dead_code)]
+				pub fn balance_thresholds<Chain: relay_substrate_client::Chain>(
+					&self,
+				) -> anyhow::Result<
+					Option<
+						relay_substrate_client::guard::BalanceThresholds<
+							relay_substrate_client::BalanceOf<Chain>,
+						>,
+					>,
+				> {
+					use sp_runtime::traits::UniqueSaturatedInto;
+
+					let (soft, hard) = match (
+						self.[<$chain_prefix _balance_soft_threshold>],
+						self.[<$chain_prefix _balance_hard_threshold>],
+					) {
+						(None, None) => return Ok(None),
+						(soft, hard) => (soft.or(hard).unwrap_or_default(), hard.unwrap_or_default()),
+					};
+					if soft < hard {
+						return Err(anyhow::format_err!(
+							"Soft balance threshold {} is less than hard balance threshold {}",
+							soft,
+							hard,
+						))
+					}
+
+					Ok(Some(relay_substrate_client::guard::BalanceThresholds {
+						soft: soft.unique_saturated_into(),
+						hard: hard.unique_saturated_into(),
+					}))
+				}
+
+				/// Start relayer account balance guard, if any of balance thresholds is specified.
+				#[allow(dead_code)]
+				pub fn start_balance_guard<Chain>(
+					&self,
+					client: &relay_substrate_client::Client<Chain>,
+					signer: &relay_substrate_client::AccountKeyPairOf<Chain>,
+					metrics_params: &relay_utils::metrics::MetricsParams,
+				) -> anyhow::Result<()>
+				where
+					Chain: relay_substrate_client::ChainWithBalances
+						+ relay_substrate_client::ChainWithTransactions,
+					relay_substrate_client::AccountIdOf<Chain>: From<
+						<relay_substrate_client::AccountKeyPairOf<Chain> as sp_core::Pair>::Public,
+					>,
+				{
+					use relay_utils::metrics::Metric;
+
+					let thresholds = match self.balance_thresholds::<Chain>()? {
+						Some(thresholds) => thresholds,
+						None => return Ok(()),
+					};
+
+					let metrics = relay_substrate_client::guard::BalanceGuardMetrics::new(
+						<Chain as relay_substrate_client::Chain>::NAME,
+					)?;
+					metrics.register(&metrics_params.registry)?;
+					relay_substrate_client::guard::halt_submissions_on_low_balance(
+						client.clone(),
+						sp_core::Pair::public(signer).into(),
+						thresholds,
+						Some(metrics),
+					);
+					Ok(())
+				}
+			}
+		}
+	};
+}
+
 /// Create chain-specific set of configuration objects: connection parameters,
 /// signing parameters and bridge initialization parameters.
 #[macro_export]
@@ -266,6 +449,7 @@ macro_rules! declare_chain_cli_schema {
 		$crate::declare_chain_runtime_version_params_cli_schema!($chain, $chain_prefix);
 		$crate::declare_chain_connection_params_cli_schema!($chain, $chain_prefix);
 		$crate::declare_chain_signing_params_cli_schema!($chain, $chain_prefix);
+		$crate::declare_chain_balance_guard_params_cli_schema!($chain, $chain_prefix);
 	};
 }
 
@@ -277,7 +461,79 @@ declare_chain_cli_schema!(Parachain, parachain);
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use relay_substrate_client::{guard::BalanceThresholds, TipStrategy};
 	use sp_core::Pair;
+	use sp_runtime::FixedPointNumber;
+
+	#[test]
+	fn balance_thresholds_are_parsed() {
+		let thresholds = |soft, hard| {
+			TargetBalanceGuardParams {
+				target_balance_soft_threshold: soft,
+				target_balance_hard_threshold: hard,
+			}
+			.balance_thresholds::<relay_rialto_client::Rialto>()
+			.map_err(drop)
+		};
+
+		assert_eq!(thresholds(None, None), Ok(None));
+		assert_eq!(thresholds(Some(100), None), Ok(Some(BalanceThresholds { soft: 100, hard: 0 })));
+		assert_eq!(thresholds(None, Some(10)), Ok(Some(BalanceThresholds { soft: 10, hard: 10 })));
+		assert_eq!(
+			thresholds(Some(100), Some(10)),
+			Ok(Some(BalanceThresholds { soft: 100, hard: 10 }))
+		);
+		assert_eq!(thresholds(Some(10), Some(100)), Err(()));
+	}
+
+	#[test]
+	fn tip_strategy_is_parsed() {
+		let tip_strategy = |tip, tip_multiplier_percent| {
+			TargetConnectionParams {
+				target_host: "127.0.0.1".into(),
+				target_port: 9944,
+				target_secure: false,
+				target_runtime_version: TargetRuntimeVersionParams {
+					target_version_mode: RuntimeVersionType::Bundle,
+					target_spec_version: None,
+					target_transaction_version: None,
+				},
+				target_tip: tip,
+				target_tip_multiplier_percent: tip_multiplier_percent,
+				target_default_transactions_mortality: None,
+				target_unsigned_transactions: false,
+				target_tls_ca_certificates: None,
+				target_tls_client_certificate: None,
+				target_tls_client_key: None,
+				target_tls_server_name: None,
+				target_proxy: None,
+				target_max_requests_per_second: None,
+			}
+			.tip_strategy()
+			.map_err(drop)
+		};
+
+		assert_eq!(tip_strategy(None, None), Ok(TipStrategy::NoTip));
+		assert_eq!(tip_strategy(Some(100), None), Ok(TipStrategy::Static(100)));
+		assert_eq!(
+			tip_strategy(Some(100), Some(150)),
+			Ok(TipStrategy::Dynamic {
+				base_tip: 100,
+				multiplier: sp_runtime::FixedU128::saturating_from_rational(3, 2),
+			})
+		);
+		assert_eq!(tip_strategy(None, Some(150)), Err(()));
+	}
+
+	#[test]
+	fn transactions_mortality_is_validated() {
+		assert_eq!(validate_transactions_mortality(None).map_err(drop), Ok(None));
+		assert_eq!(validate_transactions_mortality(Some(4)).map_err(drop), Ok(Some(4)));
+		assert_eq!(validate_transactions_mortality(Some(65536)).map_err(drop), Ok(Some(65536)));
+		assert_eq!(validate_transactions_mortality(Some(2)).map_err(drop), Err(()));
+		assert_eq!(validate_transactions_mortality(Some(100)).map_err(drop), Err(()));
+		assert_eq!(validate_transactions_mortality(Some(131072)).map_err(drop), Err(()));
+	}
 
 	#[test]
 	fn reads_suri_from_file() {
@@ -307,6 +563,7 @@ mod tests {
 
 				target_signer_file: None,
 				target_signer_password_file: None,
+				target_signer_key: None,
 
 				target_transactions_mortality: None,
 			}
@@ -324,6 +581,7 @@ mod tests {
 
 				target_signer_file: Some(suri_file_path.clone()),
 				target_signer_password_file: Some(password_file_path.clone()),
+				target_signer_key: None,
 
 				target_transactions_mortality: None,
 			}
@@ -341,6 +599,7 @@ mod tests {
 
 				target_signer_file: Some(suri_file_path.clone()),
 				target_signer_password_file: Some(password_file_path.clone()),
+				target_signer_key: None,
 
 				target_transactions_mortality: None,
 			}
@@ -358,6 +617,7 @@ mod tests {
 
 				target_signer_file: Some(suri_file_path),
 				target_signer_password_file: Some(password_file_path),
+				target_signer_key: None,
 
 				target_transactions_mortality: None,
 			}
@@ -367,4 +627,29 @@ mod tests {
 			Ok(alice.public()),
 		);
 	}
+
+	#[test]
+	fn reads_suri_from_keystore() {
+		let temp_dir = tempfile::tempdir().unwrap();
+		std::fs::write(temp_dir.path().join("relayer"), b"//Bob").unwrap();
+		std::env::set_var(crate::cli::keys::KEYSTORE_PATH_ENV, temp_dir.path());
+
+		let bob: sp_core::sr25519::Pair = Pair::from_string("//Bob", None).unwrap();
+		assert_eq!(
+			TargetSigningParams {
+				target_signer: None,
+				target_signer_password: None,
+
+				target_signer_file: None,
+				target_signer_password_file: None,
+				target_signer_key: Some("relayer".into()),
+
+				target_transactions_mortality: None,
+			}
+			.to_keypair::<relay_rialto_client::Rialto>()
+			.map(|p| p.public())
+			.map_err(drop),
+			Ok(bob.public()),
+		);
+	}
 }
@@ -0,0 +1,286 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::cli::{chain_schema::*, relayer_funds::CliTransfer, Balance};
+
+use bp_relayers::RewardsAccountParams;
+use bp_runtime::EncodedOrDecodedCall;
+use codec::{Decode, Encode};
+use frame_support::{Blake2_128Concat, StorageHasher, Twox128};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BalanceOf, Client, UnsignedTransaction,
+};
+use relay_utils::{TrackedTransactionStatus, TransactionTracker};
+use sp_core::{storage::StorageKey, Pair};
+use sp_runtime::{traits::Zero, AccountId32};
+use structopt::StructOpt;
+use strum::{EnumString, EnumVariantNames, VariantNames};
+
+/// Claim relayer rewards.
+#[derive(StructOpt)]
+pub struct ClaimRewards {
+	/// A chain where rewards are claimed.
+	#[structopt(possible_values = RewardsChain::VARIANTS, case_insensitive = true)]
+	chain: RewardsChain,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+	#[structopt(flatten)]
+	target_sign: TargetSigningParams,
+	/// Only claim rewards that are larger than or equal to this value.
+	#[structopt(long)]
+	min_reward: Option<Balance>,
+	/// If passed, all claimed rewards are transferred to this account.
+	#[structopt(long)]
+	beneficiary: Option<AccountId32>,
+}
+
+/// Chain, where relayer rewards are claimed.
+#[derive(Debug, EnumString, EnumVariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "kebab_case")]
+pub enum RewardsChain {
+	Millau,
+	Rialto,
+}
+
+/// Chain that has the bridge relayers pallet.
+pub trait CliClaimRewards: CliTransfer {
+	/// Name of the bridge relayers pallet (used in `construct_runtime` macro call).
+	const RELAYERS_PALLET_NAME: &'static str;
+
+	/// Encode `claim_rewards` call of the bridge relayers pallet.
+	fn encode_claim_rewards_call(
+		rewards_account_params: RewardsAccountParams,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>>;
+}
+
+macro_rules! select_chain {
+	($chain: expr, $generic: tt) => {
+		match $chain {
+			RewardsChain::Millau => {
+				type Target = relay_millau_client::Millau;
+
+				$generic
+			},
+			RewardsChain::Rialto => {
+				type Target = relay_rialto_client::Rialto;
+
+				$generic
+			},
+		}
+	};
+}
+
+impl ClaimRewards {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		select_chain!(self.chain, {
+			let client = self.target.into_client::<Target>().await?;
+			let signer = self.target_sign.to_keypair::<Target>()?;
+			claim_rewards::<Target>(
+				client,
+				signer,
+				self.min_reward,
+				self.beneficiary.map(Into::into),
+			)
+			.await
+		})
+	}
+}
+
+/// Claim all pending rewards of the signer account.
+async fn claim_rewards<C: CliClaimRewards>(
+	client: Client<C>,
+	signer: AccountKeyPairOf<C>,
+	min_reward: Option<Balance>,
+	beneficiary: Option<AccountIdOf<C>>,
+) -> anyhow::Result<()>
+where
+	AccountIdOf<C>: From<<AccountKeyPairOf<C> as Pair>::Public>,
+	BalanceOf<C>: Into<u128>,
+{
+	let relayer: AccountIdOf<C> = signer.public().into();
+	let rewards = pending_rewards(&client, &relayer).await?;
+	if rewards.is_empty() {
+		log::info!(target: "bridge", "There are no {} rewards of {:?}", C::NAME, relayer);
+		return Ok(())
+	}
+
+	let mut trackers = Vec::with_capacity(rewards.len());
+	for (rewards_account_params, reward) in rewards {
+		let reward_value = Balance(reward.into());
+		if min_reward.map(|min_reward| reward_value.0 < min_reward.0).unwrap_or(false) {
+			log::info!(
+				target: "bridge",
+				"Skipping {} reward {} for {:?}: it is below the threshold",
+				C::NAME,
+				reward_value,
+				rewards_account_params,
+			);
+			continue
+		}
+
+		log::info!(
+			target: "bridge",
+			"Claiming {} reward {} for {:?}",
+			C::NAME,
+			reward_value,
+			rewards_account_params,
+		);
+		let call = C::encode_claim_rewards_call(rewards_account_params)?;
+		let tracker = client
+			.submit_and_watch_signed_extrinsic(&signer, move |_, transaction_nonce| {
+				Ok(UnsignedTransaction::new(call, transaction_nonce))
+			})
+			.await?;
+		trackers.push((rewards_account_params, reward, tracker));
+	}
+
+	let mut claimed = BalanceOf::<C>::zero();
+	for (rewards_account_params, reward, tracker) in trackers {
+		match tracker.wait().await {
+			TrackedTransactionStatus::Finalized(at_block) => {
+				log::info!(
+					target: "bridge",
+					"Claimed {} reward for {:?} in block {:?}",
+					C::NAME,
+					rewards_account_params,
+					at_block,
+				);
+				claimed += reward;
+			},
+			TrackedTransactionStatus::Lost => log::warn!(
+				target: "bridge",
+				"Transaction that claims {} reward for {:?} has been lost",
+				C::NAME,
+				rewards_account_params,
+			),
+			TrackedTransactionStatus::DryRun => (),
+		}
+	}
+
+	if let Some(beneficiary) = beneficiary {
+		if claimed.is_zero() {
+			return Ok(())
+		}
+
+		log::info!(
+			target: "bridge",
+			"Transferring claimed {} rewards ({}) to {:?}",
+			C::NAME,
+			Balance(claimed.into()),
+			beneficiary,
+		);
+		let call = C::encode_transfer_call(beneficiary, claimed)?;
+		client
+			.submit_signed_extrinsic(&signer, move |_, transaction_nonce| {
+				Ok(UnsignedTransaction::new(call, transaction_nonce))
+			})
+			.await?;
+	}
+
+	Ok(())
+}
+
+/// Read all pending rewards of the relayer.
+async fn pending_rewards<C: CliClaimRewards>(
+	client: &Client<C>,
+	relayer: &AccountIdOf<C>,
+) -> anyhow::Result<Vec<(RewardsAccountParams, BalanceOf<C>)>> {
+	let at_block = client.best_finalized_header_hash().await?;
+	let prefix = relayer_rewards_prefix(C::RELAYERS_PALLET_NAME, relayer);
+	let keys = client.storage_keys(prefix.clone(), at_block).await?;
+
+	let mut rewards = Vec::with_capacity(keys.len());
+	for key in keys {
+		// the second key of the `RelayerRewards` map is using `Identity` hasher
+		let rewards_account_params = RewardsAccountParams::decode(&mut &key.0[prefix.0.len()..])
+			.map_err(|e| {
+				anyhow::format_err!("Failed to decode {} rewards key {:?}: {:?}", C::NAME, key, e)
+			})?;
+		let reward: Option<BalanceOf<C>> = client.storage_value(key, Some(at_block)).await?;
+		if let Some(reward) = reward {
+			rewards.push((rewards_account_params, reward));
+		}
+	}
+
+	Ok(rewards)
+}
+
+/// Returns prefix of all `RelayerRewards` map entries of given relayer.
+fn relayer_rewards_prefix<AccountId: Encode>(
+	relayers_pallet_name: &str,
+	relayer: &AccountId,
+) -> StorageKey {
+	let mut prefix = Vec::new();
+	prefix.extend_from_slice(&Twox128::hash(relayers_pallet_name.as_bytes()));
+	prefix.extend_from_slice(&Twox128::hash(b"RelayerRewards"));
+	prefix.extend_from_slice(&Blake2_128Concat::hash(&relayer.encode()));
+	StorageKey(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bp_messages::LaneId;
+	use bp_relayers::{RelayerRewardsKeyProvider, RewardsAccountOwner};
+	use bp_runtime::StorageDoubleMapKeyProvider;
+
+	#[test]
+	fn relayer_rewards_prefix_is_prefix_of_final_key() {
+		let relayer = AccountId32::new([42u8; 32]);
+		let rewards_account_params = RewardsAccountParams::new(
+			LaneId([0, 0, 0, 1]),
+			*b"test",
+			RewardsAccountOwner::ThisChain,
+		);
+
+		let prefix = relayer_rewards_prefix("BridgeRelayers", &relayer);
+		let final_key = RelayerRewardsKeyProvider::<AccountId32, u128>::final_key(
+			"BridgeRelayers",
+			&relayer,
+			&rewards_account_params,
+		);
+		assert!(final_key.0.starts_with(&prefix.0));
+		assert_eq!(
+			RewardsAccountParams::decode(&mut &final_key.0[prefix.0.len()..]).unwrap(),
+			rewards_account_params,
+		);
+	}
+
+	#[test]
+	fn claim_rewards_params_are_parsed() {
+		let claim_rewards = ClaimRewards::from_iter(vec![
+			"claim-rewards",
+			"millau",
+			"--target-port",
+			"1234",
+			"--target-signer",
+			"//Alice",
+			"--min-reward",
+			"1000",
+			"--beneficiary",
+			"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+		]);
+
+		assert_eq!(claim_rewards.chain, RewardsChain::Millau);
+		assert_eq!(claim_rewards.target.target_port, 1234);
+		assert_eq!(claim_rewards.min_reward, Some(Balance(1000)));
+		assert_eq!(
+			claim_rewards.beneficiary,
+			Some(sp_core::sr25519::Pair::from_string("//Alice", None).unwrap().public().into()),
+		);
+	}
+}
@@ -0,0 +1,480 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Relay configuration file support.
+//!
+//! Configuration file is a TOML or YAML file, which is passed to the relay with the
+//! `--config <PATH>` argument. Files with `.yaml` or `.yml` extension are read as YAML files and
+//! all other files are read as TOML files. The file describes the command to run and its
+//! arguments:
+//!
+//! ```toml
+//! command = ["relay-headers-and-messages", "millau-rialto"]
+//! lane = ["00000000", "73776170"]
+//!
+//! [millau]
+//! host = "millau-node-alice"
+//! port = 9944
+//! signer = "//Charlie"
+//! version-mode = "Bundle"
+//!
+//! [rialto]
+//! host = "rialto-node-alice"
+//! port = 9944
+//! signer = "//Charlie"
+//! version-mode = "Bundle"
+//! ```
+//!
+//! The same configuration in YAML:
+//!
+//! ```yaml
+//! command: ["relay-headers-and-messages", "millau-rialto"]
+//! lane: ["00000000", "73776170"]
+//! millau:
+//!   host: millau-node-alice
+//!   port: 9944
+//!   signer: //Charlie
+//!   version-mode: Bundle
+//! rialto:
+//!   host: rialto-node-alice
+//!   port: 9944
+//!   signer: //Charlie
+//!   version-mode: Bundle
+//! ```
+//!
+//! Every key is converted into the command line argument. Keys of nested tables are prefixed
+//! with the table name, so the `host` key of the `millau` table is converted into the
+//! `--millau-host` argument. Boolean `true` values are converted into flags, `false` values are
+//! ignored and arrays are converted into multiple argument values.
+//!
+//! All other command line arguments are treated as overrides: if the same argument is specified
+//! both in the configuration file and in the command line, the command line value is used. A flag
+//! that is set in the configuration file may be turned off with the `--no-<flag>` command line
+//! argument (e.g. `--no-only-mandatory-headers`).
+
+use anyhow::Context;
+use std::{ffi::OsString, path::Path};
+
+/// Name of the command line argument that is used to pass the configuration file path.
+const CONFIG_ARG: &str = "--config";
+/// Name of the configuration file key that specifies command to run.
+const COMMAND_KEY: &str = "command";
+
+/// Configuration file format.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ConfigFormat {
+	/// TOML file.
+	Toml,
+	/// YAML file.
+	Yaml,
+}
+
+impl ConfigFormat {
+	/// Select configuration file format by the file extension.
+	fn from_path(path: &Path) -> Self {
+		match path.extension().and_then(|extension| extension.to_str()) {
+			Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+			_ => ConfigFormat::Toml,
+		}
+	}
+
+	/// Parse configuration file contents.
+	fn parse(&self, config: &str) -> anyhow::Result<toml::value::Table> {
+		Ok(match *self {
+			ConfigFormat::Toml => toml::from_str(config)?,
+			ConfigFormat::Yaml => serde_yaml::from_str(config)?,
+		})
+	}
+}
+
+/// Command line argument with its values.
+#[derive(Debug, Clone, PartialEq)]
+struct Arg {
+	/// Argument name, including the `--` prefix.
+	name: String,
+	/// Argument values.
+	values: Vec<OsString>,
+	/// True if argument is a flag, set in the configuration file.
+	is_flag: bool,
+}
+
+impl Arg {
+	/// Returns name of the argument that turns this flag off.
+	fn negated_name(&self) -> String {
+		format!("--no-{}", self.name.trim_start_matches("--"))
+	}
+}
+
+/// If command line arguments have the `--config <PATH>` argument, read the configuration file
+/// and return arguments that are built from the configuration file and the rest of the command
+/// line arguments. Otherwise, return the unchanged arguments.
+///
+/// Arguments that are not valid unicode are passed through unchanged, so it is up to the
+/// command to report them.
+pub fn args_with_config_file(args: Vec<OsString>) -> anyhow::Result<Vec<OsString>> {
+	let (config_path, args) = match extract_config_path(args)? {
+		(Some(config_path), args) => (config_path, args),
+		(None, args) => return Ok(args),
+	};
+
+	let config_path = Path::new(&config_path);
+	let config = std::fs::read_to_string(config_path).with_context(|| {
+		format!("Failed to read relay configuration file {}", config_path.display())
+	})?;
+	args_with_config(&config, ConfigFormat::from_path(config_path), args)
+		.with_context(|| format!("Invalid relay configuration file {}", config_path.display()))
+}
+
+/// Remove `--config <PATH>` from the arguments, returning the path.
+fn extract_config_path(args: Vec<OsString>) -> anyhow::Result<(Option<OsString>, Vec<OsString>)> {
+	let mut config_path = None;
+	let mut other_args = Vec::with_capacity(args.len());
+	let mut args = args.into_iter();
+	while let Some(arg) = args.next() {
+		if arg == CONFIG_ARG {
+			config_path = Some(
+				args.next()
+					.ok_or_else(|| anyhow::format_err!("Missing {CONFIG_ARG} argument value"))?,
+			);
+		} else if let Some(path) = arg
+			.to_str()
+			.and_then(|arg| arg.strip_prefix(CONFIG_ARG))
+			.and_then(|arg| arg.strip_prefix('='))
+		{
+			config_path = Some(path.into());
+		} else {
+			other_args.push(arg);
+		}
+	}
+
+	Ok((config_path, other_args))
+}
+
+/// Build arguments from the configuration file contents and command line overrides.
+///
+/// The first of `args` is the binary name and the rest are the overrides.
+fn args_with_config(
+	config: &str,
+	format: ConfigFormat,
+	args: Vec<OsString>,
+) -> anyhow::Result<Vec<OsString>> {
+	let mut config = format.parse(config)?;
+
+	let command = match config.remove(COMMAND_KEY) {
+		Some(toml::Value::String(command)) => vec![command],
+		Some(toml::Value::Array(command)) => command
+			.into_iter()
+			.map(|word| match word {
+				toml::Value::String(word) => Ok(word),
+				_ => Err(anyhow::format_err!("`{COMMAND_KEY}` must be an array of strings")),
+			})
+			.collect::<Result<_, _>>()?,
+		Some(_) => anyhow::bail!("`{COMMAND_KEY}` must be a string or an array of strings"),
+		None => anyhow::bail!("Missing `{COMMAND_KEY}` key"),
+	};
+
+	let mut args = args.into_iter();
+	let binary = args.next().unwrap_or_default();
+	let overrides = group_args(args.collect())?;
+
+	let mut config_args = Vec::new();
+	flatten_table("", config, &mut config_args)?;
+	// `--no-<flag>` is only consumed here if the flag is set in the configuration file. Otherwise
+	// it is passed to the command, because there are flags like `--no-prometheus`
+	let (negated_flags, overrides): (Vec<_>, Vec<_>) = overrides.into_iter().partition(|o| {
+		o.values.is_empty() &&
+			config_args.iter().any(|arg| arg.is_flag && arg.negated_name() == o.name)
+	});
+	config_args.retain(|arg| {
+		!overrides.iter().any(|o| o.name == arg.name) &&
+			!negated_flags.iter().any(|o| o.name == arg.negated_name())
+	});
+
+	Ok(std::iter::once(binary)
+		.chain(command.into_iter().map(OsString::from))
+		.chain(
+			config_args
+				.into_iter()
+				.chain(overrides)
+				.flat_map(|arg| std::iter::once(arg.name.into()).chain(arg.values)),
+		)
+		.collect())
+}
+
+/// Convert configuration table into command line arguments.
+fn flatten_table(
+	prefix: &str,
+	table: toml::value::Table,
+	args: &mut Vec<Arg>,
+) -> anyhow::Result<()> {
+	for (key, value) in table {
+		let name = format!("{prefix}{}", key.replace('_', "-"));
+		let is_flag = matches!(value, toml::Value::Boolean(true));
+		let values = match value {
+			toml::Value::Table(table) => {
+				flatten_table(&format!("{name}-"), table, args)?;
+				continue
+			},
+			toml::Value::Boolean(false) => continue,
+			toml::Value::Boolean(true) => vec![],
+			toml::Value::Array(values) => values
+				.into_iter()
+				.map(|value| scalar_value(&name, value))
+				.collect::<Result<_, _>>()?,
+			value => vec![scalar_value(&name, value)?],
+		};
+		args.push(Arg { name: format!("--{name}"), values, is_flag });
+	}
+
+	Ok(())
+}
+
+/// Convert configuration scalar value into command line argument value.
+fn scalar_value(name: &str, value: toml::Value) -> anyhow::Result<OsString> {
+	match value {
+		toml::Value::String(value) => Ok(value.into()),
+		toml::Value::Integer(value) => Ok(value.to_string().into()),
+		toml::Value::Float(value) => Ok(value.to_string().into()),
+		_ => anyhow::bail!("Unsupported value of `{name}`: {value}"),
+	}
+}
+
+/// Group command line overrides into arguments.
+fn group_args(args: Vec<OsString>) -> anyhow::Result<Vec<Arg>> {
+	let mut grouped: Vec<Arg> = Vec::new();
+	for arg in args {
+		match arg.to_str().filter(|arg| arg.starts_with("--")) {
+			Some(arg) => match arg.split_once('=') {
+				Some((name, value)) => grouped.push(Arg {
+					name: name.into(),
+					values: vec![value.into()],
+					is_flag: false,
+				}),
+				None => grouped.push(Arg { name: arg.into(), values: vec![], is_flag: false }),
+			},
+			None => match grouped.last_mut() {
+				Some(last) => last.values.push(arg),
+				None => anyhow::bail!(
+					"When {CONFIG_ARG} is used, the command must be specified in the configuration \
+					file. Unexpected argument: {}",
+					arg.to_string_lossy(),
+				),
+			},
+		}
+	}
+
+	Ok(grouped)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const CONFIG: &str = r#"
+		command = ["relay-headers-and-messages", "millau-rialto"]
+		lane = ["00000000", "73776170"]
+		only-mandatory-headers = true
+		cooperative_backoff = false
+
+		[millau]
+		host = "millau-node-alice"
+		port = 9944
+
+		[rialto]
+		host = "rialto-node-alice"
+		port = 9944
+	"#;
+
+	const YAML_CONFIG: &str = r#"
+command: ["relay-headers-and-messages", "millau-rialto"]
+lane: ["00000000", "73776170"]
+only-mandatory-headers: true
+cooperative_backoff: false
+millau:
+  host: millau-node-alice
+  port: 9944
+rialto:
+  host: rialto-node-alice
+  port: 9944
+"#;
+
+	fn args(args: &[&str]) -> Vec<OsString> {
+		args.iter().map(OsString::from).collect()
+	}
+
+	#[test]
+	fn args_are_unchanged_without_config() {
+		let cli_args = args(&["substrate-relay", "relay-messages", "millau-to-rialto"]);
+		assert_eq!(args_with_config_file(cli_args.clone()).unwrap(), cli_args);
+	}
+
+	#[test]
+	fn config_path_is_extracted() {
+		assert_eq!(
+			extract_config_path(args(&[
+				"substrate-relay",
+				"--config",
+				"relay.toml",
+				"--lane",
+				"00"
+			]))
+			.unwrap(),
+			(Some("relay.toml".into()), args(&["substrate-relay", "--lane", "00"])),
+		);
+		assert_eq!(
+			extract_config_path(args(&["substrate-relay", "--config=relay.toml"])).unwrap(),
+			(Some("relay.toml".into()), args(&["substrate-relay"])),
+		);
+		assert!(extract_config_path(args(&["substrate-relay", "--config"])).is_err());
+	}
+
+	#[test]
+	fn args_are_built_from_config() {
+		assert_eq!(
+			args_with_config(CONFIG, ConfigFormat::Toml, args(&["substrate-relay"])).unwrap(),
+			args(&[
+				"substrate-relay",
+				"relay-headers-and-messages",
+				"millau-rialto",
+				"--lane",
+				"00000000",
+				"73776170",
+				"--millau-host",
+				"millau-node-alice",
+				"--millau-port",
+				"9944",
+				"--only-mandatory-headers",
+				"--rialto-host",
+				"rialto-node-alice",
+				"--rialto-port",
+				"9944",
+			]),
+		);
+	}
+
+	#[test]
+	fn command_line_args_override_config() {
+		assert_eq!(
+			args_with_config(
+				CONFIG,
+				ConfigFormat::Toml,
+				args(&[
+					"substrate-relay",
+					"--millau-port=9955",
+					"--lane",
+					"00000001",
+					"--no-prometheus"
+				])
+			)
+			.unwrap(),
+			args(&[
+				"substrate-relay",
+				"relay-headers-and-messages",
+				"millau-rialto",
+				"--millau-host",
+				"millau-node-alice",
+				"--only-mandatory-headers",
+				"--rialto-host",
+				"rialto-node-alice",
+				"--rialto-port",
+				"9944",
+				"--millau-port",
+				"9955",
+				"--lane",
+				"00000001",
+				"--no-prometheus",
+			]),
+		);
+	}
+
+	#[test]
+	fn command_line_args_turn_off_config_flags() {
+		assert_eq!(
+			args_with_config(
+				CONFIG,
+				ConfigFormat::Toml,
+				args(&["substrate-relay", "--no-only-mandatory-headers", "--no-prometheus"])
+			)
+			.unwrap(),
+			args(&[
+				"substrate-relay",
+				"relay-headers-and-messages",
+				"millau-rialto",
+				"--lane",
+				"00000000",
+				"73776170",
+				"--millau-host",
+				"millau-node-alice",
+				"--millau-port",
+				"9944",
+				"--rialto-host",
+				"rialto-node-alice",
+				"--rialto-port",
+				"9944",
+				"--no-prometheus",
+			]),
+		);
+	}
+
+	#[test]
+	fn yaml_config_is_read() {
+		assert_eq!(ConfigFormat::from_path(Path::new("relay.yaml")), ConfigFormat::Yaml);
+		assert_eq!(ConfigFormat::from_path(Path::new("relay.yml")), ConfigFormat::Yaml);
+		assert_eq!(ConfigFormat::from_path(Path::new("relay.toml")), ConfigFormat::Toml);
+		assert_eq!(
+			args_with_config(YAML_CONFIG, ConfigFormat::Yaml, args(&["substrate-relay"])).unwrap(),
+			args_with_config(CONFIG, ConfigFormat::Toml, args(&["substrate-relay"])).unwrap(),
+		);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn non_unicode_args_are_passed_through() {
+		use std::os::unix::ffi::OsStringExt;
+
+		let non_unicode = OsString::from_vec(vec![0x66, 0x6f, 0x80, 0x6f]);
+		let mut cli_args = args(&["substrate-relay", "relay-messages", "millau-to-rialto"]);
+		cli_args.push(non_unicode.clone());
+		assert_eq!(args_with_config_file(cli_args.clone()).unwrap(), cli_args);
+
+		let mut cli_args = args(&["substrate-relay", "--millau-signer-file"]);
+		cli_args.push(non_unicode.clone());
+		assert_eq!(
+			args_with_config(CONFIG, ConfigFormat::Toml, cli_args).unwrap().last(),
+			Some(&non_unicode),
+		);
+	}
+
+	#[test]
+	fn config_without_command_is_rejected() {
+		assert!(args_with_config(
+			"lane = \"00000000\"",
+			ConfigFormat::Toml,
+			args(&["substrate-relay"])
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn positional_command_line_args_are_rejected() {
+		assert!(args_with_config(
+			CONFIG,
+			ConfigFormat::Toml,
+			args(&["substrate-relay", "relay-messages"])
+		)
+		.is_err());
+	}
+}
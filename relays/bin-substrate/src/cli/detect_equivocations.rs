@@ -0,0 +1,384 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Detection of conflicting finalized headers.
+//!
+//! The bridge GRANDPA pallet at the target chain only accepts headers, finalized by the source
+//! chain GRANDPA authorities. If it has imported a header that is not in the canonical source
+//! chain, then source chain authorities have finalized conflicting headers (equivocated) and the
+//! bridge may no longer be trusted.
+
+use crate::{
+	chains::{
+		millau_headers_to_rialto::MillauToRialtoCliBridge,
+		millau_headers_to_rialto_parachain::MillauToRialtoParachainCliBridge,
+		rialto_headers_to_millau::RialtoToMillauCliBridge,
+		rococo_headers_to_bridge_hub_westend::RococoToBridgeHubWestendCliBridge,
+		rococo_headers_to_bridge_hub_wococo::RococoToBridgeHubWococoCliBridge,
+		westend_headers_to_bridge_hub_rococo::WestendToBridgeHubRococoCliBridge,
+		westend_headers_to_millau::WestendToMillauCliBridge,
+		wococo_headers_to_bridge_hub_rococo::WococoToBridgeHubRococoCliBridge,
+	},
+	cli::{bridge::RelayToRelayHeadersCliBridge, chain_schema::*},
+};
+use async_trait::async_trait;
+use bp_header_chain::{storage_keys::imported_headers_prefix, StoredHeaderData};
+use bp_runtime::BasicOperatingMode;
+use codec::Decode;
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BlockNumberOf, CallOf, Chain, ChainWithGrandpa, Client, HashOf,
+	UnsignedTransaction,
+};
+use relay_utils::{relay_loop::Client as RelayClient, MaybeConnectionError};
+use sp_core::Pair;
+use structopt::StructOpt;
+use strum::{EnumString, EnumVariantNames, VariantNames};
+
+/// Detect conflicting finalized headers, imported into the target chain light client.
+#[derive(StructOpt)]
+pub struct DetectEquivocations {
+	/// A bridge instance to check.
+	#[structopt(possible_values = DetectEquivocationsBridge::VARIANTS, case_insensitive = true)]
+	bridge: DetectEquivocationsBridge,
+	/// If passed, the command keeps watching the light client instead of checking it once.
+	#[structopt(long)]
+	watch: bool,
+	/// If passed, the bridge GRANDPA pallet at the target chain is halted when conflicting
+	/// headers are found. The target signer must be the owner of the pallet.
+	#[structopt(long)]
+	halt_bridge: bool,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+	#[structopt(flatten)]
+	target_sign: TargetSigningParams,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "kebab_case")]
+/// Bridge with the GRANDPA light client at the target chain.
+pub enum DetectEquivocationsBridge {
+	MillauToRialto,
+	RialtoToMillau,
+	WestendToMillau,
+	MillauToRialtoParachain,
+	RococoToBridgeHubWococo,
+	WococoToBridgeHubRococo,
+	RococoToBridgeHubWestend,
+	WestendToBridgeHubRococo,
+}
+
+/// Source chain header, imported into the target chain light client, that conflicts with the
+/// canonical source chain.
+struct ConflictingHeader<C: Chain> {
+	/// Number of the header.
+	number: BlockNumberOf<C>,
+	/// Hash of the header, imported into the light client.
+	imported_hash: HashOf<C>,
+	/// Hash of the canonical source chain header with the same number.
+	canonical_hash: HashOf<C>,
+}
+
+#[async_trait]
+trait EquivocationsDetector: RelayToRelayHeadersCliBridge
+where
+	Self::Source: ChainWithGrandpa,
+	AccountIdOf<Self::Target>: From<<AccountKeyPairOf<Self::Target> as Pair>::Public>,
+{
+	/// Encode call that halts the bridge GRANDPA pallet at the target chain.
+	fn encode_halt_bridge_call() -> CallOf<Self::Target>;
+
+	/// Detect conflicting headers.
+	async fn detect_equivocations(data: DetectEquivocations) -> anyhow::Result<()> {
+		let mut source_client = data.source.into_client::<Self::Source>().await?;
+		let mut target_client = data.target.into_client::<Self::Target>().await?;
+		let target_sign = if data.halt_bridge {
+			Some(data.target_sign.to_keypair::<Self::Target>()?)
+		} else {
+			None
+		};
+
+		let mut reported_hashes = Vec::new();
+		loop {
+			let conflicting_headers =
+				conflicting_headers::<Self::Source, Self::Target>(&source_client, &target_client)
+					.await;
+			let conflicting_headers = match conflicting_headers {
+				Ok(conflicting_headers) => conflicting_headers,
+				Err(e) if data.watch => {
+					log::warn!(
+						target: "bridge",
+						"Failed to check {} headers at {}: {}",
+						Self::Source::NAME,
+						Self::Target::NAME,
+						e,
+					);
+					if e.is_connection_error() {
+						source_client.reconnect().await?;
+						target_client.reconnect().await?;
+					}
+					async_std::task::sleep(Self::Source::AVERAGE_BLOCK_INTERVAL).await;
+					continue
+				},
+				Err(e) => return Err(e.into()),
+			};
+
+			let new_conflicting_headers = conflicting_headers
+				.into_iter()
+				.filter(|header| !reported_hashes.contains(&header.imported_hash))
+				.collect::<Vec<_>>();
+			for header in &new_conflicting_headers {
+				log::error!(
+					target: "bridge",
+					"{} header {}/{} has been imported at {}, but canonical {} header is {}/{}",
+					Self::Source::NAME,
+					header.number,
+					header.imported_hash,
+					Self::Target::NAME,
+					Self::Source::NAME,
+					header.number,
+					header.canonical_hash,
+				);
+			}
+
+			let is_first_report = reported_hashes.is_empty();
+			if !new_conflicting_headers.is_empty() && is_first_report {
+				if let Some(ref target_sign) = target_sign {
+					let call = Self::encode_halt_bridge_call();
+					let transaction_hash = target_client
+						.submit_signed_extrinsic(target_sign, move |_, transaction_nonce| {
+							Ok(UnsignedTransaction::new(call.into(), transaction_nonce))
+						})
+						.await?;
+					log::info!(
+						target: "bridge",
+						"Submitted transaction {:?} that halts {} bridge GRANDPA pallet at {}",
+						transaction_hash,
+						Self::Source::NAME,
+						Self::Target::NAME,
+					);
+				}
+			}
+			reported_hashes
+				.extend(new_conflicting_headers.iter().map(|header| header.imported_hash));
+
+			if !data.watch {
+				if !reported_hashes.is_empty() {
+					anyhow::bail!(
+						"Found {} conflicting {} headers at {}",
+						reported_hashes.len(),
+						Self::Source::NAME,
+						Self::Target::NAME,
+					)
+				}
+
+				log::info!(
+					target: "bridge",
+					"No conflicting {} headers found at {}",
+					Self::Source::NAME,
+					Self::Target::NAME,
+				);
+				return Ok(())
+			}
+
+			async_std::task::sleep(Self::Source::AVERAGE_BLOCK_INTERVAL).await;
+		}
+	}
+}
+
+/// Compare headers, imported into the target chain light client, with the canonical source chain
+/// and return conflicting headers.
+async fn conflicting_headers<Source: ChainWithGrandpa, Target: Chain>(
+	source_client: &Client<Source>,
+	target_client: &Client<Target>,
+) -> Result<Vec<ConflictingHeader<Source>>, relay_substrate_client::Error> {
+	let at_target = target_client.best_finalized_header_hash().await?;
+	let best_finalized_source_number = source_client.best_finalized_header_number().await?;
+	let prefix = imported_headers_prefix(Source::WITH_CHAIN_GRANDPA_PALLET_NAME);
+	let keys = target_client.storage_keys(prefix.clone(), at_target).await?;
+
+	let mut conflicting_headers = Vec::new();
+	for key in keys {
+		// the `ImportedHeaders` map is using `Identity` hasher
+		let imported_hash = HashOf::<Source>::decode(&mut &key.0[prefix.0.len()..])?;
+		let header: Option<StoredHeaderData<BlockNumberOf<Source>, HashOf<Source>>> =
+			target_client.storage_value(key, Some(at_target)).await?;
+		let number = match header {
+			Some(header) => header.number,
+			None => continue,
+		};
+
+		// the source node may be lagging behind the light client
+		if number > best_finalized_source_number {
+			log::debug!(
+				target: "bridge",
+				"Skipping {} header {}/{}: it is not yet finalized by the {} node",
+				Source::NAME,
+				number,
+				imported_hash,
+				Source::NAME,
+			);
+			continue
+		}
+
+		let canonical_hash = source_client.block_hash_by_number(number).await?;
+		if canonical_hash != imported_hash {
+			conflicting_headers.push(ConflictingHeader { number, imported_hash, canonical_hash });
+		}
+	}
+	conflicting_headers.sort_by_key(|header| header.number);
+
+	Ok(conflicting_headers)
+}
+
+impl EquivocationsDetector for MillauToRialtoCliBridge {
+	fn encode_halt_bridge_call() -> CallOf<Self::Target> {
+		rialto_runtime::BridgeGrandpaCall::set_operating_mode {
+			operating_mode: BasicOperatingMode::Halted,
+		}
+		.into()
+	}
+}
+
+impl EquivocationsDetector for RialtoToMillauCliBridge {
+	fn encode_halt_bridge_call() -> CallOf<Self::Target> {
+		millau_runtime::BridgeGrandpaCall::<
+			millau_runtime::Runtime,
+			millau_runtime::RialtoGrandpaInstance,
+		>::set_operating_mode {
+			operating_mode: BasicOperatingMode::Halted,
+		}
+		.into()
+	}
+}
+
+impl EquivocationsDetector for WestendToMillauCliBridge {
+	fn encode_halt_bridge_call() -> CallOf<Self::Target> {
+		millau_runtime::BridgeGrandpaCall::<
+			millau_runtime::Runtime,
+			millau_runtime::WestendGrandpaInstance,
+		>::set_operating_mode {
+			operating_mode: BasicOperatingMode::Halted,
+		}
+		.into()
+	}
+}
+
+impl EquivocationsDetector for MillauToRialtoParachainCliBridge {
+	fn encode_halt_bridge_call() -> CallOf<Self::Target> {
+		type RuntimeCall = relay_rialto_parachain_client::RuntimeCall;
+		type BridgeGrandpaCall = relay_rialto_parachain_client::BridgeGrandpaCall;
+		type OperatingMode =
+			relay_rialto_parachain_client::runtime_types::bp_runtime::BasicOperatingMode;
+
+		RuntimeCall::BridgeMillauGrandpa(BridgeGrandpaCall::set_operating_mode {
+			operating_mode: OperatingMode::Halted,
+		})
+	}
+}
+
+impl EquivocationsDetector for RococoToBridgeHubWococoCliBridge {
+	fn encode_halt_bridge_call() -> CallOf<Self::Target> {
+		relay_bridge_hub_wococo_client::runtime::Call::BridgeRococoGrandpa(
+			relay_bridge_hub_wococo_client::runtime::BridgeRococoGrandpaCall::set_operating_mode {
+				operating_mode: BasicOperatingMode::Halted,
+			},
+		)
+	}
+}
+
+impl EquivocationsDetector for WococoToBridgeHubRococoCliBridge {
+	fn encode_halt_bridge_call() -> CallOf<Self::Target> {
+		relay_bridge_hub_rococo_client::runtime::Call::BridgeWococoGrandpa(
+			relay_bridge_hub_rococo_client::runtime::BridgeWococoGrandpaCall::set_operating_mode {
+				operating_mode: BasicOperatingMode::Halted,
+			},
+		)
+	}
+}
+
+impl EquivocationsDetector for RococoToBridgeHubWestendCliBridge {
+	fn encode_halt_bridge_call() -> CallOf<Self::Target> {
+		relay_bridge_hub_westend_client::runtime::Call::BridgeRococoGrandpa(
+			relay_bridge_hub_westend_client::runtime::BridgeRococoGrandpaCall::set_operating_mode {
+				operating_mode: BasicOperatingMode::Halted,
+			},
+		)
+	}
+}
+
+impl EquivocationsDetector for WestendToBridgeHubRococoCliBridge {
+	fn encode_halt_bridge_call() -> CallOf<Self::Target> {
+		relay_bridge_hub_rococo_client::runtime::Call::BridgeWestendGrandpa(
+			relay_bridge_hub_rococo_client::runtime::BridgeWestendGrandpaCall::set_operating_mode {
+				operating_mode: BasicOperatingMode::Halted,
+			},
+		)
+	}
+}
+
+impl DetectEquivocations {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		match self.bridge {
+			DetectEquivocationsBridge::MillauToRialto =>
+				MillauToRialtoCliBridge::detect_equivocations(self),
+			DetectEquivocationsBridge::RialtoToMillau =>
+				RialtoToMillauCliBridge::detect_equivocations(self),
+			DetectEquivocationsBridge::WestendToMillau =>
+				WestendToMillauCliBridge::detect_equivocations(self),
+			DetectEquivocationsBridge::MillauToRialtoParachain =>
+				MillauToRialtoParachainCliBridge::detect_equivocations(self),
+			DetectEquivocationsBridge::RococoToBridgeHubWococo =>
+				RococoToBridgeHubWococoCliBridge::detect_equivocations(self),
+			DetectEquivocationsBridge::WococoToBridgeHubRococo =>
+				WococoToBridgeHubRococoCliBridge::detect_equivocations(self),
+			DetectEquivocationsBridge::RococoToBridgeHubWestend =>
+				RococoToBridgeHubWestendCliBridge::detect_equivocations(self),
+			DetectEquivocationsBridge::WestendToBridgeHubRococo =>
+				WestendToBridgeHubRococoCliBridge::detect_equivocations(self),
+		}
+		.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn detect_equivocations_params_are_parsed() {
+		let detect_equivocations = DetectEquivocations::from_iter(vec![
+			"detect-equivocations",
+			"rialto-to-millau",
+			"--watch",
+			"--halt-bridge",
+			"--source-port",
+			"1234",
+			"--target-port",
+			"5678",
+			"--target-signer",
+			"//Alice",
+		]);
+
+		assert_eq!(detect_equivocations.bridge, DetectEquivocationsBridge::RialtoToMillau);
+		assert!(detect_equivocations.watch);
+		assert!(detect_equivocations.halt_bridge);
+		assert_eq!(detect_equivocations.source.source_port, 1234);
+		assert_eq!(detect_equivocations.target.target_port, 5678);
+	}
+}
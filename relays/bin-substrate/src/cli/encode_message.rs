@@ -36,8 +36,30 @@ pub enum Message {
 		/// Sized of the message.
 		size: ExplicitOrMaximal<u32>,
 	},
+	/// XCM message that dispatches given call at the target chain (using `Transact`
+	/// instruction).
+	Transact {
+		/// Hex-encoded call of the target chain.
+		call: HexBytes,
+		/// Maximal weight (`ref_time` component) of the call dispatch.
+		#[structopt(long, default_value = "1000000000")]
+		weight: u64,
+	},
+	/// XCM message that dispatches `System::remark` call with given text at the target chain.
+	Remark {
+		/// Remark text.
+		remark: String,
+	},
 }
 
+/// Weight (`ref_time` component) of the `System::remark` call, dispatched by the `Remark`
+/// message.
+const REMARK_WEIGHT: u64 = 1_000_000_000;
+/// Index of the `System` pallet in the target chain runtime.
+const SYSTEM_PALLET_INDEX: u8 = 0;
+/// Index of the `remark` call of the `System` pallet.
+const SYSTEM_REMARK_CALL_INDEX: u8 = 0;
+
 /// Raw, SCALE-encoded message payload used in expected deployment.
 pub type RawMessage = Vec<u8>;
 
@@ -86,9 +108,28 @@ pub(crate) fn encode_message<Source: Chain, Target: Chain>(
 			};
 			xcm.encode()
 		},
+		Message::Transact { ref call, ref weight } =>
+			transact_xcm(call.0.clone(), *weight).encode(),
+		Message::Remark { ref remark } => transact_xcm(
+			(SYSTEM_PALLET_INDEX, SYSTEM_REMARK_CALL_INDEX, remark.as_bytes()).encode(),
+			REMARK_WEIGHT,
+		)
+		.encode(),
 	})
 }
 
+/// Returns XCM program that dispatches given encoded call at the target chain.
+fn transact_xcm(call: Vec<u8>, weight: u64) -> xcm::VersionedXcm<()> {
+	xcm::VersionedXcm::<()>::V3(
+		vec![xcm::v3::Instruction::Transact {
+			origin_kind: xcm::v3::OriginKind::SovereignAccount,
+			require_weight_at_most: xcm::v3::Weight::from_parts(weight, 0),
+			call: call.into(),
+		}]
+		.into(),
+	)
+}
+
 /// Compute maximal message size, given max extrinsic size at source and target chains.
 pub(crate) fn compute_maximal_message_size(
 	maximal_source_extrinsic_size: u32,
@@ -142,4 +183,26 @@ mod tests {
 		// check that it decodes to valid xcm
 		let _ = decode_xcm(msg).unwrap();
 	}
+
+	#[test]
+	fn encode_transact_message_works() {
+		let msg = encode_message::<Rialto, Millau>(&Message::Transact {
+			call: HexBytes(vec![0x00, 0x01, 0x04, 0x2a]),
+			weight: 1_000,
+		})
+		.unwrap();
+		assert_eq!(decode_xcm(msg).unwrap(), transact_xcm(vec![0x00, 0x01, 0x04, 0x2a], 1_000));
+	}
+
+	#[test]
+	fn encode_remark_message_works() {
+		let msg =
+			encode_message::<Rialto, Millau>(&Message::Remark { remark: "Hello".into() }).unwrap();
+		let expected_call =
+			millau_runtime::RuntimeCall::System(millau_runtime::SystemCall::remark {
+				remark: b"Hello".to_vec(),
+			})
+			.encode();
+		assert_eq!(decode_xcm(msg).unwrap(), transact_xcm(expected_call, REMARK_WEIGHT));
+	}
 }
@@ -0,0 +1,337 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	chains::{
+		millau_headers_to_rialto::MillauToRialtoCliBridge,
+		millau_headers_to_rialto_parachain::MillauToRialtoParachainCliBridge,
+		rialto_headers_to_millau::RialtoToMillauCliBridge,
+		rialto_parachains_to_millau::RialtoParachainToMillauCliBridge,
+	},
+	cli::{
+		bridge::{FullBridge, MessagesCliBridge},
+		chain_schema::*,
+		encode_message::{self, CliEncodeMessage, Message},
+		send_message::decode_xcm,
+		Balance, ExplicitOrMaximal, HexLaneId,
+	},
+};
+use async_trait::async_trait;
+use bp_messages::{LaneId, UnrewardedRelayersState};
+use bp_runtime::EncodedOrDecodedCall;
+use bridge_runtime_common::messages::{
+	source::FromBridgedChainMessagesDeliveryProof, target::FromBridgedChainMessagesProof,
+};
+use frame_support::weights::Weight;
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BalanceOf, Chain, ChainWithTransactions, Client, SignParam,
+	UnsignedTransaction,
+};
+use sp_core::Pair;
+use sp_runtime::traits::Zero;
+use structopt::StructOpt;
+use strum::VariantNames;
+use substrate_relay_helper::messages_lane::{
+	ReceiveMessagesDeliveryProofCallBuilder, ReceiveMessagesProofCallBuilder, SubstrateMessageLane,
+};
+
+/// Estimate cost of sending and delivering the message over the bridge.
+#[derive(StructOpt)]
+pub struct EstimateFee {
+	/// A bridge instance to estimate fee for.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	/// Hex-encoded id of the lane that will be used to deliver the message. Defaults to
+	/// `00000000`.
+	#[structopt(long, default_value = "00000000")]
+	lane: HexLaneId,
+	/// Size of the message. Either explicit number of bytes or `max` for the maximal message size.
+	#[structopt(long, default_value = "max")]
+	size: ExplicitOrMaximal<u32>,
+	/// Weight (`ref_time` component) of the message dispatch at the target chain.
+	#[structopt(long, default_value = "0")]
+	dispatch_weight: u64,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+}
+
+/// Messages lane of given bridge.
+pub(crate) type MessagesLaneOf<B> = <B as MessagesCliBridge>::MessagesLane;
+/// Builder of the message delivery call of given bridge.
+pub(crate) type DeliveryCallBuilderOf<B> =
+	<MessagesLaneOf<B> as SubstrateMessageLane>::ReceiveMessagesProofCallBuilder;
+/// Builder of the message delivery confirmation call of given bridge.
+pub(crate) type ConfirmationCallBuilderOf<B> =
+	<MessagesLaneOf<B> as SubstrateMessageLane>::ReceiveMessagesDeliveryProofCallBuilder;
+
+/// Estimated cost of the message.
+struct MessageCost {
+	/// Fee of the transaction that sends message at the source chain.
+	source_fee: Balance,
+	/// Fee of the message delivery transaction at the target chain.
+	delivery_fee: Balance,
+	/// Fee of the delivery confirmation transaction at the source chain.
+	confirmation_fee: Balance,
+}
+
+#[async_trait]
+trait FeeEstimator: MessagesCliBridge
+where
+	Self::Source: ChainWithTransactions + CliEncodeMessage,
+	BalanceOf<Self::Source>: Into<u128>,
+	BalanceOf<Self::Target>: Into<u128>,
+	AccountIdOf<Self::Source>: From<<AccountKeyPairOf<Self::Source> as Pair>::Public>,
+{
+	async fn estimate_fee(data: EstimateFee) -> anyhow::Result<()> {
+		let source_client = data.source.into_client::<Self::Source>().await?;
+		let target_client = data.target.into_client::<Self::Target>().await?;
+		let lane: LaneId = data.lane.into();
+
+		let payload =
+			encode_message::encode_message::<Self::Source, Self::Target>(&Message::Sized {
+				size: data.size,
+			})?;
+		let payload_len = payload.len() as u32;
+		let send_message_call = Self::Source::encode_send_xcm(
+			decode_xcm(payload)?,
+			data.bridge.bridge_instance_index(),
+		)?;
+
+		let cost = estimate_message_cost::<Self>(
+			&source_client,
+			&target_client,
+			send_message_call,
+			lane,
+			payload_len,
+			Weight::from_parts(data.dispatch_weight, 0),
+		)
+		.await?;
+
+		log::info!(
+			target: "bridge",
+			"Estimated cost of {}-byte message from {} to {} over lane {}:\n\
+			\tsend transaction fee: {} ({})\n\
+			\tdelivery transaction fee: {} ({})\n\
+			\tconfirmation transaction fee: {} ({})",
+			payload_len,
+			Self::Source::NAME,
+			Self::Target::NAME,
+			hex::encode(lane),
+			cost.source_fee,
+			Self::Source::NAME,
+			cost.delivery_fee,
+			Self::Target::NAME,
+			cost.confirmation_fee,
+			Self::Source::NAME,
+		);
+
+		Ok(())
+	}
+}
+
+impl FeeEstimator for MillauToRialtoCliBridge {}
+impl FeeEstimator for RialtoToMillauCliBridge {}
+impl FeeEstimator for MillauToRialtoParachainCliBridge {}
+impl FeeEstimator for RialtoParachainToMillauCliBridge {}
+
+impl EstimateFee {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		match self.bridge {
+			FullBridge::MillauToRialto => MillauToRialtoCliBridge::estimate_fee(self),
+			FullBridge::RialtoToMillau => RialtoToMillauCliBridge::estimate_fee(self),
+			FullBridge::MillauToRialtoParachain =>
+				MillauToRialtoParachainCliBridge::estimate_fee(self),
+			FullBridge::RialtoParachainToMillau =>
+				RialtoParachainToMillauCliBridge::estimate_fee(self),
+			FullBridge::BridgeHubRococoToBridgeHubWococo => unimplemented!(
+				"Estimating fee of BridgeHubRococo to BridgeHubWococo message is not supported"
+			),
+			FullBridge::BridgeHubWococoToBridgeHubRococo => unimplemented!(
+				"Estimating fee of BridgeHubWococo to BridgeHubRococo message is not supported"
+			),
+			FullBridge::BridgeHubRococoToBridgeHubWestend => unimplemented!(
+				"Estimating fee of BridgeHubRococo to BridgeHubWestend message is not supported"
+			),
+			FullBridge::BridgeHubWestendToBridgeHubRococo => unimplemented!(
+				"Estimating fee of BridgeHubWestend to BridgeHubRococo message is not supported"
+			),
+		}
+		.await
+	}
+}
+
+/// Estimate cost of sending, delivering and confirming single message.
+async fn estimate_message_cost<B>(
+	source_client: &Client<B::Source>,
+	target_client: &Client<B::Target>,
+	send_message_call: EncodedOrDecodedCall<<B::Source as Chain>::Call>,
+	lane: LaneId,
+	payload_len: u32,
+	dispatch_weight: Weight,
+) -> anyhow::Result<MessageCost>
+where
+	B: FeeEstimator,
+	B::Source: ChainWithTransactions + CliEncodeMessage,
+	BalanceOf<B::Source>: Into<u128>,
+	BalanceOf<B::Target>: Into<u128>,
+	AccountIdOf<B::Source>: From<<AccountKeyPairOf<B::Source> as Pair>::Public>,
+{
+	// we don't care about most of call values here, because fee estimation RPC only computes
+	// the weight and the size of the transaction. So we only fill values that affect them -
+	// e.g. number of messages, dispatch weight and the storage proof size
+	let source_signer = dummy_signer::<B::Source>()?;
+	let target_signer = dummy_signer::<B::Target>()?;
+
+	let source_fee = source_client
+		.estimate_extrinsic_fee(dummy_transaction::<B::Source>(
+			source_signer.clone(),
+			send_message_call,
+		)?)
+		.await
+		.map_err(|e| anyhow::format_err!("Failed to estimate send transaction fee: {:?}", e))?;
+
+	let delivery_call = DeliveryCallBuilderOf::<B>::build_receive_messages_proof_call(
+		source_signer.public().into(),
+		(
+			Weight::zero(),
+			FromBridgedChainMessagesProof {
+				bridged_header_hash: Default::default(),
+				storage_proof: vec![vec![
+					42u8;
+					(payload_len + pallet_bridge_messages::EXTRA_STORAGE_PROOF_SIZE)
+						as usize
+				]],
+				lane,
+				nonces_start: 1,
+				nonces_end: 1,
+			},
+		),
+		1,
+		dispatch_weight,
+		false,
+	);
+	let delivery_fee = target_client
+		.estimate_extrinsic_fee(dummy_transaction::<B::Target>(
+			target_signer,
+			delivery_call.into(),
+		)?)
+		.await
+		.map_err(|e| anyhow::format_err!("Failed to estimate delivery transaction fee: {:?}", e))?;
+
+	let confirmation_call =
+		ConfirmationCallBuilderOf::<B>::build_receive_messages_delivery_proof_call(
+			(
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					messages_in_oldest_entry: 1,
+					total_messages: 1,
+					last_delivered_nonce: 1,
+				},
+				FromBridgedChainMessagesDeliveryProof {
+					bridged_header_hash: Default::default(),
+					storage_proof: vec![vec![
+						42u8;
+						pallet_bridge_messages::EXTRA_STORAGE_PROOF_SIZE
+							as usize
+					]],
+					lane,
+				},
+			),
+			false,
+		);
+	let confirmation_fee = source_client
+		.estimate_extrinsic_fee(dummy_transaction::<B::Source>(
+			source_signer,
+			confirmation_call.into(),
+		)?)
+		.await
+		.map_err(|e| {
+			anyhow::format_err!("Failed to estimate confirmation transaction fee: {:?}", e)
+		})?;
+
+	Ok(MessageCost {
+		source_fee: Balance(source_fee.into()),
+		delivery_fee: Balance(delivery_fee.into()),
+		confirmation_fee: Balance(confirmation_fee.into()),
+	})
+}
+
+/// Returns signer that is used to sign dummy transactions.
+pub(crate) fn dummy_signer<C: ChainWithTransactions>() -> anyhow::Result<AccountKeyPairOf<C>> {
+	AccountKeyPairOf::<C>::from_string("//Alice", None)
+		.map_err(|e| anyhow::format_err!("Failed to create dummy {} signer: {:?}", C::NAME, e))
+}
+
+/// Returns dummy transaction with given call, that may only be used for fee estimation.
+pub(crate) fn dummy_transaction<C: ChainWithTransactions>(
+	signer: AccountKeyPairOf<C>,
+	call: EncodedOrDecodedCall<C::Call>,
+) -> anyhow::Result<C::SignedTransaction> {
+	C::sign_transaction(
+		SignParam {
+			spec_version: 0,
+			transaction_version: 0,
+			genesis_hash: Default::default(),
+			signer,
+		},
+		UnsignedTransaction::new(call, Zero::zero()),
+	)
+	.map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn estimate_fee_params_are_parsed() {
+		// when
+		let estimate_fee = EstimateFee::from_iter(vec![
+			"estimate-fee",
+			"rialto-to-millau",
+			"--lane",
+			"00000001",
+			"--size",
+			"1024",
+			"--dispatch-weight",
+			"1000000",
+			"--source-port",
+			"1234",
+			"--target-port",
+			"5678",
+		]);
+
+		// then
+		assert_eq!(estimate_fee.bridge, FullBridge::RialtoToMillau);
+		assert_eq!(estimate_fee.lane, HexLaneId([0, 0, 0, 1]));
+		assert_eq!(estimate_fee.size, ExplicitOrMaximal::Explicit(1024));
+		assert_eq!(estimate_fee.dispatch_weight, 1_000_000);
+		assert_eq!(estimate_fee.source.source_port, 1234);
+		assert_eq!(estimate_fee.target.target_port, 5678);
+	}
+
+	#[test]
+	fn estimate_fee_uses_maximal_message_size_by_default() {
+		let estimate_fee = EstimateFee::from_iter(vec!["estimate-fee", "millau-to-rialto"]);
+
+		assert_eq!(estimate_fee.lane, HexLaneId([0, 0, 0, 0]));
+		assert_eq!(estimate_fee.size, ExplicitOrMaximal::Maximal);
+		assert_eq!(estimate_fee.dispatch_weight, 0);
+	}
+}
@@ -15,25 +15,37 @@
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
 use async_trait::async_trait;
-use codec::Encode;
+use codec::{Decode, Encode};
 
 use crate::{
 	chains::{
+		millau_beefy_to_rialto::MillauToRialtoBeefyCliBridge,
 		millau_headers_to_rialto::MillauToRialtoCliBridge,
 		millau_headers_to_rialto_parachain::MillauToRialtoParachainCliBridge,
 		rialto_headers_to_millau::RialtoToMillauCliBridge,
+		rococo_headers_to_bridge_hub_westend::RococoToBridgeHubWestendCliBridge,
 		rococo_headers_to_bridge_hub_wococo::RococoToBridgeHubWococoCliBridge,
+		westend_headers_to_bridge_hub_rococo::WestendToBridgeHubRococoCliBridge,
 		westend_headers_to_millau::WestendToMillauCliBridge,
 		wococo_headers_to_bridge_hub_rococo::WococoToBridgeHubRococoCliBridge,
 	},
-	cli::{bridge::CliBridgeBase, chain_schema::*},
+	cli::{
+		bridge::{CliBridgeBase, RelayToRelayBeefyCliBridge},
+		chain_schema::*,
+	},
 };
 use bp_runtime::Chain as ChainBase;
-use relay_substrate_client::{AccountKeyPairOf, Chain, UnsignedTransaction};
-use sp_core::Pair;
+use relay_substrate_client::{
+	AccountKeyPairOf, Chain, ChainWithBeefy, Client, HashOf, UnsignedTransaction,
+};
+use sp_core::{Pair, H256};
+use sp_runtime::traits::Header as HeaderT;
 use structopt::StructOpt;
 use strum::{EnumString, EnumVariantNames, VariantNames};
-use substrate_relay_helper::finality::engine::{Engine, Grandpa as GrandpaFinalityEngine};
+use substrate_relay_helper::{
+	beefy::initialize::InitializationDataOf as BeefyInitializationDataOf,
+	finality::engine::{Engine, Grandpa as GrandpaFinalityEngine},
+};
 
 /// Initialize bridge pallet.
 #[derive(StructOpt)]
@@ -50,6 +62,35 @@ pub struct InitBridge {
 	/// Generates all required data, but does not submit extrinsic
 	#[structopt(long)]
 	dry_run: bool,
+	/// Hash (0x-prefixed) or number of the finalized source chain block to initialize the bridge
+	/// with. If not specified, the block finalized by the next justification is used.
+	#[structopt(long)]
+	at_block: Option<InitialBlock>,
+}
+
+/// Source chain block, used to initialize the bridge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InitialBlock {
+	/// Block with given hash.
+	Hash(H256),
+	/// Canonical block with given number.
+	Number(u64),
+}
+
+impl std::str::FromStr for InitialBlock {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Some(hash) = s.strip_prefix("0x") {
+			let hash = hex::decode(hash)?;
+			if hash.len() != H256::len_bytes() {
+				anyhow::bail!("Invalid block hash length: {} bytes", hash.len())
+			}
+			return Ok(InitialBlock::Hash(H256::from_slice(&hash)))
+		}
+
+		Ok(InitialBlock::Number(s.parse()?))
+	}
 }
 
 #[derive(Debug, EnumString, EnumVariantNames)]
@@ -57,11 +98,14 @@ pub struct InitBridge {
 /// Bridge to initialize.
 pub enum InitBridgeName {
 	MillauToRialto,
+	MillauToRialtoBeefy,
 	RialtoToMillau,
 	WestendToMillau,
 	MillauToRialtoParachain,
 	RococoToBridgeHubWococo,
 	WococoToBridgeHubRococo,
+	RococoToBridgeHubWestend,
+	WestendToBridgeHubRococo,
 }
 
 #[async_trait]
@@ -82,11 +126,16 @@ where
 		let target_client = data.target.into_client::<Self::Target>().await?;
 		let target_sign = data.target_sign.to_keypair::<Self::Target>()?;
 		let dry_run = data.dry_run;
+		let at_block = match data.at_block {
+			Some(at_block) => Some(finalized_block_hash(&source_client, at_block).await?),
+			None => None,
+		};
 
 		substrate_relay_helper::finality::initialize::initialize::<Self::Engine, _, _, _>(
 			source_client,
 			target_client.clone(),
 			target_sign,
+			at_block,
 			move |transaction_nonce, initialization_data| {
 				let call = Self::encode_init_bridge(initialization_data);
 				log::info!(
@@ -104,6 +153,93 @@ where
 	}
 }
 
+#[async_trait]
+trait BeefyBridgeInitializer: RelayToRelayBeefyCliBridge
+where
+	Self::Source: ChainWithBeefy,
+	<Self::Target as ChainBase>::AccountId: From<<AccountKeyPairOf<Self::Target> as Pair>::Public>,
+{
+	/// Get the encoded call to init the BEEFY bridge.
+	fn encode_init_bridge(
+		init_data: BeefyInitializationDataOf<Self::Source>,
+	) -> <Self::Target as Chain>::Call;
+
+	/// Initialize the BEEFY bridge.
+	async fn init_bridge(data: InitBridge) -> anyhow::Result<()> {
+		let source_client = data.source.into_client::<Self::Source>().await?;
+		let target_client = data.target.into_client::<Self::Target>().await?;
+		let target_sign = data.target_sign.to_keypair::<Self::Target>()?;
+		let dry_run = data.dry_run;
+		let at_block = match data.at_block {
+			Some(at_block) => Some(finalized_block_hash(&source_client, at_block).await?),
+			None => None,
+		};
+
+		substrate_relay_helper::beefy::initialize::initialize::<Self::Beefy, _>(
+			source_client,
+			target_client.clone(),
+			target_sign,
+			at_block,
+			move |transaction_nonce, initialization_data| {
+				let call = Self::encode_init_bridge(initialization_data);
+				log::info!(
+					target: "bridge",
+					"Initialize BEEFY bridge call encoded as hex string: {:?}",
+					format!("0x{}", hex::encode(call.encode()))
+				);
+				Ok(UnsignedTransaction::new(call.into(), transaction_nonce))
+			},
+			dry_run,
+		)
+		.await;
+
+		Ok(())
+	}
+}
+
+/// Returns hash of given source chain block, making sure that it is finalized.
+async fn finalized_block_hash<C: Chain>(
+	client: &Client<C>,
+	block: InitialBlock,
+) -> anyhow::Result<HashOf<C>> {
+	let best_finalized_number = client.best_finalized_header_number().await?;
+	let (hash, number) = match block {
+		InitialBlock::Hash(hash) => {
+			let hash = HashOf::<C>::decode(&mut hash.as_bytes())?;
+			let number = *client.header_by_hash(hash).await?.number();
+			(hash, number)
+		},
+		InitialBlock::Number(number) => {
+			let number = C::BlockNumber::try_from(number)
+				.map_err(|_| anyhow::format_err!("Invalid {} block number: {}", C::NAME, number))?;
+			if number > best_finalized_number {
+				anyhow::bail!(
+					"{} block {} is not finalized yet. Best finalized block is {}",
+					C::NAME,
+					number,
+					best_finalized_number,
+				)
+			}
+			(client.block_hash_by_number(number).await?, number)
+		},
+	};
+
+	// if the block is not canonical (or not yet finalized), it can't be used to initialize bridge
+	let is_finalized =
+		number <= best_finalized_number && client.block_hash_by_number(number).await? == hash;
+	if !is_finalized {
+		anyhow::bail!(
+			"{} block {}/{} is not finalized. Best finalized block is {}",
+			C::NAME,
+			number,
+			hash,
+			best_finalized_number,
+		)
+	}
+
+	Ok(hash)
+}
+
 impl BridgeInitializer for MillauToRialtoCliBridge {
 	type Engine = GrandpaFinalityEngine<Self::Source>;
 
@@ -117,6 +253,20 @@ impl BridgeInitializer for MillauToRialtoCliBridge {
 	}
 }
 
+impl BeefyBridgeInitializer for MillauToRialtoBeefyCliBridge {
+	fn encode_init_bridge(
+		init_data: BeefyInitializationDataOf<Self::Source>,
+	) -> <Self::Target as Chain>::Call {
+		let initialize_call = rialto_runtime::BridgeBeefyCall::<
+			rialto_runtime::Runtime,
+			rialto_runtime::MillauBeefyInstance,
+		>::initialize {
+			init_data,
+		};
+		rialto_runtime::SudoCall::sudo { call: Box::new(initialize_call.into()) }.into()
+	}
+}
+
 impl BridgeInitializer for MillauToRialtoParachainCliBridge {
 	type Engine = GrandpaFinalityEngine<Self::Source>;
 
@@ -198,11 +348,41 @@ impl BridgeInitializer for WococoToBridgeHubRococoCliBridge {
 	}
 }
 
+impl BridgeInitializer for RococoToBridgeHubWestendCliBridge {
+	type Engine = GrandpaFinalityEngine<Self::Source>;
+
+	fn encode_init_bridge(
+		init_data: <Self::Engine as Engine<Self::Source>>::InitializationData,
+	) -> <Self::Target as Chain>::Call {
+		relay_bridge_hub_westend_client::runtime::Call::BridgeRococoGrandpa(
+			relay_bridge_hub_westend_client::runtime::BridgeRococoGrandpaCall::initialize {
+				init_data,
+			},
+		)
+	}
+}
+
+impl BridgeInitializer for WestendToBridgeHubRococoCliBridge {
+	type Engine = GrandpaFinalityEngine<Self::Source>;
+
+	fn encode_init_bridge(
+		init_data: <Self::Engine as Engine<Self::Source>>::InitializationData,
+	) -> <Self::Target as Chain>::Call {
+		relay_bridge_hub_rococo_client::runtime::Call::BridgeWestendGrandpa(
+			relay_bridge_hub_rococo_client::runtime::BridgeWestendGrandpaCall::initialize {
+				init_data,
+			},
+		)
+	}
+}
+
 impl InitBridge {
 	/// Run the command.
 	pub async fn run(self) -> anyhow::Result<()> {
 		match self.bridge {
 			InitBridgeName::MillauToRialto => MillauToRialtoCliBridge::init_bridge(self),
+			InitBridgeName::MillauToRialtoBeefy =>
+				<MillauToRialtoBeefyCliBridge as BeefyBridgeInitializer>::init_bridge(self),
 			InitBridgeName::RialtoToMillau => RialtoToMillauCliBridge::init_bridge(self),
 			InitBridgeName::WestendToMillau => WestendToMillauCliBridge::init_bridge(self),
 			InitBridgeName::MillauToRialtoParachain =>
@@ -211,7 +391,47 @@ impl InitBridge {
 				RococoToBridgeHubWococoCliBridge::init_bridge(self),
 			InitBridgeName::WococoToBridgeHubRococo =>
 				WococoToBridgeHubRococoCliBridge::init_bridge(self),
+			InitBridgeName::RococoToBridgeHubWestend =>
+				RococoToBridgeHubWestendCliBridge::init_bridge(self),
+			InitBridgeName::WestendToBridgeHubRococo =>
+				WestendToBridgeHubRococoCliBridge::init_bridge(self),
 		}
 		.await
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+
+	#[test]
+	fn initial_block_is_parsed() {
+		assert_eq!(InitialBlock::from_str("42").unwrap(), InitialBlock::Number(42));
+		assert_eq!(
+			InitialBlock::from_str(&format!("0x{}", "01".repeat(32))).unwrap(),
+			InitialBlock::Hash(H256::repeat_byte(1)),
+		);
+		assert!(InitialBlock::from_str("0x0101").is_err());
+		assert!(InitialBlock::from_str("best").is_err());
+	}
+
+	#[test]
+	fn init_bridge_params_are_parsed() {
+		let init_bridge = InitBridge::from_iter(vec![
+			"init-bridge",
+			"millau-to-rialto",
+			"--source-port",
+			"1234",
+			"--target-port",
+			"5678",
+			"--target-signer",
+			"//Alice",
+			"--at-block",
+			"100",
+		]);
+
+		assert_eq!(init_bridge.at_block, Some(InitialBlock::Number(100)));
+		assert!(!init_bridge.dry_run);
+	}
+}
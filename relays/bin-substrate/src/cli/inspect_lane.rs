@@ -0,0 +1,391 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	chains::{
+		bridge_hub_rococo_messages_to_bridge_hub_westend::BridgeHubRococoToBridgeHubWestendMessagesCliBridge,
+		bridge_hub_rococo_messages_to_bridge_hub_wococo::BridgeHubRococoToBridgeHubWococoMessagesCliBridge,
+		bridge_hub_westend_messages_to_bridge_hub_rococo::BridgeHubWestendToBridgeHubRococoMessagesCliBridge,
+		bridge_hub_wococo_messages_to_bridge_hub_rococo::BridgeHubWococoToBridgeHubRococoMessagesCliBridge,
+		millau_headers_to_rialto::MillauToRialtoCliBridge,
+		millau_headers_to_rialto_parachain::MillauToRialtoParachainCliBridge,
+		rialto_headers_to_millau::RialtoToMillauCliBridge,
+		rialto_parachains_to_millau::RialtoParachainToMillauCliBridge,
+	},
+	cli::{
+		bridge::{FullBridge, MessagesCliBridge},
+		chain_schema::*,
+		HexLaneId,
+	},
+};
+use async_trait::async_trait;
+use bp_messages::{
+	storage_keys::{inbound_lane_data_key, outbound_lane_data_key},
+	InboundLaneData, LaneId, MessageNonce, OutboundLaneData,
+};
+use relay_substrate_client::{
+	AccountIdOf, BlockNumberOf, Chain, ChainWithMessages, Client, HashOf,
+};
+use sp_runtime::traits::{One, Saturating};
+use std::{
+	fmt::Debug,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use structopt::StructOpt;
+use strum::VariantNames;
+use substrate_relay_helper::messages_lane::SubstrateMessageLane;
+
+/// Print state of the message lane at both chains.
+#[derive(StructOpt)]
+pub struct InspectLane {
+	/// A bridge instance to inspect lane of.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	/// Hex-encoded id of the lane to inspect. Defaults to `00000000`.
+	#[structopt(long, default_value = "00000000")]
+	lane: HexLaneId,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+}
+
+/// State of the message lane at both chains.
+#[derive(Debug)]
+struct LaneReport<RelayerId> {
+	/// Outbound lane state at the source chain.
+	outbound: OutboundLaneData,
+	/// Inbound lane state at the target chain.
+	inbound: InboundLaneData<RelayerId>,
+	/// The oldest undelivered message and its age (if we have been able to compute it).
+	oldest_undelivered: Option<(MessageNonce, Option<Duration>)>,
+}
+
+impl<RelayerId> LaneReport<RelayerId> {
+	/// Nonce of the latest message, delivered to the target chain.
+	fn latest_received_nonce(&self) -> MessageNonce {
+		self.inbound.last_delivered_nonce()
+	}
+
+	/// Nonce of the oldest message that has been generated, but not delivered yet.
+	fn oldest_undelivered_nonce(&self) -> Option<MessageNonce> {
+		if self.outbound.latest_generated_nonce > self.latest_received_nonce() {
+			Some(self.latest_received_nonce() + 1)
+		} else {
+			None
+		}
+	}
+
+	/// Number of messages that are generated, but not delivered yet.
+	fn pending_messages(&self) -> MessageNonce {
+		self.outbound
+			.latest_generated_nonce
+			.saturating_sub(self.latest_received_nonce())
+	}
+
+	/// Number of messages that are delivered, but not confirmed yet.
+	fn unconfirmed_messages(&self) -> MessageNonce {
+		self.latest_received_nonce().saturating_sub(self.outbound.latest_received_nonce)
+	}
+}
+
+impl<RelayerId: Debug> LaneReport<RelayerId> {
+	/// Format report to print it to the user.
+	fn format(&self, lane: LaneId, source_name: &str, target_name: &str) -> String {
+		let unrewarded_relayers = if self.inbound.relayers.is_empty() {
+			"none".into()
+		} else {
+			self.inbound
+				.relayers
+				.iter()
+				.map(|entry| {
+					format!(
+						"{:?} ({}..={})",
+						entry.relayer, entry.messages.begin, entry.messages.end
+					)
+				})
+				.collect::<Vec<_>>()
+				.join(", ")
+		};
+		let oldest_undelivered = match self.oldest_undelivered {
+			Some((nonce, Some(age))) => format!("{} (sent {}s ago)", nonce, age.as_secs()),
+			Some((nonce, None)) => format!("{nonce} (age is unknown)"),
+			None => "none".into(),
+		};
+
+		format!(
+			"Lane {} from {} to {}:\n\
+			\t{}: latest generated nonce: {}, latest confirmed nonce: {}, oldest unpruned nonce: {}\n\
+			\t{}: latest received nonce: {}, latest confirmed nonce: {}\n\
+			\tpending messages: {}\n\
+			\tdelivered, but unconfirmed messages: {}\n\
+			\tunrewarded relayers: {}\n\
+			\toldest undelivered message: {}",
+			hex::encode(lane),
+			source_name,
+			target_name,
+			source_name,
+			self.outbound.latest_generated_nonce,
+			self.outbound.latest_received_nonce,
+			self.outbound.oldest_unpruned_nonce,
+			target_name,
+			self.latest_received_nonce(),
+			self.inbound.last_confirmed_nonce,
+			self.pending_messages(),
+			self.unconfirmed_messages(),
+			unrewarded_relayers,
+			oldest_undelivered,
+		)
+	}
+}
+
+#[async_trait]
+trait LaneInspector: MessagesCliBridge {
+	async fn inspect_lane(data: InspectLane) -> anyhow::Result<()> {
+		let source_client = data.source.into_client::<Self::Source>().await?;
+		let target_client = data.target.into_client::<Self::Target>().await?;
+		let lane: LaneId = data.lane.into();
+
+		let source_best_finalized = source_client.best_finalized_header_hash().await?;
+		let target_best_finalized = target_client.best_finalized_header_hash().await?;
+		let outbound =
+			outbound_lane_data::<Self::MessagesLane>(&source_client, lane, source_best_finalized)
+				.await?;
+		let inbound =
+			inbound_lane_data::<Self::MessagesLane>(&target_client, lane, target_best_finalized)
+				.await?;
+
+		let mut report = LaneReport { outbound, inbound, oldest_undelivered: None };
+		if let Some(nonce) = report.oldest_undelivered_nonce() {
+			// it may fail if the source node has already pruned state of the block, where the
+			// message has been generated - it isn't critical for the report
+			let age = match message_age::<Self::MessagesLane>(&source_client, lane, nonce).await {
+				Ok(age) => Some(age),
+				Err(e) => {
+					log::warn!(
+						target: "bridge",
+						"Failed to compute age of {} message {}: {:?}",
+						Self::Source::NAME,
+						nonce,
+						e,
+					);
+					None
+				},
+			};
+			report.oldest_undelivered = Some((nonce, age));
+		}
+
+		log::info!(
+			target: "bridge",
+			"{}",
+			report.format(lane, Self::Source::NAME, Self::Target::NAME),
+		);
+
+		Ok(())
+	}
+}
+
+impl LaneInspector for MillauToRialtoCliBridge {}
+impl LaneInspector for RialtoToMillauCliBridge {}
+impl LaneInspector for MillauToRialtoParachainCliBridge {}
+impl LaneInspector for RialtoParachainToMillauCliBridge {}
+impl LaneInspector for BridgeHubRococoToBridgeHubWococoMessagesCliBridge {}
+impl LaneInspector for BridgeHubWococoToBridgeHubRococoMessagesCliBridge {}
+impl LaneInspector for BridgeHubRococoToBridgeHubWestendMessagesCliBridge {}
+impl LaneInspector for BridgeHubWestendToBridgeHubRococoMessagesCliBridge {}
+
+impl InspectLane {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		match self.bridge {
+			FullBridge::MillauToRialto => MillauToRialtoCliBridge::inspect_lane(self),
+			FullBridge::RialtoToMillau => RialtoToMillauCliBridge::inspect_lane(self),
+			FullBridge::MillauToRialtoParachain =>
+				MillauToRialtoParachainCliBridge::inspect_lane(self),
+			FullBridge::RialtoParachainToMillau =>
+				RialtoParachainToMillauCliBridge::inspect_lane(self),
+			FullBridge::BridgeHubRococoToBridgeHubWococo =>
+				BridgeHubRococoToBridgeHubWococoMessagesCliBridge::inspect_lane(self),
+			FullBridge::BridgeHubWococoToBridgeHubRococo =>
+				BridgeHubWococoToBridgeHubRococoMessagesCliBridge::inspect_lane(self),
+			FullBridge::BridgeHubRococoToBridgeHubWestend =>
+				BridgeHubRococoToBridgeHubWestendMessagesCliBridge::inspect_lane(self),
+			FullBridge::BridgeHubWestendToBridgeHubRococo =>
+				BridgeHubWestendToBridgeHubRococoMessagesCliBridge::inspect_lane(self),
+		}
+		.await
+	}
+}
+
+/// Read outbound lane state at given block of the source chain.
+async fn outbound_lane_data<P: SubstrateMessageLane>(
+	client: &Client<P::SourceChain>,
+	lane: LaneId,
+	at_block: HashOf<P::SourceChain>,
+) -> anyhow::Result<OutboundLaneData> {
+	Ok(client
+		.storage_value(
+			outbound_lane_data_key(P::TargetChain::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane),
+			Some(at_block),
+		)
+		.await?
+		.unwrap_or_default())
+}
+
+/// Read inbound lane state at given block of the target chain.
+async fn inbound_lane_data<P: SubstrateMessageLane>(
+	client: &Client<P::TargetChain>,
+	lane: LaneId,
+	at_block: HashOf<P::TargetChain>,
+) -> anyhow::Result<InboundLaneData<AccountIdOf<P::SourceChain>>> {
+	Ok(client
+		.storage_value(
+			inbound_lane_data_key(P::SourceChain::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane),
+			Some(at_block),
+		)
+		.await?
+		.unwrap_or_default())
+}
+
+/// Returns age of the message with given nonce.
+///
+/// We are looking for the first finalized block where the message has been generated and then
+/// compare its timestamp with the current time.
+async fn message_age<P: SubstrateMessageLane>(
+	client: &Client<P::SourceChain>,
+	lane: LaneId,
+	nonce: MessageNonce,
+) -> anyhow::Result<Duration> {
+	let mut low: BlockNumberOf<P::SourceChain> = Default::default();
+	let mut high = client.best_finalized_header_number().await?;
+	while low < high {
+		let middle = low + (high - low) / 2u32.into();
+		let middle_hash = client.block_hash_by_number(middle).await?;
+		let outbound = outbound_lane_data::<P>(client, lane, middle_hash).await?;
+		if outbound.latest_generated_nonce >= nonce {
+			high = middle;
+		} else {
+			low = middle.saturating_add(One::one());
+		}
+	}
+
+	let generated_at = client.block_hash_by_number(high).await?;
+	let timestamp: u64 = client
+		.storage_value(bp_runtime::storage_value_key("Timestamp", "Now"), Some(generated_at))
+		.await?
+		.ok_or_else(|| {
+			anyhow::format_err!(
+				"Missing {} timestamp at block {:?}",
+				P::SourceChain::NAME,
+				generated_at
+			)
+		})?;
+	let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+	Ok(now.saturating_sub(Duration::from_millis(timestamp)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bp_messages::{DeliveredMessages, UnrewardedRelayer};
+
+	fn report(
+		latest_generated_nonce: MessageNonce,
+		latest_received_nonce: MessageNonce,
+		latest_confirmed_nonce: MessageNonce,
+	) -> LaneReport<u64> {
+		LaneReport {
+			outbound: OutboundLaneData {
+				oldest_unpruned_nonce: latest_confirmed_nonce + 1,
+				latest_received_nonce: latest_confirmed_nonce,
+				latest_generated_nonce,
+			},
+			inbound: InboundLaneData {
+				relayers: if latest_received_nonce > latest_confirmed_nonce {
+					vec![UnrewardedRelayer {
+						relayer: 42,
+						messages: DeliveredMessages {
+							begin: latest_confirmed_nonce + 1,
+							end: latest_received_nonce,
+						},
+					}]
+					.into()
+				} else {
+					Default::default()
+				},
+				last_confirmed_nonce: latest_confirmed_nonce,
+			},
+			oldest_undelivered: None,
+		}
+	}
+
+	#[test]
+	fn lane_report_counts_pending_and_unconfirmed_messages() {
+		let report = report(10, 7, 5);
+		assert_eq!(report.latest_received_nonce(), 7);
+		assert_eq!(report.oldest_undelivered_nonce(), Some(8));
+		assert_eq!(report.pending_messages(), 3);
+		assert_eq!(report.unconfirmed_messages(), 2);
+	}
+
+	#[test]
+	fn lane_report_of_idle_lane() {
+		let report = report(10, 10, 10);
+		assert_eq!(report.oldest_undelivered_nonce(), None);
+		assert_eq!(report.pending_messages(), 0);
+		assert_eq!(report.unconfirmed_messages(), 0);
+
+		let formatted = report.format(LaneId([0, 0, 0, 1]), "Source", "Target");
+		assert!(formatted.contains("Lane 00000001 from Source to Target"));
+		assert!(formatted.contains("unrewarded relayers: none"));
+		assert!(formatted.contains("oldest undelivered message: none"));
+	}
+
+	#[test]
+	fn lane_report_is_formatted() {
+		let mut report = report(10, 7, 5);
+		report.oldest_undelivered = Some((8, Some(Duration::from_secs(120))));
+
+		let formatted = report.format(LaneId([0, 0, 0, 0]), "Source", "Target");
+		assert!(formatted.contains(
+			"Source: latest generated nonce: 10, latest confirmed nonce: 5, oldest unpruned nonce: 6"
+		));
+		assert!(formatted.contains("Target: latest received nonce: 7, latest confirmed nonce: 5"));
+		assert!(formatted.contains("pending messages: 3"));
+		assert!(formatted.contains("delivered, but unconfirmed messages: 2"));
+		assert!(formatted.contains("unrewarded relayers: 42 (6..=7)"));
+		assert!(formatted.contains("oldest undelivered message: 8 (sent 120s ago)"));
+	}
+
+	#[test]
+	fn inspect_lane_params_are_parsed() {
+		let inspect_lane = InspectLane::from_iter(vec![
+			"inspect-lane",
+			"millau-to-rialto",
+			"--lane",
+			"00000001",
+			"--source-port",
+			"1234",
+			"--target-port",
+			"5678",
+		]);
+
+		assert_eq!(inspect_lane.bridge, FullBridge::MillauToRialto);
+		assert_eq!(inspect_lane.lane, HexLaneId([0, 0, 0, 1]));
+		assert_eq!(inspect_lane.source.source_port, 1234);
+		assert_eq!(inspect_lane.target.target_port, 5678);
+	}
+}
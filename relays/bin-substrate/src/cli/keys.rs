@@ -0,0 +1,313 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Relay keystore and key management subcommands.
+//!
+//! The keystore is a directory, where every file holds SURI of a single key. The file name is
+//! the key name, which may be used by relay commands instead of passing SURI on the command line
+//! (e.g. `--source-signer-key relayer`).
+
+use sp_core::{
+	crypto::{Ss58AddressFormat, Ss58Codec},
+	sr25519, Pair,
+};
+use std::{
+	io::Write,
+	path::{Path, PathBuf},
+};
+use structopt::StructOpt;
+
+/// Name of the environment variable with the keystore path.
+pub const KEYSTORE_PATH_ENV: &str = "SUBSTRATE_RELAY_KEYSTORE";
+/// Default keystore path, relative to the user home directory.
+const DEFAULT_KEYSTORE_PATH: &str = ".substrate-relay/keystore";
+
+/// Manage relayer keys.
+#[derive(StructOpt)]
+pub enum Keys {
+	/// Generate new key and store it in the keystore.
+	Generate {
+		#[structopt(flatten)]
+		keystore: KeystoreParams,
+		/// Name of the new key.
+		name: String,
+		/// SS58 prefix of the printed address.
+		#[structopt(long, default_value = "42")]
+		ss58_prefix: u16,
+	},
+	/// Import existing key into the keystore.
+	Import {
+		#[structopt(flatten)]
+		keystore: KeystoreParams,
+		/// Name of the imported key.
+		name: String,
+		/// Path to the file, that contains SURI of the key.
+		#[structopt(long)]
+		from_file: Option<PathBuf>,
+		/// Name of the environment variable, that contains SURI of the key.
+		#[structopt(long)]
+		from_env: Option<String>,
+		/// Path to the key file of the Substrate node keystore.
+		#[structopt(long)]
+		from_keystore_file: Option<PathBuf>,
+		/// SS58 prefix of the printed address.
+		#[structopt(long, default_value = "42")]
+		ss58_prefix: u16,
+	},
+	/// Print public key and address of the key from the keystore.
+	Inspect {
+		#[structopt(flatten)]
+		keystore: KeystoreParams,
+		/// Name of the key.
+		name: String,
+		/// SS58 prefix of the printed address.
+		#[structopt(long, default_value = "42")]
+		ss58_prefix: u16,
+	},
+	/// List names of all keys in the keystore.
+	List {
+		#[structopt(flatten)]
+		keystore: KeystoreParams,
+	},
+}
+
+/// Keystore parameters.
+#[derive(StructOpt, Debug, PartialEq, Eq)]
+pub struct KeystoreParams {
+	/// Path to the keystore directory. Defaults to the value of `SUBSTRATE_RELAY_KEYSTORE`
+	/// environment variable or to the `~/.substrate-relay/keystore`.
+	#[structopt(long)]
+	keystore: Option<PathBuf>,
+}
+
+impl Keys {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		match self {
+			Keys::Generate { keystore, name, ss58_prefix } => {
+				let keystore = Keystore::new(keystore.keystore)?;
+				let (pair, phrase, _) = sr25519::Pair::generate_with_phrase(None);
+				keystore.write_suri(&name, &phrase)?;
+				log::info!(target: "bridge", "Generated key {}", name);
+				print_key(&pair, ss58_prefix);
+			},
+			Keys::Import {
+				keystore,
+				name,
+				from_file,
+				from_env,
+				from_keystore_file,
+				ss58_prefix,
+			} => {
+				let keystore = Keystore::new(keystore.keystore)?;
+				let suri = match (from_file, from_env, from_keystore_file) {
+					(Some(path), None, None) => read_file(&path)?,
+					(None, Some(var), None) => std::env::var(&var).map_err(|e| {
+						anyhow::format_err!("Failed to read environment variable {}: {}", var, e)
+					})?,
+					(None, None, Some(path)) =>
+						suri_from_substrate_keystore_file(&read_file(&path)?),
+					_ => anyhow::bail!(
+						"Exactly one of options must be specified: '--from-file', '--from-env' or \
+						'--from-keystore-file'"
+					),
+				};
+				let pair = pair_from_suri(&suri)?;
+				keystore.write_suri(&name, suri.trim())?;
+				log::info!(target: "bridge", "Imported key {}", name);
+				print_key(&pair, ss58_prefix);
+			},
+			Keys::Inspect { keystore, name, ss58_prefix } => {
+				let keystore = Keystore::new(keystore.keystore)?;
+				let pair = pair_from_suri(&keystore.read_suri(&name)?)?;
+				print_key(&pair, ss58_prefix);
+			},
+			Keys::List { keystore } => {
+				let keystore = Keystore::new(keystore.keystore)?;
+				for name in keystore.names()? {
+					log::info!(target: "bridge", "{}", name);
+				}
+			},
+		}
+
+		Ok(())
+	}
+}
+
+/// Relay keystore.
+#[derive(Debug)]
+pub struct Keystore {
+	/// Path to the keystore directory.
+	path: PathBuf,
+}
+
+impl Keystore {
+	/// Open keystore at given path. If path is not specified, it is read from the
+	/// `SUBSTRATE_RELAY_KEYSTORE` environment variable. If it is missing, default path is used.
+	pub fn new(path: Option<PathBuf>) -> anyhow::Result<Self> {
+		let path = match path {
+			Some(path) => path,
+			None => match std::env::var_os(KEYSTORE_PATH_ENV) {
+				Some(path) => path.into(),
+				None => std::env::var_os("HOME")
+					.map(|home| Path::new(&home).join(DEFAULT_KEYSTORE_PATH))
+					.ok_or_else(|| {
+						anyhow::format_err!(
+							"Unable to select keystore path: neither {} nor HOME are set",
+							KEYSTORE_PATH_ENV,
+						)
+					})?,
+			},
+		};
+
+		Ok(Keystore { path })
+	}
+
+	/// Read SURI of the key with given name.
+	pub fn read_suri(&self, name: &str) -> anyhow::Result<String> {
+		read_file(&self.key_path(name)?).map(|suri| suri.trim().to_owned())
+	}
+
+	/// Write SURI of the new key with given name.
+	fn write_suri(&self, name: &str, suri: &str) -> anyhow::Result<()> {
+		let key_path = self.key_path(name)?;
+		std::fs::create_dir_all(&self.path).map_err(|e| {
+			anyhow::format_err!("Failed to create keystore directory {:?}: {}", self.path, e)
+		})?;
+
+		let mut options = std::fs::OpenOptions::new();
+		options.write(true).create_new(true);
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::OpenOptionsExt;
+			options.mode(0o600);
+		}
+		options
+			.open(&key_path)
+			.and_then(|mut file| file.write_all(suri.as_bytes()))
+			.map_err(|e| anyhow::format_err!("Failed to write key file {:?}: {}", key_path, e))
+	}
+
+	/// Returns names of all keys in the keystore.
+	fn names(&self) -> anyhow::Result<Vec<String>> {
+		let entries = std::fs::read_dir(&self.path).map_err(|e| {
+			anyhow::format_err!("Failed to read keystore directory {:?}: {}", self.path, e)
+		})?;
+
+		let mut names = Vec::new();
+		for entry in entries {
+			let entry = entry?;
+			if !entry.file_type()?.is_file() {
+				continue
+			}
+			if let Some(name) = entry.file_name().to_str() {
+				if is_valid_key_name(name) {
+					names.push(name.to_owned());
+				}
+			}
+		}
+		names.sort();
+
+		Ok(names)
+	}
+
+	/// Returns path to the key file.
+	fn key_path(&self, name: &str) -> anyhow::Result<PathBuf> {
+		if !is_valid_key_name(name) {
+			anyhow::bail!(
+				"Invalid key name: {:?}. Only alphanumeric characters, '-' and '_' are allowed",
+				name,
+			)
+		}
+
+		Ok(self.path.join(name))
+	}
+}
+
+/// Returns true if key name may be used as a file name within the keystore directory.
+fn is_valid_key_name(name: &str) -> bool {
+	!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Read file contents.
+fn read_file(path: &Path) -> anyhow::Result<String> {
+	std::fs::read_to_string(path)
+		.map_err(|e| anyhow::format_err!("Failed to read file {:?}: {}", path, e))
+}
+
+/// Extract SURI from the key file of the Substrate node keystore.
+///
+/// The file contains JSON-encoded string with the secret phrase or seed.
+fn suri_from_substrate_keystore_file(contents: &str) -> String {
+	let contents = contents.trim();
+	contents
+		.strip_prefix('"')
+		.and_then(|contents| contents.strip_suffix('"'))
+		.unwrap_or(contents)
+		.to_owned()
+}
+
+/// Parse SURI into key pair.
+fn pair_from_suri(suri: &str) -> anyhow::Result<sr25519::Pair> {
+	sr25519::Pair::from_string(suri.trim(), None)
+		.map_err(|e| anyhow::format_err!("Invalid SURI: {:?}", e))
+}
+
+/// Print public key and address of the key pair.
+fn print_key(pair: &sr25519::Pair, ss58_prefix: u16) {
+	let public = pair.public();
+	log::info!(
+		target: "bridge",
+		"Public key: 0x{}\n\tSS58 address: {}",
+		hex::encode(public),
+		public.to_ss58check_with_version(Ss58AddressFormat::custom(ss58_prefix)),
+	);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn keystore_stores_and_reads_keys() {
+		let temp_dir = tempfile::tempdir().unwrap();
+		let keystore = Keystore::new(Some(temp_dir.path().join("keystore"))).unwrap();
+
+		keystore.write_suri("relayer", "//Alice").unwrap();
+		keystore.write_suri("relayer-2", "//Bob").unwrap();
+		assert_eq!(keystore.read_suri("relayer").unwrap(), "//Alice");
+		assert_eq!(keystore.names().unwrap(), vec!["relayer", "relayer-2"]);
+
+		// existing keys are never overwritten
+		assert!(keystore.write_suri("relayer", "//Bob").is_err());
+		assert_eq!(keystore.read_suri("relayer").unwrap(), "//Alice");
+	}
+
+	#[test]
+	fn keystore_rejects_invalid_key_names() {
+		let keystore = Keystore::new(Some(PathBuf::from("keystore"))).unwrap();
+		assert!(keystore.key_path("relayer_1").is_ok());
+		assert!(keystore.key_path("").is_err());
+		assert!(keystore.key_path("../relayer").is_err());
+		assert!(keystore.key_path("relayer/1").is_err());
+	}
+
+	#[test]
+	fn suri_is_read_from_substrate_keystore_file() {
+		assert_eq!(suri_from_substrate_keystore_file("\"//Alice\"\n"), "//Alice");
+		assert_eq!(suri_from_substrate_keystore_file("//Alice"), "//Alice");
+	}
+}
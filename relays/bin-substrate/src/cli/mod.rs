@@ -27,28 +27,49 @@ use signal_hook_async_std::Signals;
 use structopt::{clap::arg_enum, StructOpt};
 use strum::{EnumString, EnumVariantNames};
 
-use bp_messages::LaneId;
+use bp_messages::{LaneId, MessageNonce};
 use relay_substrate_client::SimpleRuntimeVersion;
 
 pub(crate) mod bridge;
 pub(crate) mod encode_message;
 pub(crate) mod send_message;
 
+mod benchmark_proof;
+mod bridge_call;
 mod chain_schema;
+mod claim_rewards;
+mod config_file;
+mod detect_equivocations;
+mod estimate_fee;
 mod init_bridge;
+mod inspect_lane;
+mod keys;
+mod monitor;
 mod register_parachain;
 mod relay_headers;
 mod relay_headers_and_messages;
 mod relay_messages;
 mod relay_parachains;
+mod relayer_funds;
 mod resubmit_transactions;
+mod track_parachain;
 
 /// The target that will be used when publishing logs related to this pallet.
 pub const LOG_TARGET: &str = "bridge";
 
 /// Parse relay CLI args.
+///
+/// If `--config <PATH>` argument is passed, the command and its arguments are read from the
+/// configuration file and other CLI args are used as overrides.
 pub fn parse_args() -> Command {
-	Command::from_args()
+	match config_file::args_with_config_file(std::env::args_os().collect()) {
+		Ok(args) => Command::from_iter(args),
+		Err(e) => structopt::clap::Error::with_description(
+			&format!("{e:#}"),
+			structopt::clap::ErrorKind::InvalidValue,
+		)
+		.exit(),
+	}
 }
 
 /// Substrate-to-Substrate bridge utilities.
@@ -82,10 +103,76 @@ pub enum Command {
 	/// The message is being sent to the source chain, delivered to the target chain and dispatched
 	/// there.
 	SendMessage(send_message::SendMessage),
+	/// Estimate cost of sending message over the bridge.
+	///
+	/// Queries both chains and prints fees of the message send, delivery and confirmation
+	/// transactions. The relayer reward is not included, because it is configured by the source
+	/// chain runtime and is not exposed to the relay.
+	EstimateFee(estimate_fee::EstimateFee),
+	/// Benchmark bridge proofs.
+	///
+	/// Builds typical and worst-case messages, delivery and finality proofs using the live chains
+	/// state and prints their sizes and weights, computed by the runtime.
+	BenchmarkProof(benchmark_proof::BenchmarkProof),
+	/// Print state of the message lane.
+	///
+	/// Reads lane state from both chains and prints latest nonces, number of pending messages,
+	/// unrewarded relayers and age of the oldest undelivered message.
+	InspectLane(inspect_lane::InspectLane),
+	/// Detect conflicting finalized headers.
+	///
+	/// Compares headers, imported into the GRANDPA light client at the target chain, with the
+	/// canonical source chain and reports conflicting headers. Optionally halts the light client
+	/// when conflicting headers are found.
+	DetectEquivocations(detect_equivocations::DetectEquivocations),
+	/// Decode bridge call or transaction.
+	///
+	/// Decodes hex-encoded call or signed transaction (e.g. copied from the block explorer) of
+	/// the source or target chain of the bridge and prints it.
+	DecodeCall(bridge_call::DecodeCall),
+	/// Encode bridge call.
+	///
+	/// Constructs messages delivery or delivery confirmation call from its parts and prints its
+	/// hex encoding.
+	EncodeCall(bridge_call::EncodeCall),
+	/// Claim relayer rewards.
+	///
+	/// Claims all pending rewards of the signer account from the bridge relayers pallet and
+	/// optionally transfers them to the beneficiary account.
+	ClaimRewards(claim_rewards::ClaimRewards),
+	/// Transfer tokens to the relayer account.
+	///
+	/// Transfers given amount, or the amount that is missing for the relayer balance to reach
+	/// given value, from the signer account. Refuses to create relayer account with balance
+	/// below existential deposit.
+	FundRelayer(relayer_funds::FundRelayer),
+	/// Transfer all tokens from the relayer account.
+	///
+	/// By default, existential deposit is kept at the relayer account.
+	SweepRelayer(relayer_funds::SweepRelayer),
+	/// Manage relayer keys.
+	///
+	/// Generates, imports and inspects keys of the relay keystore. Relay commands may reference
+	/// these keys by name (e.g. `--source-signer-key relayer`) instead of passing SURI on the
+	/// command line.
+	Keys(keys::Keys),
+	/// Start bridge monitor.
+	///
+	/// Tracks state of message lanes, finality of both chains and balances of relayer accounts
+	/// and exposes it as Prometheus metrics, using the same metric names as the relay. Never
+	/// submits any transactions.
+	Monitor(monitor::Monitor),
 	/// Resubmit transactions with increased tip if they are stalled.
 	ResubmitTransactions(resubmit_transactions::ResubmitTransactions),
 	/// Register parachain.
 	RegisterParachain(register_parachain::RegisterParachain),
+	/// Add parachain to the tracked parachains set of the bridge parachains pallet.
+	///
+	/// Submits transaction that adds parachain to the tracked set at the target chain and waits
+	/// until the parachain head is imported by the parachains relay. The target signer must be
+	/// the owner of the pallet. Not to be confused with the `register-parachain` command, which
+	/// registers parachain at the relay chain.
+	TrackParachain(track_parachain::TrackParachain),
 	///
 	RelayParachains(relay_parachains::RelayParachains),
 }
@@ -99,6 +186,7 @@ impl Command {
 			Self::RelayHeaders(_) |
 			Self::RelayMessages(_) |
 			Self::RelayHeadersAndMessages(_) |
+			Self::Monitor(_) |
 			Self::InitBridge(_) => {
 				initialize_relay();
 			},
@@ -116,8 +204,20 @@ impl Command {
 			Self::RelayHeadersAndMessages(arg) => arg.run().await?,
 			Self::InitBridge(arg) => arg.run().await?,
 			Self::SendMessage(arg) => arg.run().await?,
+			Self::EstimateFee(arg) => arg.run().await?,
+			Self::BenchmarkProof(arg) => arg.run().await?,
+			Self::InspectLane(arg) => arg.run().await?,
+			Self::DetectEquivocations(arg) => arg.run().await?,
+			Self::DecodeCall(arg) => arg.run().await?,
+			Self::EncodeCall(arg) => arg.run().await?,
+			Self::ClaimRewards(arg) => arg.run().await?,
+			Self::FundRelayer(arg) => arg.run().await?,
+			Self::SweepRelayer(arg) => arg.run().await?,
+			Self::Keys(arg) => arg.run().await?,
+			Self::Monitor(arg) => arg.run().await?,
 			Self::ResubmitTransactions(arg) => arg.run().await?,
 			Self::RegisterParachain(arg) => arg.run().await?,
+			Self::TrackParachain(arg) => arg.run().await?,
 			Self::RelayParachains(arg) => arg.run().await?,
 		}
 		Ok(())
@@ -226,7 +326,7 @@ impl std::str::FromStr for HexBytes {
 	type Err = hex::FromHexError;
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		Ok(Self(hex::decode(s)?))
+		Ok(Self(hex::decode(s.strip_prefix("0x").unwrap_or(s))?))
 	}
 }
 
@@ -256,6 +356,56 @@ pub struct PrometheusParams {
 	pub prometheus_port: u16,
 }
 
+/// Messages delivery confirmations params.
+#[derive(Clone, Debug, PartialEq, Eq, StructOpt)]
+pub struct ConfirmationParams {
+	/// Minimal number of messages that must be confirmed by single delivery confirmation
+	/// transaction. Smaller transactions are still submitted if the number of unconfirmed
+	/// messages approaches the limit of the source chain.
+	#[structopt(long, default_value = "1")]
+	pub min_confirmations_in_batch: MessageNonce,
+	/// If passed, delivery confirmations won't wait for other confirmations for longer than
+	/// given number of seconds.
+	#[structopt(long)]
+	pub max_confirmation_delay: Option<u64>,
+}
+
+impl From<ConfirmationParams> for substrate_relay_helper::messages_lane::ConfirmationParams {
+	fn from(params: ConfirmationParams) -> Self {
+		substrate_relay_helper::messages_lane::ConfirmationParams {
+			min_confirmations_in_batch: params.min_confirmations_in_batch,
+			max_confirmation_delay: params
+				.max_confirmation_delay
+				.map(std::time::Duration::from_secs),
+		}
+	}
+}
+
+/// Messages lane alerts params.
+#[derive(Clone, Debug, PartialEq, Eq, StructOpt)]
+pub struct AlertParams {
+	/// If the oldest undelivered message is not delivered for given number of seconds, the lane
+	/// is considered stalled. Defaults to 10 minutes.
+	#[structopt(long)]
+	pub max_undelivered_message_age: Option<u64>,
+	/// If number of delivered, but not yet confirmed messages exceeds this value, confirmations
+	/// are considered lagging. Defaults to the half of the number of unconfirmed messages that
+	/// the target chain accepts.
+	#[structopt(long)]
+	pub max_unconfirmed_messages: Option<MessageNonce>,
+}
+
+impl From<AlertParams> for substrate_relay_helper::messages_lane::AlertParams {
+	fn from(params: AlertParams) -> Self {
+		substrate_relay_helper::messages_lane::AlertParams {
+			max_undelivered_message_age: params
+				.max_undelivered_message_age
+				.map(std::time::Duration::from_secs),
+			max_unconfirmed_messages: params.max_unconfirmed_messages,
+		}
+	}
+}
+
 /// Struct to get git commit info and build time.
 #[derive(BuildInfo)]
 struct SubstrateRelayBuildInfo;
@@ -352,4 +502,10 @@ mod tests {
 		// then
 		assert_eq!(hex.0, hex2.0);
 	}
+
+	#[test]
+	fn hex_bytes_may_have_prefix() {
+		assert_eq!("0x01020304".parse::<HexBytes>().unwrap().0, vec![1, 2, 3, 4]);
+		assert_eq!("01020304".parse::<HexBytes>().unwrap().0, vec![1, 2, 3, 4]);
+	}
 }
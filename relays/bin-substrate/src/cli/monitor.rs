@@ -0,0 +1,207 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	chains::{
+		bridge_hub_rococo_messages_to_bridge_hub_westend::BridgeHubRococoToBridgeHubWestendMessagesCliBridge,
+		bridge_hub_rococo_messages_to_bridge_hub_wococo::BridgeHubRococoToBridgeHubWococoMessagesCliBridge,
+		bridge_hub_westend_messages_to_bridge_hub_rococo::BridgeHubWestendToBridgeHubRococoMessagesCliBridge,
+		bridge_hub_wococo_messages_to_bridge_hub_rococo::BridgeHubWococoToBridgeHubRococoMessagesCliBridge,
+		millau_headers_to_rialto::MillauToRialtoCliBridge,
+		millau_headers_to_rialto_parachain::MillauToRialtoParachainCliBridge,
+		rialto_headers_to_millau::RialtoToMillauCliBridge,
+		rialto_parachains_to_millau::RialtoParachainToMillauCliBridge,
+	},
+	cli::{
+		bridge::{FullBridge, MessagesCliBridge},
+		chain_schema::*,
+		AlertParams, HexLaneId, PrometheusParams,
+	},
+};
+use async_trait::async_trait;
+use bp_messages::LaneId;
+use futures::FutureExt;
+use relay_substrate_client::{AccountIdOf, BalanceOf, Chain, ChainWithBalances};
+use relay_utils::metrics::{GlobalMetrics, StandaloneMetric};
+use sp_runtime::AccountId32;
+use structopt::StructOpt;
+use strum::VariantNames;
+use substrate_relay_helper::{messages_lane::MessagesMonitorParams, TaggedAccount};
+
+/// Start bridge monitor.
+///
+/// The monitor exposes the same Prometheus metrics as the relay (lane state, finality lag and
+/// relayer balances), but it never submits any transactions.
+#[derive(StructOpt)]
+pub struct Monitor {
+	/// A bridge instance to monitor.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	/// Hex-encoded lane identifiers that should be monitored. Defaults to `00000000`.
+	#[structopt(long, default_value = "00000000")]
+	lane: Vec<HexLaneId>,
+	/// Relayer accounts at the source chain, which balances are exposed as metrics.
+	#[structopt(long)]
+	source_relayer: Vec<AccountId32>,
+	/// Relayer accounts at the target chain, which balances are exposed as metrics.
+	#[structopt(long)]
+	target_relayer: Vec<AccountId32>,
+	#[structopt(flatten)]
+	alert_params: AlertParams,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+	#[structopt(flatten)]
+	prometheus_params: PrometheusParams,
+}
+
+#[async_trait]
+trait BridgeMonitor: MessagesCliBridge
+where
+	Self::Source: ChainWithBalances,
+	Self::Target: ChainWithBalances,
+	AccountIdOf<Self::Source>: From<AccountId32>,
+	AccountIdOf<Self::Target>: From<AccountId32>,
+	BalanceOf<Self::Source>: Into<u128>,
+	BalanceOf<Self::Target>: Into<u128>,
+{
+	async fn monitor(data: Monitor) -> anyhow::Result<()> {
+		// the monitor never submits transactions, but let's be extra careful here
+		let source_client = data.source.into_client::<Self::Source>().await?.with_dry_run(true);
+		let target_client = data.target.into_client::<Self::Target>().await?.with_dry_run(true);
+
+		let metrics_params = data.prometheus_params.into_metrics_params()?;
+		let mut metrics_params = relay_utils::relay_metrics(metrics_params).into_params();
+		GlobalMetrics::new()?.register_and_spawn(&metrics_params.registry)?;
+
+		let lanes = data.lane.into_iter().map(LaneId::from).collect::<Vec<_>>();
+		let alert_params = data.alert_params.into();
+		let source_accounts = data
+			.source_relayer
+			.into_iter()
+			.map(|id| TaggedAccount::Messages {
+				id: id.into(),
+				bridged_chain: Self::Target::NAME.to_string(),
+			})
+			.collect::<Vec<_>>();
+		let target_accounts = data
+			.target_relayer
+			.into_iter()
+			.map(|id| TaggedAccount::Messages {
+				id: id.into(),
+				bridged_chain: Self::Source::NAME.to_string(),
+			})
+			.collect::<Vec<_>>();
+		substrate_relay_helper::messages_metrics::add_relay_balances_metrics::<_, Self::Target>(
+			source_client.clone(),
+			&mut metrics_params,
+			&source_accounts,
+			&lanes,
+		)
+		.await?;
+		substrate_relay_helper::messages_metrics::add_relay_balances_metrics::<_, Self::Source>(
+			target_client.clone(),
+			&mut metrics_params,
+			&target_accounts,
+			&lanes,
+		)
+		.await?;
+
+		let mut monitors = Vec::with_capacity(lanes.len());
+		for lane_id in lanes {
+			monitors.push(
+				substrate_relay_helper::messages_lane::monitor::<Self::MessagesLane>(
+					MessagesMonitorParams {
+						source_client: source_client.clone(),
+						target_client: target_client.clone(),
+						lane_id,
+						alert_params,
+						metrics_params: metrics_params.clone().disable(),
+					},
+				)
+				.boxed(),
+			);
+		}
+
+		relay_utils::relay_metrics(metrics_params)
+			.expose()
+			.await
+			.map_err(|e| anyhow::format_err!("{}", e))?;
+
+		futures::future::select_all(monitors).await.0
+	}
+}
+
+impl BridgeMonitor for MillauToRialtoCliBridge {}
+impl BridgeMonitor for RialtoToMillauCliBridge {}
+impl BridgeMonitor for MillauToRialtoParachainCliBridge {}
+impl BridgeMonitor for RialtoParachainToMillauCliBridge {}
+impl BridgeMonitor for BridgeHubRococoToBridgeHubWococoMessagesCliBridge {}
+impl BridgeMonitor for BridgeHubWococoToBridgeHubRococoMessagesCliBridge {}
+impl BridgeMonitor for BridgeHubRococoToBridgeHubWestendMessagesCliBridge {}
+impl BridgeMonitor for BridgeHubWestendToBridgeHubRococoMessagesCliBridge {}
+
+impl Monitor {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		match self.bridge {
+			FullBridge::MillauToRialto => MillauToRialtoCliBridge::monitor(self),
+			FullBridge::RialtoToMillau => RialtoToMillauCliBridge::monitor(self),
+			FullBridge::MillauToRialtoParachain => MillauToRialtoParachainCliBridge::monitor(self),
+			FullBridge::RialtoParachainToMillau => RialtoParachainToMillauCliBridge::monitor(self),
+			FullBridge::BridgeHubRococoToBridgeHubWococo =>
+				BridgeHubRococoToBridgeHubWococoMessagesCliBridge::monitor(self),
+			FullBridge::BridgeHubWococoToBridgeHubRococo =>
+				BridgeHubWococoToBridgeHubRococoMessagesCliBridge::monitor(self),
+			FullBridge::BridgeHubRococoToBridgeHubWestend =>
+				BridgeHubRococoToBridgeHubWestendMessagesCliBridge::monitor(self),
+			FullBridge::BridgeHubWestendToBridgeHubRococo =>
+				BridgeHubWestendToBridgeHubRococoMessagesCliBridge::monitor(self),
+		}
+		.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn monitor_params_are_parsed() {
+		let monitor = Monitor::from_iter(vec![
+			"monitor",
+			"millau-to-rialto",
+			"--lane",
+			"00000000",
+			"--lane",
+			"00000001",
+			"--source-port",
+			"1234",
+			"--target-port",
+			"5678",
+			"--target-relayer",
+			"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+		]);
+
+		assert_eq!(monitor.bridge, FullBridge::MillauToRialto);
+		assert_eq!(monitor.lane, vec![HexLaneId([0, 0, 0, 0]), HexLaneId([0, 0, 0, 1])]);
+		assert!(monitor.source_relayer.is_empty());
+		assert_eq!(monitor.target_relayer.len(), 1);
+		assert_eq!(monitor.source.source_port, 1234);
+		assert_eq!(monitor.target.target_port, 5678);
+	}
+}
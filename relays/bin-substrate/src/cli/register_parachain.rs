@@ -299,13 +299,24 @@ mod tests {
 						relaychain_version_mode: RuntimeVersionType::Bundle,
 						relaychain_spec_version: None,
 						relaychain_transaction_version: None,
-					}
+					},
+					relaychain_tip: None,
+					relaychain_tip_multiplier_percent: None,
+					relaychain_default_transactions_mortality: None,
+					relaychain_unsigned_transactions: false,
+					relaychain_tls_ca_certificates: None,
+					relaychain_tls_client_certificate: None,
+					relaychain_tls_client_key: None,
+					relaychain_tls_server_name: None,
+					relaychain_proxy: None,
+					relaychain_max_requests_per_second: None,
 				},
 				relay_sign: RelaychainSigningParams {
 					relaychain_signer: Some("//Alice".into()),
 					relaychain_signer_password: None,
 					relaychain_signer_file: None,
 					relaychain_signer_password_file: None,
+					relaychain_signer_key: None,
 					relaychain_transactions_mortality: None,
 				},
 				para_connection: ParachainConnectionParams {
@@ -316,7 +327,17 @@ mod tests {
 						parachain_version_mode: RuntimeVersionType::Bundle,
 						parachain_spec_version: None,
 						parachain_transaction_version: None,
-					}
+					},
+					parachain_tip: None,
+					parachain_tip_multiplier_percent: None,
+					parachain_default_transactions_mortality: None,
+					parachain_unsigned_transactions: false,
+					parachain_tls_ca_certificates: None,
+					parachain_tls_client_certificate: None,
+					parachain_tls_client_key: None,
+					parachain_tls_server_name: None,
+					parachain_proxy: None,
+					parachain_max_requests_per_second: None,
 				},
 			}
 		);
@@ -15,16 +15,19 @@
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
 use async_trait::async_trait;
-use relay_substrate_client::{AccountIdOf, AccountKeyPairOf};
+use relay_substrate_client::{AccountIdOf, AccountKeyPairOf, ChainWithBalances, ChainWithBeefy};
 use sp_core::Pair;
 use structopt::StructOpt;
 use strum::{EnumString, EnumVariantNames, VariantNames};
 
 use crate::chains::{
+	millau_beefy_to_rialto::MillauToRialtoBeefyCliBridge,
 	millau_headers_to_rialto::MillauToRialtoCliBridge,
 	millau_headers_to_rialto_parachain::MillauToRialtoParachainCliBridge,
 	rialto_headers_to_millau::RialtoToMillauCliBridge,
+	rococo_headers_to_bridge_hub_westend::RococoToBridgeHubWestendCliBridge,
 	rococo_headers_to_bridge_hub_wococo::RococoToBridgeHubWococoCliBridge,
+	westend_headers_to_bridge_hub_rococo::WestendToBridgeHubRococoCliBridge,
 	westend_headers_to_millau::WestendToMillauCliBridge,
 	wococo_headers_to_bridge_hub_rococo::WococoToBridgeHubRococoCliBridge,
 };
@@ -39,10 +42,13 @@ pub struct RelayHeaders {
 	/// A bridge instance to relay headers for.
 	#[structopt(possible_values = RelayHeadersBridge::VARIANTS, case_insensitive = true)]
 	bridge: RelayHeadersBridge,
-	/// If passed, only mandatory headers (headers that are changing the GRANDPA authorities set)
-	/// are relayed.
+	/// If passed, only mandatory headers (headers that are changing the GRANDPA or BEEFY
+	/// authorities set) are relayed.
 	#[structopt(long)]
 	only_mandatory_headers: bool,
+	/// If passed, the relay prepares and validates transactions, but never submits them.
+	#[structopt(long)]
+	dry_run: bool,
 	#[structopt(flatten)]
 	source: SourceConnectionParams,
 	#[structopt(flatten)]
@@ -50,6 +56,8 @@ pub struct RelayHeaders {
 	#[structopt(flatten)]
 	target_sign: TargetSigningParams,
 	#[structopt(flatten)]
+	target_balance_guard: TargetBalanceGuardParams,
+	#[structopt(flatten)]
 	prometheus_params: PrometheusParams,
 }
 
@@ -63,23 +71,37 @@ pub enum RelayHeadersBridge {
 	MillauToRialtoParachain,
 	RococoToBridgeHubWococo,
 	WococoToBridgeHubRococo,
+	RococoToBridgeHubWestend,
+	WestendToBridgeHubRococo,
+	MillauToRialtoBeefy,
 }
 
 #[async_trait]
 trait HeadersRelayer: RelayToRelayHeadersCliBridge
 where
+	Self::Target: ChainWithBalances,
 	AccountIdOf<Self::Target>: From<<AccountKeyPairOf<Self::Target> as Pair>::Public>,
 {
 	/// Relay headers.
 	async fn relay_headers(data: RelayHeaders) -> anyhow::Result<()> {
 		let source_client = data.source.into_client::<Self::Source>().await?;
-		let target_client = data.target.into_client::<Self::Target>().await?;
+		let target_client =
+			data.target.into_client::<Self::Target>().await?.with_dry_run(data.dry_run);
 		let target_transactions_mortality = data.target_sign.target_transactions_mortality;
 		let target_sign = data.target_sign.to_keypair::<Self::Target>()?;
 
 		let metrics_params: relay_utils::metrics::MetricsParams =
 			data.prometheus_params.into_metrics_params()?;
 		GlobalMetrics::new()?.register_and_spawn(&metrics_params.registry)?;
+		data.target_balance_guard.start_balance_guard(
+			&target_client,
+			&target_sign,
+			&metrics_params,
+		)?;
+		relay_substrate_client::guard::start_runtime_compatibility_guard(
+			&target_client,
+			&metrics_params.registry,
+		)?;
 
 		let target_transactions_params = substrate_relay_helper::TransactionParams {
 			signer: target_sign,
@@ -109,6 +131,54 @@ impl HeadersRelayer for WestendToMillauCliBridge {}
 impl HeadersRelayer for MillauToRialtoParachainCliBridge {}
 impl HeadersRelayer for RococoToBridgeHubWococoCliBridge {}
 impl HeadersRelayer for WococoToBridgeHubRococoCliBridge {}
+impl HeadersRelayer for RococoToBridgeHubWestendCliBridge {}
+impl HeadersRelayer for WestendToBridgeHubRococoCliBridge {}
+
+#[async_trait]
+trait BeefyRelayer: RelayToRelayBeefyCliBridge
+where
+	Self::Source: ChainWithBeefy,
+	Self::Target: ChainWithBalances,
+	AccountIdOf<Self::Target>: From<<AccountKeyPairOf<Self::Target> as Pair>::Public>,
+{
+	/// Relay BEEFY commitments.
+	async fn relay_headers(data: RelayHeaders) -> anyhow::Result<()> {
+		let source_client = data.source.into_client::<Self::Source>().await?;
+		let target_client =
+			data.target.into_client::<Self::Target>().await?.with_dry_run(data.dry_run);
+		let target_transactions_mortality = data.target_sign.target_transactions_mortality;
+		let target_sign = data.target_sign.to_keypair::<Self::Target>()?;
+
+		let metrics_params: relay_utils::metrics::MetricsParams =
+			data.prometheus_params.into_metrics_params()?;
+		GlobalMetrics::new()?.register_and_spawn(&metrics_params.registry)?;
+		data.target_balance_guard.start_balance_guard(
+			&target_client,
+			&target_sign,
+			&metrics_params,
+		)?;
+		relay_substrate_client::guard::start_runtime_compatibility_guard(
+			&target_client,
+			&metrics_params.registry,
+		)?;
+
+		let target_transactions_params = substrate_relay_helper::TransactionParams {
+			signer: target_sign,
+			mortality: target_transactions_mortality,
+		};
+
+		substrate_relay_helper::beefy::run::<Self::Beefy>(
+			source_client,
+			target_client,
+			data.only_mandatory_headers,
+			target_transactions_params,
+			metrics_params,
+		)
+		.await
+	}
+}
+
+impl BeefyRelayer for MillauToRialtoBeefyCliBridge {}
 
 impl RelayHeaders {
 	/// Run the command.
@@ -123,6 +193,12 @@ impl RelayHeaders {
 				RococoToBridgeHubWococoCliBridge::relay_headers(self),
 			RelayHeadersBridge::WococoToBridgeHubRococo =>
 				WococoToBridgeHubRococoCliBridge::relay_headers(self),
+			RelayHeadersBridge::RococoToBridgeHubWestend =>
+				RococoToBridgeHubWestendCliBridge::relay_headers(self),
+			RelayHeadersBridge::WestendToBridgeHubRococo =>
+				WestendToBridgeHubRococoCliBridge::relay_headers(self),
+			RelayHeadersBridge::MillauToRialtoBeefy =>
+				MillauToRialtoBeefyCliBridge::relay_headers(self),
 		}
 		.await
 	}
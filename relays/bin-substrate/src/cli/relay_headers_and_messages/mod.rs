@@ -31,20 +31,24 @@ mod relay_to_relay;
 mod relay_to_parachain;
 
 use async_trait::async_trait;
-use std::{marker::PhantomData, sync::Arc};
+use std::{marker::PhantomData, path::PathBuf, sync::Arc, time::Duration};
 use structopt::StructOpt;
+use strum::{EnumString, EnumVariantNames, VariantNames};
 
-use futures::{FutureExt, TryFutureExt};
+use futures::{future::BoxFuture, FutureExt, TryFutureExt};
 use relay_to_parachain::*;
 use relay_to_relay::*;
 
 use crate::{
 	chains::{
+		millau_beefy_to_rialto::MillauBeefyToRialto,
 		millau_headers_to_rialto::MillauToRialtoCliBridge,
 		millau_headers_to_rialto_parachain::MillauToRialtoParachainCliBridge,
 		rialto_headers_to_millau::RialtoToMillauCliBridge,
 		rialto_parachains_to_millau::RialtoParachainToMillauCliBridge,
+		rococo_parachains_to_bridge_hub_westend::BridgeHubRococoToBridgeHubWestendCliBridge,
 		rococo_parachains_to_bridge_hub_wococo::BridgeHubRococoToBridgeHubWococoCliBridge,
+		westend_parachains_to_bridge_hub_rococo::BridgeHubWestendToBridgeHubRococoCliBridge,
 		wococo_parachains_to_bridge_hub_rococo::BridgeHubWococoToBridgeHubRococoCliBridge,
 	},
 	cli::{
@@ -54,32 +58,68 @@ use crate::{
 		},
 		chain_schema::*,
 		relay_headers_and_messages::parachain_to_parachain::ParachainToParachainBridge,
-		CliChain, HexLaneId, PrometheusParams,
+		AlertParams, CliChain, ConfirmationParams, HexLaneId, PrometheusParams,
 	},
 	declare_chain_cli_schema,
 };
 use bp_messages::LaneId;
 use bp_runtime::BalanceOf;
 use relay_substrate_client::{
+	guard::{BalanceGuardMetrics, BalanceThresholds},
 	AccountIdOf, AccountKeyPairOf, Chain, ChainWithBalances, ChainWithMessages,
 	ChainWithTransactions, Client, Parachain,
 };
-use relay_utils::metrics::MetricsParams;
+use relay_utils::metrics::{Metric, MetricsParams};
 use sp_core::Pair;
 use substrate_relay_helper::{
 	messages_lane::MessagesRelayParams, on_demand::OnDemandRelay, TaggedAccount, TransactionParams,
 };
 
+/// Finality engine, used by the complex relay.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+pub enum FinalityEngine {
+	/// Only GRANDPA headers are relayed (on-demand).
+	Grandpa,
+	/// BEEFY commitments are relayed to the bridged chain, in addition to on-demand GRANDPA
+	/// headers. Messages pallets are verifying storage proofs using headers, imported by the
+	/// GRANDPA pallet, so GRANDPA headers are still required to deliver messages.
+	Beefy,
+}
+
 /// Parameters that have the same names across all bridges.
 #[derive(Debug, PartialEq, StructOpt)]
 pub struct HeadersAndMessagesSharedParams {
 	/// Hex-encoded lane identifiers that should be served by the complex relay.
 	#[structopt(long, default_value = "00000000")]
 	pub lane: Vec<HexLaneId>,
-	/// If passed, only mandatory headers (headers that are changing the GRANDPA authorities set)
-	/// are relayed.
+	/// If passed, only mandatory headers (headers that are changing the GRANDPA or BEEFY
+	/// authorities set) are relayed.
 	#[structopt(long)]
 	pub only_mandatory_headers: bool,
+	/// Finality engine, used by the relay.
+	#[structopt(
+		long,
+		default_value = "grandpa",
+		possible_values = FinalityEngine::VARIANTS,
+		case_insensitive = true
+	)]
+	pub finality_engine: FinalityEngine,
+	/// If passed, message relays won't deliver messages for given number of seconds after they
+	/// have seen messages delivered by other relayers.
+	#[structopt(long)]
+	pub cooperative_backoff: Option<u64>,
+	#[structopt(flatten)]
+	pub confirmation_params: ConfirmationParams,
+	#[structopt(flatten)]
+	pub alert_params: AlertParams,
+	/// If passed, the relay prepares and validates transactions, but never submits them.
+	#[structopt(long)]
+	pub dry_run: bool,
+	/// Path to the Unix socket, where the relay listens to admin commands (`pause-lane`,
+	/// `resume-lane`, `set-max-batch` and `status`).
+	#[structopt(long)]
+	pub admin_socket: Option<PathBuf>,
 	#[structopt(flatten)]
 	pub prometheus_params: PrometheusParams,
 }
@@ -106,9 +146,12 @@ impl<Left: ChainWithTransactions + CliChain, Right: ChainWithTransactions + CliC
 	/// Creates new bridge parameters from its components.
 	pub fn new<L2R: MessagesCliBridge<Source = Left, Target = Right>>(
 		shared: HeadersAndMessagesSharedParams,
-		left: BridgeEndCommonParams<Left>,
-		right: BridgeEndCommonParams<Right>,
+		mut left: BridgeEndCommonParams<Left>,
+		mut right: BridgeEndCommonParams<Right>,
 	) -> anyhow::Result<Self> {
+		left.client = left.client.with_dry_run(shared.dry_run);
+		right.client = right.client.with_dry_run(shared.dry_run);
+
 		// Create metrics registry.
 		let metrics_params = shared.prometheus_params.clone().into_metrics_params()?;
 		let metrics_params = relay_utils::relay_metrics(metrics_params).into_params();
@@ -127,6 +170,32 @@ pub struct BridgeEndCommonParams<Chain: ChainWithTransactions + CliChain> {
 	pub transactions_mortality: Option<u32>,
 	/// Accounts, which balances are exposed as metrics by the relay process.
 	pub accounts: Vec<TaggedAccount<AccountIdOf<Chain>>>,
+	/// Thresholds of the transactions signer balance, if low balance guard is enabled.
+	pub balance_thresholds: Option<BalanceThresholds<BalanceOf<Chain>>>,
+}
+
+/// Start low balance guard of the transactions signer at given bridge end, if it is enabled.
+fn start_balance_guard<Chain: ChainWithTransactions + ChainWithBalances + CliChain>(
+	params: &BridgeEndCommonParams<Chain>,
+	metrics_params: &MetricsParams,
+) -> anyhow::Result<()>
+where
+	AccountIdOf<Chain>: From<<AccountKeyPairOf<Chain> as Pair>::Public>,
+{
+	let thresholds = match params.balance_thresholds {
+		Some(thresholds) => thresholds,
+		None => return Ok(()),
+	};
+
+	let metrics = BalanceGuardMetrics::new(Chain::NAME)?;
+	metrics.register(&metrics_params.registry)?;
+	relay_substrate_client::guard::halt_submissions_on_low_balance(
+		params.client.clone(),
+		params.sign.public().into(),
+		thresholds,
+		Some(metrics),
+	);
+	Ok(())
 }
 
 /// All data of the bidirectional complex relay.
@@ -168,6 +237,9 @@ where
 		source_to_target_headers_relay: Arc<dyn OnDemandRelay<Source, Target>>,
 		target_to_source_headers_relay: Arc<dyn OnDemandRelay<Target, Source>>,
 		lane_id: LaneId,
+		cooperative_backoff: Option<Duration>,
+		confirmation_params: ConfirmationParams,
+		alert_params: AlertParams,
 	) -> MessagesRelayParams<Bridge::MessagesLane> {
 		MessagesRelayParams {
 			source_client: self.source.client.clone(),
@@ -183,6 +255,12 @@ where
 			source_to_target_headers_relay: Some(source_to_target_headers_relay),
 			target_to_source_headers_relay: Some(target_to_source_headers_relay),
 			lane_id,
+			cooperative_backoff,
+			start_from_nonce: None,
+			skip_nonces: Default::default(),
+			ancient_source_header_threshold: None,
+			confirmation_params: confirmation_params.into(),
+			alert_params: alert_params.into(),
 			metrics_params: self.metrics_params.clone().disable(),
 		}
 	}
@@ -196,6 +274,8 @@ declare_chain_cli_schema!(Rococo, rococo);
 declare_chain_cli_schema!(BridgeHubRococo, bridge_hub_rococo);
 declare_chain_cli_schema!(Wococo, wococo);
 declare_chain_cli_schema!(BridgeHubWococo, bridge_hub_wococo);
+declare_chain_cli_schema!(Westend, westend);
+declare_chain_cli_schema!(BridgeHubWestend, bridge_hub_westend);
 // Means to override signers of different layer transactions.
 declare_chain_cli_schema!(MillauHeadersToRialto, millau_headers_to_rialto);
 declare_chain_cli_schema!(MillauHeadersToRialtoParachain, millau_headers_to_rialto_parachain);
@@ -211,10 +291,21 @@ declare_chain_cli_schema!(
 	WococoParachainsToBridgeHubRococo,
 	wococo_parachains_to_bridge_hub_rococo
 );
+declare_chain_cli_schema!(RococoHeadersToBridgeHubWestend, rococo_headers_to_bridge_hub_westend);
+declare_chain_cli_schema!(
+	RococoParachainsToBridgeHubWestend,
+	rococo_parachains_to_bridge_hub_westend
+);
+declare_chain_cli_schema!(WestendHeadersToBridgeHubRococo, westend_headers_to_bridge_hub_rococo);
+declare_chain_cli_schema!(
+	WestendParachainsToBridgeHubRococo,
+	westend_parachains_to_bridge_hub_rococo
+);
 // All supported bridges.
 declare_relay_to_relay_bridge_schema!(Millau, Rialto);
 declare_relay_to_parachain_bridge_schema!(Millau, RialtoParachain, Rialto);
 declare_parachain_to_parachain_bridge_schema!(BridgeHubRococo, Rococo, BridgeHubWococo, Wococo);
+declare_parachain_to_parachain_bridge_schema!(BridgeHubRococo, Rococo, BridgeHubWestend, Westend);
 
 /// Base portion of the bidirectional complex relay.
 ///
@@ -277,6 +368,17 @@ where
 	/// Mutable reference to the base relay portion.
 	fn mut_base(&mut self) -> &mut Self::Base;
 
+	/// Start BEEFY finality relays, if the `FinalityEngine::Beefy` engine is selected.
+	async fn start_beefy_relays(
+		&mut self,
+	) -> anyhow::Result<Vec<BoxFuture<'static, anyhow::Result<()>>>> {
+		Err(anyhow::format_err!(
+			"{} <> {} relay doesn't support BEEFY finality engine",
+			Self::Left::NAME,
+			Self::Right::NAME,
+		))
+	}
+
 	/// Creates and returns Left to Right complex relay.
 	fn left_to_right(&mut self) -> FullBridge<Self::Left, Self::Right, Self::L2R> {
 		let common = self.mut_base().mut_common();
@@ -312,10 +414,36 @@ where
 			});
 		}
 
+		// start low balance and runtime compatibility guards
+		{
+			let common = self.mut_base().mut_common();
+			start_balance_guard(&common.left, &common.metrics_params)?;
+			start_balance_guard(&common.right, &common.metrics_params)?;
+			relay_substrate_client::guard::start_runtime_compatibility_guard(
+				&common.left.client,
+				&common.metrics_params.registry,
+			)?;
+			relay_substrate_client::guard::start_runtime_compatibility_guard(
+				&common.right.client,
+				&common.metrics_params.registry,
+			)?;
+		}
+
+		// start serving admin commands
+		if let Some(ref admin_socket) = self.mut_base().mut_common().shared.admin_socket {
+			messages_relay::lane_control::start_admin_server(admin_socket).await?;
+		}
+
 		// start on-demand header relays
 		let (left_to_right_on_demand_headers, right_to_left_on_demand_headers) =
 			self.mut_base().start_on_demand_headers_relayers().await?;
 
+		// start BEEFY finality relays
+		let finality_relays = match self.base().common().shared.finality_engine {
+			FinalityEngine::Grandpa => Vec::new(),
+			FinalityEngine::Beefy => self.start_beefy_relays().await?,
+		};
+
 		// add balance-related metrics
 		let lanes = self
 			.base()
@@ -344,6 +472,11 @@ where
 			.await?;
 		}
 
+		let cooperative_backoff =
+			self.base().common().shared.cooperative_backoff.map(Duration::from_secs);
+		let confirmation_params = self.base().common().shared.confirmation_params.clone();
+		let alert_params = self.base().common().shared.alert_params.clone();
+
 		// Need 2x capacity since we consider both directions for each lane
 		let mut message_relays = Vec::with_capacity(lanes.len() * 2);
 		for lane in lanes {
@@ -353,6 +486,9 @@ where
 				left_to_right_on_demand_headers.clone(),
 				right_to_left_on_demand_headers.clone(),
 				lane,
+				cooperative_backoff,
+				confirmation_params.clone(),
+				alert_params.clone(),
 			))
 			.map_err(|e| anyhow::format_err!("{}", e))
 			.boxed();
@@ -364,11 +500,15 @@ where
 				right_to_left_on_demand_headers.clone(),
 				left_to_right_on_demand_headers.clone(),
 				lane,
+				cooperative_backoff,
+				confirmation_params.clone(),
+				alert_params.clone(),
 			))
 			.map_err(|e| anyhow::format_err!("{}", e))
 			.boxed();
 			message_relays.push(right_to_left_messages);
 		}
+		message_relays.extend(finality_relays);
 
 		relay_utils::relay_metrics(self.base().common().metrics_params.clone())
 			.expose()
@@ -403,6 +543,24 @@ impl Full2WayBridge for MillauRialtoFull2WayBridge {
 	fn mut_base(&mut self) -> &mut Self::Base {
 		&mut self.base
 	}
+
+	async fn start_beefy_relays(
+		&mut self,
+	) -> anyhow::Result<Vec<BoxFuture<'static, anyhow::Result<()>>>> {
+		// only Rialto has the BEEFY bridge pallet, so Rialto headers are always relayed using
+		// GRANDPA finality engine
+		let common = self.base.common();
+		let millau_to_rialto_beefy = substrate_relay_helper::beefy::run::<MillauBeefyToRialto>(
+			common.left.client.clone(),
+			common.right.client.clone(),
+			common.shared.only_mandatory_headers,
+			self.base.left_to_right_transaction_params.clone(),
+			common.metrics_params.clone().disable(),
+		)
+		.boxed();
+
+		Ok(vec![millau_to_rialto_beefy])
+	}
 }
 
 /// Millau <> RialtoParachain complex relay.
@@ -457,6 +615,32 @@ impl Full2WayBridge for BridgeHubRococoBridgeHubWococoFull2WayBridge {
 	}
 }
 
+/// BridgeHubRococo <> BridgeHubWestend complex relay.
+pub struct BridgeHubRococoBridgeHubWestendFull2WayBridge {
+	base: <Self as Full2WayBridge>::Base,
+}
+
+#[async_trait]
+impl Full2WayBridge for BridgeHubRococoBridgeHubWestendFull2WayBridge {
+	type Base = ParachainToParachainBridge<Self::L2R, Self::R2L>;
+	type Left = relay_bridge_hub_rococo_client::BridgeHubRococo;
+	type Right = relay_bridge_hub_westend_client::BridgeHubWestend;
+	type L2R = BridgeHubRococoToBridgeHubWestendCliBridge;
+	type R2L = BridgeHubWestendToBridgeHubRococoCliBridge;
+
+	fn new(base: Self::Base) -> anyhow::Result<Self> {
+		Ok(Self { base })
+	}
+
+	fn base(&self) -> &Self::Base {
+		&self.base
+	}
+
+	fn mut_base(&mut self) -> &mut Self::Base {
+		&mut self.base
+	}
+}
+
 /// Complex headers+messages relay.
 #[derive(Debug, PartialEq, StructOpt)]
 pub enum RelayHeadersAndMessages {
@@ -466,6 +650,8 @@ pub enum RelayHeadersAndMessages {
 	MillauRialtoParachain(MillauRialtoParachainHeadersAndMessages),
 	/// BridgeHubRococo <> BridgeHubWococo relay.
 	BridgeHubRococoBridgeHubWococo(BridgeHubRococoBridgeHubWococoHeadersAndMessages),
+	/// BridgeHubRococo <> BridgeHubWestend relay.
+	BridgeHubRococoBridgeHubWestend(BridgeHubRococoBridgeHubWestendHeadersAndMessages),
 }
 
 impl RelayHeadersAndMessages {
@@ -482,6 +668,10 @@ impl RelayHeadersAndMessages {
 				BridgeHubRococoBridgeHubWococoFull2WayBridge::new(params.into_bridge().await?)?
 					.run()
 					.await,
+			RelayHeadersAndMessages::BridgeHubRococoBridgeHubWestend(params) =>
+				BridgeHubRococoBridgeHubWestendFull2WayBridge::new(params.into_bridge().await?)?
+					.run()
+					.await,
 		}
 	}
 }
@@ -530,6 +720,18 @@ mod tests {
 						HexLaneId([0x73, 0x77, 0x61, 0x70])
 					],
 					only_mandatory_headers: false,
+					finality_engine: FinalityEngine::Grandpa,
+					cooperative_backoff: None,
+					confirmation_params: ConfirmationParams {
+						min_confirmations_in_batch: 1,
+						max_confirmation_delay: None,
+					},
+					alert_params: AlertParams {
+						max_undelivered_message_age: None,
+						max_unconfirmed_messages: None,
+					},
+					dry_run: false,
+					admin_socket: None,
 					prometheus_params: PrometheusParams {
 						no_prometheus: false,
 						prometheus_host: "0.0.0.0".into(),
@@ -545,19 +747,35 @@ mod tests {
 						millau_spec_version: None,
 						millau_transaction_version: None,
 					},
+					millau_tip: None,
+					millau_tip_multiplier_percent: None,
+					millau_default_transactions_mortality: None,
+					millau_unsigned_transactions: false,
+					millau_tls_ca_certificates: None,
+					millau_tls_client_certificate: None,
+					millau_tls_client_key: None,
+					millau_tls_server_name: None,
+					millau_proxy: None,
+					millau_max_requests_per_second: None,
 				},
 				left_sign: MillauSigningParams {
 					millau_signer: Some("//Charlie".into()),
 					millau_signer_password: None,
 					millau_signer_file: None,
 					millau_signer_password_file: None,
+					millau_signer_key: None,
 					millau_transactions_mortality: Some(64),
 				},
+				left_balance_guard: MillauBalanceGuardParams {
+					millau_balance_soft_threshold: None,
+					millau_balance_hard_threshold: None,
+				},
 				left_headers_to_right_sign_override: MillauHeadersToRialtoSigningParams {
 					millau_headers_to_rialto_signer: None,
 					millau_headers_to_rialto_signer_password: None,
 					millau_headers_to_rialto_signer_file: None,
 					millau_headers_to_rialto_signer_password_file: None,
+					millau_headers_to_rialto_signer_key: None,
 					millau_headers_to_rialto_transactions_mortality: None,
 				},
 				right: RialtoConnectionParams {
@@ -569,19 +787,35 @@ mod tests {
 						rialto_spec_version: None,
 						rialto_transaction_version: None,
 					},
+					rialto_tip: None,
+					rialto_tip_multiplier_percent: None,
+					rialto_default_transactions_mortality: None,
+					rialto_unsigned_transactions: false,
+					rialto_tls_ca_certificates: None,
+					rialto_tls_client_certificate: None,
+					rialto_tls_client_key: None,
+					rialto_tls_server_name: None,
+					rialto_proxy: None,
+					rialto_max_requests_per_second: None,
 				},
 				right_sign: RialtoSigningParams {
 					rialto_signer: Some("//Charlie".into()),
 					rialto_signer_password: None,
 					rialto_signer_file: None,
 					rialto_signer_password_file: None,
+					rialto_signer_key: None,
 					rialto_transactions_mortality: Some(64),
 				},
+				right_balance_guard: RialtoBalanceGuardParams {
+					rialto_balance_soft_threshold: None,
+					rialto_balance_hard_threshold: None,
+				},
 				right_headers_to_left_sign_override: RialtoHeadersToMillauSigningParams {
 					rialto_headers_to_millau_signer: None,
 					rialto_headers_to_millau_signer_password: None,
 					rialto_headers_to_millau_signer_file: None,
 					rialto_headers_to_millau_signer_password_file: None,
+					rialto_headers_to_millau_signer_key: None,
 					rialto_headers_to_millau_transactions_mortality: None,
 				},
 			}),
@@ -630,6 +864,18 @@ mod tests {
 					shared: HeadersAndMessagesSharedParams {
 						lane: vec![HexLaneId([0x00, 0x00, 0x00, 0x00])],
 						only_mandatory_headers: false,
+						finality_engine: FinalityEngine::Grandpa,
+						cooperative_backoff: None,
+						confirmation_params: ConfirmationParams {
+							min_confirmations_in_batch: 1,
+							max_confirmation_delay: None,
+						},
+						alert_params: AlertParams {
+							max_undelivered_message_age: None,
+							max_unconfirmed_messages: None,
+						},
+						dry_run: false,
+						admin_socket: None,
 						prometheus_params: PrometheusParams {
 							no_prometheus: false,
 							prometheus_host: "0.0.0.0".into(),
@@ -645,20 +891,36 @@ mod tests {
 							millau_spec_version: None,
 							millau_transaction_version: None,
 						},
+						millau_tip: None,
+						millau_tip_multiplier_percent: None,
+						millau_default_transactions_mortality: None,
+						millau_unsigned_transactions: false,
+						millau_tls_ca_certificates: None,
+						millau_tls_client_certificate: None,
+						millau_tls_client_key: None,
+						millau_tls_server_name: None,
+						millau_proxy: None,
+						millau_max_requests_per_second: None,
 					},
 					left_sign: MillauSigningParams {
 						millau_signer: Some("//Iden".into()),
 						millau_signer_password: None,
 						millau_signer_file: None,
 						millau_signer_password_file: None,
+						millau_signer_key: None,
 						millau_transactions_mortality: Some(64),
 					},
+					left_balance_guard: MillauBalanceGuardParams {
+						millau_balance_soft_threshold: None,
+						millau_balance_hard_threshold: None,
+					},
 					left_headers_to_right_sign_override:
 						MillauHeadersToRialtoParachainSigningParams {
 							millau_headers_to_rialto_parachain_signer: None,
 							millau_headers_to_rialto_parachain_signer_password: None,
 							millau_headers_to_rialto_parachain_signer_file: None,
 							millau_headers_to_rialto_parachain_signer_password_file: None,
+							millau_headers_to_rialto_parachain_signer_key: None,
 							millau_headers_to_rialto_parachain_transactions_mortality: None,
 						},
 					right: RialtoParachainConnectionParams {
@@ -670,19 +932,35 @@ mod tests {
 							rialto_parachain_spec_version: None,
 							rialto_parachain_transaction_version: None,
 						},
+						rialto_parachain_tip: None,
+						rialto_parachain_tip_multiplier_percent: None,
+						rialto_parachain_default_transactions_mortality: None,
+						rialto_parachain_unsigned_transactions: false,
+						rialto_parachain_tls_ca_certificates: None,
+						rialto_parachain_tls_client_certificate: None,
+						rialto_parachain_tls_client_key: None,
+						rialto_parachain_tls_server_name: None,
+						rialto_parachain_proxy: None,
+						rialto_parachain_max_requests_per_second: None,
 					},
 					right_sign: RialtoParachainSigningParams {
 						rialto_parachain_signer: Some("//George".into()),
 						rialto_parachain_signer_password: None,
 						rialto_parachain_signer_file: None,
 						rialto_parachain_signer_password_file: None,
+						rialto_parachain_signer_key: None,
 						rialto_parachain_transactions_mortality: Some(64),
 					},
+					right_balance_guard: RialtoParachainBalanceGuardParams {
+						rialto_parachain_balance_soft_threshold: None,
+						rialto_parachain_balance_hard_threshold: None,
+					},
 					right_relay_headers_to_left_sign_override: RialtoHeadersToMillauSigningParams {
 						rialto_headers_to_millau_signer: Some("//Ken".into()),
 						rialto_headers_to_millau_signer_password: None,
 						rialto_headers_to_millau_signer_file: None,
 						rialto_headers_to_millau_signer_password_file: None,
+						rialto_headers_to_millau_signer_key: None,
 						rialto_headers_to_millau_transactions_mortality: None,
 					},
 					right_parachains_to_left_sign_override: RialtoParachainsToMillauSigningParams {
@@ -690,6 +968,7 @@ mod tests {
 						rialto_parachains_to_millau_signer_password: None,
 						rialto_parachains_to_millau_signer_file: None,
 						rialto_parachains_to_millau_signer_password_file: None,
+						rialto_parachains_to_millau_signer_key: None,
 						rialto_parachains_to_millau_transactions_mortality: None,
 					},
 					right_relay: RialtoConnectionParams {
@@ -701,7 +980,226 @@ mod tests {
 							rialto_spec_version: None,
 							rialto_transaction_version: None,
 						},
+						rialto_tip: None,
+						rialto_tip_multiplier_percent: None,
+						rialto_default_transactions_mortality: None,
+						rialto_unsigned_transactions: false,
+						rialto_tls_ca_certificates: None,
+						rialto_tls_client_certificate: None,
+						rialto_tls_client_key: None,
+						rialto_tls_server_name: None,
+						rialto_proxy: None,
+						rialto_max_requests_per_second: None,
+					},
+				}
+			),
+		);
+	}
+
+	#[test]
+	fn should_parse_parachain_to_parachain_options() {
+		// when
+		let res = RelayHeadersAndMessages::from_iter(vec![
+			"relay-headers-and-messages",
+			"bridge-hub-rococo-bridge-hub-wococo",
+			"--bridge-hub-rococo-host",
+			"bridge-hub-rococo-node-alice",
+			"--bridge-hub-rococo-port",
+			"9944",
+			"--bridge-hub-rococo-signer",
+			"//Iden",
+			"--bridge-hub-rococo-transactions-mortality",
+			"64",
+			"--rococo-host",
+			"rococo-node-alice",
+			"--rococo-port",
+			"9944",
+			"--bridge-hub-wococo-host",
+			"bridge-hub-wococo-node-alice",
+			"--bridge-hub-wococo-port",
+			"9944",
+			"--bridge-hub-wococo-signer",
+			"//George",
+			"--bridge-hub-wococo-transactions-mortality",
+			"64",
+			"--wococo-host",
+			"wococo-node-alice",
+			"--wococo-port",
+			"9944",
+			"--wococo-headers-to-bridge-hub-rococo-signer",
+			"//Ken",
+			"--lane",
+			"00000000",
+			"--prometheus-host",
+			"0.0.0.0",
+		]);
+
+		// then
+		assert_eq!(
+			res,
+			RelayHeadersAndMessages::BridgeHubRococoBridgeHubWococo(
+				BridgeHubRococoBridgeHubWococoHeadersAndMessages {
+					shared: HeadersAndMessagesSharedParams {
+						lane: vec![HexLaneId([0x00, 0x00, 0x00, 0x00])],
+						only_mandatory_headers: false,
+						finality_engine: FinalityEngine::Grandpa,
+						cooperative_backoff: None,
+						confirmation_params: ConfirmationParams {
+							min_confirmations_in_batch: 1,
+							max_confirmation_delay: None,
+						},
+						alert_params: AlertParams {
+							max_undelivered_message_age: None,
+							max_unconfirmed_messages: None,
+						},
+						dry_run: false,
+						admin_socket: None,
+						prometheus_params: PrometheusParams {
+							no_prometheus: false,
+							prometheus_host: "0.0.0.0".into(),
+							prometheus_port: 9616,
+						},
+					},
+					left: BridgeHubRococoConnectionParams {
+						bridge_hub_rococo_host: "bridge-hub-rococo-node-alice".into(),
+						bridge_hub_rococo_port: 9944,
+						bridge_hub_rococo_secure: false,
+						bridge_hub_rococo_runtime_version: BridgeHubRococoRuntimeVersionParams {
+							bridge_hub_rococo_version_mode: RuntimeVersionType::Bundle,
+							bridge_hub_rococo_spec_version: None,
+							bridge_hub_rococo_transaction_version: None,
+						},
+						bridge_hub_rococo_tip: None,
+						bridge_hub_rococo_tip_multiplier_percent: None,
+						bridge_hub_rococo_default_transactions_mortality: None,
+						bridge_hub_rococo_unsigned_transactions: false,
+						bridge_hub_rococo_tls_ca_certificates: None,
+						bridge_hub_rococo_tls_client_certificate: None,
+						bridge_hub_rococo_tls_client_key: None,
+						bridge_hub_rococo_tls_server_name: None,
+						bridge_hub_rococo_proxy: None,
+						bridge_hub_rococo_max_requests_per_second: None,
 					},
+					left_relay: RococoConnectionParams {
+						rococo_host: "rococo-node-alice".into(),
+						rococo_port: 9944,
+						rococo_secure: false,
+						rococo_runtime_version: RococoRuntimeVersionParams {
+							rococo_version_mode: RuntimeVersionType::Bundle,
+							rococo_spec_version: None,
+							rococo_transaction_version: None,
+						},
+						rococo_tip: None,
+						rococo_tip_multiplier_percent: None,
+						rococo_default_transactions_mortality: None,
+						rococo_unsigned_transactions: false,
+						rococo_tls_ca_certificates: None,
+						rococo_tls_client_certificate: None,
+						rococo_tls_client_key: None,
+						rococo_tls_server_name: None,
+						rococo_proxy: None,
+						rococo_max_requests_per_second: None,
+					},
+					left_sign: BridgeHubRococoSigningParams {
+						bridge_hub_rococo_signer: Some("//Iden".into()),
+						bridge_hub_rococo_signer_password: None,
+						bridge_hub_rococo_signer_file: None,
+						bridge_hub_rococo_signer_password_file: None,
+						bridge_hub_rococo_signer_key: None,
+						bridge_hub_rococo_transactions_mortality: Some(64),
+					},
+					left_balance_guard: BridgeHubRococoBalanceGuardParams {
+						bridge_hub_rococo_balance_soft_threshold: None,
+						bridge_hub_rococo_balance_hard_threshold: None,
+					},
+					right: BridgeHubWococoConnectionParams {
+						bridge_hub_wococo_host: "bridge-hub-wococo-node-alice".into(),
+						bridge_hub_wococo_port: 9944,
+						bridge_hub_wococo_secure: false,
+						bridge_hub_wococo_runtime_version: BridgeHubWococoRuntimeVersionParams {
+							bridge_hub_wococo_version_mode: RuntimeVersionType::Bundle,
+							bridge_hub_wococo_spec_version: None,
+							bridge_hub_wococo_transaction_version: None,
+						},
+						bridge_hub_wococo_tip: None,
+						bridge_hub_wococo_tip_multiplier_percent: None,
+						bridge_hub_wococo_default_transactions_mortality: None,
+						bridge_hub_wococo_unsigned_transactions: false,
+						bridge_hub_wococo_tls_ca_certificates: None,
+						bridge_hub_wococo_tls_client_certificate: None,
+						bridge_hub_wococo_tls_client_key: None,
+						bridge_hub_wococo_tls_server_name: None,
+						bridge_hub_wococo_proxy: None,
+						bridge_hub_wococo_max_requests_per_second: None,
+					},
+					right_relay: WococoConnectionParams {
+						wococo_host: "wococo-node-alice".into(),
+						wococo_port: 9944,
+						wococo_secure: false,
+						wococo_runtime_version: WococoRuntimeVersionParams {
+							wococo_version_mode: RuntimeVersionType::Bundle,
+							wococo_spec_version: None,
+							wococo_transaction_version: None,
+						},
+						wococo_tip: None,
+						wococo_tip_multiplier_percent: None,
+						wococo_default_transactions_mortality: None,
+						wococo_unsigned_transactions: false,
+						wococo_tls_ca_certificates: None,
+						wococo_tls_client_certificate: None,
+						wococo_tls_client_key: None,
+						wococo_tls_server_name: None,
+						wococo_proxy: None,
+						wococo_max_requests_per_second: None,
+					},
+					right_sign: BridgeHubWococoSigningParams {
+						bridge_hub_wococo_signer: Some("//George".into()),
+						bridge_hub_wococo_signer_password: None,
+						bridge_hub_wococo_signer_file: None,
+						bridge_hub_wococo_signer_password_file: None,
+						bridge_hub_wococo_signer_key: None,
+						bridge_hub_wococo_transactions_mortality: Some(64),
+					},
+					right_balance_guard: BridgeHubWococoBalanceGuardParams {
+						bridge_hub_wococo_balance_soft_threshold: None,
+						bridge_hub_wococo_balance_hard_threshold: None,
+					},
+					right_relay_headers_to_left_sign_override:
+						WococoHeadersToBridgeHubRococoSigningParams {
+							wococo_headers_to_bridge_hub_rococo_signer: Some("//Ken".into()),
+							wococo_headers_to_bridge_hub_rococo_signer_password: None,
+							wococo_headers_to_bridge_hub_rococo_signer_file: None,
+							wococo_headers_to_bridge_hub_rococo_signer_password_file: None,
+							wococo_headers_to_bridge_hub_rococo_signer_key: None,
+							wococo_headers_to_bridge_hub_rococo_transactions_mortality: None,
+						},
+					left_relay_headers_to_right_sign_override:
+						RococoHeadersToBridgeHubWococoSigningParams {
+							rococo_headers_to_bridge_hub_wococo_signer: None,
+							rococo_headers_to_bridge_hub_wococo_signer_password: None,
+							rococo_headers_to_bridge_hub_wococo_signer_file: None,
+							rococo_headers_to_bridge_hub_wococo_signer_password_file: None,
+							rococo_headers_to_bridge_hub_wococo_signer_key: None,
+							rococo_headers_to_bridge_hub_wococo_transactions_mortality: None,
+						},
+					right_parachains_to_left_sign_override:
+						WococoParachainsToBridgeHubRococoSigningParams {
+							wococo_parachains_to_bridge_hub_rococo_signer: None,
+							wococo_parachains_to_bridge_hub_rococo_signer_password: None,
+							wococo_parachains_to_bridge_hub_rococo_signer_file: None,
+							wococo_parachains_to_bridge_hub_rococo_signer_password_file: None,
+							wococo_parachains_to_bridge_hub_rococo_signer_key: None,
+							wococo_parachains_to_bridge_hub_rococo_transactions_mortality: None,
+						},
+					left_parachains_to_right_sign_override:
+						RococoParachainsToBridgeHubWococoSigningParams {
+							rococo_parachains_to_bridge_hub_wococo_signer: None,
+							rococo_parachains_to_bridge_hub_wococo_signer_password: None,
+							rococo_parachains_to_bridge_hub_wococo_signer_file: None,
+							rococo_parachains_to_bridge_hub_wococo_signer_password_file: None,
+							rococo_parachains_to_bridge_hub_wococo_signer_key: None,
+							rococo_parachains_to_bridge_hub_wococo_transactions_mortality: None,
+						},
 				}
 			),
 		);
@@ -89,6 +89,8 @@ macro_rules! declare_parachain_to_parachain_bridge_schema {
 				// default signer, which is always used to sign messages relay transactions on the left chain
 				#[structopt(flatten)]
 				left_sign: [<$left_parachain SigningParams>],
+				#[structopt(flatten)]
+				left_balance_guard: [<$left_parachain BalanceGuardParams>],
 
 				#[structopt(flatten)]
 				right: [<$right_parachain ConnectionParams>],
@@ -98,6 +100,8 @@ macro_rules! declare_parachain_to_parachain_bridge_schema {
 				// default signer, which is always used to sign messages relay transactions on the right chain
 				#[structopt(flatten)]
 				right_sign: [<$right_parachain SigningParams>],
+				#[structopt(flatten)]
+				right_balance_guard: [<$right_parachain BalanceGuardParams>],
 
 				// override for right_relay->left-parachain headers signer
 				#[structopt(flatten)]
@@ -137,12 +141,14 @@ macro_rules! declare_parachain_to_parachain_bridge_schema {
 								sign: self.left_sign.to_keypair::<Left>()?,
 								transactions_mortality: self.left_sign.transactions_mortality()?,
 								accounts: vec![],
+								balance_thresholds: self.left_balance_guard.balance_thresholds::<Left>()?,
 							},
 							BridgeEndCommonParams {
 								client: self.right.into_client::<Right>().await?,
 								sign: self.right_sign.to_keypair::<Right>()?,
 								transactions_mortality: self.right_sign.transactions_mortality()?,
 								accounts: vec![],
+								balance_thresholds: self.right_balance_guard.balance_thresholds::<Right>()?,
 							},
 						)?,
 						left_relay: self.left_relay.into_client::<LeftRelay>().await?,
@@ -213,7 +219,7 @@ where
 		});
 		self.common.right.accounts.push(TaggedAccount::Headers {
 			id: self.left_headers_to_right_transaction_params.signer.public().into(),
-			bridged_chain: Left::NAME.to_string(),
+			bridged_chain: LeftRelay::NAME.to_string(),
 		});
 		self.common.right.accounts.push(TaggedAccount::Parachains {
 			id: self.left_parachains_to_right_transaction_params.signer.public().into(),
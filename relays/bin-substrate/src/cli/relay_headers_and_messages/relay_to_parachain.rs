@@ -83,6 +83,8 @@ macro_rules! declare_relay_to_parachain_bridge_schema {
 				// default signer, which is always used to sign messages relay transactions on the left chain
 				#[structopt(flatten)]
 				left_sign: [<$left_chain SigningParams>],
+				#[structopt(flatten)]
+				left_balance_guard: [<$left_chain BalanceGuardParams>],
 
 				#[structopt(flatten)]
 				right: [<$right_parachain ConnectionParams>],
@@ -92,6 +94,8 @@ macro_rules! declare_relay_to_parachain_bridge_schema {
 				// default signer, which is always used to sign messages relay transactions on the right chain
 				#[structopt(flatten)]
 				right_sign: [<$right_parachain SigningParams>],
+				#[structopt(flatten)]
+				right_balance_guard: [<$right_parachain BalanceGuardParams>],
 
 				// override for right_relay->left headers signer
 				#[structopt(flatten)]
@@ -125,12 +129,14 @@ macro_rules! declare_relay_to_parachain_bridge_schema {
 								sign: self.left_sign.to_keypair::<Left>()?,
 								transactions_mortality: self.left_sign.transactions_mortality()?,
 								accounts: vec![],
+								balance_thresholds: self.left_balance_guard.balance_thresholds::<Left>()?,
 							},
 							BridgeEndCommonParams {
 								client: self.right.into_client::<Right>().await?,
 								sign: self.right_sign.to_keypair::<Right>()?,
 								transactions_mortality: self.right_sign.transactions_mortality()?,
 								accounts: vec![],
+								balance_thresholds: self.right_balance_guard.balance_thresholds::<Right>()?,
 							},
 						)?,
 						right_relay: self.right_relay.into_client::<RightRelay>().await?,
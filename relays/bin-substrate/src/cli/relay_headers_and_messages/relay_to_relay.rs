@@ -64,6 +64,8 @@ macro_rules! declare_relay_to_relay_bridge_schema {
 				right_headers_to_left_sign_override: [<$right_chain HeadersTo $left_chain SigningParams>],
 				#[structopt(flatten)]
 				left_sign: [<$left_chain SigningParams>],
+				#[structopt(flatten)]
+				left_balance_guard: [<$left_chain BalanceGuardParams>],
 				// default signer, which is always used to sign messages relay transactions on the right chain
 				#[structopt(flatten)]
 				right: [<$right_chain ConnectionParams>],
@@ -72,6 +74,8 @@ macro_rules! declare_relay_to_relay_bridge_schema {
 				left_headers_to_right_sign_override: [<$left_chain HeadersTo $right_chain SigningParams>],
 				#[structopt(flatten)]
 				right_sign: [<$right_chain SigningParams>],
+				#[structopt(flatten)]
+				right_balance_guard: [<$right_chain BalanceGuardParams>],
 			}
 
 			impl [<$left_chain $right_chain HeadersAndMessages>] {
@@ -91,12 +95,14 @@ macro_rules! declare_relay_to_relay_bridge_schema {
 								sign: self.left_sign.to_keypair::<Left>()?,
 								transactions_mortality: self.left_sign.transactions_mortality()?,
 								accounts: vec![],
+								balance_thresholds: self.left_balance_guard.balance_thresholds::<Left>()?,
 							},
 							BridgeEndCommonParams {
 								client: self.right.into_client::<Right>().await?,
 								sign: self.right_sign.to_keypair::<Right>()?,
 								transactions_mortality: self.right_sign.transactions_mortality()?,
 								accounts: vec![],
+								balance_thresholds: self.right_balance_guard.balance_thresholds::<Right>()?,
 							},
 						)?,
 						right_to_left_transaction_params: self
@@ -15,22 +15,31 @@
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
 use async_trait::async_trait;
+use bp_messages::MessageNonce;
 use sp_core::Pair;
+use std::{path::PathBuf, time::Duration};
 use structopt::StructOpt;
 use strum::VariantNames;
 
 use crate::chains::{
+	bridge_hub_rococo_messages_to_bridge_hub_westend::BridgeHubRococoToBridgeHubWestendMessagesCliBridge,
 	bridge_hub_rococo_messages_to_bridge_hub_wococo::BridgeHubRococoToBridgeHubWococoMessagesCliBridge,
+	bridge_hub_westend_messages_to_bridge_hub_rococo::BridgeHubWestendToBridgeHubRococoMessagesCliBridge,
 	bridge_hub_wococo_messages_to_bridge_hub_rococo::BridgeHubWococoToBridgeHubRococoMessagesCliBridge,
 	millau_headers_to_rialto::MillauToRialtoCliBridge,
 	millau_headers_to_rialto_parachain::MillauToRialtoParachainCliBridge,
 	rialto_headers_to_millau::RialtoToMillauCliBridge,
 	rialto_parachains_to_millau::RialtoParachainToMillauCliBridge,
 };
-use relay_substrate_client::{AccountIdOf, AccountKeyPairOf, BalanceOf, ChainWithTransactions};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BalanceOf, ChainWithBalances, ChainWithTransactions,
+};
 use substrate_relay_helper::{messages_lane::MessagesRelayParams, TransactionParams};
 
-use crate::cli::{bridge::*, chain_schema::*, CliChain, HexLaneId, PrometheusParams};
+use crate::cli::{
+	bridge::*, chain_schema::*, AlertParams, CliChain, ConfirmationParams, HexLaneId,
+	PrometheusParams,
+};
 
 /// Start messages relayer process.
 #[derive(StructOpt)]
@@ -41,34 +50,98 @@ pub struct RelayMessages {
 	/// Hex-encoded lane id that should be served by the relay. Defaults to `00000000`.
 	#[structopt(long, default_value = "00000000")]
 	lane: HexLaneId,
+	/// If passed, the relay won't deliver messages for given number of seconds after it has seen
+	/// messages delivered by other relayers. It resumes delivery only if the lane stays stale for
+	/// that period.
+	#[structopt(long)]
+	cooperative_backoff: Option<u64>,
+	/// If passed, the relay won't deliver messages with lower nonces. Use with care: the target
+	/// chain accepts messages in order only, so the lane is stalled until these messages are
+	/// delivered by other relayers.
+	#[structopt(long)]
+	start_from_nonce: Option<MessageNonce>,
+	/// Comma-separated nonces of messages that the relay won't deliver (e.g. messages that always
+	/// fail delivery). Use with care: the lane is stalled until these messages are delivered by
+	/// other relayers.
+	#[structopt(long, use_delimiter = true)]
+	skip_nonces: Vec<MessageNonce>,
+	/// If passed, the relay stops delivering messages when the best source header, known to the
+	/// target chain, is at least that many blocks behind the best finalized source header (its
+	/// state has likely been pruned by the source node). Delivery is resumed once the fresh source
+	/// header is imported at the target chain. Ancient headers can't be proved (e.g. using MMR)
+	/// yet, so a finality relay must be running for the delivery to resume.
+	#[structopt(long)]
+	ancient_source_header_threshold: Option<u32>,
+	#[structopt(flatten)]
+	confirmation_params: ConfirmationParams,
+	#[structopt(flatten)]
+	alert_params: AlertParams,
+	/// If passed, the relay prepares and validates transactions, but never submits them.
+	#[structopt(long)]
+	dry_run: bool,
+	/// Path to the Unix socket, where the relay listens to admin commands (`pause-lane`,
+	/// `resume-lane`, `set-max-batch` and `status`).
+	#[structopt(long)]
+	admin_socket: Option<PathBuf>,
 	#[structopt(flatten)]
 	source: SourceConnectionParams,
 	#[structopt(flatten)]
 	source_sign: SourceSigningParams,
 	#[structopt(flatten)]
+	source_balance_guard: SourceBalanceGuardParams,
+	#[structopt(flatten)]
 	target: TargetConnectionParams,
 	#[structopt(flatten)]
 	target_sign: TargetSigningParams,
 	#[structopt(flatten)]
+	target_balance_guard: TargetBalanceGuardParams,
+	#[structopt(flatten)]
 	prometheus_params: PrometheusParams,
 }
 
 #[async_trait]
 trait MessagesRelayer: MessagesCliBridge
 where
-	Self::Source: ChainWithTransactions + CliChain,
+	Self::Source: ChainWithTransactions + ChainWithBalances + CliChain,
+	Self::Target: ChainWithBalances,
 	AccountIdOf<Self::Source>: From<<AccountKeyPairOf<Self::Source> as Pair>::Public>,
 	AccountIdOf<Self::Target>: From<<AccountKeyPairOf<Self::Target> as Pair>::Public>,
 	BalanceOf<Self::Source>: TryFrom<BalanceOf<Self::Target>>,
 {
 	async fn relay_messages(data: RelayMessages) -> anyhow::Result<()> {
-		let source_client = data.source.into_client::<Self::Source>().await?;
+		let source_client =
+			data.source.into_client::<Self::Source>().await?.with_dry_run(data.dry_run);
 		let source_sign = data.source_sign.to_keypair::<Self::Source>()?;
 		let source_transactions_mortality = data.source_sign.transactions_mortality()?;
-		let target_client = data.target.into_client::<Self::Target>().await?;
+		let target_client =
+			data.target.into_client::<Self::Target>().await?.with_dry_run(data.dry_run);
 		let target_sign = data.target_sign.to_keypair::<Self::Target>()?;
 		let target_transactions_mortality = data.target_sign.transactions_mortality()?;
 
+		let metrics_params = data.prometheus_params.into_metrics_params()?;
+		data.source_balance_guard.start_balance_guard(
+			&source_client,
+			&source_sign,
+			&metrics_params,
+		)?;
+		data.target_balance_guard.start_balance_guard(
+			&target_client,
+			&target_sign,
+			&metrics_params,
+		)?;
+		relay_substrate_client::guard::start_runtime_compatibility_guard(
+			&source_client,
+			&metrics_params.registry,
+		)?;
+		relay_substrate_client::guard::start_runtime_compatibility_guard(
+			&target_client,
+			&metrics_params.registry,
+		)?;
+
+		if let Some(ref admin_socket) = data.admin_socket {
+			messages_relay::lane_control::start_admin_server(admin_socket).await?;
+		}
+
 		substrate_relay_helper::messages_lane::run::<Self::MessagesLane>(MessagesRelayParams {
 			source_client,
 			source_transaction_params: TransactionParams {
@@ -83,7 +156,13 @@ where
 			source_to_target_headers_relay: None,
 			target_to_source_headers_relay: None,
 			lane_id: data.lane.into(),
-			metrics_params: data.prometheus_params.into_metrics_params()?,
+			cooperative_backoff: data.cooperative_backoff.map(Duration::from_secs),
+			start_from_nonce: data.start_from_nonce,
+			skip_nonces: data.skip_nonces.into_iter().collect(),
+			ancient_source_header_threshold: data.ancient_source_header_threshold,
+			confirmation_params: data.confirmation_params.into(),
+			alert_params: data.alert_params.into(),
+			metrics_params,
 		})
 		.await
 		.map_err(|e| anyhow::format_err!("{}", e))
@@ -96,6 +175,8 @@ impl MessagesRelayer for MillauToRialtoParachainCliBridge {}
 impl MessagesRelayer for RialtoParachainToMillauCliBridge {}
 impl MessagesRelayer for BridgeHubRococoToBridgeHubWococoMessagesCliBridge {}
 impl MessagesRelayer for BridgeHubWococoToBridgeHubRococoMessagesCliBridge {}
+impl MessagesRelayer for BridgeHubRococoToBridgeHubWestendMessagesCliBridge {}
+impl MessagesRelayer for BridgeHubWestendToBridgeHubRococoMessagesCliBridge {}
 
 impl RelayMessages {
 	/// Run the command.
@@ -111,7 +192,60 @@ impl RelayMessages {
 				BridgeHubRococoToBridgeHubWococoMessagesCliBridge::relay_messages(self),
 			FullBridge::BridgeHubWococoToBridgeHubRococo =>
 				BridgeHubWococoToBridgeHubRococoMessagesCliBridge::relay_messages(self),
+			FullBridge::BridgeHubRococoToBridgeHubWestend =>
+				BridgeHubRococoToBridgeHubWestendMessagesCliBridge::relay_messages(self),
+			FullBridge::BridgeHubWestendToBridgeHubRococo =>
+				BridgeHubWestendToBridgeHubRococoMessagesCliBridge::relay_messages(self),
 		}
 		.await
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn nonce_overrides_are_parsed() {
+		let relay_messages = RelayMessages::from_iter(vec![
+			"relay-messages",
+			"millau-to-rialto",
+			"--source-port",
+			"1234",
+			"--source-signer",
+			"//Alice",
+			"--target-port",
+			"5678",
+			"--target-signer",
+			"//Bob",
+			"--start-from-nonce",
+			"10",
+			"--skip-nonces",
+			"12,15",
+		]);
+
+		assert_eq!(relay_messages.start_from_nonce, Some(10));
+		assert_eq!(relay_messages.skip_nonces, vec![12, 15]);
+		assert_eq!(relay_messages.ancient_source_header_threshold, None);
+	}
+
+	#[test]
+	fn ancient_source_header_threshold_is_parsed() {
+		let relay_messages = RelayMessages::from_iter(vec![
+			"relay-messages",
+			"millau-to-rialto",
+			"--source-port",
+			"1234",
+			"--source-signer",
+			"//Alice",
+			"--target-port",
+			"5678",
+			"--target-signer",
+			"//Bob",
+			"--ancient-source-header-threshold",
+			"128",
+		]);
+
+		assert_eq!(relay_messages.ancient_source_header_threshold, Some(128));
+	}
+}
@@ -16,19 +16,25 @@
 
 use crate::chains::{
 	rialto_parachains_to_millau::RialtoParachainToMillauCliBridge,
+	rococo_parachains_to_bridge_hub_westend::BridgeHubRococoToBridgeHubWestendCliBridge,
 	rococo_parachains_to_bridge_hub_wococo::BridgeHubRococoToBridgeHubWococoCliBridge,
+	westend_parachains_to_bridge_hub_rococo::BridgeHubWestendToBridgeHubRococoCliBridge,
 	westend_parachains_to_millau::WestmintToMillauCliBridge,
 	wococo_parachains_to_bridge_hub_rococo::BridgeHubWococoToBridgeHubRococoCliBridge,
 };
 use async_std::sync::Mutex;
 use async_trait::async_trait;
-use parachains_relay::parachains_loop::{AvailableHeader, SourceClient, TargetClient};
-use relay_substrate_client::Parachain;
-use relay_utils::metrics::{GlobalMetrics, StandaloneMetric};
+use parachains_relay::parachains_loop::{
+	metrics_prefix, AvailableHeader, SourceClient, TargetClient,
+};
+use relay_substrate_client::{AccountIdOf, AccountKeyPairOf, ChainWithBalances, Parachain};
+use relay_utils::metrics::{GlobalMetrics, Metric, StandaloneMetric};
+use sp_core::Pair;
 use std::sync::Arc;
 use structopt::StructOpt;
 use strum::{EnumString, EnumVariantNames, VariantNames};
 use substrate_relay_helper::{
+	fee_metrics::FeeMetrics,
 	parachains::{source::ParachainsSource, target::ParachainsTarget, ParachainsPipelineAdapter},
 	TransactionParams,
 };
@@ -45,6 +51,9 @@ pub struct RelayParachains {
 	/// A bridge instance to relay parachains heads for.
 	#[structopt(possible_values = RelayParachainsBridge::VARIANTS, case_insensitive = true)]
 	bridge: RelayParachainsBridge,
+	/// If passed, the relay prepares and validates transactions, but never submits them.
+	#[structopt(long)]
+	dry_run: bool,
 	#[structopt(flatten)]
 	source: SourceConnectionParams,
 	#[structopt(flatten)]
@@ -52,6 +61,8 @@ pub struct RelayParachains {
 	#[structopt(flatten)]
 	target_sign: TargetSigningParams,
 	#[structopt(flatten)]
+	target_balance_guard: TargetBalanceGuardParams,
+	#[structopt(flatten)]
 	prometheus_params: PrometheusParams,
 }
 
@@ -64,6 +75,8 @@ pub enum RelayParachainsBridge {
 	WestendToMillau,
 	BridgeHubRococoToBridgeHubWococo,
 	BridgeHubWococoToBridgeHubRococo,
+	BridgeHubRococoToBridgeHubWestend,
+	BridgeHubWestendToBridgeHubRococo,
 }
 
 #[async_trait]
@@ -74,6 +87,8 @@ where
 	ParachainsTarget<Self::ParachainFinality>:
 		TargetClient<ParachainsPipelineAdapter<Self::ParachainFinality>>,
 	<Self as CliBridgeBase>::Source: Parachain,
+	<Self as CliBridgeBase>::Target: ChainWithBalances,
+	AccountIdOf<Self::Target>: From<<AccountKeyPairOf<Self::Target> as Pair>::Public>,
 {
 	async fn relay_headers(data: RelayParachains) -> anyhow::Result<()> {
 		let source_client = data.source.into_client::<Self::SourceRelay>().await?;
@@ -86,16 +101,31 @@ where
 			signer: data.target_sign.to_keypair::<Self::Target>()?,
 			mortality: data.target_sign.target_transactions_mortality,
 		};
-		let target_client = data.target.into_client::<Self::Target>().await?;
+		let metrics_params: relay_utils::metrics::MetricsParams =
+			data.prometheus_params.into_metrics_params()?;
+		GlobalMetrics::new()?.register_and_spawn(&metrics_params.registry)?;
+		let fee_metrics = FeeMetrics::new(Some(&metrics_prefix::<
+			ParachainsPipelineAdapter<Self::ParachainFinality>,
+		>()))?;
+		fee_metrics.register(&metrics_params.registry)?;
+
+		let target_client =
+			data.target.into_client::<Self::Target>().await?.with_dry_run(data.dry_run);
+		data.target_balance_guard.start_balance_guard(
+			&target_client,
+			&target_transaction_params.signer,
+			&metrics_params,
+		)?;
+		relay_substrate_client::guard::start_runtime_compatibility_guard(
+			&target_client,
+			&metrics_params.registry,
+		)?;
 		let target_client = ParachainsTarget::<Self::ParachainFinality>::new(
 			target_client.clone(),
 			target_transaction_params,
+			Some(fee_metrics),
 		);
 
-		let metrics_params: relay_utils::metrics::MetricsParams =
-			data.prometheus_params.into_metrics_params()?;
-		GlobalMetrics::new()?.register_and_spawn(&metrics_params.registry)?;
-
 		parachains_relay::parachains_loop::run(
 			source_client,
 			target_client,
@@ -115,6 +145,10 @@ impl ParachainsRelayer for BridgeHubRococoToBridgeHubWococoCliBridge {}
 
 impl ParachainsRelayer for BridgeHubWococoToBridgeHubRococoCliBridge {}
 
+impl ParachainsRelayer for BridgeHubRococoToBridgeHubWestendCliBridge {}
+
+impl ParachainsRelayer for BridgeHubWestendToBridgeHubRococoCliBridge {}
+
 impl RelayParachains {
 	/// Run the command.
 	pub async fn run(self) -> anyhow::Result<()> {
@@ -127,6 +161,10 @@ impl RelayParachains {
 				BridgeHubRococoToBridgeHubWococoCliBridge::relay_headers(self),
 			RelayParachainsBridge::BridgeHubWococoToBridgeHubRococo =>
 				BridgeHubWococoToBridgeHubRococoCliBridge::relay_headers(self),
+			RelayParachainsBridge::BridgeHubRococoToBridgeHubWestend =>
+				BridgeHubRococoToBridgeHubWestendCliBridge::relay_headers(self),
+			RelayParachainsBridge::BridgeHubWestendToBridgeHubRococo =>
+				BridgeHubWestendToBridgeHubRococoCliBridge::relay_headers(self),
 		}
 		.await
 	}
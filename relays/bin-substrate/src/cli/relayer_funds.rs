@@ -0,0 +1,408 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Relayer funding subcommands.
+
+use crate::cli::{chain_schema::*, Balance};
+
+use bp_runtime::EncodedOrDecodedCall;
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BalanceOf, ChainWithBalances, ChainWithTransactions, Client,
+	Error as SubstrateError, UnsignedTransaction,
+};
+use relay_utils::{TrackedTransactionStatus, TransactionTracker};
+use sp_core::Pair;
+use sp_runtime::AccountId32;
+use structopt::StructOpt;
+use strum::{EnumString, EnumVariantNames, VariantNames};
+
+/// Transfer tokens to the relayer account.
+#[derive(StructOpt)]
+pub struct FundRelayer {
+	/// A chain where the relayer account is funded.
+	#[structopt(possible_values = FundsChain::VARIANTS, case_insensitive = true)]
+	chain: FundsChain,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+	/// The account that is used to fund relayer.
+	#[structopt(flatten)]
+	target_sign: TargetSigningParams,
+	/// The relayer account.
+	#[structopt(long)]
+	relayer: AccountId32,
+	/// Amount of tokens to transfer.
+	#[structopt(long, required_unless = "top-up-to", conflicts_with = "top-up-to")]
+	amount: Option<Balance>,
+	/// Transfer as many tokens as required for the relayer balance to reach this value. Nothing
+	/// is transferred if the relayer balance is already larger.
+	#[structopt(long)]
+	top_up_to: Option<Balance>,
+}
+
+/// Transfer all tokens from the relayer account.
+#[derive(StructOpt)]
+pub struct SweepRelayer {
+	/// A chain where the relayer account is swept.
+	#[structopt(possible_values = FundsChain::VARIANTS, case_insensitive = true)]
+	chain: FundsChain,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+	/// The relayer account.
+	#[structopt(flatten)]
+	target_sign: TargetSigningParams,
+	/// The account that receives relayer tokens.
+	#[structopt(long)]
+	beneficiary: AccountId32,
+	/// Transfer all tokens, allowing the relayer account to be reaped. By default, the
+	/// existential deposit is kept at the relayer account.
+	#[structopt(long)]
+	allow_death: bool,
+}
+
+/// Chain, where relayer account is funded or swept.
+#[derive(Debug, EnumString, EnumVariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "kebab_case")]
+pub enum FundsChain {
+	Millau,
+	Rialto,
+}
+
+/// Chain that supports balance transfers.
+pub trait CliTransfer: ChainWithTransactions + ChainWithBalances {
+	/// Returns existential deposit of the chain.
+	fn existential_deposit() -> BalanceOf<Self>;
+
+	/// Encode call that transfers given amount to the beneficiary, keeping the sender alive.
+	fn encode_transfer_call(
+		beneficiary: AccountIdOf<Self>,
+		amount: BalanceOf<Self>,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>>;
+
+	/// Encode call that transfers all transferable tokens of the sender to the beneficiary.
+	fn encode_transfer_all_call(
+		beneficiary: AccountIdOf<Self>,
+		keep_alive: bool,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>>;
+}
+
+macro_rules! select_chain {
+	($chain: expr, $generic: tt) => {
+		match $chain {
+			FundsChain::Millau => {
+				type Target = relay_millau_client::Millau;
+
+				$generic
+			},
+			FundsChain::Rialto => {
+				type Target = relay_rialto_client::Rialto;
+
+				$generic
+			},
+		}
+	};
+}
+
+impl FundRelayer {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		select_chain!(self.chain, {
+			let client = self.target.into_client::<Target>().await?;
+			let signer = self.target_sign.to_keypair::<Target>()?;
+			fund_relayer::<Target>(client, signer, self.relayer.into(), self.amount, self.top_up_to)
+				.await
+		})
+	}
+}
+
+impl SweepRelayer {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		select_chain!(self.chain, {
+			let client = self.target.into_client::<Target>().await?;
+			let signer = self.target_sign.to_keypair::<Target>()?;
+			sweep_relayer::<Target>(client, signer, self.beneficiary.into(), self.allow_death).await
+		})
+	}
+}
+
+/// Transfer tokens from the signer account to the relayer account.
+async fn fund_relayer<C: CliTransfer>(
+	client: Client<C>,
+	signer: AccountKeyPairOf<C>,
+	relayer: AccountIdOf<C>,
+	amount: Option<Balance>,
+	top_up_to: Option<Balance>,
+) -> anyhow::Result<()>
+where
+	AccountIdOf<C>: From<<AccountKeyPairOf<C> as Pair>::Public>,
+	BalanceOf<C>: From<u64> + Into<u128>,
+{
+	let funder: AccountIdOf<C> = signer.public().into();
+	let existential_deposit = C::existential_deposit().into();
+	let relayer_balance = free_balance(&client, relayer.clone()).await?;
+	let amount = match funding_amount(relayer_balance, amount, top_up_to, existential_deposit)? {
+		Some(amount) => amount,
+		None => {
+			log::info!(
+				target: "bridge",
+				"{} balance of {:?} is {} - nothing to transfer",
+				C::NAME,
+				relayer,
+				Balance(relayer_balance),
+			);
+			return Ok(())
+		},
+	};
+
+	// the funder account is kept alive by the `transfer_keep_alive` call
+	let funder_balance = free_balance(&client, funder.clone()).await?;
+	if funder_balance.saturating_sub(amount) < existential_deposit {
+		anyhow::bail!(
+			"{} balance of {:?} is {}. It is not enough to transfer {} and keep existential \
+			deposit {}",
+			C::NAME,
+			funder,
+			Balance(funder_balance),
+			Balance(amount),
+			Balance(existential_deposit),
+		)
+	}
+
+	log::info!(
+		target: "bridge",
+		"Transferring {} {} from {:?} to {:?}",
+		Balance(amount),
+		C::NAME,
+		funder,
+		relayer,
+	);
+	let call = C::encode_transfer_call(relayer.clone(), Balance(amount).cast().into())?;
+	submit_and_wait(&client, &signer, call).await?;
+
+	log::info!(
+		target: "bridge",
+		"{} balance of {:?} is {}",
+		C::NAME,
+		relayer,
+		Balance(free_balance(&client, relayer).await?),
+	);
+	Ok(())
+}
+
+/// Transfer all tokens from the relayer (signer) account to the beneficiary account.
+async fn sweep_relayer<C: CliTransfer>(
+	client: Client<C>,
+	signer: AccountKeyPairOf<C>,
+	beneficiary: AccountIdOf<C>,
+	allow_death: bool,
+) -> anyhow::Result<()>
+where
+	AccountIdOf<C>: From<<AccountKeyPairOf<C> as Pair>::Public>,
+	BalanceOf<C>: Into<u128>,
+{
+	let relayer: AccountIdOf<C> = signer.public().into();
+	let existential_deposit = C::existential_deposit().into();
+	let relayer_balance = free_balance(&client, relayer.clone()).await?;
+	if !allow_death && relayer_balance <= existential_deposit {
+		log::info!(
+			target: "bridge",
+			"{} balance of {:?} is {} and it doesn't exceed existential deposit {} - nothing to \
+			transfer",
+			C::NAME,
+			relayer,
+			Balance(relayer_balance),
+			Balance(existential_deposit),
+		);
+		return Ok(())
+	}
+
+	let beneficiary_balance = free_balance(&client, beneficiary.clone()).await?;
+	if beneficiary_balance == 0 && relayer_balance < existential_deposit.saturating_mul(2) {
+		log::warn!(
+			target: "bridge",
+			"{} beneficiary account {:?} doesn't exist and the transfer may fail if transferred \
+			amount is below existential deposit {}",
+			C::NAME,
+			beneficiary,
+			Balance(existential_deposit),
+		);
+	}
+
+	log::info!(
+		target: "bridge",
+		"Transferring all {} tokens from {:?} to {:?}{}",
+		C::NAME,
+		relayer,
+		beneficiary,
+		if allow_death { "" } else { ", keeping existential deposit" },
+	);
+	let call = C::encode_transfer_all_call(beneficiary.clone(), !allow_death)?;
+	submit_and_wait(&client, &signer, call).await?;
+
+	log::info!(
+		target: "bridge",
+		"{} balance of {:?} is {}",
+		C::NAME,
+		beneficiary,
+		Balance(free_balance(&client, beneficiary).await?),
+	);
+	Ok(())
+}
+
+/// Submit transaction and wait until it is finalized.
+async fn submit_and_wait<C: CliTransfer>(
+	client: &Client<C>,
+	signer: &AccountKeyPairOf<C>,
+	call: EncodedOrDecodedCall<C::Call>,
+) -> anyhow::Result<()>
+where
+	AccountIdOf<C>: From<<AccountKeyPairOf<C> as Pair>::Public>,
+{
+	let tracker = client
+		.submit_and_watch_signed_extrinsic(signer, move |_, transaction_nonce| {
+			Ok(UnsignedTransaction::new(call, transaction_nonce))
+		})
+		.await?;
+	match tracker.wait().await {
+		TrackedTransactionStatus::Finalized(at_block) => {
+			log::info!(
+				target: "bridge",
+				"{} transfer is finalized in block {:?}",
+				C::NAME,
+				at_block,
+			);
+			Ok(())
+		},
+		TrackedTransactionStatus::Lost =>
+			Err(anyhow::format_err!("{} transfer transaction has been lost", C::NAME)),
+		TrackedTransactionStatus::DryRun => Ok(()),
+	}
+}
+
+/// Returns free balance of the account or zero if account doesn't exist.
+async fn free_balance<C: ChainWithBalances>(
+	client: &Client<C>,
+	account: AccountIdOf<C>,
+) -> anyhow::Result<u128>
+where
+	BalanceOf<C>: Into<u128>,
+{
+	match client.free_native_balance(account).await {
+		Ok(balance) => Ok(balance.into()),
+		Err(SubstrateError::AccountDoesNotExist) => Ok(0),
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// Compute amount that needs to be transferred to the relayer account.
+///
+/// Returns `None` if nothing needs to be transferred.
+fn funding_amount(
+	relayer_balance: u128,
+	amount: Option<Balance>,
+	top_up_to: Option<Balance>,
+	existential_deposit: u128,
+) -> anyhow::Result<Option<u128>> {
+	let amount = match (amount, top_up_to) {
+		(Some(amount), None) => amount.0,
+		(None, Some(top_up_to)) => top_up_to.0.saturating_sub(relayer_balance),
+		_ => anyhow::bail!("Exactly one of options must be specified: '--amount' or '--top-up-to'"),
+	};
+	if amount == 0 {
+		return Ok(None)
+	}
+
+	// transfers that are creating accounts with balance below existential deposit are failing
+	if relayer_balance == 0 && amount < existential_deposit {
+		anyhow::bail!(
+			"Relayer account doesn't exist and transferred amount {} is below existential \
+			deposit {}",
+			Balance(amount),
+			Balance(existential_deposit),
+		)
+	}
+
+	Ok(Some(amount))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn fund_relayer_params_are_parsed() {
+		let fund_relayer = FundRelayer::from_iter(vec![
+			"fund-relayer",
+			"rialto",
+			"--target-port",
+			"1234",
+			"--target-signer",
+			"//Alice",
+			"--relayer",
+			"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+			"--top-up-to",
+			"1000000",
+		]);
+
+		assert_eq!(fund_relayer.chain, FundsChain::Rialto);
+		assert_eq!(fund_relayer.target.target_port, 1234);
+		assert_eq!(fund_relayer.amount, None);
+		assert_eq!(fund_relayer.top_up_to, Some(Balance(1_000_000)));
+
+		assert!(FundRelayer::from_iter_safe(vec![
+			"fund-relayer",
+			"rialto",
+			"--relayer",
+			"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+			"--amount",
+			"1000",
+			"--top-up-to",
+			"1000000",
+		])
+		.is_err());
+	}
+
+	#[test]
+	fn sweep_relayer_params_are_parsed() {
+		let sweep_relayer = SweepRelayer::from_iter(vec![
+			"sweep-relayer",
+			"millau",
+			"--target-signer",
+			"//Alice",
+			"--beneficiary",
+			"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+			"--allow-death",
+		]);
+
+		assert_eq!(sweep_relayer.chain, FundsChain::Millau);
+		assert!(sweep_relayer.allow_death);
+	}
+
+	#[test]
+	fn funding_amount_is_computed() {
+		// explicit amount is transferred as is
+		assert_eq!(funding_amount(100, Some(Balance(1000)), None, 500).unwrap(), Some(1000));
+		// top up transfers the missing amount
+		assert_eq!(funding_amount(100, None, Some(Balance(1000)), 500).unwrap(), Some(900));
+		// nothing is transferred if relayer already has enough tokens
+		assert_eq!(funding_amount(2000, None, Some(Balance(1000)), 500).unwrap(), None);
+		// new account can't be created with balance below existential deposit
+		assert!(funding_amount(0, Some(Balance(100)), None, 500).is_err());
+		assert!(funding_amount(0, None, Some(Balance(100)), 500).is_err());
+		// but existing accounts may receive any amount
+		assert_eq!(funding_amount(500, Some(Balance(100)), None, 500).unwrap(), Some(100));
+	}
+}
@@ -25,19 +25,25 @@ use crate::{
 		bridge::{FullBridge, MessagesCliBridge},
 		chain_schema::*,
 		encode_message::{self, CliEncodeMessage, RawMessage},
-		CliChain,
+		CliChain, HexLaneId,
 	},
 };
 use async_trait::async_trait;
+use bp_messages::{
+	storage_keys::outbound_lane_data_key, LaneId, MessageKey, MessageNonce, OutboundLaneData,
+};
 use codec::{Decode, Encode};
 use relay_substrate_client::{
-	AccountIdOf, AccountKeyPairOf, Chain, ChainBase, ChainWithTransactions, UnsignedTransaction,
+	AccountIdOf, AccountKeyPairOf, Chain, ChainBase, ChainWithMessages, ChainWithTransactions,
+	Client, HashOf, UnsignedTransaction,
 };
+use relay_utils::{TrackedTransactionStatus, TransactionTracker};
 use sp_core::Pair;
-use sp_runtime::AccountId32;
+use sp_runtime::{traits::Header as HeaderT, AccountId32};
 use std::fmt::Display;
 use structopt::StructOpt;
 use strum::VariantNames;
+use substrate_relay_helper::messages_lane::SubstrateMessageLane;
 
 /// Send bridge message.
 #[derive(StructOpt)]
@@ -45,6 +51,14 @@ pub struct SendMessage {
 	/// A bridge instance to encode call for.
 	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
 	bridge: FullBridge,
+	/// Hex-encoded id of the lane, used by the source chain to send the message. It is only
+	/// used to find the nonce of the sent message. Defaults to `00000000`.
+	#[structopt(long, default_value = "00000000")]
+	lane: HexLaneId,
+	/// If passed, the command doesn't wait until the message transaction is finalized and
+	/// doesn't print the message nonce.
+	#[structopt(long)]
+	no_wait: bool,
 	#[structopt(flatten)]
 	source: SourceConnectionParams,
 	#[structopt(flatten)]
@@ -76,23 +90,90 @@ where
 			data.bridge.bridge_instance_index(),
 		)?;
 
-		source_client
-			.submit_signed_extrinsic(&source_sign, move |_, transaction_nonce| {
-				let unsigned = UnsignedTransaction::new(send_message_call, transaction_nonce);
-				log::info!(
-					target: "bridge",
-					"Sending message to {}. Size: {}",
-					Self::Target::NAME,
-					payload_len,
-				);
-				Ok(unsigned)
-			})
+		let lane: LaneId = data.lane.into();
+		let prepare_extrinsic = move |_, transaction_nonce| {
+			let unsigned = UnsignedTransaction::new(send_message_call, transaction_nonce);
+			log::info!(
+				target: "bridge",
+				"Sending message to {}. Size: {}",
+				Self::Target::NAME,
+				payload_len,
+			);
+			Ok(unsigned)
+		};
+		if data.no_wait {
+			source_client.submit_signed_extrinsic(&source_sign, prepare_extrinsic).await?;
+			return Ok(())
+		}
+
+		let tracker = source_client
+			.submit_and_watch_signed_extrinsic(&source_sign, prepare_extrinsic)
 			.await?;
 
+		// messages pallet doesn't tell us the nonce of the message, sent by the XCM router, so
+		// we are looking at the lane state before and after the block with our transaction
+		let at_block = match tracker.wait().await {
+			TrackedTransactionStatus::Finalized(at_block) => at_block,
+			TrackedTransactionStatus::Lost =>
+				anyhow::bail!("Message transaction has been lost or invalidated"),
+			TrackedTransactionStatus::DryRun => return Ok(()),
+		};
+		let at_header = source_client.header_by_hash(at_block.hash()).await?;
+		let nonce_before = latest_generated_nonce::<Self::MessagesLane>(
+			&source_client,
+			lane,
+			*at_header.parent_hash(),
+		)
+		.await?;
+		let nonce_after =
+			latest_generated_nonce::<Self::MessagesLane>(&source_client, lane, at_block.hash())
+				.await?;
+
+		match nonce_after.saturating_sub(nonce_before) {
+			0 => anyhow::bail!(
+				"Message has been sent in block {:?}, but no messages have been queued at lane {}. \
+				Is it the lane, used by the {} XCM router?",
+				at_block,
+				hex::encode(lane),
+				Self::Source::NAME,
+			),
+			1 => log::info!(
+				target: "bridge",
+				"Message has been sent in block {:?}. Nonce: {}. Message id: {:?}",
+				at_block,
+				nonce_after,
+				MessageKey { lane_id: lane, nonce: nonce_after },
+			),
+			_ => log::info!(
+				target: "bridge",
+				"Message has been sent in block {:?}. Multiple messages have been queued at lane {} \
+				in this block, so the nonce of our message is in range {}..={}",
+				at_block,
+				hex::encode(lane),
+				nonce_before + 1,
+				nonce_after,
+			),
+		}
+
 		Ok(())
 	}
 }
 
+/// Read nonce of the latest message, generated at given lane of the source chain.
+async fn latest_generated_nonce<P: SubstrateMessageLane>(
+	client: &Client<P::SourceChain>,
+	lane: LaneId,
+	at_block: HashOf<P::SourceChain>,
+) -> anyhow::Result<MessageNonce> {
+	let lane_data: Option<OutboundLaneData> = client
+		.storage_value(
+			outbound_lane_data_key(P::TargetChain::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane),
+			Some(at_block),
+		)
+		.await?;
+	Ok(lane_data.map(|lane_data| lane_data.latest_generated_nonce).unwrap_or(0))
+}
+
 impl MessageSender for MillauToRialtoCliBridge {}
 impl MessageSender for RialtoToMillauCliBridge {}
 impl MessageSender for MillauToRialtoParachainCliBridge {}
@@ -114,6 +195,12 @@ impl SendMessage {
 			FullBridge::BridgeHubWococoToBridgeHubRococo => unimplemented!(
 				"Sending message from BridgeHubWococo to BridgeHubRococo is not supported"
 			),
+			FullBridge::BridgeHubRococoToBridgeHubWestend => unimplemented!(
+				"Sending message from BridgeHubRococo to BridgeHubWestend is not supported"
+			),
+			FullBridge::BridgeHubWestendToBridgeHubRococo => unimplemented!(
+				"Sending message from BridgeHubWestend to BridgeHubRococo is not supported"
+			),
 		}
 		.await
 	}
@@ -177,4 +264,59 @@ mod tests {
 			crate::cli::encode_message::Message::Sized { size: ExplicitOrMaximal::Maximal }
 		);
 	}
+
+	#[test]
+	fn send_remark_rialto_to_millau() {
+		// given
+		let send_message = SendMessage::from_iter(vec![
+			"send-message",
+			"rialto-to-millau",
+			"--lane",
+			"00000001",
+			"--no-wait",
+			"--source-port",
+			"1234",
+			"--source-signer",
+			"//Alice",
+			"remark",
+			"Hello",
+		]);
+
+		// then
+		assert_eq!(send_message.bridge, FullBridge::RialtoToMillau);
+		assert_eq!(send_message.lane, HexLaneId([0, 0, 0, 1]));
+		assert!(send_message.no_wait);
+		assert_eq!(
+			send_message.message,
+			crate::cli::encode_message::Message::Remark { remark: "Hello".into() }
+		);
+	}
+
+	#[test]
+	fn send_transact_rialto_to_millau() {
+		// given
+		let send_message = SendMessage::from_iter(vec![
+			"send-message",
+			"rialto-to-millau",
+			"--source-port",
+			"1234",
+			"--source-signer",
+			"//Alice",
+			"transact",
+			"00010c2a2a2a",
+			"--weight",
+			"1000",
+		]);
+
+		// then
+		assert_eq!(send_message.lane, HexLaneId([0, 0, 0, 0]));
+		assert!(!send_message.no_wait);
+		assert_eq!(
+			send_message.message,
+			crate::cli::encode_message::Message::Transact {
+				call: HexBytes(vec![0x00, 0x01, 0x0c, 0x2a, 0x2a, 0x2a]),
+				weight: 1000,
+			}
+		);
+	}
 }
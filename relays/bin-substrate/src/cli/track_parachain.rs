@@ -0,0 +1,214 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Onboarding of the new parachain to the bridge parachains pallet.
+//!
+//! The bridge parachains pallet at the target chain only accepts heads of tracked parachains.
+//! Apart from parachains that are supported by the runtime itself, the pallet owner may add
+//! parachains to the tracked parachains set at runtime.
+//!
+//! The command is named `track-parachain`, because the `register-parachain` name is already taken
+//! by the command that registers the parachain at the relay chain (see `register_parachain`).
+
+use crate::{
+	chains::{
+		rialto_parachains_to_millau::RialtoParachainToMillauCliBridge,
+		westend_parachains_to_millau::WestmintToMillauCliBridge,
+	},
+	cli::{bridge::ParachainToRelayHeadersCliBridge, chain_schema::*},
+};
+use async_trait::async_trait;
+use bp_parachains::{ParaInfo, ParasInfoKeyProvider};
+use bp_polkadot_core::parachains::ParaId;
+use bp_runtime::StorageMapKeyProvider;
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, CallOf, Chain, Client, Parachain, RelayChain,
+	UnsignedTransaction,
+};
+use relay_utils::{TrackedTransactionStatus, TransactionTracker};
+use sp_core::Pair;
+use structopt::StructOpt;
+use strum::{EnumString, EnumVariantNames, VariantNames};
+
+/// Add parachain to the tracked parachains set of the bridge parachains pallet.
+#[derive(StructOpt)]
+pub struct TrackParachain {
+	/// A bridge instance, which pallet is updated.
+	#[structopt(possible_values = TrackParachainBridge::VARIANTS, case_insensitive = true)]
+	bridge: TrackParachainBridge,
+	/// Identifier of the parachain at the bridged relay chain.
+	#[structopt(long)]
+	parachain_id: u32,
+	/// Number of target chain blocks to wait for the first parachain head after the parachain
+	/// is added to the tracked set. The parachains relay must be running to deliver the head.
+	#[structopt(long, default_value = "64")]
+	max_wait_blocks: u32,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+	#[structopt(flatten)]
+	target_sign: TargetSigningParams,
+}
+
+/// Bridge with the bridge parachains pallet at the target chain.
+#[derive(Debug, EnumString, EnumVariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "kebab_case")]
+pub enum TrackParachainBridge {
+	RialtoToMillau,
+	WestendToMillau,
+}
+
+#[async_trait]
+trait ParachainTracker: ParachainToRelayHeadersCliBridge
+where
+	Self::Source: Parachain,
+	AccountIdOf<Self::Target>: From<<AccountKeyPairOf<Self::Target> as Pair>::Public>,
+{
+	/// Encode `track_parachain` call of the bridge parachains pallet at the target chain.
+	fn encode_track_parachain_call(parachain: ParaId) -> CallOf<Self::Target>;
+
+	/// Add parachain to the tracked set and wait until its head is imported.
+	async fn track_parachain(data: TrackParachain) -> anyhow::Result<()> {
+		let target_client = data.target.into_client::<Self::Target>().await?;
+		let target_sign = data.target_sign.to_keypair::<Self::Target>()?;
+		let parachain = ParaId(data.parachain_id);
+		let parachains_pallet_name = Self::SourceRelay::PARACHAINS_FINALITY_PALLET_NAME;
+
+		let call = Self::encode_track_parachain_call(parachain);
+		let tracker = target_client
+			.submit_and_watch_signed_extrinsic(&target_sign, move |_, transaction_nonce| {
+				Ok(UnsignedTransaction::new(call.into(), transaction_nonce))
+			})
+			.await?;
+		match tracker.wait().await {
+			TrackedTransactionStatus::Finalized(at_block) => log::info!(
+				target: "bridge",
+				"Parachain {:?} has been added to the tracked set of {} at {} block {:?}",
+				parachain,
+				parachains_pallet_name,
+				Self::Target::NAME,
+				at_block,
+			),
+			TrackedTransactionStatus::Lost => anyhow::bail!(
+				"Transaction that adds parachain {:?} to the tracked set of {} at {} has been lost",
+				parachain,
+				parachains_pallet_name,
+				Self::Target::NAME,
+			),
+			TrackedTransactionStatus::DryRun => return Ok(()),
+		}
+
+		for _ in 0..data.max_wait_blocks {
+			if let Some(para_info) = best_parachain_info::<Self::Target>(
+				&target_client,
+				parachains_pallet_name,
+				parachain,
+			)
+			.await?
+			{
+				log::info!(
+					target: "bridge",
+					"Head {} of parachain {:?} has been imported at {}",
+					para_info.best_head_hash.head_hash,
+					parachain,
+					Self::Target::NAME,
+				);
+				return Ok(())
+			}
+
+			async_std::task::sleep(Self::Target::AVERAGE_BLOCK_INTERVAL).await;
+		}
+
+		anyhow::bail!(
+			"Head of parachain {:?} has not been imported at {} in {} blocks. Is the parachains \
+			relay running?",
+			parachain,
+			Self::Target::NAME,
+			data.max_wait_blocks,
+		)
+	}
+}
+
+/// Read best known head of the parachain from the bridge parachains pallet at given chain.
+async fn best_parachain_info<C: Chain>(
+	client: &Client<C>,
+	parachains_pallet_name: &str,
+	parachain: ParaId,
+) -> anyhow::Result<Option<ParaInfo>> {
+	let at_block = client.best_finalized_header_hash().await?;
+	let key = ParasInfoKeyProvider::final_key(parachains_pallet_name, &parachain);
+	Ok(client.storage_value(key, Some(at_block)).await?)
+}
+
+impl ParachainTracker for RialtoParachainToMillauCliBridge {
+	fn encode_track_parachain_call(parachain: ParaId) -> CallOf<Self::Target> {
+		millau_runtime::BridgeParachainsCall::<
+			millau_runtime::Runtime,
+			millau_runtime::WithRialtoParachainsInstance,
+		>::track_parachain {
+			parachain,
+		}
+		.into()
+	}
+}
+
+impl ParachainTracker for WestmintToMillauCliBridge {
+	fn encode_track_parachain_call(parachain: ParaId) -> CallOf<Self::Target> {
+		millau_runtime::BridgeParachainsCall::<
+			millau_runtime::Runtime,
+			millau_runtime::WithWestendParachainsInstance,
+		>::track_parachain {
+			parachain,
+		}
+		.into()
+	}
+}
+
+impl TrackParachain {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		match self.bridge {
+			TrackParachainBridge::RialtoToMillau =>
+				RialtoParachainToMillauCliBridge::track_parachain(self),
+			TrackParachainBridge::WestendToMillau =>
+				WestmintToMillauCliBridge::track_parachain(self),
+		}
+		.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn track_parachain_params_are_parsed() {
+		let track_parachain = TrackParachain::from_iter(vec![
+			"track-parachain",
+			"rialto-to-millau",
+			"--parachain-id",
+			"2000",
+			"--target-port",
+			"1234",
+			"--target-signer",
+			"//Alice",
+		]);
+
+		assert_eq!(track_parachain.bridge, TrackParachainBridge::RialtoToMillau);
+		assert_eq!(track_parachain.parachain_id, 2000);
+		assert_eq!(track_parachain.max_wait_blocks, 64);
+		assert_eq!(track_parachain.target.target_port, 1234);
+	}
+}
@@ -35,6 +35,10 @@ pub type UncheckedExtrinsic = bp_bridge_hub_rococo::UncheckedExtrinsic<
 pub type BridgeWococoGrandpaCall = BridgeGrandpaCallOf<bp_wococo::Wococo>;
 // The indirect pallet call used to sync `BridgeHubWococo` messages to `BHRococo`.
 pub type BridgeWococoMessagesCall = BridgeMessagesCallOf<bp_bridge_hub_wococo::BridgeHubWococo>;
+// The indirect pallet call used to sync `Westend` GRANDPA finality to `BHRococo`.
+pub type BridgeWestendGrandpaCall = BridgeGrandpaCallOf<bp_westend::Westend>;
+// The indirect pallet call used to sync `BridgeHubWestend` messages to `BHRococo`.
+pub type BridgeWestendMessagesCall = BridgeMessagesCallOf<bp_bridge_hub_westend::BridgeHubWestend>;
 
 /// `BridgeHubRococo` Runtime `Call` enum.
 ///
@@ -63,6 +67,16 @@ pub enum Call {
 	/// Wococo messages bridge pallet.
 	#[codec(index = 46)]
 	BridgeWococoMessages(BridgeWococoMessagesCall),
+
+	/// Westend bridge pallet.
+	#[codec(index = 48)]
+	BridgeWestendGrandpa(BridgeWestendGrandpaCall),
+	/// Westend parachain bridge pallet.
+	#[codec(index = 49)]
+	BridgeWestendParachain(BridgeParachainCall),
+	/// Westend messages bridge pallet.
+	#[codec(index = 51)]
+	BridgeWestendMessages(BridgeWestendMessagesCall),
 }
 
 impl From<UtilityCall<Call>> for Call {
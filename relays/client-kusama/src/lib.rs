@@ -18,7 +18,7 @@
 
 use bp_kusama::AccountInfoStorageMapKeyProvider;
 use bp_runtime::ChainId;
-use relay_substrate_client::{Chain, ChainWithBalances, UnderlyingChainProvider};
+use relay_substrate_client::{Chain, ChainWithBalances, RuntimeVersion, UnderlyingChainProvider};
 use sp_core::storage::StorageKey;
 use std::time::Duration;
 
@@ -40,6 +40,7 @@ impl Chain for Kusama {
 	const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
 		bp_kusama::BEST_FINALIZED_KUSAMA_HEADER_METHOD;
 	const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(6);
+	const RUNTIME_VERSION: Option<RuntimeVersion> = None;
 
 	type SignedBlock = bp_kusama::SignedBlock;
 	type Call = ();
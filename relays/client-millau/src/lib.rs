@@ -20,9 +20,9 @@ use bp_messages::MessageNonce;
 use bp_runtime::ChainId;
 use codec::{Compact, Decode, Encode};
 use relay_substrate_client::{
-	BalanceOf, Chain, ChainWithBalances, ChainWithMessages, ChainWithTransactions,
-	ChainWithUtilityPallet, Error as SubstrateError, FullRuntimeUtilityPallet, IndexOf, SignParam,
-	UnderlyingChainProvider, UnsignedTransaction,
+	BalanceOf, Chain, ChainWithBalances, ChainWithBeefy, ChainWithMessages, ChainWithTransactions,
+	ChainWithUtilityPallet, Error as SubstrateError, FullRuntimeUtilityPallet, IndexOf,
+	RuntimeVersion, SignParam, UnderlyingChainProvider, UnsignedTransaction,
 };
 use sp_core::{storage::StorageKey, Pair};
 use sp_runtime::{generic::SignedPayload, traits::IdentifyAccount};
@@ -39,6 +39,12 @@ impl UnderlyingChainProvider for Millau {
 	type Chain = bp_millau::Millau;
 }
 
+impl ChainWithBeefy for Millau {
+	const WITH_CHAIN_BEEFY_PALLET_NAME: &'static str = bp_millau::WITH_MILLAU_BEEFY_PALLET_NAME;
+
+	type BeefyChain = bp_millau::Millau;
+}
+
 impl ChainWithMessages for Millau {
 	const WITH_CHAIN_MESSAGES_PALLET_NAME: &'static str =
 		bp_millau::WITH_MILLAU_MESSAGES_PALLET_NAME;
@@ -48,6 +54,8 @@ impl ChainWithMessages for Millau {
 		bp_millau::TO_MILLAU_MESSAGE_DETAILS_METHOD;
 	const FROM_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
 		bp_millau::FROM_MILLAU_MESSAGE_DETAILS_METHOD;
+	const FROM_CHAIN_MESSAGES_DELIVERY_LIMITS_METHOD: &'static str =
+		bp_millau::FROM_MILLAU_MESSAGES_DELIVERY_LIMITS_METHOD;
 	const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce =
 		bp_millau::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce =
@@ -62,6 +70,7 @@ impl Chain for Millau {
 	const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
 		bp_millau::BEST_FINALIZED_MILLAU_HEADER_METHOD;
 	const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(5);
+	const RUNTIME_VERSION: Option<RuntimeVersion> = Some(millau_runtime::VERSION);
 
 	type SignedBlock = millau_runtime::SignedBlock;
 	type Call = millau_runtime::RuntimeCall;
@@ -5719,6 +5719,10 @@ pub mod api {
 				Basic(runtime_types::bp_runtime::BasicOperatingMode),
 				#[codec(index = 1)]
 				RejectingOutboundMessages,
+				#[codec(index = 2)]
+				DispatchPaused,
+				#[codec(index = 3)]
+				AcceptingConfirmationsOnly,
 			}
 			#[derive(
 				:: subxt :: ext :: codec :: Decode, :: subxt :: ext :: codec :: Encode, Clone, Debug,
@@ -5740,6 +5744,10 @@ pub mod api {
 				TooManyUnrewardedRelayers,
 				#[codec(index = 3)]
 				TooManyUnconfirmedMessages,
+				#[codec(index = 4)]
+				Deferred,
+				#[codec(index = 5)]
+				TooManyDeferredMessages,
 			}
 			#[derive(
 				:: subxt :: ext :: codec :: Decode, :: subxt :: ext :: codec :: Encode, Clone, Debug,
@@ -24,7 +24,7 @@ use bp_runtime::ChainId;
 use codec::Encode;
 use relay_substrate_client::{
 	Chain, ChainWithBalances, ChainWithMessages, ChainWithTransactions, Error as SubstrateError,
-	SignParam, UnderlyingChainProvider, UnsignedTransaction,
+	RuntimeVersion, SignParam, UnderlyingChainProvider, UnsignedTransaction,
 };
 use sp_core::{storage::StorageKey, Pair};
 use sp_runtime::{generic::SignedPayload, traits::IdentifyAccount, MultiAddress};
@@ -56,6 +56,7 @@ impl Chain for RialtoParachain {
 	const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
 		bp_rialto_parachain::BEST_FINALIZED_RIALTO_PARACHAIN_HEADER_METHOD;
 	const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(5);
+	const RUNTIME_VERSION: Option<RuntimeVersion> = None;
 
 	type SignedBlock = bp_polkadot_core::SignedBlock;
 	type Call = runtime_types::rialto_parachain_runtime::RuntimeCall;
@@ -77,6 +78,8 @@ impl ChainWithMessages for RialtoParachain {
 		bp_rialto_parachain::TO_RIALTO_PARACHAIN_MESSAGE_DETAILS_METHOD;
 	const FROM_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
 		bp_rialto_parachain::FROM_RIALTO_PARACHAIN_MESSAGE_DETAILS_METHOD;
+	const FROM_CHAIN_MESSAGES_DELIVERY_LIMITS_METHOD: &'static str =
+		bp_rialto_parachain::FROM_RIALTO_PARACHAIN_MESSAGES_DELIVERY_LIMITS_METHOD;
 	const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce =
 		bp_rialto_parachain::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce =
@@ -17,7 +17,9 @@
 //! Types used to connect to the Rococo-Substrate chain.
 
 use bp_runtime::ChainId;
-use relay_substrate_client::{Chain, ChainWithBalances, RelayChain, UnderlyingChainProvider};
+use relay_substrate_client::{
+	Chain, ChainWithBalances, RelayChain, RuntimeVersion, UnderlyingChainProvider,
+};
 use sp_core::storage::StorageKey;
 use std::time::Duration;
 
@@ -42,6 +44,7 @@ impl Chain for Rococo {
 	const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
 		bp_rococo::BEST_FINALIZED_ROCOCO_HEADER_METHOD;
 	const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(6);
+	const RUNTIME_VERSION: Option<RuntimeVersion> = None;
 
 	type SignedBlock = bp_rococo::SignedBlock;
 	type Call = ();
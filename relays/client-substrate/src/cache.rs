@@ -0,0 +1,113 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Cache of the data that is read from the Substrate node.
+//!
+//! All cached entries are keyed by the block hash. Header, storage and runtime version at
+//! given block never change, so the cache never needs to be invalidated. Old entries are
+//! simply evicted when the cache is full.
+
+use crate::{Chain, HashOf};
+
+use lru::LruCache;
+use sp_core::storage::{StorageData, StorageKey};
+use sp_trie::StorageProof;
+use sp_version::RuntimeVersion;
+use std::{hash::Hash, num::NonZeroUsize, sync::Mutex};
+
+/// Maximal number of cached headers.
+const HEADERS_CACHE_CAPACITY: usize = 1024;
+/// Maximal number of cached storage values.
+const STORAGE_VALUES_CACHE_CAPACITY: usize = 4096;
+/// Maximal number of cached storage proofs.
+const STORAGE_PROOFS_CACHE_CAPACITY: usize = 256;
+/// Maximal number of cached runtime versions.
+const RUNTIME_VERSIONS_CACHE_CAPACITY: usize = 256;
+
+/// Cache of the data, read from the Substrate node.
+///
+/// The cache is shared by all clones of the same `Client`.
+pub(crate) struct ClientCache<C: Chain> {
+	/// Headers, keyed by their hash.
+	pub headers: LruMap<HashOf<C>, C::Header>,
+	/// Storage values, keyed by block hash and storage key.
+	pub storage_values: LruMap<(HashOf<C>, StorageKey), Option<StorageData>>,
+	/// Storage proofs, keyed by block hash and proved storage keys.
+	pub storage_proofs: LruMap<(HashOf<C>, Vec<StorageKey>), StorageProof>,
+	/// Runtime versions, keyed by block hash.
+	pub runtime_versions: LruMap<HashOf<C>, RuntimeVersion>,
+}
+
+impl<C: Chain> ClientCache<C> {
+	/// Create new empty cache.
+	pub fn new() -> Self {
+		ClientCache {
+			headers: LruMap::new(HEADERS_CACHE_CAPACITY),
+			storage_values: LruMap::new(STORAGE_VALUES_CACHE_CAPACITY),
+			storage_proofs: LruMap::new(STORAGE_PROOFS_CACHE_CAPACITY),
+			runtime_versions: LruMap::new(RUNTIME_VERSIONS_CACHE_CAPACITY),
+		}
+	}
+}
+
+/// Thread-safe LRU map.
+pub(crate) struct LruMap<K: Hash + Eq, V: Clone>(Mutex<LruCache<K, V>>);
+
+impl<K: Hash + Eq, V: Clone> LruMap<K, V> {
+	/// Create new map with given capacity.
+	fn new(capacity: usize) -> Self {
+		LruMap(Mutex::new(LruCache::new(
+			NonZeroUsize::new(capacity).expect("all cache capacities are non-zero; qed"),
+		)))
+	}
+
+	/// Return clone of the cached value, if it is in the cache.
+	pub fn get(&self, key: &K) -> Option<V> {
+		self.0.lock().expect("cache lock is never poisoned; qed").get(key).cloned()
+	}
+
+	/// Insert value into the cache, possibly evicting the least recently used value.
+	pub fn insert(&self, key: K, value: V) {
+		self.0.lock().expect("cache lock is never poisoned; qed").put(key, value);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn cached_value_is_returned() {
+		let map = LruMap::new(2);
+		map.insert(1, "one");
+		assert_eq!(map.get(&1), Some("one"));
+		assert_eq!(map.get(&2), None);
+	}
+
+	#[test]
+	fn least_recently_used_value_is_evicted() {
+		let map = LruMap::new(2);
+		map.insert(1, "one");
+		map.insert(2, "two");
+		// touch the first value, so the second becomes the least recently used one
+		assert_eq!(map.get(&1), Some("one"));
+		map.insert(3, "three");
+
+		assert_eq!(map.get(&1), Some("one"));
+		assert_eq!(map.get(&2), None);
+		assert_eq!(map.get(&3), Some("three"));
+	}
+}
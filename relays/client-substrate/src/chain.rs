@@ -31,6 +31,7 @@ use sp_runtime::{
 	traits::{Block as BlockT, Member},
 	ConsensusEngineId, EncodedJustification,
 };
+use sp_version::RuntimeVersion;
 use std::{fmt::Debug, time::Duration};
 
 /// Substrate-based chain from minimal relay-client point of view.
@@ -56,6 +57,11 @@ pub trait Chain: ChainBase + Clone {
 	/// How often blocks are produced on that chain. It's suggested to set this value
 	/// to match the block time of the chain.
 	const AVERAGE_BLOCK_INTERVAL: Duration;
+	/// Version of the chain runtime, bundled with the relay.
+	///
+	/// If it is `Some(_)`, the relay refuses to submit transactions to the chain with a runtime
+	/// that is not compatible with this version. If it is `None`, the compatibility is not checked.
+	const RUNTIME_VERSION: Option<RuntimeVersion>;
 
 	/// Block type.
 	type SignedBlock: Member + Serialize + DeserializeOwned + BlockWithJustification<Self::Header>;
@@ -100,6 +106,23 @@ where
 		<T::Chain as bp_header_chain::ChainWithGrandpa>::WITH_CHAIN_GRANDPA_PALLET_NAME;
 }
 
+/// Substrate-based chain that is using BEEFY finality from minimal relay-client point of view.
+pub trait ChainWithBeefy: Chain {
+	/// Name of the bridge BEEFY pallet (used in `construct_runtime` macro call) that is deployed
+	/// at some other chain to bridge with this `ChainWithBeefy`.
+	///
+	/// We assume that all chains that are bridging with this `ChainWithBeefy` are using
+	/// the same name.
+	const WITH_CHAIN_BEEFY_PALLET_NAME: &'static str;
+
+	/// Underlying chain with BEEFY primitives.
+	type BeefyChain: bp_beefy::ChainWithBeefy<
+		BlockNumber = Self::BlockNumber,
+		Hash = Self::Hash,
+		Header = Self::Header,
+	>;
+}
+
 /// Substrate-based parachain from minimal relay-client point of view.
 pub trait Parachain: Chain + ParachainBase {}
 
@@ -132,8 +155,15 @@ pub trait ChainWithMessages: Chain {
 	/// The method is provided by the runtime that is bridged with this `ChainWithMessages`.
 	const FROM_CHAIN_MESSAGE_DETAILS_METHOD: &'static str;
 
+	/// Name of the `From<ChainWithMessages>InboundLaneApi::delivery_limits` runtime API method.
+	/// The method is provided by the runtime that is bridged with this `ChainWithMessages`.
+	const FROM_CHAIN_MESSAGES_DELIVERY_LIMITS_METHOD: &'static str;
+
 	/// Maximal number of unrewarded relayers in a single confirmation transaction at this
 	/// `ChainWithMessages`.
+	///
+	/// The relay prefers the value, returned by the `FROM_CHAIN_MESSAGES_DELIVERY_LIMITS_METHOD`
+	/// and only falls back to this constant if the bridged runtime doesn't support this method.
 	const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce;
 	/// Maximal number of unconfirmed messages in a single confirmation transaction at this
 	/// `ChainWithMessages`.
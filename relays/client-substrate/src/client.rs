@@ -17,13 +17,17 @@
 //! Substrate node client.
 
 use crate::{
-	chain::{Chain, ChainWithBalances, ChainWithTransactions},
+	cache::ClientCache,
+	chain::{Chain, ChainWithBalances, ChainWithBeefy, ChainWithGrandpa, ChainWithTransactions},
+	guard::runtime_incompatibility,
+	request_budget::{RequestBudget, RequestPriority},
 	rpc::{
-		SubstrateAuthorClient, SubstrateChainClient, SubstrateFinalityClient,
-		SubstrateFrameSystemClient, SubstrateStateClient, SubstrateSystemClient,
+		SubstrateAuthorClient, SubstrateBeefyClient, SubstrateChainClient, SubstrateFinalityClient,
+		SubstrateFrameSystemClient, SubstrateGrandpaClient, SubstrateMmrClient,
+		SubstrateStateClient, SubstrateSystemClient,
 	},
 	transaction_stall_timeout, AccountKeyPairOf, ConnectionParams, Error, HashOf, HeaderIdOf,
-	Result, SignParam, TransactionTracker, UnsignedTransaction,
+	Result, SignParam, TransactionEra, TransactionTracker, UnsignedTransaction,
 };
 
 use async_std::sync::{Arc, Mutex, RwLock};
@@ -34,29 +38,51 @@ use frame_support::weights::Weight;
 use frame_system::AccountInfo;
 use futures::{SinkExt, StreamExt};
 use jsonrpsee::{
-	core::DeserializeOwned,
+	core::{DeserializeOwned, Error as RpcError},
+	types::error::CallError,
 	ws_client::{WsClient as RpcClient, WsClientBuilder as RpcClientBuilder},
 };
-use num_traits::{Saturating, Zero};
+use mmr_rpc::LeavesProof;
+use num_traits::{One, Saturating, Zero};
 use pallet_balances::AccountData;
 use pallet_transaction_payment::RuntimeDispatchInfo;
-use relay_utils::{relay_loop::RECONNECT_DELAY, STALL_TIMEOUT};
+use relay_utils::{relay_loop::RECONNECT_DELAY, CircuitBreaker, RetryPolicy, STALL_TIMEOUT};
 use sp_core::{
 	storage::{StorageData, StorageKey},
 	Bytes, Hasher, Pair,
 };
 use sp_runtime::{
-	traits::Header as HeaderT,
+	traits::{Header as HeaderT, UniqueSaturatedFrom},
 	transaction_validity::{TransactionSource, TransactionValidity},
+	FixedPointNumber, FixedPointOperand, FixedU128,
 };
 use sp_trie::StorageProof;
 use sp_version::RuntimeVersion;
-use std::future::Future;
+use std::{
+	future::Future,
+	sync::atomic::{AtomicBool, Ordering},
+	time::{Duration, Instant},
+};
 
 const SUB_API_GRANDPA_AUTHORITIES: &str = "GrandpaApi_grandpa_authorities";
 const SUB_API_TXPOOL_VALIDATE_TRANSACTION: &str = "TaggedTransactionQueue_validate_transaction";
 const SUB_API_TX_PAYMENT_QUERY_INFO: &str = "TransactionPaymentApi_query_info";
+const TRANSACTION_PAYMENT_PALLET_NAME: &str = "TransactionPayment";
+const NEXT_FEE_MULTIPLIER_VALUE_NAME: &str = "NextFeeMultiplier";
 const MAX_SUBSCRIPTION_CAPACITY: usize = 4096;
+/// Maximal number of storage keys that we read in a single `state_getKeysPaged` call.
+const STORAGE_KEYS_PAGE_SIZE: u32 = 512;
+/// Version of the extrinsic format, used to encode unsigned transactions.
+const UNSIGNED_EXTRINSIC_FORMAT_VERSION: u8 = 4;
+/// Transaction pool error: the transaction has been recently included or rejected.
+const POOL_TEMPORARILY_BANNED: i32 = 1012;
+/// Transaction pool error: the same transaction is already in the pool.
+const POOL_ALREADY_IMPORTED: i32 = 1013;
+/// Transaction pool error: the pool has a transaction with the same tags and higher priority.
+const POOL_TOO_LOW_PRIORITY: i32 = 1014;
+/// Interval of WebSocket ping frames. If the connection is dead, the ping fails and all pending
+/// requests and subscriptions are terminated, so the relay is able to reconnect.
+const WS_PING_INTERVAL: Duration = Duration::from_secs(30);
 
 /// The difference between best block number and number of its ancestor, that is enough
 /// for us to consider that ancestor an "ancient" block with dropped state.
@@ -109,6 +135,23 @@ pub enum ChainRuntimeVersion {
 	Custom(SimpleRuntimeVersion),
 }
 
+/// Strategy of computing tip for transactions, submitted by the relay.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TipStrategy {
+	/// Transactions are submitted without tip.
+	NoTip,
+	/// All transactions are submitted with the same tip.
+	Static(u128),
+	/// Tip follows the chain congestion: `tip = base_tip * next_fee_multiplier * multiplier`,
+	/// where `next_fee_multiplier` is read from the `TransactionPayment` pallet storage.
+	Dynamic {
+		/// Tip that is used when the chain is not congested.
+		base_tip: u128,
+		/// Additional multiplier over the chain `NextFeeMultiplier`.
+		multiplier: FixedU128,
+	},
+}
+
 /// Substrate client type.
 ///
 /// Cloning `Client` is a cheap operation that only clones internal references. Different
@@ -126,6 +169,17 @@ pub struct Client<C: Chain> {
 	submit_signed_extrinsic_lock: Arc<Mutex<()>>,
 	/// Genesis block hash.
 	genesis_hash: HashOf<C>,
+	/// If `true`, submission of new signed transactions is halted. It is set by the low balance
+	/// guard (see `guard::halt_submissions_on_low_balance`).
+	submissions_halted: Arc<AtomicBool>,
+	/// If `Some(_)`, the chain runtime is not compatible with the relay and submission of new
+	/// signed transactions is refused. It is set by the runtime compatibility guard (see
+	/// `guard::halt_submissions_on_incompatible_runtime`).
+	runtime_incompatibility: Arc<std::sync::Mutex<Option<String>>>,
+	/// Cache of the data, read from the node at given blocks.
+	cache: Arc<ClientCache<C>>,
+	/// Budget of RPC requests to the node, if it is limited.
+	request_budget: Option<Arc<RequestBudget>>,
 	/// Shared dynamic data.
 	data: Arc<RwLock<ClientData>>,
 }
@@ -158,6 +212,10 @@ impl<C: Chain> Clone for Client<C> {
 			chain_runtime_version: self.chain_runtime_version.clone(),
 			submit_signed_extrinsic_lock: self.submit_signed_extrinsic_lock.clone(),
 			genesis_hash: self.genesis_hash,
+			submissions_halted: self.submissions_halted.clone(),
+			runtime_incompatibility: self.runtime_incompatibility.clone(),
+			cache: self.cache.clone(),
+			request_budget: self.request_budget.clone(),
 			data: self.data.clone(),
 		}
 	}
@@ -173,22 +231,32 @@ impl<C: Chain> Client<C> {
 	/// Returns client that is able to call RPCs on Substrate node over websocket connection.
 	///
 	/// This function will keep connecting to given Substrate node until connection is established
-	/// and is functional. If attempt fail, it will wait for `RECONNECT_DELAY` and retry again.
+	/// and is functional. If attempt fail, it will wait (starting from `RECONNECT_DELAY` and
+	/// backing off exponentially) and retry again. The attempt also fails if the chain runtime is
+	/// not compatible with the runtime, bundled with the relay.
 	pub async fn new(params: ConnectionParams) -> Self {
 		let params = Arc::new(params);
+		let mut circuit_breaker = CircuitBreaker::new(
+			format!("{} node", C::NAME),
+			RetryPolicy::reconnect(RECONNECT_DELAY),
+		);
 		loop {
-			match Self::try_connect(params.clone()).await {
+			let retry_delay = match Self::try_connect(params.clone()).await {
 				Ok(client) => return client,
-				Err(error) => log::error!(
-					target: "bridge",
-					"Failed to connect to {} node: {:?}. Going to retry in {}s",
-					C::NAME,
-					error,
-					RECONNECT_DELAY.as_secs(),
-				),
-			}
+				Err(error) => {
+					let retry_delay = circuit_breaker.on_failure(Instant::now());
+					log::error!(
+						target: "bridge",
+						"Failed to connect to {} node: {:?}. Going to retry in {}s",
+						C::NAME,
+						error,
+						retry_delay.as_secs(),
+					);
+					retry_delay
+				},
+			};
 
-			async_std::task::sleep(RECONNECT_DELAY).await;
+			async_std::task::sleep(retry_delay).await;
 		}
 	}
 
@@ -206,13 +274,21 @@ impl<C: Chain> Client<C> {
 			.await??;
 
 		let chain_runtime_version = params.chain_runtime_version.clone();
-		Ok(Self {
+		let client = Self {
 			params,
 			chain_runtime_version,
 			submit_signed_extrinsic_lock: Arc::new(Mutex::new(())),
 			genesis_hash,
+			submissions_halted: Arc::new(AtomicBool::new(false)),
+			runtime_incompatibility: Arc::new(std::sync::Mutex::new(None)),
+			cache: Arc::new(ClientCache::new()),
+			request_budget: params.max_requests_per_second.map(|max_requests_per_second| {
+				Arc::new(RequestBudget::new(max_requests_per_second))
+			}),
 			data: Arc::new(RwLock::new(ClientData { tokio, client })),
-		})
+		};
+		client.ensure_runtime_is_compatible().await?;
+		Ok(client)
 	}
 
 	/// Build client to use in connection.
@@ -226,12 +302,39 @@ impl<C: Chain> Client<C> {
 			params.host,
 			params.port,
 		);
-		log::info!(target: "bridge", "Connecting to {} node at {}", C::NAME, uri);
+		match params.proxy {
+			Some(ref proxy) => log::info!(
+				target: "bridge",
+				"Connecting to {} node at {} through proxy {}",
+				C::NAME,
+				uri,
+				proxy,
+			),
+			None => log::info!(target: "bridge", "Connecting to {} node at {}", C::NAME, uri),
+		}
+
+		// the default transport is only used when connection is not customized
+		if params.proxy.is_some() || (params.secure && !params.tls.is_default()) {
+			let params = params.clone();
+			let client = tokio
+				.spawn(async move {
+					let (sender, receiver) = crate::transport::connect(&params).await?;
+					Ok::<_, Error>(
+						jsonrpsee::core::client::ClientBuilder::default()
+							.max_notifs_per_subscription(MAX_SUBSCRIPTION_CAPACITY)
+							.ping_interval(WS_PING_INTERVAL)
+							.build_with_tokio(sender, receiver),
+					)
+				})
+				.await??;
+			return Ok((Arc::new(tokio), Arc::new(client)))
+		}
 
 		let client = tokio
 			.spawn(async move {
 				RpcClientBuilder::default()
 					.max_notifs_per_subscription(MAX_SUBSCRIPTION_CAPACITY)
+					.ping_interval(WS_PING_INTERVAL)
 					.build(&uri)
 					.await
 			})
@@ -253,6 +356,40 @@ impl<C: Chain> Client<C> {
 		})
 	}
 
+	/// Return version of the chain runtime that the relay is compatible with, or `None` if the
+	/// relay does not bundle the chain runtime or if the runtime version is `Auto`.
+	///
+	/// In the `Auto` mode, the relay reads runtime version from the chain and is expected to
+	/// keep working after runtime upgrades, so the bundled runtime version is not enforced. If the
+	/// runtime version is defined by user, its `spec_version` and `transaction_version` are
+	/// expected instead of bundled.
+	pub fn expected_runtime_version(&self) -> Option<RuntimeVersion> {
+		let version = match &self.chain_runtime_version {
+			ChainRuntimeVersion::Auto => return None,
+			ChainRuntimeVersion::Custom(version) => *version,
+		};
+		C::RUNTIME_VERSION.map(|mut expected| {
+			expected.spec_version = version.spec_version;
+			expected.transaction_version = version.transaction_version;
+			expected
+		})
+	}
+
+	/// Returns error if the chain runtime is not compatible with the runtime, bundled with the
+	/// relay.
+	pub async fn ensure_runtime_is_compatible(&self) -> Result<()> {
+		let expected = match self.expected_runtime_version() {
+			Some(expected) => expected,
+			None => return Ok(()),
+		};
+
+		let actual = self.runtime_version().await?;
+		match runtime_incompatibility(&expected, &actual) {
+			Some(reason) => Err(Error::IncompatibleRuntime { chain: C::NAME.into(), reason }),
+			None => Ok(()),
+		}
+	}
+
 	/// Returns true if client is connected to at least one peer and is in synced state.
 	pub async fn ensure_synced(&self) -> Result<()> {
 		self.jsonrpsee_execute(|client| async move {
@@ -315,19 +452,28 @@ impl<C: Chain> Client<C> {
 	}
 
 	/// Get a Substrate header by its hash.
+	///
+	/// Headers are cached, so the same header is only read from the node once.
 	pub async fn header_by_hash(&self, block_hash: C::Hash) -> Result<C::Header>
 	where
 		C::Header: DeserializeOwned,
 	{
-		self.jsonrpsee_execute(move |client| async move {
-			Ok(SubstrateChainClient::<C>::header(&*client, Some(block_hash)).await?)
-		})
-		.await
-		.map_err(|e| Error::FailedToReadHeaderByHash {
-			chain: C::NAME.into(),
-			hash: format!("{block_hash}"),
-			error: e.boxed(),
-		})
+		if let Some(header) = self.cache.headers.get(&block_hash) {
+			return Ok(header)
+		}
+
+		let header = self
+			.jsonrpsee_execute(move |client| async move {
+				Ok(SubstrateChainClient::<C>::header(&*client, Some(block_hash)).await?)
+			})
+			.await
+			.map_err(|e| Error::FailedToReadHeaderByHash {
+				chain: C::NAME.into(),
+				hash: format!("{block_hash}"),
+				error: e.boxed(),
+			})?;
+		self.cache.headers.insert(block_hash, header.clone());
+		Ok(header)
 	}
 
 	/// Get a Substrate block hash by its number.
@@ -351,11 +497,29 @@ impl<C: Chain> Client<C> {
 	/// Return runtime version.
 	pub async fn runtime_version(&self) -> Result<RuntimeVersion> {
 		self.jsonrpsee_execute(move |client| async move {
-			Ok(SubstrateStateClient::<C>::runtime_version(&*client).await?)
+			Ok(SubstrateStateClient::<C>::runtime_version(&*client, None).await?)
 		})
 		.await
 	}
 
+	/// Return runtime version at given block.
+	///
+	/// Runtime versions are cached, so the version at the same block is only read from the
+	/// node once.
+	pub async fn runtime_version_at(&self, block_hash: C::Hash) -> Result<RuntimeVersion> {
+		if let Some(runtime_version) = self.cache.runtime_versions.get(&block_hash) {
+			return Ok(runtime_version)
+		}
+
+		let runtime_version = self
+			.jsonrpsee_execute(move |client| async move {
+				Ok(SubstrateStateClient::<C>::runtime_version(&*client, Some(block_hash)).await?)
+			})
+			.await?;
+		self.cache.runtime_versions.insert(block_hash, runtime_version.clone());
+		Ok(runtime_version)
+	}
+
 	/// Read value from runtime storage.
 	pub async fn storage_value<T: Send + Decode + 'static>(
 		&self,
@@ -406,22 +570,67 @@ impl<C: Chain> Client<C> {
 	}
 
 	/// Read raw value from runtime storage.
+	///
+	/// Values, read at given block, are cached. Values, read at the best block, are never cached.
 	pub async fn raw_storage_value(
 		&self,
 		storage_key: StorageKey,
 		block_hash: Option<C::Hash>,
 	) -> Result<Option<StorageData>> {
+		let cache_key = block_hash.map(|block_hash| (block_hash, storage_key.clone()));
+		if let Some(storage_value) = cache_key
+			.as_ref()
+			.and_then(|cache_key| self.cache.storage_values.get(cache_key))
+		{
+			return Ok(storage_value)
+		}
+
 		let cloned_storage_key = storage_key.clone();
-		self.jsonrpsee_execute(move |client| async move {
-			Ok(SubstrateStateClient::<C>::storage(&*client, storage_key.clone(), block_hash)
-				.await?)
-		})
-		.await
-		.map_err(|e| Error::FailedToReadRuntimeStorageValue {
-			chain: C::NAME.into(),
-			key: cloned_storage_key,
-			error: e.boxed(),
-		})
+		let storage_value = self
+			.jsonrpsee_execute(move |client| async move {
+				Ok(SubstrateStateClient::<C>::storage(&*client, storage_key.clone(), block_hash)
+					.await?)
+			})
+			.await
+			.map_err(|e| Error::FailedToReadRuntimeStorageValue {
+				chain: C::NAME.into(),
+				key: cloned_storage_key,
+				error: e.boxed(),
+			})?;
+		if let Some(cache_key) = cache_key {
+			self.cache.storage_values.insert(cache_key, storage_value.clone());
+		}
+		Ok(storage_value)
+	}
+
+	/// Read keys of all runtime storage entries with given prefix.
+	pub async fn storage_keys(
+		&self,
+		prefix: StorageKey,
+		block_hash: C::Hash,
+	) -> Result<Vec<StorageKey>> {
+		let mut keys = Vec::new();
+		loop {
+			let start_key = keys.last().cloned();
+			let page_prefix = prefix.clone();
+			let page = self
+				.jsonrpsee_execute(move |client| async move {
+					Ok(SubstrateStateClient::<C>::storage_keys_paged(
+						&*client,
+						page_prefix,
+						STORAGE_KEYS_PAGE_SIZE,
+						start_key,
+						Some(block_hash),
+					)
+					.await?)
+				})
+				.await?;
+			let is_last_page = page.len() < STORAGE_KEYS_PAGE_SIZE as usize;
+			keys.extend(page);
+			if is_last_page {
+				return Ok(keys)
+			}
+		}
 	}
 
 	/// Return native tokens balance of the account.
@@ -458,7 +667,18 @@ impl<C: Chain> Client<C> {
 	///
 	/// Note: The given transaction needs to be SCALE encoded beforehand.
 	pub async fn submit_unsigned_extrinsic(&self, transaction: Bytes) -> Result<C::Hash> {
-		self.jsonrpsee_execute(move |client| async move {
+		if self.params.dry_run {
+			let tx_hash = C::Hasher::hash(&transaction.0);
+			log::info!(
+				target: "bridge",
+				"Dry-run mode: not submitting unsigned {} transaction {:?}",
+				C::NAME,
+				tx_hash,
+			);
+			return Ok(tx_hash)
+		}
+
+		self.jsonrpsee_execute_with_priority(RequestPriority::Proof, move |client| async move {
 			let tx_hash = SubstrateAuthorClient::<C>::submit_extrinsic(&*client, transaction)
 				.await
 				.map_err(|e| {
@@ -502,6 +722,13 @@ impl<C: Chain> Client<C> {
 		C: ChainWithTransactions,
 		C::AccountId: From<<C::AccountKeyPair as Pair>::Public>,
 	{
+		self.ensure_submissions_are_not_halted()?;
+		let tip = self.transaction_tip().await?;
+		let mortality = self.params.transactions_mortality;
+		let prepare_extrinsic = move |best_header_id, transaction_nonce| {
+			prepare_extrinsic(best_header_id, transaction_nonce)
+				.map(|tx| with_tip(with_default_mortality(tx, best_header_id, mortality), tip))
+		};
 		let _guard = self.submit_signed_extrinsic_lock.lock().await;
 		let transaction_nonce = self.next_account_index(signer.public().into()).await?;
 		let best_header = self.best_header().await?;
@@ -514,7 +741,13 @@ impl<C: Chain> Client<C> {
 		// will be dropped from the pool.
 		let best_header_id = best_header.parent_id().unwrap_or_else(|| best_header.id());
 
-		self.jsonrpsee_execute(move |client| async move {
+		if self.params.dry_run {
+			let extrinsic = prepare_extrinsic(best_header_id, transaction_nonce)?;
+			let signed_extrinsic = C::sign_transaction(signing_data, extrinsic)?;
+			return self.dry_run_extrinsic(best_header.hash(), signed_extrinsic).await
+		}
+
+		self.jsonrpsee_execute_with_priority(RequestPriority::Proof, move |client| async move {
 			let extrinsic = prepare_extrinsic(best_header_id, transaction_nonce)?;
 			let signed_extrinsic = C::sign_transaction(signing_data, extrinsic)?.encode();
 			let tx_hash =
@@ -543,26 +776,43 @@ impl<C: Chain> Client<C> {
 		C: ChainWithTransactions,
 		C::AccountId: From<<C::AccountKeyPair as Pair>::Public>,
 	{
+		self.ensure_submissions_are_not_halted()?;
+		let tip = self.transaction_tip().await?;
+		let mortality = self.params.transactions_mortality;
+		let prepare_extrinsic = move |best_header_id, transaction_nonce| {
+			prepare_extrinsic(best_header_id, transaction_nonce)
+				.map(|tx| with_tip(with_default_mortality(tx, best_header_id, mortality), tip))
+		};
 		let self_clone = self.clone();
 		let signing_data = self.build_sign_params(signer.clone()).await?;
 		let _guard = self.submit_signed_extrinsic_lock.lock().await;
 		let transaction_nonce = self.next_account_index(signer.public().into()).await?;
 		let best_header = self.best_header().await?;
 		let best_header_id = best_header.id();
+
+		if self.params.dry_run {
+			let extrinsic = prepare_extrinsic(best_header_id, transaction_nonce)?;
+			let signed_extrinsic = C::sign_transaction(signing_data, extrinsic)?;
+			let tx_hash = self.dry_run_extrinsic(best_header_id.1, signed_extrinsic).await?;
+			return Ok(TransactionTracker::new_dry_run(self_clone, tx_hash))
+		}
+
 		let (sender, receiver) = futures::channel::mpsc::channel(MAX_SUBSCRIPTION_CAPACITY);
-		let (tracker, subscription) = self
-			.jsonrpsee_execute(move |client| async move {
+		let (tracker, subscription, signed_extrinsic, tip) = self
+			.jsonrpsee_execute_with_priority(RequestPriority::Proof, move |client| async move {
 				let extrinsic = prepare_extrinsic(best_header_id, transaction_nonce)?;
 				let stall_timeout = transaction_stall_timeout(
 					extrinsic.era.mortality_period(),
 					C::AVERAGE_BLOCK_INTERVAL,
 					STALL_TIMEOUT,
 				);
-				let signed_extrinsic = C::sign_transaction(signing_data, extrinsic)?.encode();
-				let tx_hash = C::Hasher::hash(&signed_extrinsic);
+				let tip = extrinsic.tip;
+				let signed_extrinsic = C::sign_transaction(signing_data, extrinsic)?;
+				let encoded_extrinsic = signed_extrinsic.encode();
+				let tx_hash = C::Hasher::hash(&encoded_extrinsic);
 				let subscription = SubstrateAuthorClient::<C>::submit_and_watch_extrinsic(
 					&*client,
-					Bytes(signed_extrinsic),
+					Bytes(encoded_extrinsic),
 				)
 				.await
 				.map_err(|e| {
@@ -576,7 +826,89 @@ impl<C: Chain> Client<C> {
 					tx_hash,
 					Subscription(Mutex::new(receiver)),
 				);
-				Ok((tracker, subscription))
+				Ok((tracker, subscription, signed_extrinsic, tip))
+			})
+			.await?;
+		self.data.read().await.tokio.spawn(Subscription::background_worker(
+			C::NAME.into(),
+			"extrinsic".into(),
+			subscription,
+			sender,
+		));
+
+		// estimated fee is only used for metrics, so we don't want to fail if we can't estimate it
+		let estimated_fee = match self.estimate_extrinsic_fee(signed_extrinsic).await {
+			Ok(partial_fee) =>
+				Some(sp_runtime::traits::Saturating::saturating_add(partial_fee, tip)),
+			Err(e) => {
+				log::debug!(
+					target: "bridge",
+					"Failed to estimate fee of {} transaction {:?}: {:?}",
+					C::NAME,
+					tracker.transaction_hash(),
+					e,
+				);
+				None
+			},
+		};
+
+		Ok(tracker.with_estimated_fee(estimated_fee))
+	}
+
+	/// Submit unsigned extrinsic with given call and keep watching for its status.
+	///
+	/// Unsigned transactions have no nonce and the transaction pool deduplicates them. So if the
+	/// same (or competing) transaction, e.g. submitted by other relayer, is already known to the
+	/// pool, the `Error::UnsignedTransactionRejected` is returned. It isn't a connection error, so
+	/// relay loops are retrying the submission later instead of reconnecting.
+	pub async fn submit_and_watch_unsigned_extrinsic(
+		&self,
+		call: C::Call,
+	) -> Result<TransactionTracker<C, Self>> {
+		self.ensure_runtime_is_compatible_for_submissions()?;
+		let self_clone = self.clone();
+		let extrinsic_body = unsigned_extrinsic_body(&call);
+		let encoded_extrinsic = extrinsic_body.encode();
+		let tx_hash = C::Hasher::hash(&encoded_extrinsic);
+		// unsigned transactions are immortal
+		let stall_timeout =
+			transaction_stall_timeout(None, C::AVERAGE_BLOCK_INTERVAL, STALL_TIMEOUT);
+
+		if self.params.dry_run {
+			let best_header_hash = self.best_header().await?.hash();
+			self.dry_run_extrinsic(best_header_hash, extrinsic_body).await?;
+			return Ok(TransactionTracker::new_dry_run(self_clone, tx_hash))
+		}
+
+		let (sender, receiver) = futures::channel::mpsc::channel(MAX_SUBSCRIPTION_CAPACITY);
+		let subscription = self
+			.jsonrpsee_execute_with_priority(RequestPriority::Proof, move |client| async move {
+				let subscription = SubstrateAuthorClient::<C>::submit_and_watch_extrinsic(
+					&*client,
+					Bytes(encoded_extrinsic),
+				)
+				.await
+				.map_err(|e| {
+					let error = unsigned_transaction_submission_error::<C>(e);
+					match error {
+						Error::UnsignedTransactionRejected { .. } => log::debug!(
+							target: "bridge",
+							"Unsigned transaction {:?} has not been sent to {} node: {}",
+							tx_hash,
+							C::NAME,
+							error,
+						),
+						_ => log::error!(
+							target: "bridge",
+							"Failed to send unsigned transaction to {} node: {:?}",
+							C::NAME,
+							error,
+						),
+					}
+					error
+				})?;
+				log::trace!(target: "bridge", "Sent unsigned transaction to {} node: {:?}", C::NAME, tx_hash);
+				Ok(subscription)
 			})
 			.await?;
 		self.data.read().await.tokio.spawn(Subscription::background_worker(
@@ -585,7 +917,129 @@ impl<C: Chain> Client<C> {
 			subscription,
 			sender,
 		));
-		Ok(tracker)
+
+		Ok(TransactionTracker::new(
+			self_clone,
+			stall_timeout,
+			tx_hash,
+			Subscription(Mutex::new(receiver)),
+		))
+	}
+
+	/// Validate extrinsic and log it instead of submitting to the node. Used in the dry-run
+	/// mode.
+	async fn dry_run_extrinsic<Extrinsic: Clone + std::fmt::Debug + Encode + Send + 'static>(
+		&self,
+		at_block: C::Hash,
+		extrinsic: Extrinsic,
+	) -> Result<C::Hash> {
+		let encoded_extrinsic = extrinsic.encode();
+		let tx_hash = C::Hasher::hash(&encoded_extrinsic);
+		let validity = self.validate_transaction(at_block, extrinsic.clone()).await?;
+		let valid_transaction = validity.map_err(|e| {
+			log::error!(
+				target: "bridge",
+				"Dry-run mode: {} transaction {:?} is invalid: {:?}",
+				C::NAME,
+				tx_hash,
+				e,
+			);
+			Error::TransactionInvalid(e)
+		})?;
+
+		log::info!(
+			target: "bridge",
+			"Dry-run mode: not submitting valid {} transaction {:?} of {} bytes with priority {}",
+			C::NAME,
+			tx_hash,
+			encoded_extrinsic.len(),
+			valid_transaction.priority,
+		);
+		log::debug!(
+			target: "bridge",
+			"Dry-run mode: {} transaction {:?}: {:?}",
+			C::NAME,
+			tx_hash,
+			extrinsic,
+		);
+
+		Ok(tx_hash)
+	}
+
+	/// Returns `true` if bridge transactions shall be submitted as unsigned transactions.
+	pub fn submits_unsigned_transactions(&self) -> bool {
+		self.params.unsigned_transactions
+	}
+
+	/// Switch the client to the dry-run mode, where transactions are validated and logged, but
+	/// never submitted to the node.
+	pub fn with_dry_run(self, dry_run: bool) -> Self {
+		Client { params: Arc::new(ConnectionParams { dry_run, ..(*self.params).clone() }), ..self }
+	}
+
+	/// Halt (or resume) submission of new signed transactions.
+	///
+	/// While submissions are halted, all `submit_signed_extrinsic` and
+	/// `submit_and_watch_signed_extrinsic` calls are failing. Already submitted transactions
+	/// are still tracked. The flag is shared by all clones of the client.
+	pub fn set_submissions_halted(&self, halted: bool) {
+		self.submissions_halted.store(halted, Ordering::Relaxed);
+	}
+
+	/// Refuse (or allow) submission of new signed transactions because of (in)compatible chain
+	/// runtime.
+	///
+	/// If `incompatibility` is `Some(_)`, all `submit_signed_extrinsic`,
+	/// `submit_and_watch_signed_extrinsic` and `submit_and_watch_unsigned_extrinsic` calls are
+	/// failing with `Error::IncompatibleRuntime`.
+	/// The incompatibility is shared by all clones of the client.
+	pub fn set_runtime_incompatibility(&self, incompatibility: Option<String>) {
+		*self.runtime_incompatibility.lock().unwrap_or_else(|e| e.into_inner()) = incompatibility;
+	}
+
+	/// Returns tip that shall be added to the new transaction, or `None` if the tip, selected by
+	/// the transaction builder, shall be kept.
+	async fn transaction_tip(&self) -> Result<Option<C::Balance>> {
+		Ok(match self.params.tip {
+			TipStrategy::NoTip => None,
+			TipStrategy::Static(tip) => Some(C::Balance::unique_saturated_from(tip)),
+			TipStrategy::Dynamic { base_tip, multiplier } => {
+				let next_fee_multiplier = self
+					.storage_value::<FixedU128>(
+						bp_runtime::storage_value_key(
+							TRANSACTION_PAYMENT_PALLET_NAME,
+							NEXT_FEE_MULTIPLIER_VALUE_NAME,
+						),
+						None,
+					)
+					.await?
+					.unwrap_or_else(FixedU128::one);
+				Some(dynamic_tip(
+					C::Balance::unique_saturated_from(base_tip),
+					next_fee_multiplier,
+					multiplier,
+				))
+			},
+		})
+	}
+
+	/// Returns error if submission of new signed transactions is halted.
+	fn ensure_submissions_are_not_halted(&self) -> Result<()> {
+		self.ensure_runtime_is_compatible_for_submissions()?;
+		if self.submissions_halted.load(Ordering::Relaxed) {
+			return Err(Error::SubmissionsHalted(C::NAME.into()))
+		}
+		Ok(())
+	}
+
+	/// Returns error if the chain runtime is not compatible with the relay.
+	fn ensure_runtime_is_compatible_for_submissions(&self) -> Result<()> {
+		let incompatibility =
+			self.runtime_incompatibility.lock().unwrap_or_else(|e| e.into_inner()).clone();
+		if let Some(reason) = incompatibility {
+			return Err(Error::IncompatibleRuntime { chain: C::NAME.into(), reason })
+		}
+		Ok(())
 	}
 
 	/// Returns pending extrinsics from transaction pool.
@@ -621,6 +1075,28 @@ impl<C: Chain> Client<C> {
 		&self,
 		transaction: SignedTransaction,
 	) -> Result<Weight> {
+		self.query_dispatch_info(transaction)
+			.await
+			.map(|dispatch_info| dispatch_info.weight)
+	}
+
+	/// Returns estimated fee (excluding tip) that will be paid for the given transaction.
+	///
+	/// The estimation is made at the best block, so the actual fee may be different.
+	pub async fn estimate_extrinsic_fee<SignedTransaction: Encode + Send + 'static>(
+		&self,
+		transaction: SignedTransaction,
+	) -> Result<C::Balance> {
+		self.query_dispatch_info(transaction)
+			.await
+			.map(|dispatch_info| dispatch_info.partial_fee)
+	}
+
+	/// Returns dispatch info of the given transaction, computed at the best block.
+	async fn query_dispatch_info<SignedTransaction: Encode + Send + 'static>(
+		&self,
+		transaction: SignedTransaction,
+	) -> Result<RuntimeDispatchInfo<C::Balance>> {
 		self.jsonrpsee_execute(move |client| async move {
 			let transaction_len = transaction.encoded_size() as u32;
 
@@ -633,7 +1109,7 @@ impl<C: Chain> Client<C> {
 				RuntimeDispatchInfo::<C::Balance>::decode(&mut &encoded_response.0[..])
 					.map_err(Error::ResponseParseFailed)?;
 
-			Ok(dispatch_info.weight)
+			Ok(dispatch_info)
 		})
 		.await
 	}
@@ -656,6 +1132,58 @@ impl<C: Chain> Client<C> {
 		.await
 	}
 
+	/// Generate GRANDPA finality proof of given block.
+	///
+	/// Returns encoded `FinalityProof` structure of the `sc-consensus-grandpa` crate. The proof may
+	/// finalize some descendant of the requested block. Returns `None` if the node is unable
+	/// to prove finality of the block (e.g. if it has not stored required justification).
+	pub async fn prove_grandpa_finality(
+		&self,
+		block_number: C::BlockNumber,
+	) -> Result<Option<Bytes>>
+	where
+		C: ChainWithGrandpa,
+	{
+		self.jsonrpsee_execute_with_priority(RequestPriority::Finality, move |client| async move {
+			Ok(SubstrateGrandpaClient::<C>::prove_finality(&*client, block_number).await?)
+		})
+		.await
+	}
+
+	/// Return hash of the best block, finalized by BEEFY.
+	pub async fn best_beefy_finalized_header_hash(&self) -> Result<C::Hash>
+	where
+		C: ChainWithBeefy,
+	{
+		self.jsonrpsee_execute(|client| async move {
+			Ok(SubstrateBeefyClient::<C>::finalized_head(&*client).await?)
+		})
+		.await
+	}
+
+	/// Generate MMR proof of the leaf, inserted at given block.
+	///
+	/// The proof is generated against MMR root at the `best_known_block_number`.
+	pub async fn generate_mmr_proof(
+		&self,
+		block_number: C::BlockNumber,
+		best_known_block_number: C::BlockNumber,
+	) -> Result<LeavesProof<C::Hash>>
+	where
+		C: ChainWithBeefy,
+	{
+		self.jsonrpsee_execute_with_priority(RequestPriority::Proof, move |client| async move {
+			Ok(SubstrateMmrClient::<C>::generate_proof(
+				&*client,
+				vec![block_number],
+				Some(best_known_block_number),
+				None,
+			)
+			.await?)
+		})
+		.await
+	}
+
 	/// Execute runtime call at given block, provided the input and output types.
 	/// It also performs the input encode and output decode.
 	pub async fn typed_state_call<Input: codec::Encode, Output: codec::Decode>(
@@ -691,20 +1219,31 @@ impl<C: Chain> Client<C> {
 	}
 
 	/// Returns storage proof of given storage keys.
+	///
+	/// Proofs are cached, so the proof of the same keys at the same block is only read from
+	/// the node once.
 	pub async fn prove_storage(
 		&self,
 		keys: Vec<StorageKey>,
 		at_block: C::Hash,
 	) -> Result<StorageProof> {
-		self.jsonrpsee_execute(move |client| async move {
-			SubstrateStateClient::<C>::prove_storage(&*client, keys, Some(at_block))
-				.await
-				.map(|proof| {
-					StorageProof::new(proof.proof.into_iter().map(|b| b.0).collect::<Vec<_>>())
-				})
-				.map_err(Into::into)
-		})
-		.await
+		let cache_key = (at_block, keys.clone());
+		if let Some(storage_proof) = self.cache.storage_proofs.get(&cache_key) {
+			return Ok(storage_proof)
+		}
+
+		let storage_proof = self
+			.jsonrpsee_execute_with_priority(RequestPriority::Proof, move |client| async move {
+				SubstrateStateClient::<C>::prove_storage(&*client, keys, Some(at_block))
+					.await
+					.map(|proof| {
+						StorageProof::new(proof.proof.into_iter().map(|b| b.0).collect::<Vec<_>>())
+					})
+					.map_err(Into::into)
+			})
+			.await?;
+		self.cache.storage_proofs.insert(cache_key, storage_proof.clone());
+		Ok(storage_proof)
 	}
 
 	/// Return `tokenDecimals` property from the set of chain properties.
@@ -721,7 +1260,7 @@ impl<C: Chain> Client<C> {
 		&self,
 	) -> Result<Subscription<Bytes>> {
 		let subscription = self
-			.jsonrpsee_execute(move |client| async move {
+			.jsonrpsee_execute_with_priority(RequestPriority::Finality, move |client| async move {
 				Ok(FC::subscribe_justifications(&client).await?)
 			})
 			.await?;
@@ -735,6 +1274,70 @@ impl<C: Chain> Client<C> {
 		Ok(Subscription(Mutex::new(receiver)))
 	}
 
+	/// Return new finalized headers stream.
+	pub async fn subscribe_finalized_headers(&self) -> Result<Subscription<C::Header>>
+	where
+		C::Header: DeserializeOwned,
+	{
+		let subscription = self
+			.jsonrpsee_execute_with_priority(RequestPriority::Finality, move |client| async move {
+				Ok(SubstrateChainClient::<C>::subscribe_finalized_heads(&*client).await?)
+			})
+			.await?;
+		let (sender, receiver) = futures::channel::mpsc::channel(MAX_SUBSCRIPTION_CAPACITY);
+		self.data.read().await.tokio.spawn(Subscription::background_worker(
+			C::NAME.into(),
+			"finalized headers".into(),
+			subscription,
+			sender,
+		));
+		Ok(Subscription(Mutex::new(receiver)))
+	}
+
+	/// Return stream of numbers of all headers, finalized after this call.
+	///
+	/// The node may skip some headers in its finalized headers notifications (e.g. when several
+	/// headers are finalized at once). Numbers of skipped headers are yielded without reading
+	/// headers from the node, so every finalized header number is yielded exactly once and in
+	/// order.
+	///
+	/// The stream ends when the underlying subscription is closed.
+	pub async fn finalized_header_numbers_stream(
+		&self,
+	) -> Result<impl futures::Stream<Item = C::BlockNumber> + Send + 'static>
+	where
+		C::Header: DeserializeOwned,
+	{
+		let state = FinalizedHeadersStreamState {
+			subscription: self.subscribe_finalized_headers().await?,
+			best_yielded: None,
+			notified: None,
+		};
+		Ok(futures::stream::unfold(state, |mut state| async move {
+			loop {
+				if let Some(notified) = state.notified.take() {
+					match next_finalized_header_action(state.best_yielded, notified) {
+						FinalizedHeaderAction::Skip => continue,
+						FinalizedHeaderAction::Yield => {
+							state.best_yielded = Some(notified);
+							return Some((notified, state))
+						},
+						FinalizedHeaderAction::FillGap(number) => {
+							state.notified = Some(notified);
+							state.best_yielded = Some(number);
+							return Some((number, state))
+						},
+					}
+				}
+
+				match state.subscription.next().await {
+					Ok(Some(header)) => state.notified = Some(*header.number()),
+					Ok(None) | Err(_) => return None,
+				}
+			}
+		}))
+	}
+
 	/// Execute jsonrpsee future in tokio context.
 	async fn jsonrpsee_execute<MF, F, T>(&self, make_jsonrpsee_future: MF) -> Result<T>
 	where
@@ -742,6 +1345,26 @@ impl<C: Chain> Client<C> {
 		F: Future<Output = Result<T>> + Send,
 		T: Send + 'static,
 	{
+		self.jsonrpsee_execute_with_priority(RequestPriority::Informational, make_jsonrpsee_future)
+			.await
+	}
+
+	/// Execute jsonrpsee future in tokio context, when the request budget allows sending the
+	/// request with given priority.
+	async fn jsonrpsee_execute_with_priority<MF, F, T>(
+		&self,
+		priority: RequestPriority,
+		make_jsonrpsee_future: MF,
+	) -> Result<T>
+	where
+		MF: FnOnce(Arc<RpcClient>) -> F + Send + 'static,
+		F: Future<Output = Result<T>> + Send,
+		T: Send + 'static,
+	{
+		if let Some(ref request_budget) = self.request_budget {
+			request_budget.acquire(priority).await;
+		}
+
 		let data = self.data.read().await;
 		let client = data.client.clone();
 		data.tokio.spawn(async move { make_jsonrpsee_future(client).await }).await?
@@ -757,6 +1380,43 @@ impl<C: Chain> Client<C> {
 	}
 }
 
+/// State of the `Client::finalized_header_numbers_stream`.
+struct FinalizedHeadersStreamState<C: Chain> {
+	/// Finalized headers subscription.
+	subscription: Subscription<C::Header>,
+	/// Number of the best header, yielded by the stream.
+	best_yielded: Option<C::BlockNumber>,
+	/// Number of the header that we have received from the subscription, but have not yielded
+	/// yet.
+	notified: Option<C::BlockNumber>,
+}
+
+/// What to do with the header, received from the finalized headers subscription.
+#[derive(Debug, PartialEq)]
+enum FinalizedHeaderAction<N> {
+	/// Header is already yielded, so it must be skipped.
+	Skip,
+	/// Some headers have been skipped by the node. Given number must be yielded before the number
+	/// of the received header.
+	FillGap(N),
+	/// Header must be yielded.
+	Yield,
+}
+
+/// Select what to do with the header, received from the finalized headers subscription.
+fn next_finalized_header_action<N: Copy + Ord + One + Saturating>(
+	best_yielded: Option<N>,
+	notified: N,
+) -> FinalizedHeaderAction<N> {
+	match best_yielded {
+		None => FinalizedHeaderAction::Yield,
+		Some(best_yielded) if notified <= best_yielded => FinalizedHeaderAction::Skip,
+		Some(best_yielded) if notified > best_yielded.saturating_add(One::one()) =>
+			FinalizedHeaderAction::FillGap(best_yielded.saturating_add(One::one())),
+		Some(_) => FinalizedHeaderAction::Yield,
+	}
+}
+
 impl<T: DeserializeOwned> Subscription<T> {
 	/// Consumes subscription and returns future statuses stream.
 	pub fn into_stream(self) -> impl futures::Stream<Item = T> {
@@ -811,3 +1471,199 @@ impl<T: DeserializeOwned> Subscription<T> {
 		}
 	}
 }
+
+/// Set transaction tip, if it is specified.
+fn with_tip<C: Chain>(
+	tx: UnsignedTransaction<C>,
+	tip: Option<C::Balance>,
+) -> UnsignedTransaction<C> {
+	match tip {
+		Some(tip) => tx.tip(tip),
+		None => tx,
+	}
+}
+
+/// Makes transaction mortal, if the transaction builder hasn't selected its era.
+fn with_default_mortality<C: Chain>(
+	tx: UnsignedTransaction<C>,
+	best_header_id: HeaderIdOf<C>,
+	mortality: Option<u32>,
+) -> UnsignedTransaction<C> {
+	match tx.era {
+		TransactionEra::Immortal => tx.era(TransactionEra::new(best_header_id, mortality)),
+		TransactionEra::Mortal(..) => tx,
+	}
+}
+
+/// Returns body (version and call) of the unsigned extrinsic in the generic Substrate format.
+///
+/// The body is `Vec<u8>`, so its SCALE encoding (with the length prefix) is the extrinsic itself.
+fn unsigned_extrinsic_body<Call: Encode>(call: &Call) -> Vec<u8> {
+	(UNSIGNED_EXTRINSIC_FORMAT_VERSION, call).encode()
+}
+
+/// Convert error, returned by the node when unsigned transaction is submitted.
+///
+/// Errors that mean that the same (or competing) transaction is already known to the pool, are
+/// converted into `Error::UnsignedTransactionRejected`.
+fn unsigned_transaction_submission_error<C: Chain>(error: RpcError) -> Error {
+	let reason = match error {
+		RpcError::Call(CallError::Custom(ref e)) => match e.code() {
+			POOL_TEMPORARILY_BANNED =>
+				Some("the same transaction has been recently included or rejected"),
+			POOL_ALREADY_IMPORTED => Some("the same transaction is already in the pool"),
+			POOL_TOO_LOW_PRIORITY =>
+				Some("competing transaction with higher priority is already in the pool"),
+			_ => None,
+		},
+		_ => None,
+	};
+
+	match reason {
+		Some(reason) =>
+			Error::UnsignedTransactionRejected { chain: C::NAME.into(), reason: reason.into() },
+		None => Error::RpcError(error),
+	}
+}
+
+/// Compute dynamic tip: `base_tip * next_fee_multiplier * multiplier`.
+fn dynamic_tip<Balance: FixedPointOperand>(
+	base_tip: Balance,
+	next_fee_multiplier: FixedU128,
+	multiplier: FixedU128,
+) -> Balance {
+	multiplier.saturating_mul_int(next_fee_multiplier.saturating_mul_int(base_tip))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn first_finalized_header_is_yielded() {
+		assert_eq!(next_finalized_header_action(None, 100u32), FinalizedHeaderAction::Yield);
+	}
+
+	#[test]
+	fn next_finalized_header_is_yielded() {
+		assert_eq!(next_finalized_header_action(Some(99u32), 100), FinalizedHeaderAction::Yield);
+	}
+
+	#[test]
+	fn already_yielded_finalized_header_is_skipped() {
+		assert_eq!(next_finalized_header_action(Some(100u32), 100), FinalizedHeaderAction::Skip);
+		assert_eq!(next_finalized_header_action(Some(100u32), 90), FinalizedHeaderAction::Skip);
+	}
+
+	#[test]
+	fn skipped_finalized_headers_are_filled() {
+		assert_eq!(
+			next_finalized_header_action(Some(90u32), 100),
+			FinalizedHeaderAction::FillGap(91)
+		);
+		assert_eq!(
+			next_finalized_header_action(Some(98u32), 100),
+			FinalizedHeaderAction::FillGap(99)
+		);
+	}
+
+	#[test]
+	fn dynamic_tip_follows_next_fee_multiplier() {
+		let base_tip = 1_000u128;
+		assert_eq!(dynamic_tip(base_tip, FixedU128::one(), FixedU128::one()), 1_000);
+		assert_eq!(
+			dynamic_tip(base_tip, FixedU128::saturating_from_integer(3), FixedU128::one()),
+			3_000
+		);
+		assert_eq!(
+			dynamic_tip(
+				base_tip,
+				FixedU128::saturating_from_integer(3),
+				FixedU128::saturating_from_rational(3, 2)
+			),
+			4_500
+		);
+		assert_eq!(
+			dynamic_tip(
+				base_tip,
+				FixedU128::from_inner(u128::MAX),
+				FixedU128::from_inner(u128::MAX)
+			),
+			u128::MAX
+		);
+	}
+
+	#[test]
+	fn default_mortality_is_only_applied_to_immortal_transactions() {
+		type TestChain = crate::test_chain::TestChain;
+		let best_header_id = bp_runtime::HeaderId(100, Default::default());
+		let immortal_tx = || {
+			UnsignedTransaction::<TestChain>::new(bp_runtime::EncodedOrDecodedCall::Decoded(()), 0)
+		};
+
+		assert_eq!(
+			with_default_mortality(immortal_tx(), best_header_id, None).era,
+			TransactionEra::Immortal,
+		);
+		assert_eq!(
+			with_default_mortality(immortal_tx(), best_header_id, Some(64)).era,
+			TransactionEra::Mortal(best_header_id, 64),
+		);
+		assert_eq!(
+			with_default_mortality(
+				immortal_tx().era(TransactionEra::Mortal(best_header_id, 16)),
+				best_header_id,
+				Some(64),
+			)
+			.era,
+			TransactionEra::Mortal(best_header_id, 16),
+		);
+	}
+
+	#[test]
+	fn unsigned_extrinsic_is_encoded_in_generic_format() {
+		let call = (42u8, vec![1u8, 2, 3]);
+		assert_eq!(
+			unsigned_extrinsic_body(&call).encode(),
+			sp_runtime::generic::UncheckedExtrinsic::<(), _, (), ()>::new_unsigned(call).encode(),
+		);
+	}
+
+	#[test]
+	fn unsigned_transaction_pool_errors_are_not_connection_errors() {
+		use jsonrpsee::types::error::ErrorObject;
+		use relay_utils::MaybeConnectionError;
+
+		let pool_error = |code| {
+			unsigned_transaction_submission_error::<crate::test_chain::TestChain>(RpcError::Call(
+				CallError::Custom(ErrorObject::owned(code, "pool error", None::<()>)),
+			))
+		};
+
+		for code in [POOL_TEMPORARILY_BANNED, POOL_ALREADY_IMPORTED, POOL_TOO_LOW_PRIORITY] {
+			let error = pool_error(code);
+			assert!(matches!(error, Error::UnsignedTransactionRejected { .. }));
+			assert!(!error.is_connection_error());
+			assert!(!error.is_fatal_error());
+		}
+		assert!(matches!(pool_error(1010), Error::RpcError(_)));
+	}
+
+	#[test]
+	fn response_parse_errors_are_retryable() {
+		use relay_utils::{ErrorClass, MaybeConnectionError};
+
+		let error = Error::ResponseParseFailed(codec::Error::from("undecodable response"));
+		assert_eq!(ErrorClass::of(&error), ErrorClass::Retryable);
+
+		let error = Error::ErrorExecutingRuntimeCall {
+			chain: "Test".into(),
+			method: "method".into(),
+			error: Box::new(Error::ResponseParseFailed(codec::Error::from("undecodable response"))),
+		};
+		assert!(!error.is_fatal_error());
+
+		let error = Error::IncompatibleRuntime { chain: "Test".into(), reason: "reason".into() };
+		assert_eq!(ErrorClass::of(&error), ErrorClass::Fatal);
+	}
+}
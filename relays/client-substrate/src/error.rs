@@ -16,6 +16,8 @@
 
 //! Substrate node RPC errors.
 
+use crate::TransportError;
+
 use bp_polkadot_core::parachains::ParaId;
 use jsonrpsee::core::Error as RpcError;
 use relay_utils::MaybeConnectionError;
@@ -34,6 +36,9 @@ pub enum Error {
 	/// IO error.
 	#[error("IO error: {0}")]
 	Io(#[from] std::io::Error),
+	/// Failed to establish connection to the node.
+	#[error("Failed to connect to the node: {0}")]
+	Transport(#[from] TransportError),
 	/// An error that can occur when making a request to
 	/// an JSON-RPC server.
 	#[error("RPC error: {0}")]
@@ -105,6 +110,10 @@ pub enum Error {
 	/// The bridge pallet is halted and all transactions will be rejected.
 	#[error("Bridge pallet is halted.")]
 	BridgePalletIsHalted,
+	/// The bridge messages pallet is not accepting inbound messages and all messages delivery
+	/// transactions will be rejected.
+	#[error("Bridge messages pallet is not accepting inbound messages.")]
+	BridgePalletIsNotAcceptingInboundMessages,
 	/// The bridge pallet is not yet initialized and all transactions will be rejected.
 	#[error("Bridge pallet is not initialized.")]
 	BridgePalletIsNotInitialized,
@@ -114,6 +123,26 @@ pub enum Error {
 	/// An error has happened when we have tried to parse storage proof.
 	#[error("Error when parsing storage proof: {0:?}.")]
 	StorageProofError(bp_runtime::StorageProofError),
+	/// Submission of new transactions is halted, because relayer account balance is too low.
+	#[error("Submission of new {0} transactions is halted, because relayer balance is too low.")]
+	SubmissionsHalted(String),
+	/// The chain runtime is not compatible with the runtime, bundled with the relay.
+	#[error("{chain} runtime is not compatible with the relay: {reason}.")]
+	IncompatibleRuntime {
+		/// Name of the chain where the error has happened.
+		chain: String,
+		/// Description of the incompatibility.
+		reason: String,
+	},
+	/// The transaction pool has rejected unsigned transaction, because the same (or competing)
+	/// transaction is already known to the pool. The submission may be retried later.
+	#[error("{chain} transaction pool has rejected unsigned transaction: {reason}.")]
+	UnsignedTransactionRejected {
+		/// Name of the chain where the error has happened.
+		chain: String,
+		/// Rejection reason.
+		reason: String,
+	},
 	/// The Substrate transaction is invalid.
 	#[error("Substrate transaction is invalid: {0:?}")]
 	TransactionInvalid(#[from] TransactionValidityError),
@@ -143,7 +172,8 @@ impl MaybeConnectionError for Error {
 				// we're getting this error
 				| Error::RpcError(RpcError::Internal(_))
 				| Error::RpcError(RpcError::RestartNeeded(_))
-				| Error::ClientNotSynced(_) => true,
+				| Error::ClientNotSynced(_)
+				| Error::Transport(_) => true,
 			Error::FailedToReadBestFinalizedHeaderHash { ref error, .. } => error.is_connection_error(),
 			Error::FailedToReadBestHeader { ref error, .. } => error.is_connection_error(),
 			Error::FailedToReadHeaderByHash { ref error, .. } => error.is_connection_error(),
@@ -152,4 +182,19 @@ impl MaybeConnectionError for Error {
 			_ => false,
 		}
 	}
+
+	fn is_fatal_error(&self) -> bool {
+		match *self {
+			// the runtime is not compatible with the relay, so the same request will fail again.
+			// Undecodable responses are not fatal: they may be caused by a node that is lagging
+			// behind or by a runtime upgrade that is not yet enacted at the best block
+			Error::IncompatibleRuntime { .. } => true,
+			Error::FailedToReadBestFinalizedHeaderHash { ref error, .. } => error.is_fatal_error(),
+			Error::FailedToReadBestHeader { ref error, .. } => error.is_fatal_error(),
+			Error::FailedToReadHeaderByHash { ref error, .. } => error.is_fatal_error(),
+			Error::ErrorExecutingRuntimeCall { ref error, .. } => error.is_fatal_error(),
+			Error::FailedToReadRuntimeStorageValue { ref error, .. } => error.is_fatal_error(),
+			_ => false,
+		}
+	}
 }
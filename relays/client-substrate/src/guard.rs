@@ -21,6 +21,11 @@ use crate::{error::Error, Chain, ChainWithBalances, Client};
 
 use async_trait::async_trait;
 use num_traits::CheckedSub;
+use relay_utils::{
+	metrics::{metric_name, register, Gauge, Metric, PrometheusError, Registry, F64, U64},
+	UniqueSaturatedInto,
+};
+use sp_core::hexdisplay::HexDisplay;
 use sp_version::RuntimeVersion;
 use std::{
 	collections::VecDeque,
@@ -42,6 +47,12 @@ pub trait Environment<C: ChainWithBalances>: Send + Sync + 'static {
 		account: C::AccountId,
 	) -> Result<C::Balance, Self::Error>;
 
+	/// Halt (or resume) submission of new transactions to the chain.
+	fn set_submissions_halted(&mut self, halted: bool);
+	/// Refuse (or allow) submission of new transactions to the chain because of (in)compatible
+	/// chain runtime.
+	fn set_runtime_incompatibility(&mut self, incompatibility: Option<String>);
+
 	/// Return current time.
 	fn now(&self) -> Instant {
 		Instant::now()
@@ -99,6 +110,168 @@ pub fn abort_on_spec_version_change<C: ChainWithBalances>(
 	});
 }
 
+/// Returns description of the first found incompatibility between the expected (bundled with the
+/// relay) and actual chain runtime, or `None` if runtimes are compatible.
+///
+/// Runtimes are compatible if they have the same spec name, spec and transaction versions and
+/// if the actual runtime provides all bundled runtime APIs of the same version.
+pub fn runtime_incompatibility(
+	expected: &RuntimeVersion,
+	actual: &RuntimeVersion,
+) -> Option<String> {
+	if actual.spec_name != expected.spec_name {
+		return Some(format!(
+			"spec_name is {}, while the relay expects {}",
+			actual.spec_name, expected.spec_name,
+		))
+	}
+	if actual.spec_version != expected.spec_version {
+		return Some(format!(
+			"spec_version is {}, while the relay expects {}",
+			actual.spec_version, expected.spec_version,
+		))
+	}
+	if actual.transaction_version != expected.transaction_version {
+		return Some(format!(
+			"transaction_version is {}, while the relay expects {}",
+			actual.transaction_version, expected.transaction_version,
+		))
+	}
+	for (api_id, expected_api_version) in expected.apis.iter() {
+		let actual_api_version = actual.api_version(api_id);
+		if actual_api_version != Some(*expected_api_version) {
+			return Some(format!(
+				"version of runtime API 0x{} is {:?}, while the relay expects {}",
+				HexDisplay::from(api_id),
+				actual_api_version,
+				expected_api_version,
+			))
+		}
+	}
+
+	None
+}
+
+/// Runtime compatibility guard metrics.
+#[derive(Clone)]
+pub struct RuntimeCompatibilityMetrics {
+	/// `1` if the chain runtime is not compatible with the relay.
+	is_incompatible: Gauge<U64>,
+}
+
+impl RuntimeCompatibilityMetrics {
+	/// Create metrics for the runtime of given chain.
+	pub fn new(chain: &str) -> Result<Self, PrometheusError> {
+		Ok(RuntimeCompatibilityMetrics {
+			is_incompatible: Gauge::new(
+				metric_name(None, &format!("{chain}_runtime_is_incompatible")),
+				format!("1 if the {chain} runtime is not compatible with the relay"),
+			)?,
+		})
+	}
+}
+
+impl Metric for RuntimeCompatibilityMetrics {
+	fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		register(self.is_incompatible.clone(), registry)?;
+		Ok(())
+	}
+}
+
+/// Refuse submission of new transactions when the chain runtime becomes incompatible with the
+/// expected runtime.
+///
+/// The compatibility is rechecked every time the runtime `spec_version` changes. Submission is
+/// allowed again if the runtime becomes compatible (e.g. when the upgrade is reverted).
+pub fn halt_submissions_on_incompatible_runtime<C: ChainWithBalances>(
+	mut env: impl Environment<C>,
+	expected: RuntimeVersion,
+	metrics: Option<RuntimeCompatibilityMetrics>,
+) {
+	async_std::task::spawn(async move {
+		log::info!(
+			target: "bridge-guard",
+			"Starting runtime compatibility guard for {}. Expected runtime: {}/{}",
+			C::NAME,
+			expected.spec_name,
+			expected.spec_version,
+		);
+
+		let health_component = format!("{} runtime", C::NAME);
+		let mut checked_spec_version = None;
+
+		loop {
+			match env.runtime_version().await {
+				Ok(actual) if checked_spec_version != Some(actual.spec_version) => {
+					let incompatibility = runtime_incompatibility(&expected, &actual);
+					if let Some(ref metrics) = metrics {
+						metrics.is_incompatible.set(incompatibility.is_some() as u64);
+					}
+
+					match incompatibility {
+						Some(ref reason) => {
+							log::error!(
+								target: "bridge-guard",
+								"{} runtime has been upgraded to {}/{} and it is not compatible \
+								with the relay: {}. Submission of new transactions is refused",
+								C::NAME,
+								actual.spec_name,
+								actual.spec_version,
+								reason,
+							);
+							relay_utils::health::report_unhealthy(
+								health_component.clone(),
+								format!("incompatible runtime: {reason}"),
+							);
+						},
+						None => {
+							log::info!(
+								target: "bridge-guard",
+								"{} runtime {}/{} is compatible with the relay",
+								C::NAME,
+								actual.spec_name,
+								actual.spec_version,
+							);
+							relay_utils::health::report_healthy(
+								health_component.clone(),
+								format!("compatible runtime {}", actual.spec_version),
+							);
+						},
+					}
+
+					env.set_runtime_incompatibility(incompatibility);
+					checked_spec_version = Some(actual.spec_version);
+				},
+				Ok(_) => (),
+				Err(error) => log::warn!(
+					target: "bridge-guard",
+					"Failed to read {} runtime version: {}",
+					C::NAME,
+					error,
+				),
+			}
+
+			env.sleep(conditions_check_delay::<C>()).await;
+		}
+	});
+}
+
+/// Start runtime compatibility guard for given client, if the relay bundles the chain runtime.
+pub fn start_runtime_compatibility_guard<C: ChainWithBalances>(
+	client: &Client<C>,
+	registry: &Registry,
+) -> Result<(), PrometheusError> {
+	let expected = match client.expected_runtime_version() {
+		Some(expected) => expected,
+		None => return Ok(()),
+	};
+
+	let metrics = RuntimeCompatibilityMetrics::new(C::NAME)?;
+	metrics.register(registry)?;
+	halt_submissions_on_incompatible_runtime(client.clone(), expected, Some(metrics));
+	Ok(())
+}
+
 /// Abort if, during 24 hours, free balance of given account is decreased at least by given value.
 /// Other components may increase (or decrease) balance of account and it WILL affect logic of the
 /// guard.
@@ -173,6 +346,168 @@ pub fn abort_when_account_balance_decreased<C: ChainWithBalances>(
 	});
 }
 
+/// Thresholds of the relayer account free balance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BalanceThresholds<Balance> {
+	/// When free balance is below this value, the guard warns that the account needs to be
+	/// replenished.
+	pub soft: Balance,
+	/// When free balance is below this value, the guard halts submission of new transactions.
+	pub hard: Balance,
+}
+
+/// State of the relayer account balance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BalanceState {
+	/// Balance is above both thresholds.
+	Enough,
+	/// Balance is below the soft threshold.
+	BelowSoftThreshold,
+	/// Balance is below the hard threshold.
+	BelowHardThreshold,
+}
+
+impl<Balance: PartialOrd> BalanceThresholds<Balance> {
+	/// Returns state of the given balance.
+	pub fn state(&self, balance: Balance) -> BalanceState {
+		if balance < self.hard {
+			BalanceState::BelowHardThreshold
+		} else if balance < self.soft {
+			BalanceState::BelowSoftThreshold
+		} else {
+			BalanceState::Enough
+		}
+	}
+}
+
+/// Relayer account balance metrics.
+#[derive(Clone)]
+pub struct BalanceGuardMetrics {
+	/// Free balance of the relayer account, in the smallest units of the chain token.
+	free_balance: Gauge<F64>,
+	/// `1` if submission of new transactions is halted because of low balance.
+	submissions_halted: Gauge<U64>,
+}
+
+impl BalanceGuardMetrics {
+	/// Create metrics for the relayer account at given chain.
+	pub fn new(chain: &str) -> Result<Self, PrometheusError> {
+		Ok(BalanceGuardMetrics {
+			free_balance: Gauge::new(
+				metric_name(None, &format!("at_{chain}_relay_free_balance")),
+				format!("Free balance of the relay account at the {chain}"),
+			)?,
+			submissions_halted: Gauge::new(
+				metric_name(None, &format!("at_{chain}_relay_submissions_halted")),
+				format!("1 if transactions submission to the {chain} is halted due to low balance"),
+			)?,
+		})
+	}
+}
+
+impl Metric for BalanceGuardMetrics {
+	fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		register(self.free_balance.clone(), registry)?;
+		register(self.submissions_halted.clone(), registry)?;
+		Ok(())
+	}
+}
+
+/// Warn when free balance of given account is below the soft threshold and halt submission of
+/// new transactions when it is below the hard threshold.
+///
+/// Transactions that have been submitted before are still tracked, so the relay may finish them.
+/// Submission is resumed once the balance is above the hard threshold again.
+pub fn halt_submissions_on_low_balance<C: ChainWithBalances>(
+	mut env: impl Environment<C>,
+	account_id: C::AccountId,
+	thresholds: BalanceThresholds<C::Balance>,
+	metrics: Option<BalanceGuardMetrics>,
+) {
+	async_std::task::spawn(async move {
+		log::info!(
+			target: "bridge-guard",
+			"Starting low balance guard for {}/{:?}. Soft threshold: {:?}. Hard threshold: {:?}",
+			C::NAME,
+			account_id,
+			thresholds.soft,
+			thresholds.hard,
+		);
+
+		let mut prev_state = BalanceState::Enough;
+
+		loop {
+			match env.free_native_balance(account_id.clone()).await {
+				Ok(balance) => {
+					let state = thresholds.state(balance);
+					let is_halted = state == BalanceState::BelowHardThreshold;
+					if let Some(ref metrics) = metrics {
+						let balance: u128 = balance.unique_saturated_into();
+						metrics.free_balance.set(balance as f64);
+						metrics.submissions_halted.set(is_halted as u64);
+					}
+
+					match state {
+						BalanceState::BelowHardThreshold => log::error!(
+							target: "bridge-guard",
+							"Balance of {} account {:?} is {:?}, which is below the hard threshold {:?}. \
+							Submission of new transactions is halted",
+							C::NAME,
+							account_id,
+							balance,
+							thresholds.hard,
+						),
+						BalanceState::BelowSoftThreshold => log::warn!(
+							target: "bridge-guard",
+							"Balance of {} account {:?} is {:?}, which is below the soft threshold {:?}. \
+							The account needs to be replenished",
+							C::NAME,
+							account_id,
+							balance,
+							thresholds.soft,
+						),
+						BalanceState::Enough if prev_state != BalanceState::Enough => log::info!(
+							target: "bridge-guard",
+							"Balance of {} account {:?} has been replenished: {:?}",
+							C::NAME,
+							account_id,
+							balance,
+						),
+						BalanceState::Enough => (),
+					}
+
+					let health_component = format!("{} relayer balance", C::NAME);
+					if is_halted {
+						relay_utils::health::report_unhealthy(
+							health_component,
+							format!("balance {balance:?} is below the hard threshold"),
+						);
+					} else {
+						relay_utils::health::report_healthy(
+							health_component,
+							format!("balance {balance:?}"),
+						);
+					}
+
+					env.set_submissions_halted(is_halted);
+					prev_state = state;
+				},
+				Err(error) => {
+					log::warn!(
+						target: "bridge-guard",
+						"Failed to read {} account {:?} balance: {}",
+						C::NAME,
+						account_id,
+						error,
+					);
+				},
+			}
+
+			env.sleep(conditions_check_delay::<C>()).await;
+		}
+	});
+}
+
 /// Delay between conditions check.
 fn conditions_check_delay<C: Chain>() -> Duration {
 	C::AVERAGE_BLOCK_INTERVAL * (10 + rand::random::<u32>() % 10)
@@ -192,6 +527,14 @@ impl<C: ChainWithBalances> Environment<C> for Client<C> {
 	) -> Result<C::Balance, Self::Error> {
 		Client::<C>::free_native_balance(self, account).await
 	}
+
+	fn set_submissions_halted(&mut self, halted: bool) {
+		Client::<C>::set_submissions_halted(self, halted)
+	}
+
+	fn set_runtime_incompatibility(&mut self, incompatibility: Option<String>) {
+		Client::<C>::set_runtime_incompatibility(self, incompatibility)
+	}
 }
 
 #[cfg(test)]
@@ -210,6 +553,8 @@ mod tests {
 		free_native_balance_rx: UnboundedReceiver<u32>,
 		slept_tx: UnboundedSender<()>,
 		aborted_tx: UnboundedSender<()>,
+		submissions_halted_tx: UnboundedSender<bool>,
+		runtime_incompatibility_tx: UnboundedSender<Option<String>>,
 	}
 
 	#[async_trait]
@@ -224,6 +569,14 @@ mod tests {
 			Ok(self.free_native_balance_rx.next().await.unwrap_or_default())
 		}
 
+		fn set_submissions_halted(&mut self, halted: bool) {
+			let _ = self.submissions_halted_tx.unbounded_send(halted);
+		}
+
+		fn set_runtime_incompatibility(&mut self, incompatibility: Option<String>) {
+			let _ = self.runtime_incompatibility_tx.unbounded_send(incompatibility);
+		}
+
 		async fn sleep(&mut self, _duration: Duration) {
 			let _ = self.slept_tx.send(()).await;
 		}
@@ -250,6 +603,8 @@ mod tests {
 					free_native_balance_rx,
 					slept_tx,
 					aborted_tx,
+					submissions_halted_tx: unbounded().0,
+					runtime_incompatibility_tx: unbounded().0,
 				},
 				0,
 			);
@@ -282,6 +637,8 @@ mod tests {
 					free_native_balance_rx,
 					slept_tx,
 					aborted_tx,
+					submissions_halted_tx: unbounded().0,
+					runtime_incompatibility_tx: unbounded().0,
 				},
 				42,
 			);
@@ -314,6 +671,8 @@ mod tests {
 					free_native_balance_rx,
 					slept_tx,
 					aborted_tx,
+					submissions_halted_tx: unbounded().0,
+					runtime_incompatibility_tx: unbounded().0,
 				},
 				0,
 				100,
@@ -350,6 +709,8 @@ mod tests {
 					free_native_balance_rx,
 					slept_tx,
 					aborted_tx,
+					submissions_halted_tx: unbounded().0,
+					runtime_incompatibility_tx: unbounded().0,
 				},
 				0,
 				100,
@@ -370,4 +731,141 @@ mod tests {
 			assert!(aborted_rx.next().now_or_never().is_none());
 		});
 	}
+
+	#[test]
+	fn runtime_incompatibility_is_detected() {
+		let expected = RuntimeVersion {
+			spec_name: "test".into(),
+			spec_version: 42,
+			transaction_version: 1,
+			apis: std::borrow::Cow::Owned(vec![([1; 8], 2)]),
+			..Default::default()
+		};
+		assert_eq!(runtime_incompatibility(&expected, &expected), None);
+		assert!(runtime_incompatibility(
+			&expected,
+			&RuntimeVersion { spec_name: "other".into(), ..expected.clone() }
+		)
+		.is_some());
+		assert!(runtime_incompatibility(
+			&expected,
+			&RuntimeVersion { spec_version: 43, ..expected.clone() }
+		)
+		.is_some());
+		assert!(runtime_incompatibility(
+			&expected,
+			&RuntimeVersion { transaction_version: 2, ..expected.clone() }
+		)
+		.is_some());
+		assert!(runtime_incompatibility(
+			&expected,
+			&RuntimeVersion {
+				apis: std::borrow::Cow::Owned(vec![([1; 8], 3)]),
+				..expected.clone()
+			}
+		)
+		.is_some());
+		assert!(runtime_incompatibility(
+			&expected,
+			&RuntimeVersion { apis: std::borrow::Cow::Owned(vec![]), ..expected.clone() }
+		)
+		.is_some());
+	}
+
+	#[test]
+	fn refuses_submissions_when_runtime_is_incompatible() {
+		async_std::task::block_on(async {
+			let (
+				(mut runtime_version_tx, runtime_version_rx),
+				(_free_native_balance_tx, free_native_balance_rx),
+				(slept_tx, mut slept_rx),
+				(aborted_tx, mut aborted_rx),
+				(runtime_incompatibility_tx, mut runtime_incompatibility_rx),
+			) = (unbounded(), unbounded(), unbounded(), unbounded(), unbounded());
+			halt_submissions_on_incompatible_runtime(
+				TestEnvironment {
+					runtime_version_rx,
+					free_native_balance_rx,
+					slept_tx,
+					aborted_tx,
+					submissions_halted_tx: unbounded().0,
+					runtime_incompatibility_tx,
+				},
+				RuntimeVersion { spec_version: 42, ..Default::default() },
+				Some(RuntimeCompatibilityMetrics::new("TestChain").unwrap()),
+			);
+
+			// runtime is compatible => submissions are allowed
+			runtime_version_tx
+				.send(RuntimeVersion { spec_version: 42, ..Default::default() })
+				.await
+				.unwrap();
+			assert_eq!(runtime_incompatibility_rx.next().await, Some(None));
+			slept_rx.next().await;
+
+			// runtime is upgraded => submissions are refused
+			runtime_version_tx
+				.send(RuntimeVersion { spec_version: 43, ..Default::default() })
+				.await
+				.unwrap();
+			assert!(matches!(runtime_incompatibility_rx.next().await, Some(Some(_))));
+			slept_rx.next().await;
+
+			// and the relay is never aborted
+			assert!(aborted_rx.next().now_or_never().is_none());
+		});
+	}
+
+	#[test]
+	fn balance_state_is_computed_using_thresholds() {
+		let thresholds = BalanceThresholds { soft: 100, hard: 10 };
+		assert_eq!(thresholds.state(100), BalanceState::Enough);
+		assert_eq!(thresholds.state(99), BalanceState::BelowSoftThreshold);
+		assert_eq!(thresholds.state(10), BalanceState::BelowSoftThreshold);
+		assert_eq!(thresholds.state(9), BalanceState::BelowHardThreshold);
+	}
+
+	#[test]
+	fn halts_and_resumes_submissions_depending_on_balance() {
+		async_std::task::block_on(async {
+			let (
+				(_runtime_version_tx, runtime_version_rx),
+				(mut free_native_balance_tx, free_native_balance_rx),
+				(slept_tx, mut slept_rx),
+				(aborted_tx, mut aborted_rx),
+				(submissions_halted_tx, mut submissions_halted_rx),
+			) = (unbounded(), unbounded(), unbounded(), unbounded(), unbounded());
+			halt_submissions_on_low_balance(
+				TestEnvironment {
+					runtime_version_rx,
+					free_native_balance_rx,
+					slept_tx,
+					aborted_tx,
+					submissions_halted_tx,
+					runtime_incompatibility_tx: unbounded().0,
+				},
+				0,
+				BalanceThresholds { soft: 100, hard: 10 },
+				Some(BalanceGuardMetrics::new("TestChain").unwrap()),
+			);
+
+			// balance is below the soft threshold => submissions are not halted
+			free_native_balance_tx.send(50).await.unwrap();
+			assert_eq!(submissions_halted_rx.next().await, Some(false));
+			slept_rx.next().await;
+
+			// balance is below the hard threshold => submissions are halted
+			free_native_balance_tx.send(5).await.unwrap();
+			assert_eq!(submissions_halted_rx.next().await, Some(true));
+			slept_rx.next().await;
+
+			// balance is replenished => submissions are resumed
+			free_native_balance_tx.send(1000).await.unwrap();
+			assert_eq!(submissions_halted_rx.next().await, Some(false));
+			slept_rx.next().await;
+
+			// and the relay is never aborted
+			assert!(aborted_rx.next().now_or_never().is_none());
+		});
+	}
 }
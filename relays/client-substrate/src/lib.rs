@@ -18,41 +18,48 @@
 
 #![warn(missing_docs)]
 
+mod cache;
 mod chain;
 mod client;
 mod error;
+mod request_budget;
 mod rpc;
 mod sync_header;
 mod transaction_tracker;
+mod transport;
 
 pub mod calls;
 pub mod guard;
 pub mod metrics;
 pub mod test_chain;
 
-use std::time::Duration;
+use std::{num::NonZeroU32, time::Duration};
 
 pub use crate::{
 	chain::{
-		AccountKeyPairOf, BlockWithJustification, CallOf, Chain, ChainWithBalances,
+		AccountKeyPairOf, BlockWithJustification, CallOf, Chain, ChainWithBalances, ChainWithBeefy,
 		ChainWithGrandpa, ChainWithMessages, ChainWithTransactions, ChainWithUtilityPallet,
 		FullRuntimeUtilityPallet, MockedRuntimeUtilityPallet, Parachain, RelayChain, SignParam,
 		TransactionStatusOf, UnsignedTransaction, UtilityPallet,
 	},
 	client::{
 		is_ancient_block, ChainRuntimeVersion, Client, OpaqueGrandpaAuthoritiesSet,
-		SimpleRuntimeVersion, Subscription, ANCIENT_BLOCK_THRESHOLD,
+		SimpleRuntimeVersion, Subscription, TipStrategy, ANCIENT_BLOCK_THRESHOLD,
 	},
 	error::{Error, Result},
+	request_budget::RequestPriority,
 	rpc::{SubstrateBeefyFinalityClient, SubstrateFinalityClient, SubstrateGrandpaFinalityClient},
 	sync_header::SyncHeader,
 	transaction_tracker::TransactionTracker,
+	transport::{ProxyKind, ProxyParams, TlsParams, TransportError},
 };
 pub use bp_runtime::{
 	AccountIdOf, AccountPublicOf, BalanceOf, BlockNumberOf, Chain as ChainBase, HashOf, HeaderIdOf,
 	HeaderOf, IndexOf, Parachain as ParachainBase, SignatureOf, TransactionEra, TransactionEraOf,
 	UnderlyingChainProvider,
 };
+pub use mmr_rpc::LeavesProof as MmrLeavesProof;
+pub use sp_version::RuntimeVersion;
 
 /// Substrate-over-websocket connection params.
 #[derive(Debug, Clone)]
@@ -65,6 +72,23 @@ pub struct ConnectionParams {
 	pub secure: bool,
 	/// Defined chain runtime version
 	pub chain_runtime_version: ChainRuntimeVersion,
+	/// Never submit transactions to the node. Instead, they are validated and logged.
+	pub dry_run: bool,
+	/// Tip that is added to all signed transactions, submitted to the chain.
+	pub tip: TipStrategy,
+	/// Mortality period of signed transactions, submitted to the chain, if the transaction
+	/// builder hasn't selected the era itself. If `None`, such transactions are immortal.
+	pub transactions_mortality: Option<u32>,
+	/// Submit bridge transactions as unsigned transactions, validated by the bridge pallets,
+	/// instead of signing them by the relayer account.
+	pub unsigned_transactions: bool,
+	/// Custom TLS configuration of the secure websocket connection.
+	pub tls: TlsParams,
+	/// Proxy, used to connect to the node.
+	pub proxy: Option<ProxyParams>,
+	/// Maximal number of RPC requests per second, sent to the node. If `None`, the number of
+	/// requests is not limited.
+	pub max_requests_per_second: Option<NonZeroU32>,
 }
 
 impl Default for ConnectionParams {
@@ -74,6 +98,13 @@ impl Default for ConnectionParams {
 			port: 9944,
 			secure: false,
 			chain_runtime_version: ChainRuntimeVersion::Auto,
+			dry_run: false,
+			tip: TipStrategy::NoTip,
+			transactions_mortality: None,
+			unsigned_transactions: false,
+			tls: TlsParams::default(),
+			proxy: None,
+			max_requests_per_second: None,
 		}
 	}
 }
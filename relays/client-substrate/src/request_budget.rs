@@ -0,0 +1,204 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Budget of RPC requests to the single node.
+//!
+//! Public RPC nodes are usually rate-limited and may ban clients that are sending too many
+//! requests. The budget limits the number of requests per second, sent by all clones of the
+//! client. When the budget is exhausted, requests are waiting in the queue and requests with
+//! higher priority are sent first.
+
+use std::{
+	num::NonZeroU32,
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+/// Priority of the RPC request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RequestPriority {
+	/// Finality subscriptions. They have the highest priority, because the relay is useless
+	/// without them.
+	Finality = 0,
+	/// Generating proofs and submitting transactions.
+	Proof = 1,
+	/// All other (informational) queries.
+	Informational = 2,
+}
+
+/// Number of request priorities.
+const PRIORITIES: usize = 3;
+
+/// Budget of RPC requests to the single node.
+#[derive(Debug)]
+pub struct RequestBudget {
+	/// Maximal number of requests per second.
+	requests_per_second: NonZeroU32,
+	/// Budget state.
+	state: Mutex<BudgetState>,
+}
+
+/// State of the request budget.
+#[derive(Debug)]
+struct BudgetState {
+	/// Number of requests that may be sent right now.
+	available: f64,
+	/// When `available` has been updated last time.
+	refilled_at: Instant,
+	/// Number of requests of every priority, that are waiting for the budget.
+	waiting: [usize; PRIORITIES],
+}
+
+impl RequestBudget {
+	/// Create new request budget.
+	pub fn new(requests_per_second: NonZeroU32) -> Self {
+		RequestBudget {
+			requests_per_second,
+			state: Mutex::new(BudgetState {
+				available: requests_per_second.get() as f64,
+				refilled_at: Instant::now(),
+				waiting: [0; PRIORITIES],
+			}),
+		}
+	}
+
+	/// Wait until request with given priority may be sent.
+	pub async fn acquire(&self, priority: RequestPriority) {
+		let mut waiting_guard = None;
+		loop {
+			let delay = match self.try_acquire(priority, Instant::now()) {
+				Ok(()) => return,
+				Err(delay) => delay,
+			};
+
+			if waiting_guard.is_none() {
+				self.state().waiting[priority as usize] += 1;
+				waiting_guard = Some(WaitingGuard { budget: self, priority });
+			}
+
+			async_std::task::sleep(delay).await;
+		}
+	}
+
+	/// Try to spend the budget on the request with given priority. Returns the time we shall wait
+	/// before the next attempt if the budget is exhausted or there are waiting requests with
+	/// higher priority.
+	fn try_acquire(&self, priority: RequestPriority, now: Instant) -> Result<(), Duration> {
+		let requests_per_second = self.requests_per_second.get() as f64;
+		let mut state = self.state();
+
+		let elapsed = now.saturating_duration_since(state.refilled_at).as_secs_f64();
+		state.available =
+			(state.available + elapsed * requests_per_second).min(requests_per_second);
+		state.refilled_at = now;
+
+		let has_waiting_higher_priority_requests =
+			state.waiting[..priority as usize].iter().any(|waiting| *waiting != 0);
+		if state.available >= 1.0 && !has_waiting_higher_priority_requests {
+			state.available -= 1.0;
+			return Ok(())
+		}
+
+		let missing = (1.0 - state.available).max(0.0);
+		let delay = if missing > 0.0 { missing } else { 1.0 };
+		Err(Duration::from_secs_f64(delay / requests_per_second))
+	}
+
+	/// Lock and return the budget state.
+	fn state(&self) -> std::sync::MutexGuard<BudgetState> {
+		self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
+
+/// Removes the request from the waiting requests when dropped (even if the request future has
+/// been cancelled).
+struct WaitingGuard<'a> {
+	budget: &'a RequestBudget,
+	priority: RequestPriority,
+}
+
+impl Drop for WaitingGuard<'_> {
+	fn drop(&mut self) {
+		self.budget.state().waiting[self.priority as usize] -= 1;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn budget(requests_per_second: u32) -> RequestBudget {
+		RequestBudget::new(NonZeroU32::new(requests_per_second).unwrap())
+	}
+
+	#[test]
+	fn budget_is_refilled_over_time() {
+		let budget = budget(2);
+		let now = Instant::now();
+		assert_eq!(budget.try_acquire(RequestPriority::Informational, now), Ok(()));
+		assert_eq!(budget.try_acquire(RequestPriority::Informational, now), Ok(()));
+		assert_eq!(
+			budget.try_acquire(RequestPriority::Informational, now),
+			Err(Duration::from_millis(500))
+		);
+
+		let later = now + Duration::from_millis(500);
+		assert_eq!(budget.try_acquire(RequestPriority::Informational, later), Ok(()));
+	}
+
+	#[test]
+	fn budget_is_not_accumulated_above_limit() {
+		let budget = budget(2);
+		let later = Instant::now() + Duration::from_secs(60);
+		assert_eq!(budget.try_acquire(RequestPriority::Informational, later), Ok(()));
+		assert_eq!(budget.try_acquire(RequestPriority::Informational, later), Ok(()));
+		assert!(budget.try_acquire(RequestPriority::Informational, later).is_err());
+	}
+
+	#[test]
+	fn requests_with_higher_priority_are_sent_first() {
+		let budget = budget(1);
+		let now = Instant::now();
+		assert_eq!(budget.try_acquire(RequestPriority::Proof, now), Ok(()));
+		assert!(budget.try_acquire(RequestPriority::Proof, now).is_err());
+
+		// proof request is waiting for the budget
+		budget.state().waiting[RequestPriority::Proof as usize] += 1;
+		let guard = WaitingGuard { budget: &budget, priority: RequestPriority::Proof };
+
+		// => informational request is not sent, even if budget is available
+		let later = now + Duration::from_secs(1);
+		assert!(budget.try_acquire(RequestPriority::Informational, later).is_err());
+		// => but finality request is sent
+		assert_eq!(budget.try_acquire(RequestPriority::Finality, later), Ok(()));
+
+		// when proof request is sent, informational requests may be sent again
+		drop(guard);
+		let later = later + Duration::from_secs(1);
+		assert_eq!(budget.try_acquire(RequestPriority::Informational, later), Ok(()));
+	}
+
+	#[async_std::test]
+	async fn acquire_waits_for_budget() {
+		let budget = budget(10);
+		let started_at = Instant::now();
+		for _ in 0..11 {
+			budget.acquire(RequestPriority::Informational).await;
+		}
+		assert!(started_at.elapsed() >= Duration::from_millis(90));
+		assert_eq!(budget.state().waiting, [0; PRIORITIES]);
+	}
+}
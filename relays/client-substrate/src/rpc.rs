@@ -18,13 +18,14 @@
 
 use async_trait::async_trait;
 
-use crate::{Chain, ChainWithGrandpa, TransactionStatusOf};
+use crate::{Chain, ChainWithBeefy, ChainWithGrandpa, TransactionStatusOf};
 
 use jsonrpsee::{
 	core::{client::Subscription, RpcResult},
 	proc_macros::rpc,
 	ws_client::WsClient,
 };
+use mmr_rpc::LeavesProof;
 use pallet_transaction_payment_rpc_runtime_api::FeeDetails;
 use sc_rpc_api::{state::ReadProof, system::Health};
 use sp_core::{
@@ -60,6 +61,9 @@ pub(crate) trait SubstrateChain<C> {
 	/// Return signed block (with justifications) by its hash.
 	#[method(name = "getBlock")]
 	async fn block(&self, block_hash: Option<C::Hash>) -> RpcResult<C::SignedBlock>;
+	/// Subscribe to best finalized headers.
+	#[subscription(name = "subscribeFinalizedHeads", unsubscribe = "unsubscribeFinalizedHeads", item = C::Header)]
+	fn subscribe_finalized_heads(&self);
 }
 
 /// RPC methods of Substrate `author` namespace, that we are using.
@@ -79,9 +83,9 @@ pub(crate) trait SubstrateAuthor<C> {
 /// RPC methods of Substrate `state` namespace, that we are using.
 #[rpc(client, client_bounds(C: Chain), namespace = "state")]
 pub(crate) trait SubstrateState<C> {
-	/// Get current runtime version.
+	/// Get runtime version at given block (or at the best block if `None`).
 	#[method(name = "getRuntimeVersion")]
-	async fn runtime_version(&self) -> RpcResult<RuntimeVersion>;
+	async fn runtime_version(&self, at_block: Option<C::Hash>) -> RpcResult<RuntimeVersion>;
 	/// Call given runtime method.
 	#[method(name = "call")]
 	async fn call(
@@ -97,6 +101,15 @@ pub(crate) trait SubstrateState<C> {
 		key: StorageKey,
 		at_block: Option<C::Hash>,
 	) -> RpcResult<Option<StorageData>>;
+	/// Get (paged) keys of the runtime storage with given prefix.
+	#[method(name = "getKeysPaged")]
+	async fn storage_keys_paged(
+		&self,
+		prefix: StorageKey,
+		count: u32,
+		start_key: Option<StorageKey>,
+		at_block: Option<C::Hash>,
+	) -> RpcResult<Vec<StorageKey>>;
 	/// Get proof of the runtime storage value.
 	#[method(name = "getReadProof")]
 	async fn prove_storage(
@@ -119,6 +132,11 @@ pub(crate) trait SubstrateGrandpa<C> {
 	/// Subscribe to GRANDPA justifications.
 	#[subscription(name = "subscribeJustifications", unsubscribe = "unsubscribeJustifications", item = Bytes)]
 	fn subscribe_justifications(&self);
+	/// Prove finality of the given block.
+	///
+	/// The returned proof may finalize some descendant of the block.
+	#[method(name = "proveFinality")]
+	async fn prove_finality(&self, block: C::BlockNumber) -> RpcResult<Option<Bytes>>;
 }
 
 /// RPC finality methods of Substrate `grandpa` namespace, that we are using.
@@ -130,10 +148,12 @@ impl<C: ChainWithGrandpa> SubstrateFinalityClient<C> for SubstrateGrandpaFinalit
 	}
 }
 
-// TODO: Use `ChainWithBeefy` instead of `Chain` after #1606 is merged
 /// RPC methods of Substrate `beefy` namespace, that we are using.
-#[rpc(client, client_bounds(C: Chain), namespace = "beefy")]
+#[rpc(client, client_bounds(C: ChainWithBeefy), namespace = "beefy")]
 pub(crate) trait SubstrateBeefy<C> {
+	/// Return hash of the best block, finalized by BEEFY.
+	#[method(name = "getFinalizedHead")]
+	async fn finalized_head(&self) -> RpcResult<C::Hash>;
 	/// Subscribe to BEEFY justifications.
 	#[subscription(name = "subscribeJustifications", unsubscribe = "unsubscribeJustifications", item = Bytes)]
 	fn subscribe_justifications(&self);
@@ -141,14 +161,26 @@ pub(crate) trait SubstrateBeefy<C> {
 
 /// RPC finality methods of Substrate `beefy` namespace, that we are using.
 pub struct SubstrateBeefyFinalityClient;
-// TODO: Use `ChainWithBeefy` instead of `Chain` after #1606 is merged
 #[async_trait]
-impl<C: Chain> SubstrateFinalityClient<C> for SubstrateBeefyFinalityClient {
+impl<C: ChainWithBeefy> SubstrateFinalityClient<C> for SubstrateBeefyFinalityClient {
 	async fn subscribe_justifications(client: &WsClient) -> RpcResult<Subscription<Bytes>> {
 		SubstrateBeefyClient::<C>::subscribe_justifications(client).await
 	}
 }
 
+/// RPC methods of Substrate `mmr` namespace, that we are using.
+#[rpc(client, client_bounds(C: ChainWithBeefy), namespace = "mmr")]
+pub(crate) trait SubstrateMmr<C> {
+	/// Generate MMR proof for the leaves, inserted at given blocks.
+	#[method(name = "generateProof")]
+	async fn generate_proof(
+		&self,
+		block_numbers: Vec<C::BlockNumber>,
+		best_known_block_number: Option<C::BlockNumber>,
+		at_block: Option<C::Hash>,
+	) -> RpcResult<LeavesProof<C::Hash>>;
+}
+
 /// RPC methods of Substrate `system` frame pallet, that we are using.
 #[rpc(client, client_bounds(C: Chain), namespace = "system")]
 pub(crate) trait SubstrateFrameSystem<C> {
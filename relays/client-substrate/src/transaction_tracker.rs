@@ -16,7 +16,9 @@
 
 //! Helper for tracking transaction invalidation events.
 
-use crate::{Chain, Client, Error, HashOf, HeaderIdOf, Subscription, TransactionStatusOf};
+use crate::{
+	BalanceOf, Chain, Client, Error, HashOf, HeaderIdOf, Subscription, TransactionStatusOf,
+};
 
 use async_trait::async_trait;
 use futures::{future::Either, Future, FutureExt, Stream, StreamExt};
@@ -63,6 +65,8 @@ pub struct TransactionTracker<C: Chain, E> {
 	transaction_hash: HashOf<C>,
 	stall_timeout: Duration,
 	subscription: Subscription<TransactionStatusOf<C>>,
+	estimated_fee: Option<BalanceOf<C>>,
+	dry_run: bool,
 }
 
 impl<C: Chain, E: Environment<C>> TransactionTracker<C, E> {
@@ -73,7 +77,47 @@ impl<C: Chain, E: Environment<C>> TransactionTracker<C, E> {
 		transaction_hash: HashOf<C>,
 		subscription: Subscription<TransactionStatusOf<C>>,
 	) -> Self {
-		Self { environment, stall_timeout, transaction_hash, subscription }
+		Self {
+			environment,
+			stall_timeout,
+			transaction_hash,
+			subscription,
+			estimated_fee: None,
+			dry_run: false,
+		}
+	}
+
+	/// Create tracker of the transaction that is never submitted, because the client runs in
+	/// the dry-run mode. The tracker reports the `TrackedTransactionStatus::DryRun` status
+	/// immediately.
+	pub fn new_dry_run(environment: E, transaction_hash: HashOf<C>) -> Self {
+		let (_, receiver) = futures::channel::mpsc::channel(0);
+		Self {
+			environment,
+			stall_timeout: Duration::ZERO,
+			transaction_hash,
+			subscription: Subscription(async_std::sync::Mutex::new(receiver)),
+			estimated_fee: None,
+			dry_run: true,
+		}
+	}
+
+	/// Set estimated fee (including tip) of the tracked transaction.
+	pub fn with_estimated_fee(self, estimated_fee: Option<BalanceOf<C>>) -> Self {
+		Self { estimated_fee, ..self }
+	}
+
+	/// Returns hash of the tracked transaction.
+	pub fn transaction_hash(&self) -> HashOf<C> {
+		self.transaction_hash
+	}
+
+	/// Returns estimated fee (including tip) of the tracked transaction, if known.
+	///
+	/// The fee is estimated using the `TransactionPaymentApi_query_info` call before the
+	/// transaction is included into the block, so the actual fee may be different.
+	pub fn estimated_fee(&self) -> Option<BalanceOf<C>> {
+		self.estimated_fee
 	}
 
 	/// Wait for final transaction status and return it along with last known internal invalidation
@@ -133,6 +177,10 @@ impl<C: Chain, E: Environment<C>> relay_utils::TransactionTracker for Transactio
 	type HeaderId = HeaderIdOf<C>;
 
 	async fn wait(self) -> TrackedTransactionStatus<HeaderIdOf<C>> {
+		if self.dry_run {
+			return TrackedTransactionStatus::DryRun
+		}
+
 		let wait_for_stall_timeout = async_std::task::sleep(self.stall_timeout).shared();
 		let wait_for_stall_timeout_rest = wait_for_stall_timeout.clone();
 		self.do_wait(wait_for_stall_timeout, wait_for_stall_timeout_rest).await.0
@@ -444,4 +492,17 @@ mod tests {
 
 		assert_eq!(wait_result, Some((TrackedTransactionStatus::Lost, None)));
 	}
+
+	#[async_std::test]
+	async fn dry_run_transaction_is_reported_immediately() {
+		let tx_tracker = TransactionTracker::<TestChain, TestEnvironment>::new_dry_run(
+			TestEnvironment(Ok(HeaderId(0, Default::default()))),
+			Default::default(),
+		);
+
+		assert_eq!(
+			relay_utils::TransactionTracker::wait(tx_tracker).now_or_never(),
+			Some(TrackedTransactionStatus::DryRun),
+		);
+	}
 }
@@ -30,6 +30,7 @@ use num_traits::{One, Saturating};
 use relay_utils::{
 	metrics::MetricsParams, relay_loop::Client as RelayClient, retry_backoff, FailedClient,
 	HeaderId, MaybeConnectionError, TrackedTransactionStatus, TransactionTracker,
+	UniqueSaturatedInto,
 };
 use std::{
 	fmt::Debug,
@@ -80,6 +81,16 @@ pub trait SourceClient<P: FinalitySyncPipeline>: RelayClient {
 		number: P::Number,
 	) -> Result<(P::Header, Option<P::FinalityProof>), Self::Error>;
 
+	/// Generate finality proof of the canonical header with given number or of some of its
+	/// descendants.
+	///
+	/// It is used as a fallback when the finality proofs stream has missed proofs that we need.
+	/// Returns `None` if the source is unable to generate the proof.
+	async fn prove_finality(
+		&self,
+		number: P::Number,
+	) -> Result<Option<P::FinalityProof>, Self::Error>;
+
 	/// Subscribe to new finality proofs.
 	async fn finality_proofs(&self) -> Result<Self::FinalityProofsStream, Self::Error>;
 }
@@ -167,8 +178,10 @@ where
 {
 	fn fail_if_connection_error(&self) -> Result<(), FailedClient> {
 		match *self {
-			Error::Source(ref error) if error.is_connection_error() => Err(FailedClient::Source),
-			Error::Target(ref error) if error.is_connection_error() => Err(FailedClient::Target),
+			Error::Source(ref error) if error.is_connection_error() || error.is_fatal_error() =>
+				Err(FailedClient::Source),
+			Error::Target(ref error) if error.is_connection_error() || error.is_fatal_error() =>
+				Err(FailedClient::Target),
 			_ => Ok(()),
 		}
 	}
@@ -232,16 +245,27 @@ impl<Tracker: TransactionTracker, Number: Debug + PartialOrd> Transaction<Tracke
 					})
 			},
 			TrackedTransactionStatus::Lost => Err("transaction failed".to_string()),
+			// the transaction is never submitted, but the loop won't submit other headers until
+			// the target chain has `submitted_header_number`
+			TrackedTransactionStatus::DryRun => Ok(()),
 		}
 	}
 }
 
+/// If the finality proofs stream has not yielded any proofs for this period while the source
+/// chain keeps finalizing headers, we consider the subscription dead and restart it.
+pub(crate) const MAX_FINALITY_PROOFS_STREAM_SILENCE: Duration = Duration::from_secs(5 * 60);
+
 /// Finality proofs stream that may be restarted.
 pub(crate) struct RestartableFinalityProofsStream<S> {
 	/// Flag that the stream needs to be restarted.
 	pub(crate) needs_restart: bool,
 	/// The stream itself.
 	stream: Pin<Box<S>>,
+	/// Time when the stream has yielded its last item (or has been started).
+	last_item_at: Instant,
+	/// Best finalized source header number, seen after the stream has yielded its last item.
+	best_source_number_after_last_item: Option<u64>,
 }
 
 impl<S: Stream> RestartableFinalityProofsStream<S> {
@@ -275,13 +299,19 @@ impl<S: Stream> RestartableFinalityProofsStream<S> {
 
 			self.needs_restart = false;
 			self.stream = Box::pin(Self::create_raw_stream(source_client).await?);
+			self.last_item_at = Instant::now();
+			self.best_source_number_after_last_item = None;
 		}
 		Ok(())
 	}
 
 	pub fn next(&mut self) -> Option<S::Item> {
 		match self.stream.next().now_or_never() {
-			Some(Some(finality_proof)) => Some(finality_proof),
+			Some(Some(finality_proof)) => {
+				self.last_item_at = Instant::now();
+				self.best_source_number_after_last_item = None;
+				Some(finality_proof)
+			},
 			Some(None) => {
 				self.needs_restart = true;
 				None
@@ -289,11 +319,50 @@ impl<S: Stream> RestartableFinalityProofsStream<S> {
 			None => None,
 		}
 	}
+
+	/// Schedule stream restart if it looks dead.
+	///
+	/// The subscription may silently die (e.g. when the node drops it without closing the
+	/// connection). We treat the stream as dead if it has not yielded anything for the
+	/// `max_silence` period, while the source chain has finalized new headers. This method
+	/// must be called after all available items have been read from the stream.
+	pub fn schedule_restart_if_silent(
+		&mut self,
+		best_number_at_source: u64,
+		max_silence: Duration,
+	) {
+		let best_source_number_after_last_item = match self.best_source_number_after_last_item {
+			Some(best_source_number_after_last_item) => best_source_number_after_last_item,
+			None => {
+				self.best_source_number_after_last_item = Some(best_number_at_source);
+				return
+			},
+		};
+
+		let silence = self.last_item_at.elapsed();
+		if best_number_at_source > best_source_number_after_last_item && silence >= max_silence {
+			log::warn!(
+				target: "bridge",
+				"Finality proofs stream has not yielded anything for {}s while source chain has \
+				finalized headers {}..={}. Scheduling stream restart",
+				silence.as_secs(),
+				best_source_number_after_last_item + 1,
+				best_number_at_source,
+			);
+
+			self.needs_restart = true;
+		}
+	}
 }
 
 impl<S> From<S> for RestartableFinalityProofsStream<S> {
 	fn from(stream: S) -> Self {
-		RestartableFinalityProofsStream { needs_restart: false, stream: Box::pin(stream) }
+		RestartableFinalityProofsStream {
+			needs_restart: false,
+			stream: Box::pin(stream),
+			last_item_at: Instant::now(),
+			best_source_number_after_last_item: None,
+		}
 	}
 }
 
@@ -329,6 +398,7 @@ pub(crate) async fn run_until_connection_lost<P: FinalitySyncPipeline>(
 	let mut progress = (Instant::now(), None);
 	let mut retry_backoff = retry_backoff();
 	let mut last_submitted_header_number = None;
+	let health_component = format!("{} -> {} finality sync", P::SOURCE_NAME, P::TARGET_NAME);
 
 	loop {
 		// run loop iteration
@@ -352,10 +422,12 @@ pub(crate) async fn run_until_connection_lost<P: FinalitySyncPipeline>(
 				last_submitted_header_number = Some(updated_transaction.submitted_header_number);
 				last_transaction_tracker.set(updated_transaction.track(&target_client).fuse());
 				retry_backoff.reset();
+				relay_utils::health::report_healthy(health_component.clone(), "syncing");
 				sync_params.tick
 			},
 			Ok(None) => {
 				retry_backoff.reset();
+				relay_utils::health::report_healthy(health_component.clone(), "syncing");
 				sync_params.tick
 			},
 			Err(error) => {
@@ -370,6 +442,10 @@ pub(crate) async fn run_until_connection_lost<P: FinalitySyncPipeline>(
 		select! {
 			transaction_result = last_transaction_tracker => {
 				transaction_result.map_err(|e| {
+					relay_utils::health::report_unhealthy(
+						health_component.clone(),
+						format!("stalled: {e}"),
+					);
 					log::error!(
 						target: "bridge",
 						"Finality synchronization from {} to {} has stalled with error: {}. Going to restart",
@@ -512,12 +588,24 @@ where
 	// => even if we have already selected some header and its persistent finality proof,
 	// we may try to select better header by reading non-persistent proofs from the stream
 	read_finality_proofs_from_stream::<P, _>(finality_proofs_stream, recent_finality_proofs);
+	finality_proofs_stream.schedule_restart_if_silent(
+		best_number_at_source.unique_saturated_into(),
+		MAX_FINALITY_PROOFS_STREAM_SILENCE,
+	);
 	selected_finality_proof = select_better_recent_finality_proof::<P>(
 		recent_finality_proofs,
 		&mut unjustified_headers,
 		selected_finality_proof,
 	);
 
+	// the stream is allowed to miss proofs, so if we have not found any proof yet, let's ask
+	// the source to generate it. Otherwise we'll be stalled until the next mandatory header
+	if selected_finality_proof.is_none() && !unjustified_headers.is_empty() {
+		selected_finality_proof =
+			select_generated_finality_proof::<P, SC, TC>(source_client, &mut unjustified_headers)
+				.await?;
+	}
+
 	// remove obsolete 'recent' finality proofs + keep its size under certain limit
 	let oldest_finality_proof_to_keep = selected_finality_proof
 		.as_ref()
@@ -716,6 +804,59 @@ pub(crate) fn select_better_recent_finality_proof<P: FinalitySyncPipeline>(
 	Some((selected_header, finality_proof.clone()))
 }
 
+/// Ask the source client to generate finality proof for the first unjustified header.
+///
+/// The generated proof may be the proof of any descendant of this header. It is selected only
+/// if its target header is one of unjustified headers.
+pub(crate) async fn select_generated_finality_proof<P, SC, TC>(
+	source_client: &SC,
+	unjustified_headers: &mut UnjustifiedHeaders<P::Header>,
+) -> Result<Option<(P::Header, P::FinalityProof)>, Error<P, SC::Error, TC::Error>>
+where
+	P: FinalitySyncPipeline,
+	SC: SourceClient<P>,
+	TC: TargetClient<P>,
+{
+	let first_unjustified_header_number = match unjustified_headers.first() {
+		Some(header) => header.number(),
+		None => return Ok(None),
+	};
+	let finality_proof = match source_client
+		.prove_finality(first_unjustified_header_number)
+		.await
+		.map_err(Error::Source)?
+	{
+		Some(finality_proof) => finality_proof,
+		None => return Ok(None),
+	};
+
+	let proved_header_number = finality_proof.target_header_number();
+	let proved_header_position = match unjustified_headers
+		.binary_search_by_key(&proved_header_number, |header| header.number())
+	{
+		Ok(proved_header_position) => proved_header_position,
+		Err(_) => {
+			log::trace!(
+				target: "bridge",
+				"Ignoring generated {} finality proof for header {:?}. Headers range: [{:?}; {:?}]",
+				P::SOURCE_NAME,
+				proved_header_number,
+				first_unjustified_header_number,
+				unjustified_headers.last().map(|header| header.number()),
+			);
+			return Ok(None)
+		},
+	};
+
+	log::trace!(
+		target: "bridge",
+		"Selected generated {} finality proof for header {:?}",
+		P::SOURCE_NAME,
+		proved_header_number,
+	);
+	Ok(Some((unjustified_headers.swap_remove(proved_header_position), finality_proof)))
+}
+
 pub(crate) fn prune_recent_finality_proofs<P: FinalitySyncPipeline>(
 	justified_header_number: P::Number,
 	recent_finality_proofs: &mut FinalityProofs<P>,
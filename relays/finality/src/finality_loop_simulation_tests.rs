@@ -0,0 +1,651 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Simulation tests for finality synchronization loop.
+//!
+//! The source chain and the bridge pallet at the target chain are simulated using the logical
+//! clock, which advances on every client call. Every step, the source chain produces new
+//! finalized header and the target chain imports all submitted headers that are ready for
+//! inclusion. The [`Script`] may delay transactions inclusion, drop transactions and revert
+//! non-finalized target blocks.
+//!
+//! Every test checks that the target chain eventually knows the best source header and all
+//! mandatory headers (liveness) and that the loop never submits headers that are already
+//! known to the target chain (non-duplication).
+//!
+//! With the `fault-injection` feature, the simulated clients may also fail RPC calls with
+//! connection errors, silently stop yielding finality proofs from the subscription and return
+//! invalid justifications. Those faults are driven by the seeded
+//! [`relay_utils::fault_injection::FaultInjector`], so every soak test is reproducible.
+
+#![cfg(test)]
+
+use crate::{
+	finality_loop::{run_until_connection_lost, FinalitySyncParams, SourceClient, TargetClient},
+	finality_loop_tests::{
+		TestError, TestFinalityProof, TestFinalitySyncPipeline, TestHash, TestNumber,
+		TestSourceHeader,
+	},
+};
+
+use async_trait::async_trait;
+use futures::{channel::mpsc::UnboundedSender, task::Poll, FutureExt, Stream, StreamExt};
+use parking_lot::Mutex;
+#[cfg(feature = "fault-injection")]
+use relay_utils::fault_injection::{FaultInjectionParams, FaultInjectionStats, FaultInjector};
+use relay_utils::{
+	relay_loop::Client as RelayClient, FailedClient, HeaderId, TrackedTransactionStatus,
+	TransactionTracker,
+};
+use std::{pin::Pin, sync::Arc, task::Waker, time::Duration};
+
+/// Simulation step (logical time).
+type Step = u64;
+
+/// The simulation script.
+#[derive(Clone, Debug)]
+struct Script {
+	/// Number of headers that are produced by the source chain. Single header is produced at
+	/// every step until all headers are produced.
+	headers_count: TestNumber,
+	/// Numbers of mandatory source headers. All mandatory headers have persistent finality
+	/// proofs.
+	mandatory_headers: Vec<TestNumber>,
+	/// Every header with number that is divisible by this value has persistent finality proof.
+	persistent_proofs_interval: TestNumber,
+	/// Finality proof of every header with number that is divisible by this value is yielded
+	/// by the finality proofs stream.
+	stream_proofs_interval: TestNumber,
+	/// Number of steps between transaction submission and its inclusion. The `i`-th
+	/// transaction is delayed by `transaction_latencies[i % len]`.
+	transaction_latencies: Vec<Step>,
+	/// Number of steps between transaction inclusion and its finalization.
+	finality_lag: Step,
+	/// Scheduled target chain reorgs: `(step, depth)`. Every reorg reverts at most `depth`
+	/// non-finalized headers imports.
+	reorgs: Vec<(Step, usize)>,
+	/// Indices of transactions that are dropped instead of being included.
+	dropped_transactions: Vec<usize>,
+	/// Maximal number of steps. If target chain is not synced within this number of steps,
+	/// the test fails.
+	max_steps: Step,
+	/// Faults that are injected into simulated clients.
+	#[cfg(feature = "fault-injection")]
+	faults: FaultInjectionParams,
+}
+
+impl Default for Script {
+	fn default() -> Self {
+		Script {
+			headers_count: 64,
+			mandatory_headers: vec![7, 8, 30, 31, 50],
+			persistent_proofs_interval: 10,
+			stream_proofs_interval: 3,
+			transaction_latencies: vec![1],
+			finality_lag: 0,
+			reorgs: Vec::new(),
+			dropped_transactions: Vec::new(),
+			max_steps: 10_000,
+			#[cfg(feature = "fault-injection")]
+			faults: Default::default(),
+		}
+	}
+}
+
+/// Reason of transaction rejection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Rejection {
+	/// Header is already known to the target chain.
+	Duplicate,
+	/// Some mandatory header between the best known header and submitted header is missing.
+	MissingMandatoryHeader,
+	/// Submitted finality proof doesn't match the submitted header.
+	InvalidJustification,
+}
+
+/// Transaction status.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TransactionStatus {
+	/// Transaction is in the pool.
+	Pending,
+	/// Transaction is included in the non-finalized block at given step.
+	Included(Step),
+	/// Transaction is included in the finalized block.
+	Finalized,
+	/// Transaction is dropped from the pool or its block has been reverted.
+	Lost,
+}
+
+/// Transaction, submitted to the target chain.
+#[derive(Clone, Debug)]
+struct SubmittedTransaction {
+	/// Submitted header.
+	header: TestSourceHeader,
+	/// Submitted finality proof.
+	proof: TestFinalityProof,
+	/// Step, at which the transaction becomes ready for inclusion.
+	include_at: Step,
+	/// Whether the transaction is dropped instead of being included.
+	is_dropped: bool,
+	/// Current transaction status.
+	status: TransactionStatus,
+	/// Rejection reason, if transaction has been included, but has failed.
+	rejection: Option<Rejection>,
+}
+
+/// Simulated source chain and bridge pallet at the target chain.
+#[derive(Clone, Debug)]
+struct SimulatedChains {
+	/// The simulation script.
+	script: Script,
+	/// Current step.
+	step: Step,
+	/// Finalized source headers. Header with number `n` is stored at `n`-th position.
+	source_headers: Vec<(TestSourceHeader, Option<TestFinalityProof>)>,
+	/// Sender of the current finality proofs subscription.
+	finality_proofs_sender: Option<UnboundedSender<TestFinalityProof>>,
+	/// Whether the current finality proofs subscription has gone stale. Stale subscription is
+	/// not closed, but it never yields new finality proofs.
+	is_finality_proofs_stream_stale: bool,
+	/// Source headers, imported by the target chain: `(header, index of the transaction)`.
+	target_headers: Vec<(TestSourceHeader, Option<usize>)>,
+	/// All transactions, submitted to the target chain.
+	transactions: Vec<SubmittedTransaction>,
+	/// Tasks that are waiting for transaction status updates.
+	wakers: Vec<Waker>,
+	/// Exit signal sender. It is fired when the target chain knows the best source header.
+	exit_sender: Option<UnboundedSender<()>>,
+	/// Number of clients reconnects.
+	reconnects: usize,
+	/// Injector of client faults.
+	#[cfg(feature = "fault-injection")]
+	fault_injector: FaultInjector,
+}
+
+impl SimulatedChains {
+	fn new(script: Script, exit_sender: UnboundedSender<()>) -> Self {
+		let genesis = TestSourceHeader(false, 0, 0);
+		SimulatedChains {
+			#[cfg(feature = "fault-injection")]
+			fault_injector: FaultInjector::new(script.faults.clone()),
+			script,
+			step: 0,
+			source_headers: vec![(genesis.clone(), None)],
+			finality_proofs_sender: None,
+			is_finality_proofs_stream_stale: false,
+			target_headers: vec![(genesis, None)],
+			transactions: Vec::new(),
+			wakers: Vec::new(),
+			exit_sender: Some(exit_sender),
+			reconnects: 0,
+		}
+	}
+
+	fn best_target_header(&self) -> &TestSourceHeader {
+		&self.target_headers.last().expect("target always knows genesis header; qed").0
+	}
+
+	/// Advance the logical clock.
+	fn advance(&mut self) {
+		self.step += 1;
+		assert!(
+			self.step <= self.script.max_steps,
+			"Target chain is not synced in {} steps. Best header at target: {:?}",
+			self.script.max_steps,
+			self.best_target_header(),
+		);
+
+		let step = self.step;
+		let reorgs = self.script.reorgs.iter().filter(|(at, _)| *at == step);
+		for depth in reorgs.map(|(_, depth)| *depth).collect::<Vec<_>>() {
+			self.revert_target_imports(depth);
+		}
+
+		self.produce_source_header();
+		self.import_target_headers();
+
+		let finality_lag = self.script.finality_lag;
+		for transaction in &mut self.transactions {
+			if let TransactionStatus::Included(at) = transaction.status {
+				if at + finality_lag <= step {
+					transaction.status = TransactionStatus::Finalized;
+				}
+			}
+		}
+
+		self.wakers.drain(..).for_each(Waker::wake);
+
+		if self.best_target_header().1 == self.script.headers_count {
+			if let Some(exit_sender) = self.exit_sender.take() {
+				exit_sender.unbounded_send(()).unwrap();
+			}
+		}
+	}
+
+	/// Produce and finalize next source header.
+	fn produce_source_header(&mut self) {
+		let number = self.source_headers.len() as TestNumber;
+		if number > self.script.headers_count {
+			return
+		}
+
+		let is_mandatory = self.script.mandatory_headers.contains(&number);
+		let has_persistent_proof = is_mandatory ||
+			number % self.script.persistent_proofs_interval == 0 ||
+			number == self.script.headers_count;
+		let persistent_proof = has_persistent_proof.then_some(TestFinalityProof(number));
+		self.source_headers
+			.push((TestSourceHeader(is_mandatory, number, number), persistent_proof));
+
+		let is_streamed_proof = number % self.script.stream_proofs_interval == 0;
+		if is_streamed_proof && !self.is_finality_proofs_stream_stale {
+			self.is_finality_proofs_stream_stale = self.inject_stale_subscription();
+		}
+		if is_streamed_proof && !self.is_finality_proofs_stream_stale {
+			if let Some(ref finality_proofs_sender) = self.finality_proofs_sender {
+				let _ = finality_proofs_sender.unbounded_send(TestFinalityProof(number));
+			}
+		}
+	}
+
+	/// Import all headers that are ready for inclusion at the target chain.
+	fn import_target_headers(&mut self) {
+		let step = self.step;
+		for index in 0..self.transactions.len() {
+			let transaction = &self.transactions[index];
+			if transaction.status != TransactionStatus::Pending || transaction.include_at > step {
+				continue
+			}
+			if transaction.is_dropped {
+				self.transactions[index].status = TransactionStatus::Lost;
+				continue
+			}
+
+			let header = transaction.header.clone();
+			let best_number = self.best_target_header().1;
+			let rejection = if header.1 <= best_number {
+				Some(Rejection::Duplicate)
+			} else if transaction.proof.0 != header.1 {
+				Some(Rejection::InvalidJustification)
+			} else if self
+				.script
+				.mandatory_headers
+				.iter()
+				.any(|mandatory| *mandatory > best_number && *mandatory < header.1)
+			{
+				Some(Rejection::MissingMandatoryHeader)
+			} else {
+				self.target_headers.push((header, Some(index)));
+				None
+			};
+
+			let transaction = &mut self.transactions[index];
+			transaction.rejection = rejection;
+			transaction.status = TransactionStatus::Included(step);
+		}
+	}
+
+	/// Revert at most `depth` non-finalized header imports. Reverted transactions are lost.
+	fn revert_target_imports(&mut self, depth: usize) {
+		for _ in 0..depth {
+			let last_import = self.target_headers.last().and_then(|(_, index)| *index);
+			let index = match last_import {
+				Some(index) if self.transactions[index].status != TransactionStatus::Finalized =>
+					index,
+				_ => break,
+			};
+
+			self.target_headers.pop();
+			self.transactions[index].status = TransactionStatus::Lost;
+		}
+	}
+
+	/// Returns all included transactions that have been rejected with given reason.
+	fn rejected_headers(&self, rejection: Rejection) -> Vec<TestSourceHeader> {
+		self.transactions
+			.iter()
+			.filter(|transaction| transaction.rejection == Some(rejection))
+			.map(|transaction| transaction.header.clone())
+			.collect()
+	}
+}
+
+#[cfg(feature = "fault-injection")]
+impl SimulatedChains {
+	/// Returns connection error if the RPC call needs to fail.
+	fn inject_rpc_failure(&mut self) -> Result<(), TestError> {
+		if self.fault_injector.fail_rpc_call() {
+			return Err(TestError::Connection)
+		}
+		Ok(())
+	}
+
+	/// Returns true if the finality proofs subscription needs to go stale.
+	fn inject_stale_subscription(&mut self) -> bool {
+		self.fault_injector.stall_subscription()
+	}
+
+	/// Returns true if the returned finality proof needs to be invalid.
+	fn inject_invalid_justification(&mut self) -> bool {
+		self.fault_injector.corrupt_justification()
+	}
+}
+
+#[cfg(not(feature = "fault-injection"))]
+impl SimulatedChains {
+	fn inject_rpc_failure(&mut self) -> Result<(), TestError> {
+		Ok(())
+	}
+
+	fn inject_stale_subscription(&mut self) -> bool {
+		false
+	}
+
+	fn inject_invalid_justification(&mut self) -> bool {
+		false
+	}
+}
+
+/// Transaction tracker of the simulated target chain.
+struct SimulatedTransactionTracker {
+	chains: Arc<Mutex<SimulatedChains>>,
+	index: usize,
+}
+
+#[async_trait]
+impl TransactionTracker for SimulatedTransactionTracker {
+	type HeaderId = HeaderId<TestHash, TestNumber>;
+
+	async fn wait(self) -> TrackedTransactionStatus<HeaderId<TestHash, TestNumber>> {
+		futures::future::poll_fn(|cx| {
+			let mut chains = self.chains.lock();
+			match chains.transactions[self.index].status {
+				TransactionStatus::Finalized =>
+					Poll::Ready(TrackedTransactionStatus::Finalized(Default::default())),
+				TransactionStatus::Lost => Poll::Ready(TrackedTransactionStatus::Lost),
+				TransactionStatus::Pending | TransactionStatus::Included(_) => {
+					chains.wakers.push(cx.waker().clone());
+					Poll::Pending
+				},
+			}
+		})
+		.await
+	}
+}
+
+#[derive(Clone)]
+struct SimulatedSourceClient {
+	chains: Arc<Mutex<SimulatedChains>>,
+}
+
+#[async_trait]
+impl RelayClient for SimulatedSourceClient {
+	type Error = TestError;
+
+	async fn reconnect(&mut self) -> Result<(), TestError> {
+		self.chains.lock().reconnects += 1;
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl SourceClient<TestFinalitySyncPipeline> for SimulatedSourceClient {
+	type FinalityProofsStream = Pin<Box<dyn Stream<Item = TestFinalityProof> + 'static + Send>>;
+
+	async fn best_finalized_block_number(&self) -> Result<TestNumber, TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		Ok(chains.source_headers.len() as TestNumber - 1)
+	}
+
+	async fn header_and_finality_proof(
+		&self,
+		number: TestNumber,
+	) -> Result<(TestSourceHeader, Option<TestFinalityProof>), TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		let (header, proof) = chains.source_headers[number as usize].clone();
+		let proof = match proof {
+			Some(_) if chains.inject_invalid_justification() =>
+				Some(TestFinalityProof(TestNumber::MAX)),
+			proof => proof,
+		};
+		Ok((header, proof))
+	}
+
+	async fn prove_finality(
+		&self,
+		_number: TestNumber,
+	) -> Result<Option<TestFinalityProof>, TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		Ok(None)
+	}
+
+	async fn finality_proofs(&self) -> Result<Self::FinalityProofsStream, TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		let (finality_proofs_sender, finality_proofs_receiver) =
+			futures::channel::mpsc::unbounded();
+		chains.finality_proofs_sender = Some(finality_proofs_sender);
+		chains.is_finality_proofs_stream_stale = false;
+		Ok(finality_proofs_receiver.boxed())
+	}
+}
+
+#[derive(Clone)]
+struct SimulatedTargetClient {
+	chains: Arc<Mutex<SimulatedChains>>,
+}
+
+#[async_trait]
+impl RelayClient for SimulatedTargetClient {
+	type Error = TestError;
+
+	async fn reconnect(&mut self) -> Result<(), TestError> {
+		self.chains.lock().reconnects += 1;
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl TargetClient<TestFinalitySyncPipeline> for SimulatedTargetClient {
+	type TransactionTracker = SimulatedTransactionTracker;
+
+	async fn best_finalized_source_block_id(
+		&self,
+	) -> Result<HeaderId<TestHash, TestNumber>, TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		let best_header = chains.best_target_header();
+		Ok(HeaderId(best_header.1, best_header.2))
+	}
+
+	async fn submit_finality_proof(
+		&self,
+		header: TestSourceHeader,
+		proof: TestFinalityProof,
+	) -> Result<SimulatedTransactionTracker, TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		let index = chains.transactions.len();
+		let latencies = &chains.script.transaction_latencies;
+		let include_at = chains.step + latencies[index % latencies.len()];
+		let is_dropped = chains.script.dropped_transactions.contains(&index);
+		chains.transactions.push(SubmittedTransaction {
+			header,
+			proof,
+			include_at,
+			is_dropped,
+			status: TransactionStatus::Pending,
+			rejection: None,
+		});
+		Ok(SimulatedTransactionTracker { chains: self.chains.clone(), index })
+	}
+}
+
+/// Run finality synchronization loop until the target chain knows the best source header.
+///
+/// The loop is restarted every time it has stalled or has lost connection to one of clients,
+/// the same way it is done by the relay loop.
+fn run_simulation(script: Script) -> SimulatedChains {
+	let (exit_sender, exit_receiver) = futures::channel::mpsc::unbounded();
+	let exit_signal = exit_receiver.into_future().map(|(_, _)| ()).shared();
+	let chains = Arc::new(Mutex::new(SimulatedChains::new(script, exit_sender)));
+	let mut source_client = SimulatedSourceClient { chains: chains.clone() };
+	let mut target_client = SimulatedTargetClient { chains: chains.clone() };
+	loop {
+		let result = async_std::task::block_on(run_until_connection_lost(
+			source_client.clone(),
+			target_client.clone(),
+			FinalitySyncParams {
+				tick: Duration::from_secs(0),
+				recent_finality_proofs_limit: 1024,
+				stall_timeout: Duration::from_secs(1),
+				only_mandatory_headers: false,
+			},
+			None,
+			exit_signal.clone(),
+		));
+		match result {
+			Ok(()) => break,
+			Err(FailedClient::Both) => continue,
+			Err(FailedClient::Source) =>
+				async_std::task::block_on(source_client.reconnect()).unwrap(),
+			Err(FailedClient::Target) =>
+				async_std::task::block_on(target_client.reconnect()).unwrap(),
+		}
+	}
+
+	let chains = chains.lock().clone();
+	let imported_headers =
+		chains.target_headers.iter().map(|(header, _)| header.1).collect::<Vec<_>>();
+	assert_eq!(chains.best_target_header().1, chains.script.headers_count);
+	for mandatory_header in &chains.script.mandatory_headers {
+		assert!(imported_headers.contains(mandatory_header));
+	}
+	// after reconnect, the loop forgets about submitted transactions and it may submit the same
+	// header again, while previous transaction is still in the pool
+	if chains.reconnects == 0 {
+		assert_eq!(chains.rejected_headers(Rejection::Duplicate), vec![]);
+	}
+	chains
+}
+
+#[test]
+fn simulated_finality_loop_syncs_all_headers() {
+	let chains = run_simulation(Script {
+		transaction_latencies: vec![1, 3, 2],
+		finality_lag: 2,
+		..Default::default()
+	});
+
+	// without reorgs there's no way for submitted header to be rejected
+	assert_eq!(chains.rejected_headers(Rejection::MissingMandatoryHeader), vec![]);
+}
+
+#[test]
+fn simulated_finality_loop_resubmits_after_transactions_are_dropped() {
+	let chains = run_simulation(Script {
+		transaction_latencies: vec![2],
+		dropped_transactions: vec![0, 3, 4],
+		..Default::default()
+	});
+
+	assert_eq!(chains.rejected_headers(Rejection::MissingMandatoryHeader), vec![]);
+	for index in [0, 3, 4] {
+		assert_eq!(chains.transactions[index].status, TransactionStatus::Lost);
+	}
+}
+
+#[test]
+fn simulated_finality_loop_survives_target_reorgs() {
+	let chains = run_simulation(Script {
+		transaction_latencies: vec![1, 2],
+		finality_lag: 5,
+		reorgs: vec![(10, 1), (30, 2), (50, 1), (70, 3)],
+		..Default::default()
+	});
+
+	// reverted headers are submitted again, but they are never imported twice
+	let imported_headers =
+		chains.target_headers.iter().map(|(header, _)| header.1).collect::<Vec<_>>();
+	assert!(imported_headers.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[cfg(feature = "fault-injection")]
+fn soak_test_faults(seed: u64) -> FaultInjectionParams {
+	FaultInjectionParams {
+		seed,
+		rpc_failure_probability: 0.05,
+		stale_subscription_probability: 0.1,
+		invalid_justification_probability: 0.1,
+	}
+}
+
+#[test]
+#[cfg(feature = "fault-injection")]
+fn simulated_finality_loop_survives_client_faults() {
+	let mut total_stats = FaultInjectionStats::default();
+	for seed in 0..16 {
+		let chains = run_simulation(Script {
+			headers_count: 128,
+			transaction_latencies: vec![1, 3, 2],
+			finality_lag: 2,
+			max_steps: 100_000,
+			faults: soak_test_faults(seed),
+			..Default::default()
+		});
+
+		let stats = chains.fault_injector.stats();
+		assert!(chains.reconnects > 0);
+		total_stats.failed_rpc_calls += stats.failed_rpc_calls;
+		total_stats.stale_subscriptions += stats.stale_subscriptions;
+		total_stats.invalid_justifications += stats.invalid_justifications;
+	}
+
+	// make sure that every kind of fault has been exercised
+	assert_ne!(total_stats.failed_rpc_calls, 0);
+	assert_ne!(total_stats.stale_subscriptions, 0);
+	assert_ne!(total_stats.invalid_justifications, 0);
+}
+
+#[test]
+#[cfg(feature = "fault-injection")]
+fn simulated_finality_loop_survives_client_faults_and_target_reorgs() {
+	for seed in 0..16 {
+		let chains = run_simulation(Script {
+			headers_count: 128,
+			transaction_latencies: vec![1, 2],
+			finality_lag: 5,
+			reorgs: vec![(10, 1), (30, 2), (50, 1), (70, 3), (150, 2)],
+			dropped_transactions: vec![0, 3, 4],
+			max_steps: 100_000,
+			faults: soak_test_faults(seed),
+			..Default::default()
+		});
+
+		let imported_headers =
+			chains.target_headers.iter().map(|(header, _)| header.1).collect::<Vec<_>>();
+		assert!(imported_headers.windows(2).all(|pair| pair[0] < pair[1]));
+	}
+}
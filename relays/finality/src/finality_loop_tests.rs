@@ -44,9 +44,9 @@ use std::{
 	time::{Duration, Instant},
 };
 
-type IsMandatory = bool;
-type TestNumber = u64;
-type TestHash = u64;
+pub(crate) type IsMandatory = bool;
+pub(crate) type TestNumber = u64;
+pub(crate) type TestHash = u64;
 
 #[derive(Clone, Debug)]
 struct TestTransactionTracker(TrackedTransactionStatus<HeaderId<TestHash, TestNumber>>);
@@ -67,18 +67,19 @@ impl TransactionTracker for TestTransactionTracker {
 }
 
 #[derive(Debug, Clone)]
-enum TestError {
+pub(crate) enum TestError {
+	Connection,
 	NonConnection,
 }
 
 impl MaybeConnectionError for TestError {
 	fn is_connection_error(&self) -> bool {
-		false
+		matches!(self, TestError::Connection)
 	}
 }
 
 #[derive(Debug, Clone)]
-struct TestFinalitySyncPipeline;
+pub(crate) struct TestFinalitySyncPipeline;
 
 impl FinalitySyncPipeline for TestFinalitySyncPipeline {
 	const SOURCE_NAME: &'static str = "TestSource";
@@ -92,7 +93,11 @@ impl FinalitySyncPipeline for TestFinalitySyncPipeline {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct TestSourceHeader(IsMandatory, TestNumber, TestHash);
+pub(crate) struct TestSourceHeader(
+	pub(crate) IsMandatory,
+	pub(crate) TestNumber,
+	pub(crate) TestHash,
+);
 
 impl SourceHeader<TestHash, TestNumber, GrandpaConsensusLogReader<TestNumber>>
 	for TestSourceHeader
@@ -111,7 +116,7 @@ impl SourceHeader<TestHash, TestNumber, GrandpaConsensusLogReader<TestNumber>>
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct TestFinalityProof(TestNumber);
+pub(crate) struct TestFinalityProof(pub(crate) TestNumber);
 
 impl FinalityProof<TestNumber> for TestFinalityProof {
 	fn target_header_number(&self) -> TestNumber {
@@ -124,6 +129,7 @@ struct ClientsData {
 	source_best_block_number: TestNumber,
 	source_headers: HashMap<TestNumber, (TestSourceHeader, Option<TestFinalityProof>)>,
 	source_proofs: Vec<TestFinalityProof>,
+	source_generated_proof: Option<TestFinalityProof>,
 
 	target_best_block_id: HeaderId<TestHash, TestNumber>,
 	target_headers: Vec<(TestSourceHeader, TestFinalityProof)>,
@@ -164,6 +170,15 @@ impl SourceClient<TestFinalitySyncPipeline> for TestSourceClient {
 		data.source_headers.get(&number).cloned().ok_or(TestError::NonConnection)
 	}
 
+	async fn prove_finality(
+		&self,
+		number: TestNumber,
+	) -> Result<Option<TestFinalityProof>, TestError> {
+		let mut data = self.data.lock();
+		(self.on_method_call)(&mut data);
+		Ok(data.source_generated_proof.clone().filter(|proof| proof.0 >= number))
+	}
+
 	async fn finality_proofs(&self) -> Result<Self::FinalityProofsStream, TestError> {
 		let mut data = self.data.lock();
 		(self.on_method_call)(&mut data);
@@ -227,6 +242,7 @@ fn prepare_test_clients(
 		source_best_block_number: 10,
 		source_headers,
 		source_proofs: vec![TestFinalityProof(12), TestFinalityProof(14)],
+		source_generated_proof: None,
 
 		target_best_block_id: HeaderId(5, 5),
 		target_headers: vec![],
@@ -388,6 +404,47 @@ fn select_header_to_submit_selects_mandatory_headers_when_only_mandatory_headers
 	);
 }
 
+fn run_generated_finality_proof_test(
+	generated_proof: Option<TestFinalityProof>,
+) -> Option<(TestSourceHeader, TestFinalityProof)> {
+	let (exit_sender, _) = futures::channel::mpsc::unbounded();
+	let (source_client, target_client) = prepare_test_clients(
+		exit_sender,
+		|_| false,
+		vec![
+			(6, (TestSourceHeader(false, 6, 6), None)),
+			(7, (TestSourceHeader(false, 7, 7), None)),
+			(8, (TestSourceHeader(false, 8, 8), None)),
+			(9, (TestSourceHeader(false, 9, 9), None)),
+			(10, (TestSourceHeader(false, 10, 10), None)),
+		]
+		.into_iter()
+		.collect(),
+	);
+	source_client.data.lock().source_generated_proof = generated_proof;
+	async_std::task::block_on(select_header_to_submit(
+		&source_client,
+		&target_client,
+		&mut RestartableFinalityProofsStream::from(futures::stream::empty().boxed()),
+		&mut vec![],
+		10,
+		5,
+		&test_sync_params(),
+	))
+	.unwrap()
+}
+
+#[test]
+fn select_header_to_submit_uses_generated_proof_when_stream_has_missed_proofs() {
+	assert_eq!(run_generated_finality_proof_test(None), None);
+	assert_eq!(
+		run_generated_finality_proof_test(Some(TestFinalityProof(8))),
+		Some((TestSourceHeader(false, 8, 8), TestFinalityProof(8))),
+	);
+	// proof of the header that is not in the range is ignored
+	assert_eq!(run_generated_finality_proof_test(Some(TestFinalityProof(12))), None);
+}
+
 #[test]
 fn select_better_recent_finality_proof_works() {
 	// if there are no unjustified headers, nothing is changed
@@ -499,6 +556,32 @@ fn read_finality_proofs_from_stream_works() {
 	assert!(stream.needs_restart);
 }
 
+#[test]
+fn silent_finality_proofs_stream_is_restarted() {
+	let mut stream: RestartableFinalityProofsStream<_> =
+		futures::stream::iter(vec![TestFinalityProof(4)])
+			.chain(futures::stream::pending())
+			.into();
+
+	// when silence period has passed, but source chain has not finalized new headers
+	stream.schedule_restart_if_silent(4, Duration::ZERO);
+	stream.schedule_restart_if_silent(4, Duration::ZERO);
+	assert!(!stream.needs_restart);
+
+	// when source chain has finalized new headers, but the silence period has not passed yet
+	stream.schedule_restart_if_silent(5, Duration::from_secs(60));
+	assert!(!stream.needs_restart);
+
+	// when the stream has yielded something, we start counting from scratch
+	assert_eq!(stream.next(), Some(TestFinalityProof(4)));
+	stream.schedule_restart_if_silent(6, Duration::ZERO);
+	assert!(!stream.needs_restart);
+
+	// when silence period has passed and source chain has finalized new headers
+	stream.schedule_restart_if_silent(7, Duration::ZERO);
+	assert!(stream.needs_restart);
+}
+
 #[test]
 fn prune_recent_finality_proofs_works() {
 	let original_recent_finality_proofs: FinalityProofs<TestFinalitySyncPipeline> = vec![
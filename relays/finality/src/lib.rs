@@ -28,6 +28,7 @@ use bp_header_chain::{ConsensusLogReader, FinalityProof};
 use std::fmt::Debug;
 
 mod finality_loop;
+mod finality_loop_simulation_tests;
 mod finality_loop_tests;
 mod sync_loop_metrics;
 
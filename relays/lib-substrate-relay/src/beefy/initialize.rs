@@ -0,0 +1,227 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Initialize Substrate -> Substrate BEEFY bridge.
+//!
+//! Initialization is a transaction that calls `initialize()` function of the bridge BEEFY
+//! pallet. This transaction brings number of the best BEEFY-finalized source block and the
+//! commitment to the validator set, that is signing commitments for its descendants. The
+//! BEEFY sync starts right after this block.
+
+use crate::{
+	beefy::{source::validator_set_at, BeefyChainOf, SubstrateBeefySyncPipeline},
+	error::Error,
+};
+
+use bp_beefy::{merkle_root, BeefyAuthorityIdToMerkleLeafOf, MmrHashOf, MmrHashingOf};
+use bp_runtime::{BasicOperatingMode, HeaderIdOf};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BlockNumberOf, Chain, ChainWithBeefy, Client,
+	Error as SubstrateError, HashOf, IndexOf, UnsignedTransaction,
+};
+use relay_utils::{TrackedTransactionStatus, TransactionTracker};
+use sp_core::Pair;
+use sp_runtime::traits::{Convert, Header as HeaderT};
+
+/// Initialization data of the bridge BEEFY pallet, that is bridging with given chain.
+pub type InitializationDataOf<C> =
+	bp_beefy::InitializationData<BlockNumberOf<C>, MmrHashOf<BeefyChainOf<C>>>;
+
+/// Error type, returned by the BEEFY bridge initialization routines.
+type InitializeError<C> = Error<HashOf<C>, BlockNumberOf<C>>;
+
+/// Submit BEEFY bridge initialization transaction.
+///
+/// If `at_block` is specified, the bridge is initialized with this source header, which must be
+/// finalized by BEEFY. Otherwise, the best BEEFY-finalized source header is used.
+pub async fn initialize<P: SubstrateBeefySyncPipeline, F>(
+	source_client: Client<P::SourceChain>,
+	target_client: Client<P::TargetChain>,
+	target_signer: AccountKeyPairOf<P::TargetChain>,
+	at_block: Option<HashOf<P::SourceChain>>,
+	prepare_initialize_transaction: F,
+	dry_run: bool,
+) where
+	F: FnOnce(
+			IndexOf<P::TargetChain>,
+			InitializationDataOf<P::SourceChain>,
+		) -> Result<UnsignedTransaction<P::TargetChain>, SubstrateError>
+		+ Send
+		+ 'static,
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+{
+	let result = do_initialize::<P, _>(
+		source_client,
+		target_client,
+		target_signer,
+		at_block,
+		prepare_initialize_transaction,
+		dry_run,
+	)
+	.await;
+
+	match result {
+		Ok(Some(TrackedTransactionStatus::Finalized(_))) => log::info!(
+			target: "bridge",
+			"Successfully executed {}-BEEFY bridge initialization transaction on {}",
+			P::SourceChain::NAME,
+			P::TargetChain::NAME,
+		),
+		Ok(Some(TrackedTransactionStatus::DryRun)) => (),
+		Ok(Some(tx_status)) => log::error!(
+			target: "bridge",
+			"Failed to execute {}-BEEFY bridge initialization transaction on {}: {:?}.",
+			P::SourceChain::NAME,
+			P::TargetChain::NAME,
+			tx_status,
+		),
+		Ok(None) => (),
+		Err(err) => log::error!(
+			target: "bridge",
+			"Failed to submit {}-BEEFY bridge initialization transaction to {}: {:?}",
+			P::SourceChain::NAME,
+			P::TargetChain::NAME,
+			err,
+		),
+	}
+}
+
+/// Craft and submit initialization transaction, returning any error that may occur.
+async fn do_initialize<P: SubstrateBeefySyncPipeline, F>(
+	source_client: Client<P::SourceChain>,
+	target_client: Client<P::TargetChain>,
+	target_signer: AccountKeyPairOf<P::TargetChain>,
+	at_block: Option<HashOf<P::SourceChain>>,
+	prepare_initialize_transaction: F,
+	dry_run: bool,
+) -> Result<
+	Option<TrackedTransactionStatus<HeaderIdOf<P::TargetChain>>>,
+	InitializeError<P::SourceChain>,
+>
+where
+	F: FnOnce(
+			IndexOf<P::TargetChain>,
+			InitializationDataOf<P::SourceChain>,
+		) -> Result<UnsignedTransaction<P::TargetChain>, SubstrateError>
+		+ Send
+		+ 'static,
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+{
+	let is_initialized_key = bp_beefy::storage_keys::imported_commitments_info_key(
+		P::SourceChain::WITH_CHAIN_BEEFY_PALLET_NAME,
+	);
+	let is_initialized = target_client
+		.raw_storage_value(is_initialized_key, None)
+		.await
+		.map_err(|e| Error::IsInitializedRetrieve(P::SourceChain::NAME, P::TargetChain::NAME, e))?
+		.is_some();
+	if is_initialized {
+		log::info!(
+			target: "bridge",
+			"{}-BEEFY bridge at {} is already initialized. Skipping",
+			P::SourceChain::NAME,
+			P::TargetChain::NAME,
+		);
+		if !dry_run {
+			return Ok(None)
+		}
+	}
+
+	let initialization_data = prepare_initialization_data::<P>(&source_client, at_block).await?;
+	log::info!(
+		target: "bridge",
+		"Prepared initialization data for {}-BEEFY bridge at {}: {:?}",
+		P::SourceChain::NAME,
+		P::TargetChain::NAME,
+		initialization_data,
+	);
+
+	let tx_status = target_client
+		.submit_and_watch_signed_extrinsic(&target_signer, move |_, transaction_nonce| {
+			let tx = prepare_initialize_transaction(transaction_nonce, initialization_data);
+			if dry_run {
+				Err(SubstrateError::Custom(
+					"Not submitting extrinsic in `dry-run` mode!".to_string(),
+				))
+			} else {
+				tx
+			}
+		})
+		.await
+		.map_err(|err| Error::SubmitTransaction(P::TargetChain::NAME, err))?
+		.wait()
+		.await;
+
+	Ok(Some(tx_status))
+}
+
+/// Prepare initialization data for the bridge BEEFY pallet.
+pub async fn prepare_initialization_data<P: SubstrateBeefySyncPipeline>(
+	source_client: &Client<P::SourceChain>,
+	at_block: Option<HashOf<P::SourceChain>>,
+) -> Result<InitializationDataOf<P::SourceChain>, InitializeError<P::SourceChain>> {
+	let best_finalized_hash = source_client
+		.best_beefy_finalized_header_hash()
+		.await
+		.map_err(|err| Error::RetrieveBeefyFinalizedHeader(P::SourceChain::NAME, err))?;
+	let best_finalized_number = *source_header(source_client, best_finalized_hash).await?.number();
+
+	let initial_header_hash = at_block.unwrap_or(best_finalized_hash);
+	let initial_header_number = *source_header(source_client, initial_header_hash).await?.number();
+	if initial_header_number > best_finalized_number {
+		return Err(Error::NotBeefyFinalized(
+			P::SourceChain::NAME,
+			initial_header_number,
+			best_finalized_number,
+		))
+	}
+
+	// the validator set, read at the initial header, is the set that is signing commitments
+	// for descendants of this header
+	let validator_set =
+		validator_set_at::<P>(source_client, initial_header_hash).await.map_err(|err| {
+			Error::RetrieveBeefyValidatorSet(P::SourceChain::NAME, initial_header_hash, err)
+		})?;
+	let validators_root = merkle_root::<MmrHashingOf<BeefyChainOf<P::SourceChain>>, _>(
+		validator_set
+			.validators()
+			.iter()
+			.cloned()
+			.map(BeefyAuthorityIdToMerkleLeafOf::<BeefyChainOf<P::SourceChain>>::convert)
+			.collect::<Vec<_>>(),
+	);
+
+	Ok(InitializationDataOf::<P::SourceChain> {
+		operating_mode: BasicOperatingMode::Normal,
+		best_block_number: initial_header_number,
+		authority_set: bp_beefy::BeefyAuthoritySet {
+			id: validator_set.id(),
+			len: validator_set.len() as u32,
+			root: validators_root,
+		},
+	})
+}
+
+/// Read header by hash from the source client.
+async fn source_header<C: ChainWithBeefy>(
+	source_client: &Client<C>,
+	header_hash: HashOf<C>,
+) -> Result<C::Header, InitializeError<C>> {
+	source_client
+		.header_by_hash(header_hash)
+		.await
+		.map_err(|err| Error::RetrieveHeader(C::NAME, header_hash, err))
+}
@@ -0,0 +1,208 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types and functions intended to ease adding of new Substrate -> Substrate
+//! BEEFY finality proofs synchronization pipelines.
+//!
+//! Unlike GRANDPA justification, signed BEEFY commitment is not enough to import it at the
+//! target chain. The BEEFY bridge pallet also needs the validator set that has signed the
+//! commitment and the MMR leaf (with its proof), inserted at the commitment block. So the
+//! BEEFY source completes every commitment with this data, before passing it to the finality
+//! relay loop.
+
+use crate::{
+	beefy::{source::SubstrateBeefySource, target::SubstrateBeefyTarget},
+	fee_metrics::FeeMetrics,
+	finality::RECENT_FINALITY_PROOFS_LIMIT,
+	TransactionParams,
+};
+
+use bp_beefy::{
+	BeefyAuthorityIdOf, BeefyAuthoritySetOf, BeefyConsensusLogReader, BeefyMmrLeafOf,
+	BeefySignedCommitmentOf, MmrProofOf,
+};
+use bp_header_chain::FinalityProof;
+use finality_relay::FinalitySyncPipeline;
+use pallet_bridge_beefy::{Call as BridgeBeefyCall, Config as BridgeBeefyConfig};
+use relay_substrate_client::{
+	transaction_stall_timeout, AccountIdOf, AccountKeyPairOf, BlockNumberOf, CallOf, Chain,
+	ChainWithBeefy, ChainWithTransactions, Client, HashOf, HeaderOf, SyncHeader,
+};
+use relay_utils::metrics::{Metric, MetricsParams};
+use sp_core::Pair;
+use std::{fmt::Debug, marker::PhantomData};
+
+pub mod initialize;
+pub mod source;
+pub mod target;
+
+/// BEEFY primitives of given Substrate chain.
+pub type BeefyChainOf<C> = <C as ChainWithBeefy>::BeefyChain;
+
+/// Substrate -> Substrate BEEFY finality proofs synchronization pipeline.
+pub trait SubstrateBeefySyncPipeline: 'static + Clone + Debug + Send + Sync {
+	/// Commitments of this chain are submitted to the `TargetChain`.
+	type SourceChain: ChainWithBeefy;
+	/// Commitments of the `SourceChain` are submitted to this chain.
+	type TargetChain: ChainWithTransactions;
+
+	/// How submit commitment call is built?
+	type SubmitBeefyCommitmentCallBuilder: SubmitBeefyCommitmentCallBuilder<Self>;
+}
+
+/// Adapter that allows all `SubstrateBeefySyncPipeline` to act as `FinalitySyncPipeline`.
+#[derive(Clone, Debug)]
+pub struct BeefySyncPipelineAdapter<P: SubstrateBeefySyncPipeline> {
+	_phantom: PhantomData<P>,
+}
+
+impl<P: SubstrateBeefySyncPipeline> FinalitySyncPipeline for BeefySyncPipelineAdapter<P> {
+	const SOURCE_NAME: &'static str = P::SourceChain::NAME;
+	const TARGET_NAME: &'static str = P::TargetChain::NAME;
+
+	type Hash = HashOf<P::SourceChain>;
+	type Number = BlockNumberOf<P::SourceChain>;
+	type ConsensusLogReader =
+		BeefyConsensusLogReader<BeefyAuthorityIdOf<BeefyChainOf<P::SourceChain>>>;
+	type Header = SyncHeader<HeaderOf<P::SourceChain>>;
+	type FinalityProof = BeefyFinalityProof<P::SourceChain>;
+}
+
+/// BEEFY finality proof, that is accepted by the BEEFY bridge pallet.
+pub struct BeefyFinalityProof<C: ChainWithBeefy> {
+	/// Signed BEEFY commitment.
+	pub signed_commitment: BeefySignedCommitmentOf<BeefyChainOf<C>>,
+	/// Validator set that has signed the commitment.
+	pub validator_set: BeefyAuthoritySetOf<BeefyChainOf<C>>,
+	/// MMR leaf, inserted at the commitment block.
+	pub mmr_leaf: BeefyMmrLeafOf<BeefyChainOf<C>>,
+	/// Proof of the `mmr_leaf` against MMR root from the commitment payload.
+	pub mmr_proof: MmrProofOf<BeefyChainOf<C>>,
+}
+
+impl<C: ChainWithBeefy> Clone for BeefyFinalityProof<C> {
+	fn clone(&self) -> Self {
+		BeefyFinalityProof {
+			signed_commitment: self.signed_commitment.clone(),
+			validator_set: self.validator_set.clone(),
+			mmr_leaf: self.mmr_leaf.clone(),
+			mmr_proof: self.mmr_proof.clone(),
+		}
+	}
+}
+
+impl<C: ChainWithBeefy> Debug for BeefyFinalityProof<C> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("BeefyFinalityProof")
+			.field("signed_commitment", &self.signed_commitment)
+			.field("validator_set", &self.validator_set)
+			.field("mmr_leaf", &self.mmr_leaf)
+			.field("mmr_proof", &self.mmr_proof)
+			.finish()
+	}
+}
+
+impl<C: ChainWithBeefy> FinalityProof<BlockNumberOf<C>> for BeefyFinalityProof<C> {
+	fn target_header_number(&self) -> BlockNumberOf<C> {
+		self.signed_commitment.commitment.block_number
+	}
+}
+
+/// Different ways of building `submit_commitment` calls.
+pub trait SubmitBeefyCommitmentCallBuilder<P: SubstrateBeefySyncPipeline> {
+	/// Given BEEFY finality proof, build call of `submit_commitment` function of bridge BEEFY
+	/// module at the target chain.
+	fn build_submit_commitment_call(
+		proof: BeefyFinalityProof<P::SourceChain>,
+	) -> CallOf<P::TargetChain>;
+}
+
+/// Building `submit_commitment` call when you have direct access to the target
+/// chain runtime.
+pub struct DirectSubmitBeefyCommitmentCallBuilder<P, R, I> {
+	_phantom: PhantomData<(P, R, I)>,
+}
+
+impl<P, R, I> SubmitBeefyCommitmentCallBuilder<P>
+	for DirectSubmitBeefyCommitmentCallBuilder<P, R, I>
+where
+	P: SubstrateBeefySyncPipeline,
+	R: BridgeBeefyConfig<I, BridgedChain = BeefyChainOf<P::SourceChain>>,
+	I: 'static,
+	CallOf<P::TargetChain>: From<BridgeBeefyCall<R, I>>,
+{
+	fn build_submit_commitment_call(
+		proof: BeefyFinalityProof<P::SourceChain>,
+	) -> CallOf<P::TargetChain> {
+		BridgeBeefyCall::<R, I>::submit_commitment {
+			commitment: proof.signed_commitment,
+			validator_set: proof.validator_set,
+			mmr_leaf: Box::new(proof.mmr_leaf),
+			mmr_proof: proof.mmr_proof,
+		}
+		.into()
+	}
+}
+
+/// Run Substrate-to-Substrate BEEFY finality sync loop.
+pub async fn run<P: SubstrateBeefySyncPipeline>(
+	source_client: Client<P::SourceChain>,
+	target_client: Client<P::TargetChain>,
+	only_mandatory_headers: bool,
+	transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+	metrics_params: MetricsParams,
+) -> anyhow::Result<()>
+where
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+{
+	log::info!(
+		target: "bridge",
+		"Starting {} -> {} BEEFY finality proof relay",
+		P::SourceChain::NAME,
+		P::TargetChain::NAME,
+	);
+
+	let fee_metrics =
+		FeeMetrics::new(Some(&finality_relay::metrics_prefix::<BeefySyncPipelineAdapter<P>>()))?;
+	fee_metrics.register(&metrics_params.registry)?;
+
+	finality_relay::run(
+		SubstrateBeefySource::<P>::new(source_client.clone()),
+		SubstrateBeefyTarget::<P>::new(
+			target_client,
+			source_client,
+			transaction_params.clone(),
+			Some(fee_metrics),
+		),
+		finality_relay::FinalitySyncParams {
+			tick: std::cmp::max(
+				P::SourceChain::AVERAGE_BLOCK_INTERVAL,
+				P::TargetChain::AVERAGE_BLOCK_INTERVAL,
+			),
+			recent_finality_proofs_limit: RECENT_FINALITY_PROOFS_LIMIT,
+			stall_timeout: transaction_stall_timeout(
+				transaction_params.mortality,
+				P::TargetChain::AVERAGE_BLOCK_INTERVAL,
+				relay_utils::STALL_TIMEOUT,
+			),
+			only_mandatory_headers,
+		},
+		metrics_params,
+		futures::future::pending(),
+	)
+	.await
+	.map_err(|e| anyhow::format_err!("{}", e))
+}
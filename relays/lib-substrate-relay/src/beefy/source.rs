@@ -0,0 +1,240 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Default generic implementation of BEEFY finality source for basic Substrate client.
+
+use crate::beefy::{
+	BeefyChainOf, BeefyFinalityProof, BeefySyncPipelineAdapter, SubstrateBeefySyncPipeline,
+};
+
+use async_trait::async_trait;
+use bp_beefy::{
+	BeefyAuthoritySetOf, BeefyMmrLeafOf, BeefySignedCommitmentOf, BeefyValidatorSignatureOf,
+	MmrProofOf, VersionedFinalityProof, BEEFY_ENGINE_ID,
+};
+use codec::Decode;
+use finality_relay::SourceClient;
+use futures::stream::{unfold, Stream, StreamExt};
+use relay_substrate_client::{
+	BlockNumberOf, BlockWithJustification, Chain, Client, Error, HashOf, HeaderOf,
+	SubstrateBeefyFinalityClient, SyncHeader,
+};
+use relay_utils::relay_loop::Client as RelayClient;
+use sp_runtime::traits::Header;
+use std::pin::Pin;
+
+/// Name of the runtime API method, that returns current BEEFY validator set.
+const BEEFY_API_VALIDATOR_SET_METHOD: &str = "BeefyApi_validator_set";
+
+/// Substrate BEEFY finality proofs stream.
+pub type SubstrateBeefyFinalityProofsStream<P> = Pin<
+	Box<
+		dyn Stream<Item = BeefyFinalityProof<<P as SubstrateBeefySyncPipeline>::SourceChain>>
+			+ Send,
+	>,
+>;
+
+/// Encoded BEEFY finality proof, that is stored in the block justifications and is
+/// sent by the node over the justifications subscription.
+type VersionedFinalityProofOf<C> =
+	VersionedFinalityProof<BlockNumberOf<C>, BeefyValidatorSignatureOf<BeefyChainOf<C>>>;
+
+/// Substrate node as BEEFY finality source.
+pub struct SubstrateBeefySource<P: SubstrateBeefySyncPipeline> {
+	client: Client<P::SourceChain>,
+}
+
+impl<P: SubstrateBeefySyncPipeline> SubstrateBeefySource<P> {
+	/// Create new BEEFY source using given client.
+	pub fn new(client: Client<P::SourceChain>) -> Self {
+		SubstrateBeefySource { client }
+	}
+
+	/// Returns reference to the underlying RPC client.
+	pub fn client(&self) -> &Client<P::SourceChain> {
+		&self.client
+	}
+}
+
+impl<P: SubstrateBeefySyncPipeline> Clone for SubstrateBeefySource<P> {
+	fn clone(&self) -> Self {
+		SubstrateBeefySource { client: self.client.clone() }
+	}
+}
+
+#[async_trait]
+impl<P: SubstrateBeefySyncPipeline> RelayClient for SubstrateBeefySource<P> {
+	type Error = Error;
+
+	async fn reconnect(&mut self) -> Result<(), Error> {
+		self.client.reconnect().await
+	}
+}
+
+#[async_trait]
+impl<P: SubstrateBeefySyncPipeline> SourceClient<BeefySyncPipelineAdapter<P>>
+	for SubstrateBeefySource<P>
+{
+	type FinalityProofsStream = SubstrateBeefyFinalityProofsStream<P>;
+
+	async fn best_finalized_block_number(&self) -> Result<BlockNumberOf<P::SourceChain>, Error> {
+		let best_finalized_header_hash = self.client.best_beefy_finalized_header_hash().await?;
+		Ok(*self.client.header_by_hash(best_finalized_header_hash).await?.number())
+	}
+
+	async fn header_and_finality_proof(
+		&self,
+		number: BlockNumberOf<P::SourceChain>,
+	) -> Result<
+		(SyncHeader<HeaderOf<P::SourceChain>>, Option<BeefyFinalityProof<P::SourceChain>>),
+		Error,
+	> {
+		let header_hash = self.client.block_hash_by_number(number).await?;
+		let signed_block = self.client.get_block(Some(header_hash)).await?;
+
+		let signed_commitment = signed_block
+			.justification(BEEFY_ENGINE_ID)
+			.map(|raw_justification| decode_signed_commitment::<P>(raw_justification))
+			.transpose()?;
+		let proof = match signed_commitment {
+			Some(signed_commitment) =>
+				Some(prove_commitment::<P>(&self.client, signed_commitment).await?),
+			None => None,
+		};
+
+		Ok((signed_block.header().into(), proof))
+	}
+
+	async fn prove_finality(
+		&self,
+		_number: BlockNumberOf<P::SourceChain>,
+	) -> Result<Option<BeefyFinalityProof<P::SourceChain>>, Error> {
+		// there's no RPC method to generate BEEFY finality proof of arbitrary block
+		Ok(None)
+	}
+
+	async fn finality_proofs(&self) -> Result<Self::FinalityProofsStream, Error> {
+		let client = self.client.clone();
+		let subscription = client
+			.subscribe_finality_justifications::<SubstrateBeefyFinalityClient>()
+			.await?;
+		Ok(unfold((client, subscription), move |(client, subscription)| async move {
+			loop {
+				let log_error = |err| {
+					log::error!(
+						target: "bridge",
+						"Failed to read BEEFY commitment from the {} justifications stream: {:?}",
+						P::SourceChain::NAME,
+						err,
+					);
+				};
+
+				let next_justification =
+					subscription.next().await.map_err(|err| log_error(err.to_string())).ok()??;
+
+				let signed_commitment = match decode_signed_commitment::<P>(&next_justification) {
+					Ok(signed_commitment) => signed_commitment,
+					Err(err) => {
+						log_error(format!("decode failed with error {err:?}"));
+						continue
+					},
+				};
+
+				match prove_commitment::<P>(&client, signed_commitment).await {
+					Ok(proof) => return Some((proof, (client, subscription))),
+					Err(err) => {
+						log_error(format!("failed to prove commitment: {err:?}"));
+						continue
+					},
+				}
+			}
+		})
+		.boxed())
+	}
+}
+
+/// Decode signed BEEFY commitment from the encoded BEEFY justification.
+fn decode_signed_commitment<P: SubstrateBeefySyncPipeline>(
+	raw_justification: &[u8],
+) -> Result<BeefySignedCommitmentOf<BeefyChainOf<P::SourceChain>>, Error> {
+	let proof = VersionedFinalityProofOf::<P::SourceChain>::decode(&mut &raw_justification[..])?;
+	Ok(match proof {
+		VersionedFinalityProof::V1(signed_commitment) => signed_commitment,
+	})
+}
+
+/// Complete signed commitment with the data, required to import it at the target chain.
+async fn prove_commitment<P: SubstrateBeefySyncPipeline>(
+	client: &Client<P::SourceChain>,
+	signed_commitment: BeefySignedCommitmentOf<BeefyChainOf<P::SourceChain>>,
+) -> Result<BeefyFinalityProof<P::SourceChain>, Error> {
+	let block_number = signed_commitment.commitment.block_number;
+	let validator_set_id = signed_commitment.commitment.validator_set_id;
+	let header = client.header_by_number(block_number).await?;
+
+	// if the commitment block enacts new validator set, it may be signed by the previous set
+	let mut validator_set = validator_set_at::<P>(client, header.hash()).await?;
+	if validator_set.id() != validator_set_id {
+		validator_set = validator_set_at::<P>(client, *header.parent_hash()).await?;
+	}
+	if validator_set.id() != validator_set_id {
+		return Err(Error::Custom(format!(
+			"Failed to find {} BEEFY validator set {} that has signed commitment for block {:?}",
+			P::SourceChain::NAME,
+			validator_set_id,
+			block_number,
+		)))
+	}
+
+	// commitment payload has the MMR root at the commitment block, so we need to prove the
+	// leaf, inserted at this block, against this root
+	let leaves_proof = client.generate_mmr_proof(block_number, block_number).await?;
+	let mut leaves = Vec::<Vec<u8>>::decode(&mut &leaves_proof.leaves[..])?;
+	let mmr_leaf: BeefyMmrLeafOf<BeefyChainOf<P::SourceChain>> = match leaves.pop() {
+		Some(encoded_leaf) if leaves.is_empty() => Decode::decode(&mut &encoded_leaf[..])?,
+		_ =>
+			return Err(Error::Custom(format!(
+				"Expected single MMR leaf in the {} MMR proof for block {:?}",
+				P::SourceChain::NAME,
+				block_number,
+			))),
+	};
+	let mmr_proof =
+		MmrProofOf::<BeefyChainOf<P::SourceChain>>::decode(&mut &leaves_proof.proof[..])?;
+
+	Ok(BeefyFinalityProof { signed_commitment, validator_set, mmr_leaf, mmr_proof })
+}
+
+/// Read BEEFY validator set at given block.
+pub(crate) async fn validator_set_at<P: SubstrateBeefySyncPipeline>(
+	client: &Client<P::SourceChain>,
+	at_block: HashOf<P::SourceChain>,
+) -> Result<BeefyAuthoritySetOf<BeefyChainOf<P::SourceChain>>, Error> {
+	client
+		.typed_state_call::<_, Option<BeefyAuthoritySetOf<BeefyChainOf<P::SourceChain>>>>(
+			BEEFY_API_VALIDATOR_SET_METHOD.into(),
+			(),
+			Some(at_block),
+		)
+		.await?
+		.ok_or_else(|| {
+			Error::Custom(format!(
+				"BEEFY validator set is missing at {} block {:?}",
+				P::SourceChain::NAME,
+				at_block,
+			))
+		})
+}
@@ -0,0 +1,149 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Substrate client as Substrate BEEFY finality proof target.
+
+use crate::{
+	beefy::{
+		BeefyFinalityProof, BeefySyncPipelineAdapter, SubmitBeefyCommitmentCallBuilder,
+		SubstrateBeefySyncPipeline,
+	},
+	fee_metrics::{FeeMetrics, FeeTrackingTransactionTracker, TransactionKind},
+	submit_and_watch_call, TransactionParams,
+};
+
+use async_trait::async_trait;
+use bp_beefy::ImportedCommitmentsInfoData;
+use bp_runtime::{BasicOperatingMode, HeaderId, OperatingMode};
+use finality_relay::TargetClient;
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BlockNumberOf, ChainWithBeefy, Client, Error, HeaderIdOf,
+	HeaderOf, SyncHeader,
+};
+use relay_utils::relay_loop::Client as RelayClient;
+use sp_core::Pair;
+
+/// Substrate client as Substrate BEEFY finality target.
+pub struct SubstrateBeefyTarget<P: SubstrateBeefySyncPipeline> {
+	client: Client<P::TargetChain>,
+	source_client: Client<P::SourceChain>,
+	transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+	fee_metrics: Option<FeeMetrics>,
+}
+
+impl<P: SubstrateBeefySyncPipeline> SubstrateBeefyTarget<P> {
+	/// Create new Substrate BEEFY target.
+	///
+	/// The BEEFY pallet only stores number of the best imported block, so the source client
+	/// is used to read hash of this block.
+	pub fn new(
+		client: Client<P::TargetChain>,
+		source_client: Client<P::SourceChain>,
+		transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+		fee_metrics: Option<FeeMetrics>,
+	) -> Self {
+		SubstrateBeefyTarget { client, source_client, transaction_params, fee_metrics }
+	}
+
+	/// Ensure that the BEEFY pallet at target chain is active.
+	pub async fn ensure_pallet_active(&self) -> Result<(), Error> {
+		let is_halted = self
+			.client
+			.storage_value::<BasicOperatingMode>(
+				bp_beefy::storage_keys::pallet_operating_mode_key(
+					P::SourceChain::WITH_CHAIN_BEEFY_PALLET_NAME,
+				),
+				None,
+			)
+			.await?
+			.map(|operating_mode| operating_mode.is_halted())
+			.unwrap_or(false);
+		if is_halted {
+			return Err(Error::BridgePalletIsHalted)
+		}
+
+		Ok(())
+	}
+}
+
+impl<P: SubstrateBeefySyncPipeline> Clone for SubstrateBeefyTarget<P> {
+	fn clone(&self) -> Self {
+		SubstrateBeefyTarget {
+			client: self.client.clone(),
+			source_client: self.source_client.clone(),
+			transaction_params: self.transaction_params.clone(),
+			fee_metrics: self.fee_metrics.clone(),
+		}
+	}
+}
+
+#[async_trait]
+impl<P: SubstrateBeefySyncPipeline> RelayClient for SubstrateBeefyTarget<P> {
+	type Error = Error;
+
+	async fn reconnect(&mut self) -> Result<(), Error> {
+		self.client.reconnect().await?;
+		self.source_client.reconnect().await
+	}
+}
+
+#[async_trait]
+impl<P: SubstrateBeefySyncPipeline> TargetClient<BeefySyncPipelineAdapter<P>>
+	for SubstrateBeefyTarget<P>
+where
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+{
+	type TransactionTracker = FeeTrackingTransactionTracker<P::TargetChain>;
+
+	async fn best_finalized_source_block_id(&self) -> Result<HeaderIdOf<P::SourceChain>, Error> {
+		// we can't continue to relay finality if target node is out of sync, because
+		// it may have already received (some of) commitments that we're going to relay
+		self.client.ensure_synced().await?;
+		// we can't relay finality if bridge pallet at target chain is halted
+		self.ensure_pallet_active().await?;
+
+		let best_block_number = self
+			.client
+			.storage_value::<ImportedCommitmentsInfoData<BlockNumberOf<P::SourceChain>>>(
+				bp_beefy::storage_keys::imported_commitments_info_key(
+					P::SourceChain::WITH_CHAIN_BEEFY_PALLET_NAME,
+				),
+				None,
+			)
+			.await?
+			.ok_or(Error::BridgePalletIsNotInitialized)?
+			.best_block_number;
+		let best_block_hash = self.source_client.block_hash_by_number(best_block_number).await?;
+
+		Ok(HeaderId(best_block_number, best_block_hash))
+	}
+
+	async fn submit_finality_proof(
+		&self,
+		_header: SyncHeader<HeaderOf<P::SourceChain>>,
+		proof: BeefyFinalityProof<P::SourceChain>,
+	) -> Result<Self::TransactionTracker, Error> {
+		let call = P::SubmitBeefyCommitmentCallBuilder::build_submit_commitment_call(proof);
+		let tx_tracker =
+			submit_and_watch_call(&self.client, &self.transaction_params, call).await?;
+		Ok(FeeTrackingTransactionTracker::new(
+			tx_tracker,
+			self.fee_metrics.clone(),
+			TransactionKind::Finality,
+			0,
+		))
+	}
+}
@@ -52,6 +52,12 @@ pub enum Error<Hash: Debug + MaybeDisplay, HeaderNumber: Debug + MaybeDisplay> {
 	/// Failed to decode GRANDPA authorities at the given header of the source chain.
 	#[error("Failed to decode {0} GRANDPA authorities set at header {1}: {2:?}")]
 	DecodeAuthorities(&'static str, Hash, codec::Error),
+	/// Failed to retrieve GRANDPA authorities set id at the given header from the source chain.
+	#[error("Failed to retrieve {0} GRANDPA authorities set id at header {1}: {2:?}")]
+	RetrieveAuthoritiesSetId(&'static str, Hash, client::Error),
+	/// GRANDPA authorities set id is missing from the source chain storage.
+	#[error("{0} GRANDPA authorities set id is missing from the storage at header {1}")]
+	MissingAuthoritiesSetId(&'static str, Hash),
 	/// Failed to retrieve header by the hash from the source chain.
 	#[error("Failed to retrieve {0} header with hash {1}: {2:?}")]
 	RetrieveHeader(&'static str, Hash, client::Error),
@@ -60,4 +66,13 @@ pub enum Error<Hash: Debug + MaybeDisplay, HeaderNumber: Debug + MaybeDisplay> {
 		"Failed to retrieve `is_initialized` flag of the with-{0} finality pallet at {1}: {2:?}"
 	)]
 	IsInitializedRetrieve(&'static str, &'static str, client::Error),
+	/// Failed to retrieve hash of the best header, finalized by BEEFY.
+	#[error("Failed to retrieve best {0} BEEFY-finalized header: {1:?}")]
+	RetrieveBeefyFinalizedHeader(&'static str, client::Error),
+	/// Failed to retrieve BEEFY validator set at the given header from the source chain.
+	#[error("Failed to retrieve {0} BEEFY validator set at header {1}: {2:?}")]
+	RetrieveBeefyValidatorSet(&'static str, Hash, client::Error),
+	/// The header is not yet finalized by BEEFY.
+	#[error("{0} header {1} is not finalized by BEEFY. Best BEEFY-finalized header is {2}")]
+	NotBeefyFinalized(&'static str, HeaderNumber, HeaderNumber),
 }
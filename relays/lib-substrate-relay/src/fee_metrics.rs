@@ -0,0 +1,271 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Metrics of estimated fees, spent by the relayer.
+
+use async_trait::async_trait;
+use bp_messages::MessageNonce;
+use parking_lot::Mutex;
+use relay_substrate_client::{Chain, Client, HeaderIdOf, TransactionTracker};
+use relay_utils::{
+	metrics::{
+		metric_name, register, CounterVec, GaugeVec, Metric, Opts, PrometheusError, Registry, F64,
+		U64,
+	},
+	TrackedTransactionStatus, UniqueSaturatedInto,
+};
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::Arc,
+};
+
+/// Number of the latest messages transactions at every chain, that are used to compute the
+/// cost-per-message.
+const COST_PER_MESSAGE_WINDOW: usize = 16;
+
+/// Kind of the relay transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionKind {
+	/// Transaction with finality proof of the bridged chain header.
+	Finality,
+	/// Transaction with bridged parachain heads.
+	ParachainHeads,
+	/// Messages delivery transaction.
+	MessagesDelivery,
+	/// Messages delivery confirmation transaction.
+	MessagesConfirmation,
+}
+
+impl TransactionKind {
+	/// Returns metric label of the transaction kind.
+	pub fn label(&self) -> &'static str {
+		match *self {
+			TransactionKind::Finality => "finality",
+			TransactionKind::ParachainHeads => "parachain_heads",
+			TransactionKind::MessagesDelivery => "delivery",
+			TransactionKind::MessagesConfirmation => "confirmation",
+		}
+	}
+}
+
+/// Metrics of estimated fees, spent by the relayer.
+///
+/// Fees are estimated right after the transaction is submitted (see
+/// `TransactionTracker::estimated_fee`), so they may differ from fees actually paid. Fees are
+/// reported in the smallest units of the chain token. Only fees of finalized transactions are
+/// accounted. Cloning only clones references.
+#[derive(Clone)]
+pub struct FeeMetrics {
+	/// Total estimated fees (including tips), spent by the relayer: by chain and transaction
+	/// kind.
+	estimated_transaction_fees: CounterVec<F64>,
+	/// Number of finalized relayer transactions: by chain and transaction kind.
+	finalized_transactions: CounterVec<U64>,
+	/// Average estimated fee, spent to deliver or confirm single message at the latest messages
+	/// transactions: by chain.
+	estimated_cost_per_message: GaugeVec<F64>,
+	/// Estimated fees and number of messages of the latest messages transactions: by chain.
+	recent_messages_transactions: Arc<Mutex<HashMap<&'static str, VecDeque<(f64, MessageNonce)>>>>,
+}
+
+impl FeeMetrics {
+	/// Create fee metrics.
+	pub fn new(prefix: Option<&str>) -> Result<Self, PrometheusError> {
+		Ok(FeeMetrics {
+			estimated_transaction_fees: CounterVec::new(
+				Opts::new(
+					metric_name(prefix, "estimated_transaction_fees"),
+					"Total estimated fees (including tips), spent by the relayer",
+				),
+				&["chain", "kind"],
+			)?,
+			finalized_transactions: CounterVec::new(
+				Opts::new(
+					metric_name(prefix, "finalized_transactions"),
+					"Number of finalized relayer transactions",
+				),
+				&["chain", "kind"],
+			)?,
+			estimated_cost_per_message: GaugeVec::new(
+				Opts::new(
+					metric_name(prefix, "estimated_cost_per_message"),
+					"Average estimated fee, spent to deliver or confirm single message",
+				),
+				&["chain"],
+			)?,
+			recent_messages_transactions: Arc::new(Mutex::new(HashMap::new())),
+		})
+	}
+
+	/// Note finalized transaction of given kind, that has been submitted to the given chain.
+	///
+	/// The `estimated_fee` is the fee, estimated when the transaction has been submitted. The
+	/// `messages` is the number of messages, delivered or confirmed by the transaction.
+	pub fn note_finalized_transaction(
+		&self,
+		chain: &'static str,
+		kind: TransactionKind,
+		estimated_fee: f64,
+		messages: MessageNonce,
+	) {
+		self.estimated_transaction_fees
+			.with_label_values(&[chain, kind.label()])
+			.inc_by(estimated_fee);
+		self.finalized_transactions.with_label_values(&[chain, kind.label()]).inc();
+
+		if messages == 0 {
+			return
+		}
+
+		let mut recent_messages_transactions = self.recent_messages_transactions.lock();
+		let recent_transactions = recent_messages_transactions.entry(chain).or_default();
+		recent_transactions.push_back((estimated_fee, messages));
+		if recent_transactions.len() > COST_PER_MESSAGE_WINDOW {
+			recent_transactions.pop_front();
+		}
+
+		let total_fee: f64 = recent_transactions.iter().map(|(fee, _)| fee).sum();
+		let total_messages: MessageNonce =
+			recent_transactions.iter().map(|(_, messages)| messages).sum();
+		self.estimated_cost_per_message
+			.with_label_values(&[chain])
+			.set(total_fee / total_messages as f64);
+	}
+}
+
+impl Metric for FeeMetrics {
+	fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		register(self.estimated_transaction_fees.clone(), registry)?;
+		register(self.finalized_transactions.clone(), registry)?;
+		register(self.estimated_cost_per_message.clone(), registry)?;
+		Ok(())
+	}
+}
+
+/// Transaction tracker that reports estimated fee of the finalized transaction to the fee metrics.
+pub struct FeeTrackingTransactionTracker<C: Chain> {
+	/// Actual transaction tracker.
+	tracker: TransactionTracker<C, Client<C>>,
+	/// Fee metrics.
+	metrics: Option<FeeMetrics>,
+	/// Kind of the tracked transaction.
+	kind: TransactionKind,
+	/// Number of messages, delivered or confirmed by the tracked transaction.
+	messages: MessageNonce,
+}
+
+impl<C: Chain> FeeTrackingTransactionTracker<C> {
+	/// Create new tracker.
+	pub fn new(
+		tracker: TransactionTracker<C, Client<C>>,
+		metrics: Option<FeeMetrics>,
+		kind: TransactionKind,
+		messages: MessageNonce,
+	) -> Self {
+		log::debug!(
+			target: "bridge",
+			tx_hash = format!("{:?}", tracker.transaction_hash()),
+			purpose = kind.label();
+			"Submitted {} transaction {:?} to {}",
+			kind.label(),
+			tracker.transaction_hash(),
+			C::NAME,
+		);
+
+		FeeTrackingTransactionTracker { tracker, metrics, kind, messages }
+	}
+}
+
+#[async_trait]
+impl<C: Chain> relay_utils::TransactionTracker for FeeTrackingTransactionTracker<C> {
+	type HeaderId = HeaderIdOf<C>;
+
+	async fn wait(self) -> TrackedTransactionStatus<HeaderIdOf<C>> {
+		let estimated_fee = self.tracker.estimated_fee();
+		let tx_hash = self.tracker.transaction_hash();
+		let status = relay_utils::TransactionTracker::wait(self.tracker).await;
+		log::debug!(
+			target: "bridge",
+			tx_hash = format!("{tx_hash:?}"),
+			purpose = self.kind.label();
+			"{} transaction {:?} at {}: {:?}",
+			self.kind.label(),
+			tx_hash,
+			C::NAME,
+			status,
+		);
+		if let (TrackedTransactionStatus::Finalized(_), Some(metrics), Some(estimated_fee)) =
+			(&status, self.metrics, estimated_fee)
+		{
+			let estimated_fee: u128 = estimated_fee.unique_saturated_into();
+			metrics.note_finalized_transaction(
+				C::NAME,
+				self.kind,
+				estimated_fee as f64,
+				self.messages,
+			);
+		}
+		status
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn cost_per_message_is_computed_using_recent_transactions() {
+		let metrics = FeeMetrics::new(None).unwrap();
+		let cost_per_message =
+			|chain| metrics.estimated_cost_per_message.with_label_values(&[chain]).get();
+
+		// finality transactions are not affecting cost-per-message
+		metrics.note_finalized_transaction("Source", TransactionKind::Finality, 1000.0, 0);
+		metrics.note_finalized_transaction("Target", TransactionKind::MessagesDelivery, 100.0, 1);
+		metrics.note_finalized_transaction("Target", TransactionKind::MessagesDelivery, 200.0, 3);
+		metrics.note_finalized_transaction(
+			"Source",
+			TransactionKind::MessagesConfirmation,
+			40.0,
+			4,
+		);
+		assert_eq!(cost_per_message("Target"), 75.0);
+		assert_eq!(cost_per_message("Source"), 10.0);
+
+		// old transactions are pruned
+		for _ in 0..COST_PER_MESSAGE_WINDOW {
+			metrics.note_finalized_transaction(
+				"Target",
+				TransactionKind::MessagesDelivery,
+				10.0,
+				2,
+			);
+		}
+		assert_eq!(cost_per_message("Target"), 5.0);
+
+		assert_eq!(
+			metrics
+				.estimated_transaction_fees
+				.with_label_values(&["Source", "finality"])
+				.get(),
+			1000.0
+		);
+		assert_eq!(
+			metrics.finalized_transactions.with_label_values(&["Target", "delivery"]).get(),
+			2 + COST_PER_MESSAGE_WINDOW as u64,
+		);
+	}
+}
@@ -27,10 +27,13 @@ use codec::{Decode, Encode};
 use finality_grandpa::voter_set::VoterSet;
 use num_traits::{One, Zero};
 use relay_substrate_client::{
-	BlockNumberOf, Chain, ChainWithGrandpa, Client, Error as SubstrateError, HashOf, HeaderOf,
-	Subscription, SubstrateFinalityClient, SubstrateGrandpaFinalityClient,
+	BlockNumberOf, BlockWithJustification, Chain, ChainWithGrandpa, Client,
+	Error as SubstrateError, HashOf, HeaderOf, Subscription, SubstrateFinalityClient,
+	SubstrateGrandpaFinalityClient,
+};
+use sp_consensus_grandpa::{
+	AuthorityList as GrandpaAuthoritiesSet, ScheduledChange, SetId, GRANDPA_ENGINE_ID,
 };
-use sp_consensus_grandpa::{AuthorityList as GrandpaAuthoritiesSet, GRANDPA_ENGINE_ID};
 use sp_core::{storage::StorageKey, Bytes};
 use sp_runtime::{traits::Header, ConsensusEngineId};
 use std::marker::PhantomData;
@@ -87,6 +90,17 @@ pub trait Engine<C: Chain>: Send {
 		client.subscribe_finality_justifications::<Self::FinalityClient>().await
 	}
 
+	/// Generate finality proof of given block, or of some of its descendants.
+	///
+	/// This is used as a fallback when the finality proofs subscription has missed proofs that
+	/// we need. Returns `None` if the engine or the node is unable to generate proofs.
+	async fn prove_finality(
+		_client: &Client<C>,
+		_number: BlockNumberOf<C>,
+	) -> Result<Option<Self::FinalityProof>, SubstrateError> {
+		Ok(None)
+	}
+
 	/// Optimize finality proof before sending it to the target node.
 	async fn optimize_proof<TargetChain: Chain>(
 		target_client: &Client<TargetChain>,
@@ -95,11 +109,19 @@ pub trait Engine<C: Chain>: Send {
 	) -> Result<Self::FinalityProof, SubstrateError>;
 
 	/// Prepare initialization data for the finality bridge pallet.
+	///
+	/// If `at_block` is `None`, the header that is finalized by the next justification is
+	/// used as the initial header. Otherwise the bridge is initialized with given header,
+	/// which must be finalized by the caller.
 	async fn prepare_initialization_data(
 		client: Client<C>,
+		at_block: Option<HashOf<C>>,
 	) -> Result<Self::InitializationData, Error<HashOf<C>, BlockNumberOf<C>>>;
 }
 
+/// Name of the GRANDPA pallet at the source chain (used in `construct_runtime` macro call).
+const GRANDPA_PALLET_NAME: &str = "Grandpa";
+
 /// GRANDPA finality engine.
 pub struct Grandpa<C>(PhantomData<C>);
 
@@ -127,82 +149,29 @@ impl<C: ChainWithGrandpa> Grandpa<C> {
 		GrandpaAuthoritiesSet::decode(&mut &raw_authorities_set[..])
 			.map_err(|err| Error::DecodeAuthorities(C::NAME, header_hash, err))
 	}
-}
-
-#[async_trait]
-impl<C: ChainWithGrandpa> Engine<C> for Grandpa<C> {
-	const ID: ConsensusEngineId = GRANDPA_ENGINE_ID;
-	type ConsensusLogReader = GrandpaConsensusLogReader<<C::Header as Header>::Number>;
-	type FinalityClient = SubstrateGrandpaFinalityClient;
-	type FinalityProof = GrandpaJustification<HeaderOf<C>>;
-	type InitializationData = bp_header_chain::InitializationData<C::Header>;
-	type OperatingMode = BasicOperatingMode;
-
-	fn is_initialized_key() -> StorageKey {
-		bp_header_chain::storage_keys::best_finalized_key(C::WITH_CHAIN_GRANDPA_PALLET_NAME)
-	}
 
-	fn pallet_operating_mode_key() -> StorageKey {
-		bp_header_chain::storage_keys::pallet_operating_mode_key(C::WITH_CHAIN_GRANDPA_PALLET_NAME)
-	}
-
-	async fn optimize_proof<TargetChain: Chain>(
-		target_client: &Client<TargetChain>,
-		header: &C::Header,
-		proof: Self::FinalityProof,
-	) -> Result<Self::FinalityProof, SubstrateError> {
-		let current_authority_set_key = bp_header_chain::storage_keys::current_authority_set_key(
-			C::WITH_CHAIN_GRANDPA_PALLET_NAME,
-		);
-		let (authority_set, authority_set_id): (
-			sp_consensus_grandpa::AuthorityList,
-			sp_consensus_grandpa::SetId,
-		) = target_client
-			.storage_value(current_authority_set_key, None)
-			.await?
-			.map(Ok)
-			.unwrap_or(Err(SubstrateError::Custom(format!(
-				"{} `CurrentAuthoritySet` is missing from the {} storage",
-				C::NAME,
-				TargetChain::NAME,
-			))))?;
-		let authority_set =
-			finality_grandpa::voter_set::VoterSet::new(authority_set).expect("TODO");
-		// we're risking with race here - we have decided to submit justification some time ago and
-		// actual authorities set (which we have read now) may have changed, so this
-		// `optimize_justification` may fail. But if target chain is configured properly, it'll fail
-		// anyway, after we submit transaction and failing earlier is better. So - it is fine
-		verify_and_optimize_justification(
-			(header.hash(), *header.number()),
-			authority_set_id,
-			&authority_set,
-			proof,
-		)
-		.map_err(|e| {
-			SubstrateError::Custom(format!(
-				"Failed to optimize {} GRANDPA jutification for header {:?}: {:?}",
-				C::NAME,
-				header.id(),
-				e,
-			))
-		})
+	/// Read GRANDPA authorities set id at given header.
+	async fn source_authorities_set_id(
+		source_client: &Client<C>,
+		header_hash: C::Hash,
+	) -> Result<SetId, Error<HashOf<C>, BlockNumberOf<C>>> {
+		source_client
+			.storage_value(
+				bp_runtime::storage_value_key(GRANDPA_PALLET_NAME, "CurrentSetId"),
+				Some(header_hash),
+			)
+			.await
+			.map_err(|err| Error::RetrieveAuthoritiesSetId(C::NAME, header_hash, err))?
+			.ok_or(Error::MissingAuthoritiesSetId(C::NAME, header_hash))
 	}
 
-	/// Prepare initialization data for the GRANDPA verifier pallet.
-	async fn prepare_initialization_data(
-		source_client: Client<C>,
-	) -> Result<Self::InitializationData, Error<HashOf<C>, BlockNumberOf<C>>> {
-		// In ideal world we just need to get best finalized header and then to read GRANDPA
-		// authorities set (`pallet_grandpa::CurrentSetId` + `GrandpaApi::grandpa_authorities()`) at
-		// this header.
-		//
-		// But now there are problems with this approach - `CurrentSetId` may return invalid value.
-		// So here we're waiting for the next justification, read the authorities set and then try
-		// to figure out the set id with bruteforce.
-		let justifications = Self::finality_proofs(&source_client)
+	/// Read next GRANDPA justification from the justifications subscription.
+	async fn next_justification(
+		source_client: &Client<C>,
+	) -> Result<GrandpaJustification<C::Header>, Error<HashOf<C>, BlockNumberOf<C>>> {
+		let justifications = Self::finality_proofs(source_client)
 			.await
 			.map_err(|err| Error::Subscribe(C::NAME, err))?;
-		// Read next justification - the header that it finalizes will be used as initial header.
 		let justification = justifications
 			.next()
 			.await
@@ -211,15 +180,54 @@ impl<C: ChainWithGrandpa> Engine<C> for Grandpa<C> {
 				justification.ok_or(Error::ReadJustificationStreamEnded(C::NAME))
 			})?;
 
-		// Read initial header.
-		let justification: GrandpaJustification<C::Header> =
-			Decode::decode(&mut &justification.0[..])
-				.map_err(|err| Error::DecodeJustification(C::NAME, err))?;
+		Decode::decode(&mut &justification.0[..])
+			.map_err(|err| Error::DecodeJustification(C::NAME, err))
+	}
 
+	/// Read GRANDPA justification of given header, if it is stored by the source node.
+	async fn stored_justification(
+		source_client: &Client<C>,
+		header_hash: C::Hash,
+	) -> Result<Option<GrandpaJustification<C::Header>>, Error<HashOf<C>, BlockNumberOf<C>>> {
+		let block = source_client
+			.get_block(Some(header_hash))
+			.await
+			.map_err(|err| Error::RetrieveHeader(C::NAME, header_hash, err))?;
+		block
+			.justification(GRANDPA_ENGINE_ID)
+			.map(|justification| {
+				Decode::decode(&mut &justification[..])
+					.map_err(|err| Error::DecodeJustification(C::NAME, err))
+			})
+			.transpose()
+	}
+
+	/// Returns scheduled GRANDPA authorities set change, if it is signalled by the header.
+	///
+	/// Panics if the change is scheduled with non-zero delay.
+	fn scheduled_change(header: &C::Header) -> Option<ScheduledChange<BlockNumberOf<C>>> {
+		let scheduled_change =
+			GrandpaConsensusLogReader::<BlockNumberOf<C>>::find_authorities_change(header.digest());
+		assert!(
+			scheduled_change.as_ref().map(|c| c.delay.is_zero()).unwrap_or(true),
+			"GRANDPA authorities change at {} scheduled to happen in {:?} blocks. We expect\
+			regular change to have zero delay",
+			header.hash(),
+			scheduled_change.as_ref().map(|c| c.delay),
+		);
+		scheduled_change
+	}
+
+	/// Prepare initialization data using header, finalized by given justification.
+	async fn initialization_data_from_justification(
+		source_client: &Client<C>,
+		justification: GrandpaJustification<C::Header>,
+	) -> Result<bp_header_chain::InitializationData<C::Header>, Error<HashOf<C>, BlockNumberOf<C>>>
+	{
 		let (initial_header_hash, initial_header_number) =
 			(justification.commit.target_hash, justification.commit.target_number);
 
-		let initial_header = Self::source_header(&source_client, initial_header_hash).await?;
+		let initial_header = Self::source_header(source_client, initial_header_hash).await?;
 		log::trace!(target: "bridge", "Selected {} initial header: {}/{}",
 			C::NAME,
 			initial_header_number,
@@ -228,7 +236,7 @@ impl<C: ChainWithGrandpa> Engine<C> for Grandpa<C> {
 
 		// Read GRANDPA authorities set at initial header.
 		let initial_authorities_set =
-			Self::source_authorities_set(&source_client, initial_header_hash).await?;
+			Self::source_authorities_set(source_client, initial_header_hash).await?;
 		log::trace!(target: "bridge", "Selected {} initial authorities set: {:?}",
 			C::NAME,
 			initial_authorities_set,
@@ -237,21 +245,10 @@ impl<C: ChainWithGrandpa> Engine<C> for Grandpa<C> {
 		// If initial header changes the GRANDPA authorities set, then we need previous authorities
 		// to verify justification.
 		let mut authorities_for_verification = initial_authorities_set.clone();
-		let scheduled_change =
-			GrandpaConsensusLogReader::<BlockNumberOf<C>>::find_authorities_change(
-				initial_header.digest(),
-			);
-		assert!(
-			scheduled_change.as_ref().map(|c| c.delay.is_zero()).unwrap_or(true),
-			"GRANDPA authorities change at {} scheduled to happen in {:?} blocks. We expect\
-			regular change to have zero delay",
-			initial_header_hash,
-			scheduled_change.as_ref().map(|c| c.delay),
-		);
-		let schedules_change = scheduled_change.is_some();
+		let schedules_change = Self::scheduled_change(&initial_header).is_some();
 		if schedules_change {
 			authorities_for_verification =
-				Self::source_authorities_set(&source_client, *initial_header.parent_hash()).await?;
+				Self::source_authorities_set(source_client, *initial_header.parent_hash()).await?;
 			log::trace!(
 				target: "bridge",
 				"Selected {} header is scheduling GRANDPA authorities set changes. Using previous set: {:?}",
@@ -305,4 +302,156 @@ impl<C: ChainWithGrandpa> Engine<C> for Grandpa<C> {
 			operating_mode: BasicOperatingMode::Normal,
 		})
 	}
+
+	/// Prepare initialization data using given header and GRANDPA authorities set id, read from
+	/// the runtime storage at this header.
+	async fn initialization_data_from_storage(
+		source_client: &Client<C>,
+		initial_header_hash: C::Hash,
+	) -> Result<bp_header_chain::InitializationData<C::Header>, Error<HashOf<C>, BlockNumberOf<C>>>
+	{
+		let initial_header = Self::source_header(source_client, initial_header_hash).await?;
+		log::trace!(target: "bridge", "Selected {} initial header: {}/{}",
+			C::NAME,
+			initial_header.number(),
+			initial_header_hash,
+		);
+
+		// if the header schedules (regular) authorities set change, then both values are read
+		// after the change is enacted, so they are consistent with each other
+		Self::scheduled_change(&initial_header);
+		let initial_authorities_set =
+			Self::source_authorities_set(source_client, initial_header_hash).await?;
+		let initial_authorities_set_id =
+			Self::source_authorities_set_id(source_client, initial_header_hash).await?;
+		log::trace!(target: "bridge", "Selected {} initial authorities set: {:?}, set id: {}",
+			C::NAME,
+			initial_authorities_set,
+			initial_authorities_set_id,
+		);
+
+		Ok(bp_header_chain::InitializationData {
+			header: Box::new(initial_header),
+			authority_list: initial_authorities_set,
+			set_id: initial_authorities_set_id,
+			operating_mode: BasicOperatingMode::Normal,
+		})
+	}
+}
+
+#[async_trait]
+impl<C: ChainWithGrandpa> Engine<C> for Grandpa<C> {
+	const ID: ConsensusEngineId = GRANDPA_ENGINE_ID;
+	type ConsensusLogReader = GrandpaConsensusLogReader<<C::Header as Header>::Number>;
+	type FinalityClient = SubstrateGrandpaFinalityClient;
+	type FinalityProof = GrandpaJustification<HeaderOf<C>>;
+	type InitializationData = bp_header_chain::InitializationData<C::Header>;
+	type OperatingMode = BasicOperatingMode;
+
+	fn is_initialized_key() -> StorageKey {
+		bp_header_chain::storage_keys::best_finalized_key(C::WITH_CHAIN_GRANDPA_PALLET_NAME)
+	}
+
+	fn pallet_operating_mode_key() -> StorageKey {
+		bp_header_chain::storage_keys::pallet_operating_mode_key(C::WITH_CHAIN_GRANDPA_PALLET_NAME)
+	}
+
+	async fn prove_finality(
+		client: &Client<C>,
+		number: BlockNumberOf<C>,
+	) -> Result<Option<Self::FinalityProof>, SubstrateError> {
+		let encoded_proof = match client.prove_grandpa_finality(number).await? {
+			Some(encoded_proof) => encoded_proof,
+			None => return Ok(None),
+		};
+
+		// the node returns encoded `sc_consensus_grandpa::FinalityProof`, which starts with the
+		// hash of the finalized block, followed by the encoded justification of this block
+		let (_, encoded_justification): (HashOf<C>, Vec<u8>) =
+			Decode::decode(&mut &encoded_proof.0[..])
+				.map_err(SubstrateError::ResponseParseFailed)?;
+		GrandpaJustification::decode(&mut &encoded_justification[..])
+			.map(Some)
+			.map_err(SubstrateError::ResponseParseFailed)
+	}
+
+	async fn optimize_proof<TargetChain: Chain>(
+		target_client: &Client<TargetChain>,
+		header: &C::Header,
+		proof: Self::FinalityProof,
+	) -> Result<Self::FinalityProof, SubstrateError> {
+		let current_authority_set_key = bp_header_chain::storage_keys::current_authority_set_key(
+			C::WITH_CHAIN_GRANDPA_PALLET_NAME,
+		);
+		let (authority_set, authority_set_id): (
+			sp_consensus_grandpa::AuthorityList,
+			sp_consensus_grandpa::SetId,
+		) = target_client
+			.storage_value(current_authority_set_key, None)
+			.await?
+			.map(Ok)
+			.unwrap_or(Err(SubstrateError::Custom(format!(
+				"{} `CurrentAuthoritySet` is missing from the {} storage",
+				C::NAME,
+				TargetChain::NAME,
+			))))?;
+		let authority_set =
+			finality_grandpa::voter_set::VoterSet::new(authority_set).expect("TODO");
+		// we're risking with race here - we have decided to submit justification some time ago and
+		// actual authorities set (which we have read now) may have changed, so this
+		// `optimize_justification` may fail. But if target chain is configured properly, it'll fail
+		// anyway, after we submit transaction and failing earlier is better. So - it is fine
+		verify_and_optimize_justification(
+			(header.hash(), *header.number()),
+			authority_set_id,
+			&authority_set,
+			proof,
+		)
+		.map_err(|e| {
+			SubstrateError::Custom(format!(
+				"Failed to optimize {} GRANDPA jutification for header {:?}: {:?}",
+				C::NAME,
+				header.id(),
+				e,
+			))
+		})
+	}
+
+	/// Prepare initialization data for the GRANDPA verifier pallet.
+	async fn prepare_initialization_data(
+		source_client: Client<C>,
+		at_block: Option<HashOf<C>>,
+	) -> Result<Self::InitializationData, Error<HashOf<C>, BlockNumberOf<C>>> {
+		// In ideal world we just need to get best finalized header and then to read GRANDPA
+		// authorities set (`pallet_grandpa::CurrentSetId` + `GrandpaApi::grandpa_authorities()`) at
+		// this header.
+		//
+		// But now there are problems with this approach - `CurrentSetId` may return invalid value.
+		// So here we're waiting for the next justification, read the authorities set and then try
+		// to figure out the set id with bruteforce.
+		let at_block = match at_block {
+			Some(at_block) => at_block,
+			None => {
+				let justification = Self::next_justification(&source_client).await?;
+				return Self::initialization_data_from_justification(&source_client, justification)
+					.await
+			},
+		};
+
+		// If the node has justification of the requested header, we still prefer to guess the set
+		// id. Otherwise we have no other option than to trust the `CurrentSetId` value.
+		match Self::stored_justification(&source_client, at_block).await? {
+			Some(justification) =>
+				Self::initialization_data_from_justification(&source_client, justification).await,
+			None => {
+				log::info!(
+					target: "bridge",
+					"{} node has no justification for header {}. Reading authorities set id from the storage",
+					C::NAME,
+					at_block,
+				);
+				Self::initialization_data_from_storage(&source_client, at_block).await
+			},
+		}
+	}
 }
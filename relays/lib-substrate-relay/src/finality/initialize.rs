@@ -26,13 +26,16 @@ use sp_core::Pair;
 
 use bp_runtime::HeaderIdOf;
 use relay_substrate_client::{
-	AccountKeyPairOf, Chain, ChainWithTransactions, Client, Error as SubstrateError,
+	AccountKeyPairOf, Chain, ChainWithTransactions, Client, Error as SubstrateError, HashOf,
 	UnsignedTransaction,
 };
 use relay_utils::{TrackedTransactionStatus, TransactionTracker};
 use sp_runtime::traits::Header as HeaderT;
 
 /// Submit headers-bridge initialization transaction.
+///
+/// If `at_block` is specified, the bridge is initialized with this (finalized) source header.
+/// Otherwise, the header finalized by the next source chain justification is used.
 pub async fn initialize<
 	E: Engine<SourceChain>,
 	SourceChain: Chain,
@@ -42,6 +45,7 @@ pub async fn initialize<
 	source_client: Client<SourceChain>,
 	target_client: Client<TargetChain>,
 	target_signer: AccountKeyPairOf<TargetChain>,
+	at_block: Option<HashOf<SourceChain>>,
 	prepare_initialize_transaction: F,
 	dry_run: bool,
 ) where
@@ -57,6 +61,7 @@ pub async fn initialize<
 		source_client,
 		target_client,
 		target_signer,
+		at_block,
 		prepare_initialize_transaction,
 		dry_run,
 	)
@@ -82,6 +87,7 @@ pub async fn initialize<
 					tx_status
 				)
 			},
+			TrackedTransactionStatus::DryRun => (),
 		},
 		Ok(None) => (),
 		Err(err) => log::error!(
@@ -104,6 +110,7 @@ async fn do_initialize<
 	source_client: Client<SourceChain>,
 	target_client: Client<TargetChain>,
 	target_signer: AccountKeyPairOf<TargetChain>,
+	at_block: Option<HashOf<SourceChain>>,
 	prepare_initialize_transaction: F,
 	dry_run: bool,
 ) -> Result<
@@ -134,7 +141,7 @@ where
 		}
 	}
 
-	let initialization_data = E::prepare_initialization_data(source_client).await?;
+	let initialization_data = E::prepare_initialization_data(source_client, at_block).await?;
 	log::info!(
 		target: "bridge",
 		"Prepared initialization data for {}-headers bridge at {}: {:?}",
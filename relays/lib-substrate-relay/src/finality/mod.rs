@@ -18,6 +18,7 @@
 //! finality proofs synchronization pipelines.
 
 use crate::{
+	fee_metrics::FeeMetrics,
 	finality::{
 		engine::Engine,
 		source::{SubstrateFinalityProof, SubstrateFinalitySource},
@@ -34,7 +35,7 @@ use relay_substrate_client::{
 	transaction_stall_timeout, AccountIdOf, AccountKeyPairOf, BlockNumberOf, CallOf, Chain,
 	ChainWithTransactions, Client, HashOf, HeaderOf, SyncHeader,
 };
-use relay_utils::metrics::MetricsParams;
+use relay_utils::metrics::{Metric, MetricsParams};
 use sp_core::Pair;
 use std::{fmt::Debug, marker::PhantomData};
 
@@ -185,9 +186,17 @@ where
 		P::TargetChain::NAME,
 	);
 
+	let fee_metrics =
+		FeeMetrics::new(Some(&finality_relay::metrics_prefix::<FinalitySyncPipelineAdapter<P>>()))?;
+	fee_metrics.register(&metrics_params.registry)?;
+
 	finality_relay::run(
 		SubstrateFinalitySource::<P>::new(source_client, None),
-		SubstrateFinalityTarget::<P>::new(target_client, transaction_params.clone()),
+		SubstrateFinalityTarget::<P>::new(
+			target_client,
+			transaction_params.clone(),
+			Some(fee_metrics),
+		),
 		finality_relay::FinalitySyncParams {
 			tick: std::cmp::max(
 				P::SourceChain::AVERAGE_BLOCK_INTERVAL,
@@ -232,6 +232,13 @@ impl<P: SubstrateFinalitySyncPipeline> SourceClient<FinalitySyncPipelineAdapter<
 		header_and_finality_proof::<P>(&self.client, number).await
 	}
 
+	async fn prove_finality(
+		&self,
+		number: BlockNumberOf<P::SourceChain>,
+	) -> Result<Option<SubstrateFinalityProof<P>>, Error> {
+		P::FinalityEngine::prove_finality(&self.client, number).await
+	}
+
 	async fn finality_proofs(&self) -> Result<Self::FinalityProofsStream, Error> {
 		Ok(unfold(
 			P::FinalityEngine::finality_proofs(&self.client).await?,
@@ -17,18 +17,18 @@
 //! Substrate client as Substrate finality proof target.
 
 use crate::{
+	fee_metrics::{FeeMetrics, FeeTrackingTransactionTracker, TransactionKind},
 	finality::{
 		engine::Engine, source::SubstrateFinalityProof, FinalitySyncPipelineAdapter,
 		SubmitFinalityProofCallBuilder, SubstrateFinalitySyncPipeline,
 	},
-	TransactionParams,
+	submit_and_watch_call, TransactionParams,
 };
 
 use async_trait::async_trait;
 use finality_relay::TargetClient;
 use relay_substrate_client::{
-	AccountIdOf, AccountKeyPairOf, Client, Error, HeaderIdOf, HeaderOf, SyncHeader, TransactionEra,
-	TransactionTracker, UnsignedTransaction,
+	AccountIdOf, AccountKeyPairOf, Client, Error, HeaderIdOf, HeaderOf, SyncHeader,
 };
 use relay_utils::relay_loop::Client as RelayClient;
 use sp_core::Pair;
@@ -37,6 +37,7 @@ use sp_core::Pair;
 pub struct SubstrateFinalityTarget<P: SubstrateFinalitySyncPipeline> {
 	client: Client<P::TargetChain>,
 	transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+	fee_metrics: Option<FeeMetrics>,
 }
 
 impl<P: SubstrateFinalitySyncPipeline> SubstrateFinalityTarget<P> {
@@ -44,8 +45,9 @@ impl<P: SubstrateFinalitySyncPipeline> SubstrateFinalityTarget<P> {
 	pub fn new(
 		client: Client<P::TargetChain>,
 		transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+		fee_metrics: Option<FeeMetrics>,
 	) -> Self {
-		SubstrateFinalityTarget { client, transaction_params }
+		SubstrateFinalityTarget { client, transaction_params, fee_metrics }
 	}
 
 	/// Ensure that the bridge pallet at target chain is active.
@@ -69,6 +71,7 @@ impl<P: SubstrateFinalitySyncPipeline> Clone for SubstrateFinalityTarget<P> {
 		SubstrateFinalityTarget {
 			client: self.client.clone(),
 			transaction_params: self.transaction_params.clone(),
+			fee_metrics: self.fee_metrics.clone(),
 		}
 	}
 }
@@ -88,7 +91,7 @@ impl<P: SubstrateFinalitySyncPipeline> TargetClient<FinalitySyncPipelineAdapter<
 where
 	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
 {
-	type TransactionTracker = TransactionTracker<P::TargetChain, Client<P::TargetChain>>;
+	type TransactionTracker = FeeTrackingTransactionTracker<P::TargetChain>;
 
 	async fn best_finalized_source_block_id(&self) -> Result<HeaderIdOf<P::SourceChain>, Error> {
 		// we can't continue to relay finality if target node is out of sync, because
@@ -115,17 +118,15 @@ where
 		let proof = P::FinalityEngine::optimize_proof(&self.client, &header, proof).await?;
 
 		// now we may submit optimized finality proof
-		let transaction_params = self.transaction_params.clone();
 		let call =
 			P::SubmitFinalityProofCallBuilder::build_submit_finality_proof_call(header, proof);
-		self.client
-			.submit_and_watch_signed_extrinsic(
-				&self.transaction_params.signer,
-				move |best_block_id, transaction_nonce| {
-					Ok(UnsignedTransaction::new(call.into(), transaction_nonce)
-						.era(TransactionEra::new(best_block_id, transaction_params.mortality)))
-				},
-			)
-			.await
+		let tx_tracker =
+			submit_and_watch_call(&self.client, &self.transaction_params, call).await?;
+		Ok(FeeTrackingTransactionTracker::new(
+			tx_tracker,
+			self.fee_metrics.clone(),
+			TransactionKind::Finality,
+			0,
+		))
 	}
 }
@@ -18,11 +18,18 @@
 
 #![warn(missing_docs)]
 
-use relay_substrate_client::{Chain, ChainWithUtilityPallet, UtilityPallet};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, CallOf, Chain, ChainWithTransactions, ChainWithUtilityPallet,
+	Client, Error as SubstrateError, TransactionEra, TransactionTracker, UnsignedTransaction,
+	UtilityPallet,
+};
+use sp_core::Pair;
 
 use std::marker::PhantomData;
 
+pub mod beefy;
 pub mod error;
+pub mod fee_metrics;
 pub mod finality;
 pub mod messages_lane;
 pub mod messages_metrics;
@@ -40,6 +47,35 @@ pub struct TransactionParams<TS> {
 	pub mortality: Option<u32>,
 }
 
+/// Submit bridge transaction with given call and start watching its status.
+///
+/// If the client is configured to submit unsigned transactions, the call is submitted as unsigned
+/// transaction and `transaction_params` are ignored. Otherwise the transaction is signed by the
+/// `transaction_params.signer`.
+pub async fn submit_and_watch_call<C: ChainWithTransactions>(
+	client: &Client<C>,
+	transaction_params: &TransactionParams<AccountKeyPairOf<C>>,
+	call: CallOf<C>,
+) -> Result<TransactionTracker<C, Client<C>>, SubstrateError>
+where
+	AccountIdOf<C>: From<<AccountKeyPairOf<C> as Pair>::Public>,
+{
+	if client.submits_unsigned_transactions() {
+		return client.submit_and_watch_unsigned_extrinsic(call).await
+	}
+
+	let mortality = transaction_params.mortality;
+	client
+		.submit_and_watch_signed_extrinsic(
+			&transaction_params.signer,
+			move |best_block_id, transaction_nonce| {
+				Ok(UnsignedTransaction::new(call.into(), transaction_nonce)
+					.era(TransactionEra::new(best_block_id, mortality)))
+			},
+		)
+		.await
+}
+
 /// Tagged relay account, which balance may be exposed as metrics by the relay.
 #[derive(Clone, Debug)]
 pub enum TaggedAccount<AccountId> {
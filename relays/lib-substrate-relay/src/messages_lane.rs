@@ -17,14 +17,18 @@
 //! Tools for supporting message lanes between two Substrate-based chains.
 
 use crate::{
-	messages_source::{SubstrateMessagesProof, SubstrateMessagesSource},
+	fee_metrics::FeeMetrics,
+	messages_source::{read_client_state, SubstrateMessagesProof, SubstrateMessagesSource},
 	messages_target::{SubstrateMessagesDeliveryProof, SubstrateMessagesTarget},
 	on_demand::OnDemandRelay,
 	BatchCallBuilder, BatchCallBuilderConstructor, TransactionParams,
 };
 
 use async_std::sync::Arc;
-use bp_messages::{LaneId, MessageNonce};
+use bp_messages::{
+	storage_keys::{inbound_lane_data_key, outbound_lane_data_key},
+	InboundLaneData, LaneId, MessageNonce, MessagesDeliveryLimits, OutboundLaneData,
+};
 use bp_runtime::{
 	AccountIdOf, Chain as _, EncodedOrDecodedCall, HeaderIdOf, TransactionEra, WeightExtraOps,
 };
@@ -33,7 +37,12 @@ use bridge_runtime_common::messages::{
 };
 use codec::Encode;
 use frame_support::{dispatch::GetDispatchInfo, weights::Weight};
-use messages_relay::{message_lane::MessageLane, message_lane_loop::BatchTransaction};
+use messages_relay::{
+	lane_control::{DeliveryLimits, LaneControl},
+	message_lane::MessageLane,
+	message_lane_loop::BatchTransaction,
+	metrics::MessageLaneLoopMetrics,
+};
 use pallet_bridge_messages::{Call as BridgeMessagesCall, Config as BridgeMessagesConfig};
 use relay_substrate_client::{
 	transaction_stall_timeout, AccountKeyPairOf, BalanceOf, BlockNumberOf, CallOf, Chain,
@@ -41,12 +50,19 @@ use relay_substrate_client::{
 	UnsignedTransaction,
 };
 use relay_utils::{
-	metrics::{GlobalMetrics, MetricsParams, StandaloneMetric},
-	STALL_TIMEOUT,
+	metrics::{GlobalMetrics, Metric, MetricsParams, StandaloneMetric},
+	relay_loop::Client as RelayClient,
+	MaybeConnectionError, STALL_TIMEOUT,
 };
 use sp_core::Pair;
 use sp_runtime::traits::Zero;
-use std::{convert::TryFrom, fmt::Debug, marker::PhantomData};
+use std::{
+	collections::BTreeSet, convert::TryFrom, fmt::Debug, marker::PhantomData, time::Duration,
+};
+
+/// If the oldest undelivered message is not delivered for this period, the lane is considered
+/// stalled. Used when `AlertParams::max_undelivered_message_age` is not set.
+pub const DEFAULT_MAX_UNDELIVERED_MESSAGE_AGE: Duration = Duration::from_secs(10 * 60);
 
 /// Substrate -> Substrate messages synchronization pipeline.
 pub trait SubstrateMessageLane: 'static + Clone + Debug + Send + Sync {
@@ -105,10 +121,72 @@ pub struct MessagesRelayParams<P: SubstrateMessageLane> {
 		Option<Arc<dyn OnDemandRelay<P::TargetChain, P::SourceChain>>>,
 	/// Identifier of lane that needs to be served.
 	pub lane_id: LaneId,
+	/// If set, the relay won't be delivering messages for that period after it has seen messages
+	/// delivered by other relayers.
+	pub cooperative_backoff: Option<Duration>,
+	/// If set, the relay won't be delivering messages with lower nonces.
+	pub start_from_nonce: Option<MessageNonce>,
+	/// Nonces of messages that the relay won't be delivering.
+	pub skip_nonces: BTreeSet<MessageNonce>,
+	/// If set, the relay switches to the historical backfill mode when the best source header,
+	/// known to the target chain, is at least that many blocks behind the best finalized source
+	/// header.
+	pub ancient_source_header_threshold: Option<u32>,
+	/// Delivery confirmations parameters.
+	pub confirmation_params: ConfirmationParams,
+	/// Lane alerts parameters.
+	pub alert_params: AlertParams,
 	/// Metrics parameters.
 	pub metrics_params: MetricsParams,
 }
 
+/// Messages delivery confirmations parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmationParams {
+	/// Minimal number of messages that must be confirmed by single confirmation transaction.
+	/// Smaller confirmation transactions are still submitted if the number of unconfirmed
+	/// messages approaches the limit, or if confirmations are waiting for too long.
+	pub min_confirmations_in_batch: MessageNonce,
+	/// Maximal period a delivery confirmation may wait for other confirmations.
+	pub max_confirmation_delay: Option<Duration>,
+}
+
+impl Default for ConfirmationParams {
+	fn default() -> Self {
+		ConfirmationParams { min_confirmations_in_batch: 1, max_confirmation_delay: None }
+	}
+}
+
+/// Lane alerts parameters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AlertParams {
+	/// If the oldest undelivered message is not delivered for this period, the lane is considered
+	/// stalled. Defaults to [`DEFAULT_MAX_UNDELIVERED_MESSAGE_AGE`].
+	pub max_undelivered_message_age: Option<Duration>,
+	/// If number of delivered, but not yet confirmed messages exceeds this value, confirmations
+	/// are considered lagging. Defaults to the half of the number of unconfirmed messages that
+	/// the target chain accepts.
+	pub max_unconfirmed_messages: Option<MessageNonce>,
+}
+
+impl AlertParams {
+	/// Returns lane alert parameters, given the maximal number of unconfirmed messages at the
+	/// target chain.
+	fn lane_alert_params(
+		&self,
+		max_unconfirmed_messages_at_target: MessageNonce,
+	) -> messages_relay::message_lane_loop::LaneAlertParams {
+		messages_relay::message_lane_loop::LaneAlertParams {
+			max_undelivered_message_age: self
+				.max_undelivered_message_age
+				.unwrap_or(DEFAULT_MAX_UNDELIVERED_MESSAGE_AGE),
+			max_unconfirmed_messages: self
+				.max_unconfirmed_messages
+				.unwrap_or(max_unconfirmed_messages_at_target / 2),
+		}
+	}
+}
+
 /// Batch transaction that brings headers + and messages delivery/receiving confirmations to the
 /// source node.
 #[derive(Clone)]
@@ -174,24 +252,48 @@ where
 	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
 	BalanceOf<P::SourceChain>: TryFrom<BalanceOf<P::TargetChain>>,
 {
-	// 2/3 is reserved for proofs and tx overhead
-	let max_messages_size_in_single_batch = P::TargetChain::max_extrinsic_size() / 3;
-	// we don't know exact weights of the Polkadot runtime. So to guess weights we'll be using
-	// weights from Rialto and then simply dividing it by x2.
-	let (max_messages_in_single_batch, max_messages_weight_in_single_batch) =
-		select_delivery_transaction_limits_rpc::<P>(
-			&params,
-			P::TargetChain::max_extrinsic_weight(),
-			P::SourceChain::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX,
-		)
-		.await?;
-	let (max_messages_in_single_batch, max_messages_weight_in_single_batch) =
-		(max_messages_in_single_batch / 2, max_messages_weight_in_single_batch / 2);
+	let relayer_id_at_source: AccountIdOf<P::SourceChain> =
+		params.source_transaction_params.signer.public().into();
+
+	// limits are read from the target runtime. If they're changed by the runtime upgrade, updated
+	// limits are fed into the running message lane loop
+	let (delivery_limits, are_delivery_limits_actual) =
+		match read_messages_delivery_limits::<P>(&params.target_client).await {
+			Ok(delivery_limits) => (delivery_limits, true),
+			Err(e) => (bundled_messages_delivery_limits_on_error::<P>(e), false),
+		};
+	let DeliveryLimits {
+		max_unrewarded_relayer_entries_at_target,
+		max_unconfirmed_nonces_at_target,
+		max_messages_in_single_batch,
+		max_messages_weight_in_single_batch,
+		max_messages_size_in_single_batch,
+	} = select_delivery_limits::<P>(
+		&params.target_client,
+		&relayer_id_at_source,
+		&params.target_transaction_params.signer,
+		delivery_limits,
+	)
+	.await?;
+	start_messages_delivery_limits_watcher::<P>(
+		params.target_client.clone(),
+		relayer_id_at_source.clone(),
+		params.target_transaction_params.signer.clone(),
+		messages_relay::lane_control::register(
+			params.lane_id,
+			P::SourceChain::NAME,
+			P::TargetChain::NAME,
+		),
+		delivery_limits,
+		are_delivery_limits_actual,
+	);
 
 	let source_client = params.source_client;
 	let target_client = params.target_client;
-	let relayer_id_at_source: AccountIdOf<P::SourceChain> =
-		params.source_transaction_params.signer.public().into();
+	let fee_metrics = FeeMetrics::new(Some(&messages_relay::message_lane_loop::metrics_prefix::<
+		MessageLaneAdapter<P>,
+	>(&params.lane_id)))?;
+	fee_metrics.register(&params.metrics_params.registry)?;
 
 	log::info!(
 		target: "bridge",
@@ -222,6 +324,27 @@ where
 		).as_secs_f64() / 60.0f64,
 	);
 
+	if let Some(start_from_nonce) = params.start_from_nonce {
+		log::warn!(
+			target: "bridge",
+			"{} -> {} messages relay won't deliver messages with nonces below {}. The lane is \
+			stalled until they are delivered by other relayers",
+			P::SourceChain::NAME,
+			P::TargetChain::NAME,
+			start_from_nonce,
+		);
+	}
+	if !params.skip_nonces.is_empty() {
+		log::warn!(
+			target: "bridge",
+			"{} -> {} messages relay won't deliver messages with nonces {:?}. The lane is \
+			stalled until they are delivered by other relayers",
+			P::SourceChain::NAME,
+			P::TargetChain::NAME,
+			params.skip_nonces,
+		);
+	}
+
 	messages_relay::message_lane_loop::run(
 		messages_relay::message_lane_loop::Params {
 			lane: params.lane_id,
@@ -229,14 +352,22 @@ where
 			target_tick: P::TargetChain::AVERAGE_BLOCK_INTERVAL,
 			reconnect_delay: relay_utils::relay_loop::RECONNECT_DELAY,
 			delivery_params: messages_relay::message_lane_loop::MessageDeliveryParams {
-				max_unrewarded_relayer_entries_at_target:
-					P::SourceChain::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX,
-				max_unconfirmed_nonces_at_target:
-					P::SourceChain::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX,
+				max_unrewarded_relayer_entries_at_target,
+				max_unconfirmed_nonces_at_target,
 				max_messages_in_single_batch,
 				max_messages_weight_in_single_batch,
 				max_messages_size_in_single_batch,
+				cooperative_backoff: params.cooperative_backoff,
+				start_from_nonce: params.start_from_nonce,
+				skip_nonces: params.skip_nonces,
+				ancient_source_header_threshold: params.ancient_source_header_threshold,
 			},
+			receiving_params: messages_relay::message_lane_loop::MessageReceivingParams {
+				min_confirmations_in_batch: params.confirmation_params.min_confirmations_in_batch,
+				force_at_unconfirmed_nonces: max_unconfirmed_nonces_at_target / 2,
+				max_confirmation_delay: params.confirmation_params.max_confirmation_delay,
+			},
+			alert_params: params.alert_params.lane_alert_params(max_unconfirmed_nonces_at_target),
 		},
 		SubstrateMessagesSource::<P>::new(
 			source_client.clone(),
@@ -244,6 +375,7 @@ where
 			params.lane_id,
 			params.source_transaction_params,
 			params.target_to_source_headers_relay,
+			Some(fee_metrics.clone()),
 		),
 		SubstrateMessagesTarget::<P>::new(
 			target_client,
@@ -252,6 +384,7 @@ where
 			relayer_id_at_source,
 			params.target_transaction_params,
 			params.source_to_target_headers_relay,
+			Some(fee_metrics),
 		),
 		{
 			GlobalMetrics::new()?.register_and_spawn(&params.metrics_params.registry)?;
@@ -263,6 +396,128 @@ where
 	.map_err(Into::into)
 }
 
+/// Substrate <-> Substrate messages lane monitor parameters.
+pub struct MessagesMonitorParams<P: SubstrateMessageLane> {
+	/// Messages source client.
+	pub source_client: Client<P::SourceChain>,
+	/// Messages target client.
+	pub target_client: Client<P::TargetChain>,
+	/// Identifier of lane that needs to be monitored.
+	pub lane_id: LaneId,
+	/// Lane alerts parameters.
+	pub alert_params: AlertParams,
+	/// Metrics parameters. Metrics are registered in the registry, but exposing them is up to
+	/// the caller.
+	pub metrics_params: MetricsParams,
+}
+
+/// Run Substrate-to-Substrate messages lane monitor.
+///
+/// The monitor tracks the lane state and updates the same metrics as the messages relay (see
+/// [`run`]), so existing dashboards and alerts may be used. It never signs or submits any
+/// transactions.
+pub async fn monitor<P: SubstrateMessageLane>(
+	params: MessagesMonitorParams<P>,
+) -> anyhow::Result<()> {
+	let MessagesMonitorParams {
+		mut source_client,
+		mut target_client,
+		lane_id,
+		alert_params,
+		metrics_params,
+	} = params;
+
+	let delivery_limits = messages_delivery_limits::<P>(&target_client).await;
+	let metrics = MessageLaneLoopMetrics::new(
+		Some(&messages_relay::message_lane_loop::metrics_prefix::<MessageLaneAdapter<P>>(&lane_id)),
+		alert_params.lane_alert_params(delivery_limits.max_unconfirmed_messages_at_inbound_lane),
+	)?;
+	metrics.register(&metrics_params.registry)?;
+
+	log::info!(
+		target: "bridge",
+		"Starting {} -> {} messages lane {:?} monitor",
+		P::SourceChain::NAME,
+		P::TargetChain::NAME,
+		lane_id,
+	);
+
+	loop {
+		if let Err(e) =
+			update_lane_metrics::<P>(&source_client, &target_client, lane_id, &metrics).await
+		{
+			log::warn!(
+				target: "bridge",
+				"Failed to update {} -> {} messages lane {:?} metrics: {}",
+				P::SourceChain::NAME,
+				P::TargetChain::NAME,
+				lane_id,
+				e,
+			);
+
+			if e.is_connection_error() {
+				if let Err(e) = source_client.reconnect().await {
+					log::warn!(
+						target: "bridge",
+						"Failed to reconnect to {}: {}",
+						P::SourceChain::NAME,
+						e,
+					);
+				}
+				if let Err(e) = target_client.reconnect().await {
+					log::warn!(
+						target: "bridge",
+						"Failed to reconnect to {}: {}",
+						P::TargetChain::NAME,
+						e,
+					);
+				}
+			}
+		}
+
+		async_std::task::sleep(P::SourceChain::AVERAGE_BLOCK_INTERVAL).await;
+	}
+}
+
+/// Read the lane state from both chains and update lane metrics.
+async fn update_lane_metrics<P: SubstrateMessageLane>(
+	source_client: &Client<P::SourceChain>,
+	target_client: &Client<P::TargetChain>,
+	lane_id: LaneId,
+	metrics: &MessageLaneLoopMetrics,
+) -> Result<(), SubstrateError> {
+	let source_state =
+		read_client_state::<P::SourceChain, P::TargetChain>(source_client, Some(target_client))
+			.await?;
+	let target_state =
+		read_client_state::<P::TargetChain, P::SourceChain>(target_client, Some(source_client))
+			.await?;
+
+	let outbound_lane_data: Option<OutboundLaneData> = source_client
+		.storage_value(
+			outbound_lane_data_key(P::TargetChain::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane_id),
+			Some(source_state.best_self.1),
+		)
+		.await?;
+	let inbound_lane_data: Option<InboundLaneData<AccountIdOf<P::SourceChain>>> = target_client
+		.storage_value(
+			inbound_lane_data_key(P::SourceChain::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane_id),
+			Some(target_state.best_self.1),
+		)
+		.await?;
+	let outbound_lane_data = outbound_lane_data.unwrap_or_default();
+	let inbound_lane_data = inbound_lane_data.unwrap_or_default();
+
+	metrics.update_source_state::<MessageLaneAdapter<P>>(source_state);
+	metrics.update_target_state::<MessageLaneAdapter<P>>(target_state);
+	metrics.update_source_latest_generated_nonce(outbound_lane_data.latest_generated_nonce);
+	metrics.update_source_latest_confirmed_nonce(outbound_lane_data.latest_received_nonce);
+	metrics.update_target_latest_received_nonce(inbound_lane_data.last_delivered_nonce());
+	metrics.update_target_latest_confirmed_nonce(inbound_lane_data.last_confirmed_nonce);
+
+	Ok(())
+}
+
 /// Different ways of building `receive_messages_proof` calls.
 pub trait ReceiveMessagesProofCallBuilder<P: SubstrateMessageLane> {
 	/// Given messages proof, build call of `receive_messages_proof` function of bridge
@@ -452,15 +707,212 @@ macro_rules! generate_receive_message_delivery_proof_call_builder {
 	};
 }
 
+/// Read messages delivery limits from the target chain runtime.
+///
+/// If the target runtime doesn't support the `delivery_limits` runtime API method yet, limits
+/// are computed using constants, bundled with the relay.
+pub async fn messages_delivery_limits<P: SubstrateMessageLane>(
+	target_client: &Client<P::TargetChain>,
+) -> MessagesDeliveryLimits {
+	read_messages_delivery_limits::<P>(target_client)
+		.await
+		.unwrap_or_else(bundled_messages_delivery_limits_on_error::<P>)
+}
+
+/// Read messages delivery limits from the target chain runtime.
+async fn read_messages_delivery_limits<P: SubstrateMessageLane>(
+	target_client: &Client<P::TargetChain>,
+) -> Result<MessagesDeliveryLimits, SubstrateError> {
+	let delivery_limits = target_client
+		.typed_state_call::<_, MessagesDeliveryLimits>(
+			P::SourceChain::FROM_CHAIN_MESSAGES_DELIVERY_LIMITS_METHOD.into(),
+			(),
+			None,
+		)
+		.await?;
+	log::trace!(
+		target: "bridge",
+		"Read {} -> {} messages delivery limits from the {} runtime: {:?}",
+		P::SourceChain::NAME,
+		P::TargetChain::NAME,
+		P::TargetChain::NAME,
+		delivery_limits,
+	);
+	Ok(delivery_limits)
+}
+
+/// Returns messages delivery limits, computed using constants, bundled with the relay, after we
+/// have failed to read limits from the target chain runtime.
+fn bundled_messages_delivery_limits_on_error<P: SubstrateMessageLane>(
+	error: SubstrateError,
+) -> MessagesDeliveryLimits {
+	let delivery_limits = bundled_messages_delivery_limits::<P>();
+	log::warn!(
+		target: "bridge",
+		"Failed to read {} -> {} messages delivery limits from the {} runtime: {:?}. \
+		Using bundled limits: {:?}",
+		P::SourceChain::NAME,
+		P::TargetChain::NAME,
+		P::TargetChain::NAME,
+		error,
+		delivery_limits,
+	);
+	delivery_limits
+}
+
+/// Returns messages delivery limits, computed using constants, bundled with the relay.
+fn bundled_messages_delivery_limits<P: SubstrateMessageLane>() -> MessagesDeliveryLimits {
+	MessagesDeliveryLimits {
+		max_unrewarded_relayer_entries_at_inbound_lane:
+			P::SourceChain::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX,
+		max_unconfirmed_messages_at_inbound_lane:
+			P::SourceChain::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX,
+		max_extrinsic_size: P::TargetChain::max_extrinsic_size(),
+		max_extrinsic_weight: P::TargetChain::max_extrinsic_weight(),
+	}
+}
+
+/// Feed messages delivery limits, changed by the target runtime upgrade, into the running message
+/// lane loop.
+///
+/// Limits are read again after every target runtime upgrade. If `are_known_limits_actual` is
+/// `false` (i.e. the relay has failed to read limits from the runtime at startup and uses bundled
+/// limits), we keep reading limits until we succeed.
+fn start_messages_delivery_limits_watcher<P: SubstrateMessageLane>(
+	target_client: Client<P::TargetChain>,
+	relayer_id_at_source: AccountIdOf<P::SourceChain>,
+	target_signer: AccountKeyPairOf<P::TargetChain>,
+	control: LaneControl,
+	mut known_delivery_limits: MessagesDeliveryLimits,
+	mut are_known_limits_actual: bool,
+) {
+	async_std::task::spawn(async move {
+		let mut known_spec_version = None;
+		loop {
+			async_std::task::sleep(P::TargetChain::AVERAGE_BLOCK_INTERVAL * 10).await;
+
+			let spec_version = match target_client.runtime_version().await {
+				Ok(version) => version.spec_version,
+				Err(e) => {
+					log::warn!(
+						target: "bridge",
+						"Failed to read {} runtime version: {:?}. Messages delivery limits may be outdated",
+						P::TargetChain::NAME,
+						e,
+					);
+					continue
+				},
+			};
+			let is_runtime_upgraded =
+				known_spec_version.map(|known| known != spec_version).unwrap_or(false);
+			known_spec_version = Some(spec_version);
+			if are_known_limits_actual && !is_runtime_upgraded {
+				continue
+			}
+
+			let actual_delivery_limits =
+				match read_messages_delivery_limits::<P>(&target_client).await {
+					Ok(actual_delivery_limits) => actual_delivery_limits,
+					Err(e) => {
+						log::debug!(
+							target: "bridge",
+							"Failed to read {} -> {} messages delivery limits from the {} runtime: {:?}",
+							P::SourceChain::NAME,
+							P::TargetChain::NAME,
+							P::TargetChain::NAME,
+							e,
+						);
+						are_known_limits_actual = false;
+						continue
+					},
+				};
+			if actual_delivery_limits == known_delivery_limits {
+				are_known_limits_actual = true;
+				continue
+			}
+
+			match select_delivery_limits::<P>(
+				&target_client,
+				&relayer_id_at_source,
+				&target_signer,
+				actual_delivery_limits,
+			)
+			.await
+			{
+				Ok(delivery_limits) => {
+					log::info!(
+						target: "bridge",
+						"{} -> {} messages delivery limits have changed from {:?} to {:?} at {} \
+						runtime with spec_version {}. Using updated limits: {:?}",
+						P::SourceChain::NAME,
+						P::TargetChain::NAME,
+						known_delivery_limits,
+						actual_delivery_limits,
+						P::TargetChain::NAME,
+						spec_version,
+						delivery_limits,
+					);
+
+					control.set_delivery_limits(delivery_limits);
+					known_delivery_limits = actual_delivery_limits;
+					are_known_limits_actual = true;
+				},
+				Err(e) => {
+					log::warn!(
+						target: "bridge",
+						"Failed to compute updated {} -> {} messages delivery limits: {:?}",
+						P::SourceChain::NAME,
+						P::TargetChain::NAME,
+						e,
+					);
+					are_known_limits_actual = false;
+				},
+			}
+		}
+	});
+}
+
+/// Select message delivery limits, given limits of the target runtime.
+async fn select_delivery_limits<P: SubstrateMessageLane>(
+	target_client: &Client<P::TargetChain>,
+	relayer_id_at_source: &AccountIdOf<P::SourceChain>,
+	target_signer: &AccountKeyPairOf<P::TargetChain>,
+	delivery_limits: MessagesDeliveryLimits,
+) -> anyhow::Result<DeliveryLimits> {
+	// 2/3 is reserved for proofs and tx overhead
+	let max_messages_size_in_single_batch = delivery_limits.max_extrinsic_size / 3;
+	// we don't know exact weights of the Polkadot runtime. So to guess weights we'll be using
+	// weights from Rialto and then simply dividing it by x2.
+	let (max_messages_in_single_batch, max_messages_weight_in_single_batch) =
+		select_delivery_transaction_limits_rpc::<P>(
+			target_client,
+			relayer_id_at_source,
+			target_signer,
+			delivery_limits.max_extrinsic_weight,
+			delivery_limits.max_unconfirmed_messages_at_inbound_lane,
+		)
+		.await?;
+	let (max_messages_in_single_batch, max_messages_weight_in_single_batch) =
+		(max_messages_in_single_batch / 2, max_messages_weight_in_single_batch / 2);
+
+	Ok(DeliveryLimits {
+		max_unrewarded_relayer_entries_at_target: delivery_limits
+			.max_unrewarded_relayer_entries_at_inbound_lane,
+		max_unconfirmed_nonces_at_target: delivery_limits.max_unconfirmed_messages_at_inbound_lane,
+		max_messages_in_single_batch,
+		max_messages_weight_in_single_batch,
+		max_messages_size_in_single_batch,
+	})
+}
+
 /// Returns maximal number of messages and their maximal cumulative dispatch weight.
 async fn select_delivery_transaction_limits_rpc<P: SubstrateMessageLane>(
-	params: &MessagesRelayParams<P>,
+	target_client: &Client<P::TargetChain>,
+	relayer_id_at_source: &AccountIdOf<P::SourceChain>,
+	target_signer: &AccountKeyPairOf<P::TargetChain>,
 	max_extrinsic_weight: Weight,
 	max_unconfirmed_messages_at_inbound_lane: MessageNonce,
-) -> anyhow::Result<(MessageNonce, Weight)>
-where
-	AccountIdOf<P::SourceChain>: From<<AccountKeyPairOf<P::SourceChain> as Pair>::Public>,
-{
+) -> anyhow::Result<(MessageNonce, Weight)> {
 	// We may try to guess accurate value, based on maximal number of messages and per-message
 	// weight overhead, but the relay loop isn't using this info in a super-accurate way anyway.
 	// So just a rough guess: let's say 1/3 of max tx weight is for tx itself and the rest is
@@ -474,9 +926,9 @@ where
 	let weight_for_messages_dispatch = max_extrinsic_weight - weight_for_delivery_tx;
 
 	// weight of empty message delivery with outbound lane state
-	let delivery_tx_with_zero_messages = dummy_messages_delivery_transaction::<P>(params, 0)?;
-	let delivery_tx_with_zero_messages_weight = params
-		.target_client
+	let delivery_tx_with_zero_messages =
+		dummy_messages_delivery_transaction::<P>(relayer_id_at_source, target_signer, 0)?;
+	let delivery_tx_with_zero_messages_weight = target_client
 		.extimate_extrinsic_weight(delivery_tx_with_zero_messages)
 		.await
 		.map_err(|e| {
@@ -484,9 +936,9 @@ where
 		})?;
 
 	// weight of single message delivery with outbound lane state
-	let delivery_tx_with_one_message = dummy_messages_delivery_transaction::<P>(params, 1)?;
-	let delivery_tx_with_one_message_weight = params
-		.target_client
+	let delivery_tx_with_one_message =
+		dummy_messages_delivery_transaction::<P>(relayer_id_at_source, target_signer, 1)?;
+	let delivery_tx_with_one_message_weight = target_client
 		.extimate_extrinsic_weight(delivery_tx_with_one_message)
 		.await
 		.map_err(|e| {
@@ -520,12 +972,10 @@ where
 
 /// Returns dummy message delivery transaction with zero messages and `1kb` proof.
 fn dummy_messages_delivery_transaction<P: SubstrateMessageLane>(
-	params: &MessagesRelayParams<P>,
+	relayer_id_at_source: &AccountIdOf<P::SourceChain>,
+	target_signer: &AccountKeyPairOf<P::TargetChain>,
 	messages: u32,
-) -> anyhow::Result<<P::TargetChain as ChainWithTransactions>::SignedTransaction>
-where
-	AccountIdOf<P::SourceChain>: From<<AccountKeyPairOf<P::SourceChain> as Pair>::Public>,
-{
+) -> anyhow::Result<<P::TargetChain as ChainWithTransactions>::SignedTransaction> {
 	// we don't care about any call values here, because all that the estimation RPC does
 	// is calls `GetDispatchInfo::get_dispatch_info` for the wrapped call. So we only are
 	// interested in values that affect call weight - e.g. number of messages and the
@@ -533,7 +983,7 @@ where
 
 	let dummy_messages_delivery_call =
 		P::ReceiveMessagesProofCallBuilder::build_receive_messages_proof_call(
-			params.source_transaction_params.signer.public().into(),
+			relayer_id_at_source.clone(),
 			(
 				Weight::zero(),
 				FromBridgedChainMessagesProof {
@@ -559,7 +1009,7 @@ where
 			spec_version: 0,
 			transaction_version: 0,
 			genesis_hash: Default::default(),
-			signer: params.target_transaction_params.signer.clone(),
+			signer: target_signer.clone(),
 		},
 		UnsignedTransaction {
 			call: EncodedOrDecodedCall::Decoded(dummy_messages_delivery_call),
@@ -570,3 +1020,88 @@ where
 	)
 	.map_err(Into::into)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use async_trait::async_trait;
+	use bp_runtime::HeaderId;
+	use codec::Decode;
+	use relay_rialto_client::Rialto;
+	use relay_rococo_client::Rococo;
+
+	const PROVED_HEADER: HeaderIdOf<Rococo> = HeaderId(100, sp_core::H256([42u8; 32]));
+
+	fn remark(marker: u8) -> CallOf<Rialto> {
+		rialto_runtime::RuntimeCall::System(rialto_runtime::SystemCall::remark {
+			remark: vec![marker],
+		})
+	}
+
+	/// Batch call builder that encodes all calls into the single `remark` call.
+	#[derive(Clone)]
+	struct TestBatchCallBuilder;
+
+	impl BatchCallBuilder<CallOf<Rialto>> for TestBatchCallBuilder {
+		fn build_batch_call(&self, calls: Vec<CallOf<Rialto>>) -> CallOf<Rialto> {
+			rialto_runtime::RuntimeCall::System(rialto_runtime::SystemCall::remark {
+				remark: calls.encode(),
+			})
+		}
+	}
+
+	impl BatchCallBuilderConstructor<CallOf<Rialto>> for TestBatchCallBuilder {
+		type CallBuilder = Self;
+
+		fn new_builder() -> Option<Self::CallBuilder> {
+			Some(TestBatchCallBuilder)
+		}
+	}
+
+	struct TestOnDemandRelay;
+
+	#[async_trait]
+	impl OnDemandRelay<Rococo, Rialto> for TestOnDemandRelay {
+		async fn reconnect(&self) -> Result<(), SubstrateError> {
+			Ok(())
+		}
+
+		async fn require_more_headers(&self, _required_header: BlockNumberOf<Rococo>) {}
+
+		async fn prove_header(
+			&self,
+			_required_header: BlockNumberOf<Rococo>,
+		) -> Result<(HeaderIdOf<Rococo>, Vec<CallOf<Rialto>>), SubstrateError> {
+			Ok((PROVED_HEADER, vec![remark(1), remark(2)]))
+		}
+	}
+
+	#[async_std::test]
+	async fn batch_proof_transaction_is_not_created_without_batch_call_builder() {
+		let batch_tx =
+			BatchProofTransaction::<Rialto, Rococo, ()>::new(Arc::new(TestOnDemandRelay), 90)
+				.await
+				.unwrap();
+		assert!(batch_tx.is_none());
+	}
+
+	#[async_std::test]
+	async fn batch_proof_transaction_puts_proof_calls_before_appended_call() {
+		let batch_tx = BatchProofTransaction::<Rialto, Rococo, TestBatchCallBuilder>::new(
+			Arc::new(TestOnDemandRelay),
+			90,
+		)
+		.await
+		.unwrap()
+		.unwrap();
+		assert_eq!(batch_tx.required_header_id(), PROVED_HEADER);
+
+		let batch_call = batch_tx.append_call_and_build(remark(3));
+		let batched_calls = match batch_call {
+			rialto_runtime::RuntimeCall::System(rialto_runtime::SystemCall::remark { remark }) =>
+				Vec::<CallOf<Rialto>>::decode(&mut &remark[..]).unwrap(),
+			_ => unreachable!("TestBatchCallBuilder always returns remark call"),
+		};
+		assert_eq!(batched_calls, vec![remark(1), remark(2), remark(3)]);
+	}
+}
@@ -19,12 +19,13 @@
 //! <BridgedName> chain.
 
 use crate::{
+	fee_metrics::{FeeMetrics, FeeTrackingTransactionTracker, TransactionKind},
 	messages_lane::{
 		BatchProofTransaction, MessageLaneAdapter, ReceiveMessagesDeliveryProofCallBuilder,
 		SubstrateMessageLane,
 	},
 	on_demand::OnDemandRelay,
-	TransactionParams,
+	submit_and_watch_call, TransactionParams,
 };
 
 use async_std::sync::Arc;
@@ -34,10 +35,11 @@ use bp_messages::{
 	InboundMessageDetails, LaneId, MessageNonce, MessagePayload, MessagesOperatingMode,
 	OutboundLaneData, OutboundMessageDetails,
 };
-use bp_runtime::{BasicOperatingMode, HeaderIdProvider};
+use bp_runtime::{HeaderIdProvider, OperatingMode};
 use bridge_runtime_common::messages::target::FromBridgedChainMessagesProof;
 use codec::Encode;
 use frame_support::weights::Weight;
+use futures::stream::{BoxStream, StreamExt};
 use messages_relay::{
 	message_lane::{MessageLane, SourceHeaderIdOf, TargetHeaderIdOf},
 	message_lane_loop::{
@@ -48,8 +50,7 @@ use messages_relay::{
 use num_traits::Zero;
 use relay_substrate_client::{
 	AccountIdOf, AccountKeyPairOf, BalanceOf, Chain, ChainWithMessages, Client,
-	Error as SubstrateError, HashOf, HeaderIdOf, TransactionEra, TransactionTracker,
-	UnsignedTransaction,
+	Error as SubstrateError, HashOf, HeaderIdOf,
 };
 use relay_utils::relay_loop::Client as RelayClient;
 use sp_core::Pair;
@@ -68,6 +69,7 @@ pub struct SubstrateMessagesSource<P: SubstrateMessageLane> {
 	lane_id: LaneId,
 	transaction_params: TransactionParams<AccountKeyPairOf<P::SourceChain>>,
 	target_to_source_headers_relay: Option<Arc<dyn OnDemandRelay<P::TargetChain, P::SourceChain>>>,
+	fee_metrics: Option<FeeMetrics>,
 }
 
 impl<P: SubstrateMessageLane> SubstrateMessagesSource<P> {
@@ -80,6 +82,7 @@ impl<P: SubstrateMessageLane> SubstrateMessagesSource<P> {
 		target_to_source_headers_relay: Option<
 			Arc<dyn OnDemandRelay<P::TargetChain, P::SourceChain>>,
 		>,
+		fee_metrics: Option<FeeMetrics>,
 	) -> Self {
 		SubstrateMessagesSource {
 			source_client,
@@ -87,6 +90,7 @@ impl<P: SubstrateMessageLane> SubstrateMessagesSource<P> {
 			lane_id,
 			transaction_params,
 			target_to_source_headers_relay,
+			fee_metrics,
 		}
 	}
 
@@ -120,6 +124,7 @@ impl<P: SubstrateMessageLane> Clone for SubstrateMessagesSource<P> {
 			lane_id: self.lane_id,
 			transaction_params: self.transaction_params.clone(),
 			target_to_source_headers_relay: self.target_to_source_headers_relay.clone(),
+			fee_metrics: self.fee_metrics.clone(),
 		}
 	}
 }
@@ -155,7 +160,7 @@ where
 {
 	type BatchTransaction =
 		BatchProofTransaction<P::SourceChain, P::TargetChain, P::SourceBatchCallBuilder>;
-	type TransactionTracker = TransactionTracker<P::SourceChain, Client<P::SourceChain>>;
+	type TransactionTracker = FeeTrackingTransactionTracker<P::SourceChain>;
 
 	async fn state(&self) -> Result<SourceClientState<MessageLaneAdapter<P>>, SubstrateError> {
 		// we can't continue to deliver confirmations if source node is out of sync, because
@@ -171,6 +176,13 @@ where
 		read_client_state(&self.source_client, Some(&self.target_client)).await
 	}
 
+	async fn finalized_headers_notifications(
+		&self,
+	) -> Result<Option<BoxStream<'static, ()>>, SubstrateError> {
+		let header_numbers = self.source_client.finalized_header_numbers_stream().await?;
+		Ok(Some(header_numbers.map(|_| ()).boxed()))
+	}
+
 	async fn latest_generated_nonce(
 		&self,
 		id: SourceHeaderIdOf<MessageLaneAdapter<P>>,
@@ -345,6 +357,7 @@ where
 		_generated_at_block: TargetHeaderIdOf<MessageLaneAdapter<P>>,
 		proof: <MessageLaneAdapter<P> as MessageLane>::MessagesReceivingProof,
 	) -> Result<Self::TransactionTracker, SubstrateError> {
+		let confirmed_messages = proof.0.total_messages;
 		let messages_proof_call =
 			P::ReceiveMessagesDeliveryProofCallBuilder::build_receive_messages_delivery_proof_call(
 				proof,
@@ -355,16 +368,15 @@ where
 			None => messages_proof_call,
 		};
 
-		let transaction_params = self.transaction_params.clone();
-		self.source_client
-			.submit_and_watch_signed_extrinsic(
-				&self.transaction_params.signer,
-				move |best_block_id, transaction_nonce| {
-					Ok(UnsignedTransaction::new(final_call.into(), transaction_nonce)
-						.era(TransactionEra::new(best_block_id, transaction_params.mortality)))
-				},
-			)
-			.await
+		let tx_tracker =
+			submit_and_watch_call(&self.source_client, &self.transaction_params, final_call)
+				.await?;
+		Ok(FeeTrackingTransactionTracker::new(
+			tx_tracker,
+			self.fee_metrics.clone(),
+			TransactionKind::MessagesConfirmation,
+			confirmed_messages,
+		))
 	}
 
 	async fn require_target_header_on_source(
@@ -393,18 +405,44 @@ where
 	AtChain: ChainWithMessages,
 	WithChain: ChainWithMessages,
 {
-	let operating_mode = client
-		.storage_value(operating_mode_key(WithChain::WITH_CHAIN_MESSAGES_PALLET_NAME), None)
-		.await?;
-	let is_halted =
-		operating_mode == Some(MessagesOperatingMode::Basic(BasicOperatingMode::Halted));
-	if is_halted {
+	let operating_mode = messages_pallet_operating_mode::<AtChain, WithChain>(client).await?;
+	if operating_mode.is_halted() {
 		Err(SubstrateError::BridgePalletIsHalted)
 	} else {
 		Ok(())
 	}
 }
 
+/// Ensure that the messages pallet at target chain is accepting inbound messages.
+pub(crate) async fn ensure_messages_pallet_accepts_inbound_messages<AtChain, WithChain>(
+	client: &Client<AtChain>,
+) -> Result<(), SubstrateError>
+where
+	AtChain: ChainWithMessages,
+	WithChain: ChainWithMessages,
+{
+	let operating_mode = messages_pallet_operating_mode::<AtChain, WithChain>(client).await?;
+	if operating_mode.is_accepting_inbound_messages() {
+		Ok(())
+	} else {
+		Err(SubstrateError::BridgePalletIsNotAcceptingInboundMessages)
+	}
+}
+
+/// Read operating mode of the messages pallet at given chain.
+async fn messages_pallet_operating_mode<AtChain, WithChain>(
+	client: &Client<AtChain>,
+) -> Result<MessagesOperatingMode, SubstrateError>
+where
+	AtChain: ChainWithMessages,
+	WithChain: ChainWithMessages,
+{
+	Ok(client
+		.storage_value(operating_mode_key(WithChain::WITH_CHAIN_MESSAGES_PALLET_NAME), None)
+		.await?
+		.unwrap_or_default())
+}
+
 /// Read best blocks from given client.
 ///
 /// This function assumes that the chain that is followed by the `self_client` has
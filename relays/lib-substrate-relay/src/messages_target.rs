@@ -19,13 +19,17 @@
 //! <BridgedName> chain.
 
 use crate::{
+	fee_metrics::{FeeMetrics, FeeTrackingTransactionTracker, TransactionKind},
 	messages_lane::{
 		BatchProofTransaction, MessageLaneAdapter, ReceiveMessagesProofCallBuilder,
 		SubstrateMessageLane,
 	},
-	messages_source::{ensure_messages_pallet_active, read_client_state, SubstrateMessagesProof},
+	messages_source::{
+		ensure_messages_pallet_accepts_inbound_messages, ensure_messages_pallet_active,
+		read_client_state, SubstrateMessagesProof,
+	},
 	on_demand::OnDemandRelay,
-	TransactionParams,
+	submit_and_watch_call, TransactionParams,
 };
 
 use async_std::sync::Arc;
@@ -35,13 +39,14 @@ use bp_messages::{
 	MessageNonce, UnrewardedRelayersState,
 };
 use bridge_runtime_common::messages::source::FromBridgedChainMessagesDeliveryProof;
+use futures::stream::{BoxStream, StreamExt};
 use messages_relay::{
 	message_lane::{MessageLane, SourceHeaderIdOf, TargetHeaderIdOf},
 	message_lane_loop::{NoncesSubmitArtifacts, TargetClient, TargetClientState},
 };
 use relay_substrate_client::{
 	AccountIdOf, AccountKeyPairOf, BalanceOf, CallOf, ChainWithMessages, Client,
-	Error as SubstrateError, HashOf, TransactionEra, TransactionTracker, UnsignedTransaction,
+	Error as SubstrateError, HashOf,
 };
 use relay_utils::relay_loop::Client as RelayClient;
 use sp_core::Pair;
@@ -59,6 +64,7 @@ pub struct SubstrateMessagesTarget<P: SubstrateMessageLane> {
 	relayer_id_at_source: AccountIdOf<P::SourceChain>,
 	transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
 	source_to_target_headers_relay: Option<Arc<dyn OnDemandRelay<P::SourceChain, P::TargetChain>>>,
+	fee_metrics: Option<FeeMetrics>,
 }
 
 impl<P: SubstrateMessageLane> SubstrateMessagesTarget<P> {
@@ -72,6 +78,7 @@ impl<P: SubstrateMessageLane> SubstrateMessagesTarget<P> {
 		source_to_target_headers_relay: Option<
 			Arc<dyn OnDemandRelay<P::SourceChain, P::TargetChain>>,
 		>,
+		fee_metrics: Option<FeeMetrics>,
 	) -> Self {
 		SubstrateMessagesTarget {
 			target_client,
@@ -80,6 +87,7 @@ impl<P: SubstrateMessageLane> SubstrateMessagesTarget<P> {
 			relayer_id_at_source,
 			transaction_params,
 			source_to_target_headers_relay,
+			fee_metrics,
 		}
 	}
 
@@ -114,6 +122,7 @@ impl<P: SubstrateMessageLane> Clone for SubstrateMessagesTarget<P> {
 			relayer_id_at_source: self.relayer_id_at_source.clone(),
 			transaction_params: self.transaction_params.clone(),
 			source_to_target_headers_relay: self.source_to_target_headers_relay.clone(),
+			fee_metrics: self.fee_metrics.clone(),
 		}
 	}
 }
@@ -150,7 +159,7 @@ where
 {
 	type BatchTransaction =
 		BatchProofTransaction<P::TargetChain, P::SourceChain, P::TargetBatchCallBuilder>;
-	type TransactionTracker = TransactionTracker<P::TargetChain, Client<P::TargetChain>>;
+	type TransactionTracker = FeeTrackingTransactionTracker<P::TargetChain>;
 
 	async fn state(&self) -> Result<TargetClientState<MessageLaneAdapter<P>>, SubstrateError> {
 		// we can't continue to deliver confirmations if source node is out of sync, because
@@ -166,6 +175,13 @@ where
 		read_client_state(&self.target_client, Some(&self.source_client)).await
 	}
 
+	async fn finalized_headers_notifications(
+		&self,
+	) -> Result<Option<BoxStream<'static, ()>>, SubstrateError> {
+		let header_numbers = self.target_client.finalized_header_numbers_stream().await?;
+		Ok(Some(header_numbers.map(|_| ()).boxed()))
+	}
+
 	async fn latest_received_nonce(
 		&self,
 		id: TargetHeaderIdOf<MessageLaneAdapter<P>>,
@@ -249,6 +265,12 @@ where
 		nonces: RangeInclusive<MessageNonce>,
 		proof: <MessageLaneAdapter<P> as MessageLane>::MessagesProof,
 	) -> Result<NoncesSubmitArtifacts<Self::TransactionTracker>, SubstrateError> {
+		// the pallet may be accepting delivery confirmations, but reject inbound messages
+		ensure_messages_pallet_accepts_inbound_messages::<P::TargetChain, P::SourceChain>(
+			&self.target_client,
+		)
+		.await?;
+
 		let messages_proof_call = make_messages_delivery_call::<P>(
 			self.relayer_id_at_source.clone(),
 			proof.1.nonces_start..=proof.1.nonces_end,
@@ -260,17 +282,15 @@ where
 			None => messages_proof_call,
 		};
 
-		let transaction_params = self.transaction_params.clone();
-		let tx_tracker = self
-			.target_client
-			.submit_and_watch_signed_extrinsic(
-				&self.transaction_params.signer,
-				move |best_block_id, transaction_nonce| {
-					Ok(UnsignedTransaction::new(final_call.into(), transaction_nonce)
-						.era(TransactionEra::new(best_block_id, transaction_params.mortality)))
-				},
-			)
-			.await?;
+		let tx_tracker =
+			submit_and_watch_call(&self.target_client, &self.transaction_params, final_call)
+				.await?;
+		let tx_tracker = FeeTrackingTransactionTracker::new(
+			tx_tracker,
+			self.fee_metrics.clone(),
+			TransactionKind::MessagesDelivery,
+			nonces.end().saturating_sub(*nonces.start()) + 1,
+		);
 		Ok(NoncesSubmitArtifacts { nonces, tx_tracker })
 	}
 
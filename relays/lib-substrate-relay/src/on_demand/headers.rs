@@ -177,7 +177,7 @@ async fn background_task<P: SubstrateFinalitySyncPipeline>(
 		Some(required_header_number.clone()),
 	);
 	let mut finality_target =
-		SubstrateFinalityTarget::new(target_client.clone(), target_transaction_params);
+		SubstrateFinalityTarget::new(target_client.clone(), target_transaction_params, None);
 	let mut latest_non_mandatory_at_source = Zero::zero();
 
 	let mut restart_relay = true;
@@ -257,7 +257,7 @@ async fn background_task<P: SubstrateParachainsPipeline>(
 	let mut parachains_source =
 		ParachainsSource::<P>::new(source_relay_client.clone(), required_para_header_ref.clone());
 	let mut parachains_target =
-		ParachainsTarget::<P>::new(target_client.clone(), target_transaction_params.clone());
+		ParachainsTarget::<P>::new(target_client.clone(), target_transaction_params.clone(), None);
 
 	loop {
 		select! {
@@ -311,7 +311,9 @@ async fn background_task<P: SubstrateParachainsPipeline>(
 		//
 		// 4) on-demand parachains relay waits for GRANDPA-finalized block of the source relay chain
 		//    `RH` that is storing `PH` or its descendant. Let it be `PH'`;
-		// 5) it asks on-demand headers relay to relay `RH` to the target chain;
+		// 5) it asks on-demand headers relay to relay `RH` to the target chain (this phase is
+		//    skipped if the relay header, already known to the target chain, is storing `PH` or its
+		//    descendant);
 		// 6) it waits until `RH` (or its descendant) is relayed to the target chain;
 		//
 		// Phase#2: relaying parachain header
@@ -603,8 +605,21 @@ where
 		return RelayState::Idle
 	}
 
-	// we will always try to sync latest parachain/relay header, even if we've been asked for some
-	// its ancestor
+	// if required parachain header (or its descendant) may be proved using the relay header, that
+	// is already known to the target chain, we don't need to relay new relay header. Do not do
+	// that when there are no parachain heads at the target yet - we want the best head there
+	if data.para_header_at_target.is_some() {
+		if let Some(para_header_at_relay_header_at_target) =
+			data.para_header_at_relay_header_at_target.as_ref()
+		{
+			if para_header_at_relay_header_at_target.0 >= required_para_header {
+				return RelayState::RelayingParaHeader(para_header_at_relay_header_at_target.clone())
+			}
+		}
+	}
+
+	// otherwise we will try to sync latest parachain/relay header, even if we've been asked for
+	// some its ancestor
 
 	// we need relay chain header first
 	if relay_header_at_target < data.relay_header_at_source {
@@ -955,6 +970,24 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn relay_starts_relaying_para_header_using_relay_header_at_target() {
+		assert_eq!(
+			select_headers_to_relay(
+				&RelayData {
+					required_para_header: 100,
+					para_header_at_target: Some(50),
+					para_header_at_source: Some(HeaderId(125, 125)),
+					relay_header_at_source: 800,
+					relay_header_at_target: Some(780),
+					para_header_at_relay_header_at_target: Some(HeaderId(105, 105)),
+				},
+				RelayState::Idle,
+			),
+			RelayState::RelayingParaHeader(HeaderId(105, 105)),
+		);
+	}
+
 	// tuple is:
 	//
 	// - best_finalized_relay_block_at_source
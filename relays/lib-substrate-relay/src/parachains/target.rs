@@ -17,10 +17,11 @@
 //! Parachain heads target.
 
 use crate::{
+	fee_metrics::{FeeMetrics, FeeTrackingTransactionTracker, TransactionKind},
 	parachains::{
 		ParachainsPipelineAdapter, SubmitParachainHeadsCallBuilder, SubstrateParachainsPipeline,
 	},
-	TransactionParams,
+	submit_and_watch_call, TransactionParams,
 };
 
 use async_trait::async_trait;
@@ -30,7 +31,7 @@ use codec::Decode;
 use parachains_relay::parachains_loop::TargetClient;
 use relay_substrate_client::{
 	AccountIdOf, AccountKeyPairOf, Chain, Client, Error as SubstrateError, HeaderIdOf,
-	ParachainBase, TransactionEra, TransactionTracker, UnsignedTransaction,
+	ParachainBase,
 };
 use relay_utils::relay_loop::Client as RelayClient;
 use sp_core::{Bytes, Pair};
@@ -39,6 +40,7 @@ use sp_core::{Bytes, Pair};
 pub struct ParachainsTarget<P: SubstrateParachainsPipeline> {
 	client: Client<P::TargetChain>,
 	transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+	fee_metrics: Option<FeeMetrics>,
 }
 
 impl<P: SubstrateParachainsPipeline> ParachainsTarget<P> {
@@ -46,8 +48,9 @@ impl<P: SubstrateParachainsPipeline> ParachainsTarget<P> {
 	pub fn new(
 		client: Client<P::TargetChain>,
 		transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+		fee_metrics: Option<FeeMetrics>,
 	) -> Self {
-		ParachainsTarget { client, transaction_params }
+		ParachainsTarget { client, transaction_params, fee_metrics }
 	}
 
 	/// Returns reference to the underlying RPC client.
@@ -61,6 +64,7 @@ impl<P: SubstrateParachainsPipeline> Clone for ParachainsTarget<P> {
 		ParachainsTarget {
 			client: self.client.clone(),
 			transaction_params: self.transaction_params.clone(),
+			fee_metrics: self.fee_metrics.clone(),
 		}
 	}
 }
@@ -80,7 +84,7 @@ where
 	P: SubstrateParachainsPipeline,
 	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
 {
-	type TransactionTracker = TransactionTracker<P::TargetChain, Client<P::TargetChain>>;
+	type TransactionTracker = FeeTrackingTransactionTracker<P::TargetChain>;
 
 	async fn best_block(&self) -> Result<HeaderIdOf<P::TargetChain>, Self::Error> {
 		let best_header = self.client.best_header().await?;
@@ -129,20 +133,18 @@ where
 		updated_head_hash: ParaHash,
 		proof: ParaHeadsProof,
 	) -> Result<Self::TransactionTracker, Self::Error> {
-		let transaction_params = self.transaction_params.clone();
 		let call = P::SubmitParachainHeadsCallBuilder::build_submit_parachain_heads_call(
 			at_relay_block,
 			vec![(ParaId(P::SourceParachain::PARACHAIN_ID), updated_head_hash)],
 			proof,
 		);
-		self.client
-			.submit_and_watch_signed_extrinsic(
-				&transaction_params.signer,
-				move |best_block_id, transaction_nonce| {
-					Ok(UnsignedTransaction::new(call.into(), transaction_nonce)
-						.era(TransactionEra::new(best_block_id, transaction_params.mortality)))
-				},
-			)
-			.await
+		let tx_tracker =
+			submit_and_watch_call(&self.client, &self.transaction_params, call).await?;
+		Ok(FeeTrackingTransactionTracker::new(
+			tx_tracker,
+			self.fee_metrics.clone(),
+			TransactionKind::ParachainHeads,
+			0,
+		))
 	}
 }
@@ -0,0 +1,314 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime control of message lanes.
+//!
+//! Every message lane loop registers its control here. The relay operator may then use the admin
+//! socket (see `start_admin_server`) to pause and resume message delivery over the lane or to
+//! change the maximal number of messages in the delivery transaction. Lane loops keep running
+//! while they're paused, so the race state is preserved. The relay itself uses the control to
+//! feed delivery limits, changed by the target runtime upgrade, into the running loop.
+//!
+//! Supported commands (one command per line):
+//!
+//! - `pause-lane <lane>`: stop delivering messages over the lane. Delivery confirmations are still
+//!   submitted, so the relayer is still able to receive rewards for already delivered messages;
+//!
+//! - `resume-lane <lane>`: resume delivering messages over the lane;
+//!
+//! - `set-max-batch <lane> <count|default>`: limit the number of messages in the single delivery
+//!   transaction. The limit can't be larger than the limit, computed by the relay using the target
+//!   runtime limits;
+//!
+//! - `status`: print status of all lanes.
+//!
+//! The `<lane>` is the hex-encoded lane identifier (e.g. `00000000`). Commands are applied to all
+//! loops serving the lane (i.e. to both directions of the two-way lane).
+
+use bp_messages::{LaneId, MessageNonce, Weight};
+
+use async_std::{
+	io::{prelude::BufReadExt, BufReader, WriteExt},
+	os::unix::net::{UnixListener, UnixStream},
+	stream::StreamExt,
+};
+use std::{
+	collections::BTreeMap,
+	path::Path,
+	sync::{Arc, Mutex},
+};
+
+/// Controls of all registered lane loops, keyed by the lane id and the loop direction.
+static LANES: Mutex<BTreeMap<(LaneId, String), LaneControl>> = Mutex::new(BTreeMap::new());
+
+/// Runtime control of the single message lane loop.
+///
+/// Cloning `LaneControl` is cheap and all clones share the same state.
+#[derive(Clone, Debug, Default)]
+pub struct LaneControl(Arc<Mutex<LaneControlState>>);
+
+/// State of the message lane loop, that may be changed by the operator or by the relay.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct LaneControlState {
+	/// If `true`, messages are not delivered over the lane.
+	is_paused: bool,
+	/// Maximal number of messages in the single delivery transaction, set by the operator.
+	max_messages_in_single_batch: Option<MessageNonce>,
+	/// Delivery limits, updated after the loop has been started.
+	delivery_limits: Option<DeliveryLimits>,
+}
+
+/// Message delivery limits, that are computed using limits of the target runtime.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeliveryLimits {
+	/// Maximal number of unrewarded relayer entries at the target inbound lane.
+	pub max_unrewarded_relayer_entries_at_target: MessageNonce,
+	/// Maximal number of unconfirmed messages at the target inbound lane.
+	pub max_unconfirmed_nonces_at_target: MessageNonce,
+	/// Maximal number of messages in the single delivery transaction.
+	pub max_messages_in_single_batch: MessageNonce,
+	/// Maximal cumulative dispatch weight of messages in the single delivery transaction.
+	pub max_messages_weight_in_single_batch: Weight,
+	/// Maximal cumulative size of messages in the single delivery transaction.
+	pub max_messages_size_in_single_batch: u32,
+}
+
+impl LaneControl {
+	/// Returns `true` if message delivery is paused.
+	pub fn is_paused(&self) -> bool {
+		self.state().is_paused
+	}
+
+	/// Returns maximal number of messages in the single delivery transaction, given the limit
+	/// that has been configured at startup.
+	pub fn max_messages_in_single_batch(&self, configured: MessageNonce) -> MessageNonce {
+		self.state()
+			.max_messages_in_single_batch
+			.map(|max_messages| std::cmp::min(max_messages, configured))
+			.unwrap_or(configured)
+	}
+
+	/// Returns delivery limits, if they have been updated after the loop has been started.
+	pub fn delivery_limits(&self) -> Option<DeliveryLimits> {
+		self.state().delivery_limits
+	}
+
+	/// Pause (or resume) message delivery.
+	pub fn set_paused(&self, is_paused: bool) {
+		self.update(|state| state.is_paused = is_paused)
+	}
+
+	/// Set maximal number of messages in the single delivery transaction. If `None`, the limit
+	/// that has been configured at startup is used.
+	pub fn set_max_messages_in_single_batch(&self, max_messages: Option<MessageNonce>) {
+		self.update(|state| state.max_messages_in_single_batch = max_messages)
+	}
+
+	/// Set delivery limits, that the running loop must use instead of limits, configured at
+	/// startup.
+	pub fn set_delivery_limits(&self, delivery_limits: DeliveryLimits) {
+		self.update(|state| state.delivery_limits = Some(delivery_limits))
+	}
+
+	/// Returns current state.
+	fn state(&self) -> LaneControlState {
+		*self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+
+	/// Update the state.
+	fn update(&self, update: impl FnOnce(&mut LaneControlState)) {
+		update(&mut self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+	}
+}
+
+/// Register control of the message lane loop, serving given lane in given direction.
+///
+/// If the loop is already registered, its control is returned.
+pub fn register(lane: LaneId, source_name: &str, target_name: &str) -> LaneControl {
+	lanes()
+		.entry((lane, format!("{source_name} -> {target_name}")))
+		.or_default()
+		.clone()
+}
+
+/// Lock and return all registered lane controls.
+fn lanes() -> std::sync::MutexGuard<'static, BTreeMap<(LaneId, String), LaneControl>> {
+	LANES.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Start serving admin commands at the Unix socket with given path.
+///
+/// The socket file is only accessible by the owner of the relay process.
+pub async fn start_admin_server(path: &Path) -> std::io::Result<()> {
+	use std::os::unix::fs::PermissionsExt;
+
+	// remove socket that may be left by the previous relay instance
+	if path.exists() {
+		std::fs::remove_file(path)?;
+	}
+	let listener = UnixListener::bind(path).await?;
+	std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+
+	log::info!(target: "bridge", "Listening to admin commands at {:?}", path);
+
+	async_std::task::spawn(async move {
+		let mut incoming = listener.incoming();
+		while let Some(stream) = incoming.next().await {
+			match stream {
+				Ok(stream) => {
+					async_std::task::spawn(serve_connection(stream));
+				},
+				Err(e) => {
+					log::warn!(target: "bridge", "Failed to accept admin connection: {:?}", e)
+				},
+			}
+		}
+	});
+
+	Ok(())
+}
+
+/// Serve commands from the single admin connection.
+async fn serve_connection(stream: UnixStream) {
+	let mut lines = BufReader::new(&stream).lines();
+	while let Some(Ok(command)) = lines.next().await {
+		let response = execute(&command);
+		log::info!(target: "bridge", "Executed admin command {:?}: {}", command, response);
+
+		if (&stream).write_all(format!("{response}\n").as_bytes()).await.is_err() {
+			break
+		}
+	}
+}
+
+/// Execute admin command and return the response.
+fn execute(command: &str) -> String {
+	let args = command.split_whitespace().collect::<Vec<_>>();
+	let result = match args.as_slice() {
+		["pause-lane", lane] => update_lanes(lane, |control| control.set_paused(true)),
+		["resume-lane", lane] => update_lanes(lane, |control| control.set_paused(false)),
+		["set-max-batch", lane, "default"] =>
+			update_lanes(lane, |control| control.set_max_messages_in_single_batch(None)),
+		["set-max-batch", lane, max_messages] => match max_messages.parse::<MessageNonce>() {
+			Ok(max_messages) if max_messages > 0 => update_lanes(lane, |control| {
+				control.set_max_messages_in_single_batch(Some(max_messages))
+			}),
+			_ => Err(format!("invalid number of messages: {max_messages}")),
+		},
+		["status"] => Ok(status()),
+		_ => Err(format!(
+			"unknown command: {command:?}. Supported commands: pause-lane <lane>, \
+			resume-lane <lane>, set-max-batch <lane> <count|default>, status"
+		)),
+	};
+
+	match result {
+		Ok(response) => format!("OK {response}"),
+		Err(error) => format!("ERROR {error}"),
+	}
+}
+
+/// Update state of all loops serving given lane.
+fn update_lanes(lane: &str, update: impl Fn(&LaneControl)) -> Result<String, String> {
+	let mut lane_id = [0u8; 4];
+	hex::decode_to_slice(lane, &mut lane_id).map_err(|e| format!("invalid lane {lane}: {e}"))?;
+	let lane_id = LaneId(lane_id);
+
+	let lanes = lanes();
+	let mut updated = 0;
+	for control in lanes.iter().filter(|((id, _), _)| *id == lane_id).map(|(_, control)| control) {
+		update(control);
+		updated += 1;
+	}
+
+	if updated == 0 {
+		return Err(format!("lane {lane} is not served by the relay"))
+	}
+	Ok(format!("updated {updated} loop(s) of lane {lane}"))
+}
+
+/// Returns status of all registered lanes.
+fn status() -> String {
+	lanes()
+		.iter()
+		.map(|((lane, direction), control)| {
+			let state = control.state();
+			format!(
+				"lane {} ({}): {}, max messages in batch: {}",
+				hex::encode(lane),
+				direction,
+				if state.is_paused { "paused" } else { "active" },
+				state
+					.max_messages_in_single_batch
+					.map(|max_messages| max_messages.to_string())
+					.unwrap_or_else(|| "default".into()),
+			)
+		})
+		.collect::<Vec<_>>()
+		.join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn max_messages_in_single_batch_never_exceeds_configured_value() {
+		let control = LaneControl::default();
+		assert_eq!(control.max_messages_in_single_batch(10), 10);
+
+		control.set_max_messages_in_single_batch(Some(5));
+		assert_eq!(control.max_messages_in_single_batch(10), 5);
+
+		control.set_max_messages_in_single_batch(Some(20));
+		assert_eq!(control.max_messages_in_single_batch(10), 10);
+	}
+
+	#[test]
+	fn admin_commands_are_applied_to_all_lane_loops() {
+		let forward = register(LaneId([0xaa, 0xbb, 0xcc, 0xdd]), "Source", "Target");
+		let backward = register(LaneId([0xaa, 0xbb, 0xcc, 0xdd]), "Target", "Source");
+		let other = register(LaneId([0xaa, 0xbb, 0xcc, 0xde]), "Source", "Target");
+
+		assert!(execute("pause-lane aabbccdd").starts_with("OK"));
+		assert!(forward.is_paused());
+		assert!(backward.is_paused());
+		assert!(!other.is_paused());
+
+		assert!(execute("set-max-batch aabbccdd 2").starts_with("OK"));
+		assert_eq!(forward.max_messages_in_single_batch(10), 2);
+		assert_eq!(other.max_messages_in_single_batch(10), 10);
+		assert!(execute("status").contains("lane aabbccdd (Source -> Target): paused"));
+
+		assert!(execute("resume-lane aabbccdd").starts_with("OK"));
+		assert!(execute("set-max-batch aabbccdd default").starts_with("OK"));
+		assert!(!forward.is_paused());
+		assert_eq!(forward.max_messages_in_single_batch(10), 10);
+
+		// the same control is returned when loop is registered again
+		register(LaneId([0xaa, 0xbb, 0xcc, 0xdd]), "Source", "Target").set_paused(true);
+		assert!(forward.is_paused());
+	}
+
+	#[test]
+	fn invalid_admin_commands_are_rejected() {
+		assert!(execute("pause-lane").starts_with("ERROR"));
+		assert!(execute("pause-lane not-a-lane").starts_with("ERROR"));
+		assert!(execute("pause-lane ffffffff").starts_with("ERROR"));
+		assert!(execute("set-max-batch ffffffff 0").starts_with("ERROR"));
+		assert!(execute("unknown").starts_with("ERROR"));
+	}
+}
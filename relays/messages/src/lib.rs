@@ -25,11 +25,12 @@
 #![recursion_limit = "1024"]
 #![warn(missing_docs)]
 
-mod metrics;
-
+pub mod lane_control;
 pub mod message_lane;
 pub mod message_lane_loop;
+pub mod metrics;
 
+mod message_lane_loop_simulation_tests;
 mod message_race_delivery;
 mod message_race_limits;
 mod message_race_loop;
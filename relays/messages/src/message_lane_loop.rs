@@ -24,10 +24,20 @@
 //! finalized header. I.e. when talking about headers in lane context, we
 //! only care about finalized headers.
 
-use std::{collections::BTreeMap, fmt::Debug, future::Future, ops::RangeInclusive, time::Duration};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fmt::Debug,
+	future::Future,
+	ops::RangeInclusive,
+	time::Duration,
+};
 
 use async_trait::async_trait;
-use futures::{channel::mpsc::unbounded, future::FutureExt, stream::StreamExt};
+use futures::{
+	channel::mpsc::unbounded,
+	future::FutureExt,
+	stream::{BoxStream, StreamExt},
+};
 
 use bp_messages::{LaneId, MessageNonce, UnrewardedRelayersState, Weight};
 use relay_utils::{
@@ -36,6 +46,7 @@ use relay_utils::{
 };
 
 use crate::{
+	lane_control::LaneControl,
 	message_lane::{MessageLane, SourceHeaderIdOf, TargetHeaderIdOf},
 	message_race_delivery::run as run_message_delivery_race,
 	message_race_receiving::run as run_message_receiving_race,
@@ -55,6 +66,10 @@ pub struct Params {
 	pub reconnect_delay: Duration,
 	/// Message delivery race parameters.
 	pub delivery_params: MessageDeliveryParams,
+	/// Message receiving confirmations race parameters.
+	pub receiving_params: MessageReceivingParams,
+	/// Lane alert thresholds.
+	pub alert_params: LaneAlertParams,
 }
 
 /// Message delivery race parameters.
@@ -75,6 +90,69 @@ pub struct MessageDeliveryParams {
 	pub max_messages_weight_in_single_batch: Weight,
 	/// Maximal cumulative size of relayed messages in single delivery transaction.
 	pub max_messages_size_in_single_batch: u32,
+	/// If set, the delivery race won't submit delivery transactions for that period after it has
+	/// seen messages delivered by some other relayer. So the delivery is only resumed when the
+	/// lane becomes stale.
+	pub cooperative_backoff: Option<Duration>,
+	/// If set, the delivery race won't deliver messages with lower nonces. The target chain still
+	/// accepts messages in order only, so the lane is stalled until these messages are delivered
+	/// by other relayers.
+	pub start_from_nonce: Option<MessageNonce>,
+	/// Nonces of messages that the delivery race won't deliver. Same as with `start_from_nonce`,
+	/// the lane is stalled until these messages are delivered by other relayers.
+	pub skip_nonces: BTreeSet<MessageNonce>,
+	/// If set, the delivery race switches to the historical backfill mode when the best source
+	/// header, known to the target node, is at least that many blocks behind the best finalized
+	/// source header. The state of such header has likely been discarded by the source node, so
+	/// messages can't be proved at it. In this mode, the race requires fresh source header at
+	/// the target node and resumes delivery once it is imported. Proving messages at ancient
+	/// headers (e.g. using MMR proofs) is not supported.
+	pub ancient_source_header_threshold: Option<u32>,
+}
+
+/// Message receiving confirmations race parameters.
+///
+/// The confirmation transaction is submitted if at least one of following conditions is met:
+///
+/// - there are at least `min_confirmations_in_batch` confirmable messages;
+///
+/// - there are at least `force_at_unconfirmed_nonces` messages that are delivered, but not yet
+///   confirmed;
+///
+/// - the oldest queued confirmation is waiting for more than `max_confirmation_delay`.
+#[derive(Debug, Clone)]
+pub struct MessageReceivingParams {
+	/// Minimal number of messages that must be confirmed by single confirmation transaction.
+	pub min_confirmations_in_batch: MessageNonce,
+	/// Number of delivered, but not yet confirmed messages at which we stop waiting for more
+	/// confirmations. It should be lower than the
+	/// `MessageDeliveryParams::max_unconfirmed_nonces_at_target`, otherwise the delivery race
+	/// would stall.
+	pub force_at_unconfirmed_nonces: MessageNonce,
+	/// Maximal period a confirmation may wait for other confirmations. `None` means that
+	/// confirmations may wait forever.
+	pub max_confirmation_delay: Option<Duration>,
+}
+
+impl Default for MessageReceivingParams {
+	fn default() -> Self {
+		MessageReceivingParams {
+			min_confirmations_in_batch: 1,
+			force_at_unconfirmed_nonces: MessageNonce::MAX,
+			max_confirmation_delay: None,
+		}
+	}
+}
+
+/// Thresholds that are used to raise lane alerts. Alerts are exposed as metrics and logged.
+#[derive(Debug, Clone)]
+pub struct LaneAlertParams {
+	/// If the oldest undelivered message is not delivered for this period, the
+	/// `delivery_stalled` alert is raised.
+	pub max_undelivered_message_age: Duration,
+	/// If number of messages that are delivered, but not yet confirmed exceeds this value, the
+	/// `confirmations_lagging` alert is raised.
+	pub max_unconfirmed_messages: MessageNonce,
 }
 
 /// Message details.
@@ -128,6 +206,17 @@ pub trait SourceClient<P: MessageLane>: RelayClient {
 	/// Returns state of the client.
 	async fn state(&self) -> Result<SourceClientState<P>, Self::Error>;
 
+	/// Returns stream of notifications about new finalized source headers.
+	///
+	/// Every notification makes the loop refresh the source client state immediately, instead
+	/// of waiting for the next `source_tick`. If `None` is returned, the loop is only relying
+	/// on ticks.
+	async fn finalized_headers_notifications(
+		&self,
+	) -> Result<Option<BoxStream<'static, ()>>, Self::Error> {
+		Ok(None)
+	}
+
 	/// Get nonce of instance of latest generated message.
 	async fn latest_generated_nonce(
 		&self,
@@ -193,6 +282,17 @@ pub trait TargetClient<P: MessageLane>: RelayClient {
 	/// Returns state of the client.
 	async fn state(&self) -> Result<TargetClientState<P>, Self::Error>;
 
+	/// Returns stream of notifications about new finalized target headers.
+	///
+	/// Every notification makes the loop refresh the target client state immediately, instead
+	/// of waiting for the next `target_tick`. If `None` is returned, the loop is only relying
+	/// on ticks.
+	async fn finalized_headers_notifications(
+		&self,
+	) -> Result<Option<BoxStream<'static, ()>>, Self::Error> {
+		Ok(None)
+	}
+
 	/// Get nonce of latest received message.
 	async fn latest_received_nonce(
 		&self,
@@ -288,10 +388,14 @@ pub async fn run<P: MessageLane>(
 	exit_signal: impl Future<Output = ()> + Send + 'static,
 ) -> Result<(), relay_utils::Error> {
 	let exit_signal = exit_signal.shared();
+	let control = crate::lane_control::register(params.lane, P::SOURCE_NAME, P::TARGET_NAME);
 	relay_utils::relay_loop(source_client, target_client)
 		.reconnect_delay(params.reconnect_delay)
 		.with_metrics(metrics_params)
-		.loop_metric(MessageLaneLoopMetrics::new(Some(&metrics_prefix::<P>(&params.lane)))?)?
+		.loop_metric(MessageLaneLoopMetrics::new(
+			Some(&metrics_prefix::<P>(&params.lane)),
+			params.alert_params.clone(),
+		)?)?
 		.expose()
 		.await?
 		.run(metrics_prefix::<P>(&params.lane), move |source_client, target_client, metrics| {
@@ -300,6 +404,7 @@ pub async fn run<P: MessageLane>(
 				source_client,
 				target_client,
 				metrics,
+				control.clone(),
 				exit_signal.clone(),
 			)
 		})
@@ -313,14 +418,24 @@ async fn run_until_connection_lost<P: MessageLane, SC: SourceClient<P>, TC: Targ
 	source_client: SC,
 	target_client: TC,
 	metrics_msg: Option<MessageLaneLoopMetrics>,
+	control: LaneControl,
 	exit_signal: impl Future<Output = ()>,
 ) -> Result<(), FailedClient> {
+	relay_utils::initialize::set_loop_log_context(vec![
+		("bridge", format!("{}_to_{}", P::SOURCE_NAME, P::TARGET_NAME)),
+		("lane", hex::encode(params.lane)),
+	]);
+
 	let mut source_retry_backoff = retry_backoff();
 	let mut source_client_is_online = false;
 	let mut source_state_required = true;
 	let source_state = source_client.state().fuse();
 	let source_go_offline_future = futures::future::Fuse::terminated();
 	let source_tick_stream = interval(params.source_tick).fuse();
+	let source_finalized_headers = finalized_headers_notifications_or_pending(
+		source_client.finalized_headers_notifications().await,
+		P::SOURCE_NAME,
+	);
 
 	let mut target_retry_backoff = retry_backoff();
 	let mut target_client_is_online = false;
@@ -328,18 +443,24 @@ async fn run_until_connection_lost<P: MessageLane, SC: SourceClient<P>, TC: Targ
 	let target_state = target_client.state().fuse();
 	let target_go_offline_future = futures::future::Fuse::terminated();
 	let target_tick_stream = interval(params.target_tick).fuse();
+	let target_finalized_headers = finalized_headers_notifications_or_pending(
+		target_client.finalized_headers_notifications().await,
+		P::TARGET_NAME,
+	);
 
 	let (
 		(delivery_source_state_sender, delivery_source_state_receiver),
 		(delivery_target_state_sender, delivery_target_state_receiver),
 	) = (unbounded(), unbounded());
 	let delivery_race_loop = run_message_delivery_race(
+		params.lane,
 		source_client.clone(),
 		delivery_source_state_receiver,
 		target_client.clone(),
 		delivery_target_state_receiver,
 		metrics_msg.clone(),
 		params.delivery_params,
+		control,
 	)
 	.fuse();
 
@@ -348,11 +469,13 @@ async fn run_until_connection_lost<P: MessageLane, SC: SourceClient<P>, TC: Targ
 		(receiving_target_state_sender, receiving_target_state_receiver),
 	) = (unbounded(), unbounded());
 	let receiving_race_loop = run_message_receiving_race(
+		params.lane,
 		source_client.clone(),
 		receiving_source_state_receiver,
 		target_client.clone(),
 		receiving_target_state_receiver,
 		metrics_msg.clone(),
+		params.receiving_params,
 	)
 	.fuse();
 
@@ -362,9 +485,11 @@ async fn run_until_connection_lost<P: MessageLane, SC: SourceClient<P>, TC: Targ
 		source_state,
 		source_go_offline_future,
 		source_tick_stream,
+		source_finalized_headers,
 		target_state,
 		target_go_offline_future,
 		target_tick_stream,
+		target_finalized_headers,
 		delivery_race_loop,
 		receiving_race_loop,
 		exit_signal
@@ -403,6 +528,9 @@ async fn run_until_connection_lost<P: MessageLane, SC: SourceClient<P>, TC: Targ
 			_ = source_tick_stream.next() => {
 				source_state_required = true;
 			},
+			_ = source_finalized_headers.next() => {
+				source_state_required = true;
+			},
 			new_target_state = target_state => {
 				target_state_required = false;
 
@@ -434,6 +562,9 @@ async fn run_until_connection_lost<P: MessageLane, SC: SourceClient<P>, TC: Targ
 			_ = target_tick_stream.next() => {
 				target_state_required = true;
 			},
+			_ = target_finalized_headers.next() => {
+				target_state_required = true;
+			},
 
 			delivery_error = delivery_race_loop => {
 				match delivery_error {
@@ -467,6 +598,27 @@ async fn run_until_connection_lost<P: MessageLane, SC: SourceClient<P>, TC: Targ
 	}
 }
 
+/// Returns finalized headers notifications stream or stream that never yields anything, if
+/// the client does not support notifications.
+fn finalized_headers_notifications_or_pending<E: Debug>(
+	notifications: Result<Option<BoxStream<'static, ()>>, E>,
+	chain: &str,
+) -> futures::stream::Fuse<BoxStream<'static, ()>> {
+	match notifications {
+		Ok(Some(notifications)) => notifications.fuse(),
+		Ok(None) => futures::stream::pending().boxed().fuse(),
+		Err(e) => {
+			log::warn!(
+				target: "bridge",
+				"Failed to subscribe to {} finalized headers: {:?}. Falling back to polling",
+				chain,
+				e,
+			);
+			futures::stream::pending().boxed().fuse()
+		},
+	}
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
 	use std::sync::Arc;
@@ -951,6 +1103,15 @@ pub(crate) mod tests {
 						max_messages_in_single_batch: 4,
 						max_messages_weight_in_single_batch: Weight::from_parts(4, 0),
 						max_messages_size_in_single_batch: 4,
+						cooperative_backoff: None,
+						start_from_nonce: None,
+						skip_nonces: BTreeSet::new(),
+						ancient_source_header_threshold: None,
+					},
+					receiving_params: Default::default(),
+					alert_params: LaneAlertParams {
+						max_undelivered_message_age: Duration::from_secs(60),
+						max_unconfirmed_messages: 4,
 					},
 				},
 				source_client,
@@ -1258,4 +1419,25 @@ pub(crate) mod tests {
 		assert!(!result.target_to_source_header_requirements.is_empty());
 		assert!(!result.source_to_target_header_requirements.is_empty());
 	}
+
+	#[test]
+	fn finalized_headers_notifications_are_used_if_provided() {
+		let mut notifications = finalized_headers_notifications_or_pending::<TestError>(
+			Ok(Some(futures::stream::iter(vec![(), ()]).boxed())),
+			"Test",
+		);
+		assert_eq!(notifications.next().now_or_never(), Some(Some(())));
+		assert_eq!(notifications.next().now_or_never(), Some(Some(())));
+		assert_eq!(notifications.next().now_or_never(), Some(None));
+	}
+
+	#[test]
+	fn finalized_headers_notifications_are_never_yielded_if_not_supported() {
+		let mut notifications =
+			finalized_headers_notifications_or_pending::<TestError>(Ok(None), "Test");
+		assert_eq!(notifications.next().now_or_never(), None);
+
+		let mut notifications = finalized_headers_notifications_or_pending(Err(TestError), "Test");
+		assert_eq!(notifications.next().now_or_never(), None);
+	}
 }
@@ -0,0 +1,931 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Simulation tests for the message lane loop.
+//!
+//! Source and target chains are simulated using the logical clock, which advances on every
+//! client call. Every step, both chains produce new block, including all transactions that
+//! have been submitted and are ready for inclusion. The simulation is driven by the
+//! [`Script`], which may delay transactions inclusion, drop transactions, revert
+//! non-finalized blocks and make nodes report outdated state.
+//!
+//! Since chains state depends only on the number of client calls, the delivery and
+//! confirmation races are exercised without relying on the wall-clock time. Every test
+//! checks that all messages are eventually delivered and confirmed (liveness) and that the
+//! relay never submits transactions with already delivered or confirmed messages
+//! (non-duplication).
+//!
+//! With the `fault-injection` feature, the simulated clients may also fail RPC calls with
+//! connection errors. Those faults are driven by the seeded
+//! [`relay_utils::fault_injection::FaultInjector`], so every soak test is reproducible.
+
+#![cfg(test)]
+
+use crate::message_lane_loop::{
+	run,
+	tests::{
+		TestConfirmationBatchTransaction, TestError, TestMessageLane, TestMessagesBatchTransaction,
+		TestMessagesProof, TestMessagesReceivingProof, TestSourceChainBalance, TestSourceHeaderId,
+	},
+	ClientState, LaneAlertParams, MessageDeliveryParams, MessageDetails, MessageDetailsMap,
+	MessageProofParameters, NoncesSubmitArtifacts, Params, SourceClient, SourceClientState,
+	TargetClient, TargetClientState,
+};
+
+use async_trait::async_trait;
+use bp_messages::{LaneId, MessageNonce, UnrewardedRelayersState, Weight};
+use futures::{channel::mpsc::UnboundedSender, task::Poll, FutureExt, StreamExt};
+use parking_lot::Mutex;
+#[cfg(feature = "fault-injection")]
+use relay_utils::fault_injection::{FaultInjectionParams, FaultInjector};
+use relay_utils::{
+	metrics::MetricsParams, relay_loop::Client as RelayClient, HeaderId, TrackedTransactionStatus,
+	TransactionTracker,
+};
+use std::{
+	collections::{BTreeSet, HashMap},
+	ops::RangeInclusive,
+	sync::Arc,
+	task::Waker,
+	time::Duration,
+};
+
+/// Simulation step (logical time).
+type Step = u64;
+/// Header id of both simulated chains.
+type SimulatedHeaderId = TestSourceHeaderId;
+
+/// Difference between hashes of headers with the same number at different forks.
+const FORK_HASH_OFFSET: u64 = 1_000_000;
+
+/// One of simulated chains.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+	/// Source chain, where messages are generated and where delivery is confirmed.
+	Source,
+	/// Target chain, where messages are delivered.
+	Target,
+}
+
+/// The simulation script.
+#[derive(Clone, Debug)]
+struct Script {
+	/// Number of messages that are sent at the source chain. Single message is sent at every
+	/// source block until all messages are sent.
+	messages_count: MessageNonce,
+	/// Number of steps between transaction submission and its inclusion. The `i`-th
+	/// transaction, submitted to the chain, is delayed by `transaction_latencies[i % len]`.
+	transaction_latencies: Vec<Step>,
+	/// Number of steps between relay request for peer header and its appearance at the chain.
+	headers_relay_latency: Step,
+	/// Number of blocks between best and best finalized block.
+	finality_lag: u64,
+	/// Number of blocks that nodes are lagging behind the best block when reporting their state.
+	rpc_lag: u64,
+	/// Scheduled reorgs: `(step, side, depth)`. Finalized blocks are never reverted.
+	reorgs: Vec<(Step, Side, u64)>,
+	/// Transactions that are dropped instead of being included: `(side, index)`, where `index`
+	/// is the index of transaction, submitted to the chain.
+	dropped_transactions: Vec<(Side, usize)>,
+	/// Maximal number of steps. If messages are not delivered and confirmed within this number
+	/// of steps, the test fails.
+	max_steps: Step,
+	/// Faults that are injected into simulated clients.
+	#[cfg(feature = "fault-injection")]
+	faults: FaultInjectionParams,
+}
+
+impl Default for Script {
+	fn default() -> Self {
+		Script {
+			messages_count: 16,
+			transaction_latencies: vec![1],
+			headers_relay_latency: 1,
+			finality_lag: 0,
+			rpc_lag: 0,
+			reorgs: Vec::new(),
+			dropped_transactions: Vec::new(),
+			max_steps: 10_000,
+			#[cfg(feature = "fault-injection")]
+			faults: Default::default(),
+		}
+	}
+}
+
+/// Transaction, submitted to the simulated chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Transaction {
+	/// Messages delivery transaction, submitted to the target chain.
+	Delivery(TestMessagesProof),
+	/// Messages delivery confirmation transaction, submitted to the source chain.
+	Confirmation(TestMessagesReceivingProof),
+}
+
+/// Reason of transaction rejection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Rejection {
+	/// Transaction brings messages or confirmations that are already known to the chain.
+	Duplicate,
+	/// Transaction brings messages with nonces that are far ahead of the chain state.
+	Gap,
+}
+
+/// Transaction status.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TransactionStatus {
+	/// Transaction is in the pool.
+	Pending,
+	/// Transaction is included in the non-finalized block.
+	Included(SimulatedHeaderId),
+	/// Transaction is included in the finalized block.
+	Finalized(SimulatedHeaderId),
+	/// Transaction is dropped from the pool or its block has been reverted.
+	Lost,
+}
+
+/// Transaction, submitted to the simulated chain.
+#[derive(Clone, Debug)]
+struct SubmittedTransaction {
+	/// The transaction itself.
+	transaction: Transaction,
+	/// Step, at which the transaction becomes ready for inclusion.
+	include_at: Step,
+	/// Whether the transaction is dropped instead of being included.
+	is_dropped: bool,
+	/// Current transaction status.
+	status: TransactionStatus,
+	/// Rejection reason, if transaction has been included, but has failed.
+	rejection: Option<Rejection>,
+}
+
+/// Lane state at the simulated chain block.
+#[derive(Clone, Copy, Debug, Default)]
+struct BlockState {
+	/// Nonce of the latest generated message. Only used at the source chain.
+	latest_generated_nonce: MessageNonce,
+	/// Nonce of the latest received message at the target chain or nonce of the latest
+	/// message, which delivery has been confirmed at the source chain.
+	latest_received_nonce: MessageNonce,
+	/// Nonce of the latest message, which delivery confirmation is known to the target chain.
+	/// Only used at the target chain.
+	latest_confirmed_nonce: MessageNonce,
+}
+
+impl BlockState {
+	/// Apply transaction to the state.
+	fn apply(&mut self, transaction: &Transaction) -> Result<(), Rejection> {
+		match *transaction {
+			Transaction::Delivery((ref nonces, latest_confirmed_nonce)) => {
+				if *nonces.start() <= self.latest_received_nonce {
+					return Err(Rejection::Duplicate)
+				}
+				if *nonces.start() > self.latest_received_nonce + 1 {
+					return Err(Rejection::Gap)
+				}
+
+				self.latest_received_nonce = *nonces.end();
+				if let Some(latest_confirmed_nonce) = latest_confirmed_nonce {
+					self.latest_confirmed_nonce =
+						std::cmp::max(self.latest_confirmed_nonce, latest_confirmed_nonce);
+				}
+			},
+			Transaction::Confirmation(latest_received_nonce) => {
+				if latest_received_nonce <= self.latest_received_nonce {
+					return Err(Rejection::Duplicate)
+				}
+
+				self.latest_received_nonce = latest_received_nonce;
+			},
+		}
+		Ok(())
+	}
+}
+
+/// Block of the simulated chain.
+#[derive(Clone, Debug)]
+struct Block {
+	/// Block id.
+	id: SimulatedHeaderId,
+	/// Lane state after block is imported.
+	state: BlockState,
+	/// Indices of transactions, included into this block.
+	transactions: Vec<usize>,
+}
+
+/// Simulated chain.
+#[derive(Clone, Debug)]
+struct SimulatedChain {
+	/// Canonical chain. Block with number `n` is stored at `n`-th position.
+	blocks: Vec<Block>,
+	/// Lane states at all produced blocks, including reverted.
+	states: HashMap<u64, BlockState>,
+	/// Current fork index.
+	fork: u64,
+	/// Number of the best finalized block.
+	best_finalized_number: u64,
+	/// Number of the best finalized peer chain block, known to this chain.
+	best_finalized_peer_number: u64,
+	/// Peer chain headers that are going to be imported: `(step, number)`.
+	pending_peer_headers: Vec<(Step, u64)>,
+	/// All transactions, submitted to this chain.
+	transactions: Vec<SubmittedTransaction>,
+}
+
+impl SimulatedChain {
+	fn new() -> Self {
+		let genesis =
+			Block { id: HeaderId(0, 0), state: BlockState::default(), transactions: Vec::new() };
+		SimulatedChain {
+			states: vec![(genesis.id.1, genesis.state)].into_iter().collect(),
+			blocks: vec![genesis],
+			fork: 0,
+			best_finalized_number: 0,
+			best_finalized_peer_number: 0,
+			pending_peer_headers: Vec::new(),
+			transactions: Vec::new(),
+		}
+	}
+
+	fn best_block(&self) -> &Block {
+		self.blocks.last().expect("chain always has genesis block; qed")
+	}
+
+	fn state_at(&self, id: SimulatedHeaderId) -> BlockState {
+		*self
+			.states
+			.get(&id.1)
+			.unwrap_or_else(|| panic!("Unknown block requested: {id:?}"))
+	}
+
+	/// Returns id of the best block, reported by the node that is lagging `rpc_lag` blocks
+	/// behind the chain.
+	fn visible_best_block_id(&self, rpc_lag: u64) -> SimulatedHeaderId {
+		let best_number = self.best_block().id.0;
+		self.blocks[best_number.saturating_sub(rpc_lag) as usize].id
+	}
+
+	/// Produce new block, including all transactions that are ready for inclusion.
+	fn produce_block(&mut self, step: Step, script: &Script, side: Side) {
+		let parent = self.best_block();
+		let number = parent.id.0 + 1;
+		let id = HeaderId(number, self.fork * FORK_HASH_OFFSET + number);
+		let mut state = parent.state;
+		if side == Side::Source {
+			state.latest_generated_nonce =
+				std::cmp::min(state.latest_generated_nonce + 1, script.messages_count);
+		}
+
+		let mut transactions = Vec::new();
+		for (index, transaction) in self.transactions.iter_mut().enumerate() {
+			if transaction.status != TransactionStatus::Pending || transaction.include_at > step {
+				continue
+			}
+			if transaction.is_dropped {
+				transaction.status = TransactionStatus::Lost;
+				continue
+			}
+
+			transaction.rejection = state.apply(&transaction.transaction).err();
+			transaction.status = TransactionStatus::Included(id);
+			transactions.push(index);
+		}
+
+		let best_finalized_peer_number = self
+			.pending_peer_headers
+			.iter()
+			.filter(|(import_at, _)| *import_at <= step)
+			.map(|(_, number)| *number)
+			.max();
+		if let Some(best_finalized_peer_number) = best_finalized_peer_number {
+			self.best_finalized_peer_number =
+				std::cmp::max(self.best_finalized_peer_number, best_finalized_peer_number);
+		}
+		self.pending_peer_headers.retain(|(import_at, _)| *import_at > step);
+
+		self.states.insert(id.1, state);
+		self.blocks.push(Block { id, state, transactions });
+	}
+
+	/// Finalize blocks that are at least `finality_lag` blocks behind the best block.
+	fn finalize_blocks(&mut self, finality_lag: u64) {
+		let best_number = self.best_block().id.0;
+		self.best_finalized_number =
+			std::cmp::max(self.best_finalized_number, best_number.saturating_sub(finality_lag));
+		for transaction in &mut self.transactions {
+			if let TransactionStatus::Included(id) = transaction.status {
+				if id.0 <= self.best_finalized_number {
+					transaction.status = TransactionStatus::Finalized(id);
+				}
+			}
+		}
+	}
+
+	/// Revert at most `depth` non-finalized blocks. Transactions of reverted blocks are lost.
+	fn revert_blocks(&mut self, depth: u64) {
+		let best_number = self.best_block().id.0;
+		let depth = std::cmp::min(depth, best_number - self.best_finalized_number);
+		for _ in 0..depth {
+			let block = self.blocks.pop().expect("we never revert finalized blocks; qed");
+			for index in block.transactions {
+				self.transactions[index].status = TransactionStatus::Lost;
+			}
+		}
+		self.fork += 1;
+	}
+
+	/// Returns all included transactions that have been rejected with given reason.
+	fn rejected_transactions(&self, rejection: Rejection) -> Vec<Transaction> {
+		self.transactions
+			.iter()
+			.filter(|transaction| transaction.rejection == Some(rejection))
+			.map(|transaction| transaction.transaction.clone())
+			.collect()
+	}
+}
+
+/// Simulated source and target chains.
+#[derive(Clone, Debug)]
+struct SimulatedChains {
+	/// The simulation script.
+	script: Script,
+	/// Current step.
+	step: Step,
+	/// Source chain.
+	source: SimulatedChain,
+	/// Target chain.
+	target: SimulatedChain,
+	/// Tasks that are waiting for transaction status updates.
+	wakers: Vec<Waker>,
+	/// Exit signal sender. It is fired when all messages are delivered and confirmed.
+	exit_sender: Option<UnboundedSender<()>>,
+	/// Number of clients reconnects.
+	reconnects: usize,
+	/// Injector of client faults.
+	#[cfg(feature = "fault-injection")]
+	fault_injector: FaultInjector,
+}
+
+impl SimulatedChains {
+	fn chain(&self, side: Side) -> &SimulatedChain {
+		match side {
+			Side::Source => &self.source,
+			Side::Target => &self.target,
+		}
+	}
+
+	fn chain_mut(&mut self, side: Side) -> &mut SimulatedChain {
+		match side {
+			Side::Source => &mut self.source,
+			Side::Target => &mut self.target,
+		}
+	}
+
+	/// Advance the logical clock.
+	fn advance(&mut self) {
+		self.step += 1;
+		assert!(
+			self.step <= self.script.max_steps,
+			"Messages are not delivered and confirmed in {} steps. Source: {:?}. Target: {:?}",
+			self.script.max_steps,
+			self.source.best_block(),
+			self.target.best_block(),
+		);
+
+		let step = self.step;
+		for (_, side, depth) in self.script.reorgs.iter().filter(|(at, _, _)| *at == step) {
+			match side {
+				Side::Source => self.source.revert_blocks(*depth),
+				Side::Target => self.target.revert_blocks(*depth),
+			}
+		}
+
+		self.source.produce_block(step, &self.script, Side::Source);
+		self.source.finalize_blocks(self.script.finality_lag);
+		self.target.produce_block(step, &self.script, Side::Target);
+		self.target.finalize_blocks(self.script.finality_lag);
+
+		self.wakers.drain(..).for_each(Waker::wake);
+
+		let messages_count = self.script.messages_count;
+		let is_delivered = self.target.blocks[self.target.best_finalized_number as usize]
+			.state
+			.latest_received_nonce ==
+			messages_count;
+		let is_confirmed = self.source.blocks[self.source.best_finalized_number as usize]
+			.state
+			.latest_received_nonce ==
+			messages_count;
+		if is_delivered && is_confirmed {
+			if let Some(exit_sender) = self.exit_sender.take() {
+				exit_sender.unbounded_send(()).unwrap();
+			}
+		}
+	}
+
+	/// Returns state of the given chain, as it is reported by the chain node.
+	fn client_state(&self, side: Side) -> ClientState<SimulatedHeaderId, SimulatedHeaderId> {
+		let (chain, peer) = match side {
+			Side::Source => (&self.source, &self.target),
+			Side::Target => (&self.target, &self.source),
+		};
+		let best_self = chain.visible_best_block_id(self.script.rpc_lag);
+		let best_finalized_number = std::cmp::min(chain.best_finalized_number, best_self.0);
+		let best_finalized_peer = peer.blocks[chain.best_finalized_peer_number as usize].id;
+		ClientState {
+			best_self,
+			best_finalized_self: chain.blocks[best_finalized_number as usize].id,
+			best_finalized_peer_at_best_self: Some(best_finalized_peer),
+			actual_best_finalized_peer_at_best_self: Some(best_finalized_peer),
+		}
+	}
+
+	/// Submit transaction to the given chain.
+	fn submit_transaction(&mut self, side: Side, transaction: Transaction) -> usize {
+		let step = self.step;
+		let latencies = &self.script.transaction_latencies;
+		let index = self.chain(side).transactions.len();
+		let include_at = step + latencies[index % latencies.len()];
+		let is_dropped = self.script.dropped_transactions.contains(&(side, index));
+		self.chain_mut(side).transactions.push(SubmittedTransaction {
+			transaction,
+			include_at,
+			is_dropped,
+			status: TransactionStatus::Pending,
+			rejection: None,
+		});
+		index
+	}
+
+	/// Request relay of finalized peer chain header to the given chain.
+	fn require_peer_header(&mut self, side: Side, id: SimulatedHeaderId) {
+		let import_at = self.step + self.script.headers_relay_latency;
+		self.chain_mut(side).pending_peer_headers.push((import_at, id.0));
+	}
+
+	/// Returns connection error if the RPC call needs to fail.
+	#[cfg(feature = "fault-injection")]
+	fn inject_rpc_failure(&mut self) -> Result<(), TestError> {
+		if self.fault_injector.fail_rpc_call() {
+			return Err(TestError)
+		}
+		Ok(())
+	}
+
+	#[cfg(not(feature = "fault-injection"))]
+	fn inject_rpc_failure(&mut self) -> Result<(), TestError> {
+		Ok(())
+	}
+}
+
+/// Transaction tracker of the simulated chain.
+struct SimulatedTransactionTracker {
+	chains: Arc<Mutex<SimulatedChains>>,
+	side: Side,
+	index: usize,
+}
+
+#[async_trait]
+impl TransactionTracker for SimulatedTransactionTracker {
+	type HeaderId = SimulatedHeaderId;
+
+	async fn wait(self) -> TrackedTransactionStatus<SimulatedHeaderId> {
+		futures::future::poll_fn(|cx| {
+			let mut chains = self.chains.lock();
+			match chains.chain(self.side).transactions[self.index].status {
+				TransactionStatus::Finalized(id) =>
+					Poll::Ready(TrackedTransactionStatus::Finalized(id)),
+				TransactionStatus::Lost => Poll::Ready(TrackedTransactionStatus::Lost),
+				TransactionStatus::Pending | TransactionStatus::Included(_) => {
+					chains.wakers.push(cx.waker().clone());
+					Poll::Pending
+				},
+			}
+		})
+		.await
+	}
+}
+
+#[derive(Clone)]
+struct SimulatedSourceClient {
+	chains: Arc<Mutex<SimulatedChains>>,
+}
+
+#[async_trait]
+impl RelayClient for SimulatedSourceClient {
+	type Error = TestError;
+
+	async fn reconnect(&mut self) -> Result<(), TestError> {
+		self.chains.lock().reconnects += 1;
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl SourceClient<TestMessageLane> for SimulatedSourceClient {
+	type BatchTransaction = TestConfirmationBatchTransaction;
+	type TransactionTracker = SimulatedTransactionTracker;
+
+	async fn state(&self) -> Result<SourceClientState<TestMessageLane>, TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		Ok(chains.client_state(Side::Source))
+	}
+
+	async fn latest_generated_nonce(
+		&self,
+		id: SimulatedHeaderId,
+	) -> Result<(SimulatedHeaderId, MessageNonce), TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		Ok((id, chains.source.state_at(id).latest_generated_nonce))
+	}
+
+	async fn latest_confirmed_received_nonce(
+		&self,
+		id: SimulatedHeaderId,
+	) -> Result<(SimulatedHeaderId, MessageNonce), TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		Ok((id, chains.source.state_at(id).latest_received_nonce))
+	}
+
+	async fn generated_message_details(
+		&self,
+		_id: SimulatedHeaderId,
+		nonces: RangeInclusive<MessageNonce>,
+	) -> Result<MessageDetailsMap<TestSourceChainBalance>, TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		Ok(nonces
+			.map(|nonce| {
+				(
+					nonce,
+					MessageDetails {
+						dispatch_weight: Weight::from_parts(1, 0),
+						size: 1,
+						reward: 1,
+					},
+				)
+			})
+			.collect())
+	}
+
+	async fn prove_messages(
+		&self,
+		id: SimulatedHeaderId,
+		nonces: RangeInclusive<MessageNonce>,
+		proof_parameters: MessageProofParameters,
+	) -> Result<(SimulatedHeaderId, RangeInclusive<MessageNonce>, TestMessagesProof), TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		let latest_confirmed_nonce = if proof_parameters.outbound_state_proof_required {
+			Some(chains.source.state_at(id).latest_received_nonce)
+		} else {
+			None
+		};
+		Ok((id, nonces.clone(), (nonces, latest_confirmed_nonce)))
+	}
+
+	async fn submit_messages_receiving_proof(
+		&self,
+		_maybe_batch_tx: Option<Self::BatchTransaction>,
+		_generated_at_block: SimulatedHeaderId,
+		proof: TestMessagesReceivingProof,
+	) -> Result<Self::TransactionTracker, TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		let index = chains.submit_transaction(Side::Source, Transaction::Confirmation(proof));
+		Ok(SimulatedTransactionTracker { chains: self.chains.clone(), side: Side::Source, index })
+	}
+
+	async fn require_target_header_on_source(
+		&self,
+		id: SimulatedHeaderId,
+	) -> Result<Option<Self::BatchTransaction>, TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		chains.require_peer_header(Side::Source, id);
+		Ok(None)
+	}
+}
+
+#[derive(Clone)]
+struct SimulatedTargetClient {
+	chains: Arc<Mutex<SimulatedChains>>,
+}
+
+#[async_trait]
+impl RelayClient for SimulatedTargetClient {
+	type Error = TestError;
+
+	async fn reconnect(&mut self) -> Result<(), TestError> {
+		self.chains.lock().reconnects += 1;
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl TargetClient<TestMessageLane> for SimulatedTargetClient {
+	type BatchTransaction = TestMessagesBatchTransaction;
+	type TransactionTracker = SimulatedTransactionTracker;
+
+	async fn state(&self) -> Result<TargetClientState<TestMessageLane>, TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		Ok(chains.client_state(Side::Target))
+	}
+
+	async fn latest_received_nonce(
+		&self,
+		id: SimulatedHeaderId,
+	) -> Result<(SimulatedHeaderId, MessageNonce), TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		Ok((id, chains.target.state_at(id).latest_received_nonce))
+	}
+
+	async fn latest_confirmed_received_nonce(
+		&self,
+		id: SimulatedHeaderId,
+	) -> Result<(SimulatedHeaderId, MessageNonce), TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		Ok((id, chains.target.state_at(id).latest_confirmed_nonce))
+	}
+
+	async fn unrewarded_relayers_state(
+		&self,
+		id: SimulatedHeaderId,
+	) -> Result<(SimulatedHeaderId, UnrewardedRelayersState), TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		Ok((
+			id,
+			UnrewardedRelayersState {
+				unrewarded_relayer_entries: 0,
+				messages_in_oldest_entry: 0,
+				total_messages: 0,
+				last_delivered_nonce: 0,
+			},
+		))
+	}
+
+	async fn prove_messages_receiving(
+		&self,
+		id: SimulatedHeaderId,
+	) -> Result<(SimulatedHeaderId, TestMessagesReceivingProof), TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		Ok((id, chains.target.state_at(id).latest_received_nonce))
+	}
+
+	async fn submit_messages_proof(
+		&self,
+		_maybe_batch_tx: Option<Self::BatchTransaction>,
+		_generated_at_header: SimulatedHeaderId,
+		nonces: RangeInclusive<MessageNonce>,
+		proof: TestMessagesProof,
+	) -> Result<NoncesSubmitArtifacts<Self::TransactionTracker>, TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		let index = chains.submit_transaction(Side::Target, Transaction::Delivery(proof));
+		Ok(NoncesSubmitArtifacts {
+			nonces,
+			tx_tracker: SimulatedTransactionTracker {
+				chains: self.chains.clone(),
+				side: Side::Target,
+				index,
+			},
+		})
+	}
+
+	async fn require_source_header_on_target(
+		&self,
+		id: SimulatedHeaderId,
+	) -> Result<Option<Self::BatchTransaction>, TestError> {
+		let mut chains = self.chains.lock();
+		chains.advance();
+		chains.inject_rpc_failure()?;
+		chains.require_peer_header(Side::Target, id);
+		Ok(None)
+	}
+}
+
+/// Run message lane loop until all messages are delivered and confirmed.
+fn run_simulation(script: Script) -> SimulatedChains {
+	let (exit_sender, exit_receiver) = futures::channel::mpsc::unbounded();
+	let chains = Arc::new(Mutex::new(SimulatedChains {
+		#[cfg(feature = "fault-injection")]
+		fault_injector: FaultInjector::new(script.faults.clone()),
+		script,
+		step: 0,
+		source: SimulatedChain::new(),
+		target: SimulatedChain::new(),
+		wakers: Vec::new(),
+		exit_sender: Some(exit_sender),
+		reconnects: 0,
+	}));
+
+	let _ = async_std::task::block_on(run(
+		Params {
+			lane: LaneId([0, 0, 0, 1]),
+			source_tick: Duration::from_millis(10),
+			target_tick: Duration::from_millis(10),
+			reconnect_delay: Duration::from_millis(0),
+			delivery_params: MessageDeliveryParams {
+				max_unrewarded_relayer_entries_at_target: 4,
+				max_unconfirmed_nonces_at_target: 4,
+				max_messages_in_single_batch: 4,
+				max_messages_weight_in_single_batch: Weight::from_parts(4, 0),
+				max_messages_size_in_single_batch: 4,
+				cooperative_backoff: None,
+				start_from_nonce: None,
+				skip_nonces: BTreeSet::new(),
+				ancient_source_header_threshold: None,
+			},
+			receiving_params: Default::default(),
+			alert_params: LaneAlertParams {
+				max_undelivered_message_age: Duration::from_secs(60),
+				max_unconfirmed_messages: 4,
+			},
+		},
+		SimulatedSourceClient { chains: chains.clone() },
+		SimulatedTargetClient { chains: chains.clone() },
+		MetricsParams::disabled(),
+		exit_receiver.into_future().map(|(_, _)| ()),
+	));
+
+	let chains = chains.lock().clone();
+	assert_eq!(
+		chains.target.best_block().state.latest_received_nonce,
+		chains.script.messages_count
+	);
+	assert_eq!(
+		chains.source.best_block().state.latest_received_nonce,
+		chains.script.messages_count
+	);
+	// after reconnect, the loop forgets about submitted transactions and it may submit the same
+	// messages again, while previous transaction is still in the pool
+	if chains.reconnects == 0 {
+		assert_eq!(chains.target.rejected_transactions(Rejection::Duplicate), vec![]);
+		assert_eq!(chains.source.rejected_transactions(Rejection::Duplicate), vec![]);
+	}
+	chains
+}
+
+/// Returns nonces of messages, delivered by the given delivery transactions.
+fn delivered_nonces(transactions: Vec<Transaction>) -> Vec<RangeInclusive<MessageNonce>> {
+	transactions
+		.into_iter()
+		.filter_map(|transaction| match transaction {
+			Transaction::Delivery((nonces, _)) => Some(nonces),
+			Transaction::Confirmation(_) => None,
+		})
+		.collect()
+}
+
+#[test]
+fn simulated_lane_delivers_and_confirms_all_messages() {
+	let chains = run_simulation(Script {
+		transaction_latencies: vec![1, 3, 2],
+		finality_lag: 2,
+		..Default::default()
+	});
+
+	// without reorgs there's no way for delivery transaction to be rejected
+	assert_eq!(chains.target.rejected_transactions(Rejection::Gap), vec![]);
+	// every delivery transaction brings at most `max_messages_in_single_batch` messages
+	assert!(chains
+		.target
+		.transactions
+		.iter()
+		.filter_map(|transaction| match transaction.transaction {
+			Transaction::Delivery((ref nonces, _)) => Some(nonces.end() - nonces.start() + 1),
+			Transaction::Confirmation(_) => None,
+		})
+		.all(|messages| messages <= 4));
+}
+
+#[test]
+fn simulated_lane_resubmits_dropped_transactions() {
+	let chains = run_simulation(Script {
+		transaction_latencies: vec![2],
+		finality_lag: 1,
+		dropped_transactions: vec![(Side::Target, 0), (Side::Target, 2), (Side::Source, 0)],
+		..Default::default()
+	});
+
+	// dropped transactions are never included and messages of every dropped delivery
+	// transaction are submitted again
+	assert_eq!(chains.target.rejected_transactions(Rejection::Gap), vec![]);
+	let first_delivered_nonce = |index: usize| {
+		let delivered_nonces =
+			delivered_nonces(vec![chains.target.transactions[index].transaction.clone()]);
+		*delivered_nonces[0].start()
+	};
+	assert_eq!(first_delivered_nonce(0), 1);
+	assert_eq!(first_delivered_nonce(1), first_delivered_nonce(0));
+	assert_eq!(first_delivered_nonce(3), first_delivered_nonce(2));
+	assert_eq!(chains.target.transactions[0].status, TransactionStatus::Lost);
+	assert_eq!(chains.target.transactions[2].status, TransactionStatus::Lost);
+	assert_eq!(chains.source.transactions[0].status, TransactionStatus::Lost);
+}
+
+#[test]
+fn simulated_lane_survives_reorgs() {
+	let chains = run_simulation(Script {
+		transaction_latencies: vec![1, 2],
+		finality_lag: 4,
+		reorgs: vec![
+			(20, Side::Target, 3),
+			(40, Side::Source, 3),
+			(60, Side::Target, 4),
+			(80, Side::Source, 2),
+		],
+		..Default::default()
+	});
+
+	// messages of reverted transactions are delivered again, but there are no duplicates at the
+	// canonical chain
+	let canonical_deliveries = delivered_nonces(
+		chains
+			.target
+			.blocks
+			.iter()
+			.flat_map(|block| block.transactions.iter())
+			.map(|index| &chains.target.transactions[*index])
+			.filter(|transaction| transaction.rejection.is_none())
+			.map(|transaction| transaction.transaction.clone())
+			.collect(),
+	);
+	let mut expected_begin = 1;
+	for nonces in canonical_deliveries {
+		assert_eq!(*nonces.start(), expected_begin);
+		expected_begin = nonces.end() + 1;
+	}
+	assert_eq!(expected_begin, chains.script.messages_count + 1);
+}
+
+#[test]
+fn simulated_lane_works_with_lagging_nodes() {
+	let chains = run_simulation(Script {
+		transaction_latencies: vec![3],
+		headers_relay_latency: 5,
+		finality_lag: 2,
+		rpc_lag: 2,
+		..Default::default()
+	});
+
+	// outdated node state never makes relay to resubmit messages or confirmations
+	assert_eq!(chains.target.rejected_transactions(Rejection::Gap), vec![]);
+}
+
+#[test]
+#[cfg(feature = "fault-injection")]
+fn simulated_lane_survives_rpc_failures() {
+	for seed in 0..16 {
+		let chains = run_simulation(Script {
+			messages_count: 32,
+			transaction_latencies: vec![1, 3, 2],
+			finality_lag: 2,
+			reorgs: vec![(40, Side::Target, 2), (80, Side::Source, 2)],
+			dropped_transactions: vec![(Side::Target, 1), (Side::Source, 1)],
+			max_steps: 100_000,
+			faults: FaultInjectionParams {
+				seed,
+				rpc_failure_probability: 0.05,
+				..Default::default()
+			},
+			..Default::default()
+		});
+
+		assert!(chains.reconnects > 0);
+		assert_ne!(chains.fault_injector.stats().failed_rpc_calls, 0);
+	}
+}
@@ -13,15 +13,21 @@
 
 //! Message delivery race delivers proof-of-messages from "lane.source" to "lane.target".
 
-use std::{collections::VecDeque, marker::PhantomData, ops::RangeInclusive};
+use std::{
+	collections::{BTreeSet, VecDeque},
+	marker::PhantomData,
+	ops::RangeInclusive,
+	time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
 use futures::stream::FusedStream;
 
-use bp_messages::{MessageNonce, UnrewardedRelayersState, Weight};
+use bp_messages::{LaneId, MessageNonce, UnrewardedRelayersState, Weight};
 use relay_utils::FailedClient;
 
 use crate::{
+	lane_control::{DeliveryLimits, LaneControl},
 	message_lane::{MessageLane, SourceHeaderIdOf, TargetHeaderIdOf},
 	message_lane_loop::{
 		MessageDeliveryParams, MessageDetailsMap, MessageProofParameters, NoncesSubmitArtifacts,
@@ -34,19 +40,22 @@ use crate::{
 		TargetClient, TargetClientNonces,
 	},
 	message_race_strategy::BasicStrategy,
-	metrics::MessageLaneLoopMetrics,
+	metrics::{DroppedTransactionTracker, MessageLaneLoopMetrics},
 };
 
 /// Run message delivery race.
 pub async fn run<P: MessageLane>(
+	lane: LaneId,
 	source_client: impl MessageLaneSourceClient<P>,
 	source_state_updates: impl FusedStream<Item = SourceClientState<P>>,
 	target_client: impl MessageLaneTargetClient<P>,
 	target_state_updates: impl FusedStream<Item = TargetClientState<P>>,
 	metrics_msg: Option<MessageLaneLoopMetrics>,
 	params: MessageDeliveryParams,
+	control: LaneControl,
 ) -> Result<(), FailedClient> {
 	crate::message_race_loop::run(
+		lane,
 		MessageDeliveryRaceSource {
 			client: source_client.clone(),
 			metrics_msg: metrics_msg.clone(),
@@ -68,10 +77,16 @@ pub async fn run<P: MessageLane>(
 			max_messages_in_single_batch: params.max_messages_in_single_batch,
 			max_messages_weight_in_single_batch: params.max_messages_weight_in_single_batch,
 			max_messages_size_in_single_batch: params.max_messages_size_in_single_batch,
+			cooperative_backoff: params.cooperative_backoff,
+			start_from_nonce: params.start_from_nonce,
+			skip_nonces: params.skip_nonces,
+			ancient_source_header_threshold: params.ancient_source_header_threshold,
+			last_foreign_delivery: None,
 			latest_confirmed_nonces_at_source: VecDeque::new(),
 			target_nonces: None,
 			strategy: BasicStrategy::new(),
 			metrics_msg,
+			control,
 		},
 	)
 	.await
@@ -172,7 +187,7 @@ where
 	type Error = C::Error;
 	type TargetNoncesData = DeliveryRaceTargetNoncesData;
 	type BatchTransaction = C::BatchTransaction;
-	type TransactionTracker = C::TransactionTracker;
+	type TransactionTracker = DroppedTransactionTracker<C::TransactionTracker>;
 
 	async fn require_source_header(
 		&self,
@@ -219,9 +234,18 @@ where
 		nonces: RangeInclusive<MessageNonce>,
 		proof: P::MessagesProof,
 	) -> Result<NoncesSubmitArtifacts<Self::TransactionTracker>, Self::Error> {
-		self.client
+		let artifacts = self
+			.client
 			.submit_messages_proof(maybe_batch_tx, generated_at_block, nonces, proof)
-			.await
+			.await?;
+		Ok(NoncesSubmitArtifacts {
+			nonces: artifacts.nonces,
+			tx_tracker: DroppedTransactionTracker::new(
+				artifacts.tx_tracker,
+				"delivery",
+				self.metrics_msg.clone(),
+			),
+		})
 	}
 }
 
@@ -252,6 +276,18 @@ struct MessageDeliveryStrategy<P: MessageLane, SC, TC> {
 	max_messages_weight_in_single_batch: Weight,
 	/// Maximal messages size in the single delivery transaction.
 	max_messages_size_in_single_batch: u32,
+	/// Period during which we're not delivering messages after other relayer has delivered
+	/// some messages.
+	cooperative_backoff: Option<Duration>,
+	/// We're not delivering messages with nonces lower than that.
+	start_from_nonce: Option<MessageNonce>,
+	/// Nonces of messages that we're not delivering.
+	skip_nonces: BTreeSet<MessageNonce>,
+	/// If the best source header at target is that many blocks behind the best finalized source
+	/// header, we're switching to the historical backfill mode.
+	ancient_source_header_threshold: Option<u32>,
+	/// The moment when we have last seen messages, delivered by other relayer.
+	last_foreign_delivery: Option<Instant>,
 	/// Latest confirmed nonces at the source client + the header id where we have first met this
 	/// nonce.
 	latest_confirmed_nonces_at_source: VecDeque<(SourceHeaderIdOf<P>, MessageNonce)>,
@@ -261,6 +297,8 @@ struct MessageDeliveryStrategy<P: MessageLane, SC, TC> {
 	strategy: MessageDeliveryStrategyBase<P>,
 	/// Message lane metrics.
 	metrics_msg: Option<MessageLaneLoopMetrics>,
+	/// Runtime control of the lane.
+	control: LaneControl,
 }
 
 type MessageDeliveryStrategyBase<P> = BasicStrategy<
@@ -283,14 +321,34 @@ impl<P: MessageLane, SC, TC> std::fmt::Debug for MessageDeliveryStrategy<P, SC,
 			.field("max_messages_in_single_batch", &self.max_messages_in_single_batch)
 			.field("max_messages_weight_in_single_batch", &self.max_messages_weight_in_single_batch)
 			.field("max_messages_size_in_single_batch", &self.max_messages_size_in_single_batch)
+			.field("cooperative_backoff", &self.cooperative_backoff)
+			.field("start_from_nonce", &self.start_from_nonce)
+			.field("skip_nonces", &self.skip_nonces)
+			.field("ancient_source_header_threshold", &self.ancient_source_header_threshold)
+			.field("last_foreign_delivery", &self.last_foreign_delivery)
 			.field("latest_confirmed_nonces_at_source", &self.latest_confirmed_nonces_at_source)
 			.field("target_nonces", &self.target_nonces)
 			.field("strategy", &self.strategy)
+			.field("control", &self.control)
 			.finish()
 	}
 }
 
 impl<P: MessageLane, SC, TC> MessageDeliveryStrategy<P, SC, TC> {
+	/// Returns delivery limits that shall be used now.
+	///
+	/// Limits, configured at startup, are overridden by limits that have been updated (e.g.
+	/// after the target runtime upgrade) while the race is running.
+	fn delivery_limits(&self) -> DeliveryLimits {
+		self.control.delivery_limits().unwrap_or(DeliveryLimits {
+			max_unrewarded_relayer_entries_at_target: self.max_unrewarded_relayer_entries_at_target,
+			max_unconfirmed_nonces_at_target: self.max_unconfirmed_nonces_at_target,
+			max_messages_in_single_batch: self.max_messages_in_single_batch,
+			max_messages_weight_in_single_batch: self.max_messages_weight_in_single_batch,
+			max_messages_size_in_single_batch: self.max_messages_size_in_single_batch,
+		})
+	}
+
 	/// Returns total weight of all undelivered messages.
 	fn dispatch_weight_for_range(&self, range: &RangeInclusive<MessageNonce>) -> Weight {
 		self.strategy
@@ -304,6 +362,85 @@ impl<P: MessageLane, SC, TC> MessageDeliveryStrategy<P, SC, TC> {
 			})
 			.fold(Weight::zero(), |total, weight| total.saturating_add(weight))
 	}
+
+	/// Excludes nonces that the relay operator has asked us to skip from the selected range.
+	///
+	/// Messages are accepted by the target chain in order only, so we only can deliver nonces
+	/// that precede the first skipped nonce.
+	fn exclude_skipped_nonces(
+		&self,
+		nonces: RangeInclusive<MessageNonce>,
+	) -> Option<RangeInclusive<MessageNonce>> {
+		let (begin, end) = nonces.into_inner();
+		if let Some(start_from_nonce) = self.start_from_nonce {
+			if begin < start_from_nonce {
+				log::warn!(
+					target: "bridge",
+					"Not delivering messages from {} to {}: relay operator has asked to start \
+					from nonce {}, but target expects message {}. It must be delivered by other \
+					relayer",
+					MessageDeliveryRace::<P>::source_name(),
+					MessageDeliveryRace::<P>::target_name(),
+					start_from_nonce,
+					begin,
+				);
+
+				return None
+			}
+		}
+
+		match self.skip_nonces.range(begin..=end).next() {
+			Some(skipped_nonce) if *skipped_nonce == begin => {
+				log::warn!(
+					target: "bridge",
+					"Not delivering messages from {} to {}: relay operator has asked to skip \
+					message {}, but target expects it. It must be delivered by other relayer",
+					MessageDeliveryRace::<P>::source_name(),
+					MessageDeliveryRace::<P>::target_name(),
+					begin,
+				);
+
+				None
+			},
+			Some(skipped_nonce) => Some(begin..=skipped_nonce - 1),
+			None => Some(begin..=end),
+		}
+	}
+
+	/// Returns the best finalized source header if we're in the historical backfill mode.
+	///
+	/// We are in this mode when the best source header, known to the target node, is too old
+	/// and its state has likely been discarded by the source node. Messages can't be proved at
+	/// such header, so we need to import fresh source header at the target node first. Once it
+	/// is imported, delivery is resumed.
+	fn backfill_source_header<RS: RaceState<SourceHeaderIdOf<P>, TargetHeaderIdOf<P>>>(
+		&self,
+		race_state: &RS,
+	) -> Option<SourceHeaderIdOf<P>> {
+		let ancient_source_header_threshold = self.ancient_source_header_threshold?;
+		let best_finalized_source_header_id_at_best_target =
+			race_state.best_finalized_source_header_id_at_best_target()?;
+		let best_finalized_source_header_id_at_source =
+			race_state.best_finalized_source_header_id_at_source()?;
+		let headers_behind = best_finalized_source_header_id_at_source
+			.0
+			.saturating_sub(best_finalized_source_header_id_at_best_target.0);
+		if headers_behind < ancient_source_header_threshold.into() {
+			return None
+		}
+
+		Some(best_finalized_source_header_id_at_source)
+	}
+
+	/// Returns `true` if we're backing off, because other relayer has recently delivered
+	/// messages to the target node.
+	fn is_backing_off(&self) -> bool {
+		match (self.cooperative_backoff, self.last_foreign_delivery) {
+			(Some(cooperative_backoff), Some(last_foreign_delivery)) =>
+				last_foreign_delivery.elapsed() < cooperative_backoff,
+			_ => false,
+		}
+	}
 }
 
 #[async_trait]
@@ -332,7 +469,31 @@ where
 			return None
 		}
 
+		// other relayer is serving the lane - we don't need any headers
+		if self.is_backing_off() {
+			return None
+		}
+
 		let has_nonces_to_deliver = !self.strategy.is_empty();
+
+		// if source header at target is too old, we can't prove messages at it => let's require
+		// fresh source header
+		if has_nonces_to_deliver {
+			if let Some(backfill_source_header) = self.backfill_source_header(&race_state) {
+				log::info!(
+					target: "bridge",
+					"Switching {} -> {} delivery race into historical backfill mode: source header \
+					{:?} at target is too old to prove messages. Requiring header {:?}",
+					MessageDeliveryRace::<P>::source_name(),
+					MessageDeliveryRace::<P>::target_name(),
+					current_best,
+					backfill_source_header,
+				);
+
+				return Some(backfill_source_header)
+			}
+		}
+
 		let header_required_for_messages_delivery =
 			self.strategy.required_source_header_at_target(current_best, race_state);
 		let header_required_for_reward_confirmations_delivery = self
@@ -394,6 +555,32 @@ where
 	) {
 		// best target nonces must always be ge than finalized target nonces
 		let latest_nonce = nonces.latest_nonce;
+
+		// if new messages have appeared at the target node and we haven't submitted them, then
+		// some other relayer is serving the lane
+		let prev_latest_nonce = self.target_nonces.as_ref().map(|nonces| nonces.latest_nonce);
+		let is_delivered_by_other_relayer = prev_latest_nonce
+			.map(|prev_latest_nonce| latest_nonce > prev_latest_nonce)
+			.unwrap_or(false) &&
+			!race_state
+				.nonces_submitted()
+				.map(|nonces| nonces.contains(&latest_nonce))
+				.unwrap_or(false);
+		if is_delivered_by_other_relayer && self.cooperative_backoff.is_some() {
+			log::debug!(
+				target: "bridge",
+				"Messages {:?}..={} have been delivered from {} to {} by other relayer. \
+				Backing off for {:?}",
+				prev_latest_nonce.map(|nonce| nonce + 1),
+				latest_nonce,
+				MessageDeliveryRace::<P>::source_name(),
+				MessageDeliveryRace::<P>::target_name(),
+				self.cooperative_backoff,
+			);
+
+			self.last_foreign_delivery = Some(Instant::now());
+		}
+
 		self.target_nonces = Some(nonces);
 
 		self.strategy.best_target_nonces_updated(
@@ -436,6 +623,46 @@ where
 		&self,
 		race_state: RS,
 	) -> Option<(RangeInclusive<MessageNonce>, Self::ProofParameters)> {
+		// if delivery has been paused by the relay operator, let's wait until it is resumed
+		if self.control.is_paused() {
+			log::debug!(
+				target: "bridge",
+				"Not delivering messages from {} to {}: the lane is paused by the operator",
+				MessageDeliveryRace::<P>::source_name(),
+				MessageDeliveryRace::<P>::target_name(),
+			);
+
+			return None
+		}
+
+		// if other relayer has recently delivered messages, let's give it a chance to deliver
+		// remaining messages too
+		if self.is_backing_off() {
+			log::debug!(
+				target: "bridge",
+				"Not delivering messages from {} to {}: other relayer has recently delivered \
+				messages and the lane is not stale yet",
+				MessageDeliveryRace::<P>::source_name(),
+				MessageDeliveryRace::<P>::target_name(),
+			);
+
+			return None
+		}
+
+		// if source header at target is too old, we need to wait until fresh header is imported
+		if let Some(backfill_source_header) = self.backfill_source_header(&race_state) {
+			log::debug!(
+				target: "bridge",
+				"Not delivering messages from {} to {}: waiting until source header {:?} is \
+				imported by the target node",
+				MessageDeliveryRace::<P>::source_name(),
+				MessageDeliveryRace::<P>::target_name(),
+				backfill_source_header,
+			);
+
+			return None
+		}
+
 		let best_target_nonce = self.strategy.best_at_target()?;
 		let best_finalized_source_header_id_at_best_target =
 			race_state.best_finalized_source_header_id_at_best_target()?;
@@ -447,6 +674,7 @@ where
 			.map(|(_, nonce)| *nonce)
 			.unwrap_or(best_target_nonce);
 		let target_nonces = self.target_nonces.as_ref()?;
+		let delivery_limits = self.delivery_limits();
 
 		// There's additional condition in the message delivery race: target would reject messages
 		// if there are too much unconfirmed messages at the inbound lane.
@@ -458,7 +686,7 @@ where
 			latest_received_nonce_at_target.checked_sub(latest_confirmed_nonce_at_source);
 		match confirmations_missing {
 			Some(confirmations_missing)
-				if confirmations_missing >= self.max_unconfirmed_nonces_at_target =>
+				if confirmations_missing >= delivery_limits.max_unconfirmed_nonces_at_target =>
 			{
 				log::debug!(
 					target: "bridge",
@@ -468,7 +696,7 @@ where
 					MessageDeliveryRace::<P>::target_name(),
 					latest_received_nonce_at_target,
 					latest_confirmed_nonce_at_source,
-					self.max_unconfirmed_nonces_at_target,
+					delivery_limits.max_unconfirmed_nonces_at_target,
 				);
 
 				return None
@@ -492,7 +720,7 @@ where
 		// we should wait for confirmations race.
 		let unrewarded_relayer_entries_limit_reached =
 			target_nonces.nonces_data.unrewarded_relayers.unrewarded_relayer_entries >=
-				self.max_unrewarded_relayer_entries_at_target;
+				delivery_limits.max_unrewarded_relayer_entries_at_target;
 		if unrewarded_relayer_entries_limit_reached {
 			// so there are already too many unrewarded relayer entries in the set
 			//
@@ -527,11 +755,16 @@ where
 		};
 		let max_nonces = latest_received_nonce_at_target
 			.checked_sub(future_confirmed_nonce_at_target)
-			.and_then(|diff| self.max_unconfirmed_nonces_at_target.checked_sub(diff))
+			.and_then(|diff| delivery_limits.max_unconfirmed_nonces_at_target.checked_sub(diff))
 			.unwrap_or_default();
-		let max_nonces = std::cmp::min(max_nonces, self.max_messages_in_single_batch);
-		let max_messages_weight_in_single_batch = self.max_messages_weight_in_single_batch;
-		let max_messages_size_in_single_batch = self.max_messages_size_in_single_batch;
+		let max_nonces = std::cmp::min(
+			max_nonces,
+			self.control
+				.max_messages_in_single_batch(delivery_limits.max_messages_in_single_batch),
+		);
+		let max_messages_weight_in_single_batch =
+			delivery_limits.max_messages_weight_in_single_batch;
+		let max_messages_size_in_single_batch = delivery_limits.max_messages_size_in_single_batch;
 		let lane_source_client = self.lane_source_client.clone();
 		let lane_target_client = self.lane_target_client.clone();
 
@@ -552,6 +785,7 @@ where
 		};
 
 		let selected_nonces = MessageRaceLimits::decide(reference).await?;
+		let selected_nonces = self.exclude_skipped_nonces(selected_nonces)?;
 		let dispatch_weight = self.dispatch_weight_for_range(&selected_nonces);
 
 		Some((
@@ -648,10 +882,16 @@ mod tests {
 			max_messages_in_single_batch: 4,
 			max_messages_weight_in_single_batch: Weight::from_parts(4, 0),
 			max_messages_size_in_single_batch: 4,
+			cooperative_backoff: None,
+			start_from_nonce: None,
+			skip_nonces: BTreeSet::new(),
+			ancient_source_header_threshold: None,
+			last_foreign_delivery: None,
 			latest_confirmed_nonces_at_source: vec![(header_id(1), 19)].into_iter().collect(),
 			lane_source_client: TestSourceClient::default(),
 			lane_target_client: TestTargetClient::default(),
 			metrics_msg: None,
+			control: LaneControl::default(),
 			target_nonces: Some(TargetClientNonces {
 				latest_nonce: 19,
 				nonces_data: DeliveryRaceTargetNoncesData {
@@ -1051,10 +1291,13 @@ mod tests {
 			max_messages_in_single_batch: 4,
 			max_messages_weight_in_single_batch: Weight::from_parts(4, 0),
 			max_messages_size_in_single_batch: 4,
+			cooperative_backoff: None,
+			last_foreign_delivery: None,
 			latest_confirmed_nonces_at_source: VecDeque::new(),
 			lane_source_client: TestSourceClient::default(),
 			lane_target_client: TestTargetClient::default(),
 			metrics_msg: None,
+			control: LaneControl::default(),
 			target_nonces: None,
 			strategy: BasicStrategy::new(),
 		};
@@ -1159,4 +1402,160 @@ mod tests {
 			)),
 		);
 	}
+
+	#[async_std::test]
+	async fn message_delivery_strategy_backs_off_when_other_relayer_delivers_messages() {
+		let (mut state, mut strategy) = prepare_strategy();
+		strategy.cooperative_backoff = Some(Duration::from_secs(60));
+
+		// other relayer delivers message 20
+		let target_nonces_data = strategy.target_nonces.as_ref().unwrap().nonces_data.clone();
+		strategy.best_target_nonces_updated(
+			TargetClientNonces { latest_nonce: 20, nonces_data: target_nonces_data },
+			&mut state,
+		);
+
+		// => we are not delivering anything and are not asking for source headers
+		assert!(strategy.last_foreign_delivery.is_some());
+		assert_eq!(strategy.select_nonces_to_deliver(state.clone()).await, None);
+		assert_eq!(strategy.required_source_header_at_target(&header_id(1), state.clone()), None);
+
+		// when the lane becomes stale, we are delivering messages again
+		strategy.last_foreign_delivery = Some(Instant::now() - Duration::from_secs(61));
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state).await,
+			Some(((21..=23), proof_parameters(false, 3)))
+		);
+	}
+
+	#[async_std::test]
+	async fn message_delivery_strategy_respects_lane_control() {
+		let (state, strategy) = prepare_strategy();
+
+		// paused lane => we are not delivering anything
+		strategy.control.set_paused(true);
+		assert_eq!(strategy.select_nonces_to_deliver(state.clone()).await, None);
+
+		// resumed lane with smaller batch limit => we are delivering less messages
+		strategy.control.set_paused(false);
+		strategy.control.set_max_messages_in_single_batch(Some(2));
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state).await,
+			Some(((20..=21), proof_parameters(false, 2)))
+		);
+	}
+
+	#[async_std::test]
+	async fn message_delivery_strategy_uses_updated_delivery_limits() {
+		let (state, strategy) = prepare_strategy();
+
+		// limits are updated by the target runtime upgrade => we are delivering less messages
+		strategy.control.set_delivery_limits(DeliveryLimits {
+			max_unrewarded_relayer_entries_at_target: 4,
+			max_unconfirmed_nonces_at_target: 4,
+			max_messages_in_single_batch: 3,
+			max_messages_weight_in_single_batch: Weight::from_parts(4, 0),
+			max_messages_size_in_single_batch: 4,
+		});
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state).await,
+			Some(((20..=22), proof_parameters(false, 3)))
+		);
+	}
+
+	#[async_std::test]
+	async fn message_delivery_strategy_respects_start_from_nonce() {
+		let (state, mut strategy) = prepare_strategy();
+
+		// target expects message 20, but we are asked to start from 21 => nothing is delivered
+		strategy.start_from_nonce = Some(21);
+		assert_eq!(strategy.select_nonces_to_deliver(state.clone()).await, None);
+
+		// when we are asked to start from the message that target expects, it is delivered
+		strategy.start_from_nonce = Some(20);
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state).await,
+			Some(((20..=23), proof_parameters(false, 4)))
+		);
+	}
+
+	#[async_std::test]
+	async fn message_delivery_strategy_respects_skipped_nonces() {
+		let (state, mut strategy) = prepare_strategy();
+
+		// we are asked to skip message 22 => only messages before it are delivered
+		strategy.skip_nonces = vec![22, 100].into_iter().collect();
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state.clone()).await,
+			Some(((20..=21), proof_parameters(false, 2)))
+		);
+
+		// we are asked to skip message 20, that target expects => nothing is delivered
+		strategy.skip_nonces = vec![20].into_iter().collect();
+		assert_eq!(strategy.select_nonces_to_deliver(state).await, None);
+	}
+
+	#[async_std::test]
+	async fn message_delivery_strategy_does_not_back_off_after_own_delivery() {
+		let (mut state, mut strategy) = prepare_strategy();
+		strategy.cooperative_backoff = Some(Duration::from_secs(60));
+
+		// we have submitted 20..=23 and they are delivered
+		state.nonces_submitted = Some(20..=23);
+		let target_nonces_data = strategy.target_nonces.as_ref().unwrap().nonces_data.clone();
+		strategy.best_target_nonces_updated(
+			TargetClientNonces { latest_nonce: 23, nonces_data: target_nonces_data },
+			&mut state,
+		);
+
+		assert!(strategy.last_foreign_delivery.is_none());
+		assert!(!strategy.is_backing_off());
+	}
+
+	#[async_std::test]
+	async fn message_delivery_strategy_ignores_other_relayers_if_backoff_is_disabled() {
+		let (mut state, mut strategy) = prepare_strategy();
+
+		let target_nonces_data = strategy.target_nonces.as_ref().unwrap().nonces_data.clone();
+		strategy.best_target_nonces_updated(
+			TargetClientNonces { latest_nonce: 20, nonces_data: target_nonces_data },
+			&mut state,
+		);
+
+		assert!(strategy.last_foreign_delivery.is_none());
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state).await,
+			Some(((21..=23), proof_parameters(false, 3)))
+		);
+	}
+
+	#[async_std::test]
+	async fn message_delivery_strategy_backfills_ancient_source_header() {
+		let (mut state, mut strategy) = prepare_strategy();
+		strategy.ancient_source_header_threshold = Some(10);
+
+		// source header at target is recent enough => messages are delivered
+		state.best_finalized_source_header_id_at_source = Some(header_id(10));
+		assert_eq!(strategy.required_source_header_at_target(&header_id(1), state.clone()), None);
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state.clone()).await,
+			Some(((20..=23), proof_parameters(false, 4)))
+		);
+
+		// source header at target is too old => nothing is delivered and fresh header is required
+		state.best_finalized_source_header_id_at_source = Some(header_id(11));
+		assert_eq!(
+			strategy.required_source_header_at_target(&header_id(1), state.clone()),
+			Some(header_id(11)),
+		);
+		assert_eq!(strategy.select_nonces_to_deliver(state.clone()).await, None);
+
+		// once fresh header is imported, delivery is resumed
+		state.best_finalized_source_header_id_at_best_target = Some(header_id(11));
+		assert_eq!(strategy.required_source_header_at_target(&header_id(11), state.clone()), None);
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state).await,
+			Some(((20..=23), proof_parameters(false, 4)))
+		);
+	}
 }
@@ -23,7 +23,7 @@
 use crate::message_lane_loop::{BatchTransaction, ClientState, NoncesSubmitArtifacts};
 
 use async_trait::async_trait;
-use bp_messages::MessageNonce;
+use bp_messages::{LaneId, MessageNonce};
 use futures::{
 	future::{FutureExt, TryFutureExt},
 	stream::{FusedStream, StreamExt},
@@ -322,6 +322,7 @@ where
 
 /// Run race loop until connection with target or source node is lost.
 pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
+	lane: LaneId,
 	race_source: SC,
 	race_source_updated: impl FusedStream<Item = SourceClientState<P>>,
 	race_target: TC,
@@ -337,6 +338,8 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 ) -> Result<(), FailedClient> {
 	let mut progress_context = Instant::now();
 	let mut race_state = RaceStateImpl::default();
+	let health_component = race_health_component::<P>(lane);
+	let mut is_unhealthy = false;
 
 	let mut source_retry_backoff = retry_backoff();
 	let mut source_client_is_online = true;
@@ -511,6 +514,8 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 					|(at_block, nonces_range, proof, batch_transaction)| {
 						log::debug!(
 							target: "bridge",
+							nonces_begin = *nonces_range.start(),
+							nonces_end = *nonces_range.end();
 							"Received proof for nonces in range {:?} from {}",
 							nonces_range,
 							P::source_name(),
@@ -531,6 +536,8 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 					|artifacts: NoncesSubmitArtifacts<TC::TransactionTracker>| {
 						log::debug!(
 							target: "bridge",
+							nonces_begin = *artifacts.nonces.start(),
+							nonces_end = *artifacts.nonces.end();
 							"Successfully submitted proof of nonces {:?} to {}",
 							artifacts.nonces,
 							P::target_name(),
@@ -550,23 +557,36 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 					(TrackedTransactionStatus::Finalized(at_block), Some(nonces_submitted)) => {
 						// our transaction has been mined, but was it successful or not? let's check the best
 						// nonce at the target node.
+						let (nonces_begin, nonces_end) = (*nonces_submitted.start(), *nonces_submitted.end());
 						let _ = race_target.nonces(at_block, false)
 							.await
 							.map_err(|e| format!("failed to read nonces from target node: {e:?}"))
 							.and_then(|(_, nonces_at_target)| {
-								if nonces_at_target.latest_nonce < *nonces_submitted.end() {
+								if nonces_at_target.latest_nonce < nonces_end {
 									Err(format!(
 										"best nonce at target after tx is {:?} and we've submitted {:?}",
 										nonces_at_target.latest_nonce,
-										nonces_submitted.end(),
+										nonces_end,
 									))
 								} else {
+									relay_utils::health::report_healthy(
+										health_component.clone(),
+										format!("delivered nonces {nonces_begin:?}..={nonces_end:?}"),
+									);
+									is_unhealthy = false;
 									Ok(())
 								}
 							})
 							.map_err(|e| {
+								relay_utils::health::report_unhealthy(
+									health_component.clone(),
+									format!("race transaction failed: {e}"),
+								);
+								is_unhealthy = true;
 								log::error!(
 									target: "bridge",
+									nonces_begin = nonces_begin,
+									nonces_end = nonces_end;
 									"{} -> {} race transaction failed: {}",
 									P::source_name(),
 									P::target_name(),
@@ -576,7 +596,23 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 								race_state.reset_nonces_submitted();
 							});
 					},
+					(TrackedTransactionStatus::DryRun, Some(nonces_submitted)) => {
+						// the transaction is never submitted, so we keep `nonces_submitted` to avoid
+						// resubmitting the same nonces over and over again
+						log::info!(
+							target: "bridge",
+							"{} -> {} race transaction with nonces {:?} has not been submitted in the dry-run mode",
+							P::source_name(),
+							P::target_name(),
+							nonces_submitted,
+						);
+					},
 					(TrackedTransactionStatus::Lost, _) => {
+						relay_utils::health::report_unhealthy(
+							health_component.clone(),
+							"race transaction has been lost",
+						);
+						is_unhealthy = true;
 						log::warn!(
 							target: "bridge",
 							"{} -> {} race transaction has been lost. State: {:?}. Strategy: {:?}",
@@ -603,6 +639,13 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 
 		progress_context = print_race_progress::<P, _>(progress_context, &strategy);
 
+		// if there's nothing to deliver (e.g. other relayer has delivered nonces that we have
+		// failed to deliver), the race is healthy again
+		if is_unhealthy && race_state.nonces_submitted.is_none() && strategy.is_empty() {
+			relay_utils::health::report_healthy(health_component.clone(), "nothing to deliver");
+			is_unhealthy = false;
+		}
+
 		if source_client_is_online {
 			source_client_is_online = false;
 
@@ -722,6 +765,11 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 	}
 }
 
+/// Name of the race component, reported to the relay health registry.
+fn race_health_component<P: MessageRace>(lane: LaneId) -> String {
+	format!("{} -> {} race (lane {})", P::source_name(), P::target_name(), hex::encode(lane))
+}
+
 /// Print race progress.
 fn print_race_progress<P, S>(prev_time: Instant, strategy: &S) -> Instant
 where
@@ -16,22 +16,22 @@
 use crate::{
 	message_lane::{MessageLane, SourceHeaderIdOf, TargetHeaderIdOf},
 	message_lane_loop::{
-		NoncesSubmitArtifacts, SourceClient as MessageLaneSourceClient, SourceClientState,
-		TargetClient as MessageLaneTargetClient, TargetClientState,
+		MessageReceivingParams, NoncesSubmitArtifacts, SourceClient as MessageLaneSourceClient,
+		SourceClientState, TargetClient as MessageLaneTargetClient, TargetClientState,
 	},
 	message_race_loop::{
-		MessageRace, NoncesRange, SourceClient, SourceClientNonces, TargetClient,
-		TargetClientNonces,
+		MessageRace, NoncesRange, RaceState, RaceStrategy, SourceClient, SourceClientNonces,
+		TargetClient, TargetClientNonces,
 	},
 	message_race_strategy::BasicStrategy,
-	metrics::MessageLaneLoopMetrics,
+	metrics::{DroppedTransactionTracker, MessageLaneLoopMetrics},
 };
 
 use async_trait::async_trait;
-use bp_messages::MessageNonce;
+use bp_messages::{LaneId, MessageNonce};
 use futures::stream::FusedStream;
 use relay_utils::FailedClient;
-use std::{marker::PhantomData, ops::RangeInclusive};
+use std::{marker::PhantomData, ops::RangeInclusive, time::Instant};
 
 /// Message receiving confirmations delivery strategy.
 type ReceivingConfirmationsBasicStrategy<P> = BasicStrategy<
@@ -45,13 +45,16 @@ type ReceivingConfirmationsBasicStrategy<P> = BasicStrategy<
 
 /// Run receiving confirmations race.
 pub async fn run<P: MessageLane>(
+	lane: LaneId,
 	source_client: impl MessageLaneSourceClient<P>,
 	source_state_updates: impl FusedStream<Item = SourceClientState<P>>,
 	target_client: impl MessageLaneTargetClient<P>,
 	target_state_updates: impl FusedStream<Item = TargetClientState<P>>,
 	metrics_msg: Option<MessageLaneLoopMetrics>,
+	params: MessageReceivingParams,
 ) -> Result<(), FailedClient> {
 	crate::message_race_loop::run(
+		lane,
 		ReceivingConfirmationsRaceSource {
 			client: target_client,
 			metrics_msg: metrics_msg.clone(),
@@ -64,7 +67,7 @@ pub async fn run<P: MessageLane>(
 			_phantom: Default::default(),
 		},
 		source_state_updates,
-		ReceivingConfirmationsBasicStrategy::<P>::new(),
+		ReceivingConfirmationsStrategy::<P>::new(params),
 	)
 	.await
 }
@@ -156,7 +159,7 @@ where
 	type Error = C::Error;
 	type TargetNoncesData = ();
 	type BatchTransaction = C::BatchTransaction;
-	type TransactionTracker = C::TransactionTracker;
+	type TransactionTracker = DroppedTransactionTracker<C::TransactionTracker>;
 
 	async fn require_source_header(
 		&self,
@@ -191,7 +194,155 @@ where
 			.client
 			.submit_messages_receiving_proof(maybe_batch_tx, generated_at_block, proof)
 			.await?;
-		Ok(NoncesSubmitArtifacts { nonces, tx_tracker })
+		Ok(NoncesSubmitArtifacts {
+			nonces,
+			tx_tracker: DroppedTransactionTracker::new(
+				tx_tracker,
+				"confirmation",
+				self.metrics_msg.clone(),
+			),
+		})
+	}
+}
+
+/// Message receiving confirmations delivery strategy, which may hold confirmations until
+/// there are enough of them to justify the transaction fee.
+struct ReceivingConfirmationsStrategy<P: MessageLane> {
+	/// Race parameters.
+	params: MessageReceivingParams,
+	/// Basic strategy, which actually selects confirmations.
+	strategy: ReceivingConfirmationsBasicStrategy<P>,
+	/// Time when the oldest of queued confirmations has been queued.
+	oldest_confirmation_queued_at: Option<Instant>,
+}
+
+impl<P: MessageLane> ReceivingConfirmationsStrategy<P> {
+	/// Create new confirmations delivery strategy.
+	fn new(params: MessageReceivingParams) -> Self {
+		ReceivingConfirmationsStrategy {
+			params,
+			strategy: ReceivingConfirmationsBasicStrategy::<P>::new(),
+			oldest_confirmation_queued_at: None,
+		}
+	}
+
+	/// Returns true if we may submit confirmation transaction with given number of confirmations.
+	fn is_confirmation_allowed(&self, confirmations: MessageNonce) -> bool {
+		if confirmations >= self.params.min_confirmations_in_batch {
+			return true
+		}
+
+		// we don't want to block the delivery race
+		let unconfirmed_nonces = self
+			.strategy
+			.best_at_source()
+			.unwrap_or(0)
+			.saturating_sub(self.strategy.best_at_target().unwrap_or(0));
+		if unconfirmed_nonces >= self.params.force_at_unconfirmed_nonces {
+			return true
+		}
+
+		// and we don't want relayers to wait for their rewards forever
+		match (self.params.max_confirmation_delay, self.oldest_confirmation_queued_at) {
+			(Some(max_confirmation_delay), Some(oldest_confirmation_queued_at)) =>
+				oldest_confirmation_queued_at.elapsed() >= max_confirmation_delay,
+			_ => false,
+		}
+	}
+}
+
+#[async_trait]
+impl<P: MessageLane>
+	RaceStrategy<TargetHeaderIdOf<P>, SourceHeaderIdOf<P>, P::MessagesReceivingProof>
+	for ReceivingConfirmationsStrategy<P>
+{
+	type SourceNoncesRange = RangeInclusive<MessageNonce>;
+	type ProofParameters = ();
+	type TargetNoncesData = ();
+
+	fn is_empty(&self) -> bool {
+		self.strategy.is_empty()
+	}
+
+	fn required_source_header_at_target<RS: RaceState<TargetHeaderIdOf<P>, SourceHeaderIdOf<P>>>(
+		&self,
+		current_best: &TargetHeaderIdOf<P>,
+		race_state: RS,
+	) -> Option<TargetHeaderIdOf<P>> {
+		// there's no point in relaying headers if we are not going to submit confirmations
+		let queued_confirmations = self
+			.strategy
+			.best_at_source()
+			.unwrap_or(0)
+			.saturating_sub(self.strategy.best_at_target().unwrap_or(0));
+		if !self.is_confirmation_allowed(queued_confirmations) {
+			return None
+		}
+
+		self.strategy.required_source_header_at_target(current_best, race_state)
+	}
+
+	fn best_at_source(&self) -> Option<MessageNonce> {
+		self.strategy.best_at_source()
+	}
+
+	fn best_at_target(&self) -> Option<MessageNonce> {
+		self.strategy.best_at_target()
+	}
+
+	fn source_nonces_updated(
+		&mut self,
+		at_block: TargetHeaderIdOf<P>,
+		nonces: SourceClientNonces<Self::SourceNoncesRange>,
+	) {
+		self.strategy.source_nonces_updated(at_block, nonces);
+		if self.oldest_confirmation_queued_at.is_none() && !self.strategy.is_empty() {
+			self.oldest_confirmation_queued_at = Some(Instant::now());
+		}
+	}
+
+	fn best_target_nonces_updated<RS: RaceState<TargetHeaderIdOf<P>, SourceHeaderIdOf<P>>>(
+		&mut self,
+		nonces: TargetClientNonces<()>,
+		race_state: &mut RS,
+	) {
+		self.strategy.best_target_nonces_updated(nonces, race_state)
+	}
+
+	fn finalized_target_nonces_updated<RS: RaceState<TargetHeaderIdOf<P>, SourceHeaderIdOf<P>>>(
+		&mut self,
+		nonces: TargetClientNonces<()>,
+		race_state: &mut RS,
+	) {
+		let prev_best_at_target = self.strategy.best_at_target();
+		self.strategy.finalized_target_nonces_updated(nonces, race_state);
+		if self.strategy.is_empty() {
+			self.oldest_confirmation_queued_at = None;
+		} else if self.strategy.best_at_target() != prev_best_at_target {
+			// some confirmations have been delivered, so remaining confirmations may wait
+			// for the next batch
+			self.oldest_confirmation_queued_at = Some(Instant::now());
+		}
+	}
+
+	async fn select_nonces_to_deliver<RS: RaceState<TargetHeaderIdOf<P>, SourceHeaderIdOf<P>>>(
+		&self,
+		race_state: RS,
+	) -> Option<(RangeInclusive<MessageNonce>, Self::ProofParameters)> {
+		let (nonces, proof_parameters) = self.strategy.select_nonces_to_deliver(race_state).await?;
+		let confirmations = nonces.end().saturating_sub(*nonces.start()) + 1;
+		if !self.is_confirmation_allowed(confirmations) {
+			log::trace!(
+				target: "bridge",
+				"Not delivering {} confirmations from {} to {}: waiting for more confirmations",
+				confirmations,
+				ReceivingConfirmationsRace::<P>::source_name(),
+				ReceivingConfirmationsRace::<P>::target_name(),
+			);
+			return None
+		}
+
+		Some((nonces, proof_parameters))
 	}
 }
 
@@ -218,6 +369,100 @@ impl NoncesRange for RangeInclusive<MessageNonce> {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::{
+		message_lane_loop::tests::{header_id, TestMessageLane, TestMessagesReceivingProof},
+		message_race_loop::RaceStateImpl,
+	};
+	use std::time::Duration;
+
+	type TestRaceStateImpl = RaceStateImpl<
+		TargetHeaderIdOf<TestMessageLane>,
+		SourceHeaderIdOf<TestMessageLane>,
+		TestMessagesReceivingProof,
+		(),
+	>;
+
+	fn prepare_strategy(
+		params: MessageReceivingParams,
+	) -> (TestRaceStateImpl, ReceivingConfirmationsStrategy<TestMessageLane>) {
+		let mut state = TestRaceStateImpl::default();
+		let mut strategy = ReceivingConfirmationsStrategy::<TestMessageLane>::new(params);
+		strategy.best_target_nonces_updated(
+			TargetClientNonces { latest_nonce: 0, nonces_data: () },
+			&mut state,
+		);
+		strategy.source_nonces_updated(
+			header_id(1),
+			SourceClientNonces { new_nonces: 1..=3, confirmed_nonce: None },
+		);
+		state.best_finalized_source_header_id_at_best_target = Some(header_id(1));
+		(state, strategy)
+	}
+
+	#[async_std::test]
+	async fn confirmations_are_delivered_by_default() {
+		let (state, strategy) = prepare_strategy(Default::default());
+		assert_eq!(
+			strategy.required_source_header_at_target(&header_id(0), state.clone()),
+			Some(header_id(1))
+		);
+		assert_eq!(strategy.select_nonces_to_deliver(state).await, Some((1..=3, ())));
+	}
+
+	#[async_std::test]
+	async fn confirmations_are_not_delivered_until_min_confirmations_are_queued() {
+		let (state, mut strategy) = prepare_strategy(MessageReceivingParams {
+			min_confirmations_in_batch: 5,
+			..Default::default()
+		});
+		assert_eq!(strategy.required_source_header_at_target(&header_id(0), state.clone()), None);
+		assert_eq!(strategy.select_nonces_to_deliver(state.clone()).await, None);
+
+		strategy.source_nonces_updated(
+			header_id(1),
+			SourceClientNonces { new_nonces: 1..=5, confirmed_nonce: None },
+		);
+		assert_eq!(strategy.select_nonces_to_deliver(state).await, Some((1..=5, ())));
+	}
+
+	#[async_std::test]
+	async fn confirmations_are_delivered_when_unconfirmed_nonces_limit_is_reached() {
+		let (state, strategy) = prepare_strategy(MessageReceivingParams {
+			min_confirmations_in_batch: 5,
+			force_at_unconfirmed_nonces: 3,
+			max_confirmation_delay: None,
+		});
+		assert_eq!(strategy.select_nonces_to_deliver(state).await, Some((1..=3, ())));
+	}
+
+	#[async_std::test]
+	async fn confirmations_are_delivered_when_max_delay_has_passed() {
+		let (state, strategy) = prepare_strategy(MessageReceivingParams {
+			min_confirmations_in_batch: 5,
+			force_at_unconfirmed_nonces: MessageNonce::MAX,
+			max_confirmation_delay: Some(Duration::from_secs(3600)),
+		});
+		assert_eq!(strategy.select_nonces_to_deliver(state.clone()).await, None);
+
+		let (state, strategy) = prepare_strategy(MessageReceivingParams {
+			min_confirmations_in_batch: 5,
+			force_at_unconfirmed_nonces: MessageNonce::MAX,
+			max_confirmation_delay: Some(Duration::from_secs(0)),
+		});
+		assert_eq!(strategy.select_nonces_to_deliver(state).await, Some((1..=3, ())));
+	}
+
+	#[test]
+	fn oldest_confirmation_time_is_reset_when_all_confirmations_are_delivered() {
+		let (mut state, mut strategy) = prepare_strategy(Default::default());
+		assert!(strategy.oldest_confirmation_queued_at.is_some());
+
+		strategy.finalized_target_nonces_updated(
+			TargetClientNonces { latest_nonce: 3, nonces_data: () },
+			&mut state,
+		);
+		assert!(strategy.oldest_confirmation_queued_at.is_none());
+	}
 
 	#[test]
 	fn range_inclusive_works_as_nonces_range() {
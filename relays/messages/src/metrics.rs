@@ -18,14 +18,21 @@
 
 use crate::{
 	message_lane::MessageLane,
-	message_lane_loop::{SourceClientState, TargetClientState},
+	message_lane_loop::{LaneAlertParams, SourceClientState, TargetClientState},
 };
 
+use async_trait::async_trait;
 use bp_messages::MessageNonce;
 use finality_relay::SyncLoopMetrics;
-use relay_utils::metrics::{
-	metric_name, register, GaugeVec, Metric, Opts, PrometheusError, Registry, U64,
+use parking_lot::Mutex;
+use relay_utils::{
+	metrics::{
+		metric_name, register, CounterVec, Gauge, GaugeVec, Metric, Opts, PrometheusError,
+		Registry, U64,
+	},
+	TrackedTransactionStatus, TransactionTracker,
 };
+use std::{sync::Arc, time::Instant};
 
 /// Message lane relay metrics.
 ///
@@ -39,11 +46,29 @@ pub struct MessageLaneLoopMetrics {
 	/// Lane state nonces: "source_latest_generated", "source_latest_confirmed",
 	/// "target_latest_received", "target_latest_confirmed".
 	lane_state_nonces: GaugeVec<U64>,
+	/// Age (in seconds) of the oldest message that has been generated at the source, but not yet
+	/// delivered to the target.
+	oldest_undelivered_message_age: Gauge<U64>,
+	/// Number of messages that have been delivered to the target, but not yet confirmed at the
+	/// source.
+	unconfirmed_messages: Gauge<U64>,
+	/// Active lane alerts: "delivery_stalled", "confirmations_lagging". Set to `1` when alert is
+	/// active and to `0` otherwise.
+	lane_alerts: GaugeVec<U64>,
+	/// Number of dropped (lost) transactions: "delivery", "confirmation".
+	dropped_transactions: CounterVec<U64>,
+	/// Alert thresholds.
+	alert_params: LaneAlertParams,
+	/// Lane state, used to compute alerts.
+	alerts_state: Arc<Mutex<LaneAlertsState>>,
 }
 
 impl MessageLaneLoopMetrics {
 	/// Create and register messages loop metrics.
-	pub fn new(prefix: Option<&str>) -> Result<Self, PrometheusError> {
+	pub fn new(
+		prefix: Option<&str>,
+		alert_params: LaneAlertParams,
+	) -> Result<Self, PrometheusError> {
 		Ok(MessageLaneLoopMetrics {
 			source_to_target_finality_metrics: SyncLoopMetrics::new(
 				prefix,
@@ -59,6 +84,27 @@ impl MessageLaneLoopMetrics {
 				Opts::new(metric_name(prefix, "lane_state_nonces"), "Nonces of the lane state"),
 				&["type"],
 			)?,
+			oldest_undelivered_message_age: Gauge::new(
+				metric_name(prefix, "oldest_undelivered_message_age"),
+				"Age (in seconds) of the oldest undelivered message",
+			)?,
+			unconfirmed_messages: Gauge::new(
+				metric_name(prefix, "unconfirmed_messages"),
+				"Number of delivered messages that are not yet confirmed at the source",
+			)?,
+			lane_alerts: GaugeVec::new(
+				Opts::new(metric_name(prefix, "lane_alerts"), "Active lane alerts"),
+				&["type"],
+			)?,
+			dropped_transactions: CounterVec::new(
+				Opts::new(
+					metric_name(prefix, "dropped_transactions"),
+					"Number of lost (dropped or invalidated) transactions",
+				),
+				&["race"],
+			)?,
+			alert_params,
+			alerts_state: Arc::new(Mutex::new(LaneAlertsState::default())),
 		})
 	}
 
@@ -108,6 +154,9 @@ impl MessageLaneLoopMetrics {
 		self.lane_state_nonces
 			.with_label_values(&["source_latest_generated"])
 			.set(source_latest_generated_nonce);
+		self.update_alerts(|state| {
+			state.source_latest_generated_nonce = Some(source_latest_generated_nonce)
+		});
 	}
 
 	/// Update the latest confirmed nonce at source.
@@ -118,6 +167,9 @@ impl MessageLaneLoopMetrics {
 		self.lane_state_nonces
 			.with_label_values(&["source_latest_confirmed"])
 			.set(source_latest_confirmed_nonce);
+		self.update_alerts(|state| {
+			state.source_latest_confirmed_nonce = Some(source_latest_confirmed_nonce)
+		});
 	}
 
 	/// Update the latest received nonce at target.
@@ -125,6 +177,9 @@ impl MessageLaneLoopMetrics {
 		self.lane_state_nonces
 			.with_label_values(&["target_latest_received"])
 			.set(target_latest_generated_nonce);
+		self.update_alerts(|state| {
+			state.target_latest_received_nonce = Some(target_latest_generated_nonce)
+		});
 	}
 
 	/// Update the latest confirmed nonce at target.
@@ -136,6 +191,45 @@ impl MessageLaneLoopMetrics {
 			.with_label_values(&["target_latest_confirmed"])
 			.set(target_latest_confirmed_nonce);
 	}
+
+	/// Note that the transaction, submitted by given race, has been lost.
+	pub fn note_dropped_transaction(&self, race: &str) {
+		log::warn!(target: "bridge-metrics", "Transaction of {} race has been dropped", race);
+		self.dropped_transactions.with_label_values(&[race]).inc();
+	}
+
+	/// Update lane state and recompute alerts.
+	fn update_alerts(&self, update: impl FnOnce(&mut LaneAlertsState)) {
+		let mut state = self.alerts_state.lock();
+		update(&mut state);
+		let alerts = state.recompute(&self.alert_params, Instant::now());
+
+		self.oldest_undelivered_message_age.set(alerts.oldest_undelivered_message_age);
+		self.unconfirmed_messages.set(alerts.unconfirmed_messages);
+		self.set_alert(
+			"delivery_stalled",
+			&mut state.is_delivery_stalled,
+			alerts.is_delivery_stalled,
+		);
+		self.set_alert(
+			"confirmations_lagging",
+			&mut state.are_confirmations_lagging,
+			alerts.are_confirmations_lagging,
+		);
+	}
+
+	/// Update alert gauge and log alert activation/deactivation.
+	fn set_alert(&self, alert: &str, is_active: &mut bool, new_is_active: bool) {
+		if *is_active != new_is_active {
+			if new_is_active {
+				log::warn!(target: "bridge-metrics", "Lane alert {} is raised", alert);
+			} else {
+				log::info!(target: "bridge-metrics", "Lane alert {} is resolved", alert);
+			}
+			*is_active = new_is_active;
+		}
+		self.lane_alerts.with_label_values(&[alert]).set(new_is_active as u64);
+	}
 }
 
 impl Metric for MessageLaneLoopMetrics {
@@ -143,6 +237,174 @@ impl Metric for MessageLaneLoopMetrics {
 		self.source_to_target_finality_metrics.register(registry)?;
 		self.target_to_source_finality_metrics.register(registry)?;
 		register(self.lane_state_nonces.clone(), registry)?;
+		register(self.oldest_undelivered_message_age.clone(), registry)?;
+		register(self.unconfirmed_messages.clone(), registry)?;
+		register(self.lane_alerts.clone(), registry)?;
+		register(self.dropped_transactions.clone(), registry)?;
 		Ok(())
 	}
 }
+
+/// Lane state, used to compute alerts.
+#[derive(Debug, Default)]
+struct LaneAlertsState {
+	/// The latest generated nonce at the source.
+	source_latest_generated_nonce: Option<MessageNonce>,
+	/// The latest confirmed nonce at the source.
+	source_latest_confirmed_nonce: Option<MessageNonce>,
+	/// The latest received nonce at the target.
+	target_latest_received_nonce: Option<MessageNonce>,
+	/// The oldest undelivered nonce and the moment when we have first seen it.
+	oldest_undelivered_nonce: Option<(MessageNonce, Instant)>,
+	/// Whether the `delivery_stalled` alert is active.
+	is_delivery_stalled: bool,
+	/// Whether the `confirmations_lagging` alert is active.
+	are_confirmations_lagging: bool,
+}
+
+/// Alerts, computed from the lane state.
+#[derive(Debug, PartialEq, Eq)]
+struct LaneAlerts {
+	/// Age (in seconds) of the oldest undelivered message.
+	oldest_undelivered_message_age: u64,
+	/// Number of delivered, but not yet confirmed messages.
+	unconfirmed_messages: MessageNonce,
+	/// Whether the oldest undelivered message is too old.
+	is_delivery_stalled: bool,
+	/// Whether there are too many unconfirmed messages.
+	are_confirmations_lagging: bool,
+}
+
+impl LaneAlertsState {
+	/// Recompute alerts at given moment.
+	fn recompute(&mut self, params: &LaneAlertParams, now: Instant) -> LaneAlerts {
+		let oldest_undelivered_nonce =
+			match (self.source_latest_generated_nonce, self.target_latest_received_nonce) {
+				(Some(generated), Some(received)) if generated > received => Some(received + 1),
+				_ => None,
+			};
+		self.oldest_undelivered_nonce =
+			match (oldest_undelivered_nonce, self.oldest_undelivered_nonce) {
+				(Some(nonce), Some((prev_nonce, seen_at))) if nonce == prev_nonce =>
+					Some((nonce, seen_at)),
+				(Some(nonce), _) => Some((nonce, now)),
+				(None, _) => None,
+			};
+		let oldest_undelivered_message_age = self
+			.oldest_undelivered_nonce
+			.map(|(_, seen_at)| now.saturating_duration_since(seen_at))
+			.unwrap_or_default();
+
+		let unconfirmed_messages =
+			match (self.target_latest_received_nonce, self.source_latest_confirmed_nonce) {
+				(Some(received), Some(confirmed)) => received.saturating_sub(confirmed),
+				_ => 0,
+			};
+
+		LaneAlerts {
+			oldest_undelivered_message_age: oldest_undelivered_message_age.as_secs(),
+			unconfirmed_messages,
+			is_delivery_stalled: oldest_undelivered_message_age >
+				params.max_undelivered_message_age,
+			are_confirmations_lagging: unconfirmed_messages > params.max_unconfirmed_messages,
+		}
+	}
+}
+
+/// Transaction tracker that reports lost transactions to the lane metrics.
+pub struct DroppedTransactionTracker<T> {
+	/// Wrapped transaction tracker.
+	tracker: T,
+	/// Race that has submitted the transaction.
+	race: &'static str,
+	/// Message lane metrics.
+	metrics_msg: Option<MessageLaneLoopMetrics>,
+}
+
+impl<T> DroppedTransactionTracker<T> {
+	/// Create new tracker.
+	pub fn new(
+		tracker: T,
+		race: &'static str,
+		metrics_msg: Option<MessageLaneLoopMetrics>,
+	) -> Self {
+		DroppedTransactionTracker { tracker, race, metrics_msg }
+	}
+}
+
+#[async_trait]
+impl<T: TransactionTracker> TransactionTracker for DroppedTransactionTracker<T> {
+	type HeaderId = T::HeaderId;
+
+	async fn wait(self) -> TrackedTransactionStatus<T::HeaderId> {
+		let status = self.tracker.wait().await;
+		if let (TrackedTransactionStatus::Lost, Some(metrics_msg)) =
+			(&status, self.metrics_msg.as_ref())
+		{
+			metrics_msg.note_dropped_transaction(self.race);
+		}
+		status
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Duration;
+
+	fn alert_params() -> LaneAlertParams {
+		LaneAlertParams {
+			max_undelivered_message_age: Duration::from_secs(60),
+			max_unconfirmed_messages: 10,
+		}
+	}
+
+	#[test]
+	fn delivery_stalled_alert_is_raised_when_oldest_message_is_too_old() {
+		let now = Instant::now();
+		let mut state = LaneAlertsState {
+			source_latest_generated_nonce: Some(10),
+			target_latest_received_nonce: Some(5),
+			..Default::default()
+		};
+
+		// nonce 6 is seen for the first time
+		assert_eq!(state.recompute(&alert_params(), now).oldest_undelivered_message_age, 0);
+		assert_eq!(state.oldest_undelivered_nonce, Some((6, now)));
+
+		// nonce 6 is still undelivered after 61 seconds
+		let alerts = state.recompute(&alert_params(), now + Duration::from_secs(61));
+		assert_eq!(alerts.oldest_undelivered_message_age, 61);
+		assert!(alerts.is_delivery_stalled);
+
+		// nonce 6 is delivered => the age is reset
+		state.target_latest_received_nonce = Some(6);
+		let alerts = state.recompute(&alert_params(), now + Duration::from_secs(62));
+		assert_eq!(alerts.oldest_undelivered_message_age, 0);
+		assert!(!alerts.is_delivery_stalled);
+
+		// all messages are delivered
+		state.target_latest_received_nonce = Some(10);
+		state.recompute(&alert_params(), now + Duration::from_secs(63));
+		assert_eq!(state.oldest_undelivered_nonce, None);
+	}
+
+	#[test]
+	fn confirmations_lagging_alert_is_raised_when_there_are_too_many_unconfirmed_messages() {
+		let now = Instant::now();
+		let mut state = LaneAlertsState {
+			source_latest_confirmed_nonce: Some(0),
+			target_latest_received_nonce: Some(10),
+			..Default::default()
+		};
+
+		let alerts = state.recompute(&alert_params(), now);
+		assert_eq!(alerts.unconfirmed_messages, 10);
+		assert!(!alerts.are_confirmations_lagging);
+
+		state.target_latest_received_nonce = Some(11);
+		let alerts = state.recompute(&alert_params(), now);
+		assert_eq!(alerts.unconfirmed_messages, 11);
+		assert!(alerts.are_confirmations_lagging);
+	}
+}
@@ -225,6 +225,9 @@ where
 				SubmittedHeadStatus::Final(TrackedTransactionStatus::Finalized(_)) => {
 					// all heads have been updated, we don't need this tracker anymore
 				},
+				SubmittedHeadStatus::Final(TrackedTransactionStatus::DryRun) => {
+					// never reported, see `SubmittedHeadsTracker::update`
+				},
 				SubmittedHeadStatus::Final(TrackedTransactionStatus::Lost) => {
 					log::warn!(
 						target: "bridge",
@@ -516,6 +519,9 @@ impl<P: ParachainsPipeline> SubmittedHeadsTracker<P> {
 				// updated => we're considering our loop as stalled
 				return SubmittedHeadStatus::Final(TrackedTransactionStatus::Lost)
 			},
+			// the transaction is never submitted in the dry-run mode, so we keep waiting for the
+			// head update instead of resubmitting it
+			Poll::Ready(TrackedTransactionStatus::DryRun) => (),
 			_ => (),
 		}
 
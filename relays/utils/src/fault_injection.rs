@@ -0,0 +1,136 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Fault injection for relay test clients.
+//!
+//! Test clients may use the [`FaultInjector`] to decide whether the next RPC call needs to
+//! fail, whether the subscription needs to go stale or whether the returned justification
+//! needs to be corrupted. All decisions are made using the seeded random number generator,
+//! so the same seed always leads to the same sequence of faults.
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Fault injection parameters. All probabilities are in the `[0.0; 1.0]` range.
+#[derive(Clone, Debug, Default)]
+pub struct FaultInjectionParams {
+	/// Seed of the random number generator.
+	pub seed: u64,
+	/// Probability that the RPC call fails with connection error.
+	pub rpc_failure_probability: f64,
+	/// Probability that the subscription silently stops yielding items.
+	pub stale_subscription_probability: f64,
+	/// Probability that the client returns invalid justification.
+	pub invalid_justification_probability: f64,
+}
+
+/// Number of faults that have been injected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FaultInjectionStats {
+	/// Number of failed RPC calls.
+	pub failed_rpc_calls: u64,
+	/// Number of subscriptions that went stale.
+	pub stale_subscriptions: u64,
+	/// Number of returned invalid justifications.
+	pub invalid_justifications: u64,
+}
+
+/// Fault injector.
+#[derive(Clone, Debug)]
+pub struct FaultInjector {
+	params: FaultInjectionParams,
+	rng: StdRng,
+	stats: FaultInjectionStats,
+}
+
+impl FaultInjector {
+	/// Create new fault injector.
+	pub fn new(params: FaultInjectionParams) -> Self {
+		let rng = StdRng::seed_from_u64(params.seed);
+		FaultInjector { params, rng, stats: Default::default() }
+	}
+
+	/// Returns number of faults that have been injected so far.
+	pub fn stats(&self) -> FaultInjectionStats {
+		self.stats
+	}
+
+	/// Returns true if the RPC call needs to fail.
+	pub fn fail_rpc_call(&mut self) -> bool {
+		let is_failed = self.roll(self.params.rpc_failure_probability);
+		self.stats.failed_rpc_calls += is_failed as u64;
+		is_failed
+	}
+
+	/// Returns true if the subscription needs to go stale.
+	pub fn stall_subscription(&mut self) -> bool {
+		let is_stalled = self.roll(self.params.stale_subscription_probability);
+		self.stats.stale_subscriptions += is_stalled as u64;
+		is_stalled
+	}
+
+	/// Returns true if the justification needs to be corrupted.
+	pub fn corrupt_justification(&mut self) -> bool {
+		let is_corrupted = self.roll(self.params.invalid_justification_probability);
+		self.stats.invalid_justifications += is_corrupted as u64;
+		is_corrupted
+	}
+
+	fn roll(&mut self, probability: f64) -> bool {
+		probability > 0.0 && self.rng.gen_bool(probability.min(1.0))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn test_params(seed: u64) -> FaultInjectionParams {
+		FaultInjectionParams {
+			seed,
+			rpc_failure_probability: 0.3,
+			stale_subscription_probability: 0.2,
+			invalid_justification_probability: 0.1,
+		}
+	}
+
+	fn faults(injector: &mut FaultInjector) -> Vec<(bool, bool, bool)> {
+		(0..128)
+			.map(|_| {
+				(
+					injector.fail_rpc_call(),
+					injector.stall_subscription(),
+					injector.corrupt_justification(),
+				)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn same_seed_leads_to_same_faults() {
+		let mut injector1 = FaultInjector::new(test_params(42));
+		let mut injector2 = FaultInjector::new(test_params(42));
+		assert_eq!(faults(&mut injector1), faults(&mut injector2));
+		assert_eq!(injector1.stats(), injector2.stats());
+		assert_ne!(injector1.stats(), FaultInjectionStats::default());
+	}
+
+	#[test]
+	fn faults_are_not_injected_by_default() {
+		let mut injector = FaultInjector::new(Default::default());
+		assert!(faults(&mut injector).into_iter().all(|faults| faults == (false, false, false)));
+		assert_eq!(injector.stats(), FaultInjectionStats::default());
+	}
+}
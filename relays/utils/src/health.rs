@@ -0,0 +1,238 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Health and readiness of the relay components.
+//!
+//! Every relay component (RPC connection, race, balance guard, ...) reports its status here.
+//! The status is exposed by the metrics HTTP server at two endpoints:
+//!
+//! - `/ready` responds with `200 OK` if all components are healthy;
+//!
+//! - `/health` responds with `200 OK` unless some component is unhealthy for longer than
+//!   `UNHEALTHY_GRACE_PERIOD`. It is supposed to be used as the liveness probe, so the relay that
+//!   is wedged gets restarted.
+//!
+//! Both endpoints return per-component details in JSON.
+
+use crate::metrics::Registry;
+
+use hyper::{
+	header::CONTENT_TYPE,
+	service::{make_service_fn, service_fn},
+	Body, Request, Response, Server, StatusCode,
+};
+use std::{
+	collections::BTreeMap,
+	net::SocketAddr,
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+use substrate_prometheus_endpoint::prometheus::{Encoder, TextEncoder};
+
+/// Period after which unhealthy component makes the whole relay not alive.
+pub const UNHEALTHY_GRACE_PERIOD: Duration = Duration::from_secs(15 * 60);
+
+/// Statuses of all relay components, reported so far.
+static COMPONENTS: Mutex<BTreeMap<String, ComponentStatus>> = Mutex::new(BTreeMap::new());
+
+/// Status of the single relay component.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ComponentStatus {
+	/// If `Some(_)`, the component is unhealthy since given moment.
+	unhealthy_since: Option<Instant>,
+	/// Human-readable details of the status.
+	details: String,
+}
+
+/// Report that the component is healthy.
+pub fn report_healthy(component: impl Into<String>, details: impl Into<String>) {
+	let status = ComponentStatus { unhealthy_since: None, details: details.into() };
+	components().insert(component.into(), status);
+}
+
+/// Report that the component is unhealthy.
+///
+/// If the component is already unhealthy, the moment it has become unhealthy is preserved.
+pub fn report_unhealthy(component: impl Into<String>, details: impl Into<String>) {
+	let mut components = components();
+	let status = components
+		.entry(component.into())
+		.or_insert_with(|| ComponentStatus { unhealthy_since: None, details: String::new() });
+	status.unhealthy_since = status.unhealthy_since.or_else(|| Some(Instant::now()));
+	status.details = details.into();
+}
+
+/// Lock and return statuses of all components.
+fn components() -> std::sync::MutexGuard<'static, BTreeMap<String, ComponentStatus>> {
+	// the lock is never held across panics, but let's not fail if it happens
+	COMPONENTS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Kind of the status report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ReportKind {
+	/// Liveness report (`/health` endpoint).
+	Liveness,
+	/// Readiness report (`/ready` endpoint).
+	Readiness,
+}
+
+/// Build status report of given kind. Returns `true` if the relay is alive (or ready) and the
+/// report itself.
+fn build_report(
+	kind: ReportKind,
+	components: &BTreeMap<String, ComponentStatus>,
+	now: Instant,
+) -> (bool, serde_json::Value) {
+	let mut is_ok = kind == ReportKind::Liveness || !components.is_empty();
+	let mut json_components = serde_json::Map::new();
+	for (name, status) in components {
+		let unhealthy_for =
+			status.unhealthy_since.map(|since| now.saturating_duration_since(since));
+		is_ok = is_ok &&
+			match (kind, unhealthy_for) {
+				(_, None) => true,
+				(ReportKind::Liveness, Some(unhealthy_for)) =>
+					unhealthy_for < UNHEALTHY_GRACE_PERIOD,
+				(ReportKind::Readiness, Some(_)) => false,
+			};
+
+		json_components.insert(
+			name.clone(),
+			serde_json::json!({
+				"healthy": unhealthy_for.is_none(),
+				"unhealthy_for_secs": unhealthy_for.map(|d| d.as_secs()),
+				"details": status.details,
+			}),
+		);
+	}
+
+	let report = serde_json::json!({
+		"status": if is_ok { "ok" } else { "failing" },
+		"components": json_components,
+	});
+	(is_ok, report)
+}
+
+/// Serve metrics, health and readiness requests at given address.
+pub async fn serve(address: SocketAddr, registry: Registry) -> Result<(), hyper::Error> {
+	let service = make_service_fn(move |_| {
+		let registry = registry.clone();
+		async move {
+			Ok::<_, hyper::Error>(service_fn(move |request| {
+				let registry = registry.clone();
+				async move { handle_request(request, registry) }
+			}))
+		}
+	});
+
+	Server::try_bind(&address)?.serve(service).await
+}
+
+/// Handle single HTTP request.
+fn handle_request(
+	request: Request<Body>,
+	registry: Registry,
+) -> Result<Response<Body>, hyper::http::Error> {
+	let report = |kind| {
+		let (is_ok, report) = build_report(kind, &components(), Instant::now());
+		Response::builder()
+			.status(if is_ok { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE })
+			.header(CONTENT_TYPE, "application/json")
+			.body(Body::from(report.to_string()))
+	};
+
+	match request.uri().path() {
+		"/metrics" => {
+			let encoder = TextEncoder::new();
+			let mut buffer = Vec::new();
+			match encoder.encode(&registry.gather(), &mut buffer) {
+				Ok(()) => Response::builder()
+					.status(StatusCode::OK)
+					.header(CONTENT_TYPE, encoder.format_type())
+					.body(Body::from(buffer)),
+				Err(e) => Response::builder()
+					.status(StatusCode::INTERNAL_SERVER_ERROR)
+					.body(Body::from(format!("Failed to encode metrics: {e:?}"))),
+			}
+		},
+		"/health" => report(ReportKind::Liveness),
+		"/ready" => report(ReportKind::Readiness),
+		_ => Response::builder().status(StatusCode::NOT_FOUND).body(Body::from("Not found.")),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn status(unhealthy_since: Option<Instant>) -> ComponentStatus {
+		ComponentStatus { unhealthy_since, details: String::new() }
+	}
+
+	#[test]
+	fn relay_without_components_is_alive_but_not_ready() {
+		let now = Instant::now();
+		assert!(build_report(ReportKind::Liveness, &BTreeMap::new(), now).0);
+		assert!(!build_report(ReportKind::Readiness, &BTreeMap::new(), now).0);
+	}
+
+	#[test]
+	fn relay_with_healthy_components_is_alive_and_ready() {
+		let now = Instant::now();
+		let components = vec![("a".to_string(), status(None)), ("b".to_string(), status(None))]
+			.into_iter()
+			.collect();
+		assert!(build_report(ReportKind::Liveness, &components, now).0);
+		assert!(build_report(ReportKind::Readiness, &components, now).0);
+	}
+
+	#[test]
+	fn relay_with_recently_unhealthy_component_is_alive_but_not_ready() {
+		let now = Instant::now();
+		let components =
+			vec![("a".to_string(), status(None)), ("b".to_string(), status(Some(now)))]
+				.into_iter()
+				.collect();
+		let (is_ready, report) = build_report(ReportKind::Readiness, &components, now);
+		assert!(build_report(ReportKind::Liveness, &components, now).0);
+		assert!(!is_ready);
+		assert_eq!(report["components"]["a"]["healthy"], true);
+		assert_eq!(report["components"]["b"]["healthy"], false);
+	}
+
+	#[test]
+	fn relay_with_component_that_is_unhealthy_for_too_long_is_not_alive() {
+		let now = Instant::now();
+		let components = vec![("a".to_string(), status(Some(now)))].into_iter().collect();
+		let later = now + UNHEALTHY_GRACE_PERIOD;
+		assert!(!build_report(ReportKind::Liveness, &components, later).0);
+		assert!(!build_report(ReportKind::Readiness, &components, later).0);
+	}
+
+	#[test]
+	fn unhealthy_since_is_preserved_while_component_stays_unhealthy() {
+		report_unhealthy("test_component", "first failure");
+		let first_since = components()["test_component"].unhealthy_since;
+		report_unhealthy("test_component", "second failure");
+		let status = components()["test_component"].clone();
+		assert_eq!(status.unhealthy_since, first_since);
+		assert_eq!(status.details, "second failure");
+
+		report_healthy("test_component", "recovered");
+		assert_eq!(components()["test_component"].unhealthy_since, None);
+	}
+}
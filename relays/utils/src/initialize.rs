@@ -18,8 +18,34 @@
 
 use std::{cell::RefCell, fmt::Display, io::Write};
 
+/// Name of the environment variable that selects the log format.
+///
+/// Supported values are `text` (default) and `json`.
+pub const LOG_FORMAT_ENV_VAR: &str = "RELAY_LOG_FORMAT";
+
 async_std::task_local! {
 	pub(crate) static LOOP_NAME: RefCell<String> = RefCell::new(String::default());
+	static LOOP_LOG_CONTEXT: RefCell<Vec<(&'static str, String)>> = RefCell::new(Vec::new());
+}
+
+/// Relay log format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+	/// Human-readable log lines.
+	Text,
+	/// Every log line is a JSON object. Key-value pairs of the log record and fields of the
+	/// loop log context (see `set_loop_log_context`) are written as separate JSON fields.
+	Json,
+}
+
+impl LogFormat {
+	/// Read log format from the `LOG_FORMAT_ENV_VAR` environment variable.
+	pub fn from_env() -> Self {
+		match std::env::var(LOG_FORMAT_ENV_VAR) {
+			Ok(format) if format.eq_ignore_ascii_case("json") => LogFormat::Json,
+			_ => LogFormat::Text,
+		}
+	}
 }
 
 /// Initialize relay environment.
@@ -39,7 +65,9 @@ pub fn initialize_logger(with_timestamp: bool) {
 	builder.filter_level(log::LevelFilter::Warn);
 	builder.filter_module("bridge", log::LevelFilter::Info);
 	builder.parse_default_env();
-	if with_timestamp {
+	if LogFormat::from_env() == LogFormat::Json {
+		builder.format(move |buf, record| writeln!(buf, "{}", json_log_line(record)));
+	} else if with_timestamp {
 		builder.format(move |buf, record| {
 			let timestamp = time::OffsetDateTime::now_local()
 				.unwrap_or_else(|_| time::OffsetDateTime::now_utc());
@@ -80,22 +108,78 @@ pub(crate) fn initialize_loop(loop_name: String) {
 	LOOP_NAME.with(|g_loop_name| *g_loop_name.borrow_mut() = loop_name);
 }
 
+/// Set fields that are attached to every JSON log line, written by the current relay loop task.
+///
+/// The relay loop should use it to identify itself - e.g. messages relay sets the bridge and lane
+/// here, so that log aggregation systems may select log lines of the single lane. Fields are
+/// ignored by the text log format, because they're already a part of the loop name.
+pub fn set_loop_log_context(fields: Vec<(&'static str, String)>) {
+	// try_with to avoid panic outside of async-std task context
+	let _ = LOOP_LOG_CONTEXT.try_with(|context| *context.borrow_mut() = fields);
+}
+
 /// Returns loop name prefix to use in logs. The prefix is initialized with the `initialize_loop`
 /// call.
 fn loop_name_prefix() -> String {
+	loop_name().map(|loop_name| format!("[{loop_name}] ")).unwrap_or_default()
+}
+
+/// Returns loop name, if it has been initialized with the `initialize_loop` call.
+fn loop_name() -> Option<String> {
 	// try_with to avoid panic outside of async-std task context
 	LOOP_NAME
 		.try_with(|loop_name| {
 			// using borrow is ok here, because loop is only initialized once (=> borrow_mut will
 			// only be called once)
 			let loop_name = loop_name.borrow();
-			if loop_name.is_empty() {
-				String::new()
-			} else {
-				format!("[{loop_name}] ")
-			}
+			Some(loop_name.clone()).filter(|loop_name| !loop_name.is_empty())
 		})
-		.unwrap_or_else(|_| String::new())
+		.ok()
+		.flatten()
+}
+
+/// Convert log record into JSON object.
+fn json_log_line(record: &log::Record) -> serde_json::Value {
+	let timestamp = time::OffsetDateTime::now_utc();
+	let mut line = serde_json::Map::new();
+	line.insert(
+		"timestamp".into(),
+		timestamp
+			.format(&time::format_description::well_known::Rfc3339)
+			.unwrap_or_else(|_| timestamp.to_string())
+			.into(),
+	);
+	line.insert("level".into(), record.level().as_str().into());
+	line.insert("target".into(), record.target().into());
+	if let Some(loop_name) = loop_name() {
+		line.insert("loop".into(), loop_name.into());
+	}
+	let _ = LOOP_LOG_CONTEXT.try_with(|context| {
+		for (key, value) in context.borrow().iter() {
+			line.insert((*key).into(), value.clone().into());
+		}
+	});
+	let _ = record.key_values().visit(&mut JsonKeyValues(&mut line));
+	line.insert("message".into(), record.args().to_string().into());
+	serde_json::Value::Object(line)
+}
+
+/// Visitor that writes key-value pairs of the log record to the JSON object.
+struct JsonKeyValues<'a>(&'a mut serde_json::Map<String, serde_json::Value>);
+
+impl<'a, 'kvs> log::kv::Visitor<'kvs> for JsonKeyValues<'a> {
+	fn visit_pair(
+		&mut self,
+		key: log::kv::Key<'kvs>,
+		value: log::kv::Value<'kvs>,
+	) -> Result<(), log::kv::Error> {
+		let value = match value.to_u64() {
+			Some(value) => value.into(),
+			None => value.to_string().into(),
+		};
+		self.0.insert(key.as_str().into(), value);
+		Ok(())
+	}
 }
 
 enum Either<A, B> {
@@ -134,3 +218,43 @@ fn color_level(level: log::Level) -> impl Display {
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn json_log_line_contains_key_values() {
+		let key_values = [("lane", "00000000")];
+		let line = json_log_line(
+			&log::Record::builder()
+				.args(format_args!("Received proof for nonces {:?}", 1..=2))
+				.level(log::Level::Info)
+				.target("bridge")
+				.key_values(&key_values)
+				.build(),
+		);
+
+		assert_eq!(line["level"], "INFO");
+		assert_eq!(line["target"], "bridge");
+		assert_eq!(line["lane"], "00000000");
+		assert_eq!(line["message"], "Received proof for nonces 1..=2");
+		assert!(line.get("loop").is_none());
+	}
+
+	#[test]
+	fn json_log_line_writes_numbers_as_numbers() {
+		let key_values = [("nonces_begin", 1u64), ("nonces_end", 2u64)];
+		let line = json_log_line(
+			&log::Record::builder()
+				.args(format_args!("Successfully submitted proof"))
+				.level(log::Level::Debug)
+				.target("bridge")
+				.key_values(&key_values)
+				.build(),
+		);
+
+		assert_eq!(line["nonces_begin"], 1);
+		assert_eq!(line["nonces_end"], 2);
+	}
+}
@@ -19,6 +19,7 @@
 pub use bp_runtime::HeaderId;
 pub use error::Error;
 pub use relay_loop::{relay_loop, relay_metrics};
+pub use retry::{CircuitBreaker, CircuitState, ErrorClass, RetryPolicy};
 pub use sp_runtime::traits::{UniqueSaturatedFrom, UniqueSaturatedInto};
 
 use async_trait::async_trait;
@@ -44,9 +45,13 @@ pub const MAX_BACKOFF_INTERVAL: Duration = Duration::from_secs(60);
 pub const CONNECTION_ERROR_DELAY: Duration = Duration::from_secs(10);
 
 pub mod error;
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection;
+pub mod health;
 pub mod initialize;
 pub mod metrics;
 pub mod relay_loop;
+pub mod retry;
 
 /// Block number traits shared by all chains that relay is able to serve.
 pub trait BlockNumberBase:
@@ -119,6 +124,12 @@ macro_rules! bail_on_arg_error {
 pub trait MaybeConnectionError {
 	/// Returns true if error (maybe) represents connection error.
 	fn is_connection_error(&self) -> bool;
+
+	/// Returns true if error is fatal. Retrying the same request won't help if this error has
+	/// happened, so the loop needs to be restarted.
+	fn is_fatal_error(&self) -> bool {
+		false
+	}
 }
 
 /// Final status of the tracked transaction.
@@ -128,6 +139,9 @@ pub enum TrackedTransactionStatus<BlockId> {
 	Lost,
 	/// Transaction has been mined and finalized at given block.
 	Finalized(BlockId),
+	/// Transaction has been validated, but never submitted, because the relay runs in the
+	/// dry-run mode. Relay loops treat the transaction as done and never resubmit it.
+	DryRun,
 }
 
 /// Transaction tracker.
@@ -171,14 +185,9 @@ impl MaybeConnectionError for StringifiedMaybeConnectionError {
 	}
 }
 
-/// Exponential backoff for connection-unrelated errors retries.
+/// Exponential backoff (with jitter) for connection-unrelated errors retries.
 pub fn retry_backoff() -> ExponentialBackoff {
-	ExponentialBackoff {
-		// we do not want relayer to stop
-		max_elapsed_time: None,
-		max_interval: MAX_BACKOFF_INTERVAL,
-		..Default::default()
-	}
+	RetryPolicy::default().backoff()
 }
 
 /// Compact format of IDs vector.
@@ -229,6 +238,8 @@ pub enum ProcessFutureResult {
 	Failed,
 	/// Future has failed with connection error.
 	ConnectionFailed,
+	/// Future has failed with fatal error.
+	FatalFailed,
 }
 
 impl ProcessFutureResult {
@@ -236,7 +247,9 @@ impl ProcessFutureResult {
 	pub fn is_ok(self) -> bool {
 		match self {
 			ProcessFutureResult::Success => true,
-			ProcessFutureResult::Failed | ProcessFutureResult::ConnectionFailed => false,
+			ProcessFutureResult::Failed |
+			ProcessFutureResult::ConnectionFailed |
+			ProcessFutureResult::FatalFailed => false,
 		}
 	}
 
@@ -251,8 +264,8 @@ impl ProcessFutureResult {
 	}
 
 	/// Returns Ok(true) if future has succeeded.
-	/// Returns Ok(false) if future has failed with non-connection error.
-	/// Returns Err if future is `ConnectionFailed`.
+	/// Returns Ok(false) if future has failed with retryable error.
+	/// Returns Err if future is `ConnectionFailed` or `FatalFailed`.
 	pub fn fail_if_connection_error(
 		self,
 		failed_client: FailedClient,
@@ -260,7 +273,8 @@ impl ProcessFutureResult {
 		match self {
 			ProcessFutureResult::Success => Ok(true),
 			ProcessFutureResult::Failed => Ok(false),
-			ProcessFutureResult::ConnectionFailed => Err(failed_client),
+			ProcessFutureResult::ConnectionFailed | ProcessFutureResult::FatalFailed =>
+				Err(failed_client),
 		}
 	}
 }
@@ -278,13 +292,14 @@ where
 	TError: std::fmt::Debug + MaybeConnectionError,
 	TGoOfflineFuture: FutureExt,
 {
+	let result = result.map_err(|error| (ErrorClass::of(&error), error));
 	match result {
 		Ok(result) => {
 			on_success(result);
 			retry_backoff.reset();
 			ProcessFutureResult::Success
 		},
-		Err(error) if error.is_connection_error() => {
+		Err((ErrorClass::Connection, error)) => {
 			log::error!(
 				target: "bridge",
 				"{}: {:?}. Going to restart",
@@ -296,7 +311,19 @@ where
 			go_offline_future.set(go_offline(CONNECTION_ERROR_DELAY).fuse());
 			ProcessFutureResult::ConnectionFailed
 		},
-		Err(error) => {
+		Err((ErrorClass::Fatal, error)) => {
+			log::error!(
+				target: "bridge",
+				"{}: {:?}. The error is not retryable. Going to restart",
+				error_pattern(),
+				error,
+			);
+
+			retry_backoff.reset();
+			go_offline_future.set(go_offline(CONNECTION_ERROR_DELAY).fuse());
+			ProcessFutureResult::FatalFailed
+		},
+		Err((ErrorClass::Retryable, error)) => {
 			let retry_delay = retry_backoff.next_backoff().unwrap_or(CONNECTION_ERROR_DELAY);
 			log::error!(
 				target: "bridge",
@@ -16,13 +16,20 @@
 
 use crate::{
 	error::Error,
+	health,
 	metrics::{Metric, MetricsAddress, MetricsParams},
+	retry::{CircuitBreaker, RetryPolicy},
 	FailedClient, MaybeConnectionError,
 };
 
 use async_trait::async_trait;
-use std::{fmt::Debug, future::Future, net::SocketAddr, time::Duration};
-use substrate_prometheus_endpoint::{init_prometheus, Registry};
+use std::{
+	fmt::Debug,
+	future::Future,
+	net::SocketAddr,
+	time::{Duration, Instant},
+};
+use substrate_prometheus_endpoint::Registry;
 
 /// Default pause between reconnect attempts.
 pub const RECONNECT_DELAY: Duration = Duration::from_secs(10);
@@ -120,7 +127,16 @@ impl<SC, TC, LM> Loop<SC, TC, LM> {
 		LM: 'static + Send + Clone,
 	{
 		let run_loop_task = async move {
+			// circuit breakers are living as long as the loop lives
+			let retry_policy = RetryPolicy::reconnect(self.reconnect_delay);
+			let mut source_circuit_breaker =
+				CircuitBreaker::new(format!("{loop_name} source client"), retry_policy.clone());
+			let mut target_circuit_breaker =
+				CircuitBreaker::new(format!("{loop_name} target client"), retry_policy);
+
 			crate::initialize::initialize_loop(loop_name);
+			health::report_healthy(source_circuit_breaker.endpoint(), "connected");
+			health::report_healthy(target_circuit_breaker.endpoint(), "connected");
 
 			loop {
 				let loop_metric = self.loop_metric.clone();
@@ -131,11 +147,11 @@ impl<SC, TC, LM> Loop<SC, TC, LM> {
 				match result {
 					Ok(()) => break,
 					Err(failed_client) =>
-						reconnect_failed_client(
+						reconnect_failed_client_with_breakers(
 							failed_client,
 							self.reconnect_delay,
-							&mut self.source_client,
-							&mut self.target_client,
+							(&mut self.source_client, &mut source_circuit_breaker),
+							(&mut self.target_client, &mut target_circuit_breaker),
 						)
 						.await,
 				}
@@ -175,7 +191,8 @@ impl<SC, TC, LM> LoopMetrics<SC, TC, LM> {
 
 	/// Expose metrics using address passed at creation.
 	///
-	/// If passed `address` is `None`, metrics are not exposed.
+	/// Health and readiness of the relay (see `health` module) are exposed at the same address.
+	/// If passed `address` is `None`, nothing is exposed.
 	pub async fn expose(self) -> Result<Loop<SC, TC, LM>, Error> {
 		if let Some(address) = self.address {
 			let socket_addr = SocketAddr::new(
@@ -207,7 +224,7 @@ impl<SC, TC, LM> LoopMetrics<SC, TC, LM> {
 						"Starting prometheus endpoint at: {:?}",
 						socket_addr,
 					);
-					let result = init_prometheus(socket_addr, registry).await;
+					let result = health::serve(socket_addr, registry).await;
 					log::trace!(
 						target: "bridge-metrics",
 						"Prometheus endpoint has exited with result: {:?}",
@@ -227,43 +244,74 @@ impl<SC, TC, LM> LoopMetrics<SC, TC, LM> {
 }
 
 /// Deal with the client who has returned connection error.
+///
+/// Every call creates new circuit breakers for both clients, so if you need breakers to survive
+/// multiple reconnects, use `reconnect_failed_client_with_breakers`.
 pub async fn reconnect_failed_client(
 	failed_client: FailedClient,
 	reconnect_delay: Duration,
 	source_client: &mut impl Client,
 	target_client: &mut impl Client,
 ) {
+	let retry_policy = RetryPolicy::reconnect(reconnect_delay);
+	reconnect_failed_client_with_breakers(
+		failed_client,
+		reconnect_delay,
+		(source_client, &mut CircuitBreaker::new("source client", retry_policy.clone())),
+		(target_client, &mut CircuitBreaker::new("target client", retry_policy)),
+	)
+	.await
+}
+
+/// Deal with the client who has returned connection error, using given circuit breakers.
+pub async fn reconnect_failed_client_with_breakers(
+	failed_client: FailedClient,
+	reconnect_delay: Duration,
+	source: (&mut impl Client, &mut CircuitBreaker),
+	target: (&mut impl Client, &mut CircuitBreaker),
+) {
+	async_std::task::sleep(reconnect_delay).await;
+	if failed_client == FailedClient::Both || failed_client == FailedClient::Source {
+		reconnect_client("source", source.0, source.1).await;
+	}
+	if failed_client == FailedClient::Both || failed_client == FailedClient::Target {
+		reconnect_client("target", target.0, target.1).await;
+	}
+}
+
+/// Reconnect to the client, respecting its circuit breaker.
+async fn reconnect_client(
+	client_name: &str,
+	client: &mut impl Client,
+	circuit_breaker: &mut CircuitBreaker,
+) {
+	health::report_unhealthy(circuit_breaker.endpoint(), "connection lost, reconnecting");
 	loop {
-		async_std::task::sleep(reconnect_delay).await;
-		if failed_client == FailedClient::Both || failed_client == FailedClient::Source {
-			match source_client.reconnect().await {
-				Ok(()) => (),
-				Err(error) => {
-					log::warn!(
-						target: "bridge",
-						"Failed to reconnect to source client. Going to retry in {}s: {:?}",
-						reconnect_delay.as_secs(),
-						error,
-					);
-					continue
-				},
-			}
-		}
-		if failed_client == FailedClient::Both || failed_client == FailedClient::Target {
-			match target_client.reconnect().await {
-				Ok(()) => (),
-				Err(error) => {
-					log::warn!(
-						target: "bridge",
-						"Failed to reconnect to target client. Going to retry in {}s: {:?}",
-						reconnect_delay.as_secs(),
-						error,
-					);
-					continue
-				},
-			}
+		if let Some(remaining_open_period) = circuit_breaker.remaining_open_period(Instant::now()) {
+			async_std::task::sleep(remaining_open_period).await;
 		}
 
-		break
+		match client.reconnect().await {
+			Ok(()) => {
+				circuit_breaker.on_success();
+				health::report_healthy(circuit_breaker.endpoint(), "connected");
+				return
+			},
+			Err(error) => {
+				let retry_delay = circuit_breaker.on_failure(Instant::now());
+				health::report_unhealthy(
+					circuit_breaker.endpoint(),
+					format!("failed to reconnect: {error:?}"),
+				);
+				log::warn!(
+					target: "bridge",
+					"Failed to reconnect to {} client. Going to retry in {}s: {:?}",
+					client_name,
+					retry_delay.as_secs_f64(),
+					error,
+				);
+				async_std::task::sleep(retry_delay).await;
+			},
+		}
 	}
 }
@@ -0,0 +1,317 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Retry subsystem, shared by all relay loops and clients.
+//!
+//! It consists of:
+//!
+//! - error classification (`ErrorClass`): connection errors require reconnect, retryable errors are
+//!   retried with backoff and fatal errors require restarting the loop;
+//!
+//! - retry policy (`RetryPolicy`): exponential backoff with jitter;
+//!
+//! - per-endpoint circuit breaker (`CircuitBreaker`): after several consecutive failures, the
+//!   endpoint is not bothered for some time.
+
+use crate::{MaybeConnectionError, MAX_BACKOFF_INTERVAL};
+
+use backoff::{backoff::Backoff, ExponentialBackoff};
+use std::time::{Duration, Instant};
+
+/// Default initial interval of the retry backoff.
+pub const DEFAULT_INITIAL_INTERVAL: Duration = Duration::from_millis(500);
+/// Default randomization factor (jitter) of the retry backoff. The actual interval is selected
+/// from the `[interval * (1 - factor), interval * (1 + factor)]` range.
+pub const DEFAULT_RANDOMIZATION_FACTOR: f64 = 0.5;
+/// Default number of consecutive failures after which the circuit breaker is opened.
+pub const DEFAULT_CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
+/// Default period during which opened circuit breaker rejects all requests.
+///
+/// It matches the maximal backoff interval, so the opened breaker doesn't delay requests much
+/// longer than the backoff alone would.
+pub const DEFAULT_CIRCUIT_BREAKER_OPEN_PERIOD: Duration = MAX_BACKOFF_INTERVAL;
+
+/// Class of the error, returned by the relay client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+	/// Connection-related error. The client needs to reconnect before retrying.
+	Connection,
+	/// Connection-unrelated error that may disappear if the same request is retried later.
+	Retryable,
+	/// Fatal error. Retrying the same request won't help, so the loop needs to be restarted.
+	Fatal,
+}
+
+impl ErrorClass {
+	/// Classify given error.
+	pub fn of(error: &impl MaybeConnectionError) -> Self {
+		if error.is_connection_error() {
+			ErrorClass::Connection
+		} else if error.is_fatal_error() {
+			ErrorClass::Fatal
+		} else {
+			ErrorClass::Retryable
+		}
+	}
+}
+
+/// Retry policy parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+	/// Delay before first retry.
+	pub initial_interval: Duration,
+	/// Maximal delay between retries.
+	pub max_interval: Duration,
+	/// Randomization factor (jitter) of retry intervals.
+	pub randomization_factor: f64,
+	/// Number of consecutive failures after which the circuit breaker is opened.
+	pub circuit_breaker_threshold: u32,
+	/// Period during which opened circuit breaker rejects all requests.
+	pub circuit_breaker_open_period: Duration,
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		RetryPolicy {
+			initial_interval: DEFAULT_INITIAL_INTERVAL,
+			max_interval: MAX_BACKOFF_INTERVAL,
+			randomization_factor: DEFAULT_RANDOMIZATION_FACTOR,
+			circuit_breaker_threshold: DEFAULT_CIRCUIT_BREAKER_THRESHOLD,
+			circuit_breaker_open_period: DEFAULT_CIRCUIT_BREAKER_OPEN_PERIOD,
+		}
+	}
+}
+
+impl RetryPolicy {
+	/// Policy that is used to reconnect to the failed client. First retry happens after
+	/// `reconnect_delay`.
+	pub fn reconnect(reconnect_delay: Duration) -> Self {
+		RetryPolicy {
+			initial_interval: reconnect_delay,
+			max_interval: std::cmp::max(reconnect_delay, MAX_BACKOFF_INTERVAL),
+			..Default::default()
+		}
+	}
+
+	/// Returns exponential backoff (with jitter) that never stops retrying.
+	pub fn backoff(&self) -> ExponentialBackoff {
+		ExponentialBackoff {
+			current_interval: self.initial_interval,
+			initial_interval: self.initial_interval,
+			randomization_factor: self.randomization_factor,
+			max_interval: self.max_interval,
+			// we do not want relayer to stop
+			max_elapsed_time: None,
+			..Default::default()
+		}
+	}
+}
+
+/// State of the circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+	/// Requests are allowed.
+	Closed,
+	/// Requests are rejected until given moment.
+	Open(Instant),
+	/// Open period has ended. Single trial request is allowed. If it fails, the breaker is
+	/// opened again.
+	HalfOpen,
+}
+
+/// Circuit breaker of the single endpoint.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+	/// Endpoint name, used in logs.
+	endpoint: String,
+	/// Retry policy.
+	policy: RetryPolicy,
+	/// Backoff that is used while the breaker is closed.
+	backoff: ExponentialBackoff,
+	/// Number of consecutive failures.
+	consecutive_failures: u32,
+	/// Current breaker state.
+	state: CircuitState,
+}
+
+impl CircuitBreaker {
+	/// Create new circuit breaker for given endpoint.
+	pub fn new(endpoint: impl Into<String>, policy: RetryPolicy) -> Self {
+		CircuitBreaker {
+			endpoint: endpoint.into(),
+			backoff: policy.backoff(),
+			policy,
+			consecutive_failures: 0,
+			state: CircuitState::Closed,
+		}
+	}
+
+	/// Returns name of the endpoint.
+	pub fn endpoint(&self) -> &str {
+		&self.endpoint
+	}
+
+	/// Returns current state of the breaker.
+	pub fn state(&self, now: Instant) -> CircuitState {
+		match self.state {
+			CircuitState::Open(until) if until <= now => CircuitState::HalfOpen,
+			state => state,
+		}
+	}
+
+	/// Returns delay that needs to pass before next request is allowed.
+	pub fn remaining_open_period(&self, now: Instant) -> Option<Duration> {
+		match self.state(now) {
+			CircuitState::Open(until) => Some(until.saturating_duration_since(now)),
+			CircuitState::Closed | CircuitState::HalfOpen => None,
+		}
+	}
+
+	/// Note successful request.
+	pub fn on_success(&mut self) {
+		if self.state != CircuitState::Closed {
+			log::info!(target: "bridge", "Circuit breaker of {} is closed", self.endpoint);
+		}
+
+		self.backoff.reset();
+		self.consecutive_failures = 0;
+		self.state = CircuitState::Closed;
+	}
+
+	/// Note failed request. Returns delay before next request.
+	pub fn on_failure(&mut self, now: Instant) -> Duration {
+		self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+
+		let is_trial_failed = self.state(now) == CircuitState::HalfOpen;
+		let is_threshold_reached =
+			self.consecutive_failures >= self.policy.circuit_breaker_threshold;
+		if is_trial_failed || is_threshold_reached {
+			let open_period = self.policy.circuit_breaker_open_period;
+			log::warn!(
+				target: "bridge",
+				"Circuit breaker of {} is opened for {}s after {} consecutive failures",
+				self.endpoint,
+				open_period.as_secs(),
+				self.consecutive_failures,
+			);
+
+			self.state = CircuitState::Open(now + open_period);
+			return open_period
+		}
+
+		self.backoff.next_backoff().unwrap_or(self.policy.max_interval)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::StringifiedMaybeConnectionError;
+
+	fn test_policy() -> RetryPolicy {
+		RetryPolicy {
+			initial_interval: Duration::from_secs(1),
+			max_interval: Duration::from_secs(4),
+			randomization_factor: 0.0,
+			circuit_breaker_threshold: 3,
+			circuit_breaker_open_period: Duration::from_secs(60),
+		}
+	}
+
+	fn assert_delay(actual: Duration, expected: Duration) {
+		// backoff may add a nanosecond even if randomization is disabled
+		assert!(
+			actual >= expected && actual <= expected + Duration::from_millis(1),
+			"{actual:?} != {expected:?}",
+		);
+	}
+
+	#[test]
+	fn errors_are_classified() {
+		assert_eq!(
+			ErrorClass::of(&StringifiedMaybeConnectionError::new(true, "".into())),
+			ErrorClass::Connection,
+		);
+		assert_eq!(
+			ErrorClass::of(&StringifiedMaybeConnectionError::new(false, "".into())),
+			ErrorClass::Retryable,
+		);
+	}
+
+	#[test]
+	fn backoff_is_limited_by_max_interval() {
+		let mut backoff = test_policy().backoff();
+		assert_delay(backoff.next_backoff().unwrap(), Duration::from_secs(1));
+		for _ in 0..10 {
+			assert!(
+				backoff.next_backoff().unwrap() <=
+					Duration::from_secs(4) + Duration::from_millis(1)
+			);
+		}
+	}
+
+	#[test]
+	fn circuit_breaker_opens_after_consecutive_failures() {
+		let now = Instant::now();
+		let mut breaker = CircuitBreaker::new("test", test_policy());
+
+		// first failures are retried with backoff
+		assert_delay(breaker.on_failure(now), Duration::from_secs(1));
+		assert_eq!(breaker.state(now), CircuitState::Closed);
+		assert_delay(breaker.on_failure(now), Duration::from_millis(1500));
+		assert_eq!(breaker.state(now), CircuitState::Closed);
+
+		// then the breaker is opened
+		assert_eq!(breaker.on_failure(now), Duration::from_secs(60));
+		assert_eq!(breaker.state(now), CircuitState::Open(now + Duration::from_secs(60)));
+		assert_eq!(
+			breaker.remaining_open_period(now + Duration::from_secs(20)),
+			Some(Duration::from_secs(40))
+		);
+
+		// after open period ends, the breaker is half-opened
+		let later = now + Duration::from_secs(60);
+		assert_eq!(breaker.state(later), CircuitState::HalfOpen);
+		assert_eq!(breaker.remaining_open_period(later), None);
+	}
+
+	#[test]
+	fn circuit_breaker_is_reopened_if_trial_request_fails() {
+		let now = Instant::now();
+		let mut breaker = CircuitBreaker::new("test", test_policy());
+		for _ in 0..3 {
+			breaker.on_failure(now);
+		}
+
+		let later = now + Duration::from_secs(60);
+		assert_eq!(breaker.state(later), CircuitState::HalfOpen);
+		assert_eq!(breaker.on_failure(later), Duration::from_secs(60));
+		assert_eq!(breaker.state(later), CircuitState::Open(later + Duration::from_secs(60)));
+	}
+
+	#[test]
+	fn circuit_breaker_is_closed_after_successful_request() {
+		let now = Instant::now();
+		let mut breaker = CircuitBreaker::new("test", test_policy());
+		for _ in 0..3 {
+			breaker.on_failure(now);
+		}
+
+		breaker.on_success();
+		assert_eq!(breaker.state(now), CircuitState::Closed);
+		assert_delay(breaker.on_failure(now), Duration::from_secs(1));
+	}
+}
@@ -17,6 +17,7 @@ SERVICES=(\
 	deployments_relay-messages-rialto-to-millau-generator_1 \
 	deployments_relay-messages-millau-to-rialto-lane-00000001_1 \
 	deployments_relay-messages-rialto-to-millau-lane-00000001_1 \
+	deployments_relay-headers-millau-to-rialto-beefy_1 \
 	deployments_relay-millau-rialto_1 \
 	deployments_relay-headers-westend-to-millau-1_1 \
 	deployments_relay-headers-westend-to-millau-2_1 \
@@ -24,6 +25,8 @@ SERVICES=(\
 	deployments_relay-parachains-westend-to-millau-1_2 \
 	deployments_relay-messages-millau-to-rialto-parachain-generator_1 \
 	deployments_relay-messages-rialto-parachain-to-millau-generator_1 \
+	deployments_relay-messages-millau-to-rialto-parachain_1 \
+	deployments_relay-messages-rialto-parachain-to-millau_1 \
 	deployments_relay-millau-rialto-parachain-1_1 \
 	deployments_relay-millau-rialto-parachain-2_1 \
 	deployments_rialto-node-alice_1 \
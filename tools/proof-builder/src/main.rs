@@ -0,0 +1,337 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use bp_messages::{storage_keys, LaneId, MessageNonce};
+use bp_polkadot_core::{
+	parachains::{ParaHeadsProof, ParaId},
+	Block, Hash, Header,
+};
+use bp_runtime::{RawStorageProof, StorageProofChecker};
+use bridge_runtime_common::messages::{
+	source::FromBridgedChainMessagesDeliveryProof, target::FromBridgedChainMessagesProof,
+};
+use codec::{Decode, Encode};
+use color_eyre::eyre;
+use frame_remote_externalities::{Builder, Mode, OnlineConfig, RemoteExternalities, Transport};
+use pallet_bridge_messages::{EXPECTED_DEFAULT_MESSAGE_LENGTH, EXTRA_STORAGE_PROOF_SIZE};
+use sp_core::storage::StorageKey;
+use sp_runtime::traits::{BlakeTwo256, Header as HeaderT};
+use sp_state_machine::{prove_read, Backend};
+use std::path::PathBuf;
+use structopt::StructOpt;
+use substrate_rpc_client::{ws_client, ChainApi, WsClient};
+
+/// Command for building bridge proofs from the live chain state.
+#[derive(Debug, StructOpt)]
+enum Command {
+	/// Build proof of messages, sent over given lane.
+	MessagesProof {
+		#[structopt(flatten)]
+		state: StateParams,
+		/// Hex-encoded identifier of the lane.
+		#[structopt(long, parse(try_from_str = parse_lane_id))]
+		lane: LaneId,
+		/// Nonce of the first message to prove.
+		#[structopt(long)]
+		begin: MessageNonce,
+		/// Nonce of the last message to prove.
+		#[structopt(long)]
+		end: MessageNonce,
+	},
+	/// Build proof of messages delivery over given lane.
+	MessagesDeliveryProof {
+		#[structopt(flatten)]
+		state: StateParams,
+		/// Hex-encoded identifier of the lane.
+		#[structopt(long, parse(try_from_str = parse_lane_id))]
+		lane: LaneId,
+	},
+	/// Build proof of the parachain head.
+	ParachainHeadsProof {
+		#[structopt(flatten)]
+		state: StateParams,
+		/// Identifier of the parachain.
+		#[structopt(long)]
+		para_id: u32,
+	},
+	/// Check messages proof (e.g. rejected by the bridged chain) against the chain state.
+	CheckMessagesProof {
+		/// Node RPC endpoint.
+		#[structopt(long)]
+		uri: String,
+		/// Name of the messages pallet at the chain.
+		#[structopt(long)]
+		pallet: String,
+		/// File with hex-encoded `FromBridgedChainMessagesProof`.
+		#[structopt(long)]
+		proof_file: PathBuf,
+	},
+}
+
+/// Parameters of the chain state, used to build proofs.
+#[derive(Debug, StructOpt)]
+struct StateParams {
+	/// Node RPC endpoint.
+	#[structopt(long)]
+	uri: String,
+	/// Hash of the block to read state at. Best finalized block is used if not specified.
+	#[structopt(long, parse(try_from_str = parse_hash))]
+	at: Option<Hash>,
+	/// Name of the pallet, which storage is used to build the proof.
+	#[structopt(long)]
+	pallet: String,
+	/// Download the whole chain state instead of the pallet storage.
+	#[structopt(long)]
+	all_pallets: bool,
+}
+
+/// Chain state, downloaded from the node.
+struct DownloadedState {
+	/// Hash of the block.
+	at: Hash,
+	/// State root of the block.
+	header_state_root: Hash,
+	/// Downloaded state.
+	ext: RemoteExternalities<Block>,
+}
+
+impl DownloadedState {
+	/// Download the chain state.
+	async fn download(params: &StateParams) -> color_eyre::Result<Self> {
+		let client = connect(&params.uri).await?;
+		let at = match params.at {
+			Some(at) => at,
+			None => ChainApi::<(), Hash, Header, ()>::finalized_head(&client).await?,
+		};
+		let header = read_header(&client, at).await?;
+
+		let (pallets, hashed_prefixes) = if params.all_pallets {
+			(vec![], vec![vec![]])
+		} else {
+			(vec![params.pallet.clone()], vec![])
+		};
+		let ext = Builder::<Block>::new()
+			.mode(Mode::Online(OnlineConfig {
+				transport: Transport::Uri(params.uri.clone()),
+				at: Some(at),
+				pallets,
+				hashed_prefixes,
+				..Default::default()
+			}))
+			.build()
+			.await
+			.map_err(|e| eyre::eyre!("Failed to download state at {at}: {e}"))?;
+
+		Ok(DownloadedState { at, header_state_root: *header.state_root(), ext })
+	}
+
+	/// Read raw storage value.
+	fn storage(&self, key: &StorageKey) -> color_eyre::Result<Option<Vec<u8>>> {
+		self.ext
+			.as_backend()
+			.storage(&key.0)
+			.map_err(|e| eyre::eyre!("Failed to read storage value: {e}"))
+	}
+
+	/// Prove given keys. Returns root of the downloaded state and the proof.
+	fn prove(&self, keys: &[StorageKey]) -> color_eyre::Result<(Hash, RawStorageProof)> {
+		let backend = self.ext.as_backend();
+		let root = *backend.root();
+		let proof = prove_read(backend, keys.iter().map(|key| key.0.as_slice()))
+			.map_err(|e| eyre::eyre!("Failed to build proof: {e}"))?;
+		Ok((root, proof.into_nodes().into_iter().collect()))
+	}
+
+	/// Print information on the proof.
+	fn print_proof_info(&self, proof_root: Hash, proof: &RawStorageProof) {
+		println!("Block: {:?}", self.at);
+		println!("Block state root: {:?}", self.header_state_root);
+		println!("Proof state root: {proof_root:?}");
+		if proof_root != self.header_state_root {
+			println!(
+				"WARNING: partial state has been downloaded, so the proof is only valid against \
+				the proof state root. Use `--all-pallets` to download the whole state"
+			);
+		}
+		println!("Proof nodes: {}", proof.len());
+		println!("Proof size: {} bytes", proof_size(proof));
+	}
+}
+
+#[tokio::main]
+async fn main() -> color_eyre::Result<()> {
+	color_eyre::install()?;
+
+	match Command::from_args() {
+		Command::MessagesProof { state, lane, begin, end } => {
+			let downloaded = DownloadedState::download(&state).await?;
+			let mut keys = (begin..=end)
+				.map(|nonce| storage_keys::message_key(&state.pallet, &lane, nonce))
+				.collect::<Vec<_>>();
+			let mut messages_size = 0;
+			for key in &keys {
+				let message = downloaded
+					.storage(key)?
+					.ok_or_else(|| eyre::eyre!("Message {key:?} is missing from the storage"))?;
+				messages_size += message.len();
+			}
+			keys.push(storage_keys::outbound_lane_data_key(&state.pallet, &lane));
+
+			let (proof_root, storage_proof) = downloaded.prove(&keys)?;
+			downloaded.print_proof_info(proof_root, &storage_proof);
+			let messages_count = end.saturating_sub(begin).saturating_add(1);
+			println!("Messages size: {messages_size} bytes");
+			println!(
+				"Messages size, assumed by weight formulas: {} bytes",
+				messages_count.saturating_mul(EXPECTED_DEFAULT_MESSAGE_LENGTH as _),
+			);
+			println!(
+				"Extra proof size: {} bytes",
+				proof_size(&storage_proof).saturating_sub(messages_size)
+			);
+			println!(
+				"Extra proof size, assumed by weight formulas: {EXTRA_STORAGE_PROOF_SIZE} bytes"
+			);
+			print_encoded(&FromBridgedChainMessagesProof {
+				bridged_header_hash: downloaded.at,
+				storage_proof,
+				lane,
+				nonces_start: begin,
+				nonces_end: end,
+			});
+		},
+		Command::MessagesDeliveryProof { state, lane } => {
+			let downloaded = DownloadedState::download(&state).await?;
+			let key = storage_keys::inbound_lane_data_key(&state.pallet, &lane);
+			let lane_data_size = downloaded
+				.storage(&key)?
+				.ok_or_else(|| eyre::eyre!("Inbound lane {lane:?} is missing from the storage"))?
+				.len();
+
+			let (proof_root, storage_proof) = downloaded.prove(&[key])?;
+			downloaded.print_proof_info(proof_root, &storage_proof);
+			println!(
+				"Extra proof size: {} bytes",
+				proof_size(&storage_proof).saturating_sub(lane_data_size)
+			);
+			println!(
+				"Extra proof size, assumed by weight formulas: {EXTRA_STORAGE_PROOF_SIZE} bytes"
+			);
+			print_encoded(&FromBridgedChainMessagesDeliveryProof {
+				bridged_header_hash: downloaded.at,
+				storage_proof,
+				lane,
+			});
+		},
+		Command::ParachainHeadsProof { state, para_id } => {
+			let downloaded = DownloadedState::download(&state).await?;
+			let key =
+				bp_parachains::parachain_head_storage_key_at_source(&state.pallet, ParaId(para_id));
+			let head_size = downloaded
+				.storage(&key)?
+				.ok_or_else(|| eyre::eyre!("Head of parachain {para_id} is missing"))?
+				.len();
+
+			let (proof_root, storage_proof) = downloaded.prove(&[key])?;
+			downloaded.print_proof_info(proof_root, &storage_proof);
+			println!("Parachain head size: {head_size} bytes");
+			print_encoded(&ParaHeadsProof(storage_proof));
+		},
+		Command::CheckMessagesProof { uri, pallet, proof_file } => {
+			let encoded_proof = std::fs::read_to_string(proof_file)?;
+			let proof = FromBridgedChainMessagesProof::<Hash>::decode(
+				&mut &hex::decode(encoded_proof.trim().trim_start_matches("0x"))?[..],
+			)?;
+			check_messages_proof(&uri, &pallet, proof).await?;
+		},
+	}
+
+	Ok(())
+}
+
+/// Check messages proof against the chain state and report the first problem.
+async fn check_messages_proof(
+	uri: &str,
+	pallet: &str,
+	proof: FromBridgedChainMessagesProof<Hash>,
+) -> color_eyre::Result<()> {
+	let client = connect(uri).await?;
+	let header = read_header(&client, proof.bridged_header_hash).await?;
+	println!("Block: {:?}", proof.bridged_header_hash);
+	println!("Block state root: {:?}", header.state_root());
+	println!("Proof nodes: {}", proof.storage_proof.len());
+	println!("Proof size: {} bytes", proof_size(&proof.storage_proof));
+
+	let mut checker =
+		StorageProofChecker::<BlakeTwo256>::new(*header.state_root(), proof.storage_proof)
+			.map_err(|e| eyre::eyre!("Proof is rejected: {e:?}"))?;
+	for nonce in proof.nonces_start..=proof.nonces_end {
+		let key = storage_keys::message_key(pallet, &proof.lane, nonce);
+		match checker.read_value(&key.0) {
+			Ok(Some(message)) => println!("Message {nonce}: {} bytes", message.len()),
+			Ok(None) => return Err(eyre::eyre!("Message {nonce} is missing from the proof")),
+			Err(e) => return Err(eyre::eyre!("Failed to read message {nonce}: {e:?}")),
+		}
+	}
+	let key = storage_keys::outbound_lane_data_key(pallet, &proof.lane);
+	match checker.read_value(&key.0) {
+		Ok(Some(_)) => println!("Outbound lane state is proved"),
+		Ok(None) => println!("Outbound lane state is not proved"),
+		Err(e) => return Err(eyre::eyre!("Failed to read outbound lane state: {e:?}")),
+	}
+	checker
+		.ensure_no_unused_nodes()
+		.map_err(|e| eyre::eyre!("Proof is rejected: {e:?}"))?;
+
+	println!("Proof is valid");
+	Ok(())
+}
+
+/// Connect to the node.
+async fn connect(uri: &str) -> color_eyre::Result<WsClient> {
+	ws_client(uri).await.map_err(|e| eyre::eyre!("Failed to connect to {uri}: {e}"))
+}
+
+/// Read header from the node.
+async fn read_header(client: &WsClient, at: Hash) -> color_eyre::Result<Header> {
+	ChainApi::<(), Hash, Header, ()>::header(client, Some(at))
+		.await?
+		.ok_or_else(|| eyre::eyre!("Header {at:?} is unknown to the node"))
+}
+
+/// Returns size of the storage proof.
+fn proof_size(proof: &RawStorageProof) -> usize {
+	proof.iter().map(|node| node.len()).sum()
+}
+
+/// Print hex-encoded value.
+fn print_encoded(value: &impl Encode) {
+	println!("Encoded proof: 0x{}", hex::encode(value.encode()));
+}
+
+fn parse_lane_id(s: &str) -> Result<LaneId, String> {
+	let bytes = hex::decode(s.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+	let bytes: [u8; 4] = bytes.try_into().map_err(|_| "Lane id must be 4 bytes long")?;
+	Ok(LaneId(bytes))
+}
+
+fn parse_hash(s: &str) -> Result<Hash, String> {
+	let bytes = hex::decode(s.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+	if bytes.len() != Hash::len_bytes() {
+		return Err(format!("Hash must be {} bytes long", Hash::len_bytes()))
+	}
+	Ok(Hash::from_slice(&bytes))
+}